@@ -0,0 +1,68 @@
+#!/bin/bash
+# Script to show ICP account IDs and derived subaccounts of a principal
+#
+# Usage:
+#   bash scripts/account_id.sh [principal] [--subaccount <hex>] [--memo <n>] [--swap]
+#
+# Arguments (all optional - interactive prompts if not provided):
+#   principal          - Principal to derive from (prompts with participant selection if not provided)
+#   --subaccount <hex> - Show the account ID of this 32-byte subaccount instead of the default one
+#   --memo <n>         - Also show the NNS governance staking subaccount and account ID for this memo
+#   --swap             - Also show the swap participation subaccount (and account ID if an SNS is deployed)
+#
+# Everything is computed locally; no replica is needed.
+#
+# Example:
+#   bash scripts/account_id.sh 2laou-ygqmf-... --memo 1 --swap
+
+set -euo pipefail
+
+# Colors for output
+RED='\033[0;31m'
+GREEN='\033[0;32m'
+YELLOW='\033[1;33m'
+BLUE='\033[0;34m'
+NC='\033[0m' # No Color
+
+# Helper functions
+print_info() {
+    echo -e "${BLUE}ℹ${NC} $1"
+}
+
+print_success() {
+    echo -e "${GREEN}✓${NC} $1"
+}
+
+print_warning() {
+    echo -e "${YELLOW}⚠${NC} $1"
+}
+
+print_error() {
+    echo -e "${RED}✗${NC} $1"
+}
+
+print_header() {
+    echo ""
+    echo -e "${BLUE}━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━${NC}"
+    echo -e "${BLUE}  $1${NC}"
+    echo -e "${BLUE}━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━${NC}"
+}
+
+# Get script directory (should be in local_sns/scripts/)
+SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
+LOCAL_SNS_ROOT="$(cd "$SCRIPT_DIR/.." && pwd)"
+
+# Change to local_sns root directory
+cd "$LOCAL_SNS_ROOT"
+
+print_header "Account ID"
+
+# Build command arguments - pass through whatever was provided
+CMD_ARGS=("account-id")
+# Pass all provided arguments through - Rust code will handle interactive prompts for missing ones
+for arg in "$@"; do
+    CMD_ARGS+=("$arg")
+done
+
+cargo run --bin local_sns -- "${CMD_ARGS[@]}"
+
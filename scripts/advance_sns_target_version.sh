@@ -0,0 +1,82 @@
+#!/bin/bash
+# Script to advance the SNS target version to the latest version on SNS-W
+#
+# Usage:
+#   bash scripts/advance_sns_target_version.sh [--proposer <principal>]
+#
+# Options:
+#   --proposer    - Optional. owner, participant-<n>, an identity name or a principal
+#                   (shows principal selection menu if not provided)
+#
+# Creates an AdvanceSnsTargetVersion proposal, has every participant vote for it,
+# then prints the upgrade journal while governance upgrades the SNS canisters one
+# step at a time until it runs the target version.
+#
+# Example:
+#   bash scripts/advance_sns_target_version.sh --proposer owner
+
+set -euo pipefail
+
+# Colors for output
+RED='\033[0;31m'
+GREEN='\033[0;32m'
+YELLOW='\033[1;33m'
+BLUE='\033[0;34m'
+NC='\033[0m' # No Color
+
+# Helper functions
+print_info() {
+    echo -e "${BLUE}ℹ${NC} $1"
+}
+
+print_success() {
+    echo -e "${GREEN}✓${NC} $1"
+}
+
+print_warning() {
+    echo -e "${YELLOW}⚠${NC} $1"
+}
+
+print_error() {
+    echo -e "${RED}✗${NC} $1"
+}
+
+print_header() {
+    echo ""
+    echo -e "${BLUE}━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━${NC}"
+    echo -e "${BLUE}  $1${NC}"
+    echo -e "${BLUE}━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━${NC}"
+}
+
+# Get script directory (should be in local_sns/scripts/)
+SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
+LOCAL_SNS_ROOT="$(cd "$SCRIPT_DIR/.." && pwd)"
+
+# Change to local_sns root directory
+cd "$LOCAL_SNS_ROOT"
+
+# Check if dfx is running
+if ! dfx ping >/dev/null 2>&1; then
+    print_error "dfx is not running. Start it with: dfx start --clean --system-canisters"
+    exit 1
+fi
+
+# Check if deployment data exists (needed to get swap canister ID)
+DEPLOYMENT_DATA="$(cargo run -q --bin local_sns -- data-path)"
+if [ ! -f "$DEPLOYMENT_DATA" ]; then
+    print_error "Deployment data file not found: $DEPLOYMENT_DATA"
+    print_info "Please deploy an SNS first (option 12 in menu, or run deploy_local_sns.sh)"
+    exit 1
+fi
+
+print_header "Advance SNS Target Version"
+
+# Build command arguments - pass through whatever was provided
+CMD_ARGS=("advance-sns-target-version")
+# Pass all provided arguments through - Rust code will handle interactive prompts for missing ones
+for arg in "$@"; do
+    CMD_ARGS+=("$arg")
+done
+
+cargo run --bin local_sns -- "${CMD_ARGS[@]}"
+
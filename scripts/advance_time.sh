@@ -0,0 +1,74 @@
+#!/bin/bash
+# Script to move the local replica's clock forward
+#
+# Usage:
+#   bash scripts/advance_time.sh [duration]
+#
+# Arguments (optional - prompts if not provided):
+#   duration  - Seconds, or with a unit: 30m, 2h, 7d, 1w
+#
+# Fast-forwards dissolve delays, voting deadlines, reward rounds and swap
+# deadlines. Needs the local network to run on PocketIC (the default since
+# dfx 0.24); set LOCAL_SNS_POCKET_IC_URL if the server isn't found.
+#
+# Example:
+#   bash scripts/advance_time.sh 7d
+#   bash scripts/advance_time.sh 3600
+
+set -euo pipefail
+
+# Colors for output
+RED='\033[0;31m'
+GREEN='\033[0;32m'
+YELLOW='\033[1;33m'
+BLUE='\033[0;34m'
+NC='\033[0m' # No Color
+
+# Helper functions
+print_info() {
+    echo -e "${BLUE}ℹ${NC} $1"
+}
+
+print_success() {
+    echo -e "${GREEN}✓${NC} $1"
+}
+
+print_warning() {
+    echo -e "${YELLOW}⚠${NC} $1"
+}
+
+print_error() {
+    echo -e "${RED}✗${NC} $1"
+}
+
+print_header() {
+    echo ""
+    echo -e "${BLUE}━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━${NC}"
+    echo -e "${BLUE}  $1${NC}"
+    echo -e "${BLUE}━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━${NC}"
+}
+
+# Get script directory (should be in local_sns/scripts/)
+SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
+LOCAL_SNS_ROOT="$(cd "$SCRIPT_DIR/.." && pwd)"
+
+# Change to local_sns root directory
+cd "$LOCAL_SNS_ROOT"
+
+# Check if dfx is running
+if ! dfx ping >/dev/null 2>&1; then
+    print_error "dfx is not running. Start it with: dfx start --clean --system-canisters"
+    exit 1
+fi
+
+print_header "Advance Time"
+
+# Build command arguments - pass through whatever was provided
+CMD_ARGS=("advance-time")
+# Pass all provided arguments through - Rust code will handle interactive prompts for missing ones
+for arg in "$@"; do
+    CMD_ARGS+=("$arg")
+done
+
+cargo run --bin local_sns -- "${CMD_ARGS[@]}"
+
@@ -0,0 +1,82 @@
+#!/bin/bash
+# Script to claim an SNS neuron staked by an external transfer
+#
+# Usage:
+#   bash scripts/claim_sns_neuron.sh [--memo N] [--controller PRINCIPAL]
+#
+# Arguments (all optional - interactive prompts if not provided):
+#   --memo N                - Memo the governance subaccount was derived from
+#   --controller PRINCIPAL  - Controller of the neuron. If not provided, shows participant selection menu
+#
+# Use this after a dapp or wallet has transferred SNS tokens to the governance
+# subaccount of (memo, controller) to check that the neuron can be claimed.
+#
+# Example:
+#   bash scripts/claim_sns_neuron.sh
+#   bash scripts/claim_sns_neuron.sh --memo 42 --controller 2laou-ygqmf-...
+
+set -euo pipefail
+
+# Colors for output
+RED='\033[0;31m'
+GREEN='\033[0;32m'
+YELLOW='\033[1;33m'
+BLUE='\033[0;34m'
+NC='\033[0m' # No Color
+
+# Helper functions
+print_info() {
+    echo -e "${BLUE}ℹ${NC} $1"
+}
+
+print_success() {
+    echo -e "${GREEN}✓${NC} $1"
+}
+
+print_warning() {
+    echo -e "${YELLOW}⚠${NC} $1"
+}
+
+print_error() {
+    echo -e "${RED}✗${NC} $1"
+}
+
+print_header() {
+    echo ""
+    echo -e "${BLUE}━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━${NC}"
+    echo -e "${BLUE}  $1${NC}"
+    echo -e "${BLUE}━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━${NC}"
+}
+
+# Get script directory (should be in local_sns/scripts/)
+SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
+LOCAL_SNS_ROOT="$(cd "$SCRIPT_DIR/.." && pwd)"
+
+# Change to local_sns root directory
+cd "$LOCAL_SNS_ROOT"
+
+# Check if dfx is running
+if ! dfx ping >/dev/null 2>&1; then
+    print_error "dfx is not running. Start it with: dfx start --clean --system-canisters"
+    exit 1
+fi
+
+# Check if deployment data exists
+DEPLOYMENT_DATA="$(cargo run -q --bin local_sns -- data-path)"
+if [ ! -f "$DEPLOYMENT_DATA" ]; then
+    print_error "Deployment data file not found: $DEPLOYMENT_DATA"
+    print_info "Please deploy an SNS first (option 9 in menu, or run deploy_local_sns.sh)"
+    exit 1
+fi
+
+print_header "Claim SNS Neuron"
+
+# Build command arguments - pass through whatever was provided
+CMD_ARGS=("claim-sns-neuron")
+# Pass all provided arguments through - Rust code will handle interactive prompts for missing ones
+for arg in "$@"; do
+    CMD_ARGS+=("$arg")
+done
+
+cargo run --bin local_sns -- "${CMD_ARGS[@]}"
+
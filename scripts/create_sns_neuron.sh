@@ -2,7 +2,7 @@
 # Script to create an SNS neuron by staking tokens from the SNS ledger
 #
 # Usage:
-#   bash scripts/create_sns_neuron.sh [principal] [amount_e8s] [memo] [dissolve_delay_seconds]
+#   bash scripts/create_sns_neuron.sh [principal] [amount_e8s] [memo] [dissolve_delay]
 #
 # Arguments (all optional - interactive prompts if not provided):
 #   principal              - Optional: Principal to create the neuron for
@@ -10,20 +10,20 @@
 #   amount_e8s             - Optional: Amount of tokens to stake in e8s
 #                           If not provided, stakes all available balance
 #   memo                   - Optional: Memo to use for neuron creation (auto-generated if not provided)
-#   dissolve_delay_seconds - Optional: Dissolve delay in seconds (default: 0, i.e., no delay)
+#   dissolve_delay         - Optional: Seconds, a duration (30d, 6 months, 1y) or min-to-vote/max (default: no delay)
 #
 # Interactive flow:
 #   1. Select participant/principal (if not provided)
 #   2. Enter amount (if not provided, uses all available balance)
 #   3. Enter memo (if not provided, auto-generated based on neuron count)
-#   4. Enter dissolve delay in seconds (if not provided, defaults to 0)
+#   4. Pick a dissolve delay preset or enter a duration (if not provided, defaults to none)
 #
 # Example:
 #   bash scripts/create_sns_neuron.sh
 #   bash scripts/create_sns_neuron.sh 2laou-ygqmf-...
 #   bash scripts/create_sns_neuron.sh 2laou-ygqmf-... 10000000000
 #   bash scripts/create_sns_neuron.sh 2laou-ygqmf-... 10000000000 1
-#   bash scripts/create_sns_neuron.sh 2laou-ygqmf-... 10000000000 1 2592000  # 30 days
+#   bash scripts/create_sns_neuron.sh 2laou-ygqmf-... 10000000000 1 30d
 
 set -euo pipefail
 
@@ -72,7 +72,7 @@ if ! dfx ping >/dev/null 2>&1; then
 fi
 
 # Check if deployment data exists
-DEPLOYMENT_DATA="$LOCAL_SNS_ROOT/generated/sns_deployment_data.json"
+DEPLOYMENT_DATA="$(cargo run -q --bin local_sns -- data-path)"
 if [ ! -f "$DEPLOYMENT_DATA" ]; then
     print_error "Deployment data file not found: $DEPLOYMENT_DATA"
     print_info "Please deploy an SNS first (option 9 in menu, or run deploy_local_sns.sh)"
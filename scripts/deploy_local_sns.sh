@@ -2,10 +2,23 @@
 # Script to deploy a local SNS using the local_sns Rust binary
 #
 # Usage:
-#   bash scripts/deploy_local_sns.sh
+#   bash scripts/deploy_local_sns.sh [--config path/to/sns.yaml] [--contributions e8s,e8s,...] [--fresh] [--abort-swap] [--bootstrap] [--backend pocket-ic]
+#
+# Arguments (all optional):
+#   --config <file>         - SNS config file (.yaml, .toml or .json) overriding
+#                             the defaults in src/init/sns_config.rs
+#   --contributions <list>  - ICP (e8s) each participant contributes to the swap
+#   --fresh                 - Ignore the checkpoint of an interrupted deployment
+#                             (by default a re-run resumes where it stopped)
+#   --abort-swap            - Stay below swap.minimum_participants and leave the swap
+#                             open, so it aborts at its due timestamp (see abort_swap.sh)
+#   --bootstrap             - Start the replica and install the NNS first if needed
+#                             (see init_nns.sh)
+#   --backend pocket-ic     - Deploy on a PocketIC instance started by the tool instead
+#                             of the dfx replica (also LOCAL_SNS_BACKEND=pocket-ic)
 #
 # Prerequisites:
-#   - dfx start --clean --system-canisters
+#   - dfx start --clean --system-canisters (or the pocket-ic server binary for --backend pocket-ic)
 #   - Rust toolchain installed
 #   - Sufficient ICP balance for owner (via minting account)
 
@@ -51,22 +64,41 @@ cd "$LOCAL_SNS_ROOT"
 
 print_header "Local SNS Deployment"
 
-# Check if dfx is running
-if ! dfx ping >/dev/null 2>&1; then
-    print_error "dfx is not running. Start it with: dfx start --clean --system-canisters"
-    exit 1
-fi
+# The PocketIC backend needs no dfx replica
+BACKEND="${LOCAL_SNS_BACKEND:-dfx}"
+BOOTSTRAP=false
+ARGS=("$@")
+for i in "${!ARGS[@]}"; do
+    if [ "${ARGS[$i]}" = "--backend" ] && [ $((i + 1)) -lt ${#ARGS[@]} ]; then
+        BACKEND="${ARGS[$((i + 1))]}"
+    fi
+    if [ "${ARGS[$i]}" = "--bootstrap" ]; then
+        BOOTSTRAP=true
+    fi
+done
+
+if [ "$BACKEND" = "pocket-ic" ]; then
+    print_info "Using the PocketIC backend (no dfx replica needed)"
+elif [ "$BOOTSTRAP" = true ]; then
+    print_info "Bootstrapping: the replica is started and the NNS installed if needed"
+else
+    # Check if dfx is running
+    if ! dfx ping >/dev/null 2>&1; then
+        print_error "dfx is not running. Start it with: dfx start --clean --system-canisters"
+        exit 1
+    fi
 
-print_success "dfx is running"
+    print_success "dfx is running"
+fi
 
 # Run the deployment
 print_header "Starting SNS Deployment"
 print_info "This will create an SNS on your local dfx network..."
 echo ""
 
-cargo run --bin local_sns -- deploy-sns
+cargo run --bin local_sns -- deploy-sns "$@"
 
-DEPLOYMENT_DATA="$LOCAL_SNS_ROOT/generated/sns_deployment_data.json"
+DEPLOYMENT_DATA="$(cargo run -q --bin local_sns -- data-path)"
 
 if [ -f "$DEPLOYMENT_DATA" ]; then
     print_header "Deployment Complete"
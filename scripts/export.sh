@@ -0,0 +1,78 @@
+#!/bin/bash
+# Script to export the SNS canister IDs for other projects, or neurons and balances as CSV
+#
+# Usage:
+#   bash scripts/export.sh [--env <file>] [--canister-ids <file>] [--canister-ids-network <name>]
+#   bash scripts/export.sh <neurons <sns|icp>|balances> --format csv [--output <file>]
+#
+# Arguments (all optional):
+#   --env <file>                   - .env file to write SNS_<ROLE>_CANISTER_ID variables to
+#   --canister-ids <file>          - dfx canister_ids.json to merge sns_<role> entries into
+#   --canister-ids-network <name>  - Network key in canister_ids.json (default $DFX_NETWORK or local)
+#   --format csv, --output <file>  - CSV of neurons or balances (stdout without --output)
+#
+# Without --env or --canister-ids, both .env and canister_ids.json are written.
+# Existing entries for other variables, canisters and networks are kept.
+#
+# Example:
+#   bash scripts/export.sh --env ../frontend/.env --canister-ids ../backend/canister_ids.json
+#   bash scripts/export.sh neurons sns --format csv --output sns_neurons.csv
+
+set -euo pipefail
+
+# Colors for output
+RED='\033[0;31m'
+GREEN='\033[0;32m'
+YELLOW='\033[1;33m'
+BLUE='\033[0;34m'
+NC='\033[0m' # No Color
+
+# Helper functions
+print_info() {
+    echo -e "${BLUE}ℹ${NC} $1"
+}
+
+print_success() {
+    echo -e "${GREEN}✓${NC} $1"
+}
+
+print_warning() {
+    echo -e "${YELLOW}⚠${NC} $1"
+}
+
+print_error() {
+    echo -e "${RED}✗${NC} $1"
+}
+
+print_header() {
+    echo ""
+    echo -e "${BLUE}━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━${NC}"
+    echo -e "${BLUE}  $1${NC}"
+    echo -e "${BLUE}━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━${NC}"
+}
+
+# Get script directory (should be in local_sns/scripts/)
+SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
+LOCAL_SNS_ROOT="$(cd "$SCRIPT_DIR/.." && pwd)"
+
+# Change to local_sns root directory
+cd "$LOCAL_SNS_ROOT"
+
+# Check if deployment data exists
+DEPLOYMENT_DATA="$(cargo run -q --bin local_sns -- data-path)"
+if [ ! -f "$DEPLOYMENT_DATA" ]; then
+    print_error "Deployment data not found at $DEPLOYMENT_DATA"
+    print_info "Please deploy an SNS first using: bash scripts/deploy_local_sns.sh"
+    exit 1
+fi
+
+print_header "Export Deployment Data"
+
+# Build command arguments - pass through whatever was provided
+CMD_ARGS=("export")
+for arg in "$@"; do
+    CMD_ARGS+=("$arg")
+done
+
+cargo run --bin local_sns -- "${CMD_ARGS[@]}"
+
@@ -0,0 +1,84 @@
+#!/bin/bash
+# Script to register and operate SNS extensions (treasury managers)
+#
+# Usage:
+#   bash scripts/extensions.sh [register|deposit|withdraw|list] [args...]
+#
+#   bash scripts/extensions.sh register <wasm_file> --store-canister <id> [--sns-amount <tokens>] [--icp-amount <tokens>]
+#   bash scripts/extensions.sh deposit <extension_canister> [--sns-amount <tokens>] [--icp-amount <tokens>]
+#   bash scripts/extensions.sh withdraw <extension_canister>
+#   bash scripts/extensions.sh list
+#
+# Amounts are tokens (1.5); --sns-amount-e8s <n> and --icp-amount-e8s <n> take e8s.
+# register, deposit and withdraw create a proposal every participant votes for
+# and accept --proposer <principal> and --wait. With no arguments, shows a menu.
+#
+# Example:
+#   bash scripts/extensions.sh register treasury_manager.wasm.gz --store-canister bkyz2-fmaaa-aaaaa-qaaaq-cai --sns-amount 1000.0 --icp-amount 10.0 --wait
+#   bash scripts/extensions.sh list
+
+set -euo pipefail
+
+# Colors for output
+RED='\033[0;31m'
+GREEN='\033[0;32m'
+YELLOW='\033[1;33m'
+BLUE='\033[0;34m'
+NC='\033[0m' # No Color
+
+# Helper functions
+print_info() {
+    echo -e "${BLUE}ℹ${NC} $1"
+}
+
+print_success() {
+    echo -e "${GREEN}✓${NC} $1"
+}
+
+print_warning() {
+    echo -e "${YELLOW}⚠${NC} $1"
+}
+
+print_error() {
+    echo -e "${RED}✗${NC} $1"
+}
+
+print_header() {
+    echo ""
+    echo -e "${BLUE}━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━${NC}"
+    echo -e "${BLUE}  $1${NC}"
+    echo -e "${BLUE}━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━${NC}"
+}
+
+# Get script directory (should be in local_sns/scripts/)
+SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
+LOCAL_SNS_ROOT="$(cd "$SCRIPT_DIR/.." && pwd)"
+
+# Change to local_sns root directory
+cd "$LOCAL_SNS_ROOT"
+
+# Check if dfx is running
+if ! dfx ping >/dev/null 2>&1; then
+    print_error "dfx is not running. Start it with: dfx start --clean --system-canisters"
+    exit 1
+fi
+
+# Check if deployment data exists (needed to get swap canister ID)
+DEPLOYMENT_DATA="$(cargo run -q --bin local_sns -- data-path)"
+if [ ! -f "$DEPLOYMENT_DATA" ]; then
+    print_error "Deployment data file not found: $DEPLOYMENT_DATA"
+    print_info "Please deploy an SNS first (option 12 in menu, or run deploy_local_sns.sh)"
+    exit 1
+fi
+
+print_header "SNS Extensions"
+
+# Build command arguments - pass through whatever was provided
+CMD_ARGS=("extensions")
+# Pass all provided arguments through - Rust code will handle interactive prompts for missing ones
+for arg in "$@"; do
+    CMD_ARGS+=("$arg")
+done
+
+cargo run --bin local_sns -- "${CMD_ARGS[@]}"
+
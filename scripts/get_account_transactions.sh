@@ -0,0 +1,86 @@
+#!/bin/bash
+# Script to list an SNS account's transactions from the SNS index canister
+#
+# Usage:
+#   bash scripts/get_account_transactions.sh [principal] [subaccount_hex] [--limit <n>]
+#
+# Arguments (all optional - interactive prompts if not provided):
+#   principal       - Optional: Principal whose transactions to list
+#                     If not provided, shows participant selection menu
+#   subaccount_hex  - Optional: Subaccount in hex format
+#                     If not provided, lists the default account (no subaccount)
+#   --limit <n>     - Optional: Number of transactions to show (default: 20)
+#
+# Interactive flow:
+#   1. Select participant or enter principal (if not provided)
+#
+# Example:
+#   bash scripts/get_account_transactions.sh
+#   bash scripts/get_account_transactions.sh 2laou-ygqmf-...
+#   bash scripts/get_account_transactions.sh 2laou-ygqmf-... 0xabcd1234... --limit 50
+
+set -euo pipefail
+
+# Colors for output
+RED='\033[0;31m'
+GREEN='\033[0;32m'
+YELLOW='\033[1;33m'
+BLUE='\033[0;34m'
+NC='\033[0m' # No Color
+
+# Helper functions
+print_info() {
+    echo -e "${BLUE}ℹ${NC} $1"
+}
+
+print_success() {
+    echo -e "${GREEN}✓${NC} $1"
+}
+
+print_warning() {
+    echo -e "${YELLOW}⚠${NC} $1"
+}
+
+print_error() {
+    echo -e "${RED}✗${NC} $1"
+}
+
+print_header() {
+    echo ""
+    echo -e "${BLUE}━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━${NC}"
+    echo -e "${BLUE}  $1${NC}"
+    echo -e "${BLUE}━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━${NC}"
+}
+
+# Get script directory (should be in local_sns/scripts/)
+SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
+LOCAL_SNS_ROOT="$(cd "$SCRIPT_DIR/.." && pwd)"
+
+# Change to local_sns root directory
+cd "$LOCAL_SNS_ROOT"
+
+# Check if dfx is running
+if ! dfx ping >/dev/null 2>&1; then
+    print_error "dfx is not running. Start it with: dfx start --clean --system-canisters"
+    exit 1
+fi
+
+# Check if deployment data exists (needed to get index canister ID)
+DEPLOYMENT_DATA="$(cargo run -q --bin local_sns -- data-path)"
+if [ ! -f "$DEPLOYMENT_DATA" ]; then
+    print_error "Deployment data file not found: $DEPLOYMENT_DATA"
+    print_info "Please deploy an SNS first (option 12 in menu, or run deploy_local_sns.sh)"
+    exit 1
+fi
+
+print_header "SNS Account Transactions"
+
+# Build command arguments - pass through whatever was provided
+CMD_ARGS=("get-account-transactions")
+# Pass all provided arguments through - Rust code will handle interactive prompts for missing ones
+for arg in "$@"; do
+    CMD_ARGS+=("$arg")
+done
+
+cargo run --bin local_sns -- "${CMD_ARGS[@]}"
+
@@ -3,10 +3,13 @@
 #
 # Usage:
 #   bash scripts/get_icp_balance.sh [principal] [subaccount_hex]
+#   bash scripts/get_icp_balance.sh <account_id_hex>
 #
 # Arguments (all optional - interactive prompts if not provided):
 #   principal       - Optional: Principal to query balance for
 #                     If not provided, uses default dfx identity principal or prompts
+#                     A 64-char account identifier (hex) can be given instead, e.g. a
+#                     neuron staking or swap escrow account (see account_id.sh)
 #   subaccount_hex  - Optional: Subaccount in hex format
 #                     If not provided, queries default account (no subaccount)
 #
@@ -18,6 +21,7 @@
 #   bash scripts/get_icp_balance.sh
 #   bash scripts/get_icp_balance.sh 2laou-ygqmf-...
 #   bash scripts/get_icp_balance.sh 2laou-ygqmf-... 0xabcd1234...
+#   bash scripts/get_icp_balance.sh 1c7a48ba6a562aa9eaa2481a9049cdf0433b9738c992d698c31d8abf89cadc79
 
 set -euo pipefail
 
@@ -3,14 +3,18 @@
 # This queries the ICP Governance canister for neuron details
 #
 # Usage:
-#   bash scripts/get_icp_neuron.sh [neuron_id]
+#   bash scripts/get_icp_neuron.sh [neuron_id] [principal]
+#   bash scripts/get_icp_neuron.sh <principal>
 #
 # Arguments:
 #   neuron_id - Optional: Specific neuron ID to query. If not provided, uses neuron from deployment data
+#   principal - Optional: Controller to query as (participant or extra identity), for neurons
+#               the owner cannot read. Given alone, prompts for one of its neurons
 #
 # Example:
 #   bash scripts/get_icp_neuron.sh
 #   bash scripts/get_icp_neuron.sh 1281960829742175837
+#   bash scripts/get_icp_neuron.sh 1281960829742175837 qc2qr-5u5mz-...
 
 set -euo pipefail
 
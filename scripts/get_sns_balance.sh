@@ -66,7 +66,7 @@ if ! dfx ping >/dev/null 2>&1; then
 fi
 
 # Check if deployment data exists (needed to get ledger canister ID)
-DEPLOYMENT_DATA="$LOCAL_SNS_ROOT/generated/sns_deployment_data.json"
+DEPLOYMENT_DATA="$(cargo run -q --bin local_sns -- data-path)"
 if [ ! -f "$DEPLOYMENT_DATA" ]; then
     print_error "Deployment data file not found: $DEPLOYMENT_DATA"
     print_info "Please deploy an SNS first (option 12 in menu, or run deploy_local_sns.sh)"
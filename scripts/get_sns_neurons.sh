@@ -3,15 +3,17 @@
 # This is a wrapper around the Rust binary's list-sns-neurons command
 #
 # Usage:
-#   bash scripts/get_sns_neurons.sh [principal]
+#   bash scripts/get_sns_neurons.sh [principal] [--watch] [--interval <seconds>]
 #
 # Arguments:
 #   principal - Optional: Principal to query neurons for
 #              If not provided, shows participant selection menu
+#   --watch   - Optional: Refresh the table every --interval seconds (default 5) until Ctrl-C
 #
 # Example:
 #   bash scripts/get_sns_neurons.sh
 #   bash scripts/get_sns_neurons.sh qc2qr-5u5mz-3ny2c-rzvkj-3z2lh-4uawd-5ggw7-pfwno-ghsmf-gqfau-oqe
+#   bash scripts/get_sns_neurons.sh qc2qr-5u5mz-... --watch --interval 2
 
 set -euo pipefail
 
@@ -67,7 +69,7 @@ if ! dfx ping >/dev/null 2>&1; then
 fi
 
 # Check if deployment data exists
-DEPLOYMENT_DATA="$LOCAL_SNS_ROOT/generated/sns_deployment_data.json"
+DEPLOYMENT_DATA="$(cargo run -q --bin local_sns -- data-path)"
 if [ ! -f "$DEPLOYMENT_DATA" ]; then
     print_error "Deployment data not found at: $DEPLOYMENT_DATA"
     print_info "Please deploy an SNS first (option 9 in menu, or run deploy_local_sns.sh)"
@@ -2,25 +2,25 @@
 # Script to increase dissolve delay for an ICP neuron
 #
 # Usage:
-#   bash scripts/increase_icp_dissolve_delay.sh [principal] [neuron_id] [additional_dissolve_delay_seconds]
+#   bash scripts/increase_icp_dissolve_delay.sh [principal] [neuron_id] [additional_dissolve_delay]
 #
 # Arguments (all optional - interactive prompts if not provided):
 #   principal                      - Optional: Principal of the owner of the neuron
 #                                   If not provided, shows participant selection menu
 #   neuron_id                     - Optional: Neuron ID (number)
 #                                   If not provided, shows neuron selection menu
-#   additional_dissolve_delay_seconds - Optional: Additional dissolve delay in seconds
+#   additional_dissolve_delay - Optional: Seconds, a duration (30d, 6 months, 1y) or min-to-vote/max
 #                                      If not provided, prompts interactively
 #
 # Interactive flow:
 #   1. Select principal (if not provided)
 #   2. Select neuron (if not provided)
-#   3. Enter additional dissolve delay in seconds (if not provided)
+#   3. Pick a dissolve delay preset or enter a duration (if not provided)
 #
 # Example:
 #   bash scripts/increase_icp_dissolve_delay.sh
 #   bash scripts/increase_icp_dissolve_delay.sh 2laou-ygqmf-... 2592000
-#   bash scripts/increase_icp_dissolve_delay.sh 2laou-ygqmf-... 12345 2592000
+#   bash scripts/increase_icp_dissolve_delay.sh 2laou-ygqmf-... 12345 min-to-vote
 
 set -euo pipefail
 
@@ -2,25 +2,25 @@
 # Script to increase dissolve delay for an SNS neuron
 #
 # Usage:
-#   bash scripts/increase_sns_dissolve_delay.sh [participant_principal] [neuron_id_hex] [additional_dissolve_delay_seconds]
+#   bash scripts/increase_sns_dissolve_delay.sh [participant_principal] [neuron_id_hex] [additional_dissolve_delay]
 #
 # Arguments (all optional - interactive prompts if not provided):
 #   participant_principal           - Optional: Principal of the participant who owns the neuron
 #                                    If not provided, shows participant selection menu
 #   neuron_id_hex                  - Optional: Neuron ID in hex format
 #                                    If not provided, auto-selects neuron with longest dissolve delay
-#   additional_dissolve_delay_seconds - Optional: Additional dissolve delay in seconds
+#   additional_dissolve_delay - Optional: Seconds, a duration (30d, 6 months, 1y) or min-to-vote/max
 #                                      If not provided, prompts interactively
 #
 # Interactive flow:
 #   1. Select participant (if not provided)
 #   2. Select neuron (if not provided)
-#   3. Enter additional dissolve delay in seconds (if not provided)
+#   3. Pick a dissolve delay preset or enter a duration (if not provided)
 #
 # Example:
 #   bash scripts/increase_sns_dissolve_delay.sh
 #   bash scripts/increase_sns_dissolve_delay.sh 2laou-ygqmf-... 2592000
-#   bash scripts/increase_sns_dissolve_delay.sh 2laou-ygqmf-... 0xabcd1234... 2592000
+#   bash scripts/increase_sns_dissolve_delay.sh 2laou-ygqmf-... 0xabcd1234... "6 months"
 
 set -euo pipefail
 
@@ -69,7 +69,7 @@ if ! dfx ping >/dev/null 2>&1; then
 fi
 
 # Check if deployment data exists
-DEPLOYMENT_DATA="$LOCAL_SNS_ROOT/generated/sns_deployment_data.json"
+DEPLOYMENT_DATA="$(cargo run -q --bin local_sns -- data-path)"
 if [ ! -f "$DEPLOYMENT_DATA" ]; then
     print_error "Deployment data file not found: $DEPLOYMENT_DATA"
     print_info "Please deploy an SNS first (option 9 in menu, or run deploy_local_sns.sh)"
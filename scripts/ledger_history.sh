@@ -0,0 +1,76 @@
+#!/bin/bash
+# Script to show recent transactions on the ICP or SNS ledger
+#
+# Usage:
+#   bash scripts/ledger_history.sh [icp|sns] [limit] [--principal <principal>] [--subaccount <hex>]
+#
+# Arguments (all optional - interactive prompts if not provided):
+#   icp|sns       - Optional: Which ledger to query
+#                   If not provided, shows ledger selection menu
+#   limit         - Optional: Number of most recent transactions to show (default: 20)
+#   --principal   - Optional: Only show this account's transactions (from the ICP or SNS index)
+#   --subaccount  - Optional: Subaccount of --principal in hex
+#
+# Example:
+#   bash scripts/ledger_history.sh
+#   bash scripts/ledger_history.sh icp
+#   bash scripts/ledger_history.sh sns 50
+#   bash scripts/ledger_history.sh icp --principal 2laou-ygqmf-...
+
+set -euo pipefail
+
+# Colors for output
+RED='\033[0;31m'
+GREEN='\033[0;32m'
+YELLOW='\033[1;33m'
+BLUE='\033[0;34m'
+NC='\033[0m' # No Color
+
+# Helper functions
+print_info() {
+    echo -e "${BLUE}ℹ${NC} $1"
+}
+
+print_success() {
+    echo -e "${GREEN}✓${NC} $1"
+}
+
+print_warning() {
+    echo -e "${YELLOW}⚠${NC} $1"
+}
+
+print_error() {
+    echo -e "${RED}✗${NC} $1"
+}
+
+print_header() {
+    echo ""
+    echo -e "${BLUE}━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━${NC}"
+    echo -e "${BLUE}  $1${NC}"
+    echo -e "${BLUE}━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━${NC}"
+}
+
+# Get script directory (should be in local_sns/scripts/)
+SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
+LOCAL_SNS_ROOT="$(cd "$SCRIPT_DIR/.." && pwd)"
+
+# Change to local_sns root directory
+cd "$LOCAL_SNS_ROOT"
+
+# Check if dfx is running
+if ! dfx ping >/dev/null 2>&1; then
+    print_error "dfx is not running. Start it with: dfx start --clean --system-canisters"
+    exit 1
+fi
+
+print_header "Ledger History"
+
+# Build command arguments - pass through whatever was provided
+CMD_ARGS=("ledger-history")
+# Pass all provided arguments through - Rust code will handle interactive prompts for missing ones
+for arg in "$@"; do
+    CMD_ARGS+=("$arg")
+done
+
+cargo run --bin local_sns -- "${CMD_ARGS[@]}"
+
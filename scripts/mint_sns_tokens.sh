@@ -2,7 +2,7 @@
 # Script to mint SNS tokens by creating a proposal and getting all neurons to vote
 #
 # Usage:
-#   bash scripts/mint_sns_tokens.sh [proposer_principal] [receiver_principal] [amount_e8s]
+#   bash scripts/mint_sns_tokens.sh [proposer_principal] [receiver_principal] [amount_e8s] [--wait]
 #
 # Arguments (all optional - interactive prompts if not provided):
 #   proposer_principal - Optional: Principal of the participant who will create the proposal
@@ -11,6 +11,7 @@
 #                        If not provided, prompts interactively
 #   amount_e8s        - Optional: Amount of tokens to mint (in e8s, e.g., 100000000 = 1 token)
 #                        If not provided, prompts interactively
+#   --wait            - Optional: Wait for the proposal to execute and report why it failed if it did
 #
 # Interactive flow:
 #   1. Select proposer participant (if not provided)
@@ -68,7 +69,7 @@ if ! dfx ping >/dev/null 2>&1; then
 fi
 
 # Check if deployment data exists
-DEPLOYMENT_DATA="$LOCAL_SNS_ROOT/generated/sns_deployment_data.json"
+DEPLOYMENT_DATA="$(cargo run -q --bin local_sns -- data-path)"
 if [ ! -f "$DEPLOYMENT_DATA" ]; then
     print_error "Deployment data file not found: $DEPLOYMENT_DATA"
     print_info "Please deploy an SNS first (option 9 in menu, or run deploy_local_sns.sh)"
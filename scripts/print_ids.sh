@@ -0,0 +1,74 @@
+#!/bin/bash
+# Script to print the principals, neuron IDs and canister IDs of the deployment
+#
+# Usage:
+#   bash scripts/print_ids.sh [name] [--copy]
+#
+# Arguments (all optional):
+#   name    - Print only this ID (e.g. owner, participant-1, sns-governance, icp-neuron)
+#   --copy  - Also copy the output to the system clipboard
+#
+# IDs are printed in full, one "name  id" line each, ready to paste into other commands.
+#
+# Example:
+#   bash scripts/print_ids.sh
+#   bash scripts/print_ids.sh sns-governance --copy
+
+set -euo pipefail
+
+# Colors for output
+RED='\033[0;31m'
+GREEN='\033[0;32m'
+YELLOW='\033[1;33m'
+BLUE='\033[0;34m'
+NC='\033[0m' # No Color
+
+# Helper functions
+print_info() {
+    echo -e "${BLUE}ℹ${NC} $1"
+}
+
+print_success() {
+    echo -e "${GREEN}✓${NC} $1"
+}
+
+print_warning() {
+    echo -e "${YELLOW}⚠${NC} $1"
+}
+
+print_error() {
+    echo -e "${RED}✗${NC} $1"
+}
+
+print_header() {
+    echo ""
+    echo -e "${BLUE}━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━${NC}"
+    echo -e "${BLUE}  $1${NC}"
+    echo -e "${BLUE}━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━${NC}"
+}
+
+# Get script directory (should be in local_sns/scripts/)
+SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
+LOCAL_SNS_ROOT="$(cd "$SCRIPT_DIR/.." && pwd)"
+
+# Change to local_sns root directory
+cd "$LOCAL_SNS_ROOT"
+
+# Check if deployment data exists
+DEPLOYMENT_DATA="$(cargo run -q --bin local_sns -- data-path)"
+if [ ! -f "$DEPLOYMENT_DATA" ]; then
+    print_error "Deployment data not found at $DEPLOYMENT_DATA"
+    print_info "Please deploy an SNS first using: bash scripts/deploy_local_sns.sh"
+    exit 1
+fi
+
+print_header "Print IDs"
+
+# Build command arguments - pass through whatever was provided
+CMD_ARGS=("print-ids")
+for arg in "$@"; do
+    CMD_ARGS+=("$arg")
+done
+
+cargo run --bin local_sns -- "${CMD_ARGS[@]}"
+
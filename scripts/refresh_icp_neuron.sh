@@ -0,0 +1,76 @@
+#!/bin/bash
+# Script to add ICP to an existing ICP neuron
+#
+# Usage:
+#   bash scripts/refresh_icp_neuron.sh [principal] [neuron_id] [amount_e8s]
+#
+# Arguments (all optional - interactive prompts if not provided):
+#   principal  - Optional: Controller of the neuron (owner, participant or extra identity)
+#               If not provided, shows participant selection menu
+#   neuron_id  - Optional: ICP neuron ID. If not provided, shows the principal's neurons
+#   amount_e8s - Optional: ICP to add in e8s. If not provided, prompts interactively
+#
+# Transfers the ICP to the neuron's account and refreshes the neuron, so its stake
+# grows instead of a new neuron being created.
+#
+# Example:
+#   bash scripts/refresh_icp_neuron.sh
+#   bash scripts/refresh_icp_neuron.sh 2laou-ygqmf-... 1281960829742175837 100000000
+
+set -euo pipefail
+
+# Colors for output
+RED='\033[0;31m'
+GREEN='\033[0;32m'
+YELLOW='\033[1;33m'
+BLUE='\033[0;34m'
+NC='\033[0m' # No Color
+
+# Helper functions
+print_info() {
+    echo -e "${BLUE}ℹ${NC} $1"
+}
+
+print_success() {
+    echo -e "${GREEN}✓${NC} $1"
+}
+
+print_warning() {
+    echo -e "${YELLOW}⚠${NC} $1"
+}
+
+print_error() {
+    echo -e "${RED}✗${NC} $1"
+}
+
+print_header() {
+    echo ""
+    echo -e "${BLUE}━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━${NC}"
+    echo -e "${BLUE}  $1${NC}"
+    echo -e "${BLUE}━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━${NC}"
+}
+
+# Get script directory (should be in local_sns/scripts/)
+SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
+LOCAL_SNS_ROOT="$(cd "$SCRIPT_DIR/.." && pwd)"
+
+# Change to local_sns root directory
+cd "$LOCAL_SNS_ROOT"
+
+# Check if dfx is running
+if ! dfx ping >/dev/null 2>&1; then
+    print_error "dfx is not running. Start it with: dfx start --clean --system-canisters"
+    exit 1
+fi
+
+print_header "Refresh ICP Neuron"
+
+# Build command arguments - pass through whatever was provided
+CMD_ARGS=("refresh-icp-neuron")
+# Pass all provided arguments through - Rust code will handle interactive prompts for missing ones
+for arg in "$@"; do
+    CMD_ARGS+=("$arg")
+done
+
+cargo run --bin local_sns -- "${CMD_ARGS[@]}"
+
@@ -0,0 +1,73 @@
+#!/bin/bash
+# Script to seed the SNS with holders, balances, neurons and following for demos
+#
+# Usage:
+#   bash scripts/seed.sh [file]
+#
+# Arguments (optional - prompts if not provided):
+#   file  - Seed spec (.yaml, .toml or .json), see config/seed.example.yaml
+#
+# Prints the plan (balance and neuron ladder of every holder) and then runs it.
+# Pass --dry-run to only print the plan.
+#
+# Example:
+#   bash scripts/seed.sh config/seed.example.yaml
+#   bash scripts/seed.sh --dry-run config/seed.example.yaml
+
+set -euo pipefail
+
+# Colors for output
+RED='\033[0;31m'
+GREEN='\033[0;32m'
+YELLOW='\033[1;33m'
+BLUE='\033[0;34m'
+NC='\033[0m' # No Color
+
+# Helper functions
+print_info() {
+    echo -e "${BLUE}ℹ${NC} $1"
+}
+
+print_success() {
+    echo -e "${GREEN}✓${NC} $1"
+}
+
+print_warning() {
+    echo -e "${YELLOW}⚠${NC} $1"
+}
+
+print_error() {
+    echo -e "${RED}✗${NC} $1"
+}
+
+print_header() {
+    echo ""
+    echo -e "${BLUE}━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━${NC}"
+    echo -e "${BLUE}  $1${NC}"
+    echo -e "${BLUE}━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━${NC}"
+}
+
+# Get script directory (should be in local_sns/scripts/)
+SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
+LOCAL_SNS_ROOT="$(cd "$SCRIPT_DIR/.." && pwd)"
+
+# Change to local_sns root directory
+cd "$LOCAL_SNS_ROOT"
+
+# Check if dfx is running (the PocketIC backend needs no dfx replica)
+if [ "${LOCAL_SNS_BACKEND:-dfx}" != "pocket-ic" ] && ! dfx ping >/dev/null 2>&1; then
+    print_error "dfx is not running. Start it with: dfx start --clean --system-canisters"
+    exit 1
+fi
+
+print_header "Seed Demo Data"
+
+# Build command arguments - pass through whatever was provided
+CMD_ARGS=("seed")
+# Pass all provided arguments through - Rust code will handle interactive prompts for missing ones
+for arg in "$@"; do
+    CMD_ARGS+=("$arg")
+done
+
+cargo run --bin local_sns -- "${CMD_ARGS[@]}"
+
@@ -0,0 +1,80 @@
+#!/bin/bash
+# Script to set the exact permissions of a principal on an SNS neuron
+#
+# Usage:
+#   bash scripts/set_sns_neuron_permissions.sh [controller_principal] [neuron_id_hex] [principal] [--permissions <list>]
+#
+# Arguments (all optional - interactive prompts if not provided):
+#   controller_principal - Optional: Principal that controls the neuron
+#                          If not provided, shows participant selection menu
+#   neuron_id_hex        - Optional: Neuron ID in hex format
+#                          If not provided, shows neuron selection menu
+#   principal            - Optional: Principal whose permissions to set
+#                          If not provided, shows principal selection menu
+#   --permissions <list> - Optional: Final permission set, comma-separated names or numbers
+#                          ('none' removes all). If not provided, shows the permission list
+#                          Permissions: 1=ConfigureDissolveState, 2=ManagePrincipals,
+#                          3=SubmitProposal, 4=Vote, 5=Disburse, 6=Split, 7=MergeMaturity,
+#                          8=DisburseMaturity, 9=StakeMaturity, 10=ManageVotingPermission
+#
+# Example:
+#   bash scripts/set_sns_neuron_permissions.sh
+#   bash scripts/set_sns_neuron_permissions.sh 2laou-ygqmf-... a1b2c3... hotkey-principal --permissions Vote,SubmitProposal,Disburse
+
+set -euo pipefail
+
+# Colors for output
+RED='\033[0;31m'
+GREEN='\033[0;32m'
+YELLOW='\033[1;33m'
+BLUE='\033[0;34m'
+NC='\033[0m' # No Color
+
+# Helper functions
+print_info() {
+    echo -e "${BLUE}ℹ${NC} $1"
+}
+
+print_success() {
+    echo -e "${GREEN}✓${NC} $1"
+}
+
+print_warning() {
+    echo -e "${YELLOW}⚠${NC} $1"
+}
+
+print_error() {
+    echo -e "${RED}✗${NC} $1"
+}
+
+print_header() {
+    echo ""
+    echo -e "${BLUE}━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━${NC}"
+    echo -e "${BLUE}  $1${NC}"
+    echo -e "${BLUE}━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━${NC}"
+}
+
+# Get script directory (should be in local_sns/scripts/)
+SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
+LOCAL_SNS_ROOT="$(cd "$SCRIPT_DIR/.." && pwd)"
+
+# Change to local_sns root directory
+cd "$LOCAL_SNS_ROOT"
+
+# Check if dfx is running
+if ! dfx ping >/dev/null 2>&1; then
+    print_error "dfx is not running. Start it with: dfx start --clean --system-canisters"
+    exit 1
+fi
+
+print_header "Set SNS Neuron Permissions"
+
+# Build command arguments - pass through whatever was provided
+CMD_ARGS=("set-sns-neuron-permissions")
+# Pass all provided arguments through - Rust code will handle interactive prompts for missing ones
+for arg in "$@"; do
+    CMD_ARGS+=("$arg")
+done
+
+cargo run --bin local_sns -- "${CMD_ARGS[@]}"
+
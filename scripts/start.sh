@@ -51,7 +51,7 @@ cd "$LOCAL_SNS_ROOT"
 check_sns_deployed() {
     # Use Rust binary to check via network (suppress all output)
     # Exit code 0 = deployed, 1 = not deployed
-    if cargo run --quiet --bin local_sns -- check-sns-deployed >/dev/null 2>&1; then
+    if cargo run --quiet --bin local_sns -- check-sns-deployed --quiet >/dev/null 2>&1; then
         return 0
     fi
     return 1
@@ -136,9 +136,30 @@ show_icp_menu() {
     echo -e "  ${GREEN}9${NC} / [${GREEN}V${NC}]  Set ICP Neuron Visibility"
     echo -e "     Set ICP neuron visibility (public/private)"
     echo ""
+    echo -e "  ${GREEN}10${NC} / [${GREEN}A${NC}] Account ID"
+    echo -e "     Show ICP account IDs and staking/swap subaccounts of a principal"
+    echo ""
+    echo -e "  ${GREEN}11${NC} / [${GREEN}AN${NC}] All ICP Neurons"
+    echo -e "     List the ICP neurons of the owner and every participant"
+    echo ""
+    echo -e "  ${GREEN}12${NC} / [${GREEN}R${NC}]  Refresh ICP Neuron"
+    echo -e "     Add ICP to an existing ICP neuron"
+    echo ""
+    echo -e "  ${GREEN}13${NC} / [${GREEN}MO${NC}] NNS Motion"
+    echo -e "     Make an NNS motion proposal with the ICP neuron"
+    echo ""
+    echo -e "  ${GREEN}14${NC} / [${GREEN}VP${NC}] Vote on NNS Proposal"
+    echo -e "     Vote on an NNS proposal with the ICP neuron"
+    echo ""
+    echo -e "  ${GREEN}15${NC} / [${GREEN}SP${NC}] Spawn ICP Neuron"
+    echo -e "     Spawn an ICP neuron's maturity into a new neuron"
+    echo ""
+    echo -e "  ${GREEN}16${NC} / [${GREEN}MG${NC}] Merge ICP Neurons"
+    echo -e "     Merge two ICP neurons of the same controller"
+    echo ""
     echo -e "  ${GREEN}0${NC} / ${CYAN}Enter${NC}  Back to Main Menu"
     echo ""
-    echo -n -e "${CYAN}Select operation [0-9, L, C, D, M, H, I, DD, B, V, or Enter]: ${NC}"
+    echo -n -e "${CYAN}Select operation [0-16, L, C, D, M, H, I, DD, B, V, A, AN, R, MO, VP, SP, MG, or Enter]: ${NC}"
 }
 
 # Show ICP submenu (when SNS is NOT deployed)
@@ -195,9 +216,68 @@ show_sns_menu() {
     echo -e "  ${GREEN}8${NC} / [${GREEN}B${NC}]  Get SNS Balance"
     echo -e "     Get SNS ledger balance for an account"
     echo ""
+    echo -e "  ${GREEN}9${NC} / [${GREEN}P${NC}]  Set SNS Neuron Permissions"
+    echo -e "     Grant or revoke an exact permission set on an SNS neuron"
+    echo ""
+    echo -e "  ${GREEN}10${NC} / [${GREEN}S${NC}] Get Swap State"
+    echo -e "     Show lifecycle, participation and timing of the swap"
+    echo ""
+    echo -e "  ${GREEN}11${NC} / [${GREEN}F${NC}] Finalize Swap"
+    echo -e "     Wait for the swap to commit or abort, then finalize it"
+    echo ""
+    echo -e "  ${GREEN}12${NC} / [${GREEN}A${NC}] Abort Swap"
+    echo -e "     Finalize an aborted swap and verify the participants' refunds"
+    echo ""
+    echo -e "  ${GREEN}13${NC} / [${GREEN}CS${NC}] SNS Canister Status"
+    echo -e "     Show status, cycles, memory and module hash of every SNS canister"
+    echo ""
+    echo -e "  ${GREEN}14${NC} / [${GREEN}TC${NC}] Top Up Cycles"
+    echo -e "     Add cycles to SNS canisters so they don't freeze"
+    echo ""
+    echo -e "  ${GREEN}15${NC} / [${GREEN}GM${NC}] Get SNS Metrics"
+    echo -e "     Show staked tokens, dissolve buckets, treasury and voting power"
+    echo ""
+    echo -e "  ${GREEN}16${NC} / [${GREEN}VD${NC}] Verify Deployment"
+    echo -e "     Check the deployment data against the replica"
+    echo ""
+    echo -e "  ${GREEN}17${NC} / [${GREEN}O${NC}]  Balance Overview"
+    echo -e "     Show ICP and SNS balances and stakes of the owner and every participant"
+    echo ""
+    echo -e "  ${GREEN}18${NC} / [${GREEN}AN${NC}] All SNS Neurons"
+    echo -e "     List the SNS neurons of the owner and every participant"
+    echo ""
+    echo -e "  ${GREEN}19${NC} / [${GREEN}R${NC}]  Refresh SNS Neuron"
+    echo -e "     Add SNS tokens to an existing SNS neuron"
+    echo ""
+    echo -e "  ${GREEN}20${NC} / [${GREEN}CL${NC}] Claim SNS Neuron"
+    echo -e "     Claim a neuron staked by an external transfer (memo and controller)"
+    echo ""
+    echo -e "  ${GREEN}21${NC} / [${GREEN}SB${NC}] Show Ballots"
+    echo -e "     Show how each neuron voted on a proposal"
+    echo ""
+    echo -e "  ${GREEN}22${NC} / [${GREEN}GF${NC}] Add Generic Function"
+    echo -e "     Propose a custom proposal type from a spec file"
+    echo ""
+    echo -e "  ${GREEN}23${NC} / [${GREEN}EF${NC}] Execute Generic Function"
+    echo -e "     Propose executing a custom proposal type with a payload"
+    echo ""
+    echo -e "  ${GREEN}24${NC} / [${GREEN}EX${NC}] SNS Extensions"
+    echo -e "     Register treasury-manager extensions, deposit, withdraw and list them"
+    echo ""
+    echo -e "  ${GREEN}25${NC} / [${GREEN}UN${NC}] Upgrade SNS to Next Version"
+    echo -e "     Propose a one-step SNS upgrade and follow the upgrade journal"
+    echo ""
+    echo -e "  ${GREEN}26${NC} / [${GREEN}AT${NC}] Advance SNS Target Version"
+    echo -e "     Propose upgrading to the latest SNS version and follow the upgrade journal"
+    echo ""
+    echo -e "  ${GREEN}27${NC} / [${GREEN}AX${NC}] Account Transactions"
+    echo -e "     List an account's transactions from the SNS index canister"
+    echo -e "  ${GREEN}28${NC} / [${GREEN}RN${NC}] Resolve SNS Neuron"
+    echo -e "     Derive a neuron ID from controller and memo and check its staking account"
+    echo ""
     echo -e "  ${GREEN}0${NC} / ${CYAN}Enter${NC}  Back to Main Menu"
     echo ""
-    echo -n -e "${CYAN}Select operation [0-8, L, C, D, M, H, I, DD, B, or Enter]: ${NC}"
+    echo -n -e "${CYAN}Select operation [0-28, L, C, D, M, H, I, DD, B, P, S, F, A, CS, TC, GM, VD, O, AN, R, CL, SB, GF, EF, EX, UN, AT, AX, RN, or Enter]: ${NC}"
 }
 
 # Show Utils submenu
@@ -213,9 +293,34 @@ show_utils_menu() {
     echo -e "  ${GREEN}2${NC} / [${GREEN}R${NC}]  Rebuild Binary"
     echo -e "     Rebuild the Rust binary (useful after code changes)"
     echo ""
+    echo -e "  ${GREEN}3${NC} / [${GREEN}N${NC}]  Manage Identities"
+    echo -e "     Create, list and show extra test identities"
+    echo ""
+    echo -e "  ${GREEN}4${NC} / [${GREEN}L${NC}]  List Deployed SNSes"
+    echo -e "     List SNSes on SNS-W and verify the deployment data"
+    echo ""
+    echo -e "  ${GREEN}5${NC} / [${GREEN}T${NC}]  Advance Time"
+    echo -e "     Move the replica clock forward (PocketIC only)"
+    echo ""
+    echo -e "  ${GREEN}6${NC} / [${GREEN}S${NC}]  Run Scenario"
+    echo -e "     Run a scenario file of operations and assertions"
+    echo ""
+    echo -e "  ${GREEN}7${NC} / [${GREEN}E${NC}]  Export Deployment Data"
+    echo -e "     Write the SNS canister IDs to .env and canister_ids.json"
+    echo ""
+    echo -e "  ${GREEN}8${NC} / [${GREEN}G${NC}]  Seed Demo Data"
+    echo -e "     Create holders, balances, neurons and following from a seed spec"
+    echo ""
+    echo -e "  ${GREEN}9${NC} / [${GREEN}I${NC}]  Initialize NNS"
+    echo -e "     Start the replica if needed and install the NNS (dfx nns install)"
+    echo ""
+    echo -e "  ${GREEN}10${NC} / [${GREEN}P${NC}] Print IDs"
+    echo -e "  ${GREEN}11${NC} / [${GREEN}H${NC}] History"
+    echo -e "     Print principals, neuron and canister IDs in full, ready to copy"
+    echo ""
     echo -e "  ${GREEN}0${NC} / ${CYAN}Enter${NC}  Back to Main Menu"
     echo ""
-    echo -n -e "${CYAN}Select operation [0-2, D, R, or Enter]: ${NC}"
+    echo -n -e "${CYAN}Select operation [0-10, D, R, N, L, T, S, E, G, I, P, or Enter]: ${NC}"
 }
 
 # Run selected script
@@ -272,6 +377,28 @@ run_script() {
                 9|v|V)
                     script_name="set_icp_visibility.sh"
                     ;;
+                10|a|A)
+                    script_name="account_id.sh"
+                    ;;
+                11|an|AN)
+                    script_name="list_all_neurons.sh"
+                    script_args=("icp")
+                    ;;
+                12|r|R)
+                    script_name="refresh_icp_neuron.sh"
+                    ;;
+                13|mo|MO)
+                    script_name="make_nns_motion.sh"
+                    ;;
+                14|vp|VP)
+                    script_name="vote_nns_proposal.sh"
+                    ;;
+                15|sp|SP)
+                    script_name="spawn_icp_neuron.sh"
+                    ;;
+                16|mg|MG)
+                    script_name="merge_icp_neurons.sh"
+                    ;;
                 *)
                     print_error "Invalid ICP operation: $operation"
                     return 1
@@ -304,6 +431,75 @@ run_script() {
                 8|b|B)
                     script_name="get_sns_balance.sh"
                     ;;
+                9|p|P)
+                    script_name="set_sns_neuron_permissions.sh"
+                    ;;
+                10|s|S)
+                    script_name="get_swap_state.sh"
+                    ;;
+                11|f|F)
+                    script_name="finalize_swap.sh"
+                    # From the menu, wait for the swap to end instead of failing while it is open
+                    if [ ${#script_args[@]} -eq 0 ]; then
+                        script_args=("--watch")
+                    fi
+                    ;;
+                12|a|A)
+                    script_name="abort_swap.sh"
+                    # From the menu, wait for the swap to abort instead of failing while it is open
+                    if [ ${#script_args[@]} -eq 0 ]; then
+                        script_args=("--watch")
+                    fi
+                    ;;
+                13|cs|CS)
+                    script_name="sns_canister_status.sh"
+                    ;;
+                14|tc|TC)
+                    script_name="top_up_cycles.sh"
+                    ;;
+                15|gm|GM)
+                    script_name="get_sns_metrics.sh"
+                    ;;
+                16|vd|VD)
+                    script_name="verify_deployment.sh"
+                    ;;
+                17|o|O)
+                    script_name="balances.sh"
+                    ;;
+                18|an|AN)
+                    script_name="list_all_neurons.sh"
+                    script_args=("sns")
+                    ;;
+                19|r|R)
+                    script_name="refresh_sns_neuron.sh"
+                    ;;
+                20|cl|CL)
+                    script_name="claim_sns_neuron.sh"
+                    ;;
+                21|sb|SB)
+                    script_name="show_ballots.sh"
+                    ;;
+                22|gf|GF)
+                    script_name="add_generic_function.sh"
+                    ;;
+                23|ef|EF)
+                    script_name="execute_generic_function.sh"
+                    ;;
+                24|ex|EX)
+                    script_name="extensions.sh"
+                    ;;
+                25|un|UN)
+                    script_name="upgrade_sns_to_next_version.sh"
+                    ;;
+                26|at|AT)
+                    script_name="advance_sns_target_version.sh"
+                    ;;
+                27|ax|AX)
+                    script_name="get_account_transactions.sh"
+                    ;;
+                28|rn|RN)
+                    script_name="resolve_neuron.sh"
+                    ;;
                 *)
                     print_error "Invalid SNS operation: $operation"
                     return 1
@@ -321,6 +517,33 @@ run_script() {
                     print_success "Binary rebuilt successfully!"
                     return 0
                     ;;
+                3|n|N)
+                    script_name="identity.sh"
+                    ;;
+                4|l|L)
+                    script_name="list_deployed_snses.sh"
+                    ;;
+                5|t|T)
+                    script_name="advance_time.sh"
+                    ;;
+                6|s|S)
+                    script_name="run_scenario.sh"
+                    ;;
+                7|e|E)
+                    script_name="export.sh"
+                    ;;
+                8|g|G)
+                    script_name="seed.sh"
+                    ;;
+                9|i|I)
+                    script_name="init_nns.sh"
+                    ;;
+                10|p|P)
+                    script_name="print_ids.sh"
+                    ;;
+                11|h|H)
+                    script_name="history.sh"
+                    ;;
                 *)
                     print_error "Invalid Utils operation: $operation"
                     return 1
@@ -347,14 +570,21 @@ run_script() {
         # Run the script with any passed arguments
         print_header "Running: $script_name"
         # script_args is always initialized, but check length to avoid issues with set -u
+        # A failed or cancelled operation returns to the menu (exit codes: see README)
+        local status=0
         if [ ${#script_args[@]} -eq 0 ]; then
-            bash "$script_path"
+            bash "$script_path" || status=$?
         else
-            bash "$script_path" "${script_args[@]}"
+            bash "$script_path" "${script_args[@]}" || status=$?
+        fi
+        if [ "$status" -eq 5 ]; then
+            print_info "Cancelled"
+        elif [ "$status" -ne 0 ]; then
+            print_error "$script_name failed (exit code $status)"
         fi
     fi
     
-    return $?
+    return 0
 }
 
 # Main loop
@@ -461,7 +691,7 @@ main() {
                             0)
                                 break  # Back to main menu
                                 ;;
-                            [1-9]|[lL]|[cC]|[dD]|[mM]|[hH]|[iI]|dd|DD|[bB]|[vV])
+                            [1-9]|1[0-6]|[lL]|[cC]|[dD]|[mM]|[hH]|[iI]|dd|DD|[bB]|[vV]|[aA]|an|AN|[rR]|mo|MO|vp|VP|sp|SP|mg|MG)
                                 run_script "icp" "$operation_choice"
                                 echo ""
                                 echo -n -e "${CYAN}Press Enter to return to menu...${NC}"
@@ -489,7 +719,7 @@ main() {
                             0)
                                 break  # Back to main menu
                                 ;;
-                            [1-8]|[lL]|[cC]|[dD]|[mM]|[hH]|[iI]|dd|DD|[bB])
+                            [1-9]|1[0-9]|2[0-8]|[lL]|[cC]|[dD]|[mM]|[hH]|[iI]|dd|DD|[bB]|[pP]|[sS]|[fF]|[aA]|cs|CS|tc|TC|gm|GM|vd|VD|[oO]|an|AN|[rR]|cl|CL|sb|SB|gf|GF|ef|EF|ex|EX|un|UN|at|AT|ax|AX|rn|RN)
                                 run_script "sns" "$operation_choice"
                                 echo ""
                                 echo -n -e "${CYAN}Press Enter to return to menu...${NC}"
@@ -517,7 +747,7 @@ main() {
                             0)
                                 break  # Back to main menu
                                 ;;
-                            [1-2]|[dD]|[rR])
+                            [1-9]|1[01]|[dD]|[rR]|[nN]|[lL]|[tT]|[sS]|[eE]|[gG]|[iI]|[pP]|[hH])
                                 run_script "utils" "$operation_choice"
                                 echo ""
                                 echo -n -e "${CYAN}Press Enter to return to menu...${NC}"
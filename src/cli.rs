@@ -0,0 +1,636 @@
+// Command-line dispatcher for the local_sns binary
+
+use crate::core::utils::exit_code::invalid;
+use anyhow::{Context, Result};
+use std::path::Path;
+
+use crate::core::ops::audit_log::enable_audit_log;
+use crate::core::ops::canister_call::{dry_run_stop, set_debug_candid, set_dry_run};
+use crate::core::ops::commands::{
+    handle_abort_swap, handle_account_id, handle_add_generic_function, handle_add_hotkey,
+    handle_advance_sns_target_version, handle_advance_time, handle_alias, handle_balances,
+    handle_bench, handle_check_principal, handle_check_sns_deployed, handle_claim_sns_neuron,
+    handle_config, handle_create_icp_neuron, handle_create_sns_neuron, handle_data_path,
+    handle_deploy_sns, handle_disburse_icp_neuron, handle_disburse_sns_neuron,
+    handle_execute_generic_function, handle_export, handle_extensions, handle_finalize_swap,
+    handle_get_account_transactions, handle_get_icp_balance, handle_get_icp_neuron,
+    handle_get_sns_balance, handle_get_sns_metrics, handle_get_sns_neuron, handle_get_swap_state,
+    handle_history, handle_identity, handle_import_sns, handle_increase_icp_dissolve_delay,
+    handle_increase_sns_dissolve_delay, handle_init_nns, handle_ledger_history,
+    handle_list_all_neurons, handle_list_deployed_snses, handle_list_icp_neurons,
+    handle_list_neurons, handle_list_sns_proposals, handle_make_nns_motion,
+    handle_manage_icp_dissolving, handle_manage_sns_dissolving, handle_merge_icp_neurons,
+    handle_metrics, handle_mint_icp, handle_mint_sns_tokens, handle_neuron_ladder,
+    handle_print_ids, handle_recover_stakes, handle_refresh_icp_neuron, handle_refresh_sns_neuron,
+    handle_resolve_neuron, handle_run_scenario, handle_seed, handle_set_icp_visibility,
+    handle_set_sns_neuron_permissions, handle_show_ballots, handle_show_sns_proposal_payload,
+    handle_sns_canister_status, handle_spawn_icp_neuron, handle_token_info, handle_top_up_cycles,
+    handle_trace, handle_transfer, handle_undo, handle_upgrade_sns_to_next_version,
+    handle_validate_config, handle_verify_deployment, handle_verify_distribution,
+    handle_vote_nns_proposal,
+};
+use crate::core::ops::deployment::deploy_sns;
+use crate::core::ops::identity::{
+    explain_hotkey_error, resolve_principal, set_acting_hotkey, set_dfx_identity, set_key_file,
+};
+use crate::core::ops::recording::{start_recording, start_replay};
+use crate::core::utils::backend::{Backend, start_pocket_ic_backend};
+use crate::core::utils::cli_args::{take_flag, take_switch};
+use crate::core::utils::clipboard::set_copy;
+use crate::core::utils::confirm::set_assume_yes;
+use crate::core::utils::data_output::set_data_file;
+use crate::core::utils::exit_code::set_json_errors;
+use crate::core::utils::logging::{Verbosity, init_logging};
+use crate::core::utils::network::{NetworkConfig, set_network_config};
+use crate::core::utils::print_info;
+use crate::core::utils::qr::set_qr;
+use crate::core::utils::table::{set_no_truncate, set_wide};
+use crate::core::utils::user_config::{Source, config_default, config_value, parse_bool};
+use crate::init::sns_config::SnsConfig;
+
+// Helper to check if error is a navigation error (user went back or to main menu)
+fn is_navigation_error(err: &anyhow::Error) -> bool {
+    err.to_string().contains("User went back")
+        || err.to_string().contains("User went to main menu")
+        || err.to_string().contains("User cancelled")
+}
+
+/// Apply the global `--backend` flag (or `LOCAL_SNS_BACKEND`)
+/// Returns the replica URL of the PocketIC backend, which the network flags can still override
+async fn apply_backend_flag(args: &mut Vec<String>) -> Result<Option<String>> {
+    let flag = take_flag(args, "--backend")?.or_else(|| config_value("backend"));
+    match Backend::resolve(flag.as_deref())? {
+        Backend::Dfx => Ok(None),
+        Backend::PocketIc => Ok(Some(start_pocket_ic_backend().await?)),
+    }
+}
+
+/// Apply the global network flags, falling back to the network config file and environment
+fn apply_network_flags(args: &mut Vec<String>, backend_url: Option<String>) -> Result<()> {
+    let config_path = take_flag(args, "--network-config")?;
+    // DFX_NETWORK is already honoured when the config is resolved lazily
+    let network_name = match take_flag(args, "--network")? {
+        Some(name) => Some(name),
+        None => config_default("network")
+            .filter(|(_, source)| *source != Source::Env("DFX_NETWORK"))
+            .map(|(name, _)| name),
+    };
+    let replica_url = take_flag(args, "--replica-url")?;
+    let governance = take_flag(args, "--governance-canister")?;
+    let ledger = take_flag(args, "--ledger-canister")?;
+    let snsw = take_flag(args, "--snsw-canister")?;
+    let icp_index = take_flag(args, "--icp-index-canister")?;
+
+    if backend_url.is_none()
+        && config_path.is_none()
+        && network_name.is_none()
+        && replica_url.is_none()
+        && governance.is_none()
+        && ledger.is_none()
+        && snsw.is_none()
+        && icp_index.is_none()
+    {
+        // Nothing on the command line - the config is resolved lazily from the environment
+        return Ok(());
+    }
+
+    let mut network = NetworkConfig::load(config_path.as_deref().map(std::path::Path::new))?;
+    if let Some(name) = network_name {
+        network.use_dfx_network(&name)?;
+    }
+    if let Some(url) = backend_url {
+        network.replica_url = url;
+        network.fetch_root_key = true;
+    }
+    if let Some(url) = replica_url {
+        network.replica_url = url;
+    }
+    for (flag, value, target) in [
+        (
+            "--governance-canister",
+            governance,
+            &mut network.governance_canister,
+        ),
+        ("--ledger-canister", ledger, &mut network.ledger_canister),
+        ("--snsw-canister", snsw, &mut network.snsw_canister),
+        (
+            "--icp-index-canister",
+            icp_index,
+            &mut network.icp_index_canister,
+        ),
+    ] {
+        if let Some(value) = value {
+            *target = candid::Principal::from_text(&value)
+                .map_err(|e| invalid(format!("Invalid {flag} '{value}': {e}")))?;
+        }
+    }
+
+    set_network_config(network)
+}
+
+/// Apply the global `--sns-<role>-canister` flags (or `LOCAL_SNS_SNS_<ROLE>_CANISTER`), which
+/// point the SNS commands at an SNS this tool did not deploy; with the root canister, the
+/// canisters not given are asked from root
+/// The `sns-` prefix is needed because `--governance-canister` and `--ledger-canister` are
+/// the NNS overrides applied in `apply_network_flags`
+async fn apply_sns_canister_flags(args: &mut Vec<String>) -> Result<()> {
+    use crate::core::context::app_context;
+    use crate::core::ops::sns_root_ops::discover_sns_canisters;
+    use crate::core::utils::data_output::DeployedSnsData;
+
+    let mut overrides = DeployedSnsData::default();
+    for (role, target) in [
+        ("root", &mut overrides.root_canister_id),
+        ("governance", &mut overrides.governance_canister_id),
+        ("ledger", &mut overrides.ledger_canister_id),
+        ("swap", &mut overrides.swap_canister_id),
+        ("index", &mut overrides.index_canister_id),
+    ] {
+        let flag = format!("--sns-{role}-canister");
+        let var = format!("LOCAL_SNS_SNS_{}_CANISTER", role.to_uppercase());
+        if let Some(value) = take_flag(args, &flag)?.or_else(|| std::env::var(&var).ok()) {
+            let id = candid::Principal::from_text(value.trim())
+                .map_err(|e| invalid(format!("Invalid {flag} '{value}': {e}")))?;
+            *target = Some(id.to_text());
+        }
+    }
+    let given = overrides.canister_ids().len();
+    if given == 0 {
+        return Ok(());
+    }
+    if args.get(1).is_some_and(|command| command == "deploy-sns") {
+        anyhow::bail!(
+            "--sns-*-canister flags point commands at an existing SNS; deploy-sns cannot use them"
+        );
+    }
+    if given < 5
+        && let Ok(root) = overrides.root_canister()
+    {
+        let agent = app_context().anonymous_agent().await?;
+        let mut discovered = discover_sns_canisters(&agent, root).await?;
+        discovered.apply_overrides(&overrides);
+        overrides = discovered;
+    }
+    log::info!("Using SNS canisters {:?}", overrides.canister_ids());
+    app_context().set_sns_overrides(overrides);
+    Ok(())
+}
+
+/// Apply the global `--error-format <text|json>` flag
+fn apply_error_format_flag(args: &mut Vec<String>) -> Result<()> {
+    let format = take_flag(args, "--error-format")?.or_else(|| config_value("error_format"));
+    match format.as_deref() {
+        None | Some("text") => {}
+        Some("json") => set_json_errors(true),
+        Some(other) => {
+            return Err(invalid(format!(
+                "Invalid --error-format '{other}': expected text or json"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Apply the global `--record <file>` and `--replay <file>` flags
+fn apply_recording_flags(args: &mut Vec<String>) -> Result<()> {
+    match (take_flag(args, "--record")?, take_flag(args, "--replay")?) {
+        (Some(_), Some(_)) => return Err(invalid("--record cannot be combined with --replay")),
+        (Some(path), None) => {
+            start_recording(Path::new(&path))?;
+            print_info(&format!("Recording canister calls to {path}"));
+        }
+        (None, Some(path)) => {
+            start_replay(Path::new(&path))?;
+            print_info(&format!("Replaying canister calls from {path}"));
+        }
+        (None, None) => {}
+    }
+    Ok(())
+}
+
+/// Apply the global `-v`/`-vv`/`-vvv` (or repeated `--verbose`) and `--quiet` flags
+fn apply_logging_flags(args: &mut Vec<String>) -> Result<()> {
+    let mut count = 0;
+    args.retain(|a| {
+        let level = match a.as_str() {
+            "--verbose" => 1,
+            _ if a.len() > 1 && a.starts_with('-') && a[1..].chars().all(|c| c == 'v') => {
+                a.len() - 1
+            }
+            _ => return true,
+        };
+        count += level;
+        false
+    });
+    let quiet = take_switch(args, "--quiet") | take_switch(args, "-q");
+
+    if quiet && count > 0 {
+        return Err(invalid("--quiet cannot be combined with -v/--verbose"));
+    }
+    init_logging(if quiet {
+        Verbosity::Quiet
+    } else {
+        Verbosity::from_count(count)
+    });
+    Ok(())
+}
+
+/// Run the CLI with the full argument list (including the program name at index 0)
+pub async fn run(args: &[String]) -> Result<()> {
+    crate::core::utils::interrupt::install();
+    let mut args = args.to_vec();
+    // `config` edits the defaults the global flags fall back to, so none are applied to it
+    if args.get(1).is_none_or(|command| command != "config") {
+        apply_global_flags(&mut args).await?;
+    }
+    let args = args.as_slice();
+    enable_audit_log(&if args.len() > 1 {
+        args[1..].join(" ")
+    } else {
+        "deploy-sns".to_string()
+    });
+
+    // Handle CLI commands
+    if args.len() > 1 {
+        let result = match args[1].as_str() {
+            "config" => handle_config(args).await,
+            "deploy-sns" => handle_deploy_sns(args).await,
+            "validate-config" => handle_validate_config(args).await,
+            "show-sns-proposal-payload" => handle_show_sns_proposal_payload(args).await,
+            "verify-distribution" => handle_verify_distribution(args).await,
+            "neuron-ladder" => handle_neuron_ladder(args).await,
+            "init-nns" => handle_init_nns(args).await,
+            "add-hotkey" => handle_add_hotkey(args).await,
+            "list-sns-neurons" => handle_list_neurons(args).await,
+            "get-sns-neuron" => handle_get_sns_neuron(args).await,
+            "list-icp-neurons" => handle_list_icp_neurons(args).await,
+            "list-all-neurons" => handle_list_all_neurons(args).await,
+            "mint-sns-tokens" => handle_mint_sns_tokens(args).await,
+            "add-generic-function" => handle_add_generic_function(args).await,
+            "execute-generic-function" => handle_execute_generic_function(args).await,
+            "extensions" => handle_extensions(args).await,
+            "upgrade-sns-to-next-version" => handle_upgrade_sns_to_next_version(args).await,
+            "advance-sns-target-version" => handle_advance_sns_target_version(args).await,
+            "create-sns-neuron" => handle_create_sns_neuron(args).await,
+            "refresh-sns-neuron" => handle_refresh_sns_neuron(args).await,
+            "claim-sns-neuron" => handle_claim_sns_neuron(args).await,
+            "resolve-neuron" => handle_resolve_neuron(args).await,
+            "recover-stakes" => handle_recover_stakes(args).await,
+            "disburse-sns-neuron" => handle_disburse_sns_neuron(args).await,
+            "disburse-icp-neuron" => handle_disburse_icp_neuron(args).await,
+            "spawn-icp-neuron" => handle_spawn_icp_neuron(args).await,
+            "merge-icp-neurons" => handle_merge_icp_neurons(args).await,
+            "increase-sns-dissolve-delay" => handle_increase_sns_dissolve_delay(args).await,
+            "increase-icp-dissolve-delay" => handle_increase_icp_dissolve_delay(args).await,
+            "manage-sns-dissolving" => handle_manage_sns_dissolving(args).await,
+            "manage-icp-dissolving" => handle_manage_icp_dissolving(args).await,
+            "set-icp-visibility" => handle_set_icp_visibility(args).await,
+            "get-icp-neuron" => handle_get_icp_neuron(args).await,
+            "get-icp-balance" => handle_get_icp_balance(args).await,
+            "account-id" => handle_account_id(args).await,
+            "get-sns-balance" => handle_get_sns_balance(args).await,
+            "balances" => handle_balances(args).await,
+            "mint-icp" => handle_mint_icp(args).await,
+            "transfer" => handle_transfer(args).await,
+            "create-icp-neuron" => handle_create_icp_neuron(args).await,
+            "make-nns-motion" => handle_make_nns_motion(args).await,
+            "vote-nns-proposal" => handle_vote_nns_proposal(args).await,
+            "refresh-icp-neuron" => handle_refresh_icp_neuron(args).await,
+            "check-sns-deployed" => handle_check_sns_deployed(args).await,
+            "list-deployed-snses" => handle_list_deployed_snses(args).await,
+            "import-sns" => handle_import_sns(args).await,
+            "verify-deployment" => handle_verify_deployment(args).await,
+            "sns-canister-status" => handle_sns_canister_status(args).await,
+            "top-up-cycles" => handle_top_up_cycles(args).await,
+            "ledger-history" => handle_ledger_history(args).await,
+            "get-account-transactions" => handle_get_account_transactions(args).await,
+            "token-info" => handle_token_info(args).await,
+            "trace" => handle_trace(args).await,
+            "check-principal" => handle_check_principal(args).await,
+            "identity" => handle_identity(args).await,
+            "alias" => handle_alias(args).await,
+            "set-sns-neuron-permissions" => handle_set_sns_neuron_permissions(args).await,
+            "get-swap-state" => handle_get_swap_state(args).await,
+            "list-sns-proposals" => handle_list_sns_proposals(args).await,
+            "get-sns-metrics" => handle_get_sns_metrics(args).await,
+            "show-ballots" => handle_show_ballots(args).await,
+            "advance-time" => handle_advance_time(args).await,
+            "run-scenario" => handle_run_scenario(args).await,
+            "seed" => handle_seed(args).await,
+            "bench" => handle_bench(args).await,
+            "export" => handle_export(args).await,
+            "data-path" => handle_data_path(args).await,
+            "print-ids" => handle_print_ids(args).await,
+            "history" => handle_history(args).await,
+            "undo" => handle_undo(args).await,
+            "metrics" => handle_metrics(args).await,
+            "finalize-swap" => handle_finalize_swap(args).await,
+            "abort-swap" => handle_abort_swap(args).await,
+            _ => {
+                eprintln!("Unknown command: {}", args[1]);
+                eprintln!("\nAvailable commands:");
+                eprintln!(
+                    "  deploy-sns          - Deploy a new SNS on local dfx network [--config <file>] [--abort-swap] [--bootstrap] [--backend pocket-ic]"
+                );
+                eprintln!(
+                    "  validate-config     - Check an SNS config file against the NNS limits without deploying [<file>]"
+                );
+                eprintln!(
+                    "  show-sns-proposal-payload - Print the CreateServiceNervousSystem payload deploy-sns would submit [--config <file>] [--owner <principal|name>] [--json] [--output <file>]"
+                );
+                eprintln!(
+                    "  init-nns            - Start the local replica if needed, install the NNS and record its canister IDs"
+                );
+                eprintln!("  add-hotkey          - Add a hotkey to an SNS or ICP neuron");
+                eprintln!(
+                    "  list-sns-neurons    - List SNS neurons for a principal [--watch] [--sort] [--filter]"
+                );
+                eprintln!("  get-sns-neuron      - Show any SNS neuron by its hex ID");
+                eprintln!(
+                    "  list-icp-neurons    - List ICP neurons for a principal [--sort] [--filter]"
+                );
+                eprintln!(
+                    "  list-all-neurons <sns|icp> - List the neurons of the owner and every participant"
+                );
+                eprintln!(
+                    "  mint-sns-tokens     - Create proposal to mint SNS tokens and vote [--wait]"
+                );
+                eprintln!(
+                    "  add-generic-function <spec_file> - Propose a custom proposal type and vote [--proposer <principal>] [--wait]"
+                );
+                eprintln!(
+                    "  execute-generic-function <function_id> --payload-file <file> - Propose executing a custom function and vote [--proposer <principal>] [--wait]"
+                );
+                eprintln!(
+                    "  extensions <register|deposit|withdraw|list> - Register and operate treasury-manager extensions"
+                );
+                eprintln!(
+                    "  upgrade-sns-to-next-version - Propose upgrading the SNS one step and follow the upgrade journal [--proposer <principal>]"
+                );
+                eprintln!(
+                    "  advance-sns-target-version  - Propose advancing the SNS to the latest version and follow the upgrade journal [--proposer <principal>]"
+                );
+                eprintln!("  create-sns-neuron        - Create an SNS neuron by staking tokens");
+                eprintln!(
+                    "  refresh-sns-neuron       - Add tokens to an existing SNS neuron and refresh its stake"
+                );
+                eprintln!(
+                    "  claim-sns-neuron --memo <n> --controller <principal> - Claim a neuron staked by an external transfer"
+                );
+                eprintln!(
+                    "  resolve-neuron --memo <n> --controller <principal> - Show the neuron ID and staking account of a memo"
+                );
+                eprintln!(
+                    "  recover-stakes [principal] [--refund] - Claim, refresh or refund SNS stakes governance has not counted"
+                );
+                eprintln!(
+                    "  disburse-sns-neuron      - Disburse an SNS neuron to a receiver principal"
+                );
+                eprintln!(
+                    "  disburse-icp-neuron      - Disburse an ICP neuron to a receiver principal"
+                );
+                eprintln!(
+                    "  spawn-icp-neuron         - Spawn an ICP neuron's maturity into a new neuron [--percentage <1-100>] [--controller <principal>]"
+                );
+                eprintln!(
+                    "  merge-icp-neurons <source> <target> - Merge two ICP neurons of the same controller [--principal <principal>]"
+                );
+                eprintln!(
+                    "  increase-sns-dissolve-delay - Increase dissolve delay for an SNS neuron"
+                );
+                eprintln!(
+                    "  increase-icp-dissolve-delay - Increase dissolve delay for an ICP neuron"
+                );
+                eprintln!("  manage-sns-dissolving    - Start or stop dissolving an SNS neuron");
+                eprintln!("  manage-icp-dissolving    - Start or stop dissolving an ICP neuron");
+                eprintln!("  set-icp-visibility       - Set ICP neuron visibility");
+                eprintln!(
+                    "  get-icp-neuron           - Get ICP neuron information [neuron_id] [principal]"
+                );
+                eprintln!("  get-icp-balance          - Get ICP ledger balance for an account");
+                eprintln!("  get-sns-balance          - Get SNS ledger balance for an account");
+                eprintln!(
+                    "  balances                 - Show ICP and SNS balances of the owner and participants [--json]"
+                );
+                eprintln!(
+                    "  account-id [principal]   - Show ICP account IDs [--subaccount <hex>] [--memo <n>] [--swap]"
+                );
+                eprintln!("  mint-icp                 - Mint ICP tokens from minting account");
+                eprintln!(
+                    "  transfer                 - Transfer ICP or SNS tokens between accounts <icp|sns> <from> <to> [--from-subaccount <hex>] [--to-subaccount <hex>]"
+                );
+                eprintln!("  create-icp-neuron        - Create an ICP neuron by staking ICP");
+                eprintln!(
+                    "  make-nns-motion [title] --text <text> - Make an NNS motion proposal with the ICP neuron [--url <url>]"
+                );
+                eprintln!(
+                    "  vote-nns-proposal <proposal_id> <yes|no> - Vote on an NNS proposal with the ICP neuron"
+                );
+                eprintln!(
+                    "  refresh-icp-neuron       - Add ICP to an existing ICP neuron and refresh its stake"
+                );
+                eprintln!(
+                    "  ledger-history           - Show recent ICP or SNS ledger transactions [--principal <p>]"
+                );
+                eprintln!(
+                    "  get-account-transactions - Show an account's transactions from the SNS index [--limit <n>]"
+                );
+                eprintln!(
+                    "  token-info               - Show a ledger's name, symbol, decimals, fee, total supply, minting account and metadata <icp|sns> [--json]"
+                );
+                eprintln!(
+                    "  trace                    - Follow a principal's tokens through ledgers, neurons and mint proposals <principal|name> [--limit <n>] [--json]"
+                );
+                eprintln!(
+                    "  check-principal          - Show which operations a principal (e.g. an Internet Identity delegation) can perform <principal|name> [--json]"
+                );
+                eprintln!(
+                    "  identity <new|list|show|import|add-delegated|keys|add-key|forget-key> - Manage extra test identities, wallet keys, delegated principals and registered key files"
+                );
+                eprintln!(
+                    "  alias [list|set|remove] - Name principals, usable wherever a principal is expected"
+                );
+                eprintln!(
+                    "  set-sns-neuron-permissions - Set the exact permissions of a principal on an SNS neuron"
+                );
+                eprintln!(
+                    "  check-sns-deployed       - Show the SNS canisters; exit code 0 if deployed [--json]"
+                );
+                eprintln!(
+                    "  list-deployed-snses      - List SNSes on SNS-W and verify the deployment data"
+                );
+                eprintln!(
+                    "  import-sns --root <canister_id> - Record an SNS deployed outside this tool in the deployment data [--force]"
+                );
+                eprintln!(
+                    "  verify-deployment        - Check the deployment data against the replica [--repair] [--json]"
+                );
+                eprintln!(
+                    "  verify-distribution      - Check balances and neuron baskets against the SNS config's token distribution [--config <file>] [--json]"
+                );
+                eprintln!(
+                    "  neuron-ladder            - Group SNS neurons by the dissolve delay rungs of the swap's neuron baskets [--json]"
+                );
+                eprintln!(
+                    "  sns-canister-status      - Show status, cycles, memory and module hash of SNS canisters"
+                );
+                eprintln!(
+                    "  top-up-cycles            - Add cycles to SNS canisters from the owner's cycles ledger account [all|<role>|<canister_id>] [cycles] [--provisional]"
+                );
+                eprintln!(
+                    "  get-swap-state           - Show lifecycle, participation and timing of the swap [--watch]"
+                );
+                eprintln!(
+                    "  get-sns-metrics          - Show SNS governance metrics [--json] [--window <seconds>]"
+                );
+                eprintln!(
+                    "  metrics <serve|write <path>> - Export SNS metrics, swap state and balances for Prometheus [--port <n>] [--interval <s>]"
+                );
+                eprintln!(
+                    "  list-sns-proposals       - List SNS proposals with status and tally [--limit <n>] [--watch]"
+                );
+                eprintln!(
+                    "  show-ballots <proposal_id> - Show how each neuron voted on an SNS proposal"
+                );
+                eprintln!(
+                    "  advance-time <duration>  - Move the replica clock forward (PocketIC), e.g. 7d"
+                );
+                eprintln!(
+                    "  run-scenario <file>      - Run a scenario of operations and assertions"
+                );
+                eprintln!(
+                    "  seed <file>              - Create holders, balances, neurons and following for demos"
+                );
+                eprintln!(
+                    "  bench <proposals|votes|transfers> - Load test governance and the ledger, reporting latency percentiles"
+                );
+                eprintln!(
+                    "  export                   - Write SNS canister IDs to .env and canister_ids.json"
+                );
+                eprintln!(
+                    "  export <neurons <sns|icp>|balances> --format csv - Export neurons or balances as CSV [--output <file>]"
+                );
+                eprintln!(
+                    "  config <list|get|set|unset|path> - Defaults for global flags in ~/.config/local-sns/config.toml"
+                );
+                eprintln!(
+                    "  data-path                - Print the path of the deployment data file"
+                );
+                eprintln!(
+                    "  print-ids [name]         - Print principals, neuron and canister IDs in full"
+                );
+                eprintln!(
+                    "  history                  - Show the state-changing calls made by the tool [--limit <n>] [--all] [--json]"
+                );
+                eprintln!(
+                    "  undo [entry] [--execute] - List reversible actions, or show and run the compensating call of one"
+                );
+                eprintln!(
+                    "  finalize-swap            - Finalize the swap [--watch] [--interval <seconds>]"
+                );
+                eprintln!(
+                    "  abort-swap               - Finalize an aborted swap and verify refunds [--watch]"
+                );
+                eprintln!(
+                    "\nAmounts: pass --amount <tokens> (e.g. 1.5) or --amount-e8s <n> instead of amount_e8s"
+                );
+                eprintln!(
+                    "Disbursing, large mints and starting to dissolve ask for confirmation; pass --yes to skip"
+                );
+                eprintln!(
+                    "Tables fit the terminal; pass --wide to keep rows on one line, --no-truncate for full IDs"
+                );
+                eprintln!(
+                    "Pass --copy to copy the selected neuron ID or principal (or print-ids output) to the clipboard"
+                );
+                eprintln!(
+                    "Pass --qr to show a payment link and QR code for receiving accounts (account-id, transfer)"
+                );
+                eprintln!(
+                    "Name a dfx identity as dfx:<name> wherever a principal is expected (e.g. transfer icp dfx:alice dfx:bob max)"
+                );
+                eprintln!(
+                    "Pass --sns-root-canister <id> (or --sns-<governance|ledger|swap|index>-canister) to use an SNS this tool did not deploy;"
+                );
+                eprintln!(
+                    "  they carry an sns- prefix because --governance-canister and --ledger-canister already name the NNS canisters"
+                );
+                eprintln!(
+                    "Pass --debug-candid to print the textual candid of every request and reply on stderr"
+                );
+                eprintln!(
+                    "Pass --record <file> to save every canister call, --replay <file> to answer calls from it offline"
+                );
+                eprintln!(
+                    "Exit codes: 0 success, 2 invalid input, 3 canister error, 4 network error, 5 cancelled; --error-format json prints errors as JSON"
+                );
+                return Err(invalid("Unknown command"));
+            }
+        };
+
+        finish(result)
+    } else {
+        // Default behavior: deploy SNS with the built-in config if no arguments
+        finish(deploy_sns(SnsConfig::default(), false, false).await)
+    }
+}
+
+/// Apply the global flags, each falling back to its environment variable and the config file
+async fn apply_global_flags(args: &mut Vec<String>) -> Result<()> {
+    apply_error_format_flag(args)?;
+    apply_logging_flags(args)?;
+    if let Some(path) = take_flag(args, "--data-file")?.or_else(|| config_value("data_file")) {
+        set_data_file(path.into())?;
+    }
+    if let Some(name) = take_flag(args, "--identity")?.or_else(|| config_value("identity")) {
+        set_dfx_identity(name)?;
+    }
+    if let Some(path) = take_flag(args, "--key-file")? {
+        set_key_file(path.into())?;
+    }
+    let backend_url = apply_backend_flag(args).await?;
+    apply_network_flags(args, backend_url)?;
+    if take_switch(args, "--dry-run") {
+        set_dry_run(true);
+        print_info("Dry run: update calls are printed, not submitted");
+    }
+    set_debug_candid(take_switch(args, "--debug-candid"));
+    apply_recording_flags(args)?;
+    apply_sns_canister_flags(args).await?;
+    let yes_default = config_value("yes")
+        .map(|yes| parse_bool(&yes))
+        .transpose()?
+        .unwrap_or(false);
+    if take_switch(args, "--yes") | take_switch(args, "-y") || yes_default {
+        set_assume_yes(true);
+    }
+    set_wide(take_switch(args, "--wide"));
+    set_no_truncate(take_switch(args, "--no-truncate"));
+    set_copy(take_switch(args, "--copy"));
+    set_qr(take_switch(args, "--qr"));
+    if let Some(hotkey) = take_flag(args, "--as-hotkey")? {
+        let hotkey = resolve_principal(&hotkey)
+            .with_context(|| format!("Failed to resolve --as-hotkey '{hotkey}'"))?;
+        set_acting_hotkey(hotkey)?;
+        print_info(&format!("Acting as hotkey {hotkey} for neuron commands"));
+    }
+    Ok(())
+}
+
+/// Map a command result to the process result
+/// Navigation errors and the dry-run stop exit gracefully
+fn finish(result: Result<()>) -> Result<()> {
+    match result {
+        Ok(()) => Ok(()),
+        Err(e) if is_navigation_error(&e) => Ok(()),
+        Err(e) => match dry_run_stop(&e) {
+            Some(stop) => {
+                print_info(&format!(
+                    "{stop}. Later steps depend on its result and were skipped."
+                ));
+                Ok(())
+            }
+            None => Err(explain_hotkey_error(e)),
+        },
+    }
+}
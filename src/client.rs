@@ -0,0 +1,739 @@
+// High-level programmatic client
+
+use anyhow::Result;
+use candid::Principal;
+
+use crate::core::declarations::{icp_governance, sns_governance, sns_wasm};
+use crate::core::ops::canister_call;
+use crate::core::ops::{
+    audit_log, bench_ops, deployment, extension_ops, generic_function_ops, governance_ops,
+    icp_index_ops, identity, ledger_ops, metrics_export, nns_bootstrap_ops, nns_proposal_ops,
+    scenario_ops, seed_ops, sns_governance_ops, sns_index_ops, sns_root_ops, sns_upgrade_ops,
+    snsw_ops, swap_ops, time_ops, undo_ops, verify_ops,
+};
+use crate::core::utils::backend;
+use crate::core::utils::data_output::{self, IdentityData, SnsCreationData};
+use crate::core::utils::network::{NetworkConfig, set_network_config};
+use crate::init::sns_config::SnsConfig;
+
+/// Client for a local SNS deployment
+///
+/// Wraps the same operations the CLI commands use, without any interactive prompts.
+/// Methods that act on the deployed SNS read canister IDs and participant identities
+/// from the deployment data written by [`LocalSns::deploy_sns`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LocalSns;
+
+impl LocalSns {
+    /// Client for the default network (local dfx, or overrides from the environment)
+    #[must_use]
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Client for a specific network
+    /// The network config applies to the whole process and can only be set once
+    pub fn with_network(network: NetworkConfig) -> Result<Self> {
+        set_network_config(network)?;
+        Ok(Self)
+    }
+
+    /// Client for a PocketIC instance with the NNS and SNS canisters, started if needed
+    /// Needs no dfx replica, so tests can run hermetically (see `--backend pocket-ic`)
+    pub async fn with_pocket_ic_backend() -> Result<Self> {
+        let mut network = NetworkConfig::load(None)?;
+        network.replica_url = backend::start_pocket_ic_backend().await?;
+        network.fetch_root_key = true;
+        Self::with_network(network)
+    }
+
+    /// Keep the deployment data in `path` instead of the per-project data directory
+    /// Applies to the whole process and must be set before the data is first used (see `--data-file`)
+    pub fn with_data_file(self, path: impl Into<std::path::PathBuf>) -> Result<Self> {
+        data_output::set_data_file(path.into())?;
+        Ok(self)
+    }
+
+    /// Start the local replica if needed, install the NNS with dfx and record its canister IDs
+    pub async fn init_nns(&self) -> Result<nns_bootstrap_ops::NnsInstallRecord> {
+        nns_bootstrap_ops::init_nns().await
+    }
+
+    /// Deploy a new SNS and return the resulting deployment data
+    /// Resumes from the checkpoint of an interrupted deployment if there is one
+    pub async fn deploy_sns(&self, config: SnsConfig) -> Result<SnsCreationData> {
+        deployment::deploy_sns(config, false, false).await?;
+        data_output::read_data()
+    }
+
+    /// Deploy a new SNS whose swap is left to abort (too few participants)
+    /// Use [`Self::abort_swap`] once it is due to finalize it and verify the refunds
+    pub async fn deploy_sns_with_aborting_swap(
+        &self,
+        config: SnsConfig,
+    ) -> Result<SnsCreationData> {
+        deployment::deploy_sns(config, false, true).await?;
+        data_output::read_data()
+    }
+
+    /// Deployment data of the current SNS
+    pub fn deployment_data(&self) -> Result<SnsCreationData> {
+        data_output::read_data()
+    }
+
+    /// Write the SNS canister IDs into a `.env` file (see `export`)
+    /// Returns the variables written
+    pub fn export_env(&self, path: &std::path::Path) -> Result<Vec<String>> {
+        data_output::export_env(&data_output::read_data()?, path)
+    }
+
+    /// Merge the SNS canister IDs into a dfx `canister_ids.json` for `network` (see `export`)
+    /// Returns the canister names written
+    pub fn export_canister_ids(
+        &self,
+        path: &std::path::Path,
+        network: &str,
+    ) -> Result<Vec<String>> {
+        data_output::export_canister_ids(&data_output::read_data()?, path, network)
+    }
+
+    /// Check whether the SNS from the deployment data is deployed
+    pub async fn is_sns_deployed(&self) -> Result<bool> {
+        snsw_ops::check_sns_deployed_default_path().await
+    }
+
+    /// Every SNS deployed on the SNS-W canister
+    pub async fn deployed_snses(&self) -> Result<Vec<sns_wasm::DeployedSns>> {
+        snsw_ops::list_deployed_snses_default_path().await
+    }
+
+    /// Check every field of the deployment data against the replica (see `verify-deployment`)
+    /// With `repair`, canister IDs and principals that can be recovered are fixed in the file
+    pub async fn verify_deployment(&self, repair: bool) -> Result<Vec<verify_ops::Check>> {
+        verify_ops::verify_deployment(repair).await
+    }
+
+    /// ICP and SNS balances and staked amounts of the owner and every participant
+    pub async fn balances(&self) -> Result<Vec<ledger_ops::AccountBalances>> {
+        ledger_ops::get_balance_overview().await
+    }
+
+    /// Latest transactions of an SNS account (newest first) and its balance, from the SNS index
+    pub async fn account_transactions(
+        &self,
+        principal: Principal,
+        subaccount: Option<Vec<u8>>,
+        max_results: u64,
+    ) -> Result<ledger_ops::AccountTransactions> {
+        sns_index_ops::get_account_transactions_default_path(principal, subaccount, max_results)
+            .await
+    }
+
+    /// Latest ICP transactions of an account (newest first) and its balance, from the ICP index
+    pub async fn icp_account_transactions(
+        &self,
+        principal: Principal,
+        subaccount: Option<[u8; 32]>,
+        max_results: u64,
+    ) -> Result<ledger_ops::AccountTransactions> {
+        icp_index_ops::get_icp_account_transactions_default_path(principal, subaccount, max_results)
+            .await
+    }
+
+    /// SNS canisters from the deployment data with their module hashes and cycle balances
+    /// Cycle balances come from SNS root and are left unset if it could not be asked
+    pub async fn sns_canisters(&self) -> Result<Vec<sns_root_ops::SnsCanisterInfo>> {
+        let (canisters, _root_error) = sns_root_ops::get_sns_canister_info_default_path().await?;
+        Ok(canisters)
+    }
+
+    /// Status, cycles, memory and module hash of every canister SNS root controls
+    pub async fn sns_canister_statuses(&self) -> Result<Vec<sns_root_ops::SnsCanisterStatus>> {
+        sns_root_ops::get_sns_canister_statuses_default_path().await
+    }
+
+    /// Add cycles to canisters from the owner's cycles ledger account, or minted by the
+    /// replica with [`sns_root_ops::CyclesSource::Provisional`] (see `top-up-cycles`)
+    pub async fn top_up_cycles(
+        &self,
+        canisters: &[Principal],
+        amount: u128,
+        source: sns_root_ops::CyclesSource,
+    ) -> Result<()> {
+        sns_root_ops::top_up_cycles_default_path(canisters, amount, source).await
+    }
+
+    /// Governance metrics of the SNS: stakes, dissolve buckets, treasury and voting power
+    /// Recent proposal counts cover the last `time_window_seconds`
+    pub async fn sns_metrics(
+        &self,
+        time_window_seconds: u64,
+    ) -> Result<sns_governance_ops::SnsMetrics> {
+        sns_governance_ops::get_sns_metrics_default_path(time_window_seconds).await
+    }
+
+    /// Governance metrics, swap state and balances in Prometheus text format
+    pub async fn prometheus_metrics(&self, time_window_seconds: u64) -> String {
+        metrics_export::scrape_default_path(time_window_seconds)
+            .await
+            .to_prometheus()
+    }
+
+    /// How each neuron voted on a proposal, with the tally and adoption thresholds
+    pub async fn proposal_ballots(
+        &self,
+        proposal_id: u64,
+    ) -> Result<sns_governance_ops::ProposalBallots> {
+        sns_governance_ops::get_proposal_ballots_default_path(proposal_id).await
+    }
+
+    /// Lifecycle, participation and timing of the swap
+    pub async fn swap_state(&self) -> Result<swap_ops::SwapState> {
+        swap_ops::get_swap_state_default_path().await
+    }
+
+    /// Finalize the swap, returning its final lifecycle (3 Committed, 4 Aborted)
+    /// With `watch`, waits for the swap to commit or abort first, polling every `interval`
+    pub async fn finalize_swap(&self, watch: bool, interval: std::time::Duration) -> Result<i32> {
+        swap_ops::finalize_swap_default_path(watch, interval).await
+    }
+
+    /// Finalize an aborted swap and refund any ICP left in the participants' swap subaccounts
+    /// With `watch`, waits for the swap to abort first, polling every `interval`
+    pub async fn abort_swap(
+        &self,
+        watch: bool,
+        interval: std::time::Duration,
+    ) -> Result<Vec<swap_ops::ParticipantRefund>> {
+        swap_ops::abort_swap_default_path(watch, interval).await
+    }
+
+    /// Move the replica clock forward by `seconds` (the local network must run on PocketIC)
+    pub async fn advance_time(&self, seconds: u64) -> Result<time_ops::TimeAdvance> {
+        time_ops::advance_time(seconds).await
+    }
+
+    /// Run a scenario (see `run-scenario`), returning the number of steps run
+    /// Stops at the first failing step or assertion
+    pub async fn run_scenario(&self, scenario: &scenario_ops::Scenario) -> Result<usize> {
+        scenario_ops::run_scenario(scenario).await
+    }
+
+    /// Fill the SNS with holders, balances, neurons and following (see `seed`)
+    pub async fn seed(&self, spec: &seed_ops::SeedSpec) -> Result<seed_ops::SeedSummary> {
+        seed_ops::run_seed(spec).await
+    }
+
+    /// Append the update calls of this process to the audit log under `command` (see `history`)
+    pub fn enable_audit_log(&self, command: &str) {
+        audit_log::enable_audit_log(command);
+    }
+
+    /// The actions recorded in the audit log, oldest first
+    pub fn history(&self) -> Result<Vec<audit_log::AuditEntry>> {
+        audit_log::read_audit_log()
+    }
+
+    /// Undos of the reversible actions in the audit log that were not undone yet (see `undo`)
+    pub fn undo_suggestions(&self) -> Result<Vec<undo_ops::Undo>> {
+        Ok(undo_ops::undo_suggestions(&audit_log::read_audit_log()?))
+    }
+
+    /// Make the compensating call of an undo
+    pub async fn execute_undo(&self, undo: &undo_ops::Undo) -> Result<()> {
+        undo_ops::execute_undo(undo).await
+    }
+
+    /// Load test governance or the SNS ledger (see `bench`), returning latencies and errors
+    pub async fn bench(&self, options: &bench_ops::BenchOptions) -> Result<bench_ops::BenchReport> {
+        bench_ops::run_bench(options).await
+    }
+
+    /// Sign neuron commands with a hotkey's identity instead of the controller's
+    /// Applies to the whole process and can only be set once (see `--as-hotkey`)
+    pub fn act_as_hotkey(&self, hotkey: Principal) -> Result<()> {
+        identity::set_acting_hotkey(hotkey)
+    }
+
+    /// Print update calls instead of submitting them (see `--dry-run`)
+    /// Applies to the whole process. Methods that change state stop at their first update
+    /// call with a [`canister_call::DryRunStop`] error, since later steps depend on its reply.
+    pub fn set_dry_run(&self, enabled: bool) {
+        canister_call::set_dry_run(enabled);
+    }
+
+    /// Create an extra seed-file identity (see `identity new`)
+    /// Without a name, the next free `identity-N` is used
+    pub fn create_identity(&self, name: Option<&str>) -> Result<IdentityData> {
+        identity::create_named_identity(name)
+    }
+
+    /// Extra identities recorded in the deployment data
+    pub fn identities(&self) -> Result<Vec<IdentityData>> {
+        Ok(data_output::read_data()?.identities)
+    }
+
+    /// Name a principal, so CLI commands accept the name in its place (see `alias set`)
+    pub fn set_alias(&self, name: &str, principal: Principal) -> Result<data_output::AliasData> {
+        identity::set_alias(name, principal)
+    }
+
+    // ------------------------------------------------------------------------
+    // SNS
+    // ------------------------------------------------------------------------
+
+    /// List SNS neurons of a principal
+    pub async fn list_sns_neurons(
+        &self,
+        principal: Principal,
+    ) -> Result<Vec<sns_governance::Neuron>> {
+        sns_governance_ops::list_neurons_for_principal_default_path(principal).await
+    }
+
+    /// Get any SNS neuron by its hex ID (or a unique prefix of it)
+    pub async fn get_sns_neuron(&self, neuron_id: &str) -> Result<sns_governance::Neuron> {
+        sns_governance_ops::get_sns_neuron_default_path(neuron_id).await
+    }
+
+    /// SNS neurons of the owner and every participant as (label, principal, neurons)
+    pub async fn list_all_sns_neurons(
+        &self,
+    ) -> Result<Vec<(String, Principal, Vec<sns_governance::Neuron>)>> {
+        sns_governance_ops::list_all_neurons_default_path().await
+    }
+
+    /// Create an SNS neuron by staking tokens, returning the neuron ID (subaccount)
+    /// Defaults to the neuron minimum stake, the next free memo and no dissolve delay
+    pub async fn create_sns_neuron(
+        &self,
+        principal: Principal,
+        amount_e8s: u64,
+        memo: Option<u64>,
+        dissolve_delay_seconds: Option<u64>,
+    ) -> Result<Vec<u8>> {
+        sns_governance_ops::create_sns_neuron_default_path(
+            principal,
+            Some(amount_e8s),
+            memo,
+            dissolve_delay_seconds,
+            None,
+        )
+        .await
+    }
+
+    /// Add tokens to an existing SNS neuron of `principal`, returning the new stake
+    pub async fn refresh_sns_neuron(
+        &self,
+        principal: Principal,
+        neuron_id: Vec<u8>,
+        amount_e8s: u64,
+    ) -> Result<u64> {
+        sns_governance_ops::refresh_sns_neuron_default_path(principal, neuron_id, amount_e8s, None)
+            .await
+    }
+
+    /// Claim an SNS neuron funded by a transfer to the governance subaccount of `memo` and `controller`
+    /// Returns the neuron ID
+    pub async fn claim_sns_neuron(&self, controller: Principal, memo: u64) -> Result<Vec<u8>> {
+        sns_governance_ops::claim_sns_neuron_default_path(controller, memo).await
+    }
+
+    /// Derive the SNS neuron ID of `memo` and `controller` and check its staking account
+    pub async fn resolve_sns_neuron(
+        &self,
+        controller: Principal,
+        memo: u64,
+    ) -> Result<sns_governance_ops::NeuronResolution> {
+        sns_governance_ops::resolve_sns_neuron_default_path(controller, memo).await
+    }
+
+    /// Mint SNS tokens via a governance proposal that all participants vote for
+    /// Returns the proposal ID
+    pub async fn mint_sns_tokens(
+        &self,
+        proposer_principal: Principal,
+        receiver_principal: Principal,
+        amount_e8s: u64,
+    ) -> Result<u64> {
+        sns_governance_ops::mint_sns_tokens_with_all_votes_default_path(
+            proposer_principal,
+            receiver_principal,
+            amount_e8s,
+        )
+        .await
+    }
+
+    /// Propose a custom proposal type from a spec that all participants vote for
+    /// Returns the proposal ID
+    pub async fn add_generic_function(
+        &self,
+        proposer_principal: Principal,
+        spec: &generic_function_ops::GenericFunctionSpec,
+    ) -> Result<u64> {
+        generic_function_ops::add_generic_function_default_path(proposer_principal, spec).await
+    }
+
+    /// Propose executing a custom function with a Candid payload that all participants vote for
+    /// Returns the proposal ID
+    pub async fn execute_generic_function(
+        &self,
+        proposer_principal: Principal,
+        function_id: u64,
+        payload: Vec<u8>,
+    ) -> Result<u64> {
+        generic_function_ops::execute_generic_function_default_path(
+            proposer_principal,
+            function_id,
+            payload,
+        )
+        .await
+    }
+
+    /// Propose registering an extension whose wasm is uploaded to `store_canister`'s chunk store
+    /// Returns the proposal ID
+    pub async fn register_extension(
+        &self,
+        proposer_principal: Principal,
+        wasm: &[u8],
+        store_canister: Principal,
+        init: sns_governance::PreciseValue,
+    ) -> Result<u64> {
+        extension_ops::register_extension_default_path(
+            proposer_principal,
+            wasm,
+            store_canister,
+            init,
+        )
+        .await
+    }
+
+    /// Propose running an operation (e.g. `deposit`, `withdraw`) of a registered extension
+    /// Returns the proposal ID
+    pub async fn execute_extension_operation(
+        &self,
+        proposer_principal: Principal,
+        extension_canister: Principal,
+        operation_name: &str,
+        arg: Option<sns_governance::PreciseValue>,
+    ) -> Result<u64> {
+        extension_ops::execute_extension_operation_default_path(
+            proposer_principal,
+            extension_canister,
+            operation_name,
+            arg,
+        )
+        .await
+    }
+
+    /// Operations of every extension registered with the SNS
+    pub async fn extension_operations(
+        &self,
+    ) -> Result<Vec<extension_ops::RegisteredExtensionOperation>> {
+        extension_ops::list_extension_operations_default_path().await
+    }
+
+    /// Propose upgrading the SNS one step along the SNS-W upgrade path
+    /// Returns the proposal ID
+    pub async fn upgrade_sns_to_next_version(&self, proposer_principal: Principal) -> Result<u64> {
+        sns_upgrade_ops::upgrade_sns_to_next_version_default_path(proposer_principal).await
+    }
+
+    /// Propose advancing the SNS target version to the latest version SNS-W offers
+    /// Returns the proposal ID
+    pub async fn advance_sns_target_version(&self, proposer_principal: Principal) -> Result<u64> {
+        sns_upgrade_ops::advance_sns_target_version_default_path(proposer_principal).await
+    }
+
+    /// Upgrade journal of the SNS from `offset` on (the latest entries if `None`)
+    pub async fn upgrade_journal(
+        &self,
+        offset: Option<u64>,
+    ) -> Result<sns_governance::GetUpgradeJournalResponse> {
+        sns_upgrade_ops::get_upgrade_journal_default_path(offset).await
+    }
+
+    /// Wait for an upgrade proposal to bring the SNS to its target version, passing journal
+    /// entries after `journal_offset` to `on_entry` (gives up after ten minutes)
+    /// Returns the deployed version
+    pub async fn wait_for_sns_upgrade(
+        &self,
+        proposal_id: u64,
+        journal_offset: u64,
+        on_entry: impl FnMut(&sns_governance::UpgradeJournalEntry),
+    ) -> Result<sns_governance::Version> {
+        sns_upgrade_ops::wait_for_sns_upgrade_default_path(proposal_id, journal_offset, on_entry)
+            .await
+    }
+
+    /// Wait for an SNS proposal to execute, fail or be rejected (gives up after five minutes)
+    pub async fn wait_for_proposal(
+        &self,
+        proposal_id: u64,
+    ) -> Result<sns_governance_ops::ProposalOutcome> {
+        sns_governance_ops::wait_for_proposal_outcome_default_path(proposal_id).await
+    }
+
+    /// Add a hotkey to an SNS neuron (defaults: neuron with the longest dissolve delay, voting permissions)
+    pub async fn add_sns_hotkey(
+        &self,
+        principal: Principal,
+        hotkey_principal: Principal,
+        permissions: Option<Vec<i32>>,
+        neuron_id: Option<Vec<u8>>,
+    ) -> Result<()> {
+        sns_governance_ops::add_hotkey_to_participant_neuron_default_path(
+            principal,
+            hotkey_principal,
+            permissions,
+            neuron_id,
+        )
+        .await
+    }
+
+    /// Set the exact permission set of a principal on an SNS neuron
+    /// Missing permissions are added and extra ones removed
+    pub async fn set_sns_neuron_permissions(
+        &self,
+        controller: Principal,
+        neuron_id: Vec<u8>,
+        principal: Principal,
+        permissions: Vec<i32>,
+    ) -> Result<sns_governance_ops::PermissionChange> {
+        sns_governance_ops::set_neuron_permissions_default_path(
+            controller,
+            neuron_id,
+            principal,
+            permissions,
+        )
+        .await
+    }
+
+    /// Disburse an SNS neuron to a receiver, returning the transfer block height
+    pub async fn disburse_sns_neuron(
+        &self,
+        principal: Principal,
+        receiver_principal: Principal,
+        neuron_id: Option<Vec<u8>>,
+    ) -> Result<u64> {
+        sns_governance_ops::disburse_participant_neuron_default_path(
+            principal,
+            receiver_principal,
+            neuron_id,
+        )
+        .await
+    }
+
+    /// Increase the dissolve delay of an SNS neuron
+    pub async fn increase_sns_dissolve_delay(
+        &self,
+        principal: Principal,
+        additional_dissolve_delay_seconds: u64,
+        neuron_id: Option<Vec<u8>>,
+    ) -> Result<()> {
+        sns_governance_ops::increase_dissolve_delay_participant_neuron_default_path(
+            principal,
+            additional_dissolve_delay_seconds,
+            neuron_id,
+        )
+        .await
+    }
+
+    /// Start or stop dissolving an SNS neuron
+    pub async fn set_sns_dissolving(
+        &self,
+        principal: Principal,
+        start_dissolving: bool,
+        neuron_id: Option<Vec<u8>>,
+    ) -> Result<()> {
+        sns_governance_ops::manage_dissolving_state_participant_neuron_default_path(
+            principal,
+            start_dissolving,
+            neuron_id,
+        )
+        .await
+    }
+
+    // ------------------------------------------------------------------------
+    // ICP
+    // ------------------------------------------------------------------------
+
+    /// Mint ICP from the minting account, returning the block height
+    pub async fn mint_icp(&self, receiver_principal: Principal, amount_e8s: u64) -> Result<u64> {
+        governance_ops::mint_icp_default_path(receiver_principal, amount_e8s, None).await
+    }
+
+    /// Legacy ICP account identifier (hex) of a principal and optional subaccount
+    #[must_use]
+    pub fn icp_account_id(&self, owner: Principal, subaccount: Option<[u8; 32]>) -> String {
+        let subaccount = subaccount.map(ic_ledger_types::Subaccount);
+        ledger_ops::icp_account_id(owner, subaccount.as_ref())
+    }
+
+    /// Subaccount of NNS governance that stakes a neuron of `controller` with `memo`
+    #[must_use]
+    pub fn neuron_staking_subaccount(&self, controller: Principal, memo: u64) -> [u8; 32] {
+        ledger_ops::generate_subaccount_by_nonce(memo, controller).0
+    }
+
+    /// Subaccount of the swap canister that holds the participation of `buyer`
+    #[must_use]
+    pub fn swap_participation_subaccount(&self, buyer: Principal) -> [u8; 32] {
+        swap_ops::generate_participant_subaccount(buyer).0
+    }
+
+    /// List ICP neurons of a principal
+    pub async fn list_icp_neurons(
+        &self,
+        principal: Principal,
+    ) -> Result<Vec<icp_governance::Neuron>> {
+        governance_ops::list_icp_neurons_for_principal_default_path(principal).await
+    }
+
+    /// ICP neurons of the owner and every participant as (label, principal, neurons)
+    pub async fn list_all_icp_neurons(
+        &self,
+    ) -> Result<Vec<(String, Principal, Vec<icp_governance::Neuron>)>> {
+        governance_ops::list_all_icp_neurons_default_path().await
+    }
+
+    /// Get an ICP neuron (defaults to the neuron from the deployment data)
+    pub async fn get_icp_neuron(&self, neuron_id: Option<u64>) -> Result<icp_governance::Neuron> {
+        governance_ops::get_icp_neuron_default_path(neuron_id, None).await
+    }
+
+    /// Get an ICP neuron controlled by a participant or extra identity, signed as them
+    pub async fn get_icp_neuron_of(
+        &self,
+        controller: Principal,
+        neuron_id: u64,
+    ) -> Result<icp_governance::Neuron> {
+        governance_ops::get_icp_neuron_default_path(Some(neuron_id), Some(controller)).await
+    }
+
+    /// Create an ICP neuron by staking ICP, returning the neuron ID
+    pub async fn create_icp_neuron(
+        &self,
+        principal: Principal,
+        amount_e8s: u64,
+        memo: Option<u64>,
+        dissolve_delay_seconds: Option<u64>,
+    ) -> Result<u64> {
+        governance_ops::create_icp_neuron_default_path(
+            principal,
+            amount_e8s,
+            memo,
+            dissolve_delay_seconds,
+            None,
+        )
+        .await
+    }
+
+    /// Add ICP to an existing ICP neuron of `principal`, returning the new stake
+    pub async fn refresh_icp_neuron(
+        &self,
+        principal: Principal,
+        neuron_id: u64,
+        amount_e8s: u64,
+    ) -> Result<u64> {
+        governance_ops::refresh_icp_neuron_default_path(principal, neuron_id, amount_e8s, None)
+            .await
+    }
+
+    /// Make an NNS motion proposal with the ICP neuron from the deployment data
+    /// Returns the proposal ID
+    pub async fn make_nns_motion(&self, title: &str, motion_text: &str, url: &str) -> Result<u64> {
+        nns_proposal_ops::make_motion_proposal_default_path(title, motion_text, url).await
+    }
+
+    /// Vote on an NNS proposal with the ICP neuron from the deployment data
+    pub async fn vote_nns_proposal(&self, proposal_id: u64, yes: bool) -> Result<()> {
+        nns_proposal_ops::vote_on_nns_proposal_default_path(proposal_id, yes).await
+    }
+
+    /// Status and tally of an NNS proposal (None if governance does not know it)
+    pub async fn nns_proposal(
+        &self,
+        proposal_id: u64,
+    ) -> Result<Option<icp_governance::ProposalInfo>> {
+        nns_proposal_ops::get_nns_proposal_default_path(proposal_id).await
+    }
+
+    /// Disburse an ICP neuron to a receiver, returning the transfer block height
+    pub async fn disburse_icp_neuron(
+        &self,
+        principal: Principal,
+        receiver_principal: Principal,
+        neuron_id: Option<u64>,
+        amount_e8s: Option<u64>,
+    ) -> Result<u64> {
+        governance_ops::disburse_icp_neuron_for_principal_default_path(
+            principal,
+            receiver_principal,
+            neuron_id,
+            amount_e8s,
+        )
+        .await
+    }
+
+    /// Spawn a share of an ICP neuron's maturity into a new neuron, returning its ID
+    pub async fn spawn_icp_neuron(
+        &self,
+        principal: Principal,
+        neuron_id: u64,
+        percentage_to_spawn: Option<u32>,
+        new_controller: Option<Principal>,
+    ) -> Result<u64> {
+        governance_ops::spawn_icp_neuron_for_principal_default_path(
+            principal,
+            neuron_id,
+            percentage_to_spawn,
+            new_controller,
+        )
+        .await
+    }
+
+    /// Merge an ICP neuron of `principal` into another of its neurons
+    pub async fn merge_icp_neurons(
+        &self,
+        principal: Principal,
+        source_neuron_id: u64,
+        target_neuron_id: u64,
+    ) -> Result<icp_governance::MergeResponse> {
+        governance_ops::merge_icp_neurons_for_principal_default_path(
+            principal,
+            source_neuron_id,
+            target_neuron_id,
+        )
+        .await
+    }
+
+    /// Increase the dissolve delay of an ICP neuron
+    pub async fn increase_icp_dissolve_delay(
+        &self,
+        principal: Principal,
+        neuron_id: Option<u64>,
+        additional_dissolve_delay_seconds: u64,
+    ) -> Result<()> {
+        governance_ops::increase_icp_dissolve_delay_for_principal_default_path(
+            principal,
+            neuron_id,
+            additional_dissolve_delay_seconds,
+        )
+        .await
+    }
+
+    /// Start or stop dissolving an ICP neuron
+    pub async fn set_icp_dissolving(
+        &self,
+        principal: Principal,
+        neuron_id: Option<u64>,
+        start_dissolving: bool,
+    ) -> Result<()> {
+        governance_ops::manage_icp_dissolving_state_for_principal_default_path(
+            principal,
+            neuron_id,
+            start_dissolving,
+        )
+        .await
+    }
+}
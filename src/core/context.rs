@@ -0,0 +1,192 @@
+// Shared application state: cached deployment data, agents and canister IDs
+
+use anyhow::Result;
+use candid::Principal;
+use ic_agent::{Agent, Identity};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+
+use crate::core::ops::identity::{create_agent, external_owner, identity_for_principal};
+use crate::core::ops::ledger_ops::get_ledger_decimals;
+use crate::core::utils::data_output::{self, DeployedSnsData, SnsCreationData};
+use crate::core::utils::network::{NetworkConfig, network_config};
+
+/// Process-wide state shared by all operations
+///
+/// The deployment data is parsed once and reused until it is rewritten, and agents
+/// are pooled per identity so the root key is only fetched once per principal.
+#[derive(Default)]
+pub struct AppContext {
+    deployment: RwLock<Option<Arc<SnsCreationData>>>,
+    agents: Mutex<HashMap<Principal, Agent>>,
+    /// Decimals of each ledger queried so far; they never change once a ledger is installed
+    decimals: Mutex<HashMap<Principal, u8>>,
+    /// SNS canister IDs from the `--sns-*-canister` flags, used instead of the deployment data's
+    sns_overrides: RwLock<Option<DeployedSnsData>>,
+}
+
+static APP_CONTEXT: OnceLock<AppContext> = OnceLock::new();
+
+/// The shared application context
+pub fn app_context() -> &'static AppContext {
+    APP_CONTEXT.get_or_init(AppContext::default)
+}
+
+impl AppContext {
+    /// Network config in use (replica URL and NNS canister IDs)
+    #[must_use]
+    pub fn network(&self) -> &'static NetworkConfig {
+        network_config()
+    }
+
+    /// Deployment data of the current SNS, read from disk on first use
+    pub fn deployment_data(&self) -> Result<Arc<SnsCreationData>> {
+        if let Some(data) = self.deployment.read().expect("deployment lock").as_ref() {
+            return Ok(Arc::clone(data));
+        }
+
+        let overrides = self.sns_overrides();
+        let data = match overrides {
+            Some(overrides) if !data_output::get_output_path().exists() => {
+                log::info!("No deployment data: using the SNS canisters given on the command line");
+                SnsCreationData::external(overrides, external_owner().to_text())
+            }
+            overrides => {
+                log::info!(
+                    "Loading deployment data from {}",
+                    data_output::get_output_path().display()
+                );
+                let mut data = data_output::read_data()?;
+                if let Some(overrides) = overrides {
+                    data.deployed_sns.apply_overrides(&overrides);
+                }
+                data
+            }
+        };
+        let data = Arc::new(data);
+        *self.deployment.write().expect("deployment lock") = Some(Arc::clone(&data));
+        Ok(data)
+    }
+
+    /// Use these SNS canisters instead of the deployment data's, for an SNS deployed by
+    /// other means (only the IDs set are replaced)
+    pub fn set_sns_overrides(&self, overrides: DeployedSnsData) {
+        *self.sns_overrides.write().expect("sns overrides lock") = Some(overrides);
+        self.invalidate_deployment_data();
+    }
+
+    /// SNS canister IDs given on the command line, if any
+    #[must_use]
+    pub fn sns_overrides(&self) -> Option<DeployedSnsData> {
+        self.sns_overrides
+            .read()
+            .expect("sns overrides lock")
+            .clone()
+    }
+
+    /// Deployment data from a specific file (cached only for the default location)
+    pub fn deployment_data_at(&self, path: &Path) -> Result<Arc<SnsCreationData>> {
+        if path == data_output::get_output_path() {
+            return self.deployment_data();
+        }
+
+        let (data, _migrated) = data_output::read_data_at(path)?;
+        Ok(Arc::new(data))
+    }
+
+    /// Deployment data if it exists and parses, for callers that fall back gracefully
+    #[must_use]
+    pub fn try_deployment_data(&self) -> Option<Arc<SnsCreationData>> {
+        if !data_output::get_output_path().exists() && self.sns_overrides().is_none() {
+            return None;
+        }
+        self.deployment_data().ok()
+    }
+
+    /// Forget the cached deployment data (called whenever it is rewritten)
+    pub fn invalidate_deployment_data(&self) {
+        *self.deployment.write().expect("deployment lock") = None;
+    }
+
+    /// Agent for an identity, reusing a pooled agent for the same principal
+    pub async fn agent(&self, identity: Box<dyn Identity>) -> Result<Agent> {
+        let principal = identity
+            .sender()
+            .map_err(|e| anyhow::anyhow!("Failed to get identity principal: {e}"))?;
+
+        if let Some(agent) = self.agents.lock().expect("agent pool lock").get(&principal) {
+            return Ok(agent.clone());
+        }
+
+        let agent = create_agent(identity).await?;
+        self.agents
+            .lock()
+            .expect("agent pool lock")
+            .insert(principal, agent.clone());
+        Ok(agent)
+    }
+
+    /// Agent signing as `principal` (see `identity::identity_for_principal`)
+    pub async fn agent_for_principal(&self, principal: Principal) -> Result<Agent> {
+        if let Some(agent) = self.agents.lock().expect("agent pool lock").get(&principal) {
+            return Ok(agent.clone());
+        }
+        self.agent(identity_for_principal(principal)?).await
+    }
+
+    /// Anonymous agent for queries
+    pub async fn anonymous_agent(&self) -> Result<Agent> {
+        self.agent(Box::new(ic_agent::identity::AnonymousIdentity))
+            .await
+    }
+
+    /// Decimals of a ledger, queried once per process and then served from the cache
+    pub async fn ledger_decimals(&self, ledger_canister: Principal) -> Result<u8> {
+        if let Some(decimals) = self
+            .decimals
+            .lock()
+            .expect("decimals lock")
+            .get(&ledger_canister)
+        {
+            return Ok(*decimals);
+        }
+        let agent = self.anonymous_agent().await?;
+        let decimals = get_ledger_decimals(&agent, ledger_canister).await?;
+        self.cache_ledger_decimals(ledger_canister, decimals);
+        Ok(decimals)
+    }
+
+    /// Remember the decimals of a ledger read by other means (e.g. `token-info`)
+    pub fn cache_ledger_decimals(&self, ledger_canister: Principal, decimals: u8) {
+        self.decimals
+            .lock()
+            .expect("decimals lock")
+            .insert(ledger_canister, decimals);
+    }
+
+    /// SNS governance canister from `--sns-governance-canister` or the deployment data
+    pub fn sns_governance_canister(&self) -> Result<Principal> {
+        self.deployment_data()?.deployed_sns.governance_canister()
+    }
+
+    /// SNS ledger canister from `--sns-ledger-canister` or the deployment data
+    pub fn sns_ledger_canister(&self) -> Result<Principal> {
+        self.deployment_data()?.deployed_sns.ledger_canister()
+    }
+
+    /// SNS swap canister from `--sns-swap-canister` or the deployment data
+    pub fn sns_swap_canister(&self) -> Result<Principal> {
+        self.deployment_data()?.deployed_sns.swap_canister()
+    }
+
+    /// SNS root canister from `--sns-root-canister` or the deployment data
+    pub fn sns_root_canister(&self) -> Result<Principal> {
+        self.deployment_data()?.deployed_sns.root_canister()
+    }
+
+    /// SNS index canister from `--sns-index-canister` or the deployment data
+    pub fn sns_index_canister(&self) -> Result<Principal> {
+        self.deployment_data()?.deployed_sns.index_canister()
+    }
+}
@@ -1,7 +1,12 @@
 // ICP Governance canister Candid type definitions
 // Generated from Candid, with serde_bytes::ByteBuf replaced with Vec<u8>
 
-#![allow(dead_code, unused_imports, unused_variables)]
+#![allow(
+    dead_code,
+    unused_imports,
+    unused_variables,
+    clippy::large_enum_variant
+)]
 
 use candid::{CandidType, Deserialize, Principal};
 use serde::Serialize;
@@ -723,3 +728,23 @@ pub struct ListNeuronsResponse {
     pub full_neurons: Vec<Neuron>,
     pub total_pages_available: Option<u64>,
 }
+
+/// Subset of `ProposalInfo` returned by `get_proposal_info` (other fields are skipped when decoding)
+#[derive(CandidType, Deserialize)]
+pub struct ProposalInfo {
+    pub id: Option<ProposalId>,
+    pub status: i32,
+    pub latest_tally: Option<Tally>,
+    pub decided_timestamp_seconds: u64,
+    pub executed_timestamp_seconds: u64,
+    pub failed_timestamp_seconds: u64,
+    pub failure_reason: Option<GovernanceError>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct Tally {
+    pub no: u64,
+    pub yes: u64,
+    pub total: u64,
+    pub timestamp_seconds: u64,
+}
@@ -0,0 +1,91 @@
+// ICP Index canister Candid type definitions
+// Only the types used by local_sns
+
+#![allow(dead_code, unused_imports, unused_variables)]
+use candid::{self, CandidType, Decode, Deserialize, Encode, Nat, Principal};
+
+#[derive(CandidType, Deserialize)]
+pub struct GetAccountIdentifierTransactionsArgs {
+    pub max_results: u64,
+    /// Newest transaction id to return (transactions are returned newest first)
+    pub start: Option<u64>,
+    /// Legacy account identifier (hex)
+    pub account_identifier: String,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct Tokens {
+    pub e8s: u64,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct TimeStamp {
+    pub timestamp_nanos: u64,
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum Operation {
+    Approve {
+        fee: Tokens,
+        from: String,
+        allowance: Tokens,
+        expected_allowance: Option<Tokens>,
+        expires_at: Option<TimeStamp>,
+        spender: String,
+    },
+    Burn {
+        from: String,
+        amount: Tokens,
+        spender: Option<String>,
+    },
+    Mint {
+        to: String,
+        amount: Tokens,
+    },
+    Transfer {
+        to: String,
+        fee: Tokens,
+        from: String,
+        amount: Tokens,
+        spender: Option<String>,
+    },
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct Transaction {
+    pub memo: u64,
+    pub icrc1_memo: Option<Vec<u8>>,
+    pub operation: Operation,
+    pub created_at_time: Option<TimeStamp>,
+    pub timestamp: Option<TimeStamp>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct TransactionWithId {
+    pub id: u64,
+    pub transaction: Transaction,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct GetAccountIdentifierTransactionsResponse {
+    pub balance: u64,
+    pub transactions: Vec<TransactionWithId>,
+    /// Id of the account's oldest transaction, if it has any
+    pub oldest_tx_id: Option<u64>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct GetAccountIdentifierTransactionsError {
+    pub message: String,
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum GetAccountIdentifierTransactionsResult {
+    Ok(GetAccountIdentifierTransactionsResponse),
+    Err(GetAccountIdentifierTransactionsError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct Status {
+    pub num_blocks_synced: u64,
+}
@@ -41,6 +41,11 @@ pub enum TransferResult {
     Err(TransferError),
 }
 
+#[derive(CandidType, Deserialize)]
+pub struct AccountBalanceArgs {
+    pub account: Vec<u8>,
+}
+
 #[derive(CandidType, Deserialize, Debug)]
 pub struct Tokens {
     pub e8s: u64,
@@ -77,3 +82,94 @@ pub enum TransferResult1 {
     #[serde(rename = "Err")]
     Err(TransferError1),
 }
+
+#[derive(CandidType, Deserialize)]
+pub struct GetBlocksArgs {
+    pub start: u64,
+    pub length: u64,
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum Operation {
+    Approve {
+        fee: Tokens,
+        from: Vec<u8>,
+        allowance_e8s: candid::Int,
+        allowance: Tokens,
+        expected_allowance: Option<Tokens>,
+        expires_at: Option<TimeStamp>,
+        spender: Vec<u8>,
+    },
+    Burn {
+        from: Vec<u8>,
+        amount: Tokens,
+        spender: Option<Vec<u8>>,
+    },
+    Mint {
+        to: Vec<u8>,
+        amount: Tokens,
+    },
+    Transfer {
+        to: Vec<u8>,
+        fee: Tokens,
+        from: Vec<u8>,
+        amount: Tokens,
+        spender: Option<Vec<u8>>,
+    },
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct Transaction {
+    pub memo: u64,
+    pub icrc1_memo: Option<Vec<u8>>,
+    pub operation: Option<Operation>,
+    pub created_at_time: TimeStamp,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct Block {
+    pub transaction: Transaction,
+    pub timestamp: TimeStamp,
+    pub parent_hash: Option<Vec<u8>>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct BlockRange {
+    pub blocks: Vec<Block>,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum QueryArchiveError {
+    BadFirstBlockIndex {
+        requested_index: u64,
+        first_valid_index: u64,
+    },
+    Other {
+        error_message: String,
+        error_code: u64,
+    },
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum QueryArchiveResult {
+    Ok(BlockRange),
+    Err(QueryArchiveError),
+}
+
+candid::define_function!(pub QueryArchiveFn : (GetBlocksArgs) -> (QueryArchiveResult) query);
+
+#[derive(CandidType, Deserialize)]
+pub struct ArchivedBlocksRange {
+    pub callback: QueryArchiveFn,
+    pub start: u64,
+    pub length: u64,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct QueryBlocksResponse {
+    pub certificate: Option<Vec<u8>>,
+    pub blocks: Vec<Block>,
+    pub chain_length: u64,
+    pub first_block_index: u64,
+    pub archived_blocks: Vec<ArchivedBlocksRange>,
+}
@@ -1,8 +1,11 @@
 // Candid type declarations for various canisters
 
 pub mod icp_governance;
+pub mod icp_index;
 pub mod icp_ledger;
 pub mod sns_governance;
+pub mod sns_index;
 pub mod sns_ledger;
+pub mod sns_root;
 pub mod sns_swap;
 pub mod sns_wasm;
@@ -0,0 +1,45 @@
+// SNS Index canister (ICRC-1 index-ng) Candid type definitions
+// Only the types used by local_sns; transactions share the ledger's Account and Transaction
+
+#![allow(dead_code, unused_imports, unused_variables)]
+use candid::{self, CandidType, Decode, Deserialize, Encode, Nat, Principal};
+
+pub use super::sns_ledger::{Account, Transaction};
+
+#[derive(CandidType, Deserialize)]
+pub struct GetAccountTransactionsArgs {
+    pub account: Account,
+    /// Newest transaction id to return (transactions are returned newest first)
+    pub start: Option<Nat>,
+    pub max_results: Nat,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct TransactionWithId {
+    pub id: Nat,
+    pub transaction: Transaction,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct GetTransactions {
+    pub balance: Nat,
+    pub transactions: Vec<TransactionWithId>,
+    /// Id of the account's oldest transaction, if it has any
+    pub oldest_tx_id: Option<Nat>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct GetTransactionsErr {
+    pub message: String,
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum GetTransactionsResult {
+    Ok(GetTransactions),
+    Err(GetTransactionsErr),
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct Status {
+    pub num_blocks_synced: Nat,
+}
@@ -1,7 +1,13 @@
 // SNS Ledger canister Candid type definitions
 // Generated from Candid, with serde_bytes::ByteBuf replaced with Vec<u8>
 
-#![allow(dead_code, unused_imports, unused_variables)]
+#![allow(
+    dead_code,
+    unused_imports,
+    unused_variables,
+    clippy::use_self,
+    clippy::vec_box
+)]
 
 use candid::{CandidType, Deserialize, Nat, Principal};
 
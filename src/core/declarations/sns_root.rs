@@ -0,0 +1,46 @@
+// SNS Root canister Candid type definitions
+// Only the types used by local_sns; records omit fields we don't decode
+
+#![allow(dead_code, unused_imports, unused_variables)]
+use candid::{self, CandidType, Decode, Deserialize, Encode, Nat, Principal};
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct GetSnsCanistersSummaryRequest {
+    pub update_canister_list: Option<bool>,
+}
+
+#[derive(CandidType, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CanisterStatusType {
+    #[serde(rename = "stopped")]
+    Stopped,
+    #[serde(rename = "stopping")]
+    Stopping,
+    #[serde(rename = "running")]
+    Running,
+}
+
+#[derive(CandidType, Deserialize, Debug, Clone)]
+pub struct CanisterStatusResultV2 {
+    pub status: CanisterStatusType,
+    pub memory_size: Nat,
+    pub cycles: Nat,
+    pub idle_cycles_burned_per_day: Nat,
+    pub module_hash: Option<Vec<u8>>,
+}
+
+#[derive(CandidType, Deserialize, Debug, Clone)]
+pub struct CanisterSummary {
+    pub status: Option<CanisterStatusResultV2>,
+    pub canister_id: Option<Principal>,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct GetSnsCanistersSummaryResponse {
+    pub root: Option<CanisterSummary>,
+    pub swap: Option<CanisterSummary>,
+    pub ledger: Option<CanisterSummary>,
+    pub index: Option<CanisterSummary>,
+    pub governance: Option<CanisterSummary>,
+    pub dapps: Vec<CanisterSummary>,
+    pub archives: Vec<CanisterSummary>,
+}
@@ -88,3 +88,32 @@ pub struct FinalizeSwapResponse {
     // Note: Other fields are complex nested types we don't need to decode
     // The error_message is sufficient for basic error checking
 }
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct ErrorRefundIcpRequest {
+    pub source_principal_id: Option<Principal>,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct Ok1 {
+    pub block_height: Option<u64>,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct Err1 {
+    pub description: Option<String>,
+    pub error_type: Option<i32>,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum Result1 {
+    #[serde(rename = "Ok")]
+    Ok(Ok1),
+    #[serde(rename = "Err")]
+    Err(Err1),
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct ErrorRefundIcpResponse {
+    pub result: Option<Result1>,
+}
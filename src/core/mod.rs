@@ -1,3 +1,4 @@
+pub mod context;
 pub mod declarations;
 pub mod ops;
 pub mod utils;
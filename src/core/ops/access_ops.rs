@@ -0,0 +1,237 @@
+// What a principal can do against the local SNS, read from its balances, SNS neuron
+// permissions and ICP hotkeys. Meant for principals the tool cannot sign for, such as
+// Internet Identity delegations used by dapps
+
+use anyhow::Result;
+use candid::Principal;
+use serde::Serialize;
+
+use super::governance_ops::list_all_icp_neurons_default_path;
+use super::ledger_ops::{get_icp_ledger_balance, get_sns_ledger_balance, get_sns_ledger_fee};
+use super::sns_governance_ops::{
+    SNS_NEURON_PERMISSIONS, get_neuron_minimum_stake, list_neurons_for_principal,
+    neuron_permissions_of, sns_permission_name,
+};
+use super::trace_ops::principal_label;
+use crate::core::context::app_context;
+use crate::core::utils::constants::ICP_TRANSFER_FEE;
+use crate::core::utils::network::network_config;
+
+/// An operation and whether the principal can perform it right now
+#[derive(Debug, Clone, Serialize)]
+pub struct OperationAccess {
+    pub operation: String,
+    pub allowed: bool,
+    pub detail: String,
+}
+
+/// An SNS neuron the principal holds permissions on
+#[derive(Debug, Clone, Serialize)]
+pub struct SnsNeuronAccess {
+    pub neuron_id: String,
+    pub permissions: Vec<String>,
+}
+
+/// An ICP neuron of a known identity that has the principal as a hotkey
+#[derive(Debug, Clone, Serialize)]
+pub struct IcpHotkeyAccess {
+    pub neuron_id: u64,
+    /// `owner` or `participant-N`, followed by any aliases
+    pub controller: String,
+}
+
+/// Everything a principal can do against the local SNS
+#[derive(Debug, Clone, Serialize)]
+pub struct PrincipalAccess {
+    pub principal: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Recorded with `identity add-delegated`, so the tool cannot sign for it
+    pub delegated: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub origin: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icp_balance_e8s: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sns_balance_e8s: Option<u64>,
+    pub sns_neurons: Vec<SnsNeuronAccess>,
+    pub icp_hotkey_neurons: Vec<IcpHotkeyAccess>,
+    pub operations: Vec<OperationAccess>,
+    /// Sources that could not be read; operations depending on them are reported as not allowed
+    pub warnings: Vec<String>,
+}
+
+/// Work out which operations `principal` can perform: token transfers and staking from its
+/// balances, SNS neuron commands from the permissions it holds, and NNS voting from the ICP
+/// neurons of the owner and participants that list it as a hotkey
+///
+/// Only queries are made, and nothing is signed as the principal. Sources that fail are
+/// reported as warnings rather than failing the whole check.
+pub async fn principal_access(principal: Principal) -> Result<PrincipalAccess> {
+    let agent = app_context().anonymous_agent().await?;
+    let data = app_context().try_deployment_data();
+    let delegated = data
+        .as_deref()
+        .and_then(|data| data.delegated_principal(&principal.to_text()).cloned());
+
+    let mut access = PrincipalAccess {
+        principal: principal.to_text(),
+        label: data
+            .as_deref()
+            .and_then(|data| principal_label(data, principal)),
+        delegated: delegated.is_some(),
+        origin: delegated.and_then(|d| d.origin),
+        icp_balance_e8s: None,
+        sns_balance_e8s: None,
+        sns_neurons: Vec::new(),
+        icp_hotkey_neurons: Vec::new(),
+        operations: Vec::new(),
+        warnings: Vec::new(),
+    };
+
+    // ICP
+    match get_icp_ledger_balance(&agent, network_config().ledger_canister, principal, None).await {
+        Ok(balance) => access.icp_balance_e8s = Some(balance),
+        Err(e) => access
+            .warnings
+            .push(format!("ICP balance unavailable: {e:#}")),
+    }
+    access.operations.push(match access.icp_balance_e8s {
+        Some(balance) => OperationAccess {
+            operation: "Transfer ICP".to_string(),
+            allowed: balance > ICP_TRANSFER_FEE,
+            detail: format!("balance {balance} e8s, fee {ICP_TRANSFER_FEE} e8s"),
+        },
+        None => unknown("Transfer ICP"),
+    });
+
+    // SNS tokens and staking
+    let ledger = app_context().sns_ledger_canister();
+    let governance = app_context().sns_governance_canister();
+    let (mut sns_fee, mut minimum_stake) = (None, None);
+    if let (Ok(ledger), Ok(governance)) = (&ledger, &governance) {
+        match tokio::try_join!(
+            get_sns_ledger_balance(&agent, *ledger, principal, None),
+            get_sns_ledger_fee(&agent, *ledger),
+            get_neuron_minimum_stake(&agent, *governance),
+        ) {
+            Ok((balance, fee, stake)) => {
+                access.sns_balance_e8s = Some(balance);
+                sns_fee = Some(fee);
+                minimum_stake = Some(stake);
+            }
+            Err(e) => access
+                .warnings
+                .push(format!("SNS balance unavailable: {e:#}")),
+        }
+    } else {
+        access
+            .warnings
+            .push("No SNS deployed: SNS operations cannot be checked".to_string());
+    }
+    match (access.sns_balance_e8s, sns_fee, minimum_stake) {
+        (Some(balance), Some(fee), Some(stake)) => {
+            access.operations.push(OperationAccess {
+                operation: "Transfer SNS tokens".to_string(),
+                allowed: balance > fee,
+                detail: format!("balance {balance} e8s, fee {fee} e8s"),
+            });
+            access.operations.push(OperationAccess {
+                operation: "Stake an SNS neuron".to_string(),
+                allowed: balance >= stake.saturating_add(fee),
+                detail: format!("minimum stake {stake} e8s plus the fee"),
+            });
+        }
+        _ => {
+            access.operations.push(unknown("Transfer SNS tokens"));
+            access.operations.push(unknown("Stake an SNS neuron"));
+        }
+    }
+
+    // SNS neuron permissions (list_neurons returns every neuron the principal has any on)
+    if let Ok(governance) = governance {
+        match list_neurons_for_principal(&agent, governance, principal).await {
+            Ok(neurons) => {
+                let held: Vec<(String, Vec<i32>)> = neurons
+                    .iter()
+                    .filter_map(|n| {
+                        let id = n.id.as_ref()?;
+                        Some((hex::encode(&id.id), neuron_permissions_of(n, principal)))
+                    })
+                    .collect();
+                for (value, name) in SNS_NEURON_PERMISSIONS {
+                    let count = held.iter().filter(|(_, p)| p.contains(value)).count();
+                    access.operations.push(OperationAccess {
+                        operation: format!("SNS neuron: {name}"),
+                        allowed: count > 0,
+                        detail: if count > 0 {
+                            format!("granted on {count} neuron(s)")
+                        } else {
+                            "no neuron grants it".to_string()
+                        },
+                    });
+                }
+                access.sns_neurons = held
+                    .into_iter()
+                    .map(|(neuron_id, permissions)| SnsNeuronAccess {
+                        neuron_id,
+                        permissions: permissions.into_iter().map(sns_permission_name).collect(),
+                    })
+                    .collect();
+            }
+            Err(e) => access
+                .warnings
+                .push(format!("SNS neurons unavailable: {e:#}")),
+        }
+    }
+
+    // ICP hotkeys: NNS governance only lists neurons to their controllers and hotkeys, so the
+    // neurons of the identities the tool can sign for are searched for the principal
+    if data.is_some() {
+        match list_all_icp_neurons_default_path().await {
+            Ok(accounts) => {
+                for (label, _, neurons) in accounts {
+                    for neuron in neurons {
+                        if neuron.hot_keys.contains(&principal)
+                            && let Some(id) = neuron.id
+                        {
+                            access.icp_hotkey_neurons.push(IcpHotkeyAccess {
+                                neuron_id: id.id,
+                                controller: label.clone(),
+                            });
+                        }
+                    }
+                }
+                let count = access.icp_hotkey_neurons.len();
+                access.operations.push(OperationAccess {
+                    operation: "Vote and follow on NNS proposals (ICP hotkey)".to_string(),
+                    allowed: count > 0,
+                    detail: if count > 0 {
+                        format!("hotkey of {count} ICP neuron(s)")
+                    } else {
+                        "not a hotkey of the owner's or participants' ICP neurons".to_string()
+                    },
+                });
+            }
+            Err(e) => {
+                access
+                    .warnings
+                    .push(format!("ICP neurons unavailable: {e:#}"));
+                access
+                    .operations
+                    .push(unknown("Vote and follow on NNS proposals (ICP hotkey)"));
+            }
+        }
+    }
+
+    Ok(access)
+}
+
+/// An operation whose source could not be read
+fn unknown(operation: &str) -> OperationAccess {
+    OperationAccess {
+        operation: operation.to_string(),
+        allowed: false,
+        detail: "unknown (see warnings)".to_string(),
+    }
+}
@@ -0,0 +1,240 @@
+// Append-only log of the state-changing canister calls the tool makes, next to the deployment data
+
+use anyhow::{Context, Result};
+use candid::types::value::{IDLField, IDLValue, VariantValue};
+use candid::{IDLArgs, Principal};
+use ic_agent::AgentError;
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+use crate::core::utils::data_output::get_output_dir;
+use crate::core::utils::time_format::now_secs;
+
+const AUDIT_LOG_FILE: &str = "audit_log.jsonl";
+
+/// Largest argument kept in an entry; bigger ones (e.g. wasm uploads) are left out
+const MAX_LOGGED_ARG_BYTES: usize = 16 * 1024;
+
+/// How a logged call ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditResult {
+    /// The canister replied without an error
+    Ok,
+    /// The canister replied with an error (an `Err` or `Error` variant)
+    Rejected,
+    /// The call itself failed (rejected by the replica, timed out, ...)
+    Failed,
+}
+
+impl std::fmt::Display for AuditResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Ok => "ok",
+            Self::Rejected => "rejected",
+            Self::Failed => "failed",
+        })
+    }
+}
+
+/// One state-changing call, a line of the audit log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp_seconds: u64,
+    /// The command line that made the call (without the program name)
+    pub command: String,
+    /// Principal the call was signed with
+    pub identity: String,
+    pub canister: String,
+    pub method: String,
+    pub result: AuditResult,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Block of a ledger transfer or approval
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_height: Option<u64>,
+    /// Proposal submitted by the call
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proposal_id: Option<u64>,
+    /// Candid-encoded arguments (hex), left out above [`MAX_LOGGED_ARG_BYTES`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arg: Option<String>,
+}
+
+static COMMAND: OnceLock<String> = OnceLock::new();
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Log the update calls of this process under `command` (the CLI turns this on; library
+/// users opt in)
+pub fn enable_audit_log(command: &str) {
+    let _ = COMMAND.set(command.to_string());
+}
+
+/// The audit log file, next to the deployment data
+#[must_use]
+pub fn audit_log_path() -> PathBuf {
+    get_output_dir().join(AUDIT_LOG_FILE)
+}
+
+/// Read every entry of the audit log, oldest first; a missing log is empty
+pub fn read_audit_log() -> Result<Vec<AuditEntry>> {
+    let path = audit_log_path();
+    let file = match std::fs::File::open(&path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to open {}", path.display()));
+        }
+    };
+    let mut entries = Vec::new();
+    for (index, line) in std::io::BufReader::new(file).lines().enumerate() {
+        let line = line.with_context(|| format!("Failed to read {}", path.display()))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        entries.push(serde_json::from_str(&line).with_context(|| {
+            format!("Invalid audit log {} at line {}", path.display(), index + 1)
+        })?);
+    }
+    Ok(entries)
+}
+
+/// Append an update call and its outcome to the audit log, if it is enabled
+pub(crate) fn log_update(
+    sender: Result<Principal, String>,
+    canister: &Principal,
+    method: &str,
+    arg: &[u8],
+    result: &Result<Vec<u8>, AgentError>,
+) {
+    let Some(command) = COMMAND.get() else {
+        return;
+    };
+
+    let reply = result
+        .as_ref()
+        .ok()
+        .and_then(|bytes| IDLArgs::from_bytes(bytes).ok());
+    let rejection = reply
+        .as_ref()
+        .and_then(|args| args.args.iter().find_map(rejection));
+    let (result, error) = match (result, rejection) {
+        (Err(e), _) => (AuditResult::Failed, Some(e.to_string())),
+        (Ok(_), Some(message)) => (AuditResult::Rejected, Some(message)),
+        (Ok(_), None) => (AuditResult::Ok, None),
+    };
+    let entry = AuditEntry {
+        timestamp_seconds: now_secs(),
+        command: command.clone(),
+        identity: sender.map_or_else(|_| "<unknown>".to_string(), |p| p.to_text()),
+        canister: canister.to_text(),
+        method: method.to_string(),
+        result,
+        error,
+        block_height: reply
+            .as_ref()
+            .filter(|_| method.contains("transfer") || method.contains("approve"))
+            .and_then(|args| args.args.first())
+            .and_then(ok_number),
+        proposal_id: reply
+            .as_ref()
+            .and_then(|args| args.args.iter().find_map(proposal_id)),
+        arg: (arg.len() <= MAX_LOGGED_ARG_BYTES).then(|| hex::encode(arg)),
+    };
+
+    if let Err(e) = append(&entry) {
+        log::warn!("Failed to write audit log: {e:#}");
+    }
+}
+
+fn append(entry: &AuditEntry) -> Result<()> {
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+    let _guard = WRITE_LOCK.lock().expect("audit log lock");
+    let path = audit_log_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?
+        .write_all(line.as_bytes())?;
+    Ok(())
+}
+
+fn is_label(field: &IDLField, name: &str) -> bool {
+    field.id.get_id() == candid::idl_hash(name)
+}
+
+fn as_u64(value: &IDLValue) -> Option<u64> {
+    match value {
+        IDLValue::Nat64(n) => Some(*n),
+        IDLValue::Nat(n) => u64::try_from(n.0.clone()).ok(),
+        IDLValue::Opt(inner) => as_u64(inner),
+        _ => None,
+    }
+}
+
+/// The number in an `Ok` variant, as ledger transfers reply with their block
+fn ok_number(value: &IDLValue) -> Option<u64> {
+    match value {
+        IDLValue::Variant(VariantValue(field, _)) if is_label(field, "Ok") => as_u64(&field.val),
+        _ => None,
+    }
+}
+
+/// The `id` of the first `proposal_id` field anywhere in the reply
+fn proposal_id(value: &IDLValue) -> Option<u64> {
+    match value {
+        IDLValue::Record(fields) => fields.iter().find_map(|field| {
+            if is_label(field, "proposal_id") {
+                id_of(&field.val)
+            } else {
+                proposal_id(&field.val)
+            }
+        }),
+        IDLValue::Variant(VariantValue(field, _)) => proposal_id(&field.val),
+        IDLValue::Opt(inner) => proposal_id(inner),
+        _ => None,
+    }
+}
+
+fn id_of(value: &IDLValue) -> Option<u64> {
+    match value {
+        IDLValue::Record(fields) => fields
+            .iter()
+            .find(|field| is_label(field, "id"))
+            .and_then(|field| as_u64(&field.val)),
+        IDLValue::Opt(inner) => id_of(inner),
+        other => as_u64(other),
+    }
+}
+
+/// The message of the first `Err` or `Error` variant in the reply
+fn rejection(value: &IDLValue) -> Option<String> {
+    match value {
+        IDLValue::Variant(VariantValue(field, _))
+            if is_label(field, "Err") || is_label(field, "Error") =>
+        {
+            Some(first_text(&field.val).unwrap_or_else(|| field.val.to_string()))
+        }
+        IDLValue::Variant(VariantValue(field, _)) => rejection(&field.val),
+        IDLValue::Record(fields) => fields.iter().find_map(|field| rejection(&field.val)),
+        IDLValue::Opt(inner) => rejection(inner),
+        _ => None,
+    }
+}
+
+fn first_text(value: &IDLValue) -> Option<String> {
+    match value {
+        IDLValue::Text(text) => Some(text.clone()),
+        IDLValue::Record(fields) => fields.iter().find_map(|field| first_text(&field.val)),
+        IDLValue::Variant(VariantValue(field, _)) => first_text(&field.val),
+        IDLValue::Opt(inner) => first_text(inner),
+        _ => None,
+    }
+}
@@ -0,0 +1,394 @@
+// Load testing: submit proposals, votes or transfers from many identities at a steady rate
+
+use crate::core::utils::exit_code::invalid;
+use anyhow::{Context, Result};
+use candid::Principal;
+use futures::StreamExt;
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tokio::time::Instant;
+
+use super::identity::resolve_principal;
+use super::identity::signing_principal;
+use super::ledger_ops::transfer_sns_tokens;
+use super::sns_governance_ops::{
+    list_neurons_for_principal, main_neuron_id, make_proposal, vote_on_proposal,
+};
+use crate::core::context::app_context;
+use crate::core::declarations::sns_governance::{Action, Motion, Proposal};
+use crate::core::utils::print_step;
+
+/// Default number of operations in flight at once
+pub const DEFAULT_BENCH_CONCURRENCY: usize = 10;
+
+/// Default SNS transfer amount (the fee is paid on top)
+pub const DEFAULT_BENCH_TRANSFER_E8S: u64 = 100_000;
+
+/// What a benchmark submits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BenchKind {
+    /// Motion proposals, each from an identity's main neuron
+    Proposals,
+    /// Yes votes on motion proposals submitted before the timed run
+    Votes,
+    /// SNS token transfers, each identity paying the next one
+    Transfers,
+}
+
+impl std::str::FromStr for BenchKind {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value {
+            "proposals" => Ok(Self::Proposals),
+            "votes" => Ok(Self::Votes),
+            "transfers" => Ok(Self::Transfers),
+            _ => Err(invalid(format!(
+                "Invalid benchmark '{value}': expected proposals, votes or transfers"
+            ))),
+        }
+    }
+}
+
+impl std::fmt::Display for BenchKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Proposals => "proposals",
+            Self::Votes => "votes",
+            Self::Transfers => "transfers",
+        })
+    }
+}
+
+/// How a benchmark runs
+#[derive(Debug, Clone)]
+pub struct BenchOptions {
+    pub kind: BenchKind,
+    /// Number of operations to submit
+    pub count: usize,
+    /// Operations started per second; as fast as the concurrency allows without one
+    pub rate: Option<f64>,
+    /// Operations in flight at once
+    pub concurrency: usize,
+    /// Identities taking turns, written as in scenarios; every participant if empty
+    pub identities: Vec<String>,
+    /// Amount of each transfer
+    pub amount_e8s: u64,
+    /// Print no progress (`--json`); what would have been warned about goes in the report
+    pub quiet: bool,
+}
+
+/// Latencies and errors of a benchmark run
+#[derive(Debug, Clone)]
+pub struct BenchReport {
+    pub kind: BenchKind,
+    pub identities: usize,
+    /// Wall time of the timed run, setup excluded
+    pub elapsed: Duration,
+    /// Latency of each successful operation, fastest first
+    pub latencies: Vec<Duration>,
+    /// Failed operations by error message
+    pub errors: BTreeMap<String, usize>,
+    /// Setup problems that did not stop the run, such as identities skipped for lack of a neuron
+    pub warnings: Vec<String>,
+}
+
+impl BenchReport {
+    /// Number of operations that failed
+    #[must_use]
+    pub fn error_count(&self) -> usize {
+        self.errors.values().sum()
+    }
+
+    /// Number of operations run
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.latencies.len() + self.error_count()
+    }
+
+    /// Successful operations per second
+    #[must_use]
+    pub fn throughput(&self) -> f64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs > 0.0 {
+            self.latencies.len() as f64 / secs
+        } else {
+            0.0
+        }
+    }
+
+    /// Latency at `percent` (nearest rank) of the successful operations
+    #[must_use]
+    pub fn percentile(&self, percent: f64) -> Option<Duration> {
+        if self.latencies.is_empty() {
+            return None;
+        }
+        let rank = (percent / 100.0 * self.latencies.len() as f64).ceil() as usize;
+        Some(self.latencies[rank.clamp(1, self.latencies.len()) - 1])
+    }
+}
+
+/// An identity taking part in a benchmark
+struct Actor {
+    name: String,
+    principal: Principal,
+    agent: ic_agent::Agent,
+    /// Main SNS neuron, for proposals and votes
+    neuron: Option<Vec<u8>>,
+}
+
+/// One operation of the timed run: the acting identity and what it submits
+enum Op {
+    Propose {
+        actor: usize,
+        index: usize,
+    },
+    Vote {
+        actor: usize,
+        proposal_id: u64,
+    },
+    Transfer {
+        actor: usize,
+        to: usize,
+        amount_e8s: u64,
+    },
+}
+
+/// Run a benchmark: set up the identities (and proposals to vote on), then submit
+/// `count` operations and time each one
+pub async fn run_bench(options: &BenchOptions) -> Result<BenchReport> {
+    if options.count == 0 {
+        return Err(invalid("Invalid count: expected at least 1 operation"));
+    }
+    if options.concurrency == 0 {
+        return Err(invalid("Invalid concurrency: expected at least 1"));
+    }
+    if options
+        .rate
+        .is_some_and(|rate| !(rate.is_finite() && rate > 0.0))
+    {
+        return Err(invalid(
+            "Invalid rate: expected operations per second above 0",
+        ));
+    }
+
+    let mut warnings = Vec::new();
+    let actors = load_actors(options, &mut warnings).await?;
+    let governance_canister = app_context().sns_governance_canister()?;
+    let ledger_canister = app_context().sns_ledger_canister()?;
+    let ops = plan_ops(options, &actors, governance_canister).await?;
+
+    progress(
+        options,
+        &format!(
+            "Submitting {} {} from {} identities ({} in flight, {})...",
+            ops.len(),
+            options.kind,
+            actors.len(),
+            options.concurrency,
+            options
+                .rate
+                .map_or_else(|| "no rate limit".to_string(), |rate| format!("{rate}/s")),
+        ),
+    );
+
+    let actors = &actors;
+    let start = Instant::now();
+    let results: Vec<(Duration, Result<()>)> = futures::stream::iter(ops.into_iter().enumerate())
+        .map(|(i, op)| async move {
+            if let Some(rate) = options.rate {
+                tokio::time::sleep_until(start + Duration::from_secs_f64(i as f64 / rate)).await;
+            }
+            let began = Instant::now();
+            let result = run_op(op, actors, governance_canister, ledger_canister).await;
+            (began.elapsed(), result)
+        })
+        .buffer_unordered(options.concurrency)
+        .collect()
+        .await;
+    let elapsed = start.elapsed();
+
+    let mut latencies = Vec::new();
+    let mut errors = BTreeMap::new();
+    for (latency, result) in results {
+        match result {
+            Ok(()) => latencies.push(latency),
+            Err(e) => *errors.entry(format!("{e:#}")).or_default() += 1,
+        }
+    }
+    latencies.sort();
+
+    Ok(BenchReport {
+        kind: options.kind,
+        identities: actors.len(),
+        elapsed,
+        latencies,
+        errors,
+        warnings,
+    })
+}
+
+/// Print a setup step, unless the options ask for no progress output
+fn progress(options: &BenchOptions, message: &str) {
+    if !options.quiet {
+        print_step(message);
+    }
+}
+
+/// Agents of the identities, with their main neuron when proposing or voting
+/// Identities without a neuron are skipped, with a warning added to `warnings`
+async fn load_actors(options: &BenchOptions, warnings: &mut Vec<String>) -> Result<Vec<Actor>> {
+    let names = if options.identities.is_empty() {
+        let data = app_context().deployment_data()?;
+        (1..=data.participants.len())
+            .map(|n| format!("participant-{n}"))
+            .collect()
+    } else {
+        options.identities.clone()
+    };
+    if names.is_empty() {
+        anyhow::bail!("No identities to run the benchmark with; pass --identities");
+    }
+
+    let governance_canister = app_context().sns_governance_canister()?;
+    let needs_neuron = options.kind != BenchKind::Transfers;
+    let mut actors = Vec::new();
+    for name in names {
+        let principal = resolve_principal(&name)?;
+        let agent = app_context()
+            .agent_for_principal(signing_principal(principal))
+            .await
+            .with_context(|| format!("No agent for {name}"))?;
+        let neuron = if needs_neuron {
+            let neurons =
+                list_neurons_for_principal(&agent, governance_canister, principal).await?;
+            let Some(neuron) = main_neuron_id(&neurons) else {
+                warnings.push(format!("Skipping {name}: no SNS neurons"));
+                continue;
+            };
+            Some(neuron.id.clone())
+        } else {
+            None
+        };
+        actors.push(Actor {
+            name,
+            principal,
+            agent,
+            neuron,
+        });
+    }
+    if actors.is_empty() {
+        anyhow::bail!("None of the identities has an SNS neuron");
+    }
+    Ok(actors)
+}
+
+/// The operations to time, identities taking turns; for votes this submits the proposals
+async fn plan_ops(
+    options: &BenchOptions,
+    actors: &[Actor],
+    governance_canister: Principal,
+) -> Result<Vec<Op>> {
+    let count = options.count;
+    match options.kind {
+        BenchKind::Proposals => Ok((0..count)
+            .map(|index| Op::Propose {
+                actor: index % actors.len(),
+                index,
+            })
+            .collect()),
+        BenchKind::Transfers => Ok((0..count)
+            .map(|index| Op::Transfer {
+                actor: index % actors.len(),
+                to: (index + 1) % actors.len(),
+                amount_e8s: options.amount_e8s,
+            })
+            .collect()),
+        BenchKind::Votes => {
+            // The first identity proposes and its neuron votes on submission; the rest vote
+            let (proposer, voters) = actors
+                .split_first()
+                .filter(|(_, voters)| !voters.is_empty())
+                .context("Voting needs at least two identities with SNS neurons")?;
+            let proposals = count.div_ceil(voters.len());
+            progress(
+                options,
+                &format!(
+                    "Submitting {proposals} motion proposal(s) from {} to vote on...",
+                    proposer.name
+                ),
+            );
+            let mut ops = Vec::with_capacity(count);
+            for index in 0..proposals {
+                let proposal_id = make_proposal(
+                    &proposer.agent,
+                    governance_canister,
+                    proposer.neuron.clone().unwrap_or_default(),
+                    bench_motion(index),
+                )
+                .await
+                .context("Failed to submit a proposal to vote on")?;
+                ops.extend((1..actors.len()).map(|actor| Op::Vote { actor, proposal_id }));
+            }
+            ops.truncate(count);
+            Ok(ops)
+        }
+    }
+}
+
+async fn run_op(
+    op: Op,
+    actors: &[Actor],
+    governance_canister: Principal,
+    ledger_canister: Principal,
+) -> Result<()> {
+    match op {
+        Op::Propose { actor, index } => {
+            let actor = &actors[actor];
+            make_proposal(
+                &actor.agent,
+                governance_canister,
+                actor.neuron.clone().unwrap_or_default(),
+                bench_motion(index),
+            )
+            .await
+            .map(|_| ())
+        }
+        Op::Vote { actor, proposal_id } => {
+            let actor = &actors[actor];
+            vote_on_proposal(
+                &actor.agent,
+                governance_canister,
+                actor.neuron.clone().unwrap_or_default(),
+                proposal_id,
+                1,
+            )
+            .await
+        }
+        Op::Transfer {
+            actor,
+            to,
+            amount_e8s,
+        } => transfer_sns_tokens(
+            &actors[actor].agent,
+            ledger_canister,
+            actors[to].principal,
+            amount_e8s,
+            None,
+        )
+        .await
+        .map(|_| ()),
+    }
+}
+
+fn bench_motion(index: usize) -> Proposal {
+    let title = format!("Bench motion {}", index + 1);
+    Proposal {
+        url: String::new(),
+        summary: title.clone(),
+        title,
+        action: Some(Action::Motion(Motion {
+            motion_text: "Submitted by local_sns bench".to_string(),
+        })),
+    }
+}
@@ -0,0 +1,254 @@
+// Canister calls with debug logging of the method, canister and candid arguments, recorded or
+// replayed with --record / --replay; update calls also go to the audit log
+
+use candid::Principal;
+use ic_agent::{Agent, AgentError};
+
+use super::audit_log;
+use super::canister_client::CanisterClient;
+use super::method_types::{self, Part};
+use super::recording;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static DRY_RUN: AtomicBool = AtomicBool::new(false);
+static DEBUG_CANDID: AtomicBool = AtomicBool::new(false);
+
+/// Print update calls instead of submitting them (`--dry-run`)
+/// Queries still run, so payloads that depend on current state are exact.
+pub fn set_dry_run(enabled: bool) {
+    DRY_RUN.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether update calls are printed instead of submitted
+#[must_use]
+pub fn is_dry_run() -> bool {
+    DRY_RUN.load(Ordering::Relaxed)
+}
+
+/// Print the textual candid of every request and reply on stderr (`--debug-candid`)
+pub fn set_debug_candid(enabled: bool) {
+    DEBUG_CANDID.store(enabled, Ordering::Relaxed);
+}
+
+/// Error returned by an update call in dry-run mode
+/// Later steps of an operation depend on the reply, so the operation stops at the first update.
+#[derive(Debug)]
+pub struct DryRunStop {
+    pub canister: Principal,
+    pub method: String,
+}
+
+impl std::fmt::Display for DryRunStop {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Dry run: {} on {} was not submitted",
+            self.method, self.canister
+        )
+    }
+}
+
+impl std::error::Error for DryRunStop {}
+
+/// The dry-run stop behind an error, if the operation stopped at a dry-run update
+#[must_use]
+pub fn dry_run_stop(err: &anyhow::Error) -> Option<&DryRunStop> {
+    err.downcast_ref::<DryRunStop>()
+}
+
+/// Longest argument summary logged at debug level (the full payload is logged at trace level)
+const ARG_SUMMARY_LEN: usize = 300;
+
+/// Call an update method and wait for the reply
+/// In dry-run mode the call is printed and fails with [`DryRunStop`] instead
+pub async fn update(
+    agent: &dyn CanisterClient,
+    canister: &Principal,
+    method: &str,
+    arg: Vec<u8>,
+) -> anyhow::Result<Vec<u8>> {
+    if is_dry_run() {
+        print_dry_run(agent, canister, method, &arg);
+        return Err(DryRunStop {
+            canister: *canister,
+            method: method.to_string(),
+        }
+        .into());
+    }
+
+    log_request("update", canister, method, &arg);
+    let result = match recording::replayed("update", canister, method, &arg) {
+        Some(result) => result,
+        None => {
+            let result = agent.update_raw(canister, method, arg.clone()).await;
+            recording::record("update", canister, method, &arg, &result);
+            audit_log::log_update(agent.sender(), canister, method, &arg, &result);
+            result
+        }
+    };
+    log_reply("update", canister, method, &result);
+    Ok(result?)
+}
+
+/// Call an update method of the management canister on behalf of `effective_canister`
+/// In dry-run mode the call is printed and fails with [`DryRunStop`] instead
+pub async fn management_update(
+    agent: &Agent,
+    effective_canister: &Principal,
+    method: &str,
+    arg: Vec<u8>,
+) -> anyhow::Result<Vec<u8>> {
+    let canister = Principal::management_canister();
+    if is_dry_run() {
+        print_dry_run(agent, &canister, method, &arg);
+        return Err(DryRunStop {
+            canister,
+            method: method.to_string(),
+        }
+        .into());
+    }
+
+    log_request("update", &canister, method, &arg);
+    let result = match recording::replayed("update", &canister, method, &arg) {
+        Some(result) => result,
+        None => {
+            let result = agent
+                .update(&canister, method)
+                .with_effective_canister_id(*effective_canister)
+                .with_arg(arg.clone())
+                .call_and_wait()
+                .await;
+            recording::record("update", &canister, method, &arg, &result);
+            audit_log::log_update(agent.get_principal(), &canister, method, &arg, &result);
+            result
+        }
+    };
+    log_reply("update", &canister, method, &result);
+    Ok(result?)
+}
+
+/// Call an update method that does not change state (e.g. one that gathers canister statuses)
+/// Runs in dry-run mode too, since there is nothing to hold back
+pub async fn read_only_update(
+    agent: &Agent,
+    canister: &Principal,
+    method: &str,
+    arg: Vec<u8>,
+) -> Result<Vec<u8>, AgentError> {
+    log_request("update", canister, method, &arg);
+    let result = match recording::replayed("update", canister, method, &arg) {
+        Some(result) => result,
+        None => {
+            let result = agent
+                .update(canister, method)
+                .with_arg(arg.clone())
+                .call_and_wait()
+                .await;
+            recording::record("update", canister, method, &arg, &result);
+            result
+        }
+    };
+    log_reply("update", canister, method, &result);
+    result
+}
+
+/// Call a query method
+pub async fn query(
+    agent: &dyn CanisterClient,
+    canister: &Principal,
+    method: &str,
+    arg: Vec<u8>,
+) -> Result<Vec<u8>, AgentError> {
+    log_request("query", canister, method, &arg);
+    let result = match recording::replayed("query", canister, method, &arg) {
+        Some(result) => result,
+        None => {
+            let result = agent.query_raw(canister, method, arg.clone()).await;
+            recording::record("query", canister, method, &arg, &result);
+            result
+        }
+    };
+    log_reply("query", canister, method, &result);
+    result
+}
+
+fn print_dry_run(agent: &dyn CanisterClient, canister: &Principal, method: &str, arg: &[u8]) {
+    let sender = agent
+        .sender()
+        .map_or_else(|_| "<unknown>".to_string(), |p| p.to_text());
+    let args = candid_text(canister, method, Part::Request, arg);
+
+    println!("[dry-run] Would call update method");
+    println!("  Canister: {canister}");
+    println!("  Method:   {method}");
+    println!("  Sender:   {sender}");
+    println!("  Arguments:");
+    for line in args.lines() {
+        println!("    {line}");
+    }
+}
+
+fn log_request(kind: &str, canister: &Principal, method: &str, arg: &[u8]) {
+    if DEBUG_CANDID.load(Ordering::Relaxed) {
+        print_candid(
+            &format!("{kind} {canister} {method} request"),
+            &candid_text(canister, method, Part::Request, arg),
+        );
+    }
+    if !log::log_enabled!(log::Level::Debug) {
+        return;
+    }
+
+    let args = candid_text(canister, method, Part::Request, arg);
+    if log::log_enabled!(log::Level::Trace) {
+        log::trace!("{kind} {canister} {method} {args}");
+    } else {
+        log::debug!("{kind} {canister} {method} {}", truncate(&args));
+    }
+}
+
+fn log_reply(kind: &str, canister: &Principal, method: &str, result: &Result<Vec<u8>, AgentError>) {
+    if DEBUG_CANDID.load(Ordering::Relaxed) {
+        match result {
+            Ok(bytes) => print_candid(
+                &format!("{kind} {canister} {method} reply"),
+                &candid_text(canister, method, Part::Reply, bytes),
+            ),
+            Err(e) => eprintln!("[candid] {kind} {canister} {method} failed: {e}"),
+        }
+    }
+    match result {
+        Ok(bytes) => log::debug!("{kind} {canister} {method} -> {} bytes", bytes.len()),
+        Err(e) => log::debug!("{kind} {canister} {method} failed: {e}"),
+    }
+}
+
+/// Textual candid of an encoded argument list, as candid's pretty printer formats it
+/// Fields are named from the method's declared types; methods without a declared signature
+/// show label hashes instead.
+fn candid_text(canister: &Principal, method: &str, part: Part, bytes: &[u8]) -> String {
+    method_types::decode(canister, method, part, bytes).map_or_else(
+        |e| format!("<{} bytes, undecodable: {e}>", bytes.len()),
+        |args| args.to_string(),
+    )
+}
+
+fn print_candid(label: &str, text: &str) {
+    let mut out = format!("[candid] {label}\n");
+    for line in text.lines() {
+        out.push_str("    ");
+        out.push_str(line);
+        out.push('\n');
+    }
+    // One write per call, so concurrent calls do not interleave their lines
+    eprint!("{out}");
+}
+
+fn truncate(args: &str) -> String {
+    let flat = args.split_whitespace().collect::<Vec<_>>().join(" ");
+    if flat.chars().count() <= ARG_SUMMARY_LEN {
+        return flat;
+    }
+    let head: String = flat.chars().take(ARG_SUMMARY_LEN).collect();
+    format!("{head}…")
+}
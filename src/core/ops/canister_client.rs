@@ -0,0 +1,196 @@
+// The agent layer behind canister calls: the real ic-agent, or a mock for tests without a replica
+
+use candid::{CandidType, Principal};
+use futures::future::BoxFuture;
+use ic_agent::{Agent, AgentError};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+/// Raw query and update calls, as the ops modules make them through `canister_call`
+///
+/// Implemented by [`Agent`] for real calls and by [`MockCanisterClient`] for tests. Ops
+/// functions take `&dyn CanisterClient`, so callers holding an `Agent` pass `&agent` as before.
+pub trait CanisterClient: Send + Sync {
+    /// Call a query method with candid-encoded arguments
+    fn query_raw<'a>(
+        &'a self,
+        canister: &'a Principal,
+        method: &'a str,
+        arg: Vec<u8>,
+    ) -> BoxFuture<'a, Result<Vec<u8>, AgentError>>;
+
+    /// Call an update method with candid-encoded arguments and wait for the reply
+    fn update_raw<'a>(
+        &'a self,
+        canister: &'a Principal,
+        method: &'a str,
+        arg: Vec<u8>,
+    ) -> BoxFuture<'a, Result<Vec<u8>, AgentError>>;
+
+    /// Principal the calls are signed with
+    fn sender(&self) -> Result<Principal, String>;
+}
+
+impl CanisterClient for Agent {
+    fn query_raw<'a>(
+        &'a self,
+        canister: &'a Principal,
+        method: &'a str,
+        arg: Vec<u8>,
+    ) -> BoxFuture<'a, Result<Vec<u8>, AgentError>> {
+        Box::pin(self.query(canister, method).with_arg(arg).call())
+    }
+
+    fn update_raw<'a>(
+        &'a self,
+        canister: &'a Principal,
+        method: &'a str,
+        arg: Vec<u8>,
+    ) -> BoxFuture<'a, Result<Vec<u8>, AgentError>> {
+        Box::pin(self.update(canister, method).with_arg(arg).call_and_wait())
+    }
+
+    fn sender(&self) -> Result<Principal, String> {
+        self.get_principal()
+    }
+}
+
+/// Query or update
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallKind {
+    Query,
+    Update,
+}
+
+/// A call the mock received
+#[derive(Debug, Clone)]
+pub struct RecordedCall {
+    pub kind: CallKind,
+    pub canister: Principal,
+    pub method: String,
+    /// Candid-encoded arguments
+    pub arg: Vec<u8>,
+}
+
+impl RecordedCall {
+    /// Decode the single argument of the call
+    pub fn decode_arg<T: CandidType + for<'de> serde::Deserialize<'de>>(
+        &self,
+    ) -> anyhow::Result<T> {
+        Ok(candid::decode_one(&self.arg)?)
+    }
+}
+
+/// Queued encoded replies per canister and method
+type ReplyQueues = HashMap<(Principal, String), VecDeque<Vec<u8>>>;
+
+/// Canister client that answers from canned replies and records every call
+///
+/// Replies are queued per canister and method and served in order; the last one is kept and
+/// repeated, so polling loops see a stable state. A call without a reply fails like a reject.
+#[derive(Debug)]
+pub struct MockCanisterClient {
+    sender: Principal,
+    replies: Mutex<ReplyQueues>,
+    calls: Mutex<Vec<RecordedCall>>,
+}
+
+impl MockCanisterClient {
+    /// Mock that signs its calls as `sender`
+    #[must_use]
+    pub fn new(sender: Principal) -> Self {
+        Self {
+            sender,
+            replies: Mutex::new(HashMap::new()),
+            calls: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Queue an encoded reply for `method` on `canister`
+    pub fn reply_bytes(&self, canister: Principal, method: &str, reply: Vec<u8>) {
+        self.replies
+            .lock()
+            .expect("mock replies lock")
+            .entry((canister, method.to_string()))
+            .or_default()
+            .push_back(reply);
+    }
+
+    /// Queue a reply for `method` on `canister`, encoded as a single candid value
+    pub fn reply<T: CandidType>(&self, canister: Principal, method: &str, reply: &T) {
+        let bytes = candid::encode_one(reply).expect("mock reply encodes as candid");
+        self.reply_bytes(canister, method, bytes);
+    }
+
+    /// Every call received so far, in order
+    #[must_use]
+    pub fn calls(&self) -> Vec<RecordedCall> {
+        self.calls.lock().expect("mock calls lock").clone()
+    }
+
+    /// Calls of `method` received so far, in order
+    #[must_use]
+    pub fn calls_to(&self, method: &str) -> Vec<RecordedCall> {
+        self.calls()
+            .into_iter()
+            .filter(|call| call.method == method)
+            .collect()
+    }
+
+    fn answer(
+        &self,
+        kind: CallKind,
+        canister: &Principal,
+        method: &str,
+        arg: Vec<u8>,
+    ) -> Result<Vec<u8>, AgentError> {
+        self.calls
+            .lock()
+            .expect("mock calls lock")
+            .push(RecordedCall {
+                kind,
+                canister: *canister,
+                method: method.to_string(),
+                arg,
+            });
+
+        let mut replies = self.replies.lock().expect("mock replies lock");
+        let queue = replies
+            .get_mut(&(*canister, method.to_string()))
+            .filter(|queue| !queue.is_empty())
+            .ok_or_else(|| {
+                AgentError::MessageError(format!("mock has no reply for {method} on {canister}"))
+            })?;
+        Ok(if queue.len() > 1 {
+            queue.pop_front().unwrap_or_default()
+        } else {
+            queue[0].clone()
+        })
+    }
+}
+
+impl CanisterClient for MockCanisterClient {
+    fn query_raw<'a>(
+        &'a self,
+        canister: &'a Principal,
+        method: &'a str,
+        arg: Vec<u8>,
+    ) -> BoxFuture<'a, Result<Vec<u8>, AgentError>> {
+        let reply = self.answer(CallKind::Query, canister, method, arg);
+        Box::pin(async move { reply })
+    }
+
+    fn update_raw<'a>(
+        &'a self,
+        canister: &'a Principal,
+        method: &'a str,
+        arg: Vec<u8>,
+    ) -> BoxFuture<'a, Result<Vec<u8>, AgentError>> {
+        let reply = self.answer(CallKind::Update, canister, method, arg);
+        Box::pin(async move { reply })
+    }
+
+    fn sender(&self) -> Result<Principal, String> {
+        Ok(self.sender)
+    }
+}
@@ -1,5 +1,8 @@
 // CLI command handlers
 
+use crate::core::context::app_context;
+use crate::core::ops::identity::{identity_for_principal, signing_principal};
+use crate::core::utils::exit_code::{ValidationError, invalid};
 use anyhow::{Context, Result};
 use candid::Principal;
 use hex;
@@ -9,417 +12,193 @@ use crate::core::ops::governance_ops::{
     create_icp_neuron_default_path, get_icp_neuron_default_path,
     list_icp_neurons_for_principal_default_path, mint_icp_default_path,
 };
-use crate::core::ops::ledger_ops::{get_icp_ledger_balance, get_sns_ledger_balance};
+use crate::core::ops::identity::resolve_principal;
+use crate::core::ops::ledger_ops::{
+    LedgerHistoryEntry, auto_neuron_memo, get_icp_ledger_balance, get_icp_ledger_history,
+    get_sns_ledger_balance, get_sns_ledger_history,
+};
+use crate::core::ops::neuron_view::{
+    Dissolve, NeuronFilter, NeuronSort, NeuronView, neuron_table, print_neuron_details,
+};
 use crate::core::ops::sns_governance_ops::{
     add_hotkey_to_participant_neuron_default_path, create_sns_neuron_default_path,
-    disburse_participant_neuron_default_path,
+    disburse_participant_neuron_default_path, get_voting_power_params_default_path,
     increase_dissolve_delay_participant_neuron_default_path,
     list_neurons_for_principal_default_path,
     manage_dissolving_state_participant_neuron_default_path,
     mint_sns_tokens_with_all_votes_default_path,
 };
 use crate::core::ops::snsw_ops::check_sns_deployed_default_path;
+use crate::core::utils::amount::Spendable;
+use crate::core::utils::clipboard::{copy_and_report, copy_selected};
+use crate::core::utils::confirm::{Cancelled, confirm_action, needs_confirmation};
+use crate::core::utils::constants::{LARGE_MINT_TOKENS, SNS_LEDGER_DECIMALS};
+use crate::core::utils::data_output::SnsCreationData;
+use crate::core::utils::input::read_line;
+use crate::core::utils::interrupt::SelfHandled;
+use crate::core::utils::selector::{Choice, Selector, UserNavigation};
+use crate::core::utils::spinner::Spinner;
+use crate::core::utils::table::{Table, short_id};
 use crate::core::utils::{print_header, print_info, print_success, print_warning};
 
 /// Select participant OR enter custom principal
 /// Shows participants (1-N) OR allows entering a custom principal
-fn select_participant_or_custom() -> Result<Principal> {
-    select_participant_or_custom_with_label(None)
-}
-
-/// Select participant OR enter custom principal with optional label (sync version)
-/// Shows participants (1-N) OR allows entering a custom principal
-fn select_participant_or_custom_with_label(label: Option<&str>) -> Result<Principal> {
-    select_participant_or_custom_with_label_and_counts_sync(label)
-}
-
-/// Sync wrapper for select_participant_or_custom_with_label_and_counts
 /// This version doesn't show neuron counts (for sync contexts)
-fn select_participant_or_custom_with_label_and_counts_sync(
-    label: Option<&str>,
-) -> Result<Principal> {
-    use crate::core::utils::data_output::SnsCreationData;
-
-    // Try to read deployment data
-    let deployment_path = crate::core::utils::data_output::get_output_path();
-
-    if deployment_path.exists() {
-        if let Ok(data_content) = std::fs::read_to_string(&deployment_path) {
-            if let Ok(deployment_data) = serde_json::from_str::<SnsCreationData>(&data_content) {
-                let owner_option = deployment_data.participants.len() + 1;
-                let custom_option = owner_option + 1;
-
-                if let Some(lbl) = label {
-                    println!("{}", lbl);
-                    println!();
-                }
-                println!("Available options:");
-                println!();
-                // Show participants first
-                for (i, participant) in deployment_data.participants.iter().enumerate() {
-                    println!("  [{}] {}", i + 1, participant.principal);
-                }
-                // Show owner before custom principal
-                println!(
-                    "  [{}] {} (SNS proposer)",
-                    owner_option, deployment_data.owner_principal
-                );
-                println!("  [{}] Enter custom principal", custom_option);
-                println!("  [{}] Go back to main menu", custom_option + 1);
-                println!();
-                print!(
-                    "Select option number (1-{}), press Enter/[b]ack to go back, or enter principal: ",
-                    custom_option + 1
-                );
-                io::stdout().flush()?;
-
-                let mut input = String::new();
-                io::stdin().read_line(&mut input)?;
-                let input_trimmed = input.trim();
-                let input_trimmed_lower = input_trimmed.to_lowercase();
-
-                // Check for navigation commands
-                if input_trimmed_lower == "b"
-                    || input_trimmed_lower == "back"
-                    || input_trimmed.is_empty()
-                {
-                    anyhow::bail!("User went to main menu");
-                }
-
-                // Check if input looks like a principal (contains dashes, typical format)
-                // Principals typically have 5 dashes and are 63 characters long
-                if input_trimmed.contains('-') && input_trimmed.len() > 20 {
-                    // Try to parse as principal directly
-                    match Principal::from_text(input_trimmed) {
-                        Ok(principal) => return Ok(principal),
-                        Err(e) => {
-                            // If principal parsing fails, check if it's a number
-                            // Otherwise return the error
-                            if input_trimmed.parse::<usize>().is_ok() {
-                                // It's actually a number, continue to number parsing below
-                            } else {
-                                return Err(anyhow::anyhow!("Failed to parse principal: {}", e));
-                            }
-                        }
-                    }
-                }
-
-                // Try to parse as number
-                match input_trimmed.parse::<usize>() {
-                    Ok(selection) => {
-                        if selection == custom_option + 1 {
-                            // Go back to main menu option
-                            anyhow::bail!("User went to main menu");
-                        }
-                        if selection < 1 || selection > custom_option {
-                            anyhow::bail!(
-                                "Invalid selection. Please choose a number between 1 and {}",
-                                custom_option + 1
-                            );
-                        }
-
-                        if selection == custom_option {
-                            // Custom principal option
-                            let principal_input = read_input_required(
-                                "Enter principal (or press Enter/[b]ack to go back): ",
-                            )
-                            .map_err(navigation_to_anyhow)?;
-                            Principal::from_text(&principal_input)
-                                .context("Failed to parse principal")
-                        } else if selection == owner_option {
-                            // Owner (SNS proposer)
-                            Principal::from_text(&deployment_data.owner_principal)
-                                .context("Failed to parse owner principal")
-                        } else {
-                            // Participant (selection is 1-based, participants array is 0-based)
-                            Principal::from_text(
-                                &deployment_data.participants[selection - 1].principal,
-                            )
-                            .context("Failed to parse selected participant principal")
-                        }
-                    }
-                    Err(_) => {
-                        // Not a number, try to parse as principal anyway
-                        Principal::from_text(input_trimmed).context("Failed to parse principal")
-                    }
-                }
-            } else {
-                // Deployment data exists but can't parse - fall back to custom input
-                if let Some(lbl) = label {
-                    println!("{}", lbl);
-                } else {
-                    print_header("Select Principal");
-                }
-                print!("Enter principal: ");
-                io::stdout().flush()?;
-                let mut input = String::new();
-                io::stdin().read_line(&mut input)?;
-                Principal::from_text(input.trim()).context("Failed to parse principal")
-            }
-        } else {
-            // Can't read deployment data - fall back to custom input
-            if let Some(lbl) = label {
-                println!("{}", lbl);
-            } else {
-                print_header("Select Principal");
-            }
-            print!("Enter principal: ");
-            io::stdout().flush()?;
-            let mut input = String::new();
-            io::stdin().read_line(&mut input)?;
-            Principal::from_text(input.trim()).context("Failed to parse principal")
-        }
-    } else {
-        // No deployment data - fall back to custom input
-        if let Some(lbl) = label {
-            println!("{}", lbl);
-        } else {
-            print_header("Select Principal");
-        }
-        print!("Enter principal: ");
-        io::stdout().flush()?;
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
-        Principal::from_text(input.trim()).context("Failed to parse principal")
+async fn select_participant_or_custom() -> Result<Principal> {
+    let choices = principal_choices(app_context().try_deployment_data().as_deref())?;
+    if choices.is_empty() {
+        return prompt_principal(None).await;
     }
+    Selector::new(choices).run(resolve_principal).await
 }
 
 /// Select participant OR enter custom principal with optional label and neuron counts
 /// Shows participants (1-N) OR allows entering a custom principal
-/// If neuron_type is provided ("icp" or "sns"), displays neuron counts for each participant
+/// If neuron_type is provided ("icp" or "sns"), displays neuron counts for each choice
 async fn select_participant_or_custom_with_label_and_counts(
     label: Option<&str>,
     neuron_type: Option<&str>,
 ) -> Result<Principal> {
-    use crate::core::utils::data_output::SnsCreationData;
-
-    // Try to read deployment data
-    let deployment_path = crate::core::utils::data_output::get_output_path();
-
-    if deployment_path.exists() {
-        if let Ok(data_content) = std::fs::read_to_string(&deployment_path) {
-            if let Ok(deployment_data) = serde_json::from_str::<SnsCreationData>(&data_content) {
-                let owner_option = deployment_data.participants.len() + 1;
-                let custom_option = owner_option + 1;
-
-                if let Some(lbl) = label {
-                    println!("{}", lbl);
-                    println!();
-                }
-                println!("Available options:");
-                println!();
+    let mut choices = principal_choices(app_context().try_deployment_data().as_deref())?;
+    if choices.is_empty() {
+        return prompt_principal(label).await;
+    }
 
-                // Fetch neuron counts if neuron_type is provided
-                let mut participant_counts: Vec<usize> = Vec::new();
-                let mut owner_count: Option<usize> = None;
-
-                if let Some(ntype) = neuron_type {
-                    // Fetch counts for participants
-                    for participant in &deployment_data.participants {
-                        let principal = Principal::from_text(&participant.principal)
-                            .context("Failed to parse participant principal")?;
-                        let count = match ntype {
-                            "icp" => {
-                                use crate::core::ops::governance_ops::list_icp_neurons_for_principal_default_path;
-                                list_icp_neurons_for_principal_default_path(principal)
-                                    .await
-                                    .map(|neurons| neurons.len())
-                                    .unwrap_or(0)
-                            }
-                            "sns" => {
-                                use crate::core::ops::sns_governance_ops::list_neurons_for_principal_default_path;
-                                list_neurons_for_principal_default_path(principal)
-                                    .await
-                                    .map(|neurons| neurons.len())
-                                    .unwrap_or(0)
-                            }
-                            _ => 0,
-                        };
-                        participant_counts.push(count);
-                    }
+    // One list_neurons call per principal, all concurrently
+    if let Some(ntype) = neuron_type {
+        let kind = if ntype == "icp" { "ICP" } else { "SNS" };
+        let spinner = Spinner::start("Loading neuron counts...");
+        let counts = futures::future::join_all(
+            choices
+                .iter()
+                .map(|choice| fetch_neuron_count(choice.principal, ntype)),
+        )
+        .await;
+        spinner.finish();
+        for (choice, count) in choices.iter_mut().zip(counts) {
+            choice.notes.push(format!("{count} {kind} neuron(s)"));
+        }
+    }
 
-                    // Fetch count for owner
-                    let owner_principal = Principal::from_text(&deployment_data.owner_principal)
-                        .context("Failed to parse owner principal")?;
-                    owner_count = Some(match ntype {
-                        "icp" => {
-                            use crate::core::ops::governance_ops::list_icp_neurons_for_principal_default_path;
-                            list_icp_neurons_for_principal_default_path(owner_principal)
-                                .await
-                                .map(|neurons| neurons.len())
-                                .unwrap_or(0)
-                        }
-                        "sns" => {
-                            use crate::core::ops::sns_governance_ops::list_neurons_for_principal_default_path;
-                            list_neurons_for_principal_default_path(owner_principal)
-                                .await
-                                .map(|neurons| neurons.len())
-                                .unwrap_or(0)
-                        }
-                        _ => 0,
-                    });
-                }
+    Selector::new(choices)
+        .title(label)
+        .run(resolve_principal)
+        .await
+}
 
-                // Show participants first
-                let neuron_type_label = neuron_type.map(|nt| match nt {
-                    "icp" => "ICP",
-                    "sns" => "SNS",
-                    _ => "",
-                });
+/// Picker choices: swap participants, extra identities (see `identity new`), the owner,
+/// then principals with a registered key file (see `identity keys`) and named dfx identities,
+/// each found by its names and aliases; without deployment data, the dfx identity as owner,
+/// the registered keys and the named dfx identities
+fn principal_choices(data: Option<&SnsCreationData>) -> Result<Vec<Choice>> {
+    use crate::core::ops::identity::{DFX_PREFIX, dfx_principal, plain_dfx_identities};
+    use crate::core::utils::key_registry::KeyRegistry;
+
+    let mut choices = match data {
+        Some(data) => deployment_choices(data)?,
+        None => dfx_principal()
+            .map(|principal| {
+                let mut owner = Choice::new(principal);
+                owner.keys.push("owner".to_string());
+                owner.notes.push("dfx identity".to_string());
+                vec![owner]
+            })
+            .unwrap_or_default(),
+    };
+    for entry in KeyRegistry::load()?.keys {
+        let Ok(principal) = Principal::from_text(&entry.principal) else {
+            continue;
+        };
+        if choices.iter().all(|choice| choice.principal != principal) {
+            let mut choice = Choice::new(principal);
+            choice.notes.push("registered key".to_string());
+            choices.push(choice);
+        }
+    }
+    for (name, principal) in plain_dfx_identities() {
+        let index = match choices
+            .iter()
+            .position(|choice| choice.principal == principal)
+        {
+            Some(index) => index,
+            None => {
+                choices.push(Choice::new(principal));
+                choices.len() - 1
+            }
+        };
+        choices[index].keys.push(format!("{DFX_PREFIX}{name}"));
+        choices[index].notes.push(format!("dfx: {name}"));
+    }
+    Ok(choices)
+}
 
-                for (i, participant) in deployment_data.participants.iter().enumerate() {
-                    if let Some(count) = participant_counts.get(i) {
-                        if let Some(label) = neuron_type_label {
-                            println!(
-                                "  [{}] {} ({} {} neuron(s))",
-                                i + 1,
-                                participant.principal,
-                                count,
-                                label
-                            );
-                        } else {
-                            println!("  [{}] {}", i + 1, participant.principal);
-                        }
-                    } else {
-                        println!("  [{}] {}", i + 1, participant.principal);
-                    }
-                }
-                // Show owner before custom principal
-                if let Some(count) = owner_count {
-                    if let Some(label) = neuron_type_label {
-                        println!(
-                            "  [{}] {} (SNS proposer, {} {} neuron(s))",
-                            owner_option, deployment_data.owner_principal, count, label
-                        );
-                    } else {
-                        println!(
-                            "  [{}] {} (SNS proposer)",
-                            owner_option, deployment_data.owner_principal
-                        );
-                    }
-                } else {
-                    println!(
-                        "  [{}] {} (SNS proposer)",
-                        owner_option, deployment_data.owner_principal
-                    );
-                }
-                println!("  [{}] Enter custom principal", custom_option);
-                println!("  [{}] Go back to main menu", custom_option + 1);
-                println!();
-                print!(
-                    "Select option number (1-{}), press Enter/[b]ack to go back, or enter principal: ",
-                    custom_option + 1
-                );
-                io::stdout().flush()?;
-
-                let mut input = String::new();
-                io::stdin().read_line(&mut input)?;
-                let input_trimmed_lower = input.trim().to_lowercase();
-                let input_trimmed = input.trim();
-
-                // Check for navigation commands
-                if input_trimmed_lower == "b"
-                    || input_trimmed_lower == "back"
-                    || input_trimmed.is_empty()
-                {
-                    anyhow::bail!("User went to main menu");
+/// Picker choices from the deployment data (see `principal_choices`)
+fn deployment_choices(data: &SnsCreationData) -> Result<Vec<Choice>> {
+    let mut choices = data
+        .selectable_principals()
+        .into_iter()
+        .enumerate()
+        .map(|(i, (principal, name))| {
+            let mut choice = Choice::new(
+                Principal::from_text(&principal)
+                    .context("Failed to parse participant principal")?,
+            );
+            match name {
+                Some(name) => {
+                    choice.notes.push(format!("identity: {name}"));
+                    choice.keys.push(name);
                 }
+                None => choice.keys.push(format!("participant-{}", i + 1)),
+            }
+            Ok(choice)
+        })
+        .collect::<Result<Vec<_>>>()?;
 
-                // Check if input looks like a principal (contains dashes, typical format)
-                // Principals typically have 5 dashes and are 63 characters long
-                if input_trimmed.contains('-') && input_trimmed.len() > 20 {
-                    // Try to parse as principal directly
-                    match Principal::from_text(input_trimmed) {
-                        Ok(principal) => return Ok(principal),
-                        Err(e) => {
-                            // If principal parsing fails, check if it's a number
-                            // Otherwise return the error
-                            if input_trimmed.parse::<usize>().is_ok() {
-                                // It's actually a number, continue to number parsing below
-                            } else {
-                                return Err(anyhow::anyhow!("Failed to parse principal: {}", e));
-                            }
-                        }
-                    }
-                }
+    let mut owner = Choice::new(
+        Principal::from_text(&data.owner_principal).context("Failed to parse owner principal")?,
+    );
+    owner.keys.push("owner".to_string());
+    owner.notes.push("SNS proposer".to_string());
+    choices.push(owner);
+
+    for choice in &mut choices {
+        let principal = choice.principal.to_text();
+        let aliases: Vec<String> = data
+            .aliases
+            .iter()
+            .filter(|a| a.principal == principal)
+            .map(|a| a.name.clone())
+            .collect();
+        if !aliases.is_empty() {
+            choice.notes.push(format!("alias: {}", aliases.join(", ")));
+            choice.keys.extend(aliases);
+        }
+    }
+    Ok(choices)
+}
 
-                // Try to parse as number
-                match input_trimmed.parse::<usize>() {
-                    Ok(selection) => {
-                        if selection == custom_option + 1 {
-                            // Go back to main menu option
-                            anyhow::bail!("User went to main menu");
-                        }
-                        if selection < 1 || selection > custom_option {
-                            anyhow::bail!(
-                                "Invalid selection. Please choose a number between 1 and {}",
-                                custom_option + 1
-                            );
-                        }
+/// Principal prompt without deployment data, when there is nothing to list
+async fn prompt_principal(label: Option<&str>) -> Result<Principal> {
+    match label {
+        Some(lbl) => println!("{lbl}"),
+        None => print_header("Select Principal"),
+    }
+    let input = read_line("Enter principal: ").await?;
+    resolve_principal(input.trim()).context("Failed to parse principal")
+}
 
-                        if selection == custom_option {
-                            // Custom principal option
-                            let principal_input = read_input_required(
-                                "Enter principal (or press Enter/[b]ack to go back): ",
-                            )
-                            .map_err(navigation_to_anyhow)?;
-                            Principal::from_text(&principal_input)
-                                .context("Failed to parse principal")
-                        } else if selection == owner_option {
-                            // Owner (SNS proposer)
-                            Principal::from_text(&deployment_data.owner_principal)
-                                .context("Failed to parse owner principal")
-                        } else {
-                            // Participant (selection is 1-based, participants array is 0-based)
-                            Principal::from_text(
-                                &deployment_data.participants[selection - 1].principal,
-                            )
-                            .context("Failed to parse selected participant principal")
-                        }
-                    }
-                    Err(_) => {
-                        // Not a number, try to parse as principal anyway
-                        Principal::from_text(input_trimmed).context("Failed to parse principal")
-                    }
-                }
-            } else {
-                // Deployment data exists but can't parse - fall back to custom input
-                if let Some(lbl) = label {
-                    println!("{}", lbl);
-                } else {
-                    print_header("Select Principal");
-                }
-                print!("Enter principal: ");
-                io::stdout().flush()?;
-                let mut input = String::new();
-                io::stdin().read_line(&mut input)?;
-                Principal::from_text(input.trim()).context("Failed to parse principal")
-            }
-        } else {
-            // Can't read deployment data - fall back to custom input
-            if let Some(lbl) = label {
-                println!("{}", lbl);
-            } else {
-                print_header("Select Principal");
-            }
-            print!("Enter principal: ");
-            io::stdout().flush()?;
-            let mut input = String::new();
-            io::stdin().read_line(&mut input)?;
-            Principal::from_text(input.trim()).context("Failed to parse principal")
-        }
-    } else {
-        // No deployment data - fall back to custom input
-        if let Some(lbl) = label {
-            println!("{}", lbl);
-        } else {
-            print_header("Select Principal");
-        }
-        print!("Enter principal: ");
-        io::stdout().flush()?;
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
-        Principal::from_text(input.trim()).context("Failed to parse principal")
+/// Number of ICP or SNS neurons of a principal (0 if the lookup fails)
+async fn fetch_neuron_count(principal: Principal, neuron_type: &str) -> usize {
+    match neuron_type {
+        "icp" => list_icp_neurons_for_principal_default_path(principal)
+            .await
+            .map(|neurons| neurons.len())
+            .unwrap_or(0),
+        "sns" => list_neurons_for_principal_default_path(principal)
+            .await
+            .map(|neurons| neurons.len())
+            .unwrap_or(0),
+        _ => 0,
     }
 }
 
@@ -446,7 +225,7 @@ async fn select_participant_with_back_handling(
     label: Option<&str>,
     neuron_type: Option<&str>,
 ) -> Result<Principal> {
-    select_participant_or_custom_with_label_and_counts(label, neuron_type)
+    let principal = select_participant_or_custom_with_label_and_counts(label, neuron_type)
         .await
         .map_err(|e| {
             if is_user_went_back_error(&e) {
@@ -454,43 +233,22 @@ async fn select_participant_with_back_handling(
             } else {
                 e
             }
-        })
-}
-
-/// Unified error type for user navigation
-#[derive(Debug)]
-pub enum UserNavigation {
-    GoBack,
-    GoToMainMenu,
-}
-
-impl std::fmt::Display for UserNavigation {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            UserNavigation::GoBack => write!(f, "User went back"),
-            UserNavigation::GoToMainMenu => write!(f, "User went to main menu"),
-        }
-    }
+        })?;
+    copy_selected("principal", &principal.to_text());
+    Ok(principal)
 }
 
-impl std::error::Error for UserNavigation {}
-
 /// Helper to read input with navigation support
 /// Returns Ok(Some(String)) if input is valid, Ok(None) if empty input is allowed
 /// Returns Err(UserNavigation::GoToMainMenu) if Enter is pressed (empty input) or "b"/"back" is entered
 /// Returns Err(UserNavigation::GoBack) if "b"/"back" is entered (for multi-step flows)
-fn read_input_with_navigation(
+async fn read_input_with_navigation(
     prompt: &str,
     allow_empty: bool,
 ) -> Result<Option<String>, UserNavigation> {
-    print!("{}", prompt);
-    if let Err(_) = io::stdout().flush() {
-        return Err(UserNavigation::GoToMainMenu);
-    }
-    let mut input = String::new();
-    if let Err(_) = io::stdin().read_line(&mut input) {
+    let Ok(input) = read_line(prompt).await else {
         return Err(UserNavigation::GoToMainMenu);
-    }
+    };
     let input_trimmed = input.trim().to_lowercase();
 
     // Check for navigation commands
@@ -514,8 +272,8 @@ fn read_input_with_navigation(
 /// Helper to read input with navigation support (non-empty required)
 /// Returns Ok(String) if input is valid
 /// Returns Err(UserNavigation::GoToMainMenu) if Enter is pressed or "b"/"back" is entered
-fn read_input_required(prompt: &str) -> Result<String, UserNavigation> {
-    match read_input_with_navigation(prompt, false)? {
+async fn read_input_required(prompt: &str) -> Result<String, UserNavigation> {
+    match read_input_with_navigation(prompt, false).await? {
         Some(s) => Ok(s),
         None => Err(UserNavigation::GoToMainMenu),
     }
@@ -524,8 +282,8 @@ fn read_input_required(prompt: &str) -> Result<String, UserNavigation> {
 /// Helper to read input with navigation support (empty allowed)
 /// Returns Ok(Some(String)) if input is valid, Ok(None) if empty
 /// Returns Err(UserNavigation::GoBack) if "b"/"back" is entered
-fn read_input_optional(prompt: &str) -> Result<Option<String>, UserNavigation> {
-    read_input_with_navigation(prompt, true)
+async fn read_input_optional(prompt: &str) -> Result<Option<String>, UserNavigation> {
+    read_input_with_navigation(prompt, true).await
 }
 
 /// Convert navigation error to anyhow error
@@ -533,27 +291,36 @@ fn navigation_to_anyhow(nav: UserNavigation) -> anyhow::Error {
     anyhow::anyhow!("{}", nav)
 }
 
-/// Helper function to select a neuron interactively for a given principal
-async fn select_neuron(principal: Principal) -> Result<Vec<u8>> {
-    use crate::core::ops::sns_governance_ops::list_neurons_for_principal_default_path;
-
-    print_header("Select SNS Neuron");
-    print_info(&format!("Principal: {}", principal));
-
-    let neurons = list_neurons_for_principal_default_path(principal)
-        .await
-        .context("Failed to list neurons")?;
-
+/// Show the neuron table of `principal` and let the user pick a neuron by number
+/// Without neurons, explains how to create one and goes back to the main menu
+async fn pick_neuron<'a, N: NeuronView>(
+    principal: Principal,
+    neurons: &'a [N],
+    params: &N::Params,
+) -> Result<&'a N> {
     if neurons.is_empty() {
+        let kind = N::KIND.to_lowercase();
         println!();
-        print_warning(&format!("No SNS neurons found for principal {}", principal));
+        print_warning(&format!(
+            "No {} neurons found for principal {}",
+            N::KIND,
+            principal
+        ));
         println!();
-        println!("This principal does not have any SNS neurons.");
+        println!("This principal does not have any {} neurons.", N::KIND);
         println!("You can:");
-        println!("  1. Create an SNS neuron first using 'create-sns-neuron'");
-        println!("  2. Select a different principal that has SNS neurons");
+        println!(
+            "  1. Create an {} neuron first using 'create-{}-neuron'",
+            N::KIND,
+            kind
+        );
+        println!(
+            "  2. Select a different principal that has {} neurons",
+            N::KIND
+        );
         println!();
         let _ = read_input_required("Press Enter to go back to main menu: ")
+            .await
             .map_err(navigation_to_anyhow);
         anyhow::bail!("User went to main menu");
     }
@@ -561,109 +328,113 @@ async fn select_neuron(principal: Principal) -> Result<Vec<u8>> {
     print_success(&format!("Found {} neuron(s)", neurons.len()));
     println!();
 
-    // Print table header
-    println!("{:-<100}", "");
-    println!(
-        "{:<5} {:<20} {:<20} {:<25} {:<30}",
-        "#", "Neuron ID", "Stake (e8s)", "Dissolve Delay", "Permissions"
-    );
-    println!("{:-<100}", "");
-
-    for (index, neuron) in neurons.iter().enumerate() {
-        // Neuron ID (hex) - use short format like e35f1b8...518559ea
-        let neuron_id_display = if let Some(id) = &neuron.id {
-            let hex_id = hex::encode(&id.id);
-            if hex_id.len() >= 15 {
-                // Show first 7 chars + ... + last 8 chars
-                format!("{}...{}", &hex_id[..7], &hex_id[hex_id.len() - 8..])
-            } else {
-                hex_id
-            }
-        } else {
-            "<none>".to_string()
-        };
+    neuron_table(neurons, params).print();
+    println!();
+    let input = loop {
+        let input = read_input_required(&format!(
+            "Select neuron number (1-{}), c<number> to copy its ID, or press Enter/[b]ack to go back: ",
+            neurons.len()
+        )).await
+        .map_err(navigation_to_anyhow)?;
+        match copy_request(&input, neurons.len()) {
+            Some(index) => copy_neuron_id(&neurons[index]),
+            None => break input,
+        }
+    };
 
-        // Stake
-        let stake_str = format!("{}", neuron.cached_neuron_stake_e8s);
+    let selection: usize = input
+        .parse()
+        .context(ValidationError::new("Invalid selection - must be a number"))?;
 
-        // Dissolve delay
-        let dissolve_delay_str = match &neuron.dissolve_state {
-            Some(crate::core::declarations::sns_governance::DissolveState::DissolveDelaySeconds(seconds)) => {
-                let days = *seconds / 86400;
-                format!("{} days ({}s)", days, seconds)
-            }
-            Some(crate::core::declarations::sns_governance::DissolveState::WhenDissolvedTimestampSeconds(timestamp)) => {
-                format!("Dissolving (dissolves at {})", timestamp)
-            }
-            None => "No state".to_string(),
-        };
+    if selection < 1 || selection > neurons.len() {
+        return Err(invalid(format!(
+            "Invalid selection. Please choose a number between 1 and {}",
+            neurons.len()
+        )));
+    }
+    let neuron = &neurons[selection - 1];
+    if let Some(id) = neuron.id_text() {
+        copy_selected("neuron ID", &id);
+    }
+    Ok(neuron)
+}
 
-        // Permissions - summarize all permission types across all principals, use numeric values
-        let mut all_permissions: Vec<i32> = Vec::new();
-        for perm in &neuron.permissions {
-            all_permissions.extend(&perm.permission_type);
-        }
-        all_permissions.sort();
-        all_permissions.dedup();
-        let perm_str = if all_permissions.is_empty() {
-            "None".to_string()
-        } else {
-            all_permissions
-                .iter()
-                .map(|p| p.to_string())
-                .collect::<Vec<_>>()
-                .join(",")
-        };
+/// Index of the row a `c<number>` menu input asks to copy, if the input is one
+fn copy_request(input: &str, rows: usize) -> Option<usize> {
+    let number: usize = input.strip_prefix(['c', 'C'])?.trim().parse().ok()?;
+    (1..=rows).contains(&number).then(|| number - 1)
+}
 
-        // Truncate dissolve delay if too long for table formatting
-        let dissolve_delay_display = if dissolve_delay_str.len() > 18 {
-            format!("{}...", &dissolve_delay_str[..18])
-        } else {
-            dissolve_delay_str
-        };
+/// Copy the full ID of a neuron picked from a menu with `c<number>`
+fn copy_neuron_id<N: NeuronView>(neuron: &N) {
+    match neuron.id_text() {
+        Some(id) => copy_and_report(&format!("neuron ID {}", short_id(&id)), &id),
+        None => print_warning("This neuron has no ID"),
+    }
+}
 
-        println!(
-            "{:<5} {:<20} {:<20} {:<25} {:<30}",
-            index + 1,
-            neuron_id_display,
-            stake_str,
-            dissolve_delay_display,
-            perm_str
-        );
+/// After a neuron listing, offer to show the full details of one of the neurons
+async fn prompt_neuron_details<N: NeuronView>(neurons: &[N]) -> Result<()> {
+    if neurons.is_empty() {
+        return Ok(());
     }
 
-    println!("{:-<100}", "");
     println!();
-    let input = read_input_required(&format!(
-        "Select neuron number (1-{}) or press Enter/[b]ack to go back: ",
-        neurons.len()
-    ))
-    .map_err(navigation_to_anyhow)?;
+    let input = loop {
+        let input = read_line(&format!(
+            "Enter neuron number to see full details (1-{}), c<number> to copy its ID, or press Enter to skip: ",
+            neurons.len()
+        ))
+        .await?;
+        match copy_request(input.trim(), neurons.len()) {
+            Some(index) => copy_neuron_id(&neurons[index]),
+            None => break input,
+        }
+    };
+    let selection = input.trim();
+    if selection.is_empty() {
+        return Ok(());
+    }
 
-    let selection: usize = input
+    let selection_num: usize = selection
         .parse()
-        .context("Invalid selection - must be a number")?;
-
-    if selection < 1 || selection > neurons.len() {
-        anyhow::bail!(
+        .context(ValidationError::new("Invalid selection - must be a number"))?;
+    if selection_num < 1 || selection_num > neurons.len() {
+        eprintln!(
             "Invalid selection. Please choose a number between 1 and {}",
             neurons.len()
         );
+        return Ok(());
     }
+    print_neuron_details(&neurons[selection_num - 1]);
+    Ok(())
+}
 
-    let selected_neuron = &neurons[selection - 1];
-    if let Some(id) = &selected_neuron.id {
-        Ok(id.id.clone())
-    } else {
-        anyhow::bail!("Selected neuron has no ID")
-    }
+/// Helper function to select a neuron interactively for a given principal
+async fn select_neuron(principal: Principal) -> Result<Vec<u8>> {
+    use crate::core::ops::sns_governance_ops::list_neurons_for_principal_default_path;
+
+    print_header("Select SNS Neuron");
+    print_info(&format!("Principal: {}", principal));
+
+    let neurons = list_neurons_for_principal_default_path(principal)
+        .await
+        .context("Failed to list neurons")?;
+    let params = get_voting_power_params_default_path().await?;
+
+    let neuron = pick_neuron(principal, &neurons, &params).await?;
+    neuron
+        .id
+        .as_ref()
+        .map(|id| id.id.clone())
+        .ok_or_else(|| anyhow::anyhow!("Selected neuron has no ID"))
 }
 
 /// Handle add-hotkey command
 pub async fn handle_add_hotkey(args: &[String]) -> Result<()> {
     if args.len() < 3 {
         print_add_hotkey_usage(&args[0]);
-        std::process::exit(1);
+        return Err(invalid("add-hotkey requires a neuron type: sns or icp"));
     }
 
     let neuron_type = &args[2];
@@ -672,7 +443,7 @@ pub async fn handle_add_hotkey(args: &[String]) -> Result<()> {
         "sns" => {
             // Step 1: Get owner principal (select if not provided)
             let owner_principal = if args.len() >= 4 {
-                Principal::from_text(&args[3]).context("Failed to parse owner principal")?
+                resolve_principal(&args[3]).context("Failed to parse owner principal")?
             } else {
                 match select_participant_with_back_handling(None, Some("sns")).await {
                     Ok(p) => p,
@@ -685,29 +456,20 @@ pub async fn handle_add_hotkey(args: &[String]) -> Result<()> {
             let (neuron_id, hotkey_principal, permissions) = if args.len() >= 5 {
                 let arg4 = &args[4];
 
-                // Check if arg4 looks like a neuron_id (hex string)
-                let looks_like_neuron_id = (arg4.starts_with("0x") && arg4.len() > 10)
-                    || (!arg4.contains(',')
-                        && !arg4.contains('-') // Principal contains dashes
-                        && arg4.chars().all(|c| c.is_ascii_hexdigit())
-                        && arg4.len() > 8);
-
-                if looks_like_neuron_id {
+                if looks_like_sns_neuron_id(arg4) {
                     // arg4 is neuron_id
-                    let hex_str = arg4.strip_prefix("0x").unwrap_or(arg4);
-                    let neuron_id_val =
-                        Some(hex::decode(hex_str).context("Failed to decode neuron_id from hex")?);
+                    let neuron_id_val = Some(parse_sns_neuron_id(owner_principal, arg4).await?);
 
                     // Get hotkey_principal from next arg
                     let hotkey = if args.len() >= 6 {
-                        Principal::from_text(&args[5])
-                            .context("Failed to parse hotkey principal")?
+                        resolve_principal(&args[5]).context("Failed to parse hotkey principal")?
                     } else {
                         let input = read_input_required(
                             "Enter hotkey principal (or press Enter/[b]ack to go back): ",
                         )
+                        .await
                         .map_err(navigation_to_anyhow)?;
-                        Principal::from_text(&input).context("Failed to parse hotkey principal")?
+                        resolve_principal(&input).context("Failed to parse hotkey principal")?
                     };
 
                     // Check for permissions
@@ -731,7 +493,7 @@ pub async fn handle_add_hotkey(args: &[String]) -> Result<()> {
                 } else {
                     // arg4 is hotkey_principal, need to select neuron
                     let hotkey =
-                        Principal::from_text(arg4).context("Failed to parse hotkey principal")?;
+                        resolve_principal(arg4).context("Failed to parse hotkey principal")?;
                     let neuron_id_val = match select_neuron(owner_principal).await {
                         Ok(id) => id,
                         Err(e) if is_user_cancelled_error(&e) || is_user_went_back_error(&e) => {
@@ -766,12 +528,9 @@ pub async fn handle_add_hotkey(args: &[String]) -> Result<()> {
                     Err(e) => return Err(e),
                 };
 
-                print!("Enter hotkey principal: ");
-                io::stdout().flush()?;
-                let mut input = String::new();
-                io::stdin().read_line(&mut input)?;
-                let hotkey = Principal::from_text(input.trim())
-                    .context("Failed to parse hotkey principal")?;
+                let input = read_line("Enter hotkey principal: ").await?;
+                let hotkey =
+                    resolve_principal(input.trim()).context("Failed to parse hotkey principal")?;
 
                 (Some(neuron_id_val), hotkey, None)
             };
@@ -780,16 +539,7 @@ pub async fn handle_add_hotkey(args: &[String]) -> Result<()> {
             print_info(&format!("Participant: {}", owner_principal));
             print_info(&format!("Hotkey: {}", hotkey_principal));
             if let Some(ref id) = neuron_id {
-                let hex_id = hex::encode(id);
-                if hex_id.len() >= 15 {
-                    print_info(&format!(
-                        "Neuron ID: {}...{}",
-                        &hex_id[..7],
-                        &hex_id[hex_id.len() - 8..]
-                    ));
-                } else {
-                    print_info(&format!("Neuron ID: {}", hex_id));
-                }
+                print_info(&format!("Neuron ID: {}", short_id(&hex::encode(id))));
             } else {
                 print_info("Neuron ID: Auto-selecting (longest dissolve delay)");
             }
@@ -820,7 +570,7 @@ pub async fn handle_add_hotkey(args: &[String]) -> Result<()> {
                     }
                 } else {
                     // arg3 is principal
-                    Principal::from_text(arg3).context("Failed to parse principal")?
+                    resolve_principal(arg3).context("Failed to parse principal")?
                 }
             } else {
                 match select_participant_with_back_handling(None, Some("icp")).await {
@@ -841,21 +591,21 @@ pub async fn handle_add_hotkey(args: &[String]) -> Result<()> {
 
                     // Get hotkey_principal from next arg
                     let hotkey = if args.len() >= 5 {
-                        Principal::from_text(&args[4])
-                            .context("Failed to parse hotkey principal")?
+                        resolve_principal(&args[4]).context("Failed to parse hotkey principal")?
                     } else {
                         let input = read_input_required(
                             "Enter hotkey principal (or press Enter/[b]ack to go back): ",
                         )
+                        .await
                         .map_err(navigation_to_anyhow)?;
-                        Principal::from_text(&input).context("Failed to parse hotkey principal")?
+                        resolve_principal(&input).context("Failed to parse hotkey principal")?
                     };
 
                     (neuron_id_val, hotkey)
                 } else {
                     // arg3 is hotkey_principal, need to select neuron
                     let hotkey =
-                        Principal::from_text(arg3).context("Failed to parse hotkey principal")?;
+                        resolve_principal(arg3).context("Failed to parse hotkey principal")?;
                     let neuron_id_val = match select_icp_neuron(principal).await {
                         Ok(id) => id,
                         Err(e) if is_user_cancelled_error(&e) || is_user_went_back_error(&e) => {
@@ -873,12 +623,9 @@ pub async fn handle_add_hotkey(args: &[String]) -> Result<()> {
                     Err(e) => return Err(e),
                 };
 
-                print!("Enter hotkey principal: ");
-                io::stdout().flush()?;
-                let mut input = String::new();
-                io::stdin().read_line(&mut input)?;
-                let hotkey = Principal::from_text(input.trim())
-                    .context("Failed to parse hotkey principal")?;
+                let input = read_line("Enter hotkey principal: ").await?;
+                let hotkey =
+                    resolve_principal(input.trim()).context("Failed to parse hotkey principal")?;
 
                 (neuron_id_val, hotkey)
             };
@@ -891,49 +638,16 @@ pub async fn handle_add_hotkey(args: &[String]) -> Result<()> {
 
             // Use the low-level function with principal-based identity loading
             use crate::core::ops::governance_ops::add_hotkey_to_icp_neuron;
-            use crate::core::ops::identity::{
-                create_agent, load_dfx_identity, load_identity_from_seed_file,
-            };
-            use crate::core::utils::constants::GOVERNANCE_CANISTER;
-            use crate::core::utils::data_output::SnsCreationData;
-
-            // Load identity based on principal
-            let deployment_path = crate::core::utils::data_output::get_output_path();
-            let identity = if deployment_path.exists() {
-                if let Ok(data_content) = std::fs::read_to_string(&deployment_path) {
-                    if let Ok(deployment_data) =
-                        serde_json::from_str::<SnsCreationData>(&data_content)
-                    {
-                        // Check if principal matches owner or any participant
-                        if principal.to_text() == deployment_data.owner_principal {
-                            load_dfx_identity(None).context("Failed to load owner dfx identity")?
-                        } else if let Some(participant) = deployment_data
-                            .participants
-                            .iter()
-                            .find(|p| p.principal == principal.to_text())
-                        {
-                            use std::path::PathBuf;
-                            load_identity_from_seed_file(&PathBuf::from(&participant.seed_file))
-                                .context("Failed to load participant identity")?
-                        } else {
-                            // Custom principal, try dfx identity
-                            load_dfx_identity(None).context("Failed to load dfx identity")?
-                        }
-                    } else {
-                        load_dfx_identity(None).context("Failed to load dfx identity")?
-                    }
-                } else {
-                    load_dfx_identity(None).context("Failed to load dfx identity")?
-                }
-            } else {
-                load_dfx_identity(None).context("Failed to load dfx identity")?
-            };
 
-            let agent = create_agent(identity)
+            // Load identity (owner, participant or extra identity)
+            let identity = identity_for_principal(signing_principal(principal))?;
+
+            let agent = app_context()
+                .agent(identity)
                 .await
                 .context("Failed to create agent")?;
-            let governance_canister = Principal::from_text(GOVERNANCE_CANISTER)
-                .context("Failed to parse ICP Governance canister ID")?;
+            let governance_canister =
+                crate::core::utils::network::network_config().governance_canister;
 
             add_hotkey_to_icp_neuron(&agent, governance_canister, neuron_id, hotkey_principal)
                 .await
@@ -942,15 +656,18 @@ pub async fn handle_add_hotkey(args: &[String]) -> Result<()> {
             print_success("Hotkey added successfully!");
             Ok(())
         }
-        _ => {
-            eprintln!("Unknown neuron type: {}. Use 'sns' or 'icp'", neuron_type);
-            std::process::exit(1);
-        }
+        _ => Err(invalid(format!(
+            "Unknown neuron type: {neuron_type}. Use 'sns' or 'icp'"
+        ))),
     }
 }
 
 /// Handle list-sns-neurons command
 pub async fn handle_list_neurons(args: &[String]) -> Result<()> {
+    let mut args = args.to_vec();
+    let watch = take_watch_interval(&mut args)?;
+    let listing = NeuronListing::take(&mut args, || app_context().sns_ledger_canister()).await?;
+
     let principal = if args.len() < 3 {
         // No principal provided - show participant selection or custom (includes owner)
         match select_participant_with_back_handling(None, Some("sns")).await {
@@ -959,9 +676,28 @@ pub async fn handle_list_neurons(args: &[String]) -> Result<()> {
             Err(e) => return Err(e),
         }
     } else {
-        Principal::from_text(&args[2]).context("Failed to parse principal")?
+        resolve_principal(&args[2]).context("Failed to parse principal")?
     };
 
+    if let Some(interval) = watch {
+        let listing = &listing;
+        return watch_loop(interval, || async move {
+            print_sns_neuron_table(principal, listing).await.map(|_| ())
+        })
+        .await;
+    }
+
+    let neurons = print_sns_neuron_table(principal, &listing).await?;
+
+    // Ask if user wants to see details for a specific neuron
+    prompt_neuron_details(&neurons).await
+}
+
+/// Print the SNS neuron table of a principal and return the neurons shown
+async fn print_sns_neuron_table(
+    principal: Principal,
+    listing: &NeuronListing,
+) -> Result<Vec<crate::core::declarations::sns_governance::Neuron>> {
     print_header("Listing SNS Neurons");
     print_info(&format!("Principal: {}", principal));
 
@@ -971,293 +707,38 @@ pub async fn handle_list_neurons(args: &[String]) -> Result<()> {
 
     if neurons.is_empty() {
         print_warning("No neurons found for this principal");
-        return Ok(());
+        return Ok(neurons);
+    }
+    let neurons = listing.apply(neurons).await;
+    if neurons.is_empty() {
+        print_warning("No neurons match the filters");
+        return Ok(neurons);
     }
 
     print_success(&format!("Found {} neuron(s)", neurons.len()));
     println!();
 
-    // Print table header
-    println!("{:-<100}", "");
-    println!(
-        "{:<5} {:<20} {:<20} {:<25} {:<30}",
-        "#", "Neuron ID", "Stake (e8s)", "Dissolve Delay", "Permissions"
-    );
-    println!("{:-<100}", "");
-
-    for (index, neuron) in neurons.iter().enumerate() {
-        // Neuron ID (hex) - use short format like e35f1b8...518559ea
-        let neuron_id_display = if let Some(id) = &neuron.id {
-            let hex_id = hex::encode(&id.id);
-            if hex_id.len() >= 15 {
-                // Show first 7 chars + ... + last 8 chars
-                format!("{}...{}", &hex_id[..7], &hex_id[hex_id.len() - 8..])
-            } else {
-                hex_id
-            }
-        } else {
-            "<none>".to_string()
-        };
-
-        // Stake
-        let stake_str = format!("{}", neuron.cached_neuron_stake_e8s);
-
-        // Dissolve delay
-        let dissolve_delay_str = match &neuron.dissolve_state {
-            Some(super::super::declarations::sns_governance::DissolveState::DissolveDelaySeconds(seconds)) => {
-                let days = *seconds / 86400;
-                format!("{} days ({}s)", days, seconds)
-            }
-            Some(super::super::declarations::sns_governance::DissolveState::WhenDissolvedTimestampSeconds(timestamp)) => {
-                format!("Dissolving (dissolves at {})", timestamp)
-            }
-            None => "No state".to_string(),
-        };
+    let params = get_voting_power_params_default_path().await?;
+    neuron_table(&neurons, &params).print();
+    println!();
+    Ok(neurons)
+}
 
-        // Permissions - summarize all permission types across all principals, use numeric values
-        let mut all_permissions: Vec<i32> = Vec::new();
-        for perm in &neuron.permissions {
-            all_permissions.extend(&perm.permission_type);
-        }
-        all_permissions.sort();
-        all_permissions.dedup();
-        let perm_str = if all_permissions.is_empty() {
-            "None".to_string()
-        } else {
-            all_permissions
-                .iter()
-                .map(|p| p.to_string())
-                .collect::<Vec<_>>()
-                .join(",")
-        };
-
-        // Truncate dissolve delay if too long for table formatting
-        let dissolve_delay_display = if dissolve_delay_str.len() > 18 {
-            format!("{}...", &dissolve_delay_str[..18])
-        } else {
-            dissolve_delay_str
-        };
-
-        println!(
-            "{:<5} {:<20} {:<20} {:<25} {:<30}",
-            index + 1,
-            neuron_id_display,
-            stake_str,
-            dissolve_delay_display,
-            perm_str
-        );
-    }
-
-    println!("{:-<100}", "");
-    println!();
-
-    // Ask if user wants to see details for a specific neuron
-    if neurons.len() > 0 {
-        println!();
-        print!(
-            "Enter neuron number to see full details (1-{}) or press Enter to skip: ",
-            neurons.len()
-        );
-        io::stdout().flush()?;
-
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
-        let selection = input.trim();
-
-        if !selection.is_empty() {
-            let selection_num: usize = selection
-                .parse()
-                .context("Invalid selection - must be a number")?;
-            if selection_num < 1 || selection_num > neurons.len() {
-                eprintln!(
-                    "Invalid selection. Please choose a number between 1 and {}",
-                    neurons.len()
-                );
-                return Ok(());
-            }
-
-            let selected_neuron = &neurons[selection_num - 1];
-            display_neuron_details(selected_neuron);
-        }
-    }
-
-    Ok(())
-}
-
-/// Display full details for a single neuron
-fn display_neuron_details(neuron: &crate::core::declarations::sns_governance::Neuron) {
-    use crate::core::declarations::sns_governance::DissolveState;
-
-    print_header("Neuron Details");
-
-    // Neuron ID
-    if let Some(id) = &neuron.id {
-        let hex_id = hex::encode(&id.id);
-        print_info(&format!("Neuron ID: {}", hex_id));
-    } else {
-        print_info("Neuron ID: <none>");
-    }
-
-    // Stake information
-    println!();
-    print_info("Stake Information:");
-    println!("  Cached Stake: {} e8s", neuron.cached_neuron_stake_e8s);
-    if let Some(staked_maturity) = neuron.staked_maturity_e8s_equivalent {
-        println!("  Staked Maturity: {} e8s", staked_maturity);
-    }
-    println!("  Maturity: {} e8s", neuron.maturity_e8s_equivalent);
-
-    // Dissolve state
-    println!();
-    print_info("Dissolve State:");
-    match &neuron.dissolve_state {
-        Some(DissolveState::DissolveDelaySeconds(seconds)) => {
-            let days = *seconds / 86400;
-            let hours = (*seconds % 86400) / 3600;
-            println!("  Type: Dissolve Delay");
-            println!(
-                "  Delay: {} seconds ({} days, {} hours)",
-                seconds, days, hours
-            );
-        }
-        Some(DissolveState::WhenDissolvedTimestampSeconds(timestamp)) => {
-            println!("  Type: Dissolving");
-            println!("  Dissolves at timestamp: {}", timestamp);
-        }
-        None => {
-            println!("  Type: None");
-        }
-    }
-
-    // Aging
-    println!();
-    print_info("Aging:");
-    println!(
-        "  Aging since timestamp: {}",
-        neuron.aging_since_timestamp_seconds
-    );
-    println!("  Created timestamp: {}", neuron.created_timestamp_seconds);
-
-    // Voting power
-    println!();
-    print_info(&format!(
-        "Voting Power Multiplier: {}%",
-        neuron.voting_power_percentage_multiplier
-    ));
-
-    // Permissions
-    println!();
-    print_info("Permissions:");
-    if neuron.permissions.is_empty() {
-        println!("  None");
-    } else {
-        for perm in &neuron.permissions {
-            if let Some(principal) = &perm.principal {
-                println!("  Principal: {}", principal);
-                println!("    Permission Types: {:?}", perm.permission_type);
-            } else {
-                println!("  Unknown Principal:");
-                println!("    Permission Types: {:?}", perm.permission_type);
-            }
-        }
-    }
-
-    // Auto stake maturity
-    if let Some(auto_stake) = neuron.auto_stake_maturity {
-        println!();
-        print_info(&format!("Auto Stake Maturity: {}", auto_stake));
-    }
-
-    // Vesting
-    if let Some(vesting) = neuron.vesting_period_seconds {
-        println!();
-        print_info(&format!("Vesting Period: {} seconds", vesting));
-    }
-
-    // Disburse maturity in progress
-    if !neuron.disburse_maturity_in_progress.is_empty() {
-        println!();
-        print_info("Disburse Maturity In Progress:");
-        for disburse in &neuron.disburse_maturity_in_progress {
-            println!("  Amount: {} e8s", disburse.amount_e8s);
-            println!(
-                "  Timestamp: {}",
-                disburse.timestamp_of_disbursement_seconds
-            );
-            if let Some(account) = &disburse.account_to_disburse_to {
-                if let Some(owner) = &account.owner {
-                    println!("  Account Owner: {}", owner);
-                }
-            }
-        }
-    }
-
-    // Followees
-    if !neuron.followees.is_empty() {
-        println!();
-        print_info("Followees:");
-        for (function_id, followees) in &neuron.followees {
-            println!(
-                "  Function ID {}: {} followee(s)",
-                function_id,
-                followees.followees.len()
-            );
-        }
-    }
-
-    // Topic followees
-    if let Some(topic_followees) = &neuron.topic_followees {
-        if !topic_followees.topic_id_to_followees.is_empty() {
-            println!();
-            print_info("Topic Followees:");
-            for (topic_id, topic_data) in &topic_followees.topic_id_to_followees {
-                if let Some(topic) = &topic_data.topic {
-                    // Match on the Topic enum variant
-                    use crate::core::declarations::sns_governance::Topic;
-                    let topic_str = match topic {
-                        Topic::DappCanisterManagement => "DappCanisterManagement",
-                        Topic::DaoCommunitySettings => "DaoCommunitySettings",
-                        Topic::ApplicationBusinessLogic => "ApplicationBusinessLogic",
-                        Topic::CriticalDappOperations => "CriticalDappOperations",
-                        Topic::TreasuryAssetManagement => "TreasuryAssetManagement",
-                        Topic::Governance => "Governance",
-                        Topic::SnsFrameworkManagement => "SnsFrameworkManagement",
-                    };
-                    println!(
-                        "  Topic {} (ID {}): {} followee(s)",
-                        topic_str,
-                        topic_id,
-                        topic_data.followees.len()
-                    );
-                } else {
-                    println!(
-                        "  Topic ID {}: {} followee(s)",
-                        topic_id,
-                        topic_data.followees.len()
-                    );
-                }
-            }
-        }
-    }
-
-    println!();
-}
-
-/// Handle set-icp-visibility command
-pub async fn handle_set_icp_visibility(args: &[String]) -> Result<()> {
-    // Step 1: Get principal (select participant or custom if not provided)
-    let principal = if args.len() >= 3 {
-        // Check if arg2 is a principal or neuron_id
-        let arg2 = &args[2];
-        if let Ok(_id) = arg2.parse::<u64>() {
-            // arg2 is neuron_id, need to get principal
-            select_participant_or_custom()?
+/// Handle set-icp-visibility command
+pub async fn handle_set_icp_visibility(args: &[String]) -> Result<()> {
+    // Step 1: Get principal (select participant or custom if not provided)
+    let principal = if args.len() >= 3 {
+        // Check if arg2 is a principal or neuron_id
+        let arg2 = &args[2];
+        if let Ok(_id) = arg2.parse::<u64>() {
+            // arg2 is neuron_id, need to get principal
+            select_participant_or_custom().await?
         } else {
             // arg2 is principal
-            Principal::from_text(arg2).context("Failed to parse principal")?
+            resolve_principal(arg2).context("Failed to parse principal")?
         }
     } else {
-        select_participant_or_custom()?
+        select_participant_or_custom().await?
     };
 
     // Step 2: Get neuron_id (select if not provided)
@@ -1294,7 +775,7 @@ pub async fn handle_set_icp_visibility(args: &[String]) -> Result<()> {
             _ => {
                 eprintln!("Error: Invalid visibility value: {}", args[3]);
                 eprintln!("Use 'true' or 'false'");
-                anyhow::bail!("Invalid visibility value");
+                return Err(invalid("Invalid visibility value"));
             }
         }
     } else {
@@ -1304,18 +785,16 @@ pub async fn handle_set_icp_visibility(args: &[String]) -> Result<()> {
         println!("  [1] Public (visible to everyone)");
         println!("  [2] Private (only visible to controller)");
         println!();
-        print!("Select option (1 or 2, default: 2): ");
-        io::stdout().flush()?;
-
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
+        let input = read_line("Select option (1 or 2, default: 2): ").await?;
         let input = input.trim().to_lowercase();
 
         match input.as_str() {
             "1" | "public" | "true" | "yes" => true,
             "2" | "private" | "false" | "no" | "" => false,
             _ => {
-                anyhow::bail!("Invalid selection. Use 1 for public or 2 for private.");
+                return Err(invalid(
+                    "Invalid selection. Use 1 for public or 2 for private.",
+                ));
             }
         }
     };
@@ -1333,47 +812,15 @@ pub async fn handle_set_icp_visibility(args: &[String]) -> Result<()> {
 
     // Use the function that accepts neuron_id with principal-based identity loading
     use crate::core::ops::governance_ops::set_neuron_visibility;
-    use crate::core::ops::identity::{
-        create_agent, load_dfx_identity, load_identity_from_seed_file,
-    };
-    use crate::core::utils::constants::GOVERNANCE_CANISTER;
-    use crate::core::utils::data_output::SnsCreationData;
-
-    // Load identity based on principal
-    let deployment_path = crate::core::utils::data_output::get_output_path();
-    let identity = if deployment_path.exists() {
-        if let Ok(data_content) = std::fs::read_to_string(&deployment_path) {
-            if let Ok(deployment_data) = serde_json::from_str::<SnsCreationData>(&data_content) {
-                // Check if principal matches owner or any participant
-                if principal.to_text() == deployment_data.owner_principal {
-                    load_dfx_identity(None).context("Failed to load owner dfx identity")?
-                } else if let Some(participant) = deployment_data
-                    .participants
-                    .iter()
-                    .find(|p| p.principal == principal.to_text())
-                {
-                    use std::path::PathBuf;
-                    load_identity_from_seed_file(&PathBuf::from(&participant.seed_file))
-                        .context("Failed to load participant identity")?
-                } else {
-                    // Custom principal, try dfx identity
-                    load_dfx_identity(None).context("Failed to load dfx identity")?
-                }
-            } else {
-                load_dfx_identity(None).context("Failed to load dfx identity")?
-            }
-        } else {
-            load_dfx_identity(None).context("Failed to load dfx identity")?
-        }
-    } else {
-        load_dfx_identity(None).context("Failed to load dfx identity")?
-    };
 
-    let agent = create_agent(identity)
+    // Load identity (owner, participant or extra identity)
+    let identity = identity_for_principal(signing_principal(principal))?;
+
+    let agent = app_context()
+        .agent(identity)
         .await
         .context("Failed to create agent")?;
-    let governance_canister = Principal::from_text(GOVERNANCE_CANISTER)
-        .context("Failed to parse ICP Governance canister ID")?;
+    let governance_canister = crate::core::utils::network::network_config().governance_canister;
 
     set_neuron_visibility(&agent, governance_canister, final_neuron_id, is_public)
         .await
@@ -1383,78 +830,108 @@ pub async fn handle_set_icp_visibility(args: &[String]) -> Result<()> {
     Ok(())
 }
 
-/// Handle get-icp-neuron command
-pub async fn handle_get_icp_neuron(args: &[String]) -> Result<()> {
-    // Get neuron ID (interactive if not provided)
-    let neuron_id = if args.len() > 2 {
-        Some(
-            args[2]
+/// Neuron ID for get-icp-neuron when none is given
+/// None means the neuron from the deployment data; otherwise prompts for one
+async fn prompt_icp_neuron_id() -> Result<Option<u64>> {
+    let Some(deployment_data) = app_context().try_deployment_data() else {
+        // No deployment data, must provide neuron ID
+        print_header("Get ICP Neuron Information");
+        print_info("No deployment data found");
+        let input = read_line("Enter neuron ID: ").await?;
+        return Ok(Some(
+            input
+                .trim()
                 .parse::<u64>()
-                .context("Failed to parse neuron ID")?,
+                .context("Failed to parse neuron ID - must be a number")?,
+        ));
+    };
+    if deployment_data.icp_neuron_id > 0 {
+        return Ok(None); // Will use from deployment data
+    }
+
+    // No neuron ID in deployment data, prompt for it
+    print_header("Get ICP Neuron Information");
+    print_info("No neuron ID found in deployment data");
+    let input = read_line("Enter neuron ID (or press Enter to exit): ").await?;
+    let input = input.trim();
+    if input.is_empty() {
+        anyhow::bail!("No neuron ID provided");
+    }
+    Ok(Some(
+        input
+            .parse::<u64>()
+            .context("Failed to parse neuron ID - must be a number")?,
+    ))
+}
+
+/// Handle get-sns-neuron command
+/// Usage: get-sns-neuron <neuron_id_hex>
+pub async fn handle_get_sns_neuron(args: &[String]) -> Result<()> {
+    use crate::core::ops::sns_governance_ops::get_sns_neuron_default_path;
+
+    const USAGE: &str = "Usage: get-sns-neuron <neuron_id_hex>";
+
+    if let Some(extra) = args.get(3) {
+        return Err(invalid(format!(
+            "Unknown argument for get-sns-neuron: {extra}\n{USAGE}"
+        )));
+    }
+    let neuron_id = match args.get(2) {
+        Some(id) => id.clone(),
+        None => read_input_required(
+            "Enter the neuron ID in hex, or a unique prefix (or press Enter/[b]ack to go back): ",
         )
-    } else {
-        // Try to get from deployment data, or prompt
-        let deployment_path = crate::core::utils::data_output::get_output_path();
-        if deployment_path.exists() {
-            let data_content = std::fs::read_to_string(&deployment_path)
-                .context("Failed to read deployment data")?;
-            let deployment_data: crate::core::utils::data_output::SnsCreationData =
-                serde_json::from_str(&data_content)
-                    .context("Failed to parse deployment data JSON")?;
-
-            if deployment_data.icp_neuron_id > 0 {
-                None // Will use from deployment data
-            } else {
-                // No neuron ID in deployment data, prompt for it
-                print_header("Get ICP Neuron Information");
-                print_info("No neuron ID found in deployment data");
-                print!("Enter neuron ID (or press Enter to exit): ");
-                io::stdout().flush()?;
-                let mut input = String::new();
-                io::stdin().read_line(&mut input)?;
-                let input = input.trim();
-                if input.is_empty() {
-                    anyhow::bail!("No neuron ID provided");
+        .await
+        .map_err(navigation_to_anyhow)?,
+    };
+
+    let spinner = Spinner::start("Loading SNS neuron...");
+    let neuron = get_sns_neuron_default_path(&neuron_id).await;
+    spinner.finish();
+    let neuron = neuron.context("Failed to get SNS neuron")?;
+
+    print_neuron_details(&neuron);
+    Ok(())
+}
+
+/// Handle get-icp-neuron command
+pub async fn handle_get_icp_neuron(args: &[String]) -> Result<()> {
+    // A principal instead of (or after) the neuron ID: query as that controller
+    let (neuron_id, controller) = match (args.get(2), args.get(3)) {
+        (Some(arg2), _) if arg2.parse::<u64>().is_err() => {
+            let principal = resolve_principal(arg2).context("Failed to parse principal")?;
+            match select_icp_neuron(principal).await {
+                Ok(id) => (Some(id), Some(principal)),
+                Err(e) if is_user_cancelled_error(&e) || is_user_went_back_error(&e) => {
+                    return Ok(());
                 }
-                Some(
-                    input
-                        .parse::<u64>()
-                        .context("Failed to parse neuron ID - must be a number")?,
-                )
+                Err(e) => return Err(e),
             }
-        } else {
-            // No deployment data, must provide neuron ID
-            print_header("Get ICP Neuron Information");
-            print_info("No deployment data found");
-            print!("Enter neuron ID: ");
-            io::stdout().flush()?;
-            let mut input = String::new();
-            io::stdin().read_line(&mut input)?;
-            Some(
-                input
-                    .trim()
-                    .parse::<u64>()
-                    .context("Failed to parse neuron ID - must be a number")?,
-            )
         }
+        (Some(arg2), arg3) => (
+            Some(arg2.parse::<u64>().context("Failed to parse neuron ID")?),
+            arg3.map(|p| resolve_principal(p).context("Failed to parse principal"))
+                .transpose()?,
+        ),
+        (None, _) => (prompt_icp_neuron_id().await?, None),
     };
 
     print_header("Getting ICP Neuron Information");
     if let Some(id) = neuron_id {
         print_info(&format!("Neuron ID: {} (specified)", id));
     } else {
-        let deployment_path = crate::core::utils::data_output::get_output_path();
-        let data_content =
-            std::fs::read_to_string(&deployment_path).context("Failed to read deployment data")?;
-        let deployment_data: crate::core::utils::data_output::SnsCreationData =
-            serde_json::from_str(&data_content).context("Failed to parse deployment data JSON")?;
+        let deployment_data = app_context().deployment_data()?;
         print_info(&format!(
             "Neuron ID: {} (from deployment data)",
             deployment_data.icp_neuron_id
         ));
     }
 
-    let neuron = get_icp_neuron_default_path(neuron_id)
+    if let Some(principal) = controller {
+        print_info(&format!("Querying as: {}", principal));
+    }
+
+    let neuron = get_icp_neuron_default_path(neuron_id, controller)
         .await
         .context("Failed to get neuron")?;
 
@@ -1467,13 +944,31 @@ pub async fn handle_get_icp_neuron(args: &[String]) -> Result<()> {
     Ok(())
 }
 
+/// Whether minting `amount_e8s` on a ledger asks for confirmation: at least
+/// [`LARGE_MINT_TOKENS`] whole tokens at the ledger's decimals
+async fn is_large_mint(ledger_canister: Principal, amount_e8s: u64) -> Result<bool> {
+    let decimals = app_context()
+        .ledger_decimals(ledger_canister)
+        .await
+        .context("Failed to get the ledger's decimals")?;
+    let threshold = 10u64
+        .checked_pow(u32::from(decimals))
+        .and_then(|unit| unit.checked_mul(LARGE_MINT_TOKENS))
+        .unwrap_or(u64::MAX);
+    Ok(amount_e8s >= threshold)
+}
+
 /// Handle mint-icp command
 pub async fn handle_mint_icp(args: &[String]) -> Result<()> {
+    let mut args = args.to_vec();
+    let amount = take_amount(&mut args, 3)?;
+    let to_subaccount = take_subaccount(&mut args, "--to-subaccount")?;
+
     // Step 1: Get receiver principal (select participant or custom if not provided)
     let receiver_principal = if args.len() >= 3 {
-        Principal::from_text(&args[2]).context("Failed to parse receiver principal")?
+        resolve_principal(&args[2]).context("Failed to parse receiver principal")?
     } else {
-        select_participant_or_custom()?
+        select_participant_or_custom().await?
     };
 
     // Get minting account balance to show user
@@ -1484,26 +979,49 @@ pub async fn handle_mint_icp(args: &[String]) -> Result<()> {
     let minting_balance_icp = minting_balance as f64 / 100_000_000.0;
 
     // Step 2: Get amount (interactive if not provided)
-    let amount_e8s = if args.len() >= 4 {
-        args[3]
-            .parse::<u64>()
-            .context("Failed to parse amount_e8s")?
-    } else {
-        print_header("Mint ICP");
-        print_info(&format!("Receiver: {}", receiver_principal));
-        print_info(&format!(
-            "Available balance: {} e8s ({:.8} ICP)",
-            minting_balance, minting_balance_icp
-        ));
-        println!();
-        let input = read_input_required(
-            "Enter amount in e8s (e.g., 100000000 for 1 ICP, or press Enter/[b]ack to go back): ",
-        )
-        .map_err(navigation_to_anyhow)?;
-        input
-            .parse::<u64>()
-            .context("Failed to parse amount - must be a number")?
+    let amount = match amount {
+        Some(amount) => amount,
+        None => {
+            print_header("Mint ICP");
+            print_info(&format!("Receiver: {}", receiver_principal));
+            print_info(&format!(
+                "Available balance: {} e8s ({:.8} ICP)",
+                minting_balance, minting_balance_icp
+            ));
+            println!();
+            let input = read_input_required(
+                "Enter amount in ICP (e.g., 1.5 or 2.0, or 150000000e8s in e8s; or press Enter/[b]ack to go back): ",
+            ).await
+            .map_err(navigation_to_anyhow)?;
+            AmountInput::from_prompt(&input)?
+        }
     };
+    let ledger_canister = crate::core::utils::network::network_config().ledger_canister;
+    let amount_e8s = amount.e8s(ledger_canister).await?;
+
+    if needs_confirmation() && is_large_mint(ledger_canister, amount_e8s).await? {
+        let agent = app_context().anonymous_agent().await?;
+        let balance = get_icp_ledger_balance(
+            &agent,
+            ledger_canister,
+            receiver_principal,
+            to_subaccount.clone(),
+        )
+        .await
+        .context("Failed to get receiver ICP balance")?;
+        let summary = [
+            ("Receiver", receiver_principal.to_string()),
+            ("Amount", icp_e8s_display(amount_e8s)),
+            ("Minting account", icp_e8s_display(minting_balance)),
+            (
+                "Resulting balance",
+                icp_e8s_display(balance.saturating_add(amount_e8s)),
+            ),
+        ];
+        if !confirm_action("mint ICP", &summary).await? {
+            return Err(Cancelled.into());
+        }
+    }
 
     print_header("Minting ICP");
     print_info(&format!("Receiver: {}", receiver_principal));
@@ -1517,7 +1035,13 @@ pub async fn handle_mint_icp(args: &[String]) -> Result<()> {
         amount_e8s, icp_amount
     ));
 
-    let block_height = mint_icp_default_path(receiver_principal, amount_e8s)
+    if let Some(subaccount) = &to_subaccount {
+        print_info(&format!(
+            "To subaccount: {}",
+            short_id(&hex::encode(subaccount))
+        ));
+    }
+    let block_height = mint_icp_default_path(receiver_principal, amount_e8s, to_subaccount)
         .await
         .context("Failed to mint ICP")?;
 
@@ -1528,11 +1052,106 @@ pub async fn handle_mint_icp(args: &[String]) -> Result<()> {
     Ok(())
 }
 
+/// Handle transfer command
+/// Usage: transfer <icp|sns> <from> <to> [amount_e8s] [--from-subaccount <hex>] [--to-subaccount <hex>]
+/// Moves tokens between accounts of the deployment's identities or any principal, signed as `from`
+pub async fn handle_transfer(args: &[String]) -> Result<()> {
+    use crate::core::ops::ledger_ops::{
+        transfer_icp_default_path, transfer_sns_tokens_default_path,
+    };
+    use crate::core::utils::qr::show_receiving_account;
+
+    const USAGE: &str = "Usage: transfer <icp|sns> <from> <to> [amount_e8s|max] [--amount <tokens|max>] [--from-subaccount <hex>] [--to-subaccount <hex>]";
+
+    let mut args = args.to_vec();
+    let amount = take_amount(&mut args, 5)?;
+    let from_subaccount = take_subaccount(&mut args, "--from-subaccount")?;
+    let to_subaccount = take_subaccount(&mut args, "--to-subaccount")?;
+    if args.len() < 5 {
+        return Err(invalid(format!("Missing arguments for transfer\n{USAGE}")));
+    }
+    if args.len() > 5 {
+        return Err(invalid(format!(
+            "Unknown argument for transfer: {}\n{USAGE}",
+            args[5]
+        )));
+    }
+    let use_sns = match args[2].to_lowercase().as_str() {
+        "icp" => false,
+        "sns" => true,
+        other => {
+            return Err(invalid(format!(
+                "Invalid ledger '{other}'. Use 'icp' or 'sns'\n{USAGE}"
+            )));
+        }
+    };
+    let from = resolve_principal(&args[3])?;
+    let to = resolve_principal(&args[4])?;
+    let amount = amount
+        .with_context(|| ValidationError::new(format!("Missing amount for transfer\n{USAGE}")))?;
+
+    let ledger_canister = if use_sns {
+        app_context().sns_ledger_canister()?
+    } else {
+        crate::core::utils::network::network_config().ledger_canister
+    };
+    let spendable = account_spendable(use_sns, from, from_subaccount.clone()).await?;
+    let amount_e8s = amount
+        .spend(ledger_canister, &spendable)
+        .await
+        .context(ValidationError::new("Invalid transfer amount"))?;
+
+    let account = |principal: Principal, subaccount: &Option<Vec<u8>>| match subaccount {
+        Some(subaccount) => format!("{principal}.{}", short_id(&hex::encode(subaccount))),
+        None => principal.to_string(),
+    };
+    print_header(if use_sns {
+        "Transferring SNS Tokens"
+    } else {
+        "Transferring ICP"
+    });
+    print_info(&format!("From: {}", account(from, &from_subaccount)));
+    print_info(&format!("To: {}", account(to, &to_subaccount)));
+    let unit = if use_sns { "tokens" } else { "ICP" };
+    let decimals = app_context().ledger_decimals(ledger_canister).await?;
+    print_info(&format!(
+        "Amount: {amount_e8s} e8s ({} {unit})",
+        format_tokens(amount_e8s, decimals)
+    ));
+    print_balance_after(&spendable, amount_e8s, unit, decimals);
+    show_receiving_account(
+        ledger_canister,
+        to,
+        to_subaccount
+            .as_deref()
+            .and_then(|sub| <&[u8; 32]>::try_from(sub).ok()),
+        Some(amount_e8s),
+    );
+
+    let block_height = if use_sns {
+        transfer_sns_tokens_default_path(from, from_subaccount, to, to_subaccount, amount_e8s).await
+    } else {
+        transfer_icp_default_path(from, from_subaccount, to, to_subaccount, amount_e8s).await
+    }
+    .context("Transfer failed")?;
+
+    print_success(&format!(
+        "Transfer complete! Block height: {}",
+        block_height
+    ));
+    Ok(())
+}
+
 /// Handle create-icp-neuron command
 pub async fn handle_create_icp_neuron(args: &[String]) -> Result<()> {
+    let mut args = args.to_vec();
+    let amount = take_amount(&mut args, 3)?;
+    let from_subaccount = take_subaccount(&mut args, "--from-subaccount")?;
+    let amount_given = amount.is_some();
+
     // Step 1: Get principal (select participant or custom if not provided)
     let principal = if args.len() >= 3 {
-        Principal::from_text(&args[2]).context("Failed to parse principal")?
+        resolve_principal(&args[2]).context("Failed to parse principal")?
     } else {
         match select_participant_with_back_handling(None, Some("icp")).await {
             Ok(p) => p,
@@ -1542,122 +1161,72 @@ pub async fn handle_create_icp_neuron(args: &[String]) -> Result<()> {
     };
 
     // Get ICP balance for the principal to show available amount
-    use crate::core::ops::identity::create_agent;
-    use crate::core::utils::constants::LEDGER_CANISTER;
-    let anonymous_identity = ic_agent::identity::AnonymousIdentity;
-    let agent_for_balance = create_agent(Box::new(anonymous_identity))
-        .await
-        .context("Failed to create agent for balance query")?;
-
-    let ledger_canister =
-        Principal::from_text(LEDGER_CANISTER).context("Failed to parse ICP Ledger canister ID")?;
-
-    let icp_balance = get_icp_ledger_balance(&agent_for_balance, ledger_canister, principal, None)
-        .await
-        .context("Failed to get ICP balance")?;
-    let icp_balance_display = icp_balance as f64 / 100_000_000.0;
-
-    use crate::core::utils::constants::ICP_TRANSFER_FEE;
-    let available_after_fee = if icp_balance > ICP_TRANSFER_FEE {
-        icp_balance - ICP_TRANSFER_FEE
-    } else {
-        0
-    };
-    let available_after_fee_display = available_after_fee as f64 / 100_000_000.0;
+    // The stake is sent with a fee added (see `create_icp_neuron_default_path`), and the
+    // ledger charges its fee on top of that
+    use crate::core::utils::constants::{ICP_NEURON_MINIMUM_STAKE, ICP_TRANSFER_FEE};
+    let ledger_canister = crate::core::utils::network::network_config().ledger_canister;
+    let spendable = account_spendable(false, principal, from_subaccount.clone())
+        .await?
+        .extra_fee(ICP_TRANSFER_FEE)
+        .minimum_stake(ICP_NEURON_MINIMUM_STAKE);
+    let decimals = app_context().ledger_decimals(ledger_canister).await?;
 
     // Step 2: Get amount (interactive if not provided)
-    let amount_e8s = if args.len() >= 4 {
-        args[3]
-            .parse::<u64>()
-            .context("Failed to parse amount_e8s")?
-    } else {
-        print_header("Create ICP Neuron");
-        print_info(&format!("Principal: {}", principal));
-        print_info(&format!(
-            "Available balance: {} e8s ({:.8} ICP)",
-            icp_balance, icp_balance_display
-        ));
-        print_info(&format!(
-            "Transfer fee: {} e8s ({:.8} ICP)",
-            ICP_TRANSFER_FEE,
-            ICP_TRANSFER_FEE as f64 / 100_000_000.0
-        ));
-        if available_after_fee > 0 {
-            print_info(&format!(
-                "Available after fee: {} e8s ({:.8} ICP)",
-                available_after_fee, available_after_fee_display
-            ));
-        }
-        println!();
-        let input_opt = match read_input_optional(
-            "Enter amount in e8s to stake (e.g., 100000000 for 1 ICP, press Enter to use all available, or [b]ack to go back): ",
-        ) {
-            Ok(opt) => opt,
-            Err(nav) => {
-                return Err(navigation_to_anyhow(nav));
-            }
-        };
-        if let Some(input) = input_opt {
-            input
-                .parse::<u64>()
-                .context("Failed to parse amount - must be a number")?
-        } else {
-            // Use all available balance after fee
-            if available_after_fee == 0 {
-                anyhow::bail!(
-                    "Insufficient balance. Need at least {} e8s (transfer fee) + amount to stake",
-                    ICP_TRANSFER_FEE
-                );
+    let amount = match amount {
+        Some(amount) => amount,
+        None => {
+            print_header("Create ICP Neuron");
+            print_info(&format!("Principal: {}", principal));
+            print_spendable(&spendable, "ICP", decimals);
+            println!();
+            let input_opt = match read_input_optional(
+                "Enter ICP to stake (e.g., 1.5 or 2.0, 150000000e8s in e8s, or max; press Enter to use all available, or [b]ack to go back): ",
+            ).await {
+                Ok(opt) => opt,
+                Err(nav) => {
+                    return Err(navigation_to_anyhow(nav));
+                }
+            };
+            match input_opt {
+                Some(input) => AmountInput::from_prompt(&input)?,
+                None => AmountInput::Max,
             }
-            available_after_fee
         }
     };
+    let amount_e8s = amount
+        .spend(ledger_canister, &spendable)
+        .await
+        .context(ValidationError::new("Invalid stake amount"))?;
 
     // Step 3: Get optional memo
-    let memo = if args.len() >= 5 {
-        Some(args[4].parse::<u64>().context("Failed to parse memo")?)
+    let memo = if args.len() >= 4 {
+        Some(args[3].parse::<u64>().context("Failed to parse memo")?)
     } else {
         None
     };
 
     // Step 4: Get optional dissolve delay (interactive if not provided)
-    let dissolve_delay_seconds = if args.len() >= 6 {
-        let delay = args[5]
-            .parse::<u64>()
-            .context("Failed to parse dissolve_delay_seconds")?;
-        if delay > 0 { Some(delay) } else { None }
+    let dissolve_delay_seconds = if args.len() >= 5 {
+        Some(
+            DissolveDelayPresets::icp()
+                .parse(&args[4])
+                .context("Failed to parse dissolve_delay")?,
+        )
     } else {
-        // Interactive prompt for dissolve delay
-        println!();
-        print!("Enter dissolve delay in seconds (or press Enter to skip, default: 0): ");
-        io::stdout().flush()?;
-
-        let input_opt = match read_input_optional(
-            "Enter dissolve delay in seconds (press Enter to skip/default: 0, or [b]ack to go back): ",
-        ) {
-            Ok(opt) => opt,
-            Err(nav) => {
-                return Err(navigation_to_anyhow(nav));
-            }
-        };
-        if let Some(input_trimmed) = input_opt {
-            let delay: u64 = input_trimmed
-                .parse()
-                .context("Failed to parse dissolve delay - must be a number")?;
-            if delay > 0 { Some(delay) } else { None }
-        } else {
-            None // No dissolve delay
-        }
-    };
+        DissolveDelayPresets::icp()
+            .prompt_optional("dissolve delay")
+            .await?
+    }
+    .filter(|&delay| delay > 0);
 
     // Get existing neuron count to show what memo will be used
     let existing_neurons = list_icp_neurons_for_principal_default_path(principal)
         .await
         .context("Failed to list existing neurons")?;
     let neuron_count = existing_neurons.len();
-    let auto_memo = (neuron_count + 1) as u64;
+    let auto_memo = auto_neuron_memo(neuron_count);
 
-    if args.len() >= 4 {
+    if amount_given {
         // Show header if amount was provided via args
         print_header("Creating ICP Neuron");
         print_info(&format!("Principal: {}", principal));
@@ -1692,14 +1261,23 @@ pub async fn handle_create_icp_neuron(args: &[String]) -> Result<()> {
         }
     }
 
+    print_balance_after(&spendable, amount_e8s, "ICP", decimals);
+
     // Use auto-assigned memo if not specified
     let final_memo = memo.unwrap_or(auto_memo);
 
+    if let Some(subaccount) = &from_subaccount {
+        print_info(&format!(
+            "From subaccount: {}",
+            short_id(&hex::encode(subaccount))
+        ));
+    }
     let neuron_id = create_icp_neuron_default_path(
         principal,
         amount_e8s,
         Some(final_memo),
         dissolve_delay_seconds,
+        from_subaccount,
     )
     .await
     .context("Failed to create ICP neuron")?;
@@ -1713,9 +1291,15 @@ pub async fn handle_create_icp_neuron(args: &[String]) -> Result<()> {
 
 /// Handle list-icp-neurons command
 pub async fn handle_list_icp_neurons(args: &[String]) -> Result<()> {
+    let mut args = args.to_vec();
+    let listing = NeuronListing::take(&mut args, || {
+        Ok(crate::core::utils::network::network_config().ledger_canister)
+    })
+    .await?;
+
     // Step 1: Get principal (select participant or custom if not provided)
     let principal = if args.len() >= 3 {
-        Principal::from_text(&args[2]).context("Failed to parse principal")?
+        resolve_principal(&args[2]).context("Failed to parse principal")?
     } else {
         match select_participant_with_back_handling(None, Some("icp")).await {
             Ok(p) => p,
@@ -1735,209 +1319,673 @@ pub async fn handle_list_icp_neurons(args: &[String]) -> Result<()> {
         print_warning("No neurons found for this principal");
         return Ok(());
     }
+    let neurons = listing.apply(neurons).await;
+    if neurons.is_empty() {
+        print_warning("No neurons match the filters");
+        return Ok(());
+    }
 
     print_success(&format!("Found {} neuron(s)", neurons.len()));
     println!();
 
-    // Print table header
-    println!("{:-<100}", "");
-    println!(
-        "{:<5} {:<20} {:<20} {:<25} {:<30}",
-        "#", "Neuron ID", "Stake (e8s)", "Dissolve Delay", "Hotkeys"
-    );
-    println!("{:-<100}", "");
+    neuron_table(&neurons, &()).print();
+    println!();
 
-    for (index, neuron) in neurons.iter().enumerate() {
-        // Neuron ID - ICP uses u64 IDs
-        let neuron_id_display = if let Some(id) = &neuron.id {
-            id.id.to_string()
-        } else {
-            "<none>".to_string()
-        };
+    // Ask if user wants to see details for a specific neuron
+    prompt_neuron_details(&neurons).await
+}
 
-        // Stake
-        let stake_str = format!("{}", neuron.cached_neuron_stake_e8s);
+/// Handle list-all-neurons command
+/// One table of the SNS or ICP neurons of the owner and every participant, with totals
+pub async fn handle_list_all_neurons(args: &[String]) -> Result<()> {
+    use crate::core::ops::governance_ops::list_all_icp_neurons_default_path;
+    use crate::core::ops::sns_governance_ops::list_all_neurons_default_path;
+
+    /// One table row: neuron ID, stake, dissolve state and who else can act on it
+    struct Row {
+        id: String,
+        stake_e8s: u64,
+        dissolve: String,
+        access: String,
+    }
 
-        // Dissolve delay
-        let dissolve_delay_str = match &neuron.dissolve_state {
-            Some(super::super::declarations::icp_governance::DissolveState::DissolveDelaySeconds(seconds)) => {
-                let days = *seconds / 86400;
-                format!("{} days ({}s)", days, seconds)
-            }
-            Some(super::super::declarations::icp_governance::DissolveState::WhenDissolvedTimestampSeconds(timestamp)) => {
-                format!("Dissolving (dissolves at {})", timestamp)
+    impl Row {
+        fn new(neuron: &impl NeuronView, access: String) -> Self {
+            Self {
+                id: neuron.id_cell(),
+                stake_e8s: neuron.stake_e8s(),
+                dissolve: neuron.dissolve().short(),
+                access,
             }
-            None => "No state".to_string(),
-        };
+        }
+    }
 
-        // Hotkeys
-        let hotkeys_str = if neuron.hot_keys.is_empty() {
-            "None".to_string()
-        } else {
-            format!("{} hotkey(s)", neuron.hot_keys.len())
-        };
+    let kind = args.get(2).map(|kind| kind.to_lowercase());
+    let (title, accounts, ledger_canister) = match kind.as_deref() {
+        Some("sns") => {
+            let spinner = Spinner::start("Loading SNS neurons...");
+            let accounts = list_all_neurons_default_path().await;
+            spinner.finish();
+            let accounts = accounts
+                .context("Failed to list SNS neurons")?
+                .into_iter()
+                .map(|(label, principal, neurons)| {
+                    let rows = neurons
+                        .iter()
+                        .map(|neuron| {
+                            // Permission holders other than the account itself
+                            let others = neuron
+                                .permissions
+                                .iter()
+                                .filter(|p| p.principal != Some(principal))
+                                .count();
+                            Row::new(neuron, format!("{} other principal(s)", others))
+                        })
+                        .collect::<Vec<_>>();
+                    (label, rows)
+                })
+                .collect::<Vec<_>>();
+            (
+                "All SNS Neurons",
+                accounts,
+                app_context().sns_ledger_canister()?,
+            )
+        }
+        Some("icp") => {
+            let spinner = Spinner::start("Loading ICP neurons...");
+            let accounts = list_all_icp_neurons_default_path().await;
+            spinner.finish();
+            let accounts = accounts
+                .context("Failed to list ICP neurons")?
+                .into_iter()
+                .map(|(label, _principal, neurons)| {
+                    let rows = neurons
+                        .iter()
+                        .map(|neuron| {
+                            Row::new(neuron, format!("{} hotkey(s)", neuron.hot_keys.len()))
+                        })
+                        .collect::<Vec<_>>();
+                    (label, rows)
+                })
+                .collect::<Vec<_>>();
+            (
+                "All ICP Neurons",
+                accounts,
+                crate::core::utils::network::network_config().ledger_canister,
+            )
+        }
+        _ => return Err(invalid("Usage: list-all-neurons <sns|icp>")),
+    };
 
-        // Truncate dissolve delay if too long for table formatting
-        let dissolve_delay_display = if dissolve_delay_str.len() > 18 {
-            format!("{}...", &dissolve_delay_str[..18])
-        } else {
-            dissolve_delay_str
-        };
+    let scale = 10f64.powi(i32::from(
+        app_context().ledger_decimals(ledger_canister).await?,
+    ));
+    let tokens = |e8s: u64| e8s as f64 / scale;
+    print_header(title);
+    let mut table = Table::new(&[
+        "Account",
+        "Neuron ID",
+        "Stake",
+        "Dissolve State",
+        "Permissions/Hotkeys",
+    ])
+    .right_align(&[2]);
+    for (label, rows) in &accounts {
+        for row in rows {
+            table.add_row(vec![
+                label.clone(),
+                row.id.clone(),
+                format!("{:.4}", tokens(row.stake_e8s)),
+                row.dissolve.clone(),
+                row.access.clone(),
+            ]);
+        }
+    }
+    table.print();
 
+    println!();
+    print_info("Totals:");
+    for (label, rows) in &accounts {
         println!(
-            "{:<5} {:<20} {:<20} {:<25} {:<30}",
-            index + 1,
-            neuron_id_display,
-            stake_str,
-            dissolve_delay_display,
-            hotkeys_str
+            "  {:<16} {:>4} neuron(s) {:>16.4} staked",
+            label,
+            rows.len(),
+            tokens(rows.iter().map(|row| row.stake_e8s).sum())
         );
     }
+    let all = accounts.iter().flat_map(|(_, rows)| rows);
+    println!(
+        "  {:<16} {:>4} neuron(s) {:>16.4} staked",
+        "all",
+        all.clone().count(),
+        tokens(all.map(|row| row.stake_e8s).sum())
+    );
+    Ok(())
+}
 
-    println!("{:-<100}", "");
-    println!();
+/// ICP amount in e8s with its ICP equivalent, e.g. "150000000 e8s (1.50000000 ICP)"
+fn icp_e8s_display(e8s: u64) -> String {
+    format!("{} e8s ({:.8} ICP)", e8s, e8s as f64 / 100_000_000.0)
+}
 
-    // Ask if user wants to see details for a specific neuron
-    if neurons.len() > 0 {
-        println!();
-        print!(
-            "Enter neuron number to see full details (1-{}) or press Enter to skip: ",
-            neurons.len()
-        );
-        io::stdout().flush()?;
+/// SNS neuron of `principal` with the given ID
+/// Fails when the neuron is not listed for the principal, which could not manage it anyway
+async fn find_sns_neuron(
+    principal: Principal,
+    neuron_id: &[u8],
+) -> Result<crate::core::declarations::sns_governance::Neuron> {
+    let neurons = list_neurons_for_principal_default_path(principal)
+        .await
+        .context("Failed to list neurons")?;
+    neurons
+        .into_iter()
+        .find(|n| n.id.as_ref().is_some_and(|id| id.id == neuron_id))
+        .with_context(|| {
+            format!(
+                "Neuron {} is not listed for {principal}",
+                short_id(&hex::encode(neuron_id))
+            )
+        })
+}
 
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
-        let selection = input.trim();
-
-        if !selection.is_empty() {
-            let selection_num: usize = selection
-                .parse()
-                .context("Invalid selection - must be a number")?;
-            if selection_num < 1 || selection_num > neurons.len() {
-                eprintln!(
-                    "Invalid selection. Please choose a number between 1 and {}",
-                    neurons.len()
-                );
-                return Ok(());
-            }
+/// SNS neuron ID from a command-line argument: a full hex ID, or a unique prefix (or the
+/// tables' `prefix...suffix` form) of one of `principal`'s neurons
+async fn parse_sns_neuron_id(principal: Principal, arg: &str) -> Result<Vec<u8>> {
+    use crate::core::ops::sns_governance_ops::match_neuron_id;
 
-            let selected_neuron = &neurons[selection_num - 1];
-            display_icp_neuron_details(selected_neuron);
-        }
+    let hex_str = arg.strip_prefix("0x").unwrap_or(arg);
+    // SNS neuron IDs are 32 bytes; a full ID needs no lookup
+    if hex_str.len() == 64 {
+        return hex::decode(hex_str).context("Failed to decode neuron_id from hex");
     }
+    let neurons = list_neurons_for_principal_default_path(principal)
+        .await
+        .context("Failed to list neurons")?;
+    match_neuron_id(&neurons, arg)
+        .with_context(|| format!("Failed to resolve neuron ID among the neurons of {principal}"))
+}
 
-    Ok(())
+/// Whether a positional argument is an SNS neuron ID (or prefix) rather than a principal
+fn looks_like_sns_neuron_id(arg: &str) -> bool {
+    use crate::core::ops::sns_governance_ops::MIN_NEURON_ID_PREFIX_LEN;
+
+    let hex_str = arg.strip_prefix("0x").unwrap_or(arg);
+    // Principals contain dashes, so a hex-only argument is a neuron ID
+    hex_str.len() >= MIN_NEURON_ID_PREFIX_LEN
+        && hex_str
+            .split_once("...")
+            .map_or(hex_str.to_string(), |(a, b)| format!("{a}{b}"))
+            .chars()
+            .all(|c| c.is_ascii_hexdigit())
+}
+
+/// An amount in tokens or in e8s (the ledger's base units), before the ledger's decimals are known
+#[derive(Debug, Clone)]
+enum AmountInput {
+    Tokens(String),
+    E8s(u64),
+    /// `max`: everything the account can send after the fee
+    Max,
 }
 
-/// Display full details for a single ICP neuron
-fn display_icp_neuron_details(neuron: &crate::core::declarations::icp_governance::Neuron) {
-    use crate::core::declarations::icp_governance::DissolveState;
+impl AmountInput {
+    fn is_max(value: &str) -> bool {
+        value.trim().eq_ignore_ascii_case("max")
+    }
+
+    fn e8s_arg(value: &str, what: &str) -> Result<Self> {
+        if Self::is_max(value) {
+            return Ok(Self::Max);
+        }
+        value.trim().parse::<u64>().map(Self::E8s).with_context(|| {
+            ValidationError::new(format!(
+                "Failed to parse {what} - must be a whole number of e8s"
+            ))
+        })
+    }
+
+    /// Prompt input: tokens with a decimal point (`1.5`, `2.0`), e8s with an `e8s` suffix
+    /// (`150000000e8s`), or `max`
+    /// A bare whole number is refused: positional arguments read it as e8s, so reading it as
+    /// tokens here would give the same input two meanings.
+    fn from_prompt(input: &str) -> Result<Self> {
+        let input = input.trim();
+        if Self::is_max(input) {
+            return Ok(Self::Max);
+        }
+        if let Some(e8s) = input.strip_suffix("e8s") {
+            return Self::e8s_arg(e8s, "amount");
+        }
+        if input.parse::<u64>().is_ok() {
+            return Err(invalid(format!(
+                "Ambiguous amount '{input}': enter {input}.0 for tokens or {input}e8s for e8s"
+            )));
+        }
+        Ok(Self::Tokens(input.to_string()))
+    }
+
+    /// The amount in e8s, fetching the ledger's decimals for an amount in tokens
+    async fn e8s(&self, ledger_canister: Principal) -> Result<u64> {
+        use crate::core::utils::cli_args::parse_token_amount;
+
+        match self {
+            Self::E8s(e8s) => Ok(*e8s),
+            Self::Tokens(tokens) => {
+                let decimals = app_context()
+                    .ledger_decimals(ledger_canister)
+                    .await
+                    .context("Failed to get the ledger's decimals")?;
+                parse_token_amount(tokens, decimals)
+            }
+            Self::Max => Err(invalid("`max` is not accepted here - enter an amount")),
+        }
+    }
+
+    /// The amount in e8s to send out of an account, `max` being all it can send, checked
+    /// against its balance, the fee and any minimum stake
+    async fn spend(&self, ledger_canister: Principal, spendable: &Spendable) -> Result<u64> {
+        let amount_e8s = match self {
+            Self::Max => spendable.max_e8s(),
+            amount => amount.e8s(ledger_canister).await?,
+        };
+        spendable.check(amount_e8s)?;
+        Ok(amount_e8s)
+    }
+}
 
-    print_header("ICP Neuron Details");
+/// Balance and transfer fee of an account on the SNS ledger, or on the ICP ledger
+async fn account_spendable(
+    use_sns: bool,
+    principal: Principal,
+    subaccount: Option<Vec<u8>>,
+) -> Result<Spendable> {
+    use crate::core::ops::ledger_ops::get_sns_ledger_fee;
+    use crate::core::utils::constants::ICP_TRANSFER_FEE;
 
-    // Neuron ID
-    if let Some(id) = &neuron.id {
-        print_info(&format!("Neuron ID: {}", id.id));
+    let agent = app_context().anonymous_agent().await?;
+    if use_sns {
+        let ledger_canister = app_context().sns_ledger_canister()?;
+        let balance = get_sns_ledger_balance(&agent, ledger_canister, principal, subaccount)
+            .await
+            .context("Failed to get SNS ledger balance")?;
+        let fee = get_sns_ledger_fee(&agent, ledger_canister)
+            .await
+            .context("Failed to get SNS ledger transfer fee")?;
+        Ok(Spendable::new(balance, fee))
     } else {
-        print_info("Neuron ID: <none>");
+        let ledger_canister = crate::core::utils::network::network_config().ledger_canister;
+        let balance = get_icp_ledger_balance(&agent, ledger_canister, principal, subaccount)
+            .await
+            .context("Failed to get ICP balance")?;
+        Ok(Spendable::new(balance, ICP_TRANSFER_FEE))
+    }
+}
+
+/// Base units of a ledger with `decimals` decimals as a token amount, e.g. `1.50000000`
+fn format_tokens(e8s: u64, decimals: u8) -> String {
+    let scale = 10f64.powi(i32::from(decimals));
+    format!("{:.*}", usize::from(decimals), e8s as f64 / scale)
+}
+
+/// Balance, fee, minimum stake and `max` of an account, shown before asking for an amount
+fn print_spendable(spendable: &Spendable, unit: &str, decimals: u8) {
+    let display = |e8s: u64| format!("{e8s} e8s ({} {unit})", format_tokens(e8s, decimals));
+    print_info(&format!(
+        "Available balance: {}",
+        display(spendable.balance_e8s)
+    ));
+    print_info(&format!(
+        "Fees charged on top: {}",
+        display(spendable.fee_e8s)
+    ));
+    if spendable.minimum_stake_e8s > 0 {
+        print_info(&format!(
+            "Minimum stake required: {}",
+            display(spendable.minimum_stake_e8s)
+        ));
     }
+    print_info(&format!(
+        "Maximum (balance - fees, enter `max`): {}",
+        display(spendable.max_e8s())
+    ));
+}
+
+/// The fee and the balance left once `amount_e8s` is sent
+fn print_balance_after(spendable: &Spendable, amount_e8s: u64, unit: &str, decimals: u8) {
+    let remaining = spendable.remaining_e8s(amount_e8s);
+    print_info(&format!("Fees charged on top: {} e8s", spendable.fee_e8s));
+    print_info(&format!(
+        "Balance after: {remaining} e8s ({} {unit})",
+        format_tokens(remaining, decimals)
+    ));
+}
 
-    // Controller
-    if let Some(controller) = &neuron.controller {
-        print_info(&format!("Controller: {}", controller));
+/// Take the amount from `--amount <tokens>`, `--amount-e8s <n>` or the positional e8s argument
+/// at `index`, removing it from `args` so later positional arguments move up by one
+fn take_amount(args: &mut Vec<String>, index: usize) -> Result<Option<AmountInput>> {
+    match take_amount_flags(args)? {
+        Some(amount) => Ok(Some(amount)),
+        None if args.len() > index => {
+            AmountInput::e8s_arg(&args.remove(index), "amount_e8s").map(Some)
+        }
+        None => Ok(None),
     }
+}
 
-    // Stake information
-    println!();
-    print_info("Stake Information:");
-    println!("  Cached Stake: {} e8s", neuron.cached_neuron_stake_e8s);
-    if let Some(staked_maturity) = neuron.staked_maturity_e8s_equivalent {
-        println!("  Staked Maturity: {} e8s", staked_maturity);
+/// Take the amount from `--amount <tokens>` or `--amount-e8s <n>`, for commands without a
+/// positional amount
+fn take_amount_flags(args: &mut Vec<String>) -> Result<Option<AmountInput>> {
+    take_named_amount_flags(args, "--amount")
+}
+
+/// Take an amount from `<name> <tokens>` or `<name>-e8s <n>`, for commands with several
+/// amounts (e.g. `--sns-amount` and `--icp-amount`)
+fn take_named_amount_flags(args: &mut Vec<String>, name: &str) -> Result<Option<AmountInput>> {
+    use crate::core::utils::cli_args::take_flag;
+
+    let e8s_name = format!("{name}-e8s");
+    let tokens = take_flag(args, name)?;
+    let e8s = take_flag(args, &e8s_name)?;
+    match (tokens, e8s) {
+        (Some(_), Some(_)) => Err(invalid(format!(
+            "Use either {name} or {e8s_name}, not both"
+        ))),
+        (Some(tokens), None) if AmountInput::is_max(&tokens) => Ok(Some(AmountInput::Max)),
+        (Some(tokens), None) => Ok(Some(AmountInput::Tokens(tokens))),
+        (None, Some(e8s)) => AmountInput::e8s_arg(&e8s, &e8s_name).map(Some),
+        (None, None) => Ok(None),
     }
-    println!("  Maturity: {} e8s", neuron.maturity_e8s_equivalent);
+}
 
-    // Dissolve state
-    println!();
-    print_info("Dissolve State:");
-    match &neuron.dissolve_state {
-        Some(DissolveState::DissolveDelaySeconds(seconds)) => {
-            let days = *seconds / 86400;
-            let hours = (*seconds % 86400) / 3600;
-            println!("  Type: Dissolve Delay");
-            println!(
-                "  Delay: {} seconds ({} days, {} hours)",
-                seconds, days, hours
-            );
+/// `--sort` and `--filter` of the neuron list commands
+#[derive(Debug, Default)]
+struct NeuronListing {
+    sort: Option<NeuronSort>,
+    filters: Vec<NeuronFilter>,
+}
+
+impl NeuronListing {
+    /// Take `--sort <stake|delay|created|maturity>` and any number of
+    /// `--filter <dissolving|locked|dissolved|min-stake X>`; a minimum stake is tokens of
+    /// the ledger `ledger_canister` returns (`1.5`) or e8s with an `e8s` suffix
+    async fn take(
+        args: &mut Vec<String>,
+        ledger_canister: impl FnOnce() -> Result<Principal>,
+    ) -> Result<Self> {
+        use crate::core::utils::cli_args::take_flag;
+
+        let sort = take_flag(args, "--sort")?
+            .map(|value| value.parse())
+            .transpose()?;
+        let mut filters = Vec::new();
+        let mut min_stake = None;
+        while let Some(pos) = args
+            .iter()
+            .position(|a| a == "--filter" || a.starts_with("--filter="))
+        {
+            let Some(value) = take_flag(args, "--filter")? else {
+                break;
+            };
+            match value.trim().to_lowercase().as_str() {
+                "dissolving" => filters.push(NeuronFilter::Dissolving),
+                "locked" => filters.push(NeuronFilter::Locked),
+                "dissolved" => filters.push(NeuronFilter::Dissolved),
+                // `min-stake X`, `min-stake=X` or `"min-stake X"`
+                other if other.starts_with("min-stake") => {
+                    let inline = other["min-stake".len()..].trim_start_matches(['=', ' ']);
+                    let amount = if !inline.is_empty() {
+                        inline.to_string()
+                    } else if pos < args.len() {
+                        args.remove(pos)
+                    } else {
+                        return Err(invalid("--filter min-stake requires an amount"));
+                    };
+                    min_stake = Some(AmountInput::from_prompt(&amount)?);
+                }
+                _ => {
+                    return Err(invalid(format!(
+                        "Unknown filter '{value}': use dissolving, locked, dissolved or min-stake <amount>"
+                    )));
+                }
+            }
         }
-        Some(DissolveState::WhenDissolvedTimestampSeconds(timestamp)) => {
-            println!("  Type: Dissolving");
-            println!("  Dissolves at timestamp: {}", timestamp);
+        if let Some(amount) = min_stake {
+            filters.push(NeuronFilter::MinStake(
+                amount.e8s(ledger_canister()?).await?,
+            ));
         }
-        None => {
-            println!("  Type: None");
+        Ok(Self { sort, filters })
+    }
+
+    /// The neurons passing every filter, in the chosen order
+    async fn apply<N: NeuronView>(&self, mut neurons: Vec<N>) -> Vec<N> {
+        if self.sort.is_none() && self.filters.is_empty() {
+            return neurons;
         }
+        let now = crate::core::ops::time_ops::replica_now_secs().await;
+        neurons.retain(|n| self.filters.iter().all(|f| f.matches(n, now)));
+        if let Some(sort) = self.sort {
+            sort.apply(&mut neurons, now);
+        }
+        neurons
     }
+}
 
-    // Aging
-    println!();
-    print_info("Aging:");
-    println!(
-        "  Aging since timestamp: {}",
-        neuron.aging_since_timestamp_seconds
-    );
-    println!("  Created timestamp: {}", neuron.created_timestamp_seconds);
+/// Take `<flag> <hex>` as a 32-byte subaccount, e.g. `--from-subaccount`
+fn take_subaccount(args: &mut Vec<String>, flag: &str) -> Result<Option<Vec<u8>>> {
+    use crate::core::ops::ledger_ops::parse_subaccount;
+    use crate::core::utils::cli_args::take_flag;
 
-    // Voting power
-    println!();
-    if let Some(voting_power) = neuron.deciding_voting_power {
-        print_info(&format!("Deciding Voting Power: {} e8s", voting_power));
+    take_flag(args, flag)?
+        .map(|hex_str| {
+            parse_subaccount(&hex_str)
+                .map(|subaccount| subaccount.0.to_vec())
+                .with_context(|| ValidationError::new(format!("Invalid {flag}")))
+        })
+        .transpose()
+}
+
+/// Dissolve delays offered as presets wherever a dissolve delay is entered
+#[derive(Debug, Clone, Copy)]
+struct DissolveDelayPresets {
+    min_to_vote: u64,
+    max: u64,
+}
+
+impl DissolveDelayPresets {
+    /// Presets from the deployed SNS's nervous system parameters
+    async fn sns() -> Result<Self> {
+        use crate::core::ops::sns_governance_ops::get_nervous_system_parameters;
+
+        let agent = app_context().anonymous_agent().await?;
+        let params =
+            get_nervous_system_parameters(&agent, app_context().sns_governance_canister()?)
+                .await
+                .context("Failed to get nervous system parameters")?;
+        Ok(Self {
+            min_to_vote: params
+                .neuron_minimum_dissolve_delay_to_vote_seconds
+                .unwrap_or(0),
+            max: params
+                .max_dissolve_delay_seconds
+                .context("Nervous system parameters have no max_dissolve_delay_seconds")?,
+        })
     }
-    if let Some(potential_power) = neuron.potential_voting_power {
-        print_info(&format!("Potential Voting Power: {} e8s", potential_power));
+
+    /// Presets of the NNS
+    const fn icp() -> Self {
+        use crate::core::utils::constants::{
+            NNS_MAX_DISSOLVE_DELAY, NNS_MIN_DISSOLVE_DELAY_TO_VOTE,
+        };
+
+        Self {
+            min_to_vote: NNS_MIN_DISSOLVE_DELAY_TO_VOTE,
+            max: NNS_MAX_DISSOLVE_DELAY,
+        }
     }
 
-    // Hotkeys
-    println!();
-    print_info("Hotkeys:");
-    if neuron.hot_keys.is_empty() {
-        println!("  None");
-    } else {
-        for (i, hotkey) in neuron.hot_keys.iter().enumerate() {
-            println!("  [{}] {}", i + 1, hotkey);
+    /// Resolve `min-to-vote`, `max` or a duration (`30d`, `6 months`, `1y`, seconds)
+    fn parse(&self, value: &str) -> Result<u64> {
+        use crate::core::utils::cli_args::parse_duration_secs;
+
+        match value.trim().to_lowercase().as_str() {
+            "min-to-vote" | "min" => Ok(self.min_to_vote),
+            "max" => Ok(self.max),
+            _ => parse_duration_secs(value),
         }
     }
 
-    // Visibility
-    if let Some(visibility) = neuron.visibility {
-        println!();
-        print_info(&format!(
-            "Visibility: {}",
-            if visibility == 0 { "Public" } else { "Private" }
-        ));
+    /// Prompt for a dissolve delay: a preset number or a duration
+    async fn prompt(&self, what: &str) -> Result<u64> {
+        self.print_menu();
+        let input = read_input_required(&format!(
+            "Select a preset or enter the {what} (or press Enter/[b]ack to go back): "
+        ))
+        .await
+        .map_err(navigation_to_anyhow)?;
+        self.choice(&input)
     }
 
-    // KYC
-    println!();
-    print_info(&format!("KYC Verified: {}", neuron.kyc_verified));
+    /// Like `prompt`, but Enter skips it and returns `None`
+    async fn prompt_optional(&self, what: &str) -> Result<Option<u64>> {
+        self.print_menu();
+        read_input_optional(&format!(
+            "Select a preset or enter the {what} (press Enter to skip, or [b]ack to go back): "
+        ))
+        .await
+        .map_err(navigation_to_anyhow)?
+        .map(|input| self.choice(&input))
+        .transpose()
+    }
+
+    fn print_menu(&self) {
+        use crate::core::utils::cli_args::format_duration_secs;
 
-    // Auto stake maturity
-    if let Some(auto_stake) = neuron.auto_stake_maturity {
         println!();
-        print_info(&format!("Auto Stake Maturity: {}", auto_stake));
+        println!(
+            "  [1] Minimum to vote ({})",
+            format_duration_secs(self.min_to_vote)
+        );
+        println!("  [2] 1 year");
+        println!("  [3] Maximum ({})", format_duration_secs(self.max));
+        println!("  Or enter a duration: e.g. 30d, 6 months, 1y, or seconds with an s (3600s)");
+        println!();
     }
 
-    println!();
+    /// Warn when a neuron is about to start dissolving with less delay than voting needs
+    fn warn_start_dissolving(&self, dissolve: Dissolve) {
+        use crate::core::utils::cli_args::format_duration_secs;
+
+        if let Dissolve::NotDissolving { delay_seconds } = dissolve
+            && delay_seconds < self.min_to_vote
+        {
+            print_warning(&format!(
+                "The dissolve delay ({}) is below the minimum to vote ({}): the neuron cannot vote while it dissolves",
+                format_duration_secs(delay_seconds),
+                format_duration_secs(self.min_to_vote)
+            ));
+        }
+    }
+
+    /// Limit `additional` seconds so the neuron's remaining delay at `now` stays within the
+    /// maximum, saying so when it is reduced; fails when the neuron is already at the maximum
+    fn clamp_increase(&self, dissolve: Dissolve, additional: u64, now: u64) -> Result<u64> {
+        use crate::core::utils::cli_args::format_duration_secs;
+
+        let Some(remaining) = dissolve.remaining_at(now) else {
+            return Ok(additional);
+        };
+        let room = self.max.saturating_sub(remaining);
+        if room == 0 {
+            anyhow::bail!(
+                "The neuron is already at the maximum dissolve delay ({})",
+                format_duration_secs(self.max)
+            );
+        }
+        if additional > room {
+            print_warning(&format!(
+                "{} more would exceed the maximum dissolve delay ({}); increasing by {} instead",
+                format_duration_secs(additional),
+                format_duration_secs(self.max),
+                format_duration_secs(room)
+            ));
+            return Ok(room);
+        }
+        Ok(additional)
+    }
+
+    fn choice(&self, input: &str) -> Result<u64> {
+        use crate::core::utils::cli_args::ONE_YEAR_SECS;
+
+        match input {
+            "1" => Ok(self.min_to_vote),
+            "2" => Ok(ONE_YEAR_SECS),
+            "3" => Ok(self.max),
+            other => self.parse(other),
+        }
+    }
+}
+
+/// Fail with the time left when a neuron cannot be disbursed yet at `now`: it is not
+/// dissolving, or its dissolve timestamp has not passed
+fn ensure_dissolved(dissolve: Dissolve, now: u64) -> Result<()> {
+    use crate::core::utils::cli_args::format_duration_secs;
+    use crate::core::utils::time_format::format_utc;
+
+    match dissolve {
+        Dissolve::NotDissolving { delay_seconds } if delay_seconds > 0 => anyhow::bail!(
+            "The neuron is not dissolving (dissolve delay {}). Start dissolving it and wait for the delay to pass before disbursing",
+            format_duration_secs(delay_seconds)
+        ),
+        Dissolve::DissolvingAt(timestamp) if timestamp > now => anyhow::bail!(
+            "The neuron is still dissolving: {} left (dissolves at {}). Use advance-time to skip ahead",
+            format_duration_secs(timestamp - now),
+            format_utc(timestamp)
+        ),
+        _ => Ok(()),
+    }
+}
+
+/// Parse an SNS dissolve delay argument, fetching the presets only for `min-to-vote`/`max`
+async fn parse_sns_dissolve_delay(value: &str) -> Result<u64> {
+    match value.trim().to_lowercase().as_str() {
+        "min-to-vote" | "min" | "max" => DissolveDelayPresets::sns().await?.parse(value),
+        _ => crate::core::utils::cli_args::parse_duration_secs(value),
+    }
 }
 
 /// Handle get-icp-balance command
 pub async fn handle_get_icp_balance(args: &[String]) -> Result<()> {
-    use crate::core::ops::identity::create_agent;
-    use crate::core::utils::constants::LEDGER_CANISTER;
+    use crate::core::ops::ledger_ops::{
+        get_icp_ledger_balance_by_account_id, is_account_id_hex, parse_account_id, parse_subaccount,
+    };
+
+    // An account identifier (e.g. a neuron staking or swap escrow account) instead of a principal
+    if let Some(account_id) = args.get(2).filter(|arg| is_account_id_hex(arg)) {
+        if args.len() >= 4 {
+            return Err(invalid(
+                "A subaccount cannot be combined with an account identifier",
+            ));
+        }
+        let account_id = parse_account_id(account_id)?;
+        print_header("Get ICP Balance");
+        print_info(&format!("Account ID: {}", account_id));
+
+        let agent = app_context().anonymous_agent().await?;
+        let ledger_canister = crate::core::utils::network::network_config().ledger_canister;
+        let balance = get_icp_ledger_balance_by_account_id(&agent, ledger_canister, &account_id)
+            .await
+            .context("Failed to get ICP balance")?;
+
+        let icp_amount = balance as f64 / 100_000_000.0;
+        println!();
+        print_success(&format!("Balance: {} e8s ({:.8} ICP)", balance, icp_amount));
+        return Ok(());
+    }
 
     // Step 1: Get principal (select participant or custom if not provided)
     let principal = if args.len() >= 3 {
-        Principal::from_text(&args[2]).context("Failed to parse principal")?
+        resolve_principal(&args[2]).context("Failed to parse principal")?
     } else {
         match select_participant_with_back_handling(None, Some("icp")).await {
             Ok(p) => p,
@@ -1948,8 +1996,7 @@ pub async fn handle_get_icp_balance(args: &[String]) -> Result<()> {
 
     // Step 2: Get subaccount (optional)
     let subaccount = if args.len() >= 4 {
-        let hex_str = args[3].strip_prefix("0x").unwrap_or(&args[3]);
-        Some(hex::decode(hex_str).context("Failed to decode subaccount from hex")?)
+        Some(parse_subaccount(&args[3])?.0.to_vec())
     } else {
         None
     };
@@ -1957,28 +2004,15 @@ pub async fn handle_get_icp_balance(args: &[String]) -> Result<()> {
     print_header("Get ICP Balance");
     print_info(&format!("Principal: {}", principal));
     if let Some(ref sub) = subaccount {
-        let hex_sub = hex::encode(sub);
-        if hex_sub.len() >= 15 {
-            print_info(&format!(
-                "Subaccount: {}...{}",
-                &hex_sub[..7],
-                &hex_sub[hex_sub.len() - 8..]
-            ));
-        } else {
-            print_info(&format!("Subaccount: {}", hex_sub));
-        }
+        print_info(&format!("Subaccount: {}", short_id(&hex::encode(sub))));
     } else {
         print_info("Subaccount: None (default account)");
     }
 
     // Create anonymous agent for query
-    let anonymous_identity = ic_agent::identity::AnonymousIdentity;
-    let agent = create_agent(Box::new(anonymous_identity))
-        .await
-        .context("Failed to create agent")?;
+    let agent = app_context().anonymous_agent().await?;
 
-    let ledger_canister =
-        Principal::from_text(LEDGER_CANISTER).context("Failed to parse ICP Ledger canister ID")?;
+    let ledger_canister = crate::core::utils::network::network_config().ledger_canister;
 
     let balance = get_icp_ledger_balance(&agent, ledger_canister, principal, subaccount)
         .await
@@ -1990,28 +2024,183 @@ pub async fn handle_get_icp_balance(args: &[String]) -> Result<()> {
     Ok(())
 }
 
+/// Handle account-id command
+/// Derives ICP account identifiers and the governance and swap subaccounts of a principal
+pub async fn handle_account_id(args: &[String]) -> Result<()> {
+    use crate::core::ops::ledger_ops::{
+        generate_subaccount_by_nonce, icp_account_id, parse_subaccount,
+    };
+    use crate::core::ops::swap_ops::generate_participant_subaccount;
+    use crate::core::utils::cli_args::{take_flag, take_switch};
+    use crate::core::utils::qr::show_receiving_account;
+
+    let mut args = args.to_vec();
+    let subaccount = take_flag(&mut args, "--subaccount")?
+        .map(|hex_str| parse_subaccount(&hex_str))
+        .transpose()?;
+    let memo = take_flag(&mut args, "--memo")?
+        .map(|memo| {
+            memo.parse::<u64>()
+                .context(ValidationError::new("Invalid --memo (expected a u64)"))
+        })
+        .transpose()?;
+    let swap = take_switch(&mut args, "--swap");
+
+    // Step 1: Get principal (select participant or custom if not provided)
+    // Everything is derived locally, so this needs no replica
+    let principal = if args.len() >= 3 {
+        resolve_principal(&args[2]).context("Failed to parse principal")?
+    } else {
+        match select_participant_with_back_handling(None, None).await {
+            Ok(p) => p,
+            Err(e) if is_user_went_back_error(&e) => return Ok(()),
+            Err(e) => return Err(e),
+        }
+    };
+
+    print_header("Account ID");
+    print_info(&format!("Principal: {}", principal));
+    match subaccount {
+        Some(ref sub) => {
+            print_info(&format!("Subaccount: {}", hex::encode(sub.0)));
+            print_success(&format!(
+                "ICP account ID: {}",
+                icp_account_id(principal, Some(sub))
+            ));
+        }
+        None => print_success(&format!(
+            "ICP account ID: {}",
+            icp_account_id(principal, None)
+        )),
+    }
+    show_receiving_account(
+        crate::core::utils::network::network_config().ledger_canister,
+        principal,
+        subaccount.as_ref().map(|sub| &sub.0),
+        None,
+    );
+
+    // Neuron staking account: NNS governance with a subaccount of controller and memo
+    if let Some(memo) = memo {
+        let staking = generate_subaccount_by_nonce(memo, principal);
+        let governance = crate::core::utils::network::network_config().governance_canister;
+        println!();
+        print_info(&format!(
+            "Neuron staking subaccount (memo {}): {}",
+            memo,
+            hex::encode(staking.0)
+        ));
+        print_success(&format!(
+            "Neuron staking account ID: {}",
+            icp_account_id(governance, Some(&staking))
+        ));
+    }
+
+    // Swap participation account: the swap canister with a subaccount of the buyer
+    if swap {
+        let participation = generate_participant_subaccount(principal);
+        println!();
+        print_info(&format!(
+            "Swap participation subaccount: {}",
+            hex::encode(participation.0)
+        ));
+        match app_context()
+            .try_deployment_data()
+            .and_then(|data| data.deployed_sns.swap_canister().ok())
+        {
+            Some(swap_canister) => print_success(&format!(
+                "Swap participation account ID: {}",
+                icp_account_id(swap_canister, Some(&participation))
+            )),
+            None => print_info("No deployed swap canister, so no participation account ID"),
+        }
+    }
+    Ok(())
+}
+
+/// Handle balances command
+/// One table of ICP and SNS balances and staked amounts for the owner and every participant
+pub async fn handle_balances(args: &[String]) -> Result<()> {
+    use crate::core::ops::ledger_ops::get_balance_overview;
+    use crate::core::utils::cli_args::take_switch;
+
+    let mut args = args.to_vec();
+    let json = take_switch(&mut args, "--json");
+    if args.len() > 2 {
+        return Err(invalid(format!("Unexpected argument: {}", args[2])));
+    }
+
+    let spinner = Spinner::start("Loading balances...");
+    let balances = get_balance_overview().await;
+    spinner.finish();
+    let balances = balances.context("Failed to get balances")?;
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&balances).context("Failed to serialize balances")?
+        );
+        return Ok(());
+    }
+
+    let icp_decimals = app_context()
+        .ledger_decimals(crate::core::utils::network::network_config().ledger_canister)
+        .await?;
+    let sns_decimals = if balances.iter().any(|account| account.sns_e8s.is_some()) {
+        app_context()
+            .ledger_decimals(app_context().sns_ledger_canister()?)
+            .await?
+    } else {
+        icp_decimals
+    };
+    let tokens = |e8s: Option<u64>, decimals: u8| {
+        e8s.map_or_else(
+            || "-".to_string(),
+            |e8s| format!("{:.4}", e8s as f64 / 10f64.powi(i32::from(decimals))),
+        )
+    };
+    print_header("Balances");
+    let mut table = Table::new(&[
+        "Account",
+        "Principal",
+        "ICP",
+        "ICP staked",
+        "SNS",
+        "SNS staked",
+    ])
+    .right_align(&[2, 3, 4, 5]);
+    for account in &balances {
+        table.add_row(vec![
+            account.label.clone(),
+            account.principal.to_string(),
+            tokens(account.icp_e8s, icp_decimals),
+            tokens(account.icp_staked_e8s, icp_decimals),
+            tokens(account.sns_e8s, sns_decimals),
+            tokens(account.sns_staked_e8s, sns_decimals),
+        ]);
+    }
+    table.print();
+
+    if balances.iter().any(|account| {
+        account.icp_e8s.is_none()
+            || account.icp_staked_e8s.is_none()
+            || account.sns_e8s.is_none()
+            || account.sns_staked_e8s.is_none()
+    }) {
+        print_warning("Some amounts could not be fetched (-), see the warnings above");
+    }
+    Ok(())
+}
+
 /// Handle get-sns-balance command
 pub async fn handle_get_sns_balance(args: &[String]) -> Result<()> {
-    use crate::core::ops::identity::create_agent;
-    use crate::core::utils::data_output;
-
-    // Read deployment data to get ledger canister ID
-    let deployment_path = data_output::get_output_path();
-    let data_content =
-        std::fs::read_to_string(&deployment_path).context("Failed to read deployment data")?;
-    let deployment_data: data_output::SnsCreationData =
-        serde_json::from_str(&data_content).context("Failed to parse deployment data JSON")?;
+    use crate::core::ops::ledger_ops::parse_subaccount;
 
-    let ledger_canister = deployment_data
-        .deployed_sns
-        .ledger_canister_id
-        .as_ref()
-        .and_then(|s| Principal::from_text(s).ok())
-        .context("Failed to parse ledger canister ID from deployment data")?;
+    let ledger_canister = app_context().sns_ledger_canister()?;
 
     // Step 1: Get principal (select participant or custom if not provided)
     let principal = if args.len() >= 3 {
-        Principal::from_text(&args[2]).context("Failed to parse principal")?
+        resolve_principal(&args[2]).context("Failed to parse principal")?
     } else {
         match select_participant_with_back_handling(None, Some("sns")).await {
             Ok(p) => p,
@@ -2022,8 +2211,7 @@ pub async fn handle_get_sns_balance(args: &[String]) -> Result<()> {
 
     // Step 2: Get subaccount (optional)
     let subaccount = if args.len() >= 4 {
-        let hex_str = args[3].strip_prefix("0x").unwrap_or(&args[3]);
-        Some(hex::decode(hex_str).context("Failed to decode subaccount from hex")?)
+        Some(parse_subaccount(&args[3])?.0.to_vec())
     } else {
         None
     };
@@ -2032,45 +2220,623 @@ pub async fn handle_get_sns_balance(args: &[String]) -> Result<()> {
     print_info(&format!("Ledger Canister: {}", ledger_canister));
     print_info(&format!("Principal: {}", principal));
     if let Some(ref sub) = subaccount {
-        let hex_sub = hex::encode(sub);
-        if hex_sub.len() >= 15 {
-            print_info(&format!(
-                "Subaccount: {}...{}",
-                &hex_sub[..7],
-                &hex_sub[hex_sub.len() - 8..]
-            ));
-        } else {
-            print_info(&format!("Subaccount: {}", hex_sub));
-        }
+        print_info(&format!("Subaccount: {}", short_id(&hex::encode(sub))));
     } else {
         print_info("Subaccount: None (default account)");
     }
 
     // Create anonymous agent for query
-    let anonymous_identity = ic_agent::identity::AnonymousIdentity;
-    let agent = create_agent(Box::new(anonymous_identity))
-        .await
-        .context("Failed to create agent")?;
+    let agent = app_context().anonymous_agent().await?;
 
     let balance = get_sns_ledger_balance(&agent, ledger_canister, principal, subaccount)
         .await
         .context("Failed to get SNS balance")?;
 
-    // Convert to token amount (assuming 8 decimals like ICP)
-    let token_amount = balance as f64 / 100_000_000.0;
+    let decimals = app_context().ledger_decimals(ledger_canister).await?;
     println!();
     print_success(&format!(
-        "Balance: {} e8s ({:.8} tokens)",
-        balance, token_amount
+        "Balance: {balance} e8s ({} tokens)",
+        format_tokens(balance, decimals)
     ));
     Ok(())
 }
 
-/// Handle mint-sns-tokens command
+/// Handle deploy-sns command
+/// Usage: deploy-sns [--config <path/to/sns.yaml|toml|json>] [--contributions <e8s,e8s,...>] [--fresh] [--abort-swap]
+pub async fn handle_deploy_sns(args: &[String]) -> Result<()> {
+    use crate::core::ops::deployment::deploy_sns;
+    use crate::init::sns_config::SnsConfig;
+
+    const USAGE: &str = "Usage: deploy-sns [--config <path>] [--contributions <e8s,e8s,...>] [--fresh] [--abort-swap] [--bootstrap]";
+
+    let mut config_path: Option<String> = None;
+    let mut contributions: Option<String> = None;
+    let mut fresh = false;
+    let mut abort_swap = false;
+    let mut bootstrap = false;
+    let mut i = 2;
+    while i < args.len() {
+        let arg = args[i].as_str();
+        // Ignore any checkpoint from an interrupted deployment
+        if arg == "--fresh" {
+            fresh = true;
+            i += 1;
+            continue;
+        }
+        // Start the replica and install the NNS first, as init-nns does
+        if arg == "--bootstrap" {
+            bootstrap = true;
+            i += 1;
+            continue;
+        }
+        // Leave the swap below its minimums so it aborts
+        if arg == "--abort-swap" {
+            abort_swap = true;
+            i += 1;
+            continue;
+        }
+        // Accept both `--flag value` and `--flag=value`
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) => (flag, Some(value.to_string())),
+            None => (arg, None),
+        };
+        let target = match flag {
+            "--config" => &mut config_path,
+            "--contributions" => &mut contributions,
+            _ => {
+                return Err(invalid(format!(
+                    "Unknown argument for deploy-sns: {}\n{}",
+                    arg, USAGE
+                )));
+            }
+        };
+        match inline_value {
+            Some(value) => {
+                *target = Some(value);
+                i += 1;
+            }
+            None => {
+                *target = Some(args.get(i + 1).cloned().with_context(|| {
+                    ValidationError::new(format!("{} requires a value\n{}", flag, USAGE))
+                })?);
+                i += 2;
+            }
+        }
+    }
+
+    let mut config = match config_path {
+        Some(path) => {
+            let config = SnsConfig::load(std::path::Path::new(&path))?;
+            print_info(&format!("Loaded SNS config from: {}", path));
+            config
+        }
+        None => SnsConfig::default(),
+    };
+
+    // Per-participant contributions from the command line override the config file
+    if let Some(list) = contributions {
+        let amounts = list
+            .split(',')
+            .map(|a| a.trim().parse::<u64>())
+            .collect::<Result<Vec<u64>, _>>()
+            .context(ValidationError::new(
+                "Invalid --contributions - expected comma-separated e8s amounts",
+            ))?;
+        config.participants.count = config.participants.count.max(amounts.len());
+        config.participants.contributions_icp_e8s = amounts;
+        config.validate().context(ValidationError::new(
+            "Invalid --contributions for this SNS config",
+        ))?;
+    }
+
+    if bootstrap {
+        crate::core::ops::nns_bootstrap_ops::init_nns().await?;
+    }
+
+    deploy_sns(config, fresh, abort_swap).await
+}
+
+/// Handle validate-config command
+/// Usage: validate-config [<path/to/sns.yaml|toml|json>]
+/// Checks an SNS config against the NNS limits without deploying; the built-in defaults without a path
+pub async fn handle_validate_config(args: &[String]) -> Result<()> {
+    use crate::init::sns_config::SnsConfig;
+
+    let usage = "Usage: validate-config [<path/to/sns.yaml|toml|json>]";
+    if args.len() > 3 {
+        return Err(invalid(format!(
+            "Unknown argument for validate-config: {}\n{}",
+            args[3], usage
+        )));
+    }
+    let (config, source) = match args.get(2) {
+        Some(path) => (SnsConfig::parse(std::path::Path::new(path))?, path.clone()),
+        None => (SnsConfig::default(), "built-in defaults".to_string()),
+    };
+
+    let problems = config.problems();
+    if !problems.is_empty() {
+        return Err(invalid(format!(
+            "Invalid SNS config ({}): {} problem(s) found:\n  - {}",
+            source,
+            problems.len(),
+            problems.join("\n  - ")
+        )));
+    }
+
+    // The SNS ledger does not exist yet, so its tokens are shown with the decimals SNS-W gives it
+    let tokens = |e8s: u64| e8s as f64 / 10f64.powi(i32::from(SNS_LEDGER_DECIMALS));
+    let icp = |e8s: u64| e8s as f64 / 100_000_000.0;
+    let symbol = &config.token.symbol;
+    let swap = &config.swap;
+    let swap_e8s = config.distribution.swap_e8s;
+    print_success(&format!("SNS config is valid: {}", source));
+    println!("  Name:                 {} ({})", config.name, symbol);
+    if let Some(total) = config.total_supply_e8s() {
+        println!(
+            "  Total supply:         {} {} ({:.1}% in the swap)",
+            tokens(total),
+            symbol,
+            swap_e8s as f64 / total as f64 * 100.0
+        );
+    }
+    println!(
+        "  Direct participation: {} - {} ICP from at least {} participant(s)",
+        icp(swap.minimum_direct_participation_icp_e8s),
+        icp(swap.maximum_direct_participation_icp_e8s),
+        swap.minimum_participants
+    );
+    println!(
+        "  Swap price:           {:.4} {} per ICP at the maximum, {:.4} at the minimum participation",
+        swap_e8s as f64 / swap.maximum_direct_participation_icp_e8s as f64,
+        symbol,
+        swap_e8s as f64 / swap.minimum_direct_participation_icp_e8s.max(1) as f64
+    );
+    let contributed: u64 = (1..=config.participants.count)
+        .map(|n| config.participant_contribution_e8s(n))
+        .sum();
+    println!(
+        "  Simulated swap:       {} participant(s) contributing {} ICP",
+        config.participants.count,
+        icp(contributed)
+    );
+    Ok(())
+}
+
+/// Handle show-sns-proposal-payload command
+/// Usage: show-sns-proposal-payload [--config <path>] [--owner <principal|name>] [--json] [--output <file>]
+/// Renders the CreateServiceNervousSystem action deploy-sns would submit, as Candid text or JSON
+pub async fn handle_show_sns_proposal_payload(args: &[String]) -> Result<()> {
+    use crate::core::ops::governance_ops::sns_proposal_payload;
+    use crate::core::ops::identity::load_dfx_identity;
+    use crate::core::utils::candid_json::idl_to_json;
+    use crate::core::utils::cli_args::{take_flag, take_switch};
+    use crate::init::sns_config::SnsConfig;
+
+    let mut args = args.to_vec();
+    let json = take_switch(&mut args, "--json");
+    let config_path = take_flag(&mut args, "--config")?;
+    let owner = take_flag(&mut args, "--owner")?;
+    let output = take_flag(&mut args, "--output")?;
+    if args.len() > 2 {
+        return Err(invalid(format!(
+            "Unknown argument for show-sns-proposal-payload: {}\nUsage: show-sns-proposal-payload [--config <path>] [--owner <principal|name>] [--json] [--output <file>]",
+            args[2]
+        )));
+    }
+
+    let config = match config_path {
+        Some(path) => SnsConfig::load(std::path::Path::new(&path))?,
+        None => SnsConfig::default(),
+    };
+    // deploy-sns makes the deploying identity the developer neuron controller and fallback
+    let owner_principal = match owner {
+        Some(reference) => resolve_principal(&reference)
+            .with_context(|| format!("Failed to resolve --owner '{reference}'"))?,
+        None => load_dfx_identity(None)
+            .context("Failed to load dfx identity; pass --owner <principal>")?
+            .sender()
+            .map_err(|e| anyhow::anyhow!("Failed to get principal: {e}"))?,
+    };
+
+    let payload = sns_proposal_payload(owner_principal, &config)?;
+    let text = if json {
+        serde_json::to_string_pretty(&idl_to_json(&payload))
+            .context("Failed to serialize payload")?
+    } else {
+        candid::IDLArgs::new(&[payload]).to_string()
+    };
+
+    match output {
+        Some(path) => {
+            std::fs::write(&path, format!("{text}\n"))
+                .with_context(|| format!("Failed to write {}", path))?;
+            print_success(&format!(
+                "CreateServiceNervousSystem payload written to {}",
+                path
+            ));
+        }
+        None => println!("{text}"),
+    }
+    Ok(())
+}
+
+/// Handle init-nns command
+/// Usage: init-nns
+/// Starts the local replica if needed, installs the NNS with dfx and records its canister IDs
+pub async fn handle_init_nns(args: &[String]) -> Result<()> {
+    use crate::core::ops::nns_bootstrap_ops::init_nns;
+
+    if args.len() > 2 {
+        return Err(invalid(format!(
+            "Unknown argument for init-nns: {}\nUsage: init-nns",
+            args[2]
+        )));
+    }
+
+    let record = init_nns().await?;
+    println!();
+    if record.installed {
+        print_success("NNS installed. Deploy an SNS with: deploy-sns");
+    } else {
+        print_success("NNS was already installed. Deploy an SNS with: deploy-sns");
+    }
+    Ok(())
+}
+
+/// Handle ledger-history command
+/// Usage: ledger-history <icp|sns> [limit] [--principal <principal>] [--subaccount <hex>]
+pub async fn handle_ledger_history(args: &[String]) -> Result<()> {
+    use crate::core::ops::ledger_ops::parse_subaccount;
+    use crate::core::utils::cli_args::take_flag;
+
+    let mut args = args.to_vec();
+    let principal = take_flag(&mut args, "--principal")?
+        .map(|p| resolve_principal(&p).context("Failed to parse --principal"))
+        .transpose()?;
+    let subaccount = take_flag(&mut args, "--subaccount")?
+        .map(|hex_str| parse_subaccount(&hex_str))
+        .transpose()?;
+    if subaccount.is_some() && principal.is_none() {
+        return Err(invalid(
+            "Invalid --subaccount: it selects a subaccount of --principal",
+        ));
+    }
+
+    // Step 1: Get ledger (icp/sns) - interactive if not provided
+    let use_sns = if args.len() >= 3 {
+        match args[2].to_lowercase().as_str() {
+            "icp" => false,
+            "sns" => true,
+            _ => {
+                return Err(invalid("Invalid ledger. Use 'icp' or 'sns'"));
+            }
+        }
+    } else {
+        print_header("Ledger History");
+        println!("Ledgers:");
+        println!("  [1] ICP Ledger");
+        println!("  [2] SNS Ledger");
+        println!();
+        let input = read_line("Select ledger (1 or 2): ").await?;
+        let selection = input.trim().to_lowercase();
+
+        match selection.as_str() {
+            "1" | "icp" => false,
+            "2" | "sns" => true,
+            _ => {
+                return Err(invalid("Invalid selection. Use 1 for ICP or 2 for SNS."));
+            }
+        }
+    };
+
+    // Step 2: Get number of transactions to show (optional, defaults to 20)
+    let limit: u64 = if args.len() >= 4 {
+        args[3]
+            .parse()
+            .ok()
+            .filter(|n| *n > 0)
+            .context(ValidationError::new(
+                "Invalid limit - must be a positive number",
+            ))?
+    } else {
+        20
+    };
+
+    if let Some(principal) = principal {
+        return print_account_history(use_sns, principal, subaccount.map(|s| s.0), limit).await;
+    }
+
+    let ledger_canister = if use_sns {
+        app_context().sns_ledger_canister()?
+    } else {
+        crate::core::utils::network::network_config().ledger_canister
+    };
+
+    print_header(if use_sns {
+        "SNS Ledger History"
+    } else {
+        "ICP Ledger History"
+    });
+    print_info(&format!("Ledger Canister: {}", ledger_canister));
+
+    // Create anonymous agent for query
+    let agent = app_context().anonymous_agent().await?;
+
+    let entries = if use_sns {
+        get_sns_ledger_history(&agent, ledger_canister, limit)
+            .await
+            .context("Failed to get SNS ledger history")?
+    } else {
+        get_icp_ledger_history(&agent, ledger_canister, limit)
+            .await
+            .context("Failed to get ICP ledger history")?
+    };
+
+    if entries.is_empty() {
+        print_warning("No transactions found on this ledger");
+        return Ok(());
+    }
+
+    print_success(&format!(
+        "Showing {} most recent transaction(s)",
+        entries.len()
+    ));
+    println!();
+
+    print_ledger_history_table(&entries, &[]);
+
+    Ok(())
+}
+
+/// Print one account's history from the ICP or SNS index (ledger-history --principal)
+/// ICP transfers to the swap escrow and to neuron staking accounts are named in a Note column
+async fn print_account_history(
+    use_sns: bool,
+    principal: Principal,
+    subaccount: Option<[u8; 32]>,
+    limit: u64,
+) -> Result<()> {
+    use crate::core::ops::icp_index_ops::{
+        describe_counterparty, get_account_identifier_transactions, get_icp_index_sync,
+        known_icp_accounts,
+    };
+    use crate::core::ops::ledger_ops::icp_account_id;
+    use crate::core::ops::sns_index_ops::{get_account_transactions, get_index_sync};
+
+    let agent = app_context().anonymous_agent().await?;
+    let network = crate::core::utils::network::network_config();
+
+    let (result, sync, notes) = if use_sns {
+        let index_canister = app_context().sns_index_canister()?;
+        print_header("SNS Account History");
+        print_info(&format!("Index Canister: {}", index_canister));
+        print_info(&format!("Principal: {}", principal));
+        let sync =
+            get_index_sync(&agent, index_canister, app_context().sns_ledger_canister()?).await;
+        let account = crate::core::declarations::sns_index::Account {
+            owner: principal,
+            subaccount: subaccount.map(|s| s.to_vec()),
+        };
+        let result = get_account_transactions(&agent, index_canister, account, None, limit)
+            .await
+            .context("Failed to get account transactions from the SNS index")?;
+        (result, sync, Vec::new())
+    } else {
+        let index_canister = network.icp_index_canister;
+        let own_account = icp_account_id(
+            principal,
+            subaccount.map(ic_ledger_types::Subaccount).as_ref(),
+        );
+        print_header("ICP Account History");
+        print_info(&format!("Index Canister: {}", index_canister));
+        print_info(&format!("Principal: {}", principal));
+        print_info(&format!("Account ID: {}", own_account));
+        let sync = get_icp_index_sync(&agent, index_canister, network.ledger_canister).await;
+        let result =
+            get_account_identifier_transactions(&agent, index_canister, &own_account, None, limit)
+                .await
+                .context("Failed to get account transactions from the ICP index")?;
+
+        let swap_canister = app_context()
+            .try_deployment_data()
+            .and_then(|data| data.deployed_sns.swap_canister().ok());
+        let known = known_icp_accounts(principal, swap_canister);
+        let notes = result
+            .transactions
+            .iter()
+            .map(|entry| describe_counterparty(entry, &own_account, &known))
+            .collect();
+        (result, sync, notes)
+    };
+
+    // The index trails the ledger, so a fresh transfer may not be listed yet
+    match sync {
+        Ok(sync) if sync.is_synced() => {}
+        Ok(sync) => print_warning(&format!(
+            "Index is behind the ledger: {} of {} blocks synced; recent transactions may be missing",
+            sync.blocks_synced, sync.ledger_blocks
+        )),
+        Err(e) => print_warning(&format!("Could not check index sync: {e:#}")),
+    }
+    let ledger_canister = if use_sns {
+        app_context().sns_ledger_canister()?
+    } else {
+        network.ledger_canister
+    };
+    let decimals = app_context().ledger_decimals(ledger_canister).await?;
+    print_info(&format!(
+        "Balance (index): {} e8s ({} {})",
+        result.balance_e8s,
+        format_tokens(result.balance_e8s, decimals),
+        if use_sns { "tokens" } else { "ICP" }
+    ));
+
+    if result.transactions.is_empty() {
+        print_warning("No transactions found for this account");
+        return Ok(());
+    }
+    print_success(&format!(
+        "Showing {} most recent transaction(s)",
+        result.transactions.len()
+    ));
+    println!();
+
+    print_ledger_history_table(&result.transactions, &notes);
+    Ok(())
+}
+
+/// Handle get-account-transactions command
+/// Usage: get-account-transactions [principal] [subaccount_hex] [--limit <n>]
+pub async fn handle_get_account_transactions(args: &[String]) -> Result<()> {
+    use crate::core::ops::ledger_ops::parse_subaccount;
+    use crate::core::ops::sns_index_ops::{get_account_transactions, get_index_sync};
+    use crate::core::utils::cli_args::take_flag;
+
+    let mut args = args.to_vec();
+    let limit: u64 = match take_flag(&mut args, "--limit")? {
+        Some(limit) => limit
+            .parse()
+            .ok()
+            .filter(|n| *n > 0)
+            .context(ValidationError::new(
+                "Invalid --limit - must be a positive number",
+            ))?,
+        None => 20,
+    };
+
+    let index_canister = app_context().sns_index_canister()?;
+    let ledger_canister = app_context().sns_ledger_canister()?;
+
+    // Step 1: Get principal (select participant or custom if not provided)
+    let principal = if args.len() >= 3 {
+        resolve_principal(&args[2]).context("Failed to parse principal")?
+    } else {
+        match select_participant_with_back_handling(None, Some("sns")).await {
+            Ok(p) => p,
+            Err(e) if is_user_went_back_error(&e) => return Ok(()),
+            Err(e) => return Err(e),
+        }
+    };
+
+    // Step 2: Get subaccount (optional)
+    let subaccount = if args.len() >= 4 {
+        Some(parse_subaccount(&args[3])?.0.to_vec())
+    } else {
+        None
+    };
+
+    print_header("SNS Account Transactions");
+    print_info(&format!("Index Canister: {}", index_canister));
+    print_info(&format!("Principal: {}", principal));
+    if let Some(ref sub) = subaccount {
+        print_info(&format!("Subaccount: {}", short_id(&hex::encode(sub))));
+    }
+
+    // Create anonymous agent for query
+    let agent = app_context().anonymous_agent().await?;
+
+    // The index trails the ledger, so a fresh transfer may not be listed yet
+    match get_index_sync(&agent, index_canister, ledger_canister).await {
+        Ok(sync) if sync.is_synced() => print_info(&format!(
+            "Index synced: {} of {} ledger blocks",
+            sync.blocks_synced, sync.ledger_blocks
+        )),
+        Ok(sync) => print_warning(&format!(
+            "Index is behind the ledger: {} of {} blocks synced; recent transactions may be missing",
+            sync.blocks_synced, sync.ledger_blocks
+        )),
+        Err(e) => print_warning(&format!("Could not check index sync: {e:#}")),
+    }
+
+    let account = crate::core::declarations::sns_index::Account {
+        owner: principal,
+        subaccount,
+    };
+    let result = get_account_transactions(&agent, index_canister, account, None, limit)
+        .await
+        .context("Failed to get account transactions from the index")?;
+
+    let decimals = app_context().ledger_decimals(ledger_canister).await?;
+    print_info(&format!(
+        "Balance (index): {} e8s ({} tokens)",
+        result.balance_e8s,
+        format_tokens(result.balance_e8s, decimals)
+    ));
+    if result.transactions.is_empty() {
+        print_warning("No transactions found for this account");
+        return Ok(());
+    }
+
+    let older = result
+        .oldest_tx_id
+        .zip(result.transactions.last())
+        .is_some_and(|(oldest, last)| last.index > oldest);
+    print_success(&format!(
+        "Showing {} most recent transaction(s){}",
+        result.transactions.len(),
+        if older {
+            "; pass a larger --limit for older ones"
+        } else {
+            ""
+        }
+    ));
+    println!();
+
+    print_ledger_history_table(&result.transactions, &[]);
+
+    Ok(())
+}
+
+/// Print ledger history entries as a table
+/// `notes` (one per entry) add a Note column when any is set
+fn print_ledger_history_table(entries: &[LedgerHistoryEntry], notes: &[Option<String>]) {
+    // Shorten long account identifiers to keep the table readable
+    let shorten = |value: Option<&str>| value.map_or_else(|| "-".to_string(), short_id);
+    let with_notes = notes.iter().any(Option::is_some);
+
+    let mut headers = vec![
+        "Index",
+        "Kind",
+        "From",
+        "To",
+        "Amount (e8s)",
+        "Memo",
+        "Timestamp (s)",
+    ];
+    if with_notes {
+        headers.push("Note");
+    }
+    let mut table = Table::new(&headers).right_align(&[0, 4]);
+    for (i, entry) in entries.iter().enumerate() {
+        let mut row = vec![
+            entry.index.to_string(),
+            entry.kind.to_string(),
+            shorten(entry.from.as_deref()),
+            shorten(entry.to.as_deref()),
+            entry.amount_e8s.to_string(),
+            shorten(entry.memo.as_deref()),
+            (entry.timestamp_nanos / 1_000_000_000).to_string(),
+        ];
+        if with_notes {
+            row.push(notes.get(i).cloned().flatten().unwrap_or_default());
+        }
+        table.add_row(row);
+    }
+    table.print();
+    println!();
+}
+
+/// Handle mint-sns-tokens command
+/// Usage: mint-sns-tokens [proposer_principal] [receiver_principal] [amount_e8s] [--wait]
 pub async fn handle_mint_sns_tokens(args: &[String]) -> Result<()> {
+    let mut args = args.to_vec();
+    let wait = crate::core::utils::cli_args::take_switch(&mut args, "--wait");
+    let amount = take_amount(&mut args, 4)?;
+
     // Step 1: Get proposer principal (select participant or custom if not provided)
     let proposer_principal = if args.len() >= 3 {
-        Principal::from_text(&args[2]).context("Failed to parse proposer principal")?
+        resolve_principal(&args[2]).context("Failed to parse proposer principal")?
     } else {
         select_participant_or_custom_with_label_and_counts(
             Some("Select Proposer Principal:"),
@@ -2081,7 +2847,7 @@ pub async fn handle_mint_sns_tokens(args: &[String]) -> Result<()> {
 
     // Step 2: Get receiver_principal (select participant or custom if not provided)
     let receiver_principal = if args.len() >= 4 {
-        Principal::from_text(&args[3]).context("Failed to parse receiver principal")?
+        resolve_principal(&args[3]).context("Failed to parse receiver principal")?
     } else {
         select_participant_or_custom_with_label_and_counts(
             Some("Select Receiver Principal:"),
@@ -2090,21 +2856,38 @@ pub async fn handle_mint_sns_tokens(args: &[String]) -> Result<()> {
         .await?
     };
 
-    // Step 3: Get amount_e8s
-    let amount_e8s = if args.len() >= 5 {
-        args[4]
-            .parse::<u64>()
-            .context("Failed to parse amount_e8s")?
-    } else {
-        print!("Enter amount to mint (in e8s, e.g., 100000000 = 1 token): ");
-        io::stdout().flush()?;
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
-        input
-            .trim()
-            .parse::<u64>()
-            .context("Failed to parse amount_e8s")?
+    // Step 3: Get amount (interactive if not provided)
+    let amount = match amount {
+        Some(amount) => amount,
+        None => {
+            let input = read_input_required(
+                "Enter tokens to mint (e.g., 1.5 or 2.0, or 150000000e8s in e8s; or press Enter/[b]ack to go back): ",
+            ).await
+            .map_err(navigation_to_anyhow)?;
+            AmountInput::from_prompt(&input)?
+        }
     };
+    let ledger_canister = app_context().sns_ledger_canister()?;
+    let amount_e8s = amount.e8s(ledger_canister).await?;
+
+    if needs_confirmation() && is_large_mint(ledger_canister, amount_e8s).await? {
+        let agent = app_context().anonymous_agent().await?;
+        let balance = get_sns_ledger_balance(&agent, ledger_canister, receiver_principal, None)
+            .await
+            .context("Failed to get receiver SNS balance")?;
+        let summary = [
+            ("Proposer", proposer_principal.to_string()),
+            ("Receiver", receiver_principal.to_string()),
+            ("Amount", format!("{} e8s", amount_e8s)),
+            (
+                "Resulting balance",
+                format!("{} e8s", balance.saturating_add(amount_e8s)),
+            ),
+        ];
+        if !confirm_action("mint SNS tokens", &summary).await? {
+            return Err(Cancelled.into());
+        }
+    }
 
     print_header("Minting SNS Tokens");
     print_info(&format!("Proposer: {}", proposer_principal));
@@ -2125,173 +2908,699 @@ pub async fn handle_mint_sns_tokens(args: &[String]) -> Result<()> {
         proposal_id
     ));
     print_info("All participant neurons have voted on the proposal.");
+    if wait {
+        wait_for_proposal(proposal_id).await?;
+    }
     Ok(())
 }
 
-/// Handle create-sns-neuron command
-pub async fn handle_create_sns_neuron(args: &[String]) -> Result<()> {
-    use crate::core::ops::identity::create_agent;
-    use crate::core::ops::sns_governance_ops::get_neuron_minimum_stake;
-    use crate::core::utils::data_output::get_output_path;
-    use std::fs;
-
-    // Read deployment data to get governance canister ID
-    let deployment_path = get_output_path();
-    let data_content =
-        fs::read_to_string(&deployment_path).context("Failed to read deployment data")?;
-    let deployment_data: crate::core::utils::data_output::SnsCreationData =
-        serde_json::from_str(&data_content).context("Failed to parse deployment data JSON")?;
-
-    let governance_canister = deployment_data
-        .deployed_sns
-        .governance_canister_id
-        .as_ref()
-        .and_then(|s| Principal::from_text(s).ok())
-        .context("Failed to parse governance canister ID from deployment data")?;
+/// Proposer from `--proposer` (`owner`, `participant-<n>`, identity name or principal),
+/// otherwise picked from the menu
+async fn take_proposer(args: &mut Vec<String>) -> Result<Principal> {
+    use crate::core::utils::cli_args::take_flag;
 
-    // Get minimum stake (using anonymous identity for query)
-    let anonymous_identity = ic_agent::identity::AnonymousIdentity;
-    let agent = create_agent(Box::new(anonymous_identity))
-        .await
-        .context("Failed to create agent")?;
-    let minimum_stake = get_neuron_minimum_stake(&agent, governance_canister)
-        .await
-        .context("Failed to get minimum stake")?;
+    match take_flag(args, "--proposer")? {
+        Some(reference) => resolve_principal(&reference),
+        None => {
+            select_participant_or_custom_with_label_and_counts(
+                Some("Select Proposer Principal:"),
+                Some("sns"),
+            )
+            .await
+        }
+    }
+}
 
-    // Step 1: Get principal (select participant if not provided)
-    let principal = if args.len() >= 3 {
-        Principal::from_text(&args[2]).context("Failed to parse principal")?
-    } else {
-        match select_participant_with_back_handling(None, Some("sns")).await {
-            Ok(p) => p,
-            Err(e) if is_user_went_back_error(&e) => return Ok(()),
-            Err(e) => return Err(e),
+/// Handle add-generic-function command
+/// Usage: add-generic-function <spec_file> [--proposer <principal>] [--wait]
+pub async fn handle_add_generic_function(args: &[String]) -> Result<()> {
+    use crate::core::ops::generic_function_ops::{
+        GenericFunctionSpec, add_generic_function_default_path,
+    };
+    use crate::core::utils::cli_args::take_switch;
+
+    let mut args = args.to_vec();
+    let wait = take_switch(&mut args, "--wait");
+    let proposer = take_proposer(&mut args).await?;
+    if args.len() > 3 {
+        return Err(invalid(format!("Unexpected argument: {}", args[3])));
+    }
+    let path = match args.get(2) {
+        Some(path) => path.clone(),
+        None => {
+            print_header("Add Generic Function");
+            read_input_required(
+                "Enter the function spec file (.yaml, .toml or .json; Enter/[b]ack to go back): ",
+            )
+            .await
+            .map_err(navigation_to_anyhow)?
         }
     };
+    let spec = GenericFunctionSpec::load(std::path::Path::new(&path))?;
 
-    // Get balance and fee to show user options
-    use crate::core::ops::ledger_ops::{get_sns_ledger_balance, get_sns_ledger_fee};
-    let ledger_canister = deployment_data
-        .deployed_sns
-        .ledger_canister_id
-        .as_ref()
-        .and_then(|s| Principal::from_text(s).ok())
-        .context("Failed to parse ledger canister ID from deployment data")?;
+    print_header("Adding Generic Function");
+    print_info(&format!("Proposer: {}", proposer));
+    print_info(&format!("Function: {} ({})", spec.id, spec.name));
+    print_info(&format!(
+        "Target: {}.{}",
+        spec.target_canister, spec.target_method
+    ));
+    print_info(&format!(
+        "Validator: {}.{}",
+        spec.validator_canister
+            .as_deref()
+            .unwrap_or(&spec.target_canister),
+        spec.validator_method
+    ));
+    print_info("Creating proposal and getting all neurons to vote...");
 
-    let balance = get_sns_ledger_balance(&agent, ledger_canister, principal, None)
+    let proposal_id = add_generic_function_default_path(proposer, &spec)
         .await
-        .context("Failed to get SNS ledger balance")?;
-    let transfer_fee = get_sns_ledger_fee(&agent, ledger_canister)
-        .await
-        .context("Failed to get SNS ledger transfer fee")?;
-
-    // Step 2: Get optional amount (interactive if not provided)
-    let amount_e8s = if args.len() >= 4 {
-        Some(
-            args[3]
-                .parse::<u64>()
-                .context("Failed to parse amount_e8s")?,
-        )
-    } else {
-        // Interactive prompt for amount
-        print_header("Creating SNS Neuron");
-        print_info(&format!("Principal: {}", principal));
-        print_info(&format!("Available balance: {} e8s", balance));
-        print_info(&format!("Transfer fee: {} e8s", transfer_fee));
-        print_info(&format!("Minimum stake required: {} e8s", minimum_stake));
-        let max_available = if balance > transfer_fee {
-            balance - transfer_fee
-        } else {
-            0
-        };
-        if max_available >= minimum_stake {
-            print_info(&format!(
-                "Maximum stakeable (balance - fee): {} e8s",
-                max_available
-            ));
-        }
-        println!();
-        let input_opt = match read_input_optional(&format!(
-            "Enter amount to stake in e8s (press Enter to use maximum: {} e8s, or [b]ack to go back): ",
-            max_available
-        )) {
-            Ok(opt) => opt,
-            Err(nav) => {
-                return Err(navigation_to_anyhow(nav));
-            }
-        };
+        .context("Failed to add generic function")?;
 
-        if let Some(input) = input_opt {
-            let amount: u64 = input
-                .parse()
-                .context("Failed to parse amount - must be a number")?;
-            Some(amount)
+    print_success(&format!(
+        "Proposal created successfully! Proposal ID: {}",
+        proposal_id
+    ));
+    print_info("All participant neurons have voted on the proposal.");
+    if wait {
+        wait_for_proposal(proposal_id).await?;
+    }
+    Ok(())
+}
+
+/// Handle execute-generic-function command
+/// Usage: execute-generic-function <function_id> --payload-file <file> [--proposer <principal>] [--wait]
+pub async fn handle_execute_generic_function(args: &[String]) -> Result<()> {
+    use crate::core::ops::generic_function_ops::{
+        MIN_GENERIC_FUNCTION_ID, execute_generic_function_default_path, read_payload_file,
+    };
+    use crate::core::utils::cli_args::{take_flag, take_switch};
+
+    let mut args = args.to_vec();
+    let wait = take_switch(&mut args, "--wait");
+    let payload_file = take_flag(&mut args, "--payload-file")?;
+    let proposer = take_proposer(&mut args).await?;
+    if args.len() > 3 {
+        return Err(invalid(format!("Unexpected argument: {}", args[3])));
+    }
+
+    if args.len() < 3 || payload_file.is_none() {
+        print_header("Execute Generic Function");
+    }
+    let function_id = match args.get(2) {
+        Some(id) => id.clone(),
+        None => read_input_required("Enter the function ID (or press Enter/[b]ack to go back): ")
+            .await
+            .map_err(navigation_to_anyhow)?,
+    };
+    let function_id = function_id
+        .parse::<u64>()
+        .ok()
+        .filter(|id| *id >= MIN_GENERIC_FUNCTION_ID)
+        .with_context(|| {
+            format!(
+                "Invalid function ID '{}': expected a number of at least {}",
+                function_id, MIN_GENERIC_FUNCTION_ID
+            )
+        })?;
+    let payload_file = match payload_file {
+        Some(path) => path,
+        None => read_input_required(
+            "Enter the payload file (Candid bytes or hex; Enter/[b]ack to go back): ",
+        )
+        .await
+        .map_err(navigation_to_anyhow)?,
+    };
+    let payload = read_payload_file(std::path::Path::new(&payload_file))?;
+
+    print_header("Executing Generic Function");
+    print_info(&format!("Proposer: {}", proposer));
+    print_info(&format!("Function ID: {}", function_id));
+    print_info(&format!(
+        "Payload: {} bytes from {}",
+        payload.len(),
+        payload_file
+    ));
+    print_info("Creating proposal and getting all neurons to vote...");
+
+    let proposal_id = execute_generic_function_default_path(proposer, function_id, payload)
+        .await
+        .context("Failed to execute generic function")?;
+
+    print_success(&format!(
+        "Proposal created successfully! Proposal ID: {}",
+        proposal_id
+    ));
+    print_info("All participant neurons have voted on the proposal.");
+    if wait {
+        wait_for_proposal(proposal_id).await?;
+    }
+    Ok(())
+}
+
+/// Handle extensions command
+/// Usage: extensions register <wasm_file> --store-canister <id> [--sns-amount <tokens>] [--icp-amount <tokens>]
+///        extensions deposit <extension_canister> [--sns-amount <tokens>] [--icp-amount <tokens>]
+/// Amounts also take e8s as --sns-amount-e8s <n> and --icp-amount-e8s <n>
+///        extensions withdraw <extension_canister>
+///        extensions list
+/// Proposals accept [--proposer <principal>] [--wait]
+pub async fn handle_extensions(args: &[String]) -> Result<()> {
+    use crate::core::ops::extension_ops::{
+        execute_extension_operation_default_path, list_extension_operations_default_path,
+        register_extension_default_path, treasury_allocation,
+    };
+    use crate::core::utils::cli_args::{take_flag, take_switch};
+
+    let mut args = args.to_vec();
+    let wait = take_switch(&mut args, "--wait");
+    let store_canister = take_flag(&mut args, "--store-canister")?;
+    let sns_amount = take_named_amount_flags(&mut args, "--sns-amount")?;
+    let icp_amount = take_named_amount_flags(&mut args, "--icp-amount")?;
+    let proposer_flag = take_flag(&mut args, "--proposer")?;
+
+    // Step 1: Get subcommand - interactive if not provided
+    let subcommand = if args.len() >= 3 {
+        args[2].to_lowercase()
+    } else {
+        print_header("SNS Extensions");
+        println!("  [1] Register extension");
+        println!("  [2] Deposit into extension");
+        println!("  [3] Withdraw from extension");
+        println!("  [4] List extensions");
+        println!();
+        match read_input_required("Select action [1-4] (or press Enter/[b]ack to go back): ").await
+        {
+            Ok(input) => match input.as_str() {
+                "1" | "register" => "register".to_string(),
+                "2" | "deposit" => "deposit".to_string(),
+                "3" | "withdraw" => "withdraw".to_string(),
+                "4" | "list" => "list".to_string(),
+                _ => return Err(invalid("Invalid selection - must be 1, 2, 3 or 4")),
+            },
+            Err(UserNavigation::GoBack | UserNavigation::GoToMainMenu) => return Ok(()),
+        }
+    };
+    if args.len() > 4 {
+        return Err(invalid(format!("Unexpected argument: {}", args[4])));
+    }
+
+    if subcommand == "list" {
+        return extensions_list(list_extension_operations_default_path().await?);
+    }
+    if !matches!(subcommand.as_str(), "register" | "deposit" | "withdraw") {
+        return Err(invalid(format!(
+            "Unknown extensions subcommand '{}'. Use register, deposit, withdraw or list",
+            subcommand
+        )));
+    }
+
+    // Treasury allocation of register and deposit, in e8s of each ledger (0 if not given)
+    let sns_e8s = match &sns_amount {
+        Some(amount) => amount.e8s(app_context().sns_ledger_canister()?).await?,
+        None => 0,
+    };
+    let icp_e8s = match &icp_amount {
+        Some(amount) => amount.e8s(app_context().network().ledger_canister).await?,
+        None => 0,
+    };
+
+    // Step 2: The wasm file or extension canister
+    let target = match args.get(3) {
+        Some(target) => target.clone(),
+        None if subcommand == "register" => read_input_required(
+            "Enter the extension wasm file (or press Enter/[b]ack to go back): ",
+        )
+        .await
+        .map_err(navigation_to_anyhow)?,
+        None => read_input_required(
+            "Enter the extension canister ID (or press Enter/[b]ack to go back): ",
+        )
+        .await
+        .map_err(navigation_to_anyhow)?,
+    };
+    let proposer = match proposer_flag {
+        Some(reference) => resolve_principal(&reference)?,
+        None => {
+            select_participant_or_custom_with_label_and_counts(
+                Some("Select Proposer Principal:"),
+                Some("sns"),
+            )
+            .await?
+        }
+    };
+
+    let proposal_id = if subcommand == "register" {
+        let store_canister = match store_canister {
+            Some(id) => id,
+            None => read_input_required(
+                "Enter the canister to store the wasm chunks on (controlled by the owner): ",
+            )
+            .await
+            .map_err(navigation_to_anyhow)?,
+        };
+        let store_canister = Principal::from_text(&store_canister)
+            .context(ValidationError::new("Invalid --store-canister"))?;
+        let wasm = std::fs::read(&target)
+            .with_context(|| format!("Failed to read extension wasm: {}", target))?;
+
+        print_header("Registering Extension");
+        print_info(&format!("Proposer: {}", proposer));
+        print_info(&format!("Wasm: {} ({} bytes)", target, wasm.len()));
+        print_info(&format!("Chunk store: {}", store_canister));
+        print_info(&format!(
+            "Initial allocation: {} SNS e8s, {} ICP e8s",
+            sns_e8s, icp_e8s
+        ));
+        print_info("Uploading wasm chunks, creating proposal and getting all neurons to vote...");
+        register_extension_default_path(
+            proposer,
+            &wasm,
+            store_canister,
+            treasury_allocation(sns_e8s, icp_e8s),
+        )
+        .await
+        .context("Failed to register extension")?
+    } else {
+        let extension_canister = Principal::from_text(&target)
+            .context(ValidationError::new("Invalid extension canister ID"))?;
+        let arg = (subcommand == "deposit").then(|| treasury_allocation(sns_e8s, icp_e8s));
+
+        print_header(if subcommand == "deposit" {
+            "Depositing into Extension"
         } else {
-            // Use maximum available
-            if max_available < minimum_stake {
-                anyhow::bail!(
-                    "Insufficient balance: maximum available {} e8s is less than minimum stake {} e8s",
-                    max_available,
-                    minimum_stake
-                );
-            }
-            None // Will be handled in create_sns_neuron as "all available"
+            "Withdrawing from Extension"
+        });
+        print_info(&format!("Proposer: {}", proposer));
+        print_info(&format!("Extension: {}", extension_canister));
+        if arg.is_some() {
+            print_info(&format!(
+                "Allocation: {} SNS e8s, {} ICP e8s",
+                sns_e8s, icp_e8s
+            ));
         }
+        print_info("Creating proposal and getting all neurons to vote...");
+        execute_extension_operation_default_path(proposer, extension_canister, &subcommand, arg)
+            .await
+            .with_context(|| format!("Failed to {} extension", subcommand))?
     };
 
-    // Step 3: Get optional memo
-    let memo = if args.len() >= 5 {
-        Some(args[4].parse::<u64>().context("Failed to parse memo")?)
+    print_success(&format!(
+        "Proposal created successfully! Proposal ID: {}",
+        proposal_id
+    ));
+    print_info("All participant neurons have voted on the proposal.");
+    if wait {
+        wait_for_proposal(proposal_id).await?;
+    }
+    Ok(())
+}
+
+/// Print the operations of the registered extensions
+fn extensions_list(
+    operations: Vec<crate::core::ops::extension_ops::RegisteredExtensionOperation>,
+) -> Result<()> {
+    print_header("SNS Extensions");
+    if operations.is_empty() {
+        print_info("No extensions registered (see extensions register)");
+        return Ok(());
+    }
+
+    let mut table = Table::new(&["Extension", "Operation", "Topic", "Description"]);
+    for operation in operations {
+        table.add_row(vec![
+            operation
+                .canister_id
+                .map_or_else(|| "-".to_string(), |id| id.to_string()),
+            operation.operation,
+            operation.topic,
+            operation.description.unwrap_or_else(|| "-".to_string()),
+        ]);
+    }
+    table.print();
+    Ok(())
+}
+
+/// Handle upgrade-sns-to-next-version command
+/// Usage: upgrade-sns-to-next-version [--proposer <principal>]
+pub async fn handle_upgrade_sns_to_next_version(args: &[String]) -> Result<()> {
+    run_sns_upgrade(args, false).await
+}
+
+/// Handle advance-sns-target-version command
+/// Usage: advance-sns-target-version [--proposer <principal>]
+pub async fn handle_advance_sns_target_version(args: &[String]) -> Result<()> {
+    run_sns_upgrade(args, true).await
+}
+
+/// Propose an SNS upgrade (one step, or advancing the target to the latest version) and
+/// print the upgrade journal until governance runs the target version
+async fn run_sns_upgrade(args: &[String], advance_target: bool) -> Result<()> {
+    use crate::core::ops::sns_upgrade_ops::{
+        advance_sns_target_version_default_path, describe_journal_event, format_version,
+        get_upgrade_journal_default_path, upgrade_sns_to_next_version_default_path,
+        wait_for_sns_upgrade_default_path,
+    };
+    use crate::core::utils::time_format::format_utc;
+
+    let mut args = args.to_vec();
+    let proposer = take_proposer(&mut args).await?;
+    if args.len() > 2 {
+        return Err(invalid(format!("Unexpected argument: {}", args[2])));
+    }
+
+    print_header(if advance_target {
+        "Advancing SNS Target Version"
     } else {
-        None
+        "Upgrading SNS to Next Version"
+    });
+    let journal = get_upgrade_journal_default_path(None).await?;
+    print_info(&format!("Proposer: {}", proposer));
+    if let Some(deployed) = &journal.deployed_version {
+        print_info(&format!("Deployed version: {}", format_version(deployed)));
+    }
+    let steps = journal
+        .upgrade_steps
+        .as_ref()
+        .map_or(0, |steps| steps.versions.len().saturating_sub(1));
+    print_info(&format!("Upgrade steps available: {}", steps));
+    print_info("Creating proposal and getting all neurons to vote...");
+
+    let proposal_id = if advance_target {
+        advance_sns_target_version_default_path(proposer)
+            .await
+            .context("Failed to advance SNS target version")?
+    } else {
+        upgrade_sns_to_next_version_default_path(proposer)
+            .await
+            .context("Failed to upgrade SNS to next version")?
     };
 
-    // Step 4: Get optional dissolve delay (interactive if not provided)
-    let dissolve_delay_seconds = if args.len() >= 6 {
-        let delay = args[5]
-            .parse::<u64>()
-            .context("Failed to parse dissolve_delay_seconds")?;
-        if delay > 0 { Some(delay) } else { None }
+    print_success(&format!(
+        "Proposal created successfully! Proposal ID: {}",
+        proposal_id
+    ));
+    print_info("All participant neurons have voted on the proposal.");
+    print_info("Following the upgrade journal...");
+
+    let deployed = wait_for_sns_upgrade_default_path(
+        proposal_id,
+        journal.upgrade_journal_entry_count.unwrap_or_default(),
+        |entry| {
+            println!(
+                "  {}  {}",
+                entry
+                    .timestamp_seconds
+                    .map_or_else(|| "-".to_string(), format_utc),
+                describe_journal_event(entry.event.as_ref())
+            );
+        },
+    )
+    .await?;
+    print_success(&format!(
+        "SNS upgrade complete. Deployed version: {}",
+        format_version(&deployed)
+    ));
+    Ok(())
+}
+
+/// Handle make-nns-motion command
+/// Usage: make-nns-motion [title] [--text <motion_text>] [--url <url>]
+pub async fn handle_make_nns_motion(args: &[String]) -> Result<()> {
+    use crate::core::ops::nns_proposal_ops::make_motion_proposal_default_path;
+    use crate::core::utils::cli_args::take_flag;
+
+    let mut args = args.to_vec();
+    let text = take_flag(&mut args, "--text")?;
+    let url = take_flag(&mut args, "--url")?.unwrap_or_default();
+    if args.len() > 3 {
+        return Err(invalid(format!("Unexpected argument: {}", args[3])));
+    }
+
+    if args.len() < 3 || text.is_none() {
+        print_header("Make NNS Motion");
+    }
+    let title = match args.get(2) {
+        Some(title) => title.clone(),
+        None => read_input_required("Enter the motion title (or press Enter/[b]ack to go back): ")
+            .await
+            .map_err(navigation_to_anyhow)?,
+    };
+    let text = match text {
+        Some(text) => text,
+        None => read_input_required("Enter the motion text (or press Enter/[b]ack to go back): ")
+            .await
+            .map_err(navigation_to_anyhow)?,
+    };
+
+    print_header("Making NNS Motion Proposal");
+    let deployment_data = app_context().deployment_data()?;
+    print_info(&format!(
+        "Proposer: ICP neuron {} (from deployment data)",
+        deployment_data.icp_neuron_id
+    ));
+    print_info(&format!("Title: {}", title));
+
+    let proposal_id = make_motion_proposal_default_path(&title, &text, &url)
+        .await
+        .context("Failed to make motion proposal")?;
+    print_success(&format!(
+        "Proposal created successfully! Proposal ID: {}",
+        proposal_id
+    ));
+    print_nns_proposal(proposal_id).await
+}
+
+/// Handle vote-nns-proposal command
+/// Usage: vote-nns-proposal [proposal_id] [yes|no]
+pub async fn handle_vote_nns_proposal(args: &[String]) -> Result<()> {
+    use crate::core::ops::nns_proposal_ops::vote_on_nns_proposal_default_path;
+
+    if args.len() > 4 {
+        return Err(invalid(format!("Unexpected argument: {}", args[4])));
+    }
+    if args.len() < 4 {
+        print_header("Vote on NNS Proposal");
+    }
+    let proposal_id = match args.get(2) {
+        Some(id) => id.clone(),
+        None => {
+            read_input_required("Enter the NNS proposal ID (or press Enter/[b]ack to go back): ")
+                .await
+                .map_err(navigation_to_anyhow)?
+        }
+    };
+    let proposal_id = proposal_id
+        .parse::<u64>()
+        .with_context(|| ValidationError::new(format!("Invalid proposal ID '{}'", proposal_id)))?;
+    let vote = match args.get(3) {
+        Some(vote) => vote.clone(),
+        None => read_input_required("Vote [yes/no] (or press Enter/[b]ack to go back): ")
+            .await
+            .map_err(navigation_to_anyhow)?,
+    };
+    let yes = match vote.to_lowercase().as_str() {
+        "yes" | "y" => true,
+        "no" | "n" => false,
+        _ => {
+            return Err(invalid(format!(
+                "Invalid vote '{}': expected yes or no",
+                vote
+            )));
+        }
+    };
+
+    print_header("Voting on NNS Proposal");
+    let deployment_data = app_context().deployment_data()?;
+    print_info(&format!(
+        "Voting {} on proposal {} with ICP neuron {} (from deployment data)",
+        if yes { "Yes" } else { "No" },
+        proposal_id,
+        deployment_data.icp_neuron_id
+    ));
+
+    vote_on_nns_proposal_default_path(proposal_id, yes)
+        .await
+        .context("Failed to vote on NNS proposal")?;
+    print_success("Vote registered");
+    print_nns_proposal(proposal_id).await
+}
+
+/// Print the status and tally of an NNS proposal
+async fn print_nns_proposal(proposal_id: u64) -> Result<()> {
+    use crate::core::ops::governance_errors::describe_nns_governance_error;
+    use crate::core::ops::nns_proposal_ops::{
+        get_nns_proposal_default_path, nns_proposal_status_name,
+    };
+
+    let Some(info) = get_nns_proposal_default_path(proposal_id).await? else {
+        print_warning(&format!(
+            "NNS governance does not know proposal {}",
+            proposal_id
+        ));
+        return Ok(());
+    };
+    print_info(&format!(
+        "Status: {}",
+        nns_proposal_status_name(info.status)
+    ));
+    if let Some(tally) = &info.latest_tally {
+        print_info(&format!(
+            "Tally: Yes {} / No {} of {} voting power",
+            tally.yes, tally.no, tally.total
+        ));
+    }
+    if let Some(reason) = &info.failure_reason {
+        print_warning(&format!(
+            "Failure reason: {}",
+            describe_nns_governance_error(reason)
+        ));
+    }
+    Ok(())
+}
+
+/// Wait for an SNS proposal to execute (`--wait`), failing if it fails or is rejected
+async fn wait_for_proposal(proposal_id: u64) -> Result<()> {
+    use crate::core::ops::sns_governance_ops::{
+        ProposalOutcome, wait_for_proposal_outcome_default_path,
+    };
+    use crate::core::utils::time_format::format_utc;
+
+    let spinner = Spinner::start(&format!(
+        "Waiting for proposal {} to execute...",
+        proposal_id
+    ));
+    let outcome = wait_for_proposal_outcome_default_path(proposal_id).await;
+    spinner.finish();
+
+    match outcome? {
+        ProposalOutcome::Executed { timestamp_seconds } => {
+            print_success(&format!(
+                "Proposal {} executed at {}",
+                proposal_id,
+                format_utc(timestamp_seconds)
+            ));
+            Ok(())
+        }
+        ProposalOutcome::Failed {
+            timestamp_seconds,
+            reason,
+        } => anyhow::bail!(
+            "Proposal {} failed to execute at {}: {}",
+            proposal_id,
+            format_utc(timestamp_seconds),
+            reason.unwrap_or_else(|| "no failure reason given".to_string())
+        ),
+        ProposalOutcome::Rejected { timestamp_seconds } => anyhow::bail!(
+            "Proposal {} was rejected at {} (see show-ballots {})",
+            proposal_id,
+            format_utc(timestamp_seconds),
+            proposal_id
+        ),
+    }
+}
+
+/// Handle create-sns-neuron command
+pub async fn handle_create_sns_neuron(args: &[String]) -> Result<()> {
+    let mut args = args.to_vec();
+    let amount = take_amount(&mut args, 3)?;
+    let from_subaccount = take_subaccount(&mut args, "--from-subaccount")?;
+    let amount_given = amount.is_some();
+
+    use crate::core::ops::sns_governance_ops::get_neuron_minimum_stake;
+
+    let governance_canister = app_context().sns_governance_canister()?;
+
+    // Get minimum stake (using anonymous identity for query)
+    let agent = app_context().anonymous_agent().await?;
+    let minimum_stake = get_neuron_minimum_stake(&agent, governance_canister)
+        .await
+        .context("Failed to get minimum stake")?;
+
+    // Step 1: Get principal (select participant if not provided)
+    let principal = if args.len() >= 3 {
+        resolve_principal(&args[2]).context("Failed to parse principal")?
     } else {
-        // Interactive prompt for dissolve delay
-        println!();
-        print!("Enter dissolve delay in seconds (or press Enter to skip, default: 0): ");
-        io::stdout().flush()?;
+        match select_participant_with_back_handling(None, Some("sns")).await {
+            Ok(p) => p,
+            Err(e) if is_user_went_back_error(&e) => return Ok(()),
+            Err(e) => return Err(e),
+        }
+    };
 
-        let input_opt = match read_input_optional(
-            "Enter dissolve delay in seconds (press Enter to skip/default: 0, or [b]ack to go back): ",
-        ) {
-            Ok(opt) => opt,
-            Err(nav) => {
-                return Err(navigation_to_anyhow(nav));
+    // Get balance and fee to show user options
+    let ledger_canister = app_context().sns_ledger_canister()?;
+    let spendable = account_spendable(true, principal, from_subaccount.clone())
+        .await?
+        .minimum_stake(minimum_stake);
+    let decimals = app_context().ledger_decimals(ledger_canister).await?;
+
+    // Step 2: Get amount (interactive if not provided)
+    let amount = match amount {
+        Some(amount) => amount,
+        None => {
+            // Interactive prompt for amount
+            print_header("Creating SNS Neuron");
+            print_info(&format!("Principal: {}", principal));
+            print_spendable(&spendable, "tokens", decimals);
+            println!();
+            let input_opt = match read_input_optional(&format!(
+                "Enter tokens to stake (e.g., 1.5 or 2.0, 150000000e8s in e8s, or max; press Enter to use maximum: {} e8s, or [b]ack to go back): ",
+                spendable.max_e8s()
+            )).await {
+                Ok(opt) => opt,
+                Err(nav) => {
+                    return Err(navigation_to_anyhow(nav));
+                }
+            };
+            match input_opt {
+                Some(input) => AmountInput::from_prompt(&input)?,
+                None => AmountInput::Max,
             }
-        };
-        if let Some(input_trimmed) = input_opt {
-            let delay: u64 = input_trimmed
-                .parse()
-                .context("Failed to parse dissolve delay - must be a number")?;
-            if delay > 0 { Some(delay) } else { None }
-        } else {
-            None // No dissolve delay
         }
     };
+    let amount_e8s = amount
+        .spend(ledger_canister, &spendable)
+        .await
+        .context(ValidationError::new("Invalid stake amount"))?;
+
+    // Step 3: Get optional memo
+    let memo = if args.len() >= 4 {
+        Some(args[3].parse::<u64>().context("Failed to parse memo")?)
+    } else {
+        None
+    };
+
+    // Step 4: Get optional dissolve delay (interactive if not provided)
+    let dissolve_delay_seconds = if args.len() >= 5 {
+        Some(
+            parse_sns_dissolve_delay(&args[4])
+                .await
+                .context("Failed to parse dissolve_delay")?,
+        )
+    } else {
+        DissolveDelayPresets::sns()
+            .await?
+            .prompt_optional("dissolve delay")
+            .await?
+    }
+    .filter(|&delay| delay > 0);
 
     // Get existing neuron count to show what memo will be used
     let existing_neurons = list_neurons_for_principal_default_path(principal)
         .await
         .context("Failed to list existing neurons")?;
     let neuron_count = existing_neurons.len();
-    let auto_memo = neuron_count + 1;
+    let auto_memo = auto_neuron_memo(neuron_count);
 
-    if args.len() >= 4 {
+    if amount_given {
         // Show header if amount was provided via args
         print_header("Creating SNS Neuron");
         print_info(&format!("Principal: {}", principal));
         print_info(&format!("Existing neurons: {}", neuron_count));
         print_info(&format!("Minimum stake required: {} e8s", minimum_stake));
-        if let Some(amount) = amount_e8s {
-            print_info(&format!("Amount: {} e8s", amount));
-        }
+        print_info(&format!("Amount: {} e8s", amount_e8s));
         if let Some(m) = memo {
             print_info(&format!("Memo: {} (specified)", m));
         } else {
@@ -2316,11 +3625,23 @@ pub async fn handle_create_sns_neuron(args: &[String]) -> Result<()> {
             print_info("Dissolve delay: 0 seconds (none)");
         }
     }
+    print_balance_after(&spendable, amount_e8s, "tokens", decimals);
 
-    let neuron_id =
-        create_sns_neuron_default_path(principal, amount_e8s, memo, dissolve_delay_seconds)
-            .await
-            .context("Failed to create SNS neuron")?;
+    if let Some(subaccount) = &from_subaccount {
+        print_info(&format!(
+            "From subaccount: {}",
+            short_id(&hex::encode(subaccount))
+        ));
+    }
+    let neuron_id = create_sns_neuron_default_path(
+        principal,
+        Some(amount_e8s),
+        memo,
+        dissolve_delay_seconds,
+        from_subaccount,
+    )
+    .await
+    .context("Failed to create SNS neuron")?;
 
     let hex_id = hex::encode(&neuron_id);
     print_success(&format!(
@@ -2334,7 +3655,7 @@ pub async fn handle_create_sns_neuron(args: &[String]) -> Result<()> {
 pub async fn handle_disburse_sns_neuron(args: &[String]) -> Result<()> {
     // Step 1: Get participant principal (select if not provided)
     let participant_principal = if args.len() >= 3 {
-        Principal::from_text(&args[2]).context("Failed to parse participant principal")?
+        resolve_principal(&args[2]).context("Failed to parse participant principal")?
     } else {
         match select_participant_with_back_handling(None, Some("sns")).await {
             Ok(p) => p,
@@ -2346,34 +3667,26 @@ pub async fn handle_disburse_sns_neuron(args: &[String]) -> Result<()> {
     // Step 2 & 3: Get neuron_id and receiver_principal
     let (neuron_id, receiver_principal) = if args.len() >= 4 {
         let arg3 = &args[3];
-        // Check if arg3 looks like a neuron_id (hex string)
-        let looks_like_neuron_id = (arg3.starts_with("0x") && arg3.len() > 10)
-            || (!arg3.contains('-') // Principal contains dashes
-                && arg3.chars().all(|c| c.is_ascii_hexdigit())
-                && arg3.len() > 8);
-
-        if looks_like_neuron_id {
+        if looks_like_sns_neuron_id(arg3) {
             // arg3 is neuron_id
-            let hex_str = arg3.strip_prefix("0x").unwrap_or(arg3);
-            let neuron_id_val =
-                Some(hex::decode(hex_str).context("Failed to decode neuron_id from hex")?);
+            let neuron_id_val = parse_sns_neuron_id(participant_principal, arg3).await?;
 
             // Get receiver_principal from next arg
             let receiver = if args.len() >= 5 {
-                Principal::from_text(&args[4]).context("Failed to parse receiver principal")?
+                resolve_principal(&args[4]).context("Failed to parse receiver principal")?
             } else {
                 let input = read_input_required(
                     "Enter receiver principal (or press Enter/[b]ack to go back): ",
                 )
+                .await
                 .map_err(navigation_to_anyhow)?;
-                Principal::from_text(&input).context("Failed to parse receiver principal")?
+                resolve_principal(&input).context("Failed to parse receiver principal")?
             };
 
             (neuron_id_val, receiver)
         } else {
             // arg3 is receiver_principal, need to select neuron
-            let receiver =
-                Principal::from_text(arg3).context("Failed to parse receiver principal")?;
+            let receiver = resolve_principal(arg3).context("Failed to parse receiver principal")?;
             let neuron_id_val = match select_neuron(participant_principal).await {
                 Ok(id) => id,
                 Err(e) if is_user_cancelled_error(&e) || is_user_went_back_error(&e) => {
@@ -2381,7 +3694,7 @@ pub async fn handle_disburse_sns_neuron(args: &[String]) -> Result<()> {
                 }
                 Err(e) => return Err(e),
             };
-            (Some(neuron_id_val), receiver)
+            (neuron_id_val, receiver)
         }
     } else {
         // Need to select neuron and get receiver interactively
@@ -2391,39 +3704,65 @@ pub async fn handle_disburse_sns_neuron(args: &[String]) -> Result<()> {
             Err(e) => return Err(e),
         };
 
-        print!("Enter receiver principal: ");
-        io::stdout().flush()?;
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
+        let input = read_line("Enter receiver principal: ").await?;
         let receiver =
-            Principal::from_text(input.trim()).context("Failed to parse receiver principal")?;
+            resolve_principal(input.trim()).context("Failed to parse receiver principal")?;
 
-        (Some(neuron_id_val), receiver)
+        (neuron_id_val, receiver)
     };
 
-    print_header("Disbursing SNS Neuron");
-    print_info(&format!("Participant: {}", participant_principal));
-    print_info(&format!("Receiver: {}", receiver_principal));
-    if let Some(id) = &neuron_id {
-        let hex_id = hex::encode(id);
-        if hex_id.len() >= 15 {
-            print_info(&format!(
-                "Neuron ID: {}...{}",
-                &hex_id[..7],
-                &hex_id[hex_id.len() - 8..]
-            ));
-        } else {
-            print_info(&format!("Neuron ID: {}", hex_id));
+    // Checked whether the neuron was given or picked from the list
+    let neuron = find_sns_neuron(participant_principal, &neuron_id).await?;
+    ensure_dissolved(
+        neuron.dissolve(),
+        crate::core::ops::time_ops::replica_now_secs().await,
+    )?;
+
+    if needs_confirmation() {
+        use crate::core::ops::ledger_ops::get_sns_ledger_fee;
+        let agent = app_context().anonymous_agent().await?;
+        let ledger_canister = app_context().sns_ledger_canister()?;
+        let balance = get_sns_ledger_balance(&agent, ledger_canister, receiver_principal, None)
+            .await
+            .context("Failed to get receiver SNS balance")?;
+        let fee = get_sns_ledger_fee(&agent, ledger_canister)
+            .await
+            .context("Failed to get SNS ledger transfer fee")?;
+        let payout = neuron
+            .cached_neuron_stake_e8s
+            .saturating_sub(neuron.neuron_fees_e8s)
+            .saturating_sub(fee);
+        let summary = [
+            ("Participant", participant_principal.to_string()),
+            ("Neuron ID", short_id(&hex::encode(&neuron_id))),
+            ("Receiver", receiver_principal.to_string()),
+            (
+                "Amount",
+                format!("{} e8s (full stake)", neuron.cached_neuron_stake_e8s),
+            ),
+            (
+                "Resulting balance",
+                format!("{} e8s", balance.saturating_add(payout)),
+            ),
+        ];
+        if !confirm_action("disburse this SNS neuron", &summary).await? {
+            return Err(Cancelled.into());
         }
-    } else {
-        print_info("Neuron ID: Auto-selecting (lowest dissolve delay)");
     }
-    print_info("Amount: Full neuron stake");
 
-    let block_height = disburse_participant_neuron_default_path(
+    print_header("Disbursing SNS Neuron");
+    print_info(&format!("Participant: {}", participant_principal));
+    print_info(&format!("Receiver: {}", receiver_principal));
+    print_info(&format!(
+        "Neuron ID: {}",
+        short_id(&hex::encode(&neuron_id))
+    ));
+    print_info("Amount: Full neuron stake");
+
+    let block_height = disburse_participant_neuron_default_path(
         participant_principal,
         receiver_principal,
-        neuron_id,
+        Some(neuron_id),
     )
     .await
     .context("Failed to disburse neuron")?;
@@ -2449,7 +3788,9 @@ fn print_add_hotkey_usage(program_name: &str) {
     eprintln!("                    If not provided, shows participant selection menu");
     eprintln!("  neuron_id_hex   - Optional: Neuron ID in hex format");
     eprintln!("                    If not provided, shows neuron selection menu");
-    eprintln!("  hotkey_principal - Required: Principal to add as a hotkey");
+    eprintln!(
+        "  hotkey_principal - Required: Principal to add as a hotkey, or an identity or delegated principal name"
+    );
     eprintln!("                     If not provided as argument, prompts interactively");
     eprintln!(
         "  permissions    - Optional: comma-separated permission types (default: 3,4 = SubmitProposal + Vote)"
@@ -2486,7 +3827,7 @@ fn print_add_hotkey_usage(program_name: &str) {
 pub async fn handle_increase_sns_dissolve_delay(args: &[String]) -> Result<()> {
     // Step 1: Get participant principal (select participant or custom if not provided)
     let participant_principal = if args.len() >= 3 {
-        Principal::from_text(&args[2]).context("Failed to parse participant principal")?
+        resolve_principal(&args[2]).context("Failed to parse participant principal")?
     } else {
         match select_participant_with_back_handling(None, Some("sns")).await {
             Ok(p) => p,
@@ -2497,61 +3838,48 @@ pub async fn handle_increase_sns_dissolve_delay(args: &[String]) -> Result<()> {
 
     // Step 2: Get neuron ID (select if not provided)
     let neuron_id = if args.len() >= 4 {
-        let hex_str = args[3].strip_prefix("0x").unwrap_or(&args[3]);
-        Some(hex::decode(hex_str).context("Failed to decode neuron_id from hex")?)
+        parse_sns_neuron_id(participant_principal, &args[3]).await?
     } else {
         // Interactive neuron selection
         match select_neuron(participant_principal).await {
-            Ok(id) => Some(id),
+            Ok(id) => id,
             Err(e) if is_user_cancelled_error(&e) || is_user_went_back_error(&e) => return Ok(()),
             Err(e) => return Err(e),
         }
     };
 
     // Step 3: Get additional dissolve delay (interactive if not provided)
+    let presets = DissolveDelayPresets::sns().await?;
     let additional_dissolve_delay_seconds = if args.len() >= 5 {
-        args[4]
-            .parse::<u64>()
-            .context("Failed to parse additional_dissolve_delay_seconds")?
+        presets
+            .parse(&args[4])
+            .context("Failed to parse additional_dissolve_delay")?
     } else {
         // Interactive prompt
         print_header("Increase SNS Neuron Dissolve Delay");
         print_info(&format!("Participant: {}", participant_principal));
-        if let Some(ref id) = neuron_id {
-            let hex_id = hex::encode(id);
-            if hex_id.len() >= 15 {
-                print_info(&format!(
-                    "Neuron ID: {}...{}",
-                    &hex_id[..7],
-                    &hex_id[hex_id.len() - 8..]
-                ));
-            } else {
-                print_info(&format!("Neuron ID: {}", hex_id));
-            }
-        }
-        println!();
-        let input = read_input_required(
-            "Enter additional dissolve delay in seconds (e.g., 2592000 for 30 days, or press Enter/[b]ack to go back): ",
-        ).map_err(navigation_to_anyhow)?;
-        input
-            .parse::<u64>()
-            .context("Failed to parse dissolve delay - must be a number")?
+        print_info(&format!(
+            "Neuron ID: {}",
+            short_id(&hex::encode(&neuron_id))
+        ));
+        presets.prompt("additional dissolve delay").await?
     };
 
+    // Stay within the maximum rather than have governance reject the increase, whether the
+    // neuron was given or picked from the list
+    let neuron = find_sns_neuron(participant_principal, &neuron_id).await?;
+    let additional_dissolve_delay_seconds = presets.clamp_increase(
+        neuron.dissolve(),
+        additional_dissolve_delay_seconds,
+        crate::core::ops::time_ops::replica_now_secs().await,
+    )?;
+
     print_header("Increasing Dissolve Delay");
     print_info(&format!("Participant: {}", participant_principal));
-    if let Some(ref id) = neuron_id {
-        let hex_id = hex::encode(id);
-        if hex_id.len() >= 15 {
-            print_info(&format!(
-                "Neuron ID: {}...{}",
-                &hex_id[..7],
-                &hex_id[hex_id.len() - 8..]
-            ));
-        } else {
-            print_info(&format!("Neuron ID: {}", hex_id));
-        }
-    }
+    print_info(&format!(
+        "Neuron ID: {}",
+        short_id(&hex::encode(&neuron_id))
+    ));
     let days = additional_dissolve_delay_seconds / 86400;
     let hours = (additional_dissolve_delay_seconds % 86400) / 3600;
     print_info(&format!(
@@ -2562,7 +3890,7 @@ pub async fn handle_increase_sns_dissolve_delay(args: &[String]) -> Result<()> {
     increase_dissolve_delay_participant_neuron_default_path(
         participant_principal,
         additional_dissolve_delay_seconds,
-        neuron_id,
+        Some(neuron_id),
     )
     .await
     .context("Failed to increase dissolve delay")?;
@@ -2571,11 +3899,365 @@ pub async fn handle_increase_sns_dissolve_delay(args: &[String]) -> Result<()> {
     Ok(())
 }
 
+/// Handle refresh-sns-neuron command
+/// Usage: refresh-sns-neuron [principal] [neuron_id_hex] [amount_e8s] [--from-subaccount <hex>]
+pub async fn handle_refresh_sns_neuron(args: &[String]) -> Result<()> {
+    use crate::core::ops::sns_governance_ops::refresh_sns_neuron_default_path;
+
+    let mut args = args.to_vec();
+    let amount = take_amount(&mut args, 4)?;
+    let from_subaccount = take_subaccount(&mut args, "--from-subaccount")?;
+
+    // Step 1: Get principal (select participant or custom if not provided)
+    let principal = if args.len() >= 3 {
+        resolve_principal(&args[2]).context("Failed to parse principal")?
+    } else {
+        match select_participant_with_back_handling(None, Some("sns")).await {
+            Ok(p) => p,
+            Err(e) if is_user_went_back_error(&e) => return Ok(()),
+            Err(e) => return Err(e),
+        }
+    };
+
+    // Step 2: Get neuron ID (select if not provided)
+    let neuron_id = if args.len() >= 4 {
+        parse_sns_neuron_id(principal, &args[3]).await?
+    } else {
+        match select_neuron(principal).await {
+            Ok(id) => id,
+            Err(e) if is_user_cancelled_error(&e) || is_user_went_back_error(&e) => return Ok(()),
+            Err(e) => return Err(e),
+        }
+    };
+
+    // Step 3: Get amount (interactive if not provided)
+    let ledger_canister = app_context().sns_ledger_canister()?;
+    let spendable = account_spendable(true, principal, from_subaccount.clone()).await?;
+    let decimals = app_context().ledger_decimals(ledger_canister).await?;
+    let amount = match amount {
+        Some(amount) => amount,
+        None => {
+            print_header("Refresh SNS Neuron");
+            print_info(&format!("Principal: {}", principal));
+            print_info(&format!(
+                "Neuron ID: {}",
+                short_id(&hex::encode(&neuron_id))
+            ));
+            print_spendable(&spendable, "tokens", decimals);
+            println!();
+            let input = read_input_required(
+                "Enter SNS tokens to add (e.g., 1.5 or 2.0, 150000000e8s in e8s, or max; or press Enter/[b]ack to go back): ",
+            ).await
+            .map_err(navigation_to_anyhow)?;
+            AmountInput::from_prompt(&input)?
+        }
+    };
+    let amount_e8s = amount
+        .spend(ledger_canister, &spendable)
+        .await
+        .context(ValidationError::new("Invalid amount to add"))?;
+
+    print_header("Refreshing SNS Neuron");
+    print_info(&format!("Principal: {}", principal));
+    print_info(&format!(
+        "Neuron ID: {}",
+        short_id(&hex::encode(&neuron_id))
+    ));
+    print_info(&format!("Adding: {} e8s", amount_e8s));
+    print_balance_after(&spendable, amount_e8s, "tokens", decimals);
+    if let Some(subaccount) = &from_subaccount {
+        print_info(&format!(
+            "From subaccount: {}",
+            short_id(&hex::encode(subaccount))
+        ));
+    }
+
+    let stake = refresh_sns_neuron_default_path(principal, neuron_id, amount_e8s, from_subaccount)
+        .await
+        .context("Failed to refresh SNS neuron")?;
+
+    print_success(&format!(
+        "Neuron refreshed! Stake: {} e8s ({:.8} tokens)",
+        stake,
+        stake as f64 / 100_000_000.0
+    ));
+    Ok(())
+}
+
+/// Handle claim-sns-neuron command
+/// Claims a neuron funded by an external transfer to the governance subaccount of a memo and controller
+pub async fn handle_claim_sns_neuron(args: &[String]) -> Result<()> {
+    use crate::core::ops::ledger_ops::generate_subaccount_by_nonce;
+    use crate::core::ops::sns_governance_ops::claim_sns_neuron_default_path;
+    use crate::core::utils::cli_args::take_flag;
+
+    let mut args = args.to_vec();
+    let memo = take_flag(&mut args, "--memo")?
+        .map(|memo| {
+            memo.parse::<u64>()
+                .context(ValidationError::new("Invalid --memo (expected a u64)"))
+        })
+        .transpose()?;
+    let controller = take_flag(&mut args, "--controller")?
+        .map(|p| {
+            resolve_principal(&p).context(ValidationError::new("Invalid --controller principal"))
+        })
+        .transpose()?;
+
+    // Step 1: Get controller (select participant or custom if not provided)
+    let controller = match controller {
+        Some(p) => p,
+        None => match select_participant_with_back_handling(None, None).await {
+            Ok(p) => p,
+            Err(e) if is_user_went_back_error(&e) => return Ok(()),
+            Err(e) => return Err(e),
+        },
+    };
+
+    // Step 2: Get memo (interactive if not provided)
+    let memo = match memo {
+        Some(memo) => memo,
+        None => {
+            print_header("Claim SNS Neuron");
+            print_info(&format!("Controller: {}", controller));
+            println!();
+            let input = read_input_required(
+                "Enter the memo of the staking transfer (or press Enter/[b]ack to go back): ",
+            )
+            .await
+            .map_err(navigation_to_anyhow)?;
+            input
+                .parse::<u64>()
+                .context("Failed to parse memo - must be a number")?
+        }
+    };
+
+    print_header("Claiming SNS Neuron");
+    print_info(&format!("Controller: {}", controller));
+    print_info(&format!("Memo: {}", memo));
+    print_info(&format!(
+        "Governance subaccount: {}",
+        hex::encode(generate_subaccount_by_nonce(memo, controller).0)
+    ));
+
+    let neuron_id = claim_sns_neuron_default_path(controller, memo)
+        .await
+        .context("Failed to claim SNS neuron")?;
+
+    print_success(&format!("Neuron claimed! ID: {}", hex::encode(&neuron_id)));
+    Ok(())
+}
+
+/// Handle resolve-neuron command
+/// Derives the SNS neuron ID of a controller and memo and checks the neuron and its staking account
+/// Usage: resolve-neuron [--controller <principal>] [--memo <n>]
+pub async fn handle_resolve_neuron(args: &[String]) -> Result<()> {
+    use crate::core::ops::sns_governance_ops::resolve_sns_neuron_default_path;
+    use crate::core::utils::cli_args::take_flag;
+
+    let mut args = args.to_vec();
+    let memo = take_flag(&mut args, "--memo")?
+        .map(|memo| {
+            memo.parse::<u64>()
+                .context(ValidationError::new("Invalid --memo (expected a u64)"))
+        })
+        .transpose()?;
+    let controller = take_flag(&mut args, "--controller")?
+        .map(|p| {
+            resolve_principal(&p).context(ValidationError::new("Invalid --controller principal"))
+        })
+        .transpose()?;
+
+    // Step 1: Get controller (select participant or custom if not provided)
+    let controller = match controller {
+        Some(p) => p,
+        None => match select_participant_with_back_handling(None, Some("sns")).await {
+            Ok(p) => p,
+            Err(e) if is_user_went_back_error(&e) => return Ok(()),
+            Err(e) => return Err(e),
+        },
+    };
+
+    // Step 2: Get memo (interactive if not provided)
+    let memo = match memo {
+        Some(memo) => memo,
+        None => {
+            print_header("Resolve SNS Neuron");
+            print_info(&format!("Controller: {}", controller));
+            println!();
+            let input = read_input_required(
+                "Enter the memo of the staking transfer (or press Enter/[b]ack to go back): ",
+            )
+            .await
+            .map_err(navigation_to_anyhow)?;
+            input
+                .parse::<u64>()
+                .context("Failed to parse memo - must be a number")?
+        }
+    };
+
+    let resolution = resolve_sns_neuron_default_path(controller, memo)
+        .await
+        .context("Failed to resolve SNS neuron")?;
+
+    print_header("Resolve SNS Neuron");
+    print_info(&format!("Controller: {}", controller));
+    print_info(&format!("Memo: {}", memo));
+    print_info(&format!(
+        "Neuron ID (staking subaccount): {}",
+        hex::encode(&resolution.neuron_id)
+    ));
+    print_info(&format!(
+        "Staking account: {}.{}",
+        resolution.governance_canister,
+        hex::encode(&resolution.neuron_id)
+    ));
+    print_info(&format!(
+        "Staking account balance: {} e8s ({:.8} tokens)",
+        resolution.staking_balance_e8s,
+        resolution.staking_balance_e8s as f64 / 100_000_000.0
+    ));
+    println!();
+
+    match &resolution.neuron {
+        Some(neuron) => {
+            print_success("Neuron exists");
+            print_neuron_details(neuron);
+        }
+        None => print_warning("Governance has no neuron with this ID"),
+    }
+    println!();
+    print_info(&resolution.diagnosis());
+    Ok(())
+}
+
+/// Handle recover-stakes command
+/// Finds SNS staking accounts holding tokens governance has not counted (a failed claim or
+/// refresh) and claims or refreshes them; with --refund, unclaimed stakes are claimed and
+/// disbursed back to their controllers
+/// Usage: recover-stakes [principal] [--max-memo <n>] [--refund]
+pub async fn handle_recover_stakes(args: &[String]) -> Result<()> {
+    use crate::core::ops::sns_governance_ops::{
+        StakeRecovery, find_orphaned_stakes_default_path, recover_stake_default_path,
+    };
+    use crate::core::ops::trace_ops::principal_label;
+    use crate::core::utils::cli_args::{take_flag, take_switch};
+
+    const USAGE: &str = "Usage: recover-stakes [principal] [--max-memo <n>] [--refund]";
+
+    let mut args = args.to_vec();
+    let max_memo = take_flag(&mut args, "--max-memo")?
+        .map(|memo| {
+            memo.parse::<u64>()
+                .context(ValidationError::new("Invalid --max-memo (expected a u64)"))
+        })
+        .transpose()?;
+    let refund = take_switch(&mut args, "--refund");
+    if let Some(extra) = args.get(3) {
+        return Err(invalid(format!(
+            "Unknown argument for recover-stakes: {extra}\n{USAGE}"
+        )));
+    }
+
+    let data = app_context().deployment_data()?;
+    let controllers = match args.get(2) {
+        Some(principal) => vec![resolve_principal(principal)?],
+        // Everyone the tool stakes for: the owner, participants and extra identities
+        None => data
+            .accounts()
+            .into_iter()
+            .map(|(_, principal)| principal)
+            .chain(data.identities.iter().map(|i| i.principal.clone()))
+            .map(|principal| {
+                Principal::from_text(&principal)
+                    .context("Failed to parse principal in deployment data")
+            })
+            .collect::<Result<Vec<_>>>()?,
+    };
+
+    print_header("Recover SNS Stakes");
+    let spinner = Spinner::start(&format!(
+        "Checking the staking accounts of {} principal(s)...",
+        controllers.len()
+    ));
+    let orphans = find_orphaned_stakes_default_path(&controllers, max_memo).await;
+    spinner.finish();
+    let orphans = orphans.context("Failed to check staking accounts")?;
+    if orphans.is_empty() {
+        print_success("No orphaned stakes found");
+        return Ok(());
+    }
+
+    let label = |principal: Principal| {
+        principal_label(&data, principal).unwrap_or_else(|| principal.to_text())
+    };
+    let mut table = Table::new(&["Controller", "Memo", "Neuron ID", "Staked (e8s)", "State"])
+        .right_align(&[1, 3]);
+    for orphan in &orphans {
+        table.add_row(vec![
+            label(orphan.controller),
+            orphan.memo.to_string(),
+            short_id(&hex::encode(&orphan.neuron_id)),
+            orphan.staking_balance_e8s.to_string(),
+            match &orphan.neuron {
+                None => "not claimed".to_string(),
+                Some(neuron) => format!(
+                    "not refreshed (stake {} e8s)",
+                    neuron.cached_neuron_stake_e8s
+                ),
+            },
+        ]);
+    }
+    table.print();
+    println!();
+
+    if refund && needs_confirmation() {
+        let summary = [
+            ("Stakes", orphans.len().to_string()),
+            ("Unclaimed stakes go to", "their controllers".to_string()),
+        ];
+        if !confirm_action("recover and refund these stakes", &summary).await? {
+            return Err(Cancelled.into());
+        }
+    }
+
+    let mut failed = 0;
+    for orphan in &orphans {
+        let stake = format!("{} memo {}", label(orphan.controller), orphan.memo);
+        match recover_stake_default_path(orphan, refund).await {
+            Ok(StakeRecovery::Claimed(neuron_id)) => print_success(&format!(
+                "{stake}: claimed neuron {}",
+                hex::encode(neuron_id)
+            )),
+            Ok(StakeRecovery::Refreshed) => {
+                print_success(&format!("{stake}: refreshed the neuron's stake"));
+            }
+            Ok(StakeRecovery::Refunded(block)) => print_success(&format!(
+                "{stake}: returned to the controller (block {block})"
+            )),
+            Ok(StakeRecovery::BelowMinimum { minimum_stake_e8s }) => print_warning(&format!(
+                "{stake}: below the minimum stake of {minimum_stake_e8s} e8s, so governance can neither claim nor return it; top the staking account up to the minimum and run recover-stakes again"
+            )),
+            Err(e) => {
+                failed += 1;
+                print_warning(&format!("{stake}: {e:#}"));
+            }
+        }
+    }
+    if failed > 0 {
+        anyhow::bail!(
+            "{failed} of {} stake(s) could not be recovered",
+            orphans.len()
+        );
+    }
+    Ok(())
+}
+
 /// Handle manage-sns-dissolving command
 pub async fn handle_manage_sns_dissolving(args: &[String]) -> Result<()> {
     // Step 1: Get participant principal (select if not provided)
     let participant_principal = if args.len() >= 3 {
-        Principal::from_text(&args[2]).context("Failed to parse participant principal")?
+        resolve_principal(&args[2]).context("Failed to parse participant principal")?
     } else {
         match select_participant_with_back_handling(None, Some("sns")).await {
             Ok(p) => p,
@@ -2590,7 +4272,7 @@ pub async fn handle_manage_sns_dissolving(args: &[String]) -> Result<()> {
             "start" | "true" | "1" => true,
             "stop" | "false" | "0" => false,
             _ => {
-                anyhow::bail!("Invalid action. Use 'start' or 'stop'");
+                return Err(invalid("Invalid action. Use 'start' or 'stop'"));
             }
         }
     } else {
@@ -2602,194 +4284,2566 @@ pub async fn handle_manage_sns_dissolving(args: &[String]) -> Result<()> {
         println!("  [1] Start Dissolving");
         println!("  [2] Stop Dissolving");
         println!();
-        print!("Select action (1 or 2): ");
-        io::stdout().flush()?;
-
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
+        let input = read_line("Select action (1 or 2): ").await?;
         let selection = input.trim().to_lowercase();
 
         match selection.as_str() {
             "1" | "start" => true,
             "2" | "stop" => false,
             _ => {
-                anyhow::bail!("Invalid selection. Use 1 to start or 2 to stop dissolving.");
+                return Err(invalid(
+                    "Invalid selection. Use 1 to start or 2 to stop dissolving.",
+                ));
             }
         }
     };
 
     // Step 3: Get neuron ID (select if not provided)
     let neuron_id = if args.len() >= 5 {
-        let hex_str = args[4].strip_prefix("0x").unwrap_or(&args[4]);
-        Some(hex::decode(hex_str).context("Failed to decode neuron_id from hex")?)
+        parse_sns_neuron_id(participant_principal, &args[4]).await?
     } else {
         // Interactive neuron selection
         match select_neuron(participant_principal).await {
-            Ok(id) => Some(id),
+            Ok(id) => id,
             Err(e) if is_user_cancelled_error(&e) || is_user_went_back_error(&e) => return Ok(()),
             Err(e) => return Err(e),
         }
     };
 
+    // Checked whether the neuron was given or picked from the list
+    let neuron = if start_dissolving {
+        Some(find_sns_neuron(participant_principal, &neuron_id).await?)
+    } else {
+        None
+    };
+    if let Some(n) = &neuron {
+        DissolveDelayPresets::sns()
+            .await?
+            .warn_start_dissolving(n.dissolve());
+    }
+
+    if let Some(n) = &neuron
+        && needs_confirmation()
+    {
+        let summary = [
+            ("Participant", participant_principal.to_string()),
+            ("Neuron ID", short_id(&hex::encode(&neuron_id))),
+            ("Stake", format!("{} e8s", n.cached_neuron_stake_e8s)),
+            ("Dissolve state", n.dissolve().summary()),
+        ];
+        if !confirm_action("start dissolving this SNS neuron", &summary).await? {
+            return Err(Cancelled.into());
+        }
+    }
+
     print_header(if start_dissolving {
         "Starting Dissolving"
     } else {
         "Stopping Dissolving"
     });
     print_info(&format!("Participant: {}", participant_principal));
-    if let Some(ref id) = neuron_id {
-        let hex_id = hex::encode(id);
-        if hex_id.len() >= 15 {
-            print_info(&format!(
-                "Neuron ID: {}...{}",
-                &hex_id[..7],
-                &hex_id[hex_id.len() - 8..]
-            ));
-        } else {
-            print_info(&format!("Neuron ID: {}", hex_id));
-        }
-    }
+    print_info(&format!(
+        "Neuron ID: {}",
+        short_id(&hex::encode(&neuron_id))
+    ));
 
     manage_dissolving_state_participant_neuron_default_path(
         participant_principal,
         start_dissolving,
-        neuron_id,
+        Some(neuron_id),
     )
     .await
     .context("Failed to manage dissolving state")?;
 
-    print_success(if start_dissolving {
-        "Neuron is now dissolving!"
-    } else {
-        "Neuron dissolving stopped!"
-    });
+    print_success(if start_dissolving {
+        "Neuron is now dissolving!"
+    } else {
+        "Neuron dissolving stopped!"
+    });
+    Ok(())
+}
+
+/// Handle check-sns-deployed command
+/// Usage: check-sns-deployed [--json]
+/// Prints the SNS canisters from the deployment data, then exits with code 0 if an SNS
+/// is deployed on SNS-W and 1 if not, so scripts can keep relying on the exit code
+pub async fn handle_check_sns_deployed(args: &[String]) -> Result<()> {
+    use crate::core::ops::sns_root_ops::{SnsCanisterInfo, get_sns_canister_info};
+    use crate::core::utils::cli_args::take_switch;
+
+    #[derive(serde::Serialize)]
+    struct DeploymentStatus {
+        deployed: bool,
+        canisters: Vec<SnsCanisterInfo>,
+        warnings: Vec<String>,
+    }
+
+    let mut args = args.to_vec();
+    let json = take_switch(&mut args, "--json");
+    if args.len() > 2 {
+        return Err(invalid(format!("Unexpected argument: {}", args[2])));
+    }
+
+    let deployed = check_sns_deployed_default_path()
+        .await
+        .context("Failed to check SNS deployment status")?;
+
+    // With --quiet only the exit code matters, so skip the extra calls
+    if !json && crate::core::utils::logging::is_quiet() {
+        std::process::exit(if deployed { 0 } else { 1 });
+    }
+
+    // Details are best effort: the exit code only reflects SNS-W
+    let mut status = DeploymentStatus {
+        deployed,
+        canisters: Vec::new(),
+        warnings: Vec::new(),
+    };
+    match app_context().try_deployment_data() {
+        Some(deployment_data) => {
+            let agent = app_context().anonymous_agent().await?;
+            match get_sns_canister_info(&agent, &deployment_data.deployed_sns).await {
+                Ok((canisters, root_error)) => {
+                    status.canisters = canisters;
+                    if let Some(e) = root_error {
+                        status
+                            .warnings
+                            .push(format!("Could not read cycle balances from root: {e:#}"));
+                    }
+                }
+                Err(e) => status
+                    .warnings
+                    .push(format!("Invalid deployment data: {e:#}")),
+            }
+        }
+        None => status
+            .warnings
+            .push("No deployment data - canister details unavailable".to_string()),
+    }
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&status).context("Failed to serialize status")?
+        );
+    } else {
+        print_header("SNS Deployment");
+        if deployed {
+            print_success("An SNS is deployed on SNS-W");
+        } else {
+            print_warning("No SNS is deployed on SNS-W");
+        }
+        if !status.canisters.is_empty() {
+            println!();
+            let mut table = Table::new(&["Canister", "ID", "Exists", "Cycles", "Module Hash"])
+                .right_align(&[3]);
+            for canister in &status.canisters {
+                table.add_row(vec![
+                    canister.role.to_string(),
+                    canister.canister_id.to_string(),
+                    if canister.exists { "yes" } else { "no" }.to_string(),
+                    canister
+                        .cycles
+                        .map_or_else(|| "-".to_string(), |cycles| cycles.to_string()),
+                    canister
+                        .module_hash
+                        .as_deref()
+                        .map_or_else(|| "-".to_string(), short_id),
+                ]);
+            }
+            table.print();
+        }
+        for warning in &status.warnings {
+            print_warning(warning);
+        }
+    }
+
+    std::process::exit(if deployed { 0 } else { 1 });
+}
+
+/// Handle neuron-ladder command
+/// Usage: neuron-ladder [--json]
+/// Groups every SNS neuron by the dissolve delay rungs of the swap's neuron baskets
+pub async fn handle_neuron_ladder(args: &[String]) -> Result<()> {
+    use crate::core::ops::neuron_ladder::get_neuron_ladder_default_path;
+    use crate::core::utils::cli_args::{format_duration_secs, take_switch};
+
+    let mut args = args.to_vec();
+    let json = take_switch(&mut args, "--json");
+    if args.len() > 2 {
+        return Err(invalid(format!(
+            "Unknown argument for neuron-ladder: {}\nUsage: neuron-ladder [--json]",
+            args[2]
+        )));
+    }
+
+    let ladder = get_neuron_ladder_default_path()
+        .await
+        .context("Failed to build the neuron ladder")?;
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&ladder).context("Failed to serialize ladder")?
+        );
+        return Ok(());
+    }
+
+    print_header("Neuron Ladder");
+    print_info(&format!(
+        "Basket: {} neurons, {} apart ({})",
+        ladder.basket_count,
+        format_duration_secs(ladder.dissolve_delay_interval_seconds),
+        if ladder.from_swap {
+            "from the swap canister"
+        } else {
+            "swap parameters unavailable, using the default config"
+        }
+    ));
+    println!();
+
+    let mut table = Table::new(&[
+        "Rung",
+        "Dissolve delay",
+        "Neurons",
+        "Expected",
+        "Dissolving",
+        "Stake (e8s)",
+    ])
+    .right_align(&[2, 3, 4, 5]);
+    for rung in &ladder.rungs {
+        let range = match rung.max_seconds {
+            Some(max) => format!(
+                "{} - {}",
+                format_duration_secs(rung.min_seconds),
+                format_duration_secs(max)
+            ),
+            None => format!("{} +", format_duration_secs(rung.min_seconds)),
+        };
+        table.add_row(vec![
+            rung.basket_index
+                .map_or_else(|| "above".to_string(), |i| (i + 1).to_string()),
+            range,
+            rung.count.to_string(),
+            rung.basket_index.map_or_else(
+                || "-".to_string(),
+                |_| ladder.direct_participants.to_string(),
+            ),
+            rung.dissolving_count.to_string(),
+            rung.staked_e8s.to_string(),
+        ]);
+    }
+    table.print();
+
+    let short: Vec<String> = ladder
+        .rungs
+        .iter()
+        .filter(|r| r.basket_index.is_some() && r.count < ladder.direct_participants)
+        .filter_map(|r| r.basket_index.map(|i| (i + 1).to_string()))
+        .collect();
+    println!();
+    if short.is_empty() {
+        print_success("Every rung holds at least one neuron per direct swap participant");
+    } else {
+        print_warning(&format!(
+            "Rung(s) {} hold fewer neurons than the {} direct swap participants",
+            short.join(", "),
+            ladder.direct_participants
+        ));
+    }
+    Ok(())
+}
+
+/// Handle token-info command
+/// Usage: token-info <icp|sns> [--json]
+/// Shows a ledger's ICRC-1 name, symbol, decimals, fee, total supply, minting account and metadata
+pub async fn handle_token_info(args: &[String]) -> Result<()> {
+    use crate::core::ops::ledger_ops::get_token_info;
+    use crate::core::utils::cli_args::take_switch;
+    use crate::core::utils::network::network_config;
+
+    const USAGE: &str = "Usage: token-info <icp|sns> [--json]";
+
+    let mut args = args.to_vec();
+    let json = take_switch(&mut args, "--json");
+    let ledger_canister = match args.get(2).map(|s| s.to_lowercase()).as_deref() {
+        Some("icp") => network_config().ledger_canister,
+        Some("sns") => app_context().sns_ledger_canister()?,
+        Some(other) => {
+            return Err(invalid(format!(
+                "Invalid ledger '{other}'. Use 'icp' or 'sns'\n{USAGE}"
+            )));
+        }
+        None => return Err(invalid(format!("Missing ledger for token-info\n{USAGE}"))),
+    };
+    if args.len() > 3 {
+        return Err(invalid(format!(
+            "Unknown argument for token-info: {}\n{USAGE}",
+            args[3]
+        )));
+    }
+
+    let agent = app_context().anonymous_agent().await?;
+    let info = get_token_info(&agent, ledger_canister)
+        .await
+        .context("Failed to get token info")?;
+    app_context().cache_ledger_decimals(ledger_canister, info.decimals);
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&info).context("Failed to serialize token info")?
+        );
+        return Ok(());
+    }
+
+    let tokens = |e8s: u64| {
+        let scale = 10f64.powi(i32::from(info.decimals));
+        format!(
+            "{e8s} e8s ({:.*} {})",
+            usize::from(info.decimals),
+            e8s as f64 / scale,
+            info.symbol
+        )
+    };
+    print_header("Token Info");
+    print_info(&format!("Ledger: {}", info.ledger));
+    print_info(&format!("Name: {}", info.name));
+    print_info(&format!("Symbol: {}", info.symbol));
+    print_info(&format!("Decimals: {}", info.decimals));
+    print_info(&format!("Fee: {}", tokens(info.fee_e8s)));
+    print_info(&format!("Total supply: {}", tokens(info.total_supply_e8s)));
+    print_info(&format!(
+        "Minting account: {}",
+        info.minting_account.as_deref().unwrap_or("none")
+    ));
+
+    if !info.metadata.is_empty() {
+        println!();
+        let mut table = Table::new(&["Metadata", "Value"]);
+        for (key, value) in &info.metadata {
+            // Logos are data URLs of several kilobytes
+            let value = if value.chars().count() > 64 {
+                format!(
+                    "{}... ({} chars)",
+                    value.chars().take(48).collect::<String>(),
+                    value.chars().count()
+                )
+            } else {
+                value.clone()
+            };
+            table.add_row(vec![key.clone(), value]);
+        }
+        table.print();
+    }
+    Ok(())
+}
+
+/// Handle trace command
+/// Usage: trace <principal|name> [--limit <n>] [--json]
+/// Follows a principal's tokens through ICP and SNS ledger history, its neurons' staking
+/// accounts and mint proposals, oldest first
+pub async fn handle_trace(args: &[String]) -> Result<()> {
+    use crate::core::ops::trace_ops::{DEFAULT_TRACE_LIMIT, trace_principal};
+    use crate::core::utils::cli_args::{take_flag, take_switch};
+    use crate::core::utils::time_format::format_utc;
+
+    const USAGE: &str = "Usage: trace <principal|name> [--limit <n>] [--json]";
+
+    let mut args = args.to_vec();
+    let json = take_switch(&mut args, "--json");
+    let limit: u64 = match take_flag(&mut args, "--limit")? {
+        Some(limit) => limit
+            .parse()
+            .ok()
+            .filter(|n| *n > 0)
+            .context(ValidationError::new(
+                "Invalid --limit - must be a positive number",
+            ))?,
+        None => DEFAULT_TRACE_LIMIT,
+    };
+    let Some(reference) = args.get(2) else {
+        return Err(invalid(format!("Missing principal for trace\n{USAGE}")));
+    };
+    if args.len() > 3 {
+        return Err(invalid(format!(
+            "Unknown argument for trace: {}\n{USAGE}",
+            args[3]
+        )));
+    }
+    let principal = resolve_principal(reference)?;
+
+    let trace = trace_principal(principal, limit)
+        .await
+        .with_context(|| format!("Failed to trace {principal}"))?;
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&trace).context("Failed to serialize trace")?
+        );
+        return Ok(());
+    }
+
+    print_header("Trace");
+    print_info(&format!(
+        "Principal: {}{}",
+        trace.principal,
+        trace
+            .label
+            .as_ref()
+            .map(|label| format!(" ({label})"))
+            .unwrap_or_default()
+    ));
+    if let Some(balance) = trace.icp_balance_e8s {
+        print_info(&format!(
+            "ICP balance (index): {:.8} ICP",
+            balance as f64 / 100_000_000.0
+        ));
+    }
+    if let Some(balance) = trace.sns_balance_e8s {
+        print_info(&format!(
+            "SNS balance (index): {} tokens",
+            format_tokens(
+                balance,
+                app_context()
+                    .ledger_decimals(app_context().sns_ledger_canister()?)
+                    .await?
+            )
+        ));
+    }
+    if !trace.sns_neurons.is_empty() {
+        print_info(&format!(
+            "SNS neurons followed: {}",
+            trace
+                .sns_neurons
+                .iter()
+                .map(|id| short_id(id))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+    for warning in &trace.warnings {
+        print_warning(warning);
+    }
+    println!();
+
+    if trace.events.is_empty() {
+        print_warning("No transactions or mint proposals found for this principal");
+        return Ok(());
+    }
+    let mut table = Table::new(&["Time", "Source", "Id", "Event"]).right_align(&[2]);
+    for event in &trace.events {
+        table.add_row(vec![
+            format_utc(event.timestamp_seconds),
+            event.source.to_string(),
+            event.id.to_string(),
+            event.description.clone(),
+        ]);
+    }
+    table.print();
+    println!();
+    print_success(&format!(
+        "{} event(s); up to {} transaction(s) read per account",
+        trace.events.len(),
+        limit
+    ));
+    Ok(())
+}
+
+/// Handle check-principal command
+/// Usage: check-principal <principal|name> [--json]
+/// Lists which operations a principal can perform, e.g. an Internet Identity principal of a dapp
+pub async fn handle_check_principal(args: &[String]) -> Result<()> {
+    use crate::core::ops::access_ops::principal_access;
+    use crate::core::utils::cli_args::take_switch;
+
+    const USAGE: &str = "Usage: check-principal <principal|name> [--json]";
+
+    let mut args = args.to_vec();
+    let json = take_switch(&mut args, "--json");
+    let Some(reference) = args.get(2) else {
+        return Err(invalid(format!(
+            "Missing principal for check-principal\n{USAGE}"
+        )));
+    };
+    if args.len() > 3 {
+        return Err(invalid(format!(
+            "Unknown argument for check-principal: {}\n{USAGE}",
+            args[3]
+        )));
+    }
+    let principal = resolve_principal(reference)?;
+
+    let access = principal_access(principal)
+        .await
+        .with_context(|| format!("Failed to check {principal}"))?;
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&access).context("Failed to serialize access")?
+        );
+        return Ok(());
+    }
+
+    print_header("Principal Access");
+    print_info(&format!(
+        "Principal: {}{}",
+        access.principal,
+        access
+            .label
+            .as_ref()
+            .map(|label| format!(" ({label})"))
+            .unwrap_or_default()
+    ));
+    if access.delegated {
+        print_info(&format!(
+            "Delegated principal{}: the tool cannot sign for it, so these operations are done from the dapp",
+            access
+                .origin
+                .as_ref()
+                .map(|origin| format!(" for {origin}"))
+                .unwrap_or_default()
+        ));
+    }
+    for warning in &access.warnings {
+        print_warning(warning);
+    }
+    println!();
+
+    let mut table = Table::new(&["Operation", "Allowed", "Detail"]);
+    for operation in &access.operations {
+        table.add_row(vec![
+            operation.operation.clone(),
+            if operation.allowed { "yes" } else { "no" }.to_string(),
+            operation.detail.clone(),
+        ]);
+    }
+    table.print();
+
+    if !access.sns_neurons.is_empty() {
+        println!();
+        let mut table = Table::new(&["SNS Neuron", "Permissions"]);
+        for neuron in &access.sns_neurons {
+            table.add_row(vec![
+                short_id(&neuron.neuron_id),
+                neuron.permissions.join(", "),
+            ]);
+        }
+        table.print();
+    }
+    if !access.icp_hotkey_neurons.is_empty() {
+        println!();
+        let mut table = Table::new(&["ICP Neuron", "Controller"]).right_align(&[0]);
+        for neuron in &access.icp_hotkey_neurons {
+            table.add_row(vec![
+                neuron.neuron_id.to_string(),
+                neuron.controller.clone(),
+            ]);
+        }
+        table.print();
+    }
+    println!();
+    print_success(&format!(
+        "{} of {} operation(s) allowed",
+        access.operations.iter().filter(|o| o.allowed).count(),
+        access.operations.len()
+    ));
+    Ok(())
+}
+
+/// Handle import-sns command
+/// Usage: import-sns --root <canister_id> [--force]
+/// Records an SNS deployed outside this tool in the deployment data, without participants, so
+/// the other commands work against it
+pub async fn handle_import_sns(args: &[String]) -> Result<()> {
+    use crate::core::ops::identity::external_owner;
+    use crate::core::ops::snsw_ops::{SnsSource, find_sns_by_root_default_path};
+    use crate::core::utils::cli_args::{take_flag, take_switch};
+    use crate::core::utils::data_output::{self, SnsCreationData};
+
+    const USAGE: &str = "Usage: import-sns --root <canister_id> [--force]";
+
+    let mut args = args.to_vec();
+    let root = take_flag(&mut args, "--root")?;
+    let force = take_switch(&mut args, "--force");
+    if let Some(extra) = args.get(2) {
+        return Err(invalid(format!(
+            "Unknown argument for import-sns: {extra}\n{USAGE}"
+        )));
+    }
+    let root = root
+        .with_context(|| ValidationError::new(format!("Missing --root for import-sns\n{USAGE}")))?;
+    let root = Principal::from_text(root.trim())
+        .map_err(|e| invalid(format!("Invalid --root '{root}': {e}")))?;
+
+    // An existing record is only replaced on request: importing drops the participants, the
+    // ICP neuron and proposal IDs, and records the dfx identity as owner
+    let path = data_output::get_output_path();
+    let previous = if path.exists() {
+        Some(data_output::read_data()?)
+    } else {
+        None
+    };
+    if let Some(previous) = &previous
+        && !force
+    {
+        match previous.deployed_sns.root_canister() {
+            Ok(recorded_root) if recorded_root == root => {
+                print_info(&format!(
+                    "{} already records the SNS with root {root}; nothing to import. \
+                     Pass --force to re-import it (participants, ICP neuron and proposal IDs are dropped)",
+                    path.display()
+                ));
+                return Ok(());
+            }
+            recorded_root => anyhow::bail!(
+                "{} already records {}. Pass --force to replace it \
+                 (participants are dropped; identities, delegated principals and aliases are kept), \
+                 or import into another file with --data-file <file>",
+                path.display(),
+                recorded_root.map_or_else(
+                    |_| "a deployment".to_string(),
+                    |r| format!("the SNS with root {r}")
+                )
+            ),
+        }
+    }
+
+    print_header("Importing SNS");
+    let spinner = Spinner::start(&format!("Looking up the SNS with root {root}"));
+    let found = find_sns_by_root_default_path(root).await;
+    spinner.finish();
+    let (deployed_sns, source) = found?;
+    print_info(match source {
+        SnsSource::SnsW => "Canister IDs from SNS-W",
+        SnsSource::Root => "Not listed on SNS-W: canister IDs from the root canister",
+    });
+
+    let mut table = Table::new(&["Canister", "ID"]);
+    for (role, id) in deployed_sns.canister_ids() {
+        table.add_row(vec![role.to_string(), id.to_string()]);
+    }
+    table.print();
+    for (role, id) in [
+        ("governance", &deployed_sns.governance_canister_id),
+        ("ledger", &deployed_sns.ledger_canister_id),
+    ] {
+        if id.is_none() {
+            print_warning(&format!("Root reports no {role} canister"));
+        }
+    }
+
+    let owner = external_owner();
+    let mut data = SnsCreationData::external(deployed_sns, owner.to_text());
+    // Identities, delegated principals and aliases name principals, not this SNS, so keep them
+    if let Some(previous) = previous {
+        data.identities = previous.identities;
+        data.delegated = previous.delegated;
+        data.aliases = previous.aliases;
+    }
+    data_output::write_data(&data).context("Failed to write deployment data file")?;
+
+    if owner == Principal::anonymous() {
+        print_warning(&format!(
+            "Owner: {owner} (the anonymous principal: no dfx identity was found, so nothing can be signed as the owner)"
+        ));
+    } else {
+        print_info(&format!("Owner: {owner} (the dfx identity)"));
+    }
+    print_success(&format!("SNS imported into {}", path.display()));
+    Ok(())
+}
+
+/// Handle verify-deployment command
+/// Usage: verify-deployment [--repair] [--json]
+/// Exit code 0 if the deployment data matches the replica (after repairs), 1 otherwise
+pub async fn handle_verify_deployment(args: &[String]) -> Result<()> {
+    use crate::core::ops::verify_ops::{print_checks, verify_deployment};
+    use crate::core::utils::cli_args::take_switch;
+
+    let mut args = args.to_vec();
+    let repair = take_switch(&mut args, "--repair");
+    let json = take_switch(&mut args, "--json");
+
+    let checks = verify_deployment(repair)
+        .await
+        .context("Failed to verify the deployment data")?;
+    let failed = checks.iter().filter(|c| !c.ok && !c.repaired).count();
+    let repaired = checks.iter().filter(|c| c.repaired).count();
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&checks).context("Failed to serialize checks")?
+        );
+    } else {
+        print_header("Verify Deployment");
+        print_checks(&checks);
+        println!();
+        if repaired > 0 {
+            print_success(&format!(
+                "Repaired {} entries in the deployment data",
+                repaired
+            ));
+        }
+        if failed == 0 {
+            print_success("Deployment data matches the replica");
+        } else {
+            print_warning(&format!(
+                "{} discrepancies{}",
+                failed,
+                if repair {
+                    " cannot be repaired"
+                } else {
+                    " (run with --repair to fix canister IDs and principals)"
+                }
+            ));
+        }
+    }
+
+    std::process::exit(if failed == 0 { 0 } else { 1 });
+}
+
+/// Handle verify-distribution command
+/// Usage: verify-distribution [--config <path>] [--json]
+/// Exit code 0 if balances and neurons match the config's token distribution, 1 otherwise
+pub async fn handle_verify_distribution(args: &[String]) -> Result<()> {
+    use crate::core::ops::verify_ops::{print_checks, verify_distribution};
+    use crate::core::utils::cli_args::{take_flag, take_switch};
+    use crate::init::sns_config::SnsConfig;
+
+    let mut args = args.to_vec();
+    let json = take_switch(&mut args, "--json");
+    let config = match take_flag(&mut args, "--config")? {
+        Some(path) => SnsConfig::load(std::path::Path::new(&path))?,
+        None => SnsConfig::default(),
+    };
+    if args.len() > 2 {
+        return Err(invalid(format!(
+            "Unknown argument for verify-distribution: {}\nUsage: verify-distribution [--config <path>] [--json]",
+            args[2]
+        )));
+    }
+
+    let checks = verify_distribution(&config)
+        .await
+        .context("Failed to verify the token distribution")?;
+    let failed = checks.iter().filter(|c| !c.ok).count();
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&checks).context("Failed to serialize checks")?
+        );
+    } else {
+        print_header("Verify Token Distribution");
+        print_checks(&checks);
+        println!();
+        if failed == 0 {
+            print_success("Token distribution matches the SNS config");
+        } else {
+            print_warning(&format!(
+                "{} mismatches (pass the --config used by deploy-sns if it was not the default)",
+                failed
+            ));
+        }
+    }
+
+    std::process::exit(if failed == 0 { 0 } else { 1 });
+}
+
+/// Handle list-deployed-snses command
+/// Usage: list-deployed-snses
+/// Lists every SNS on SNS-W and checks the deployment data against the matching entry
+pub async fn handle_list_deployed_snses(_args: &[String]) -> Result<()> {
+    use crate::core::ops::snsw_ops::{deployment_mismatches, list_deployed_snses_default_path};
+
+    print_header("Deployed SNSes");
+    let snses = list_deployed_snses_default_path()
+        .await
+        .context("Failed to list deployed SNSes")?;
+    let recorded = app_context().try_deployment_data();
+    let recorded_root = recorded
+        .as_ref()
+        .and_then(|data| data.deployed_sns.root_canister().ok());
+
+    if snses.is_empty() {
+        print_warning("No SNS is deployed on SNS-W");
+    } else {
+        print_success(&format!("Found {} SNS(es)", snses.len()));
+    }
+
+    let format_id = |id: Option<Principal>| id.map_or_else(|| "-".to_string(), |p| p.to_text());
+    for (index, sns) in snses.iter().enumerate() {
+        let marker = if sns.root_canister_id.is_some() && sns.root_canister_id == recorded_root {
+            " (deployment data)"
+        } else {
+            ""
+        };
+        println!();
+        println!("  SNS #{}{}", index + 1, marker);
+        println!("    Root:       {}", format_id(sns.root_canister_id));
+        println!("    Governance: {}", format_id(sns.governance_canister_id));
+        println!("    Ledger:     {}", format_id(sns.ledger_canister_id));
+        println!("    Swap:       {}", format_id(sns.swap_canister_id));
+        println!("    Index:      {}", format_id(sns.index_canister_id));
+    }
+    println!();
+
+    // Verify the deployment data against SNS-W
+    let Some(recorded) = recorded else {
+        print_info("No deployment data to verify");
+        return Ok(());
+    };
+    let matching = snses
+        .iter()
+        .find(|sns| sns.root_canister_id.is_some() && sns.root_canister_id == recorded_root);
+    match matching {
+        Some(sns) => {
+            let mismatches = deployment_mismatches(&recorded.deployed_sns, sns);
+            if mismatches.is_empty() {
+                print_success("Deployment data matches SNS-W");
+            } else {
+                print_warning(&format!(
+                    "Deployment data differs from SNS-W for: {}",
+                    mismatches.join(", ")
+                ));
+            }
+        }
+        None => {
+            print_warning(
+                "The SNS in the deployment data is not on SNS-W (the replica may have been restarted with --clean)",
+            );
+            print_info("Redeploy with: cargo run --bin local_sns -- deploy-sns --fresh");
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle sns-canister-status command
+/// Usage: sns-canister-status
+pub async fn handle_sns_canister_status(_args: &[String]) -> Result<()> {
+    use crate::core::ops::sns_root_ops::get_sns_canister_statuses_default_path;
+
+    print_header("SNS Canister Status");
+    print_info(&format!(
+        "Root Canister: {}",
+        app_context().sns_root_canister()?
+    ));
+
+    let statuses = get_sns_canister_statuses_default_path()
+        .await
+        .context("Failed to get SNS canister status")?;
+
+    let format_amount =
+        |value: Option<u128>| value.map_or_else(|| "-".to_string(), |v| v.to_string());
+    println!();
+    let mut table = Table::new(&[
+        "Canister",
+        "ID",
+        "Status",
+        "Cycles",
+        "Memory",
+        "Days Left",
+        "Module Hash",
+    ])
+    .right_align(&[3, 4, 5]);
+    for status in &statuses {
+        // Days until the idle burn alone uses up the balance
+        let days_left = match (status.cycles, status.idle_cycles_burned_per_day) {
+            (Some(cycles), Some(burn)) if burn > 0 => (cycles / burn).to_string(),
+            _ => "-".to_string(),
+        };
+        table.add_row(vec![
+            status.role.to_string(),
+            status.canister_id.to_text(),
+            status.status.unwrap_or("-").to_string(),
+            format_amount(status.cycles),
+            format_amount(status.memory_size),
+            days_left,
+            status
+                .module_hash
+                .as_deref()
+                .map_or_else(|| "-".to_string(), short_id),
+        ]);
+    }
+    table.print();
+    println!();
+
+    let stopped: Vec<_> = statuses
+        .iter()
+        .filter(|s| s.status.is_some_and(|status| status != "running"))
+        .map(|s| s.role)
+        .collect();
+    if !stopped.is_empty() {
+        print_warning(&format!("Not running: {}", stopped.join(", ")));
+    }
+    Ok(())
+}
+
+/// Handle top-up-cycles command
+/// Usage: top-up-cycles [all|root|governance|ledger|swap|index|dapp|archive|<canister_id>] [cycles] [--provisional]
+pub async fn handle_top_up_cycles(args: &[String]) -> Result<()> {
+    use crate::core::ops::sns_root_ops::{
+        CyclesSource, get_sns_canister_statuses_default_path, top_up_cycles_default_path,
+    };
+    use crate::core::utils::cli_args::take_switch;
+    use crate::core::utils::constants::DEFAULT_TOP_UP_CYCLES;
+
+    let mut args = args.to_vec();
+    let source = if take_switch(&mut args, "--provisional") {
+        CyclesSource::Provisional
+    } else {
+        CyclesSource::CyclesLedger
+    };
+
+    // Step 1: Get the target (interactive if not provided)
+    let interactive = args.len() < 3;
+    let target = if interactive {
+        print_header("Top Up Cycles");
+        println!(
+            "Targets: all, root, governance, ledger, swap, index, dapp, archive, or a canister ID"
+        );
+        read_input_required("Enter target (or press Enter/[b]ack to go back): ")
+            .await
+            .map_err(navigation_to_anyhow)?
+    } else {
+        args[2].clone()
+    };
+
+    // Step 2: Get the amount (default when the target came from the command line)
+    let amount = match args.get(3) {
+        Some(value) => value
+            .parse::<u128>()
+            .with_context(|| ValidationError::new(format!("Invalid cycles amount '{}'", value)))?,
+        None if interactive => {
+            match read_input_optional(&format!(
+                "Enter cycles to add (press Enter for {}): ",
+                DEFAULT_TOP_UP_CYCLES
+            ))
+            .await
+            .map_err(navigation_to_anyhow)?
+            {
+                Some(value) => value
+                    .parse::<u128>()
+                    .context("Failed to parse cycles - must be a number")?,
+                None => DEFAULT_TOP_UP_CYCLES,
+            }
+        }
+        None => DEFAULT_TOP_UP_CYCLES,
+    };
+
+    let canisters: Vec<Principal> = match Principal::from_text(&target) {
+        Ok(canister) => vec![canister],
+        Err(_) => {
+            let role = target.to_lowercase();
+            let statuses = get_sns_canister_statuses_default_path()
+                .await
+                .context("Failed to list SNS canisters")?;
+            let canisters: Vec<Principal> = statuses
+                .iter()
+                .filter(|s| role == "all" || s.role == role)
+                .map(|s| s.canister_id)
+                .collect();
+            if canisters.is_empty() {
+                anyhow::bail!(
+                    "No SNS canister matches '{}'. Use all, root, governance, ledger, swap, index, dapp, archive, or a canister ID",
+                    target
+                );
+            }
+            canisters
+        }
+    };
+
+    print_header("Topping Up Cycles");
+    print_info(&format!("Cycles per canister: {}", amount));
+    print_info(match source {
+        CyclesSource::CyclesLedger => "Paid from: the SNS owner's cycles ledger account",
+        CyclesSource::Provisional => "Paid from: minted by the replica (--provisional)",
+    });
+    for canister in &canisters {
+        print_info(&format!("Canister: {}", canister));
+    }
+
+    top_up_cycles_default_path(&canisters, amount, source)
+        .await
+        .context("Failed to top up cycles")?;
+
+    print_success(&format!(
+        "Added {} cycles to {} canister(s)",
+        amount,
+        canisters.len()
+    ));
+    Ok(())
+}
+
+/// Handle get-sns-metrics command
+/// Usage: get-sns-metrics [--json] [--window <seconds>]
+pub async fn handle_get_sns_metrics(args: &[String]) -> Result<()> {
+    use crate::core::ops::sns_governance_ops::get_sns_metrics_default_path;
+    use crate::core::utils::cli_args::{take_flag, take_switch};
+
+    let mut args = args.to_vec();
+    let json = take_switch(&mut args, "--json");
+    let window = match take_flag(&mut args, "--window")? {
+        Some(value) => value
+            .parse::<u64>()
+            .ok()
+            .filter(|secs| *secs > 0)
+            .with_context(|| {
+                ValidationError::new(format!("Invalid --window '{}': expected seconds", value))
+            })?,
+        None => 7 * 24 * 60 * 60,
+    };
+    if args.len() > 2 {
+        return Err(invalid(format!("Unexpected argument: {}", args[2])));
+    }
+
+    let metrics = get_sns_metrics_default_path(window)
+        .await
+        .context("Failed to get SNS metrics")?;
+
+    // One JSON object per run, so repeated runs can be appended to a file and charted
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string(&metrics).context("Failed to serialize metrics")?
+        );
+        return Ok(());
+    }
+
+    let scale = 10f64.powi(i32::from(
+        app_context()
+            .ledger_decimals(app_context().sns_ledger_canister()?)
+            .await?,
+    ));
+    let tokens = |e8s: u64| e8s as f64 / scale;
+    print_header("SNS Metrics");
+    print_info(&format!(
+        "Governance Canister: {}",
+        app_context().sns_governance_canister()?
+    ));
+
+    println!();
+    println!("  Neurons: {}", metrics.neuron_count);
+    println!(
+        "  Total staked: {} e8s ({:.8} tokens)",
+        metrics.total_staked_e8s,
+        tokens(metrics.total_staked_e8s)
+    );
+    println!(
+        "  Total maturity: {} e8s ({:.8} tokens)",
+        metrics.total_maturity_e8s,
+        tokens(metrics.total_maturity_e8s)
+    );
+    println!(
+        "  Not dissolving: {} neuron(s), {} e8s",
+        metrics.not_dissolving_count, metrics.not_dissolving_e8s
+    );
+    println!(
+        "  Dissolving: {} neuron(s), {} e8s",
+        metrics.dissolving_count, metrics.dissolving_e8s
+    );
+    println!(
+        "  Dissolved: {} neuron(s), {} e8s",
+        metrics.dissolved_count, metrics.dissolved_e8s
+    );
+    println!(
+        "  Eligible to vote: {} neuron(s), {} e8s (minimum dissolve delay: {})",
+        metrics.eligible_to_vote_count,
+        metrics.eligible_to_vote_e8s,
+        metrics
+            .neuron_minimum_dissolve_delay_to_vote_seconds
+            .map_or_else(|| "-".to_string(), |secs| format!("{}s", secs))
+    );
+    println!(
+        "  Total potential voting power: {}",
+        metrics
+            .total_potential_voting_power
+            .map_or_else(|| "-".to_string(), |vp| vp.to_string())
+    );
+
+    for (title, buckets) in [
+        ("Not dissolving", &metrics.not_dissolving_buckets),
+        ("Dissolving", &metrics.dissolving_buckets),
+    ] {
+        if buckets.is_empty() {
+            continue;
+        }
+        println!();
+        println!("  {} neurons by dissolve delay:", title);
+        for bucket in buckets {
+            println!(
+                "    {:>4} - {:<4} years: {:>5} neuron(s), {} e8s",
+                bucket.half_years as f64 / 2.0,
+                (bucket.half_years + 1) as f64 / 2.0,
+                bucket.count,
+                bucket.staked_e8s
+            );
+        }
+    }
+
+    if !metrics.treasury.is_empty() {
+        println!();
+        println!("  Treasury:");
+        for treasury in &metrics.treasury {
+            println!(
+                "    {}: {} e8s (initially {} e8s)",
+                treasury.name,
+                treasury
+                    .amount_e8s
+                    .map_or_else(|| "-".to_string(), |e8s| e8s.to_string()),
+                treasury
+                    .original_amount_e8s
+                    .map_or_else(|| "-".to_string(), |e8s| e8s.to_string())
+            );
+        }
+    }
+
+    println!();
+    println!(
+        "  Proposals in the last {}s: {} submitted, {} executed",
+        window,
+        metrics
+            .recently_submitted_proposals
+            .map_or_else(|| "-".to_string(), |n| n.to_string()),
+        metrics
+            .recently_executed_proposals
+            .map_or_else(|| "-".to_string(), |n| n.to_string())
+    );
+    Ok(())
+}
+
+/// Handle show-ballots command
+/// Usage: show-ballots <proposal_id>
+pub async fn handle_show_ballots(args: &[String]) -> Result<()> {
+    use crate::core::ops::sns_governance_ops::get_proposal_ballots_default_path;
+    use crate::core::utils::time_format::{format_timestamp, now_secs};
+
+    if args.len() > 3 {
+        return Err(invalid(format!("Unexpected argument: {}", args[3])));
+    }
+    let proposal_id = match args.get(2) {
+        Some(id) => id.clone(),
+        None => {
+            print_header("Show Ballots");
+            read_input_required("Enter proposal ID (or press Enter/[b]ack to go back): ")
+                .await
+                .map_err(navigation_to_anyhow)?
+        }
+    };
+    let proposal_id = proposal_id
+        .parse::<u64>()
+        .context("Failed to parse proposal ID - must be a number")?;
+
+    let proposal = get_proposal_ballots_default_path(proposal_id)
+        .await
+        .with_context(|| format!("Failed to get ballots of proposal {}", proposal_id))?;
+    let now = now_secs();
+
+    print_header(&format!("Ballots of Proposal {}", proposal_id));
+    if let Some(title) = &proposal.title {
+        print_info(&format!("Title: {}", title));
+    }
+    let status = if proposal.failed_timestamp_seconds > 0 {
+        format!(
+            "Failed at {}",
+            format_timestamp(proposal.failed_timestamp_seconds, now)
+        )
+    } else if proposal.executed_timestamp_seconds > 0 {
+        format!(
+            "Executed at {}",
+            format_timestamp(proposal.executed_timestamp_seconds, now)
+        )
+    } else if proposal.decided_timestamp_seconds > 0 {
+        format!(
+            "Decided at {}",
+            format_timestamp(proposal.decided_timestamp_seconds, now)
+        )
+    } else {
+        match proposal.deadline_timestamp_seconds {
+            Some(deadline) => format!("Open, voting ends {}", format_timestamp(deadline, now)),
+            None => "Open".to_string(),
+        }
+    };
+    print_info(&format!("Status: {}", status));
+
+    let mut table =
+        Table::new(&["Neuron", "Account", "Vote", "Voting Power (e8s)", "Cast"]).right_align(&[3]);
+    for ballot in &proposal.ballots {
+        table.add_row(vec![
+            short_id(&ballot.neuron_id),
+            ballot.account.clone().unwrap_or_else(|| "-".to_string()),
+            match ballot.vote {
+                1 => "Yes".to_string(),
+                2 => "No".to_string(),
+                0 => "Not voted".to_string(),
+                other => format!("Unknown ({})", other),
+            },
+            ballot.voting_power.to_string(),
+            if ballot.cast_timestamp_seconds > 0 {
+                format_timestamp(ballot.cast_timestamp_seconds, now)
+            } else {
+                "-".to_string()
+            },
+        ]);
+    }
+    println!();
+    if proposal.ballots.is_empty() {
+        print_info(
+            "No ballots (neurons need the minimum dissolve delay to vote when the proposal is made)",
+        );
+    } else {
+        table.print();
+    }
+
+    // Percent of the total voting power, with two decimals
+    let percent = |power: u64| {
+        if proposal.total == 0 {
+            0.0
+        } else {
+            power as f64 * 100.0 / proposal.total as f64
+        }
+    };
+    let not_voted = proposal
+        .total
+        .saturating_sub(proposal.yes)
+        .saturating_sub(proposal.no);
+    println!();
+    println!(
+        "  Yes:       {} ({:.2}%)",
+        proposal.yes,
+        percent(proposal.yes)
+    );
+    println!(
+        "  No:        {} ({:.2}%)",
+        proposal.no,
+        percent(proposal.no)
+    );
+    println!("  Not voted: {} ({:.2}%)", not_voted, percent(not_voted));
+    println!("  Total:     {}", proposal.total);
+
+    // At the deadline the proposal is adopted only if Yes clears both thresholds, compared
+    // like SNS governance does: at least the share of total, strictly above the share of cast
+    let yes_bp = u128::from(proposal.yes) * 10_000;
+    let total_bp =
+        u128::from(proposal.total) * u128::from(proposal.minimum_yes_proportion_of_total_bp);
+    let cast_bp = u128::from(proposal.yes + proposal.no)
+        * u128::from(proposal.minimum_yes_proportion_of_exercised_bp);
+    let needed_of_total = total_bp.div_ceil(10_000);
+    let needed_of_cast = cast_bp / 10_000;
+    let check = |met: bool| if met { "met" } else { "not met" };
+    println!();
+    println!(
+        "  Yes at least {:.2}% of total voting power ({}): {}",
+        proposal.minimum_yes_proportion_of_total_bp as f64 / 100.0,
+        needed_of_total,
+        check(yes_bp >= total_bp)
+    );
+    println!(
+        "  Yes above {:.2}% of cast voting power ({}): {}",
+        proposal.minimum_yes_proportion_of_exercised_bp as f64 / 100.0,
+        needed_of_cast,
+        check(yes_bp > cast_bp)
+    );
+    Ok(())
+}
+
+/// Handle advance-time command
+/// Usage: advance-time <duration>   (seconds, or with a unit: 30m, 2h, 7d, 1w)
+pub async fn handle_advance_time(args: &[String]) -> Result<()> {
+    use crate::core::ops::time_ops::advance_time;
+    use crate::core::utils::cli_args::parse_duration_secs;
+
+    let seconds = if args.len() >= 3 {
+        parse_duration_secs(&args[2])?
+    } else {
+        print_header("Advance Time");
+        let input = read_input_required(
+            "Enter how far to advance (seconds, or e.g. 30m, 2h, 7d, 1w; Enter/[b]ack to go back): ",
+        ).await
+        .map_err(navigation_to_anyhow)?;
+        parse_duration_secs(&input)?
+    };
+
+    print_header("Advancing Time");
+    print_info(&format!("Advancing replica time by {} seconds...", seconds));
+
+    let advance = advance_time(seconds)
+        .await
+        .context("Failed to advance time")?;
+
+    print_success(&format!(
+        "Replica time is now {} (was {})",
+        advance.after_nanos / 1_000_000_000,
+        advance.before_nanos / 1_000_000_000
+    ));
+    Ok(())
+}
+
+/// Handle data-path command
+/// Usage: data-path
+/// Prints only the path, so scripts can capture it
+pub async fn handle_data_path(_args: &[String]) -> Result<()> {
+    println!(
+        "{}",
+        crate::core::utils::data_output::get_output_path().display()
+    );
+    Ok(())
+}
+
+/// Handle metrics command
+/// Usage: metrics <serve [--port <n>]|write <path> [--once]> [--interval <seconds>] [--window <seconds>]
+/// Scrapes governance metrics, swap state and balances every interval in Prometheus text format
+pub async fn handle_metrics(args: &[String]) -> Result<()> {
+    use crate::core::ops::metrics_export::scrape_default_path;
+    use crate::core::utils::cli_args::{take_flag, take_switch};
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let usage = "Usage: metrics <serve [--port <n>]|write <path> [--once]> [--interval <seconds>] [--window <seconds>]";
+    let seconds = |flag: &str, value: Option<String>, default: u64| -> Result<u64> {
+        value.map_or(Ok(default), |value| {
+            value
+                .parse::<u64>()
+                .ok()
+                .filter(|secs| *secs > 0)
+                .with_context(|| {
+                    ValidationError::new(format!("Invalid {flag} '{value}': expected seconds"))
+                })
+        })
+    };
+
+    let mut args = args.to_vec();
+    let interval = std::time::Duration::from_secs(seconds(
+        "--interval",
+        take_flag(&mut args, "--interval")?,
+        15,
+    )?);
+    let window = seconds(
+        "--window",
+        take_flag(&mut args, "--window")?,
+        7 * 24 * 60 * 60,
+    )?;
+    let port = take_flag(&mut args, "--port")?
+        .map(|port| {
+            port.parse::<u16>().with_context(|| {
+                ValidationError::new(format!("Invalid --port '{port}': expected a port number"))
+            })
+        })
+        .transpose()?
+        .unwrap_or(9464);
+    let once = take_switch(&mut args, "--once");
+
+    let scrape = move || async move {
+        let scrape = scrape_default_path(window).await;
+        for error in scrape.errors() {
+            print_warning(&format!("Scrape failed for {error}"));
+        }
+        scrape.to_prometheus()
+    };
+
+    match (args.get(2).map(String::as_str), args.get(3)) {
+        (Some("serve"), None) => {
+            let _ctrl_c = SelfHandled::enter();
+            let listener = tokio::net::TcpListener::bind(("127.0.0.1", port))
+                .await
+                .with_context(|| format!("Failed to listen on 127.0.0.1:{port}"))?;
+            let page = Arc::new(tokio::sync::RwLock::new(scrape().await));
+            print_success(&format!(
+                "Serving metrics on http://127.0.0.1:{port}/metrics, scraped every {}s (Ctrl-C to stop)",
+                interval.as_secs()
+            ));
+
+            let refresh = {
+                let page = Arc::clone(&page);
+                tokio::spawn(async move {
+                    loop {
+                        tokio::time::sleep(interval).await;
+                        let text = scrape().await;
+                        *page.write().await = text;
+                    }
+                })
+            };
+            loop {
+                let (mut stream, _) = tokio::select! {
+                    _ = tokio::signal::ctrl_c() => break,
+                    accepted = listener.accept() => accepted?,
+                };
+                let page = Arc::clone(&page);
+                tokio::spawn(async move {
+                    let mut request = [0u8; 1024];
+                    let n = stream.read(&mut request).await.unwrap_or(0);
+                    let request = String::from_utf8_lossy(&request[..n]);
+                    let path = request.split_whitespace().nth(1).unwrap_or("/");
+                    let response = if path == "/metrics" || path == "/" {
+                        let body = page.read().await.clone();
+                        format!(
+                            "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                            crate::core::utils::prometheus::CONTENT_TYPE,
+                            body.len()
+                        )
+                    } else {
+                        "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                            .to_string()
+                    };
+                    let _ = stream.write_all(response.as_bytes()).await;
+                });
+            }
+            refresh.abort();
+            Ok(())
+        }
+        (Some("write"), Some(path)) => {
+            let path = std::path::PathBuf::from(path);
+            // Written next to the target and renamed, so a collector never reads half a file
+            let partial = path.with_extension("prom.tmp");
+            let _ctrl_c = SelfHandled::enter();
+            if !once {
+                print_success(&format!(
+                    "Writing metrics to {} every {}s (Ctrl-C to stop)",
+                    path.display(),
+                    interval.as_secs()
+                ));
+            }
+            loop {
+                std::fs::write(&partial, scrape().await)
+                    .with_context(|| format!("Failed to write {}", partial.display()))?;
+                std::fs::rename(&partial, &path)
+                    .with_context(|| format!("Failed to write {}", path.display()))?;
+                if once {
+                    print_success(&format!("Wrote metrics to {}", path.display()));
+                    return Ok(());
+                }
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => return Ok(()),
+                    () = tokio::time::sleep(interval) => {}
+                }
+            }
+        }
+        _ => Err(invalid(usage)),
+    }
+}
+
+/// Handle print-ids command
+/// Usage: print-ids [name]
+/// Prints `name  id` lines with full IDs; with a name, prints only that ID
+pub async fn handle_print_ids(args: &[String]) -> Result<()> {
+    use crate::core::utils::clipboard::is_copy_enabled;
+
+    let ids = known_ids(&*app_context().deployment_data()?);
+
+    if let Some(name) = args.get(2) {
+        let Some((_, id)) = ids.iter().find(|(n, _)| n == name) else {
+            return Err(invalid(format!(
+                "Unknown argument '{name}': expected one of {}",
+                ids.iter()
+                    .map(|(n, _)| n.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )));
+        };
+        println!("{id}");
+        if is_copy_enabled() {
+            copy_and_report(name, id);
+        }
+        return Ok(());
+    }
+
+    let width = ids.iter().map(|(n, _)| n.len()).max().unwrap_or(0);
+    let lines: Vec<String> = ids
+        .iter()
+        .map(|(name, id)| format!("{name:width$}  {id}"))
+        .collect();
+    println!("{}", lines.join("\n"));
+    if is_copy_enabled() {
+        copy_and_report("all IDs", &(lines.join("\n") + "\n"));
+    }
+    Ok(())
+}
+
+/// Names and IDs of the deployment's principals, neurons, proposal and canisters
+fn known_ids(data: &SnsCreationData) -> Vec<(String, String)> {
+    use crate::core::utils::network::network_config;
+
+    let network = network_config();
+    let mut ids: Vec<(String, String)> = data.accounts();
+    ids.extend(
+        data.identities
+            .iter()
+            .map(|i| (format!("identity-{}", i.name), i.principal.clone())),
+    );
+    ids.extend(
+        data.aliases
+            .iter()
+            .map(|a| (format!("alias-{}", a.name), a.principal.clone())),
+    );
+    ids.extend(
+        data.deployed_sns
+            .canister_ids()
+            .into_iter()
+            .map(|(role, id)| (format!("sns-{role}"), id.to_string())),
+    );
+    // Imported SNSes have no ICP neuron or proposal of their own (recorded as 0)
+    ids.extend(
+        [
+            ("icp-neuron", data.icp_neuron_id),
+            ("sns-proposal", data.proposal_id),
+        ]
+        .into_iter()
+        .filter(|(_, id)| *id > 0)
+        .map(|(name, id)| (name.to_string(), id.to_string())),
+    );
+    ids.extend([
+        (
+            "nns-governance".to_string(),
+            network.governance_canister.to_text(),
+        ),
+        ("icp-ledger".to_string(), network.ledger_canister.to_text()),
+        (
+            "icp-index".to_string(),
+            network.icp_index_canister.to_text(),
+        ),
+        ("sns-wasm".to_string(), network.snsw_canister.to_text()),
+    ]);
+    ids
+}
+
+/// Handle history command
+/// Usage: history [--limit <n>] [--all] [--json]
+/// Shows the state-changing canister calls recorded in the audit log, most recent last
+pub async fn handle_history(args: &[String]) -> Result<()> {
+    use crate::core::ops::audit_log::{AuditResult, audit_log_path, read_audit_log};
+    use crate::core::utils::cli_args::{take_flag, take_switch};
+    use crate::core::utils::time_format::format_utc;
+
+    let mut args = args.to_vec();
+    let json = take_switch(&mut args, "--json");
+    let all = take_switch(&mut args, "--all");
+    let limit = take_flag(&mut args, "--limit")?
+        .map(|value| {
+            value
+                .parse::<usize>()
+                .ok()
+                .filter(|n| *n > 0)
+                .with_context(|| {
+                    ValidationError::new(format!(
+                        "Invalid --limit '{value}': expected a positive number"
+                    ))
+                })
+        })
+        .transpose()?
+        .unwrap_or(20);
+    if args.len() > 2 {
+        return Err(invalid(format!(
+            "Unknown argument: {}\nUsage: history [--limit <n>] [--all] [--json]",
+            args[2]
+        )));
+    }
+
+    let entries = read_audit_log()?;
+    let first = if all {
+        0
+    } else {
+        entries.len().saturating_sub(limit)
+    };
+    let shown = &entries[first..];
+
+    if json {
+        for entry in shown {
+            println!(
+                "{}",
+                serde_json::to_string(entry).context("Failed to serialize audit entry")?
+            );
+        }
+        return Ok(());
+    }
+
+    print_header("History");
+    print_info(&format!("Audit log: {}", audit_log_path().display()));
+    if entries.is_empty() {
+        print_info("No actions recorded yet");
+        return Ok(());
+    }
+
+    // Principals and canisters by their deployment name, where the deployment knows them
+    let ids = app_context()
+        .try_deployment_data()
+        .map(|data| known_ids(&data))
+        .unwrap_or_default();
+    let name_of = |id: &str| {
+        ids.iter()
+            .find(|(_, known)| known == id)
+            .map_or_else(|| id.to_string(), |(name, _)| name.clone())
+    };
+
+    let mut table = Table::new(&[
+        "#", "Time", "Command", "Identity", "Canister", "Method", "Result", "Ref",
+    ])
+    .right_align(&[0]);
+    for (index, entry) in shown.iter().enumerate() {
+        let result = match (&entry.result, &entry.error) {
+            (AuditResult::Ok, _) | (_, None) => entry.result.to_string(),
+            (result, Some(error)) => format!("{result}: {error}"),
+        };
+        let reference = entry
+            .proposal_id
+            .map(|id| format!("proposal {id}"))
+            .or_else(|| entry.block_height.map(|block| format!("block {block}")))
+            .unwrap_or_else(|| "-".to_string());
+        table.add_row(vec![
+            (first + index + 1).to_string(),
+            format_utc(entry.timestamp_seconds),
+            entry.command.clone(),
+            name_of(&entry.identity),
+            name_of(&entry.canister),
+            entry.method.clone(),
+            result,
+            reference,
+        ]);
+    }
+    table.print();
+    if shown.len() < entries.len() {
+        print_info(&format!(
+            "Showing the last {} of {} actions; pass --all for every one",
+            shown.len(),
+            entries.len()
+        ));
+    }
+    Ok(())
+}
+
+/// Handle undo command
+/// Usage: undo [entry] [--execute]
+/// Without an entry, lists the reversible actions of the audit log; with one, shows (and with
+/// --execute makes) the compensating call
+pub async fn handle_undo(args: &[String]) -> Result<()> {
+    use crate::core::ops::audit_log::read_audit_log;
+    use crate::core::ops::undo_ops::{execute_undo, undo_entry, undo_suggestions};
+    use crate::core::utils::cli_args::take_switch;
+    use crate::core::utils::time_format::format_utc;
+
+    let usage = "Usage: undo [entry] [--execute]";
+    let mut args = args.to_vec();
+    let execute = take_switch(&mut args, "--execute");
+    if args.len() > 3 {
+        return Err(invalid(format!("Unknown argument: {}\n{usage}", args[3])));
+    }
+    let entries = read_audit_log()?;
+
+    let Some(number) = args.get(2) else {
+        if execute {
+            return Err(invalid(format!("--execute needs an entry number\n{usage}")));
+        }
+        print_header("Undo");
+        let suggestions = undo_suggestions(&entries);
+        if suggestions.is_empty() {
+            print_info("No reversible actions in the audit log");
+            return Ok(());
+        }
+        let mut table = Table::new(&["#", "Time", "Command", "Undo"]).right_align(&[0]);
+        for undo in &suggestions {
+            let entry = &entries[undo.entry - 1];
+            table.add_row(vec![
+                undo.entry.to_string(),
+                format_utc(entry.timestamp_seconds),
+                entry.command.clone(),
+                undo.description.clone(),
+            ]);
+        }
+        table.print();
+        print_info(
+            "Run `undo <#>` to see the compensating command, `undo <#> --execute` to run it",
+        );
+        return Ok(());
+    };
+
+    let number = number.parse::<usize>().with_context(|| {
+        ValidationError::new(format!(
+            "Invalid entry '{number}': expected a number from history"
+        ))
+    })?;
+    let undo = undo_entry(&entries, number)?;
+    let entry = &entries[number - 1];
+
+    print_header(&format!("Undo #{number}"));
+    print_info(&format!(
+        "Action: {} ({} on {}, {})",
+        entry.command,
+        entry.method,
+        entry.canister,
+        format_utc(entry.timestamp_seconds)
+    ));
+    print_info(&format!("Undo: {}", undo.description));
+    if let Some(command) = &undo.command {
+        print_info(&format!("Equivalent command: local_sns {command}"));
+    }
+    if !execute {
+        print_info(&format!("Run `undo {number} --execute` to make the call"));
+        return Ok(());
+    }
+
+    if !confirm_action(
+        "undo an action",
+        &[
+            ("Entry", number.to_string()),
+            ("Action", entry.command.clone()),
+            ("Undo", undo.description.clone()),
+        ],
+    )
+    .await?
+    {
+        return Err(Cancelled.into());
+    }
+    execute_undo(&undo)
+        .await
+        .with_context(|| format!("Failed to undo entry {number}"))?;
+    print_success(&format!("Undid entry {number}: {}", undo.description));
+    Ok(())
+}
+
+/// Handle config command
+/// Usage: config <list|get <key>|set <key> <value>|unset <key>|path>
+/// Defaults for the global flags; a flag beats its environment variable, which beats the file
+pub async fn handle_config(args: &[String]) -> Result<()> {
+    use crate::core::utils::user_config::{SETTINGS, UserConfig, config_default, keys};
+
+    let usage = "Usage: config <list|get <key>|set <key> <value>|unset <key>|path>";
+    let check_key = |key: &str| {
+        if SETTINGS.iter().any(|s| s.key == key) {
+            Ok(())
+        } else {
+            Err(invalid(format!(
+                "Unknown config key '{key}'. Keys: {}",
+                keys()
+            )))
+        }
+    };
+
+    match args.get(2).map(String::as_str) {
+        None | Some("list") => {
+            let mut table = Table::new(&["Key", "Value", "Source", "Flag", "Environment"]);
+            for setting in SETTINGS {
+                let (value, source) = match config_default(setting.key) {
+                    Some((value, source)) => (value, source.to_string()),
+                    None => ("-".to_string(), "unset".to_string()),
+                };
+                table.add_row(vec![
+                    setting.key.to_string(),
+                    value,
+                    source,
+                    setting.flag.to_string(),
+                    setting.env.join(", "),
+                ]);
+            }
+            print_header("Configuration");
+            print_info(&format!("Config file: {}", UserConfig::path().display()));
+            println!();
+            table.print();
+        }
+        Some("get") => {
+            let key = args.get(3).context(usage)?;
+            check_key(key)?;
+            if let Some((value, _)) = config_default(key) {
+                println!("{value}");
+            }
+        }
+        Some("set") => {
+            let (Some(key), Some(value)) = (args.get(3), args.get(4)) else {
+                return Err(invalid(usage));
+            };
+            let mut config = UserConfig::load()?;
+            config.set(key, Some(value))?;
+            let path = config.save()?;
+            print_success(&format!("Set {key} = {value} in {}", path.display()));
+        }
+        Some("unset") => {
+            let key = args.get(3).context(usage)?;
+            let mut config = UserConfig::load()?;
+            config.set(key, None)?;
+            let path = config.save()?;
+            print_success(&format!("Removed {key} from {}", path.display()));
+        }
+        Some("path") => println!("{}", UserConfig::path().display()),
+        Some(other) => {
+            return Err(invalid(format!(
+                "Unknown command 'config {other}'. {usage}"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Handle export command
+/// Usage: export [--env <file>] [--canister-ids <file>] [--canister-ids-network <name>]
+///        export <neurons <sns|icp>|balances> --format csv [--output <file>]
+/// Without --env or --canister-ids, both files are written to their default paths
+pub async fn handle_export(args: &[String]) -> Result<()> {
+    use crate::core::utils::cli_args::take_flag;
+    use crate::core::utils::data_output::{
+        DEFAULT_CANISTER_IDS_FILE, DEFAULT_ENV_FILE, export_canister_ids, export_env, read_data,
+    };
+
+    if matches!(
+        args.get(2).map(String::as_str),
+        Some("neurons" | "balances")
+    ) {
+        return export_listing(args).await;
+    }
+
+    let mut args = args.to_vec();
+    let env_file = take_flag(&mut args, "--env")?;
+    let canister_ids_file = take_flag(&mut args, "--canister-ids")?;
+    let network = take_flag(&mut args, "--canister-ids-network")?
+        .or_else(|| std::env::var("DFX_NETWORK").ok())
+        .unwrap_or_else(|| "local".to_string());
+    let (env_file, canister_ids_file) = match (env_file, canister_ids_file) {
+        (None, None) => (
+            Some(DEFAULT_ENV_FILE.to_string()),
+            Some(DEFAULT_CANISTER_IDS_FILE.to_string()),
+        ),
+        files => files,
+    };
+
+    let data = read_data().context("No deployment data to export. Deploy an SNS first")?;
+
+    print_header("Export Deployment Data");
+    if let Some(path) = env_file {
+        let variables = export_env(&data, std::path::Path::new(&path))?;
+        print_success(&format!("Wrote {} variables to {}", variables.len(), path));
+        for name in variables {
+            println!("  {}", name);
+        }
+    }
+    if let Some(path) = canister_ids_file {
+        let names = export_canister_ids(&data, std::path::Path::new(&path), &network)?;
+        print_success(&format!(
+            "Merged {} canisters into {} (network {})",
+            names.len(),
+            path,
+            network
+        ));
+        for name in names {
+            println!("  {}", name);
+        }
+    }
+    Ok(())
+}
+
+/// `export neurons <sns|icp>` and `export balances`: the listings as CSV, with full IDs and
+/// amounts in e8s, for spreadsheets
+async fn export_listing(args: &[String]) -> Result<()> {
+    use crate::core::ops::governance_ops::list_all_icp_neurons_default_path;
+    use crate::core::ops::ledger_ops::get_balance_overview;
+    use crate::core::ops::sns_governance_ops::list_all_neurons_default_path;
+    use crate::core::utils::cli_args::take_flag;
+    use crate::core::utils::csv::Csv;
+
+    let usage = "Usage: export <neurons <sns|icp>|balances> --format csv [--output <file>]";
+    let mut args = args.to_vec();
+    match take_flag(&mut args, "--format")?.as_deref() {
+        Some("csv") => {}
+        Some(other) => return Err(invalid(format!("Invalid --format '{other}': expected csv"))),
+        None => return Err(invalid(format!("--format is required. {usage}"))),
+    }
+    let output = take_flag(&mut args, "--output")?.map(std::path::PathBuf::from);
+
+    let csv = match (args[2].as_str(), args.get(3).map(String::as_str)) {
+        ("neurons", Some("sns")) => {
+            let accounts = list_all_neurons_default_path()
+                .await
+                .context("Failed to list SNS neurons")?;
+            let mut csv = neuron_csv();
+            for (label, principal, neurons) in &accounts {
+                add_neuron_csv_rows(&mut csv, label, *principal, neurons);
+            }
+            csv
+        }
+        ("neurons", Some("icp")) => {
+            let accounts = list_all_icp_neurons_default_path()
+                .await
+                .context("Failed to list ICP neurons")?;
+            let mut csv = neuron_csv();
+            for (label, principal, neurons) in &accounts {
+                add_neuron_csv_rows(&mut csv, label, *principal, neurons);
+            }
+            csv
+        }
+        ("balances", None) => {
+            let amount = |e8s: Option<u64>| e8s.map(|e8s| e8s.to_string()).unwrap_or_default();
+            let mut csv = Csv::new(&[
+                "account",
+                "principal",
+                "icp_e8s",
+                "icp_staked_e8s",
+                "sns_e8s",
+                "sns_staked_e8s",
+            ]);
+            for account in get_balance_overview()
+                .await
+                .context("Failed to get balances")?
+            {
+                csv.add_row(vec![
+                    account.label,
+                    account.principal,
+                    amount(account.icp_e8s),
+                    amount(account.icp_staked_e8s),
+                    amount(account.sns_e8s),
+                    amount(account.sns_staked_e8s),
+                ]);
+            }
+            csv
+        }
+        _ => return Err(invalid(usage)),
+    };
+
+    csv.write(output.as_deref())?;
+    if let Some(path) = output {
+        print_success(&format!("Wrote {} rows to {}", csv.len(), path.display()));
+    }
+    Ok(())
+}
+
+/// Header of the neuron CSV export
+fn neuron_csv() -> crate::core::utils::csv::Csv {
+    crate::core::utils::csv::Csv::new(&[
+        "account",
+        "principal",
+        "neuron_id",
+        "stake_e8s",
+        "maturity_e8s",
+        "staked_maturity_e8s",
+        "dissolve_state",
+        "dissolve_delay_seconds",
+        "dissolve_timestamp_seconds",
+        "aging_since_timestamp_seconds",
+        "created_timestamp_seconds",
+    ])
+}
+
+/// One neuron CSV row per neuron of an account; blank cells where a value does not apply
+fn add_neuron_csv_rows<N: NeuronView>(
+    csv: &mut crate::core::utils::csv::Csv,
+    label: &str,
+    principal: Principal,
+    neurons: &[N],
+) {
+    use crate::core::ops::neuron_view::Dissolve;
+
+    for neuron in neurons {
+        let (state, delay, timestamp) = match neuron.dissolve() {
+            Dissolve::NotDissolving { delay_seconds } => {
+                ("not_dissolving", delay_seconds.to_string(), String::new())
+            }
+            Dissolve::DissolvingAt(timestamp) => {
+                ("dissolving", String::new(), timestamp.to_string())
+            }
+            Dissolve::Unknown => ("unknown", String::new(), String::new()),
+        };
+        csv.add_row(vec![
+            label.to_string(),
+            principal.to_text(),
+            neuron.id_text().unwrap_or_default(),
+            neuron.stake_e8s().to_string(),
+            neuron.maturity_e8s().to_string(),
+            neuron
+                .staked_maturity_e8s()
+                .map(|e8s| e8s.to_string())
+                .unwrap_or_default(),
+            state.to_string(),
+            delay,
+            timestamp,
+            neuron.aging_since_seconds().to_string(),
+            neuron.created_seconds().to_string(),
+        ]);
+    }
+}
+
+/// Handle run-scenario command
+/// Usage: run-scenario <file.yaml>
+pub async fn handle_run_scenario(args: &[String]) -> Result<()> {
+    use crate::core::ops::scenario_ops::{Scenario, run_scenario};
+
+    let path = if args.len() >= 3 {
+        args[2].clone()
+    } else {
+        print_header("Run Scenario");
+        read_input_required(
+            "Enter the scenario file (.yaml, .toml or .json; Enter/[b]ack to go back): ",
+        )
+        .await
+        .map_err(navigation_to_anyhow)?
+    };
+
+    let scenario = Scenario::load(std::path::Path::new(&path))?;
+    let steps = run_scenario(&scenario).await?;
+
+    print_header("Scenario Passed");
+    print_success(&format!("All {} steps passed", steps));
+    Ok(())
+}
+
+/// Handle seed command
+/// Usage: seed <spec-file>; with --dry-run only the plan is printed
+pub async fn handle_seed(args: &[String]) -> Result<()> {
+    use crate::core::ops::canister_call::is_dry_run;
+    use crate::core::ops::seed_ops::{SeedSpec, run_seed};
+    use crate::core::utils::cli_args::format_duration_secs;
+
+    let path = if args.len() >= 3 {
+        args[2].clone()
+    } else {
+        print_header("Seed Demo Data");
+        read_input_required(
+            "Enter the seed spec file (.yaml, .toml or .json; Enter/[b]ack to go back): ",
+        )
+        .await
+        .map_err(navigation_to_anyhow)?
+    };
+
+    let spec = SeedSpec::load(std::path::Path::new(&path))?;
+    let plan = spec.plan()?;
+
+    print_header(&format!(
+        "Seed: {}",
+        spec.name.as_deref().unwrap_or("(unnamed)")
+    ));
+    let mut table =
+        Table::new(&["Holder", "SNS (e8s)", "Neurons", "Dissolve delays"]).right_align(&[1, 2]);
+    for holder in &plan.holders {
+        let delays: Vec<String> = holder
+            .stakes
+            .iter()
+            .map(|s| format_duration_secs(s.dissolve_delay_seconds))
+            .collect();
+        table.add_row(vec![
+            holder.name.clone(),
+            holder.balance_e8s.to_string(),
+            holder.stakes.len().to_string(),
+            delays.join(", "),
+        ]);
+    }
+    table.print();
+    if let Some(icp_e8s) = plan.icp_e8s {
+        print_info(&format!("ICP per holder: {} e8s", icp_e8s));
+    }
+    if !plan.follow.is_empty() {
+        print_info(&format!("Neurons follow: {}", plan.follow.join(", ")));
+    }
+    println!();
+
+    // Seeding creates identities before its first update call, so stop at the plan
+    if is_dry_run() {
+        return Ok(());
+    }
+
+    let summary = run_seed(&spec).await?;
+    print_header("Seed Complete");
+    print_success(&format!(
+        "{} holders, {} neurons, {} neurons following",
+        summary.holders.len(),
+        summary.neurons,
+        summary.followed
+    ));
+    Ok(())
+}
+
+/// Handle bench command
+/// Usage: bench <proposals|votes|transfers> [--count <n>] [--rate <per-second>] [--concurrency <n>]
+///        [--identities <name,...>] [--amount <e8s>] [--json]
+pub async fn handle_bench(args: &[String]) -> Result<()> {
+    use crate::core::ops::bench_ops::{
+        BenchKind, BenchOptions, DEFAULT_BENCH_CONCURRENCY, DEFAULT_BENCH_TRANSFER_E8S, run_bench,
+    };
+    use crate::core::ops::canister_call::is_dry_run;
+    use crate::core::utils::cli_args::{take_flag, take_switch};
+
+    let usage = "Usage: bench <proposals|votes|transfers> [--count <n>] [--rate <per-second>] [--concurrency <n>] [--identities <name,...>] [--amount <tokens> | --amount-e8s <n>] [--json]";
+    let number = |flag: &str, value: Option<String>, default: usize| -> Result<usize> {
+        value.map_or(Ok(default), |value| {
+            value
+                .parse::<usize>()
+                .ok()
+                .filter(|n| *n > 0)
+                .with_context(|| {
+                    ValidationError::new(format!(
+                        "Invalid {flag} '{value}': expected a positive number"
+                    ))
+                })
+        })
+    };
+
+    let mut args = args.to_vec();
+    let json = take_switch(&mut args, "--json");
+    let count = number("--count", take_flag(&mut args, "--count")?, 100)?;
+    let concurrency = number(
+        "--concurrency",
+        take_flag(&mut args, "--concurrency")?,
+        DEFAULT_BENCH_CONCURRENCY,
+    )?;
+    let rate = take_flag(&mut args, "--rate")?
+        .map(|value| {
+            value
+                .parse::<f64>()
+                .ok()
+                .filter(|rate| rate.is_finite() && *rate > 0.0)
+                .with_context(|| {
+                    format!("Invalid --rate '{value}': expected operations per second")
+                })
+        })
+        .transpose()?;
+    let amount = take_amount_flags(&mut args)?;
+    let identities: Vec<String> = take_flag(&mut args, "--identities")?
+        .map(|list| {
+            list.split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+    let kind: BenchKind = match args.get(2) {
+        Some(kind) => kind.parse()?,
+        None => return Err(invalid(usage)),
+    };
+    if args.len() > 3 {
+        return Err(invalid(format!("Unknown argument: {}\n{usage}", args[3])));
+    }
+
+    // A benchmark's timings mean nothing without real calls
+    if is_dry_run() {
+        print_info(&format!("Would submit {count} {kind}"));
+        return Ok(());
+    }
+
+    let amount_e8s = match amount {
+        Some(amount) => amount.e8s(app_context().sns_ledger_canister()?).await?,
+        None => DEFAULT_BENCH_TRANSFER_E8S,
+    };
+    let report = run_bench(&BenchOptions {
+        kind,
+        count,
+        rate,
+        concurrency,
+        identities,
+        amount_e8s,
+        quiet: json,
+    })
+    .await?;
+
+    let millis = |percent: f64| report.percentile(percent).map(|d| d.as_secs_f64() * 1000.0);
+    if json {
+        let value = serde_json::json!({
+            "kind": kind.to_string(),
+            "identities": report.identities,
+            "total": report.total(),
+            "succeeded": report.latencies.len(),
+            "failed": report.error_count(),
+            "elapsed_seconds": report.elapsed.as_secs_f64(),
+            "throughput_per_second": report.throughput(),
+            "latency_ms": {
+                "p50": millis(50.0),
+                "p90": millis(90.0),
+                "p95": millis(95.0),
+                "p99": millis(99.0),
+                "max": millis(100.0),
+            },
+            "errors": report.errors,
+            "warnings": report.warnings,
+        });
+        println!("{value}");
+        return Ok(());
+    }
+
+    print_header(&format!("Benchmark: {kind}"));
+    for warning in &report.warnings {
+        print_warning(warning);
+    }
+    print_info(&format!(
+        "{} of {} succeeded in {:.2}s ({:.2}/s) from {} identities",
+        report.latencies.len(),
+        report.total(),
+        report.elapsed.as_secs_f64(),
+        report.throughput(),
+        report.identities
+    ));
+    let mut table = Table::new(&["Latency", "ms"]).right_align(&[1]);
+    for (label, percent) in [
+        ("p50", 50.0),
+        ("p90", 90.0),
+        ("p95", 95.0),
+        ("p99", 99.0),
+        ("max", 100.0),
+    ] {
+        table.add_row(vec![
+            label.to_string(),
+            millis(percent).map_or_else(|| "-".to_string(), |ms| format!("{ms:.1}")),
+        ]);
+    }
+    table.print();
+
+    if report.errors.is_empty() {
+        print_success("No errors");
+    } else {
+        print_warning(&format!("{} operation(s) failed:", report.error_count()));
+        let mut errors: Vec<_> = report.errors.iter().collect();
+        errors.sort_by(|a, b| b.1.cmp(a.1));
+        for (message, count) in errors {
+            println!("  {count:>6}  {message}");
+        }
+    }
+    Ok(())
+}
+
+/// Handle list-sns-proposals command
+/// Usage: list-sns-proposals [--limit <n>] [--watch] [--interval <seconds>]
+pub async fn handle_list_sns_proposals(args: &[String]) -> Result<()> {
+    use crate::core::utils::cli_args::take_flag;
+
+    let mut args = args.to_vec();
+    let watch = take_watch_interval(&mut args)?;
+    let limit: usize = match take_flag(&mut args, "--limit")? {
+        Some(limit) => limit
+            .parse()
+            .ok()
+            .filter(|n| *n > 0)
+            .context(ValidationError::new(
+                "Invalid --limit - must be a positive number",
+            ))?,
+        None => 20,
+    };
+    if args.len() > 2 {
+        return Err(invalid(format!("Unexpected argument: {}", args[2])));
+    }
+
+    match watch {
+        Some(interval) => watch_loop(interval, || print_sns_proposals(limit)).await,
+        None => print_sns_proposals(limit).await,
+    }
+}
+
+/// Print the newest `limit` SNS proposals with their status and tally
+async fn print_sns_proposals(limit: usize) -> Result<()> {
+    use crate::core::ops::sns_governance_ops::{
+        ProposalOutcome, list_all_proposals, proposal_outcome,
+    };
+
+    let governance_canister = app_context().sns_governance_canister()?;
+    let agent = app_context().anonymous_agent().await?;
+    let proposals = list_all_proposals(&agent, governance_canister)
+        .await
+        .context("Failed to list SNS proposals")?;
+
+    print_header("SNS Proposals");
+    if proposals.is_empty() {
+        print_info("No proposals");
+        return Ok(());
+    }
+
+    let mut table =
+        Table::new(&["ID", "Status", "Yes", "No", "Total", "Title"]).right_align(&[0, 2, 3, 4]);
+    for proposal in proposals.iter().take(limit) {
+        let status = match proposal_outcome(proposal) {
+            Some(ProposalOutcome::Executed { .. }) => "Executed",
+            Some(ProposalOutcome::Failed { .. }) => "Failed",
+            Some(ProposalOutcome::Rejected { .. }) => "Rejected",
+            None if proposal.decided_timestamp_seconds > 0 => "Adopted",
+            None => "Open",
+        };
+        let (yes, no, total) = proposal
+            .latest_tally
+            .as_ref()
+            .map_or((0, 0, 0), |tally| (tally.yes, tally.no, tally.total));
+        table.add_row(vec![
+            proposal
+                .id
+                .as_ref()
+                .map_or_else(|| "-".to_string(), |id| id.id.to_string()),
+            status.to_string(),
+            yes.to_string(),
+            no.to_string(),
+            total.to_string(),
+            proposal
+                .proposal
+                .as_ref()
+                .map_or_else(String::new, |p| p.title.clone()),
+        ]);
+    }
+    table.print();
+    if proposals.len() > limit {
+        print_info(&format!(
+            "Showing the newest {limit} of {} proposals (--limit to change)",
+            proposals.len()
+        ));
+    }
     Ok(())
 }
 
-/// Handle check-sns-deployed command
-/// Returns exit code 0 if deployed, 1 if not deployed
-pub async fn handle_check_sns_deployed(_args: &[String]) -> Result<()> {
-    let deployed = check_sns_deployed_default_path()
-        .await
-        .context("Failed to check SNS deployment status")?;
+/// Handle get-swap-state command
+/// Usage: get-swap-state [--watch] [--interval <seconds>]
+pub async fn handle_get_swap_state(args: &[String]) -> Result<()> {
+    let mut args = args.to_vec();
+    let watch = take_watch_interval(&mut args)?;
+    if args.len() > 2 {
+        return Err(invalid(format!("Unexpected argument: {}", args[2])));
+    }
 
-    if deployed {
-        // Exit with 0 if deployed
-        std::process::exit(0);
-    } else {
-        // Exit with 1 if not deployed
-        std::process::exit(1);
+    match watch {
+        Some(interval) => watch_loop(interval, print_swap_state).await,
+        None => print_swap_state().await,
     }
 }
 
-/// Select an ICP neuron interactively from a list
-async fn select_icp_neuron(principal: Principal) -> Result<u64> {
-    use crate::core::ops::governance_ops::list_icp_neurons_for_principal_default_path;
+/// Print the state of the deployed swap canister
+async fn print_swap_state() -> Result<()> {
+    use crate::core::ops::swap_ops::{get_swap_state_default_path, lifecycle_name};
 
-    print_header("Select ICP Neuron");
-    print_info(&format!("Principal: {}", principal));
+    let swap_canister = app_context().sns_swap_canister()?;
 
-    let neurons = list_icp_neurons_for_principal_default_path(principal)
+    print_header("Swap State");
+    print_info(&format!("Swap Canister: {}", swap_canister));
+
+    let state = get_swap_state_default_path()
         .await
-        .context("Failed to list ICP neurons")?;
+        .context("Failed to get swap state")?;
 
-    if neurons.is_empty() {
-        println!();
-        print_warning(&format!("No ICP neurons found for principal {}", principal));
-        println!();
-        println!("This principal does not have any ICP neurons.");
-        println!("You can:");
-        println!("  1. Create an ICP neuron first using 'create-icp-neuron'");
-        println!("  2. Select a different principal that has ICP neurons");
-        println!();
-        let _ = read_input_required("Press Enter to go back to main menu: ")
-            .map_err(navigation_to_anyhow);
-        anyhow::bail!("User went to main menu");
+    println!();
+    println!(
+        "  Lifecycle: {} ({})",
+        lifecycle_name(state.lifecycle),
+        state.lifecycle
+    );
+    println!(
+        "  Opened at: {}",
+        format_swap_timestamp(state.open_timestamp_seconds)
+    );
+    if let Some(params) = &state.params {
+        println!(
+            "  Due at: {}",
+            format_swap_timestamp(Some(params.swap_due_timestamp_seconds))
+        );
     }
+    println!(
+        "  Committed/aborted at: {}",
+        format_swap_timestamp(state.termination_timestamp_seconds)
+    );
 
-    print_success(&format!("Found {} neuron(s)", neurons.len()));
     println!();
-
-    // Print table header
-    println!("{:-<100}", "");
+    println!("  Direct participants: {}", state.direct_participant_count);
+    println!(
+        "  Direct participation: {} e8s ({:.8} ICP)",
+        state.direct_participation_icp_e8s,
+        state.direct_participation_icp_e8s as f64 / 100_000_000.0
+    );
     println!(
-        "{:<5} {:<20} {:<20} {:<25} {:<30}",
-        "#", "Neuron ID", "Stake (e8s)", "Dissolve Delay", "Hotkeys"
+        "  Total buyer ICP: {} e8s ({:.8} ICP)",
+        state.buyer_total_icp_e8s,
+        state.buyer_total_icp_e8s as f64 / 100_000_000.0
     );
-    println!("{:-<100}", "");
+    match state.sns_tokens_per_icp {
+        Some(rate) => {
+            println!("  SNS tokens per ICP: {:.4}", rate);
+            println!("  ICP per SNS token: {:.8}", 1.0 / rate);
+        }
+        None => println!("  SNS tokens per ICP: - (no participation yet)"),
+    }
 
-    for (index, neuron) in neurons.iter().enumerate() {
-        // Neuron ID - ICP uses u64 IDs
-        let neuron_id_display = if let Some(id) = &neuron.id {
-            id.id.to_string()
+    if let Some(params) = &state.params {
+        println!();
+        println!(
+            "  SNS tokens for sale: {} e8s ({:.8} tokens)",
+            params.sns_token_e8s,
+            params.sns_token_e8s as f64 / 100_000_000.0
+        );
+        println!("  Minimum participants: {}", params.min_participants);
+        if let (Some(min), Some(max)) = (
+            params.min_direct_participation_icp_e8s,
+            params.max_direct_participation_icp_e8s,
+        ) {
+            println!("  Direct participation range: {} - {} e8s", min, max);
         } else {
-            "<none>".to_string()
-        };
+            println!(
+                "  ICP range: {} - {} e8s",
+                params.min_icp_e8s, params.max_icp_e8s
+            );
+        }
+        println!(
+            "  Per-participant range: {} - {} e8s",
+            params.min_participant_icp_e8s, params.max_participant_icp_e8s
+        );
+    }
 
-        // Stake
-        let stake_str = format!("{}", neuron.cached_neuron_stake_e8s);
+    Ok(())
+}
 
-        // Dissolve delay
-        let dissolve_delay_str = match &neuron.dissolve_state {
-            Some(crate::core::declarations::icp_governance::DissolveState::DissolveDelaySeconds(seconds)) => {
-                let days = *seconds / 86400;
-                format!("{} days ({}s)", days, seconds)
-            }
-            Some(crate::core::declarations::icp_governance::DissolveState::WhenDissolvedTimestampSeconds(timestamp)) => {
-                format!("Dissolving (dissolves at {})", timestamp)
-            }
-            None => "No state".to_string(),
-        };
+/// Take `--watch` and `--interval <seconds>` (default 5) from the arguments
+/// Returns the refresh interval if watching
+fn take_watch_interval(args: &mut Vec<String>) -> Result<Option<std::time::Duration>> {
+    use crate::core::utils::cli_args::{take_flag, take_switch};
 
-        // Hotkeys
-        let hotkeys_str = if neuron.hot_keys.is_empty() {
-            "None".to_string()
-        } else {
-            format!("{} hotkey(s)", neuron.hot_keys.len())
-        };
+    let watch = take_switch(args, "--watch");
+    let interval_secs = match take_flag(args, "--interval")? {
+        Some(value) => value
+            .parse::<u64>()
+            .ok()
+            .filter(|secs| *secs > 0)
+            .with_context(|| {
+                ValidationError::new(format!("Invalid --interval '{}': expected seconds", value))
+            })?,
+        None => 5,
+    };
+    Ok(watch.then(|| std::time::Duration::from_secs(interval_secs)))
+}
 
-        // Truncate dissolve delay if too long for table formatting
-        let dissolve_delay_display = if dissolve_delay_str.len() > 18 {
-            format!("{}...", &dissolve_delay_str[..18])
-        } else {
-            dissolve_delay_str
-        };
+/// Clear the screen and run `render` every `interval` until Ctrl-C
+/// A failed refresh is shown and retried, so a busy replica doesn't end the watch
+async fn watch_loop<F, Fut>(interval: std::time::Duration, mut render: F) -> Result<()>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<()>>,
+{
+    let _ctrl_c = SelfHandled::enter();
+    loop {
+        print!("\x1B[2J\x1B[H");
+        if let Err(e) = render().await {
+            print_warning(&format!("Refresh failed: {:#}", e));
+        }
+        println!();
+        print_info(&format!(
+            "Refreshing every {}s (Ctrl-C to stop)",
+            interval.as_secs()
+        ));
+        io::stdout().flush()?;
 
-        println!(
-            "{:<5} {:<20} {:<20} {:<25} {:<30}",
-            index + 1,
-            neuron_id_display,
-            stake_str,
-            dissolve_delay_display,
-            hotkeys_str
-        );
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+            () = tokio::time::sleep(interval) => {}
+        }
     }
+}
 
-    println!("{:-<100}", "");
-    println!();
+/// Handle finalize-swap command
+/// Usage: finalize-swap [--watch] [--interval <seconds>]
+pub async fn handle_finalize_swap(args: &[String]) -> Result<()> {
+    use crate::core::ops::swap_ops::{finalize_swap_default_path, lifecycle_name};
 
-    let input = read_input_required(&format!(
-        "Select neuron number (1-{}) or press Enter/[b]ack to go back: ",
-        neurons.len()
-    ))
-    .map_err(navigation_to_anyhow)?;
+    let mut args = args.to_vec();
+    let watch = take_watch_interval(&mut args)?;
+    if args.len() > 2 {
+        return Err(invalid(format!("Unexpected argument: {}", args[2])));
+    }
 
-    let selection: usize = input
-        .parse()
-        .context("Invalid selection - must be a number")?;
+    print_header("Finalize Swap");
+    print_info(&format!(
+        "Swap Canister: {}",
+        app_context().sns_swap_canister()?
+    ));
 
-    if selection < 1 || selection > neurons.len() {
-        anyhow::bail!(
-            "Invalid selection - must be between 1 and {}",
-            neurons.len()
+    let lifecycle = finalize_swap_default_path(watch.is_some(), watch.unwrap_or_default())
+        .await
+        .context("Failed to finalize swap")?;
+
+    print_success(&format!("Swap finalized ({})", lifecycle_name(lifecycle)));
+    if lifecycle == 4 {
+        print_info(
+            "The swap aborted: participants' ICP is refunded and no SNS neurons are created",
         );
     }
+    Ok(())
+}
+
+/// Handle abort-swap command
+/// Usage: abort-swap [--watch] [--interval <seconds>]
+pub async fn handle_abort_swap(args: &[String]) -> Result<()> {
+    use crate::core::ops::swap_ops::abort_swap_default_path;
+
+    let mut args = args.to_vec();
+    let watch = take_watch_interval(&mut args)?;
+    if args.len() > 2 {
+        return Err(invalid(format!("Unexpected argument: {}", args[2])));
+    }
+
+    print_header("Abort Swap");
+    print_info(&format!(
+        "Swap Canister: {}",
+        app_context().sns_swap_canister()?
+    ));
+
+    let refunds = abort_swap_default_path(watch.is_some(), watch.unwrap_or_default())
+        .await
+        .context("Failed to abort swap")?;
+    print_success("Swap aborted and finalized");
+
+    if refunds.is_empty() {
+        print_info("No participants in the deployment data");
+        return Ok(());
+    }
+
+    println!();
+    let mut table = Table::new(&[
+        "Participant",
+        "Refund Block",
+        "Escrow (e8s)",
+        "Balance (e8s)",
+    ])
+    .right_align(&[1, 2, 3]);
+    for refund in &refunds {
+        table.add_row(vec![
+            refund.principal.to_text(),
+            refund
+                .refund_block
+                .map_or_else(|| "-".to_string(), |block| block.to_string()),
+            refund.escrow_e8s.to_string(),
+            refund.balance_e8s.to_string(),
+        ]);
+    }
+    table.print();
+    println!();
+
+    let unrefunded = refunds.iter().filter(|r| r.is_refundable()).count();
+    if unrefunded == 0 {
+        print_success("All refundable participant ICP has left the swap");
+    } else {
+        print_warning(&format!(
+            "{unrefunded} participant(s) still have refundable ICP held by the swap"
+        ));
+    }
+    Ok(())
+}
+
+/// Format a swap timestamp with how long ago (or how far ahead) it is
+fn format_swap_timestamp(timestamp_seconds: Option<u64>) -> String {
+    let Some(timestamp) = timestamp_seconds.filter(|t| *t > 0) else {
+        return "-".to_string();
+    };
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    if timestamp > now {
+        format!("{} (in {}s)", timestamp, timestamp - now)
+    } else {
+        format!("{} ({}s ago)", timestamp, now - timestamp)
+    }
+}
+
+/// Select an ICP neuron interactively from a list
+async fn select_icp_neuron(principal: Principal) -> Result<u64> {
+    use crate::core::ops::governance_ops::list_icp_neurons_for_principal_default_path;
+
+    print_header("Select ICP Neuron");
+    print_info(&format!("Principal: {}", principal));
+
+    let neurons = list_icp_neurons_for_principal_default_path(principal)
+        .await
+        .context("Failed to list ICP neurons")?;
 
-    let selected_neuron = &neurons[selection - 1];
-    selected_neuron
+    let neuron = pick_neuron(principal, &neurons, &()).await?;
+    neuron
         .id
         .as_ref()
-        .map(|n| n.id)
+        .map(|id| id.id)
         .ok_or_else(|| anyhow::anyhow!("Selected neuron has no ID"))
 }
 
@@ -2797,9 +6851,12 @@ async fn select_icp_neuron(principal: Principal) -> Result<u64> {
 pub async fn handle_disburse_icp_neuron(args: &[String]) -> Result<()> {
     use crate::core::ops::governance_ops::disburse_icp_neuron_for_principal_default_path;
 
+    let mut args = args.to_vec();
+    let amount = take_amount(&mut args, 5)?;
+
     // Step 1: Get principal (select participant or custom if not provided)
     let principal = if args.len() >= 3 {
-        Principal::from_text(&args[2]).context("Failed to parse principal")?
+        resolve_principal(&args[2]).context("Failed to parse principal")?
     } else {
         match select_participant_with_back_handling(None, Some("icp")).await {
             Ok(p) => p,
@@ -2814,26 +6871,26 @@ pub async fn handle_disburse_icp_neuron(args: &[String]) -> Result<()> {
         // Check if arg3 looks like a neuron_id (number)
         if let Ok(id) = arg3.parse::<u64>() {
             // arg3 is neuron_id
-            let neuron_id_val = Some(id);
+            let neuron_id_val = id;
 
             // Get receiver_principal from next arg
             let receiver = if args.len() >= 5 {
-                Principal::from_text(&args[4]).context("Failed to parse receiver principal")?
+                resolve_principal(&args[4]).context("Failed to parse receiver principal")?
             } else {
                 let input = read_input_required(
                     "Enter receiver principal (or press Enter/[b]ack to go back): ",
                 )
+                .await
                 .map_err(navigation_to_anyhow)?;
-                Principal::from_text(&input).context("Failed to parse receiver principal")?
+                resolve_principal(&input).context("Failed to parse receiver principal")?
             };
 
             (neuron_id_val, receiver)
         } else {
             // arg3 is receiver_principal, need to select neuron
-            let receiver =
-                Principal::from_text(arg3).context("Failed to parse receiver principal")?;
+            let receiver = resolve_principal(arg3).context("Failed to parse receiver principal")?;
             let neuron_id_val = match select_icp_neuron(principal).await {
-                Ok(id) => Some(id),
+                Ok(id) => id,
                 Err(e) if is_user_cancelled_error(&e) || is_user_went_back_error(&e) => {
                     return Ok(());
                 }
@@ -2844,38 +6901,69 @@ pub async fn handle_disburse_icp_neuron(args: &[String]) -> Result<()> {
     } else {
         // Need to select neuron and get receiver interactively
         let neuron_id_val = match select_icp_neuron(principal).await {
-            Ok(id) => Some(id),
+            Ok(id) => id,
             Err(e) if is_user_cancelled_error(&e) => return Ok(()),
             Err(e) => return Err(e),
         };
 
-        print!("Enter receiver principal: ");
-        io::stdout().flush()?;
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
+        let input = read_line("Enter receiver principal: ").await?;
         let receiver =
-            Principal::from_text(input.trim()).context("Failed to parse receiver principal")?;
+            resolve_principal(input.trim()).context("Failed to parse receiver principal")?;
 
         (neuron_id_val, receiver)
     };
 
-    // Step 4: Get amount (optional)
-    let amount_e8s = if args.len() >= 6 {
-        Some(
-            args[5]
-                .parse::<u64>()
-                .context("Failed to parse amount_e8s")?,
-        )
-    } else {
-        None // Full disbursement
+    // Step 4: Get amount (optional, full disbursement without one)
+    let ledger_canister = crate::core::utils::network::network_config().ledger_canister;
+    let amount_e8s = match amount {
+        Some(AmountInput::Max) | None => None,
+        Some(amount) => Some(amount.e8s(ledger_canister).await?),
     };
 
+    // Checked whether the neuron was given or picked from the list
+    let neuron = get_icp_neuron_default_path(Some(neuron_id), Some(principal))
+        .await
+        .context("Failed to get ICP neuron")?;
+    ensure_dissolved(
+        neuron.dissolve(),
+        crate::core::ops::time_ops::replica_now_secs().await,
+    )?;
+
+    if needs_confirmation() {
+        use crate::core::utils::constants::ICP_TRANSFER_FEE;
+        let agent = app_context().anonymous_agent().await?;
+        let balance = get_icp_ledger_balance(&agent, ledger_canister, receiver_principal, None)
+            .await
+            .context("Failed to get receiver ICP balance")?;
+        let available = neuron
+            .cached_neuron_stake_e8s
+            .saturating_sub(neuron.neuron_fees_e8s);
+        let (amount_display, disbursed) = match amount_e8s {
+            Some(amount) => (icp_e8s_display(amount), amount),
+            None => (
+                format!("{} (full stake)", icp_e8s_display(available)),
+                available,
+            ),
+        };
+        let summary = [
+            ("Principal", principal.to_string()),
+            ("Neuron ID", neuron_id.to_string()),
+            ("Receiver", receiver_principal.to_string()),
+            ("Amount", amount_display),
+            (
+                "Resulting balance",
+                icp_e8s_display(balance.saturating_add(disbursed.saturating_sub(ICP_TRANSFER_FEE))),
+            ),
+        ];
+        if !confirm_action("disburse this ICP neuron", &summary).await? {
+            return Err(Cancelled.into());
+        }
+    }
+
     print_header("Disbursing ICP Neuron");
     print_info(&format!("Principal: {}", principal));
     print_info(&format!("Receiver: {}", receiver_principal));
-    if let Some(id) = neuron_id {
-        print_info(&format!("Neuron ID: {}", id));
-    }
+    print_info(&format!("Neuron ID: {}", neuron_id));
     if let Some(amount) = amount_e8s {
         print_info(&format!("Amount: {} e8s", amount));
     } else {
@@ -2885,7 +6973,7 @@ pub async fn handle_disburse_icp_neuron(args: &[String]) -> Result<()> {
     let block_height = disburse_icp_neuron_for_principal_default_path(
         principal,
         receiver_principal,
-        neuron_id,
+        Some(neuron_id),
         amount_e8s,
     )
     .await
@@ -2898,28 +6986,234 @@ pub async fn handle_disburse_icp_neuron(args: &[String]) -> Result<()> {
     Ok(())
 }
 
+/// Handle spawn-icp-neuron command
+/// Usage: spawn-icp-neuron [principal] [neuron_id] [--percentage <1-100>] [--controller <principal>]
+pub async fn handle_spawn_icp_neuron(args: &[String]) -> Result<()> {
+    use crate::core::ops::governance_ops::spawn_icp_neuron_for_principal_default_path;
+    use crate::core::utils::cli_args::take_flag;
+
+    let mut args = args.to_vec();
+    let percentage = take_flag(&mut args, "--percentage")?
+        .map(|value| {
+            value
+                .parse::<u32>()
+                .ok()
+                .filter(|p| (1..=100).contains(p))
+                .with_context(|| {
+                    ValidationError::new(format!(
+                        "Invalid percentage '{}': expected 1 to 100",
+                        value
+                    ))
+                })
+        })
+        .transpose()?;
+    let new_controller = take_flag(&mut args, "--controller")?
+        .map(|p| resolve_principal(&p).context("Failed to parse controller principal"))
+        .transpose()?;
+    if args.len() > 4 {
+        return Err(invalid(format!("Unexpected argument: {}", args[4])));
+    }
+
+    // Step 1: Get principal (select participant or custom if not provided)
+    let principal = if args.len() >= 3 {
+        resolve_principal(&args[2]).context("Failed to parse principal")?
+    } else {
+        match select_participant_with_back_handling(None, Some("icp")).await {
+            Ok(p) => p,
+            Err(e) if is_user_went_back_error(&e) => return Ok(()),
+            Err(e) => return Err(e),
+        }
+    };
+
+    // Step 2: Get neuron ID (select if not provided)
+    let neuron_id = if args.len() >= 4 {
+        args[3]
+            .parse::<u64>()
+            .context("Failed to parse neuron_id")?
+    } else {
+        match select_icp_neuron(principal).await {
+            Ok(id) => id,
+            Err(e) if is_user_cancelled_error(&e) || is_user_went_back_error(&e) => {
+                return Ok(());
+            }
+            Err(e) => return Err(e),
+        }
+    };
+
+    let neuron = get_icp_neuron_default_path(Some(neuron_id), Some(principal))
+        .await
+        .context("Failed to get ICP neuron")?;
+    if neuron.maturity_e8s_equivalent == 0 {
+        anyhow::bail!(
+            "ICP neuron {} has no maturity to spawn (maturity accrues from voting rewards)",
+            neuron_id
+        );
+    }
+
+    print_header("Spawning ICP Neuron");
+    print_info(&format!("Principal: {}", principal));
+    print_info(&format!("Neuron ID: {}", neuron_id));
+    print_info(&format!(
+        "Maturity: {}",
+        icp_e8s_display(neuron.maturity_e8s_equivalent)
+    ));
+    print_info(&format!(
+        "Spawning: {}% of the maturity",
+        percentage.unwrap_or(100)
+    ));
+    if let Some(controller) = new_controller {
+        print_info(&format!("New controller: {}", controller));
+    }
+
+    let spawned_id = spawn_icp_neuron_for_principal_default_path(
+        principal,
+        neuron_id,
+        percentage,
+        new_controller,
+    )
+    .await
+    .context("Failed to spawn ICP neuron")?;
+
+    print_success(&format!(
+        "Neuron spawned successfully! New neuron ID: {}",
+        spawned_id
+    ));
+    print_info(
+        "The new neuron is spawning: NNS governance mints its stake from the maturity after 7 days (see advance-time).",
+    );
+    Ok(())
+}
+
+/// Handle merge-icp-neurons command
+/// Usage: merge-icp-neurons [source_neuron_id] [target_neuron_id] [--principal <principal>]
+pub async fn handle_merge_icp_neurons(args: &[String]) -> Result<()> {
+    use crate::core::ops::governance_ops::merge_icp_neurons_for_principal_default_path;
+    use crate::core::utils::cli_args::take_flag;
+
+    let mut args = args.to_vec();
+    let principal = take_flag(&mut args, "--principal")?;
+    if args.len() > 4 {
+        return Err(invalid(format!("Unexpected argument: {}", args[4])));
+    }
+
+    // Step 1: Get principal (select participant or custom if not provided)
+    let principal = match principal {
+        Some(p) => resolve_principal(&p).context("Failed to parse principal")?,
+        None => match select_participant_with_back_handling(None, Some("icp")).await {
+            Ok(p) => p,
+            Err(e) if is_user_went_back_error(&e) => return Ok(()),
+            Err(e) => return Err(e),
+        },
+    };
+
+    // Step 2: Get the source and target neurons (select if not provided)
+    let mut neuron_ids = Vec::new();
+    for (i, label) in ["source", "target"].iter().enumerate() {
+        let id = match args.get(2 + i) {
+            Some(id) => id
+                .parse::<u64>()
+                .with_context(|| format!("Failed to parse {} neuron ID", label))?,
+            None => {
+                print_info(&format!("Select the {} neuron", label));
+                match select_icp_neuron(principal).await {
+                    Ok(id) => id,
+                    Err(e) if is_user_cancelled_error(&e) || is_user_went_back_error(&e) => {
+                        return Ok(());
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+        };
+        neuron_ids.push(id);
+    }
+    let (source_id, target_id) = (neuron_ids[0], neuron_ids[1]);
+    if source_id == target_id {
+        anyhow::bail!("Cannot merge neuron {} into itself", source_id);
+    }
+
+    if needs_confirmation() {
+        let source = get_icp_neuron_default_path(Some(source_id), Some(principal))
+            .await
+            .context("Failed to get source ICP neuron")?;
+        let target = get_icp_neuron_default_path(Some(target_id), Some(principal))
+            .await
+            .context("Failed to get target ICP neuron")?;
+        let summary = [
+            ("Principal", principal.to_string()),
+            (
+                "Source",
+                format!(
+                    "{} ({})",
+                    source_id,
+                    icp_e8s_display(source.cached_neuron_stake_e8s)
+                ),
+            ),
+            (
+                "Target",
+                format!(
+                    "{} ({})",
+                    target_id,
+                    icp_e8s_display(target.cached_neuron_stake_e8s)
+                ),
+            ),
+        ];
+        if !confirm_action("merge the source neuron into the target", &summary).await? {
+            return Err(Cancelled.into());
+        }
+    }
+
+    print_header("Merging ICP Neurons");
+    print_info(&format!("Principal: {}", principal));
+    print_info(&format!("Source neuron: {}", source_id));
+    print_info(&format!("Target neuron: {}", target_id));
+
+    let merged = merge_icp_neurons_for_principal_default_path(principal, source_id, target_id)
+        .await
+        .context("Failed to merge ICP neurons")?;
+
+    print_success(&format!(
+        "Neuron {} merged into neuron {}",
+        source_id, target_id
+    ));
+    if let Some(target) = &merged.target_neuron {
+        print_info(&format!(
+            "Target stake: {}",
+            icp_e8s_display(target.cached_neuron_stake_e8s)
+        ));
+        print_info(&format!(
+            "Target maturity: {}",
+            icp_e8s_display(target.maturity_e8s_equivalent)
+        ));
+    }
+    if let Some(source) = &merged.source_neuron {
+        print_info(&format!(
+            "Source stake left: {}",
+            icp_e8s_display(source.cached_neuron_stake_e8s)
+        ));
+    }
+    Ok(())
+}
+
 /// Handle increase-icp-dissolve-delay command
 pub async fn handle_increase_icp_dissolve_delay(args: &[String]) -> Result<()> {
     use crate::core::ops::governance_ops::increase_icp_dissolve_delay_for_principal_default_path;
 
     // Step 1: Get principal (select participant if not provided)
     let principal = if args.len() >= 3 {
-        Principal::from_text(&args[2]).context("Failed to parse principal")?
+        resolve_principal(&args[2]).context("Failed to parse principal")?
     } else {
-        select_participant_or_custom()?
+        select_participant_or_custom().await?
     };
 
     // Step 2: Get neuron ID (select if not provided)
     let neuron_id = if args.len() >= 4 {
-        Some(
-            args[3]
-                .parse::<u64>()
-                .context("Failed to parse neuron_id")?,
-        )
+        args[3]
+            .parse::<u64>()
+            .context("Failed to parse neuron_id")?
     } else {
         // Interactive neuron selection
         match select_icp_neuron(principal).await {
-            Ok(id) => Some(id),
+            Ok(id) => id,
             Err(e) if is_user_cancelled_error(&e) => return Ok(()),
             Err(e) => return Err(e),
         }
@@ -2927,30 +7221,33 @@ pub async fn handle_increase_icp_dissolve_delay(args: &[String]) -> Result<()> {
 
     // Step 3: Get additional dissolve delay (interactive if not provided)
     let additional_dissolve_delay_seconds = if args.len() >= 5 {
-        args[4]
-            .parse::<u64>()
-            .context("Failed to parse additional_dissolve_delay_seconds")?
+        DissolveDelayPresets::icp()
+            .parse(&args[4])
+            .context("Failed to parse additional_dissolve_delay")?
     } else {
         // Interactive prompt
         print_header("Increase ICP Neuron Dissolve Delay");
         print_info(&format!("Principal: {}", principal));
-        if let Some(id) = neuron_id {
-            print_info(&format!("Neuron ID: {}", id));
-        }
-        println!();
-        let input = read_input_required(
-            "Enter additional dissolve delay in seconds (e.g., 2592000 for 30 days, or press Enter/[b]ack to go back): ",
-        ).map_err(navigation_to_anyhow)?;
-        input
-            .parse::<u64>()
-            .context("Failed to parse dissolve delay - must be a number")?
+        print_info(&format!("Neuron ID: {}", neuron_id));
+        DissolveDelayPresets::icp()
+            .prompt("additional dissolve delay")
+            .await?
     };
 
+    // Stay within the maximum rather than have governance reject the increase, whether the
+    // neuron was given or picked from the list
+    let neuron = get_icp_neuron_default_path(Some(neuron_id), Some(principal))
+        .await
+        .context("Failed to get ICP neuron")?;
+    let additional_dissolve_delay_seconds = DissolveDelayPresets::icp().clamp_increase(
+        neuron.dissolve(),
+        additional_dissolve_delay_seconds,
+        crate::core::ops::time_ops::replica_now_secs().await,
+    )?;
+
     print_header("Increasing Dissolve Delay");
     print_info(&format!("Principal: {}", principal));
-    if let Some(id) = neuron_id {
-        print_info(&format!("Neuron ID: {}", id));
-    }
+    print_info(&format!("Neuron ID: {}", neuron_id));
     let days = additional_dissolve_delay_seconds / 86400;
     let hours = (additional_dissolve_delay_seconds % 86400) / 3600;
     print_info(&format!(
@@ -2960,7 +7257,7 @@ pub async fn handle_increase_icp_dissolve_delay(args: &[String]) -> Result<()> {
 
     increase_icp_dissolve_delay_for_principal_default_path(
         principal,
-        neuron_id,
+        Some(neuron_id),
         additional_dissolve_delay_seconds,
     )
     .await
@@ -2970,15 +7267,92 @@ pub async fn handle_increase_icp_dissolve_delay(args: &[String]) -> Result<()> {
     Ok(())
 }
 
+/// Handle refresh-icp-neuron command
+/// Usage: refresh-icp-neuron [principal] [neuron_id] [amount_e8s] [--from-subaccount <hex>]
+pub async fn handle_refresh_icp_neuron(args: &[String]) -> Result<()> {
+    use crate::core::ops::governance_ops::refresh_icp_neuron_default_path;
+
+    let mut args = args.to_vec();
+    let amount = take_amount(&mut args, 4)?;
+    let from_subaccount = take_subaccount(&mut args, "--from-subaccount")?;
+
+    // Step 1: Get principal (select participant if not provided)
+    let principal = if args.len() >= 3 {
+        resolve_principal(&args[2]).context("Failed to parse principal")?
+    } else {
+        select_participant_or_custom().await?
+    };
+
+    // Step 2: Get neuron ID (select if not provided)
+    let neuron_id = if args.len() >= 4 {
+        args[3]
+            .parse::<u64>()
+            .context("Failed to parse neuron_id")?
+    } else {
+        match select_icp_neuron(principal).await {
+            Ok(id) => id,
+            Err(e) if is_user_cancelled_error(&e) || is_user_went_back_error(&e) => return Ok(()),
+            Err(e) => return Err(e),
+        }
+    };
+
+    // Step 3: Get amount (interactive if not provided)
+    let ledger_canister = crate::core::utils::network::network_config().ledger_canister;
+    let spendable = account_spendable(false, principal, from_subaccount.clone()).await?;
+    let decimals = app_context().ledger_decimals(ledger_canister).await?;
+    let amount = match amount {
+        Some(amount) => amount,
+        None => {
+            print_header("Refresh ICP Neuron");
+            print_info(&format!("Principal: {}", principal));
+            print_info(&format!("Neuron ID: {}", neuron_id));
+            print_spendable(&spendable, "ICP", decimals);
+            println!();
+            let input = read_input_required(
+                "Enter ICP to add (e.g., 1.5 or 2.0, 150000000e8s in e8s, or max; or press Enter/[b]ack to go back): ",
+            ).await
+            .map_err(navigation_to_anyhow)?;
+            AmountInput::from_prompt(&input)?
+        }
+    };
+    let amount_e8s = amount
+        .spend(ledger_canister, &spendable)
+        .await
+        .context(ValidationError::new("Invalid amount to add"))?;
+
+    print_header("Refreshing ICP Neuron");
+    print_info(&format!("Principal: {}", principal));
+    print_info(&format!("Neuron ID: {}", neuron_id));
+    print_info(&format!("Adding: {} e8s", amount_e8s));
+    print_balance_after(&spendable, amount_e8s, "ICP", decimals);
+    if let Some(subaccount) = &from_subaccount {
+        print_info(&format!(
+            "From subaccount: {}",
+            short_id(&hex::encode(subaccount))
+        ));
+    }
+
+    let stake = refresh_icp_neuron_default_path(principal, neuron_id, amount_e8s, from_subaccount)
+        .await
+        .context("Failed to refresh ICP neuron")?;
+
+    print_success(&format!(
+        "Neuron refreshed! Stake: {} e8s ({:.8} ICP)",
+        stake,
+        stake as f64 / 100_000_000.0
+    ));
+    Ok(())
+}
+
 /// Handle manage-icp-dissolving command
 pub async fn handle_manage_icp_dissolving(args: &[String]) -> Result<()> {
     use crate::core::ops::governance_ops::manage_icp_dissolving_state_for_principal_default_path;
 
     // Step 1: Get principal (select participant if not provided)
     let principal = if args.len() >= 3 {
-        Principal::from_text(&args[2]).context("Failed to parse principal")?
+        resolve_principal(&args[2]).context("Failed to parse principal")?
     } else {
-        select_participant_or_custom()?
+        select_participant_or_custom().await?
     };
 
     // Step 2: Get action (start/stop) - interactive if not provided
@@ -2987,7 +7361,7 @@ pub async fn handle_manage_icp_dissolving(args: &[String]) -> Result<()> {
             "start" | "true" | "1" => true,
             "stop" | "false" | "0" => false,
             _ => {
-                anyhow::bail!("Invalid action. Use 'start' or 'stop'");
+                return Err(invalid("Invalid action. Use 'start' or 'stop'"));
             }
         }
     } else {
@@ -2997,47 +7371,71 @@ pub async fn handle_manage_icp_dissolving(args: &[String]) -> Result<()> {
         println!();
         println!("  [1] Start Dissolving");
         println!("  [2] Stop Dissolving");
-        print!("Select action [1-2]: ");
-        io::stdout().flush()?;
-
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
+        let input = read_line("Select action [1-2]: ").await?;
         match input.trim() {
             "1" => true,
             "2" => false,
-            _ => anyhow::bail!("Invalid selection - must be 1 or 2"),
+            _ => return Err(invalid("Invalid selection - must be 1 or 2")),
         }
     };
 
     // Step 3: Get neuron ID (select if not provided)
     let neuron_id = if args.len() >= 5 {
-        Some(
-            args[4]
-                .parse::<u64>()
-                .context("Failed to parse neuron_id")?,
-        )
+        args[4]
+            .parse::<u64>()
+            .context("Failed to parse neuron_id")?
     } else {
         // Interactive neuron selection
         match select_icp_neuron(principal).await {
-            Ok(id) => Some(id),
+            Ok(id) => id,
             Err(e) if is_user_cancelled_error(&e) => return Ok(()),
             Err(e) => return Err(e),
         }
     };
 
+    // Checked whether the neuron was given or picked from the list
+    let neuron = if start_dissolving {
+        Some(
+            get_icp_neuron_default_path(Some(neuron_id), Some(principal))
+                .await
+                .context("Failed to get ICP neuron")?,
+        )
+    } else {
+        None
+    };
+    if let Some(n) = &neuron {
+        DissolveDelayPresets::icp().warn_start_dissolving(n.dissolve());
+    }
+
+    if let Some(neuron) = &neuron
+        && needs_confirmation()
+    {
+        let summary = [
+            ("Principal", principal.to_string()),
+            ("Neuron ID", neuron_id.to_string()),
+            ("Stake", icp_e8s_display(neuron.cached_neuron_stake_e8s)),
+            ("Dissolve state", neuron.dissolve().summary()),
+        ];
+        if !confirm_action("start dissolving this ICP neuron", &summary).await? {
+            return Err(Cancelled.into());
+        }
+    }
+
     print_header(if start_dissolving {
         "Starting Dissolving"
     } else {
         "Stopping Dissolving"
     });
     print_info(&format!("Principal: {}", principal));
-    if let Some(id) = neuron_id {
-        print_info(&format!("Neuron ID: {}", id));
-    }
+    print_info(&format!("Neuron ID: {}", neuron_id));
 
-    manage_icp_dissolving_state_for_principal_default_path(principal, neuron_id, start_dissolving)
-        .await
-        .context("Failed to manage dissolving state")?;
+    manage_icp_dissolving_state_for_principal_default_path(
+        principal,
+        Some(neuron_id),
+        start_dissolving,
+    )
+    .await
+    .context("Failed to manage dissolving state")?;
 
     print_success(if start_dissolving {
         "Dissolving started successfully!"
@@ -3046,3 +7444,521 @@ pub async fn handle_manage_icp_dissolving(args: &[String]) -> Result<()> {
     });
     Ok(())
 }
+
+/// Handle identity command (new / list / show)
+pub async fn handle_identity(args: &[String]) -> Result<()> {
+    // Step 1: Get subcommand - interactive if not provided
+    let subcommand = if args.len() >= 3 {
+        args[2].to_lowercase()
+    } else {
+        print_header("Manage Identities");
+        println!("  [1] New identity");
+        println!("  [2] List identities");
+        println!("  [3] Show identity");
+        println!("  [4] Import wallet identity");
+        println!("  [5] Add delegated principal");
+        println!("  [6] List registered keys");
+        println!();
+        match read_input_required("Select action [1-6] (or press Enter/[b]ack to go back): ").await
+        {
+            Ok(input) => match input.as_str() {
+                "1" | "new" => "new".to_string(),
+                "2" | "list" => "list".to_string(),
+                "3" | "show" => "show".to_string(),
+                "4" | "import" => "import".to_string(),
+                "5" | "add-delegated" => "add-delegated".to_string(),
+                "6" | "keys" => "keys".to_string(),
+                _ => return Err(invalid("Invalid selection - must be 1-6")),
+            },
+            Err(_) => return Ok(()),
+        }
+    };
+
+    match subcommand.as_str() {
+        "new" => {
+            let name = if args.len() >= 4 {
+                Some(args[3].clone())
+            } else if args.len() >= 3 {
+                None
+            } else {
+                read_input_optional("Enter identity name (or press Enter for identity-N): ")
+                    .await
+                    .map_err(navigation_to_anyhow)?
+            };
+            identity_new(name.as_deref())
+        }
+        "list" => identity_list(),
+        "show" => {
+            let query = if args.len() >= 4 {
+                args[3].clone()
+            } else {
+                identity_list()?;
+                println!();
+                match read_input_required("Enter identity name or principal: ").await {
+                    Ok(input) => input,
+                    Err(_) => return Ok(()),
+                }
+            };
+            identity_show(&query)
+        }
+        "import" => identity_import(args).await,
+        "add-delegated" => identity_add_delegated(args).await,
+        "keys" => identity_keys(),
+        "add-key" => identity_add_key(args).await,
+        "forget-key" => identity_forget_key(args).await,
+        other => Err(invalid(format!(
+            "Unknown identity subcommand '{other}'. Use new, list, show, import, add-delegated, keys, add-key or forget-key"
+        ))),
+    }
+}
+
+/// Create a new extra identity
+fn identity_new(name: Option<&str>) -> Result<()> {
+    use crate::core::ops::identity::create_named_identity;
+
+    print_header("New Identity");
+    let identity = create_named_identity(name)?;
+
+    print_success(&format!("Created identity '{}'", identity.name));
+    print_info(&format!("Principal: {}", identity.principal));
+    print_info(&format!("Seed file: {}", identity.seed_file));
+    print_info("The identity is now selectable in principal menus");
+    Ok(())
+}
+
+/// List the key files registered for principals outside the deployment data
+fn identity_keys() -> Result<()> {
+    use crate::core::utils::key_registry::KeyRegistry;
+
+    let registry = KeyRegistry::load()?;
+    print_header("Registered Keys");
+    if registry.keys.is_empty() {
+        print_info(
+            "No registered keys yet. Register one with `identity add-key <file>`, or pass --key-file when signing as a principal without a known key",
+        );
+        return Ok(());
+    }
+    let mut table = Table::new(&["Principal", "Key file"]);
+    for entry in &registry.keys {
+        table.add_row(vec![entry.principal.clone(), entry.key_file.clone()]);
+    }
+    table.print();
+    print_info(&format!("Registry: {}", KeyRegistry::path().display()));
+    Ok(())
+}
+
+/// Register a PEM or seed file as the key of its principal
+/// Usage: identity add-key <file>
+async fn identity_add_key(args: &[String]) -> Result<()> {
+    use crate::core::ops::identity::add_key_file;
+    use std::path::PathBuf;
+
+    const USAGE: &str = "Usage: identity add-key <file>";
+
+    if let Some(extra) = args.get(4) {
+        return Err(invalid(format!(
+            "Unknown argument for identity add-key: {extra}\n{USAGE}"
+        )));
+    }
+    let path = match args.get(3) {
+        Some(path) => PathBuf::from(path),
+        None => match read_input_required("Enter the path of a PEM or seed file: ").await {
+            Ok(input) => PathBuf::from(input),
+            Err(_) => return Ok(()),
+        },
+    };
+    let principal = add_key_file(&path)?;
+    print_success(&format!("Commands signing as {principal} now use this key"));
+    Ok(())
+}
+
+/// Forget the registered key file of a principal
+/// Usage: identity forget-key <principal>
+async fn identity_forget_key(args: &[String]) -> Result<()> {
+    use crate::core::ops::identity::forget_key_file;
+
+    const USAGE: &str = "Usage: identity forget-key <principal>";
+
+    if let Some(extra) = args.get(4) {
+        return Err(invalid(format!(
+            "Unknown argument for identity forget-key: {extra}\n{USAGE}"
+        )));
+    }
+    let principal = match args.get(3) {
+        Some(input) => resolve_principal(input)?,
+        None => match read_input_required("Enter principal: ").await {
+            Ok(input) => resolve_principal(&input)?,
+            Err(_) => return Ok(()),
+        },
+    };
+    if forget_key_file(principal)? {
+        print_success(&format!("Forgot the key file of {principal}"));
+    } else {
+        print_info(&format!("No key file is registered for {principal}"));
+    }
+    Ok(())
+}
+
+/// Import an externally generated wallet identity
+/// Usage: identity import [name] --pem <file> | --mnemonic "<words>" [--as-participant]
+async fn identity_import(args: &[String]) -> Result<()> {
+    use crate::core::ops::identity::{ImportedKey, import_identity};
+    use crate::core::utils::cli_args::{take_flag, take_switch};
+    use std::path::PathBuf;
+
+    const USAGE: &str =
+        "Usage: identity import [name] --pem <file> | --mnemonic \"<words>\" [--as-participant]";
+
+    let mut args = args.to_vec();
+    let pem = take_flag(&mut args, "--pem")?.map(PathBuf::from);
+    let mnemonic = take_flag(&mut args, "--mnemonic")?;
+    let as_participant = take_switch(&mut args, "--as-participant");
+    let name = args.get(3).cloned();
+    if args.len() > 4 {
+        return Err(invalid(format!(
+            "Unknown argument for identity import: {}\n{USAGE}",
+            args[4]
+        )));
+    }
+
+    let (pem, mnemonic) = match (pem, mnemonic) {
+        (None, None) if args.len() < 4 && !as_participant => {
+            let input = match read_input_required("Enter a PEM file path or seed phrase: ").await {
+                Ok(input) => input,
+                Err(_) => return Ok(()),
+            };
+            if input.split_whitespace().count() > 1 {
+                (None, Some(input))
+            } else {
+                (Some(PathBuf::from(input)), None)
+            }
+        }
+        (pem, mnemonic) => (pem, mnemonic),
+    };
+    let key = match (&pem, &mnemonic) {
+        (Some(path), None) => ImportedKey::Pem(path),
+        (None, Some(phrase)) => ImportedKey::Mnemonic(phrase),
+        _ => {
+            return Err(invalid(format!(
+                "Give exactly one of --pem or --mnemonic\n{USAGE}"
+            )));
+        }
+    };
+
+    print_header("Import Identity");
+    let identity = import_identity(name.as_deref(), key, as_participant)?;
+
+    print_success(&format!("Imported identity '{}'", identity.name));
+    print_info(&format!("Principal: {}", identity.principal));
+    print_info(&format!("Key file: {}", identity.seed_file));
+    if mnemonic.is_some() {
+        print_info(
+            "Derived at m/44'/223'/0'/0/0; check that the principal matches the one your wallet shows",
+        );
+    }
+    if as_participant {
+        print_info("Registered as a swap participant (it did not take part in the swap)");
+    } else {
+        print_info("The identity is now selectable in principal menus");
+    }
+    Ok(())
+}
+
+/// Record a delegated principal, e.g. the Internet Identity principal a dapp shows
+/// Usage: identity add-delegated <name> <principal> [--origin <url>]
+async fn identity_add_delegated(args: &[String]) -> Result<()> {
+    use crate::core::ops::identity::add_delegated_principal;
+    use crate::core::utils::cli_args::take_flag;
+
+    const USAGE: &str = "Usage: identity add-delegated <name> <principal> [--origin <url>]";
+
+    let mut args = args.to_vec();
+    let origin = take_flag(&mut args, "--origin")?;
+    if args.len() > 5 {
+        return Err(invalid(format!(
+            "Unknown argument for identity add-delegated: {}\n{USAGE}",
+            args[5]
+        )));
+    }
+    let name = match args.get(3) {
+        Some(name) => name.clone(),
+        None => match read_input_required("Enter a name for the principal: ").await {
+            Ok(input) => input,
+            Err(_) => return Ok(()),
+        },
+    };
+    let principal = match args.get(4) {
+        Some(principal) => principal.clone(),
+        None => match read_input_required("Enter the principal the dapp shows: ").await {
+            Ok(input) => input,
+            Err(_) => return Ok(()),
+        },
+    };
+    let principal = Principal::from_text(principal.trim()).context("Failed to parse principal")?;
+
+    print_header("Add Delegated Principal");
+    let delegated = add_delegated_principal(&name, principal, origin)?;
+
+    print_success(&format!(
+        "Recorded delegated principal '{}'",
+        delegated.name
+    ));
+    print_info(&format!("Principal: {}", delegated.principal));
+    if let Some(origin) = &delegated.origin {
+        print_info(&format!("Origin: {origin}"));
+    }
+    print_info(&format!(
+        "Use '{}' as a hotkey (add-hotkey) or recipient (transfer, mint-sns-tokens), then see what it can do with: check-principal {}",
+        delegated.name, delegated.name
+    ));
+    Ok(())
+}
+
+/// List the owner, swap participants and extra identities
+fn identity_list() -> Result<()> {
+    use crate::core::utils::data_output;
+
+    let data = data_output::read_data()?;
+
+    print_header("Identities");
+    let aliases = |principal: &str| data.aliases_of(principal).unwrap_or_default();
+    let mut table = Table::new(&["Kind", "Name", "Principal", "Aliases"]);
+    table.add_row(vec![
+        "owner".to_string(),
+        "default (dfx)".to_string(),
+        data.owner_principal.to_string(),
+        aliases(&data.owner_principal),
+    ]);
+    for (i, participant) in data.participants.iter().enumerate() {
+        table.add_row(vec![
+            "participant".to_string(),
+            format!("participant-{}", i + 1),
+            participant.principal.to_string(),
+            aliases(&participant.principal),
+        ]);
+    }
+    for identity in &data.identities {
+        table.add_row(vec![
+            "identity".to_string(),
+            identity.name.clone(),
+            identity.principal.to_string(),
+            aliases(&identity.principal),
+        ]);
+    }
+    for delegated in &data.delegated {
+        table.add_row(vec![
+            "delegated".to_string(),
+            delegated.name.clone(),
+            delegated.principal.to_string(),
+            aliases(&delegated.principal),
+        ]);
+    }
+    table.print();
+
+    if data.identities.is_empty() {
+        print_info("No extra identities yet. Create one with: identity new [name]");
+    }
+    Ok(())
+}
+
+/// Handle alias command
+/// Usage: alias [list] | alias set <name> <principal> | alias remove <name>
+pub async fn handle_alias(args: &[String]) -> Result<()> {
+    use crate::core::ops::identity::{remove_alias, set_alias};
+    use crate::core::utils::data_output;
+
+    const USAGE: &str = "Usage: alias [list] | alias set <name> <principal> | alias remove <name>";
+
+    let subcommand = args.get(2).map(|s| s.to_lowercase());
+    let expected_args = match subcommand.as_deref() {
+        None | Some("list") => 3,
+        Some("set") => 5,
+        _ => 4,
+    };
+    if let Some(extra) = args.get(expected_args) {
+        return Err(invalid(format!(
+            "Unknown argument for alias: {extra}\n{USAGE}"
+        )));
+    }
+
+    match subcommand.as_deref() {
+        None | Some("list") => {
+            let data = data_output::read_data()?;
+            print_header("Aliases");
+            if data.aliases.is_empty() {
+                print_info("No aliases yet. Add one with: alias set <name> <principal>");
+                return Ok(());
+            }
+            let mut table = Table::new(&["Alias", "Principal"]);
+            for alias in &data.aliases {
+                table.add_row(vec![alias.name.clone(), alias.principal.clone()]);
+            }
+            table.print();
+            Ok(())
+        }
+        Some("set") => {
+            let (Some(name), Some(target)) = (args.get(3), args.get(4)) else {
+                return Err(invalid(format!(
+                    "alias set needs a name and a principal\n{USAGE}"
+                )));
+            };
+            let principal = resolve_principal(target).context("Failed to parse principal")?;
+            let alias = set_alias(name, principal)?;
+            print_success(&format!(
+                "'{}' now stands for {}",
+                alias.name, alias.principal
+            ));
+            Ok(())
+        }
+        Some("remove") => {
+            let Some(name) = args.get(3) else {
+                return Err(invalid(format!("alias remove needs a name\n{USAGE}")));
+            };
+            let alias = remove_alias(name)?;
+            print_success(&format!(
+                "Removed alias '{}' ({})",
+                alias.name, alias.principal
+            ));
+            Ok(())
+        }
+        Some(other) => Err(invalid(format!(
+            "Unknown alias subcommand '{other}'. Use list, set or remove\n{USAGE}"
+        ))),
+    }
+}
+
+/// Show details of an extra identity
+fn identity_show(query: &str) -> Result<()> {
+    use crate::core::ops::identity::find_named_identity;
+
+    let identity = find_named_identity(query)?;
+    let principal =
+        Principal::from_text(&identity.principal).context("Failed to parse identity principal")?;
+    let account_id =
+        ic_ledger_types::AccountIdentifier::new(&principal, &ic_ledger_types::DEFAULT_SUBACCOUNT);
+
+    print_header(&format!("Identity: {}", identity.name));
+    print_info(&format!("Principal: {}", identity.principal));
+    print_info(&format!("ICP account ID: {}", account_id));
+    print_info(&format!("Seed file: {}", identity.seed_file));
+    Ok(())
+}
+
+/// Handle set-sns-neuron-permissions command
+pub async fn handle_set_sns_neuron_permissions(args: &[String]) -> Result<()> {
+    use crate::core::ops::sns_governance_ops::{
+        SNS_NEURON_PERMISSIONS, list_neurons_for_principal_default_path, neuron_permissions_of,
+        parse_sns_permission_list, set_neuron_permissions_default_path, sns_permission_name,
+    };
+    use crate::core::utils::cli_args::take_flag;
+
+    let mut args = args.to_vec();
+    let permissions_arg = take_flag(&mut args, "--permissions")?;
+
+    // Step 1: Get controller principal (select participant if not provided)
+    let controller = if args.len() >= 3 {
+        resolve_principal(&args[2]).context("Failed to parse principal")?
+    } else {
+        match select_participant_with_back_handling(None, Some("sns")).await {
+            Ok(p) => p,
+            Err(e) if is_user_went_back_error(&e) => return Ok(()),
+            Err(e) => return Err(e),
+        }
+    };
+
+    // Step 2: Get neuron ID (select if not provided)
+    let neuron_id = if args.len() >= 4 {
+        parse_sns_neuron_id(controller, &args[3]).await?
+    } else {
+        match select_neuron(controller).await {
+            Ok(id) => id,
+            Err(e) if is_user_cancelled_error(&e) => return Ok(()),
+            Err(e) => return Err(e),
+        }
+    };
+
+    // Step 3: Get the principal whose permissions to set
+    let target = if args.len() >= 5 {
+        resolve_principal(&args[4]).context("Failed to parse target principal")?
+    } else {
+        match select_participant_with_back_handling(
+            Some("Select the principal whose permissions to set:"),
+            None,
+        )
+        .await
+        {
+            Ok(p) => p,
+            Err(e) if is_user_went_back_error(&e) => return Ok(()),
+            Err(e) => return Err(e),
+        }
+    };
+
+    // Step 4: Get the desired permission set (interactive if not provided)
+    let desired = if let Some(list) = permissions_arg {
+        parse_sns_permission_list(&list)?
+    } else {
+        let neurons = list_neurons_for_principal_default_path(controller)
+            .await
+            .context("Failed to list neurons")?;
+        let current = neurons
+            .iter()
+            .find(|n| n.id.as_ref().is_some_and(|id| id.id == neuron_id))
+            .map(|n| neuron_permissions_of(n, target))
+            .unwrap_or_default();
+
+        print_header("Neuron Permissions");
+        print_info(&format!("Principal: {}", target));
+        println!();
+        for (value, name) in SNS_NEURON_PERMISSIONS {
+            let mark = if current.contains(value) { "x" } else { " " };
+            println!("  [{}] {:>2}  {}", mark, value, name);
+        }
+        println!();
+        let input = read_input_optional(
+            "Enter the final permission set (comma-separated numbers or names, 'none' for no permissions, Enter to keep current): ",
+        ).await
+        .map_err(navigation_to_anyhow)?;
+        match input {
+            Some(list) => parse_sns_permission_list(&list)?,
+            None => {
+                print_info("Permissions unchanged");
+                return Ok(());
+            }
+        }
+    };
+
+    let names = |permissions: &[i32]| -> String {
+        if permissions.is_empty() {
+            "None".to_string()
+        } else {
+            permissions
+                .iter()
+                .map(|p| sns_permission_name(*p))
+                .collect::<Vec<_>>()
+                .join(", ")
+        }
+    };
+
+    print_header("Setting SNS Neuron Permissions");
+    print_info(&format!("Controller: {}", controller));
+    print_info(&format!("Neuron ID: {}", hex::encode(&neuron_id)));
+    print_info(&format!("Principal: {}", target));
+    print_info(&format!("Permissions: {}", names(&desired)));
+
+    let change = set_neuron_permissions_default_path(controller, neuron_id, target, desired)
+        .await
+        .context("Failed to set SNS neuron permissions")?;
+
+    if change.added.is_empty() && change.removed.is_empty() {
+        print_success("Permissions already match - nothing to change");
+    } else {
+        if !change.added.is_empty() {
+            print_info(&format!("Added: {}", names(&change.added)));
+        }
+        if !change.removed.is_empty() {
+            print_info(&format!("Removed: {}", names(&change.removed)));
+        }
+        print_success("Permissions updated successfully!");
+    }
+    Ok(())
+}
@@ -0,0 +1,123 @@
+// Cycles ledger operations (the ICRC ledger of cycles that dfx's `dfx cycles` commands use)
+
+use super::canister_call;
+use super::canister_client::CanisterClient;
+use crate::core::utils::constants::{CYCLES_LEDGER_CANISTER, CYCLES_LEDGER_FEE};
+use anyhow::{Context, Result};
+use candid::{CandidType, Decode, Nat, Principal, encode_args};
+use serde::Deserialize;
+
+use super::super::declarations::sns_ledger::Account;
+
+#[derive(CandidType, Deserialize, Debug)]
+struct WithdrawArgs {
+    amount: Nat,
+    from_subaccount: Option<Vec<u8>>,
+    to: Principal,
+    created_at_time: Option<u64>,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+enum RejectionCode {
+    NoError,
+    CanisterError,
+    SysTransient,
+    DestinationInvalid,
+    Unknown,
+    SysFatal,
+    CanisterReject,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+enum WithdrawError {
+    GenericError {
+        message: String,
+        error_code: Nat,
+    },
+    TemporarilyUnavailable,
+    FailedToWithdraw {
+        fee_block: Option<Nat>,
+        rejection_code: RejectionCode,
+        rejection_reason: String,
+    },
+    Duplicate {
+        duplicate_of: Nat,
+    },
+    BadFee {
+        expected_fee: Nat,
+    },
+    InvalidReceiver {
+        receiver: Principal,
+    },
+    CreatedInFuture {
+        ledger_time: u64,
+    },
+    TooOld,
+    InsufficientFunds {
+        balance: Nat,
+    },
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+enum WithdrawResult {
+    Ok(Nat),
+    Err(WithdrawError),
+}
+
+fn cycles_ledger() -> Result<Principal> {
+    Principal::from_text(CYCLES_LEDGER_CANISTER)
+        .context("Failed to parse cycles ledger canister ID")
+}
+
+/// Cycles a principal holds on the cycles ledger (its default account)
+pub async fn get_cycles_balance(agent: &dyn CanisterClient, owner: Principal) -> Result<u128> {
+    let account = Account {
+        owner,
+        subaccount: None,
+    };
+
+    let result_bytes = canister_call::query(
+        agent,
+        &cycles_ledger()?,
+        "icrc1_balance_of",
+        encode_args((account,))?,
+    )
+    .await
+    .context(
+        "Failed to read the cycles ledger balance; is the cycles ledger deployed on this network?",
+    )?;
+
+    let balance: Nat = Decode!(&result_bytes, Nat).context("Failed to decode cycles balance")?;
+    u128::try_from(balance.0).context("Cycles balance does not fit in u128")
+}
+
+/// Send cycles from the caller's cycles ledger account to a canister; returns the block index
+/// The ledger charges [`CYCLES_LEDGER_FEE`] on top of `amount`.
+pub async fn withdraw_cycles(
+    agent: &dyn CanisterClient,
+    canister: Principal,
+    amount: u128,
+) -> Result<Nat> {
+    let args = WithdrawArgs {
+        amount: Nat::from(amount),
+        from_subaccount: None,
+        to: canister,
+        created_at_time: None,
+    };
+
+    let result_bytes =
+        canister_call::update(agent, &cycles_ledger()?, "withdraw", encode_args((args,))?)
+            .await
+            .with_context(|| format!("Failed to send cycles to {canister}"))?;
+
+    match Decode!(&result_bytes, WithdrawResult).context("Failed to decode withdraw result")? {
+        WithdrawResult::Ok(block) => Ok(block),
+        WithdrawResult::Err(WithdrawError::InsufficientFunds { balance }) => anyhow::bail!(
+            "Not enough cycles on the cycles ledger: balance {balance}, needed {} (including the {CYCLES_LEDGER_FEE} fee)",
+            amount.saturating_add(CYCLES_LEDGER_FEE)
+        ),
+        WithdrawResult::Err(err) => {
+            anyhow::bail!("Cycles ledger refused to send cycles to {canister}: {err:?}")
+        }
+    }
+}
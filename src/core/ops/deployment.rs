@@ -2,6 +2,7 @@
 
 use anyhow::{Context, Result};
 use candid::{Decode, Principal, encode_args};
+use futures::StreamExt;
 use hex;
 use ic_agent::Agent;
 use sha2::Digest;
@@ -9,17 +10,35 @@ use std::time::Duration as StdDuration;
 
 use crate::core::declarations::icp_ledger::Account as LedgerAccount;
 use crate::core::declarations::sns_swap::GetLifecycleResponse;
-use crate::core::ops::governance_ops::{claim_neuron, create_sns_proposal, set_dissolve_delay, set_neuron_visibility};
+use crate::core::ops::canister_call;
+use crate::core::ops::governance_ops::{
+    claim_neuron, create_sns_proposal, set_dissolve_delay, set_neuron_visibility,
+};
 use crate::core::ops::identity::{create_agent, load_dfx_identity, load_minting_identity};
-use crate::core::ops::ledger_ops::{generate_subaccount_by_nonce, transfer_icp};
+use crate::core::ops::ledger_ops::{
+    generate_subaccount_by_nonce, transfer_icp, wait_for_icp_balance,
+};
+use crate::core::ops::preflight_ops::ensure_preflight;
 use crate::core::ops::snsw_ops::get_deployed_sns;
 use crate::core::ops::swap_ops::{
     create_sale_ticket, finalize_swap, generate_participant_subaccount, get_derived_state,
     get_swap_lifecycle, refresh_buyer_tokens,
 };
+use crate::core::ops::verify_ops::{print_checks, verify_distribution};
 use crate::core::utils::{print_header, print_info, print_step, print_success, print_warning};
 
 use crate::core::utils::constants::*;
+use crate::core::utils::data_output::{
+    DeploymentCheckpoint, ParticipantData, clear_checkpoint, get_checkpoint_path, read_checkpoint,
+    write_checkpoint,
+};
+use crate::core::utils::interrupt::CriticalSection;
+use crate::core::utils::network::network_config;
+use crate::core::utils::stages::StageTracker;
+use crate::init::sns_config::SnsConfig;
+
+/// Stages `deploy_sns` reports progress and timing for
+const DEPLOY_STAGES: usize = 9;
 
 pub struct DeploymentContext {
     pub agent: Agent,
@@ -28,10 +47,11 @@ pub struct DeploymentContext {
     pub governance_canister: Principal,
     pub ledger_canister: Principal,
     pub snsw_canister: Principal,
+    pub config: SnsConfig,
 }
 
 /// Initialize deployment context (load identities, create agents, parse canisters)
-pub async fn initialize_deployment_context() -> Result<DeploymentContext> {
+pub async fn initialize_deployment_context(config: SnsConfig) -> Result<DeploymentContext> {
     print_step("Loading dfx identity...");
     let identity = load_dfx_identity(None)
         .context("Failed to load dfx identity. Make sure dfx is configured.")?;
@@ -41,14 +61,12 @@ pub async fn initialize_deployment_context() -> Result<DeploymentContext> {
     let agent = create_agent(identity).await?;
     print_success("Agent created");
 
-    print_step("Parsing canister principals...");
-    let governance_canister = Principal::from_text(GOVERNANCE_CANISTER)
-        .context("Failed to parse GOVERNANCE_CANISTER principal")?;
-    let ledger_canister = Principal::from_text(LEDGER_CANISTER)
-        .context("Failed to parse LEDGER_CANISTER principal")?;
-    let snsw_canister =
-        Principal::from_text(SNSW_CANISTER).context("Failed to parse SNSW_CANISTER principal")?;
-    print_success("Canister principals parsed");
+    // NNS canister IDs come from the network config
+    let network = network_config();
+    let governance_canister = network.governance_canister;
+    let ledger_canister = network.ledger_canister;
+    let snsw_canister = network.snsw_canister;
+    print_info(&format!("Replica: {}", network.replica_url));
 
     print_step("Getting owner principal...");
     let owner_principal = agent
@@ -72,6 +90,7 @@ pub async fn initialize_deployment_context() -> Result<DeploymentContext> {
         governance_canister,
         ledger_canister,
         snsw_canister,
+        config,
     })
 }
 
@@ -115,8 +134,16 @@ pub async fn create_icp_neuron(ctx: &DeploymentContext) -> Result<u64> {
     .context("Failed to transfer ICP to governance subaccount")?;
     print_success("ICP transferred to governance subaccount");
 
-    // Wait a bit for the transfer to settle
-    tokio::time::sleep(StdDuration::from_secs(2)).await;
+    // Wait for the transfer to land before claiming
+    wait_for_icp_balance(
+        &ctx.agent,
+        ctx.ledger_canister,
+        ctx.governance_canister,
+        Some(subaccount.0.to_vec()),
+        DEVELOPER_ICP,
+    )
+    .await
+    .context("ICP transfer to governance subaccount did not land")?;
 
     // Claim neuron
     print_step("Claiming neuron...");
@@ -141,7 +168,7 @@ pub async fn configure_neuron(ctx: &DeploymentContext, neuron_id: u64) -> Result
     .await
     .context("Failed to set dissolve delay")?;
     print_success("Dissolve delay set");
-    
+
     print_header("Setting Neuron Visibility");
     print_step("Setting neuron visibility to public...");
     set_neuron_visibility(
@@ -153,15 +180,12 @@ pub async fn configure_neuron(ctx: &DeploymentContext, neuron_id: u64) -> Result
     .await
     .context("Failed to set neuron visibility")?;
     print_success("Neuron visibility set to public");
-    
+
     Ok(())
 }
 
-/// Create and wait for SNS proposal execution
-pub async fn create_and_wait_for_proposal(
-    ctx: &DeploymentContext,
-    neuron_id: u64,
-) -> Result<(u64, crate::core::declarations::sns_wasm::DeployedSns)> {
+/// Create the CreateServiceNervousSystem proposal
+pub async fn create_proposal(ctx: &DeploymentContext, neuron_id: u64) -> Result<u64> {
     // Create SNS Proposal
     print_header("Creating SNS Proposal");
     print_step("Creating SNS proposal...");
@@ -170,11 +194,20 @@ pub async fn create_and_wait_for_proposal(
         ctx.governance_canister,
         neuron_id,
         ctx.owner_principal,
+        &ctx.config,
     )
     .await
     .context("Failed to create SNS proposal")?;
     print_success(&format!("Proposal created with ID: {proposal_id}"));
 
+    Ok(proposal_id)
+}
+
+/// Wait for an SNS proposal to execute and return the deployed SNS canisters
+pub async fn wait_for_proposal_execution(
+    ctx: &DeploymentContext,
+    proposal_id: u64,
+) -> Result<crate::core::declarations::sns_wasm::DeployedSns> {
     // Wait for Proposal Execution
     print_header("Waiting for Proposal Execution");
     print_step(&format!("Waiting for proposal {proposal_id} to execute..."));
@@ -227,7 +260,7 @@ pub async fn create_and_wait_for_proposal(
     print_info(&format!("  Ledger: {ledger_sns}"));
     print_info(&format!("  Swap: {swap_sns}"));
 
-    Ok((proposal_id, deployed_sns))
+    Ok(deployed_sns)
 }
 
 /// Wait for swap to reach Open state (lifecycle 2) - blocking operation
@@ -242,15 +275,14 @@ pub async fn wait_for_swap_to_open(ctx: &DeploymentContext, swap_sns: Principal)
     print_info(&format!("Current swap lifecycle: {current_lifecycle}"));
 
     // Get lifecycle details to show open timestamp if available
-    let lifecycle_response = ctx
-        .agent
-        .query(&swap_sns, "get_lifecycle")
-        .with_arg(encode_args((
-            crate::core::declarations::sns_swap::GetLifecycleArg {},
-        ))?)
-        .call()
-        .await
-        .ok();
+    let lifecycle_response = canister_call::query(
+        &ctx.agent,
+        &swap_sns,
+        "get_lifecycle",
+        encode_args((crate::core::declarations::sns_swap::GetLifecycleArg {},))?,
+    )
+    .await
+    .ok();
 
     if let Some(bytes) = lifecycle_response
         && let Ok(lifecycle) = Decode!(&bytes, GetLifecycleResponse)
@@ -331,7 +363,12 @@ pub async fn create_and_participate_participant(
     participant_num: usize,
     swap_sns: Principal,
 ) -> Result<Principal> {
-    print_step(&format!("Participant {participant_num}/5"));
+    print_step(&format!(
+        "Participant {participant_num}/{}",
+        ctx.config.participants.count
+    ));
+    // Participants join concurrently, so tag each line with the participant it is about
+    let tag = |msg: &str| format!("  [{participant_num}] {msg}");
 
     // Generate a deterministic Ed25519 identity for participant
     let participant_seed = format!("sns-participant-{participant_num}");
@@ -345,10 +382,10 @@ pub async fn create_and_participate_participant(
         .join(format!("participant_{}.seed", participant_num));
     crate::core::ops::identity::save_seed_to_file(&seed, &seed_path)
         .with_context(|| format!("Failed to save participant {participant_num} seed"))?;
-    print_info(&format!(
-        "  Saved participant identity: {}",
+    print_info(&tag(&format!(
+        "Saved participant identity: {}",
         seed_path.display()
-    ));
+    )));
 
     // Create identity from the seed (Ed25519 key)
     let participant_identity = ic_agent::identity::BasicIdentity::from_raw_key(&seed);
@@ -362,11 +399,18 @@ pub async fn create_and_participate_participant(
     let participant_principal = participant_agent
         .get_principal()
         .map_err(|e| anyhow::anyhow!("Failed to get participant principal: {e}"))?;
-    print_info(&format!("  Participant principal: {participant_principal}"));
+    print_info(&tag(&format!(
+        "Participant principal: {participant_principal}"
+    )));
+
+    // Each participant can contribute a different amount (see participants.contributions_icp_e8s)
+    let contribution = ctx.config.participant_contribution_e8s(participant_num);
+    print_info(&tag(&format!("Contribution: {} e8s", contribution)));
 
     // Mint ICP for participant using minting account
-    let participant_icp_amount = PARTICIPANT_ICP + 1_000_000_000 + ICP_TRANSFER_FEE;
-    print_info(&format!("  Minting ICP for participant..."));
+    let participant_icp_amount =
+        PARTICIPANT_ICP.max(contribution) + 1_000_000_000 + ICP_TRANSFER_FEE;
+    print_info(&tag("Minting ICP for participant..."));
 
     transfer_icp(
         &ctx.minting_agent,
@@ -384,9 +428,8 @@ pub async fn create_and_participate_participant(
     let participant_subaccount = generate_participant_subaccount(participant_principal);
 
     // Create sale ticket first
-    print_info("  Creating sale ticket...");
-    const MAX_SALE_TICKET_AMOUNT: u64 = 1_000_000_000; // 10 ICP in e8s
-    let sale_ticket_amount = std::cmp::min(PARTICIPANT_ICP, MAX_SALE_TICKET_AMOUNT);
+    print_info(&tag("Creating sale ticket..."));
+    let sale_ticket_amount = contribution;
 
     let sale_ticket_created = create_sale_ticket(
         &participant_agent,
@@ -398,16 +441,20 @@ pub async fn create_and_participate_participant(
     .unwrap_or(false);
 
     if sale_ticket_created {
-        print_info("  ✓ Sale ticket created");
+        print_info(&tag("✓ Sale ticket created"));
     } else {
-        print_warning("  Sale ticket creation failed or not supported (continuing)");
+        print_warning(&tag(
+            "Sale ticket creation failed or not supported (continuing)",
+        ));
     }
 
     tokio::time::sleep(StdDuration::from_secs(1)).await;
 
     // Transfer ICP to swap canister WITH subaccount derived from participant principal
-    print_info("  Transferring ICP to swap canister (with subaccount)...");
-    let transfer_amount = PARTICIPANT_ICP + ICP_TRANSFER_FEE;
+    print_info(&tag(
+        "Transferring ICP to swap canister (with subaccount)...",
+    ));
+    let transfer_amount = contribution + ICP_TRANSFER_FEE;
 
     transfer_icp(
         &participant_agent,
@@ -422,41 +469,46 @@ pub async fn create_and_participate_participant(
     tokio::time::sleep(StdDuration::from_secs(2)).await;
 
     // Verify balance at the swap's subaccount for this participant
-    print_info("  Verifying ICP balance at swap subaccount...");
+    print_info(&tag("Verifying ICP balance at swap subaccount..."));
     let balance_args = LedgerAccount {
         owner: swap_sns,
         subaccount: Some(participant_subaccount.0.to_vec()),
     };
 
-    let balance_bytes = ctx
-        .agent
-        .query(&ctx.ledger_canister, "icrc1_balance_of")
-        .with_arg(encode_args((balance_args,))?)
-        .call()
-        .await
-        .context("Failed to check balance")?;
+    let balance_bytes = canister_call::query(
+        &ctx.agent,
+        &ctx.ledger_canister,
+        "icrc1_balance_of",
+        encode_args((balance_args,))?,
+    )
+    .await
+    .context("Failed to check balance")?;
 
     let balance: candid::Nat =
         Decode!(&balance_bytes, candid::Nat).context("Failed to decode balance")?;
 
     let balance_u64 = balance.0.to_u64_digits().first().copied().unwrap_or(0);
-    print_info(&format!(
-        "  Balance at swap subaccount (participant {}): {} e8s (transferred: {} e8s, expected after fee: {} e8s)",
-        participant_principal, balance_u64, transfer_amount, PARTICIPANT_ICP
-    ));
-
-    if balance_u64 < PARTICIPANT_ICP {
-        print_warning(&format!(
-            "  ⚠ WARNING: Balance at subaccount ({}) is less than expected participation amount ({})",
-            balance_u64, PARTICIPANT_ICP
+    print_info(&tag(&format!(
+        "Balance at swap subaccount (participant {}): {} e8s (transferred: {} e8s, expected after fee: {} e8s)",
+        participant_principal, balance_u64, transfer_amount, contribution
+    )));
+
+    if balance_u64 < contribution {
+        print_warning(&tag(&format!(
+            "⚠ WARNING: Balance at subaccount ({}) is less than expected participation amount ({})",
+            balance_u64, contribution
+        )));
+        print_warning(&tag(
+            "This may cause 'Amount transferred: 0' error during refresh_buyer_tokens",
         ));
-        print_warning("  This may cause 'Amount transferred: 0' error during refresh_buyer_tokens");
     } else {
-        print_success("  ✓ ICP balance verified at swap subaccount");
+        print_success(&tag("✓ ICP balance verified at swap subaccount"));
     }
 
     // Refresh buyer tokens - this is CRITICAL as it registers the participation in the swap
-    print_info("  Refreshing buyer tokens (this registers participation)...");
+    print_info(&tag(
+        "Refreshing buyer tokens (this registers participation)...",
+    ));
 
     let mut refresh_success = false;
 
@@ -464,23 +516,23 @@ pub async fn create_and_participate_participant(
         match refresh_buyer_tokens(&participant_agent, swap_sns, participant_principal).await {
             Ok(response) => {
                 if response.icp_accepted_participation_e8s > 0 {
-                    print_info("  ✓ Buyer tokens refreshed - participation registered!");
+                    print_info(&tag("✓ Buyer tokens refreshed - participation registered!"));
                     refresh_success = true;
                     break;
                 } else {
                     let swap_balance_seen = response.icp_ledger_account_balance_e8s;
-                    print_warning(&format!(
-                        "  ⚠ Swap accepted 0 e8s (balance it saw: {} e8s)",
+                    print_warning(&tag(&format!(
+                        "⚠ Swap accepted 0 e8s (balance it saw: {} e8s)",
                         swap_balance_seen
-                    ));
+                    )));
                     if swap_balance_seen == 0 {
-                        print_warning(
-                            "  ⚠ Swap checked a different account/subaccount than where we sent funds!",
-                        );
-                        print_warning(&format!(
-                            "  ⚠ We sent to swap + subaccount (balance: {}), but swap saw: {}",
-                            balance_u64, swap_balance_seen
+                        print_warning(&tag(
+                            "⚠ Swap checked a different account/subaccount than where we sent funds!",
                         ));
+                        print_warning(&tag(&format!(
+                            "⚠ We sent to swap + subaccount (balance: {}), but swap saw: {}",
+                            balance_u64, swap_balance_seen
+                        )));
                     }
                     if retry < 2 {
                         tokio::time::sleep(StdDuration::from_secs(3)).await;
@@ -490,32 +542,38 @@ pub async fn create_and_participate_participant(
             Err(e) => {
                 let error_msg = format!("{e}");
                 if error_msg.contains("Amount transferred: 0") {
-                    print_warning("  ⚠ Swap panicked: 'Amount transferred: 0'");
-                    print_warning("  This means the swap checked a different account/subaccount");
-                    print_warning(&format!(
-                        "  We sent {} e8s to swap + subaccount, balance we see: {} e8s",
+                    print_warning(&tag("⚠ Swap panicked: 'Amount transferred: 0'"));
+                    print_warning(&tag(
+                        "This means the swap checked a different account/subaccount",
+                    ));
+                    print_warning(&tag(&format!(
+                        "We sent {} e8s to swap + subaccount, balance we see: {} e8s",
                         transfer_amount, balance_u64
+                    )));
+                    print_warning(&tag(
+                        "The swap's subaccount derivation likely doesn't match ours!",
                     ));
-                    print_warning("  The swap's subaccount derivation likely doesn't match ours!");
                 }
                 if retry < 2 {
-                    print_warning(&format!(
-                        "  Refresh failed, retrying ({}/3): {e}",
+                    print_warning(&tag(&format!(
+                        "Refresh failed, retrying ({}/3): {e}",
                         retry + 2
-                    ));
+                    )));
                     tokio::time::sleep(StdDuration::from_secs(2)).await;
                 } else {
-                    print_warning(&format!("  ⚠ Refresh failed after retries: {e}"));
-                    print_warning("  ⚠ Participant may not be registered - check swap state");
+                    print_warning(&tag(&format!("⚠ Refresh failed after retries: {e}")));
+                    print_warning(&tag(
+                        "⚠ Participant may not be registered - check swap state",
+                    ));
                 }
             }
         }
     }
 
     if !refresh_success {
-        print_warning(
-            "  ⚠ WARNING: Buyer tokens refresh failed - participation may not be registered!",
-        );
+        print_warning(&tag(
+            "⚠ WARNING: Buyer tokens refresh failed - participation may not be registered!",
+        ));
     }
 
     print_success(&format!("Participant {participant_num} configured"));
@@ -524,22 +582,51 @@ pub async fn create_and_participate_participant(
 }
 
 /// Participate in SNS sale - create participants and have them participate
+/// Up to PARTICIPANT_CONCURRENCY participants join at once. Participants already recorded in
+/// the checkpoint are skipped, and the checkpoint is saved after each participant, in order,
+/// so an interrupted run resumes where it stopped
 pub async fn participate_in_swap(
     ctx: &DeploymentContext,
     swap_sns: Principal,
-) -> Result<Vec<Principal>> {
+    checkpoint: &mut DeploymentCheckpoint,
+) -> Result<()> {
     print_header("Participating in SNS Sale");
-    const NUM_PARTICIPANTS: usize = 5;
-    print_step(&format!("Creating {NUM_PARTICIPANTS} participants..."));
-
-    let mut participant_principals = Vec::new();
+    let num_participants = ctx.config.participants.count;
+    let completed = checkpoint.participants.len();
+    if completed > 0 {
+        print_info(&format!(
+            "{completed}/{num_participants} participants already completed (from checkpoint)"
+        ));
+    }
+    print_step(&format!(
+        "Creating {num_participants} participants ({PARTICIPANT_CONCURRENCY} at a time)..."
+    ));
 
-    for i in 1..=NUM_PARTICIPANTS {
-        let principal = create_and_participate_participant(ctx, i, swap_sns).await?;
-        participant_principals.push(principal);
+    // `buffered` yields results in participant order, keeping the checkpoint a prefix
+    let mut participants = futures::stream::iter((completed + 1)..=num_participants)
+        .map(|i| async move {
+            (
+                i,
+                create_and_participate_participant(ctx, i, swap_sns).await,
+            )
+        })
+        .buffered(PARTICIPANT_CONCURRENCY);
+    while let Some((i, principal)) = participants.next().await {
+        let principal = principal?;
+
+        // Construct path using PathBuf for cross-platform compatibility
+        let seed_path = crate::core::utils::data_output::get_output_dir()
+            .join("participants")
+            .join(format!("participant_{}.seed", i));
+        checkpoint.participants.push(ParticipantData {
+            principal: principal.to_string(),
+            seed_file: seed_path.to_string_lossy().to_string(),
+            contribution_icp_e8s: Some(ctx.config.participant_contribution_e8s(i)),
+        });
+        write_checkpoint(checkpoint)?;
     }
 
-    Ok(participant_principals)
+    Ok(())
 }
 
 /// Finalize SNS sale - check thresholds and finalize swap
@@ -554,8 +641,8 @@ pub async fn finalize_sns_sale(ctx: &DeploymentContext, swap_sns: Principal) ->
 
     let direct_participants = derived_state.direct_participant_count.unwrap_or(0);
     let direct_participation_icp = derived_state.direct_participation_icp_e8s.unwrap_or(0);
-    let min_participants = 5;
-    let min_direct_participation_icp = 100_000_000 * 5; // 5 ICP in e8s
+    let min_participants = ctx.config.swap.minimum_participants;
+    let min_direct_participation_icp = ctx.config.swap.minimum_direct_participation_icp_e8s;
 
     print_info(&format!(
         "Direct participants: {direct_participants} (minimum: {min_participants})"
@@ -657,28 +744,28 @@ pub async fn write_deployment_data(
     proposal_id: u64,
     owner_principal: Principal,
     deployed_sns: &crate::core::declarations::sns_wasm::DeployedSns,
-    participant_principals: &[Principal],
+    participants: &[ParticipantData],
 ) -> Result<()> {
     print_header("Writing Deployment Data");
     let deployment_data = crate::core::utils::data_output::SnsCreationData {
+        version: crate::core::utils::data_output::DATA_VERSION,
         icp_neuron_id: neuron_id,
         proposal_id,
         owner_principal: owner_principal.to_string(),
         deployed_sns: crate::core::utils::data_output::DeployedSnsData::from(deployed_sns),
-        participants: participant_principals
-            .iter()
-            .enumerate()
-            .map(|(i, p)| {
-                // Construct path using PathBuf for cross-platform compatibility
-                let seed_path = crate::core::utils::data_output::get_output_dir()
-                    .join("participants")
-                    .join(format!("participant_{}.seed", i + 1));
-                crate::core::utils::data_output::ParticipantData {
-                    principal: p.to_string(),
-                    seed_file: seed_path.to_string_lossy().to_string(),
-                }
-            })
-            .collect(),
+        participants: participants.to_vec(),
+        // Extra identities are deterministic, so keep them across redeployments
+        identities: crate::core::utils::data_output::read_data()
+            .map(|previous| previous.identities)
+            .unwrap_or_default(),
+        // So are delegated principals, which are derived by Internet Identity per anchor and origin
+        delegated: crate::core::utils::data_output::read_data()
+            .map(|previous| previous.delegated)
+            .unwrap_or_default(),
+        // Aliases name principals, so they stay valid as well
+        aliases: crate::core::utils::data_output::read_data()
+            .map(|previous| previous.aliases)
+            .unwrap_or_default(),
     };
 
     crate::core::utils::data_output::write_data(&deployment_data)
@@ -693,20 +780,115 @@ pub async fn write_deployment_data(
     Ok(())
 }
 
+/// Load the checkpoint left by an earlier, interrupted deployment
+/// Returns a fresh checkpoint if there is none, `fresh` is set, or it belongs to another identity
+fn load_or_start_checkpoint(
+    owner_principal: Principal,
+    fresh: bool,
+) -> Result<DeploymentCheckpoint> {
+    let new_checkpoint = DeploymentCheckpoint {
+        owner_principal: owner_principal.to_string(),
+        ..Default::default()
+    };
+
+    if fresh {
+        clear_checkpoint()?;
+        return Ok(new_checkpoint);
+    }
+
+    match read_checkpoint()? {
+        Some(checkpoint) if checkpoint.owner_principal == owner_principal.to_string() => {
+            print_header("Resuming Deployment");
+            print_info(&format!(
+                "Found checkpoint: {}",
+                get_checkpoint_path().display()
+            ));
+            print_info("Completed stages are skipped. Use --fresh to start over.");
+            Ok(checkpoint)
+        }
+        Some(_) => {
+            print_warning("Ignoring checkpoint from a different owner identity");
+            Ok(new_checkpoint)
+        }
+        None => Ok(new_checkpoint),
+    }
+}
+
 /// Main SNS deployment function - orchestrates the complete deployment flow
-pub async fn deploy_sns() -> Result<()> {
+/// Each stage records completion in the checkpoint file, so re-running after a
+/// failure resumes from the first incomplete stage (unless `fresh` is set)
+///
+/// With `abort_swap`, fewer participants than `swap.minimum_participants` join the
+/// swap and it is left Open, so it aborts at its due timestamp (see `abort-swap`)
+pub async fn deploy_sns(mut config: SnsConfig, fresh: bool, abort_swap: bool) -> Result<()> {
     // Main SNS deployment flow
     println!("🚀 Starting SNS creation on local dfx network\n");
+    if abort_swap {
+        let below_minimum = usize::try_from(config.swap.minimum_participants.saturating_sub(1))
+            .unwrap_or(usize::MAX);
+        config.participants.count = config.participants.count.min(below_minimum);
+        print_warning(&format!(
+            "Abort mode: only {} participant(s) join the swap (minimum: {}), so it will abort",
+            config.participants.count, config.swap.minimum_participants
+        ));
+    }
+    print_info(&format!(
+        "SNS: {} ({}), {} participants",
+        config.name, config.token.symbol, config.participants.count
+    ));
+
+    // Report every problem with the environment before changing anything
+    ensure_preflight().await?;
+
+    // Ctrl+C lets the current stage finish and record its checkpoint
+    let section = CriticalSection::enter();
+    section.resume_with("run deploy-sns again to resume from the last completed stage");
+    let mut stages = StageTracker::new(DEPLOY_STAGES);
 
     // Initialize deployment context
-    let ctx = initialize_deployment_context().await?;
+    let ctx = stages
+        .run(
+            "Initialize deployment",
+            initialize_deployment_context(config),
+        )
+        .await?;
+    let mut checkpoint = load_or_start_checkpoint(ctx.owner_principal, fresh)?;
 
     // Setup minting account
-    setup_minting_account(&ctx).await?;
+    if checkpoint.minting_done {
+        stages.skip("Minting account setup", "already done");
+    } else {
+        stages
+            .run("Minting account setup", setup_minting_account(&ctx))
+            .await?;
+        checkpoint.minting_done = true;
+        write_checkpoint(&checkpoint)?;
+    }
 
     // Create and configure ICP neuron
-    let neuron_id = create_icp_neuron(&ctx).await?;
-    configure_neuron(&ctx, neuron_id).await?;
+    let neuron_id = match checkpoint.icp_neuron_id {
+        Some(id) => {
+            stages.skip("ICP neuron creation", &format!("neuron {id} exists"));
+            id
+        }
+        None => {
+            let id = stages
+                .run("ICP neuron creation", create_icp_neuron(&ctx))
+                .await?;
+            checkpoint.icp_neuron_id = Some(id);
+            write_checkpoint(&checkpoint)?;
+            id
+        }
+    };
+    if checkpoint.neuron_configured {
+        stages.skip("Neuron configuration", "already done");
+    } else {
+        stages
+            .run("Neuron configuration", configure_neuron(&ctx, neuron_id))
+            .await?;
+        checkpoint.neuron_configured = true;
+        write_checkpoint(&checkpoint)?;
+    }
 
     // Update SNS Subnet List (skipped for local)
     print_header("Updating SNS Subnet List");
@@ -715,7 +897,36 @@ pub async fn deploy_sns() -> Result<()> {
     );
 
     // Create proposal and wait for execution
-    let (proposal_id, deployed_sns) = create_and_wait_for_proposal(&ctx, neuron_id).await?;
+    let proposal_id = match checkpoint.proposal_id {
+        Some(id) => {
+            stages.skip("SNS proposal creation", &format!("proposal {id} exists"));
+            id
+        }
+        None => {
+            let id = stages
+                .run("SNS proposal creation", create_proposal(&ctx, neuron_id))
+                .await?;
+            checkpoint.proposal_id = Some(id);
+            write_checkpoint(&checkpoint)?;
+            id
+        }
+    };
+    let deployed_sns = if checkpoint.proposal_executed {
+        stages.skip("Proposal execution", "already executed");
+        get_deployed_sns(&ctx.agent, ctx.snsw_canister, proposal_id)
+            .await
+            .context("Failed to get deployed SNS")?
+    } else {
+        let deployed_sns = stages
+            .run(
+                "Proposal execution",
+                wait_for_proposal_execution(&ctx, proposal_id),
+            )
+            .await?;
+        checkpoint.proposal_executed = true;
+        write_checkpoint(&checkpoint)?;
+        deployed_sns
+    };
 
     let swap_sns = deployed_sns
         .swap_canister_id
@@ -727,26 +938,71 @@ pub async fn deploy_sns() -> Result<()> {
         .ledger_canister_id
         .ok_or_else(|| anyhow::anyhow!("Missing ledger canister ID"))?;
 
-    // Wait for swap to open
-    wait_for_swap_to_open(&ctx, swap_sns).await?;
-
-    // Participate in swap
-    let participant_principals = participate_in_swap(&ctx, swap_sns).await?;
-
-    // Finalize swap
-    finalize_sns_sale(&ctx, swap_sns).await?;
+    if checkpoint.swap_finalized {
+        stages.skip("Swap participation", "already done");
+        stages.skip("Swap finalization", "already done");
+    } else {
+        // Wait for swap to open, then participate in swap
+        let participants_left = checkpoint.participants.len() < ctx.config.participants.count;
+        stages
+            .run("Swap participation", async {
+                if participants_left {
+                    wait_for_swap_to_open(&ctx, swap_sns).await?;
+                }
+                participate_in_swap(&ctx, swap_sns, &mut checkpoint).await
+            })
+            .await?;
+
+        // Finalize swap (an aborting swap stays Open until its due timestamp)
+        if abort_swap {
+            print_header("Leaving Swap Open");
+            print_info("Participation is below the swap minimums - the swap is not finalized");
+            stages.skip("Swap finalization", "swap left open to abort");
+        } else {
+            stages
+                .run("Swap finalization", finalize_sns_sale(&ctx, swap_sns))
+                .await?;
+            checkpoint.swap_finalized = true;
+            write_checkpoint(&checkpoint)?;
+        }
+    }
 
     // Write deployment data
-    write_deployment_data(
-        neuron_id,
-        proposal_id,
-        ctx.owner_principal,
-        &deployed_sns,
-        &participant_principals,
-    )
-    .await?;
+    stages
+        .run(
+            "Deployment data",
+            write_deployment_data(
+                neuron_id,
+                proposal_id,
+                ctx.owner_principal,
+                &deployed_sns,
+                &checkpoint.participants,
+            ),
+        )
+        .await?;
+
+    // Deployment is complete - the checkpoint is no longer needed
+    clear_checkpoint()?;
+
+    // Compare the finalized distribution with the config; mismatches don't fail the deployment
+    if !abort_swap {
+        print_header("Verifying Token Distribution");
+        match verify_distribution(&ctx.config).await {
+            Ok(checks) => {
+                print_checks(&checks);
+                let failed = checks.iter().filter(|c| !c.ok).count();
+                if failed > 0 {
+                    print_warning(&format!(
+                        "{failed} mismatches in the token distribution (re-run with verify-distribution)"
+                    ));
+                }
+            }
+            Err(e) => print_warning(&format!("Failed to verify the token distribution: {e:#}")),
+        }
+    }
 
     // Final Summary
+    stages.print_summary();
     print_header("SNS Creation Complete");
     print_success("SNS has been created and deployed!");
     print_info(&format!("Governance Canister: {governance_sns}"));
@@ -761,6 +1017,11 @@ pub async fn deploy_sns() -> Result<()> {
         "💡 Deployment data has been saved to: {}",
         output_path.display()
     );
+    if abort_swap {
+        println!(
+            "💡 The swap aborts at its due timestamp (swap.duration_seconds after opening); use 'advance-time' to get there, then run 'abort-swap --watch' to finalize it and verify the refunds"
+        );
+    }
 
     Ok(())
 }
@@ -0,0 +1,205 @@
+// SNS extensions (treasury managers): register them, run their operations and list them
+
+use anyhow::{Context, Result};
+use candid::{Decode, Principal, encode_args};
+use ic_agent::Agent;
+use sha2::{Digest, Sha256};
+
+use super::canister_call;
+use super::sns_governance_ops::submit_proposal_with_all_votes_default_path;
+use crate::core::context::app_context;
+use crate::core::declarations::sns_governance::{
+    Action, ChunkedCanisterWasm, ExecuteExtensionOperation, ExtensionInit, ExtensionOperationArg,
+    ExtensionOperationType, ListTopicsRequest, ListTopicsResponse, PreciseValue, Proposal,
+    RegisterExtension,
+};
+
+/// Largest chunk the management canister's chunk store accepts
+const WASM_CHUNK_SIZE: usize = 1024 * 1024;
+
+#[derive(candid::CandidType)]
+struct UploadChunkArgs {
+    canister_id: Principal,
+    chunk: Vec<u8>,
+}
+
+#[derive(candid::CandidType, candid::Deserialize)]
+struct UploadChunkResult {
+    hash: Vec<u8>,
+}
+
+/// Upload a wasm to a canister's chunk store in 1 MiB chunks
+/// The agent must control `store_canister`. Returns the wasm as governance refers to it.
+pub async fn upload_wasm_chunks(
+    agent: &Agent,
+    store_canister: Principal,
+    wasm: &[u8],
+) -> Result<ChunkedCanisterWasm> {
+    let mut chunk_hashes_list = Vec::new();
+    for (i, chunk) in wasm.chunks(WASM_CHUNK_SIZE).enumerate() {
+        let args = UploadChunkArgs {
+            canister_id: store_canister,
+            chunk: chunk.to_vec(),
+        };
+        let response = canister_call::management_update(
+            agent,
+            &store_canister,
+            "upload_chunk",
+            encode_args((args,))?,
+        )
+        .await
+        .with_context(|| {
+            format!(
+                "Failed to upload wasm chunk {} to {}",
+                i + 1,
+                store_canister
+            )
+        })?;
+        let result = Decode!(&response, UploadChunkResult)
+            .context("Failed to decode upload_chunk response")?;
+        chunk_hashes_list.push(result.hash);
+    }
+
+    Ok(ChunkedCanisterWasm {
+        wasm_module_hash: Sha256::digest(wasm).to_vec(),
+        chunk_hashes_list,
+        store_canister_id: Some(store_canister),
+    })
+}
+
+/// Treasury manager argument: how many SNS and ICP e8s to move between the treasury and the
+/// extension (used to initialize it and for deposits)
+#[must_use]
+pub fn treasury_allocation(sns_e8s: u64, icp_e8s: u64) -> PreciseValue {
+    PreciseValue::Map(vec![
+        (
+            "treasury_allocation_sns_e8s".to_string(),
+            Box::new(PreciseValue::Nat(sns_e8s)),
+        ),
+        (
+            "treasury_allocation_icp_e8s".to_string(),
+            Box::new(PreciseValue::Nat(icp_e8s)),
+        ),
+    ])
+}
+
+/// Propose registering an extension and have every participant vote for it
+/// The wasm is uploaded to `store_canister`'s chunk store first, signed by the SNS owner, so
+/// the owner must control that canister. SNS root installs the extension once the proposal
+/// executes, initialized with `init`.
+pub async fn register_extension_default_path(
+    proposer_principal: Principal,
+    wasm: &[u8],
+    store_canister: Principal,
+    init: PreciseValue,
+) -> Result<u64> {
+    let owner = Principal::from_text(&app_context().deployment_data()?.owner_principal)
+        .context("Failed to parse owner principal")?;
+    let agent = app_context().agent_for_principal(owner).await?;
+    let chunked_canister_wasm = upload_wasm_chunks(&agent, store_canister, wasm).await?;
+
+    let proposal = Proposal {
+        url: String::new(),
+        title: "Register extension".to_string(),
+        summary: format!(
+            "Register the extension with wasm hash {} ({} chunks stored on {})",
+            hex::encode(&chunked_canister_wasm.wasm_module_hash),
+            chunked_canister_wasm.chunk_hashes_list.len(),
+            store_canister
+        ),
+        action: Some(Action::RegisterExtension(RegisterExtension {
+            chunked_canister_wasm: Some(chunked_canister_wasm),
+            extension_init: Some(ExtensionInit {
+                value: Some(Box::new(init)),
+            }),
+        })),
+    };
+    submit_proposal_with_all_votes_default_path(proposer_principal, proposal).await
+}
+
+/// Propose running an operation of a registered extension (e.g. `deposit` or `withdraw`) and
+/// have every participant vote for it
+pub async fn execute_extension_operation_default_path(
+    proposer_principal: Principal,
+    extension_canister: Principal,
+    operation_name: &str,
+    arg: Option<PreciseValue>,
+) -> Result<u64> {
+    let proposal = Proposal {
+        url: String::new(),
+        title: format!("Extension operation {}", operation_name),
+        summary: format!(
+            "Execute `{}` on extension {}",
+            operation_name, extension_canister
+        ),
+        action: Some(Action::ExecuteExtensionOperation(
+            ExecuteExtensionOperation {
+                extension_canister_id: Some(extension_canister),
+                operation_name: Some(operation_name.to_string()),
+                operation_arg: Some(ExtensionOperationArg {
+                    value: arg.map(Box::new),
+                }),
+            },
+        )),
+    };
+    submit_proposal_with_all_votes_default_path(proposer_principal, proposal).await
+}
+
+/// An operation a registered extension offers, as listed by `list_topics`
+#[derive(Debug, Clone)]
+pub struct RegisteredExtensionOperation {
+    pub canister_id: Option<Principal>,
+    /// e.g. `TreasuryManagerDeposit`
+    pub operation: String,
+    /// Name of the topic its proposals fall under
+    pub topic: String,
+    pub description: Option<String>,
+}
+
+/// Operations of every extension registered with the SNS, grouped by topic
+pub async fn list_extension_operations(
+    agent: &Agent,
+    governance_canister: Principal,
+) -> Result<Vec<RegisteredExtensionOperation>> {
+    let response = canister_call::query(
+        agent,
+        &governance_canister,
+        "list_topics",
+        encode_args((ListTopicsRequest {},))?,
+    )
+    .await
+    .context("Failed to call list_topics")?;
+    let topics = Decode!(&response, ListTopicsResponse)
+        .context("Failed to decode list_topics response")?
+        .topics
+        .unwrap_or_default();
+
+    let mut operations = Vec::new();
+    for topic in topics {
+        let topic_name = topic.name.unwrap_or_else(|| "-".to_string());
+        for registered in topic.extension_operations.unwrap_or_default() {
+            let spec = registered.spec;
+            operations.push(RegisteredExtensionOperation {
+                canister_id: registered.canister_id,
+                operation: match spec.as_ref().and_then(|s| s.operation_type.as_ref()) {
+                    Some(ExtensionOperationType::TreasuryManagerDeposit) => {
+                        "TreasuryManagerDeposit".to_string()
+                    }
+                    Some(ExtensionOperationType::TreasuryManagerWithdraw) => {
+                        "TreasuryManagerWithdraw".to_string()
+                    }
+                    None => "-".to_string(),
+                },
+                topic: topic_name.clone(),
+                description: spec.and_then(|s| s.description),
+            });
+        }
+    }
+    Ok(operations)
+}
+
+/// Operations of every extension registered with the deployed SNS
+pub async fn list_extension_operations_default_path() -> Result<Vec<RegisteredExtensionOperation>> {
+    let agent = app_context().anonymous_agent().await?;
+    list_extension_operations(&agent, app_context().sns_governance_canister()?).await
+}
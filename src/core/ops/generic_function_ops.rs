@@ -0,0 +1,180 @@
+// Custom (generic) nervous system functions: register them and execute them by proposal
+
+use crate::core::utils::exit_code::{ValidationError, invalid};
+use anyhow::{Context, Result};
+use candid::Principal;
+use serde::Deserialize;
+use std::path::Path;
+
+use super::scenario_ops::load_spec_file;
+use super::sns_governance_ops::submit_proposal_with_all_votes_default_path;
+use crate::core::declarations::sns_governance::{
+    Action, ExecuteGenericNervousSystemFunction, FunctionType, GenericNervousSystemFunction,
+    NervousSystemFunction, Proposal, Topic,
+};
+
+/// Lowest ID SNS governance accepts for a custom function (lower IDs are native proposals)
+pub const MIN_GENERIC_FUNCTION_ID: u64 = 1000;
+
+/// A custom proposal type: the method governance calls on a target canister once a
+/// proposal is adopted, and the method it calls first to validate the payload
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GenericFunctionSpec {
+    /// Function ID, 1000 or higher and unique within the SNS
+    pub id: u64,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Topic of the function's proposals, e.g. `application-business-logic`
+    #[serde(default)]
+    pub topic: Option<String>,
+    pub target_canister: String,
+    pub target_method: String,
+    /// Defaults to the target canister
+    #[serde(default)]
+    pub validator_canister: Option<String>,
+    pub validator_method: String,
+}
+
+impl GenericFunctionSpec {
+    /// Load a function spec from a YAML, TOML or JSON file
+    pub fn load(path: &Path) -> Result<Self> {
+        let spec: Self = load_spec_file(path, "function spec")?;
+        if spec.id < MIN_GENERIC_FUNCTION_ID {
+            anyhow::bail!(
+                "Function ID {} is reserved for native proposals; use {} or higher",
+                spec.id,
+                MIN_GENERIC_FUNCTION_ID
+            );
+        }
+        Ok(spec)
+    }
+
+    /// The function as SNS governance stores it
+    pub fn to_function(&self) -> Result<NervousSystemFunction> {
+        let target = Principal::from_text(&self.target_canister).with_context(|| {
+            ValidationError::new(format!(
+                "Invalid target_canister '{}'",
+                self.target_canister
+            ))
+        })?;
+        let validator = match &self.validator_canister {
+            Some(text) => Principal::from_text(text).with_context(|| {
+                ValidationError::new(format!("Invalid validator_canister '{}'", text))
+            })?,
+            None => target,
+        };
+        let topic = self.topic.as_deref().map(parse_topic).transpose()?;
+
+        Ok(NervousSystemFunction {
+            id: self.id,
+            name: self.name.clone(),
+            description: self.description.clone(),
+            function_type: Some(FunctionType::GenericNervousSystemFunction(
+                GenericNervousSystemFunction {
+                    topic,
+                    target_canister_id: Some(target),
+                    target_method_name: Some(self.target_method.clone()),
+                    validator_canister_id: Some(validator),
+                    validator_method_name: Some(self.validator_method.clone()),
+                },
+            )),
+        })
+    }
+}
+
+/// SNS proposal topic from its kebab-case or snake_case name
+fn parse_topic(name: &str) -> Result<Topic> {
+    Ok(match name.to_lowercase().replace('_', "-").as_str() {
+        "dapp-canister-management" => Topic::DappCanisterManagement,
+        "dao-community-settings" => Topic::DaoCommunitySettings,
+        "application-business-logic" => Topic::ApplicationBusinessLogic,
+        "critical-dapp-operations" => Topic::CriticalDappOperations,
+        "treasury-asset-management" => Topic::TreasuryAssetManagement,
+        "governance" => Topic::Governance,
+        "sns-framework-management" => Topic::SnsFrameworkManagement,
+        _ => {
+            return Err(invalid(format!(
+                "Unknown topic '{}'. Use dapp-canister-management, dao-community-settings, \
+             application-business-logic, critical-dapp-operations, treasury-asset-management, \
+             governance or sns-framework-management",
+                name
+            )));
+        }
+    })
+}
+
+/// Read a proposal payload: Candid-encoded bytes, or the same as hex text (as printed by
+/// `didc encode`)
+pub fn read_payload_file(path: &Path) -> Result<Vec<u8>> {
+    let bytes = std::fs::read(path)
+        .with_context(|| format!("Failed to read payload file: {}", path.display()))?;
+    if bytes.starts_with(b"DIDL") {
+        return Ok(bytes);
+    }
+    let text = std::str::from_utf8(&bytes)
+        .ok()
+        .map(|text| text.split_whitespace().collect::<String>())
+        .filter(|text| !text.is_empty())
+        .with_context(|| {
+            format!(
+                "Payload file {} is neither Candid bytes nor hex text",
+                path.display()
+            )
+        })?;
+    let payload = hex::decode(&text).with_context(|| {
+        ValidationError::new(format!("Invalid hex in payload file: {}", path.display()))
+    })?;
+    if !payload.starts_with(b"DIDL") {
+        anyhow::bail!(
+            "Payload in {} is not Candid-encoded (it must start with DIDL)",
+            path.display()
+        );
+    }
+    Ok(payload)
+}
+
+/// Propose registering a custom function and have every participant vote for it
+/// Governance adds the function once the proposal executes.
+pub async fn add_generic_function_default_path(
+    proposer_principal: Principal,
+    spec: &GenericFunctionSpec,
+) -> Result<u64> {
+    let proposal = Proposal {
+        url: String::new(),
+        title: format!("Add custom function {} ({})", spec.id, spec.name),
+        summary: format!(
+            "Register `{}` on {} (validated by `{}`) as custom function {}",
+            spec.target_method, spec.target_canister, spec.validator_method, spec.id
+        ),
+        action: Some(Action::AddGenericNervousSystemFunction(spec.to_function()?)),
+    };
+    submit_proposal_with_all_votes_default_path(proposer_principal, proposal).await
+}
+
+/// Propose executing a custom function with a Candid payload and have every participant
+/// vote for it. Governance calls the validator method when the proposal is made, so an
+/// invalid payload fails here; the target method is called once the proposal executes.
+pub async fn execute_generic_function_default_path(
+    proposer_principal: Principal,
+    function_id: u64,
+    payload: Vec<u8>,
+) -> Result<u64> {
+    let proposal = Proposal {
+        url: String::new(),
+        title: format!("Execute custom function {}", function_id),
+        summary: format!(
+            "Execute custom function {} with a {}-byte payload",
+            function_id,
+            payload.len()
+        ),
+        action: Some(Action::ExecuteGenericNervousSystemFunction(
+            ExecuteGenericNervousSystemFunction {
+                function_id,
+                payload,
+            },
+        )),
+    };
+    submit_proposal_with_all_votes_default_path(proposer_principal, proposal).await
+}
@@ -0,0 +1,212 @@
+// Governance errors with their error type named and a hint on what to do about them
+
+use crate::core::declarations::{icp_governance, sns_governance};
+
+/// Which governance canister returned an error (they number their error types differently)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Governance {
+    Nns,
+    Sns,
+}
+
+/// A `GovernanceError` from NNS or SNS governance, returned as the error of the operation
+/// that got it
+#[derive(Debug)]
+pub struct GovernanceCommandError {
+    pub governance: Governance,
+    /// What was being done, e.g. `Failed to claim neuron`
+    pub action: String,
+    pub error_type: i32,
+    pub message: String,
+}
+
+impl GovernanceCommandError {
+    /// Name of the error type, e.g. `NotAuthorized`
+    #[must_use]
+    pub const fn error_type_name(&self) -> &'static str {
+        match self.governance {
+            Governance::Nns => nns_error_type_name(self.error_type),
+            Governance::Sns => sns_error_type_name(self.error_type),
+        }
+    }
+
+    /// What to do about the error, if there is a known remedy
+    #[must_use]
+    pub fn hint(&self) -> Option<&'static str> {
+        error_hint(self.error_type_name(), &self.message)
+    }
+}
+
+impl std::fmt::Display for GovernanceCommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {} ({}, type {})",
+            self.action,
+            self.message,
+            self.error_type_name(),
+            self.error_type
+        )?;
+        if let Some(hint) = self.hint() {
+            write!(f, "\n  Hint: {hint}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for GovernanceCommandError {}
+
+/// Error for an NNS governance error returned to `action`
+#[must_use]
+pub fn nns_governance_error(
+    action: &str,
+    error: &icp_governance::GovernanceError,
+) -> anyhow::Error {
+    GovernanceCommandError {
+        governance: Governance::Nns,
+        action: action.to_string(),
+        error_type: error.error_type,
+        message: error.error_message.clone(),
+    }
+    .into()
+}
+
+/// Error for an SNS governance error returned to `action`
+#[must_use]
+pub fn sns_governance_error(
+    action: &str,
+    error: &sns_governance::GovernanceError,
+) -> anyhow::Error {
+    GovernanceCommandError {
+        governance: Governance::Sns,
+        action: action.to_string(),
+        error_type: error.error_type,
+        message: error.error_message.clone(),
+    }
+    .into()
+}
+
+/// One-line description of an SNS governance error, e.g. a proposal's failure reason
+#[must_use]
+pub fn describe_sns_governance_error(error: &sns_governance::GovernanceError) -> String {
+    format!(
+        "{} ({}, type {})",
+        error.error_message,
+        sns_error_type_name(error.error_type),
+        error.error_type
+    )
+}
+
+/// One-line description of an NNS governance error, e.g. a proposal's failure reason
+#[must_use]
+pub fn describe_nns_governance_error(error: &icp_governance::GovernanceError) -> String {
+    format!(
+        "{} ({}, type {})",
+        error.error_message,
+        nns_error_type_name(error.error_type),
+        error.error_type
+    )
+}
+
+/// Name of an NNS governance error type
+#[must_use]
+pub const fn nns_error_type_name(error_type: i32) -> &'static str {
+    match error_type {
+        1 => "Ok",
+        2 => "Unavailable",
+        3 => "NotAuthorized",
+        4 => "NotFound",
+        5 => "InvalidCommand",
+        6 => "RequiresNotDissolving",
+        7 => "RequiresDissolving",
+        8 => "RequiresDissolved",
+        9 => "HotKey",
+        10 => "ResourceExhausted",
+        11 => "PreconditionFailed",
+        12 => "External",
+        13 => "LedgerUpdateOngoing",
+        14 => "InsufficientFunds",
+        15 => "InvalidPrincipal",
+        16 => "InvalidProposal",
+        17 => "AlreadyJoinedCommunityFund",
+        18 => "NotInTheCommunityFund",
+        19 => "NeuronAlreadyVoted",
+        _ => "Unspecified",
+    }
+}
+
+/// Name of an SNS governance error type
+#[must_use]
+pub const fn sns_error_type_name(error_type: i32) -> &'static str {
+    match error_type {
+        1 => "Unavailable",
+        2 => "NotAuthorized",
+        3 => "NotFound",
+        4 => "InvalidCommand",
+        5 => "RequiresNotDissolving",
+        6 => "RequiresDissolving",
+        7 => "RequiresDissolved",
+        8 => "AccessControlList",
+        9 => "ResourceExhausted",
+        10 => "PreconditionFailed",
+        11 => "External",
+        12 => "NeuronLocked",
+        13 => "InsufficientFunds",
+        14 => "InvalidPrincipal",
+        15 => "InvalidProposal",
+        _ => "Unspecified",
+    }
+}
+
+/// Hint for an error type; the message refines a few broad types like PreconditionFailed
+fn error_hint(type_name: &str, message: &str) -> Option<&'static str> {
+    let message = message.to_lowercase();
+    let hint = match type_name {
+        "NotAuthorized" | "AccessControlList" => {
+            "The signing principal does not control the neuron or lacks the permission this \
+             needs; sign as the controller, or grant it with add-hotkey / set-sns-neuron-permissions"
+        }
+        "HotKey" => {
+            "ICP hotkeys can only vote, follow and make proposals; sign as the neuron's controller"
+        }
+        "NotFound" => {
+            "Check the neuron or proposal ID (list-sns-neurons, list-icp-neurons, show-ballots)"
+        }
+        "RequiresNotDissolving" => "Stop dissolving the neuron first (manage-*-dissolving stop)",
+        "RequiresDissolving" => "Start dissolving the neuron first (manage-*-dissolving start)",
+        "RequiresDissolved" => {
+            "The neuron must be fully dissolved: start dissolving and wait out the dissolve \
+             delay (advance-time on the PocketIC backend)"
+        }
+        "PreconditionFailed" if message.contains("vesting") => {
+            "The neuron is still vesting; wait until the vesting period ends (advance-time on \
+             the PocketIC backend)"
+        }
+        "PreconditionFailed" if message.contains("dissolve delay") => {
+            "The neuron's dissolve delay is too short; increase it with increase-*-dissolve-delay"
+        }
+        "PreconditionFailed" if message.contains("maturity") => {
+            "The neuron does not have enough maturity yet; vote on proposals to earn some"
+        }
+        "PreconditionFailed" => "A precondition of the command is not met; see the message",
+        "InsufficientFunds" => {
+            "Not enough stake or balance: use a smaller amount, or top up first (mint-icp, \
+             get-icp-balance, get-sns-balance)"
+        }
+        "NeuronLocked" | "LedgerUpdateOngoing" => {
+            "Another command on this neuron is still in progress; retry in a moment"
+        }
+        "Unavailable" | "ResourceExhausted" => {
+            "Governance is busy or rate limiting; retry in a moment"
+        }
+        "External" => {
+            "A call from governance to another canister (usually the ledger) failed; see the message"
+        }
+        "InvalidCommand" | "InvalidPrincipal" | "InvalidProposal" => {
+            "Governance rejected the arguments; check them against the message"
+        }
+        "NeuronAlreadyVoted" => "The neuron has already voted on this proposal",
+        _ => return None,
+    };
+    Some(hint)
+}
@@ -1,18 +1,28 @@
 // ICP Governance operations
 
+use super::canister_call;
+use super::canister_client::CanisterClient;
+use super::governance_errors::nns_governance_error;
+use super::identity::{identity_for_principal, signing_principal};
+use crate::core::context::app_context;
+use crate::core::utils::interrupt::{self, CriticalSection};
 use anyhow::{Context, Result};
 use candid::{Decode, Principal, encode_args};
-use ic_agent::Agent;
 
 use super::super::declarations::icp_governance::{
     AccountIdentifier, AddHotKey, Amount, By, ClaimOrRefresh, ClaimOrRefreshResponse, Command1,
     Configure, Disburse, DisburseResponse, IncreaseDissolveDelay, MakeProposalRequest,
-    MakeProposalResponse, ManageNeuronCommandRequest, ManageNeuronRequest, ManageNeuronResponse,
-    NeuronId, Operation, ProposalActionRequest, ProposalId, SetVisibility,
+    ManageNeuronCommandRequest, ManageNeuronRequest, ManageNeuronResponse, Merge, MergeResponse,
+    NeuronId, Operation, ProposalActionRequest, RemoveHotKey, SetVisibility, Spawn, SpawnResponse,
 };
+use crate::init::sns_config::SnsConfig;
 
 /// Claim neuron using manage_neuron
-pub async fn claim_neuron(agent: &Agent, governance_canister: Principal, memo: u64) -> Result<u64> {
+pub async fn claim_neuron(
+    agent: &dyn CanisterClient,
+    governance_canister: Principal,
+    memo: u64,
+) -> Result<u64> {
     let request = ManageNeuronRequest {
         id: None,
         command: Some(ManageNeuronCommandRequest::ClaimOrRefresh(ClaimOrRefresh {
@@ -21,12 +31,14 @@ pub async fn claim_neuron(agent: &Agent, governance_canister: Principal, memo: u
         neuron_id_or_subaccount: None,
     };
 
-    let result_bytes = agent
-        .update(&governance_canister, "manage_neuron")
-        .with_arg(encode_args((request,))?)
-        .call_and_wait()
-        .await
-        .context("Failed to call manage_neuron")?;
+    let result_bytes = canister_call::update(
+        agent,
+        &governance_canister,
+        "manage_neuron",
+        encode_args((request,))?,
+    )
+    .await
+    .context("Failed to call manage_neuron")?;
 
     let response: ManageNeuronResponse = Decode!(&result_bytes, ManageNeuronResponse)
         .context("Failed to decode manage_neuron response")?;
@@ -35,16 +47,47 @@ pub async fn claim_neuron(agent: &Agent, governance_canister: Principal, memo: u
         Some(Command1::ClaimOrRefresh(ClaimOrRefreshResponse {
             refreshed_neuron_id: Some(NeuronId { id }),
         })) => Ok(id),
-        Some(Command1::Error(e)) => {
-            anyhow::bail!("Failed to claim neuron: {}", e.error_message);
-        }
+        Some(Command1::Error(e)) => Err(nns_governance_error("Failed to claim neuron", &e)),
+        _ => anyhow::bail!("Unexpected response from manage_neuron"),
+    }
+}
+
+/// Refresh a neuron's stake from the balance of its account (after a top-up transfer)
+pub async fn refresh_neuron(
+    agent: &dyn CanisterClient,
+    governance_canister: Principal,
+    neuron_id: u64,
+) -> Result<()> {
+    let request = ManageNeuronRequest {
+        id: Some(NeuronId { id: neuron_id }),
+        command: Some(ManageNeuronCommandRequest::ClaimOrRefresh(ClaimOrRefresh {
+            by: Some(By::NeuronIdOrSubaccount {}),
+        })),
+        neuron_id_or_subaccount: None,
+    };
+
+    let result_bytes = canister_call::update(
+        agent,
+        &governance_canister,
+        "manage_neuron",
+        encode_args((request,))?,
+    )
+    .await
+    .context("Failed to call manage_neuron")?;
+
+    let response: ManageNeuronResponse = Decode!(&result_bytes, ManageNeuronResponse)
+        .context("Failed to decode manage_neuron response")?;
+
+    match response.command {
+        Some(Command1::ClaimOrRefresh(_)) => Ok(()),
+        Some(Command1::Error(e)) => Err(nns_governance_error("Failed to refresh neuron", &e)),
         _ => anyhow::bail!("Unexpected response from manage_neuron"),
     }
 }
 
 /// Set dissolve delay for neuron
 pub async fn set_dissolve_delay(
-    agent: &Agent,
+    agent: &dyn CanisterClient,
     governance_canister: Principal,
     neuron_id: u64,
     dissolve_delay: u64,
@@ -59,68 +102,56 @@ pub async fn set_dissolve_delay(
         neuron_id_or_subaccount: None,
     };
 
-    let result_bytes = agent
-        .update(&governance_canister, "manage_neuron")
-        .with_arg(encode_args((request,))?)
-        .call_and_wait()
-        .await
-        .context("Failed to set dissolve delay")?;
+    let result_bytes = canister_call::update(
+        agent,
+        &governance_canister,
+        "manage_neuron",
+        encode_args((request,))?,
+    )
+    .await
+    .context("Failed to set dissolve delay")?;
 
     let response: ManageNeuronResponse = Decode!(&result_bytes, ManageNeuronResponse)
         .context("Failed to decode configure response")?;
 
     match response.command {
         Some(Command1::Configure {}) => Ok(()),
-        Some(Command1::Error(e)) => {
-            anyhow::bail!("Failed to set dissolve delay: {}", e.error_message);
-        }
+        Some(Command1::Error(e)) => Err(nns_governance_error("Failed to set dissolve delay", &e)),
         _ => anyhow::bail!("Unexpected response from configure"),
     }
 }
 
 /// Create SNS proposal
 pub async fn create_sns_proposal(
-    agent: &Agent,
+    agent: &dyn CanisterClient,
     governance_canister: Principal,
     neuron_id: u64,
     owner_principal: Principal,
+    config: &SnsConfig,
 ) -> Result<u64> {
-    // Build SNS configuration from sns_config.rs
-    let sns_data = crate::init::sns_config::build_sns_config(owner_principal);
+    // Build SNS configuration from sns_config.rs (or the loaded config file)
+    let sns_data = crate::init::sns_config::build_sns_config(owner_principal, config);
 
     let proposal = MakeProposalRequest {
         url: "".to_string(),
-        title: Some(crate::init::sns_config::default_proposal_title()),
-        summary: crate::init::sns_config::default_proposal_summary(),
+        title: Some(crate::init::sns_config::proposal_title(config)),
+        summary: crate::init::sns_config::proposal_summary(config),
         action: Some(ProposalActionRequest::CreateServiceNervousSystem(sns_data)),
     };
 
-    let request = ManageNeuronRequest {
-        id: Some(NeuronId { id: neuron_id }),
-        command: Some(ManageNeuronCommandRequest::MakeProposal(proposal)),
-        neuron_id_or_subaccount: None,
-    };
-
-    let result_bytes = agent
-        .update(&governance_canister, "manage_neuron")
-        .with_arg(encode_args((request,))?)
-        .call_and_wait()
+    super::nns_proposal_ops::make_nns_proposal(agent, governance_canister, neuron_id, proposal)
         .await
-        .context("Failed to create SNS proposal")?;
-
-    let response: ManageNeuronResponse = Decode!(&result_bytes, ManageNeuronResponse)
-        .context("Failed to decode make_proposal response")?;
+        .context("Failed to create SNS proposal")
+}
 
-    match response.command {
-        Some(Command1::MakeProposal(MakeProposalResponse {
-            proposal_id: Some(ProposalId { id }),
-            ..
-        })) => Ok(id),
-        Some(Command1::Error(e)) => {
-            anyhow::bail!("Failed to create proposal: {}", e.error_message);
-        }
-        _ => anyhow::bail!("Unexpected response from make_proposal"),
-    }
+/// The `CreateServiceNervousSystem` action `create_sns_proposal` submits, as a Candid value
+pub fn sns_proposal_payload(
+    owner_principal: Principal,
+    config: &SnsConfig,
+) -> Result<candid::types::value::IDLValue> {
+    let sns_data = crate::init::sns_config::build_sns_config(owner_principal, config);
+    candid::types::value::IDLValue::try_from_candid_type(&sns_data)
+        .context("Failed to encode the CreateServiceNervousSystem payload")
 }
 
 /// Add a hotkey to an ICP neuron
@@ -128,7 +159,7 @@ pub async fn create_sns_proposal(
 /// Note: ICP neurons use a simpler API than SNS neurons - they don't have permission types,
 /// just add/remove hotkeys. The hotkey can perform any operation the controller can do.
 pub async fn add_hotkey_to_icp_neuron(
-    agent: &Agent,
+    agent: &dyn CanisterClient,
     governance_canister: Principal,
     neuron_id: u64,
     hotkey_principal: Principal,
@@ -147,20 +178,55 @@ pub async fn add_hotkey_to_icp_neuron(
         neuron_id_or_subaccount: None,
     };
 
-    let result_bytes = agent
-        .update(&governance_canister, "manage_neuron")
-        .with_arg(encode_args((request,))?)
-        .call_and_wait()
-        .await
-        .context("Failed to call manage_neuron for adding hotkey")?;
+    let result_bytes = canister_call::update(
+        agent,
+        &governance_canister,
+        "manage_neuron",
+        encode_args((request,))?,
+    )
+    .await
+    .context("Failed to call manage_neuron for adding hotkey")?;
 
     let result: ManageNeuronResponse = Decode!(&result_bytes, ManageNeuronResponse)?;
 
     match result.command {
         Some(Command1::Configure {}) => Ok(()),
-        Some(Command1::Error(e)) => {
-            anyhow::bail!("Failed to add hotkey: {}", e.error_message);
-        }
+        Some(Command1::Error(e)) => Err(nns_governance_error("Failed to add hotkey", &e)),
+        _ => anyhow::bail!("Unexpected response from manage_neuron"),
+    }
+}
+
+/// Remove a hotkey from an ICP neuron
+pub async fn remove_hotkey_from_icp_neuron(
+    agent: &dyn CanisterClient,
+    governance_canister: Principal,
+    neuron_id: u64,
+    hotkey_principal: Principal,
+) -> Result<()> {
+    let request = ManageNeuronRequest {
+        id: Some(NeuronId { id: neuron_id }),
+        command: Some(ManageNeuronCommandRequest::Configure(Configure {
+            operation: Some(Operation::RemoveHotKey(RemoveHotKey {
+                hot_key_to_remove: Some(hotkey_principal),
+            })),
+        })),
+        neuron_id_or_subaccount: None,
+    };
+
+    let result_bytes = canister_call::update(
+        agent,
+        &governance_canister,
+        "manage_neuron",
+        encode_args((request,))?,
+    )
+    .await
+    .context("Failed to call manage_neuron for removing hotkey")?;
+
+    let result: ManageNeuronResponse = Decode!(&result_bytes, ManageNeuronResponse)?;
+
+    match result.command {
+        Some(Command1::Configure {}) => Ok(()),
+        Some(Command1::Error(e)) => Err(nns_governance_error("Failed to remove hotkey", &e)),
         _ => anyhow::bail!("Unexpected response from manage_neuron"),
     }
 }
@@ -168,7 +234,7 @@ pub async fn add_hotkey_to_icp_neuron(
 /// Set neuron visibility (public/private)
 /// visibility: true = public (2), false = private (1)
 pub async fn set_neuron_visibility(
-    agent: &Agent,
+    agent: &dyn CanisterClient,
     governance_canister: Principal,
     neuron_id: u64,
     is_public: bool,
@@ -188,40 +254,39 @@ pub async fn set_neuron_visibility(
         neuron_id_or_subaccount: None,
     };
 
-    let result_bytes = agent
-        .update(&governance_canister, "manage_neuron")
-        .with_arg(encode_args((request,))?)
-        .call_and_wait()
-        .await
-        .context("Failed to call manage_neuron for setting visibility")?;
+    let result_bytes = canister_call::update(
+        agent,
+        &governance_canister,
+        "manage_neuron",
+        encode_args((request,))?,
+    )
+    .await
+    .context("Failed to call manage_neuron for setting visibility")?;
 
     let result: ManageNeuronResponse = Decode!(&result_bytes, ManageNeuronResponse)?;
 
     match result.command {
         Some(Command1::Configure {}) => Ok(()),
-        Some(Command1::Error(e)) => {
-            anyhow::bail!("Failed to set visibility: {}", e.error_message);
-        }
+        Some(Command1::Error(e)) => Err(nns_governance_error("Failed to set visibility", &e)),
         _ => anyhow::bail!("Unexpected response from manage_neuron"),
     }
 }
 
 /// Get minting account balance
 pub async fn get_minting_account_balance() -> Result<u64> {
-    use super::identity::{create_agent, load_minting_identity};
+    use super::identity::load_minting_identity;
     use super::ledger_ops::get_icp_ledger_balance;
-    use crate::core::utils::constants::LEDGER_CANISTER;
 
     // Load minting identity
     let identity = load_minting_identity().context("Failed to load minting identity")?;
 
     // Create authenticated agent with minting identity
-    let agent = create_agent(identity)
+    let agent = app_context()
+        .agent(identity)
         .await
         .context("Failed to create agent with minting identity")?;
 
-    let ledger_canister =
-        Principal::from_text(LEDGER_CANISTER).context("Failed to parse ICP Ledger canister ID")?;
+    let ledger_canister = crate::core::utils::network::network_config().ledger_canister;
 
     // Get minting account principal
     let minting_principal = agent
@@ -237,21 +302,24 @@ pub async fn get_minting_account_balance() -> Result<u64> {
 }
 
 /// Mint ICP tokens by transferring from minting account to a receiver
-pub async fn mint_icp_default_path(receiver_principal: Principal, amount_e8s: u64) -> Result<u64> {
-    use super::identity::{create_agent, load_minting_identity};
+pub async fn mint_icp_default_path(
+    receiver_principal: Principal,
+    amount_e8s: u64,
+    to_subaccount: Option<Vec<u8>>,
+) -> Result<u64> {
+    use super::identity::load_minting_identity;
     use super::ledger_ops::transfer_icp;
-    use crate::core::utils::constants::LEDGER_CANISTER;
 
     // Load minting identity
     let identity = load_minting_identity().context("Failed to load minting identity")?;
 
     // Create authenticated agent with minting identity
-    let agent = create_agent(identity)
+    let agent = app_context()
+        .agent(identity)
         .await
         .context("Failed to create agent with minting identity")?;
 
-    let ledger_canister =
-        Principal::from_text(LEDGER_CANISTER).context("Failed to parse ICP Ledger canister ID")?;
+    let ledger_canister = crate::core::utils::network::network_config().ledger_canister;
 
     // Transfer ICP (minting doesn't require fee - fee is deducted from minting account automatically)
     let block_height = transfer_icp(
@@ -259,7 +327,7 @@ pub async fn mint_icp_default_path(receiver_principal: Principal, amount_e8s: u6
         ledger_canister,
         receiver_principal,
         amount_e8s,
-        None,
+        to_subaccount,
     )
     .await
     .context("Failed to transfer ICP")?;
@@ -268,70 +336,30 @@ pub async fn mint_icp_default_path(receiver_principal: Principal, amount_e8s: u6
 }
 
 /// Create an ICP neuron by transferring ICP and claiming it
+/// The ICP comes from `from_subaccount` of the principal (None for its default account)
 pub async fn create_icp_neuron_default_path(
     principal: Principal,
     amount_e8s: u64,
     memo: Option<u64>,
     dissolve_delay_seconds: Option<u64>,
+    from_subaccount: Option<Vec<u8>>,
 ) -> Result<u64> {
-    use super::identity::{create_agent, load_dfx_identity, load_identity_from_seed_file};
-    use super::ledger_ops::{generate_subaccount_by_nonce, transfer_icp};
-    use crate::core::utils::constants::{GOVERNANCE_CANISTER, ICP_TRANSFER_FEE, LEDGER_CANISTER};
-    use crate::core::utils::data_output;
-    use std::path::PathBuf;
-
-    // Try to load participant identity from deployment data, fallback to dfx identity
-    let identity = {
-        let deployment_path = data_output::get_output_path();
-        if deployment_path.exists() {
-            if let Ok(data_content) = std::fs::read_to_string(&deployment_path) {
-                if let Ok(deployment_data) =
-                    serde_json::from_str::<data_output::SnsCreationData>(&data_content)
-                {
-                    // Check if principal is the owner
-                    if deployment_data.owner_principal == principal.to_string() {
-                        // Owner uses dfx identity
-                        load_dfx_identity(None).context("Failed to load dfx identity")?
-                    } else if let Some(participant_data) = deployment_data
-                        .participants
-                        .iter()
-                        .find(|p| p.principal == principal.to_string())
-                    {
-                        // Load participant identity from seed file
-                        let seed_path = PathBuf::from(&participant_data.seed_file);
-                        if let Ok(participant_identity) = load_identity_from_seed_file(&seed_path) {
-                            participant_identity
-                        } else {
-                            // Fallback to dfx identity
-                            load_dfx_identity(None).context("Failed to load dfx identity")?
-                        }
-                    } else {
-                        // Principal not found in participants or owner, use dfx identity
-                        load_dfx_identity(None).context("Failed to load dfx identity")?
-                    }
-                } else {
-                    // Failed to parse deployment data, use dfx identity
-                    load_dfx_identity(None).context("Failed to load dfx identity")?
-                }
-            } else {
-                // Failed to read deployment data, use dfx identity
-                load_dfx_identity(None).context("Failed to load dfx identity")?
-            }
-        } else {
-            // No deployment data, use dfx identity
-            load_dfx_identity(None).context("Failed to load dfx identity")?
-        }
+    use super::ledger_ops::{
+        generate_subaccount_by_nonce, transfer_icp_from_subaccount, wait_for_icp_balance,
     };
+    use crate::core::utils::constants::ICP_TRANSFER_FEE;
+
+    // Load identity (owner, participant or extra identity)
+    let identity = identity_for_principal(principal)?;
 
     // Create authenticated agent
-    let agent = create_agent(identity)
+    let agent = app_context()
+        .agent(identity)
         .await
         .context("Failed to create agent")?;
 
-    let ledger_canister =
-        Principal::from_text(LEDGER_CANISTER).context("Failed to parse ICP Ledger canister ID")?;
-    let governance_canister = Principal::from_text(GOVERNANCE_CANISTER)
-        .context("Failed to parse ICP Governance canister ID")?;
+    let ledger_canister = crate::core::utils::network::network_config().ledger_canister;
+    let governance_canister = crate::core::utils::network::network_config().governance_canister;
 
     // Use provided memo or default to 1
     let memo_value = memo.unwrap_or(1);
@@ -339,20 +367,37 @@ pub async fn create_icp_neuron_default_path(
     // Generate subaccount for neuron
     let subaccount = generate_subaccount_by_nonce(memo_value, principal);
 
+    // Once the ICP is sent, Ctrl+C waits for the claim (see `interrupt`)
+    let section = CriticalSection::enter();
+    interrupt::check()?;
+
     // Transfer ICP to governance subaccount (amount should include fee)
     let transfer_amount = amount_e8s + ICP_TRANSFER_FEE;
-    transfer_icp(
+    transfer_icp_from_subaccount(
         &agent,
         ledger_canister,
+        from_subaccount,
         governance_canister,
         transfer_amount,
         Some(subaccount.0.to_vec()),
     )
     .await
     .context("Failed to transfer ICP to governance subaccount")?;
+    section.resume_with(format!(
+        "the ICP is in governance subaccount {} (memo {memo_value}) but the neuron is not claimed",
+        hex::encode(subaccount.0)
+    ));
 
-    // Wait a bit for the transfer to settle
-    tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+    // Wait for the transfer to land before claiming
+    wait_for_icp_balance(
+        &agent,
+        ledger_canister,
+        governance_canister,
+        Some(subaccount.0.to_vec()),
+        transfer_amount,
+    )
+    .await
+    .context("ICP transfer to governance subaccount did not land")?;
 
     // Claim neuron
     let neuron_id = claim_neuron(&agent, governance_canister, memo_value)
@@ -360,22 +405,100 @@ pub async fn create_icp_neuron_default_path(
         .context("Failed to claim ICP neuron")?;
 
     // Set dissolve delay if specified
-    if let Some(dissolve_delay) = dissolve_delay_seconds {
-        if dissolve_delay > 0 {
-            set_dissolve_delay(&agent, governance_canister, neuron_id, dissolve_delay)
-                .await
-                .context("Failed to set dissolve delay")?;
-        }
+    if let Some(dissolve_delay) = dissolve_delay_seconds
+        && dissolve_delay > 0
+    {
+        section.resume_with(format!(
+            "neuron {neuron_id} was created without its dissolve delay: run increase-icp-dissolve-delay"
+        ));
+        interrupt::check()?;
+        set_dissolve_delay(&agent, governance_canister, neuron_id, dissolve_delay)
+            .await
+            .context("Failed to set dissolve delay")?;
     }
 
     Ok(neuron_id)
 }
 
+/// Increase the stake of an existing ICP neuron: transfer ICP to its account and refresh it
+/// Returns the new cached stake
+pub async fn refresh_icp_neuron_default_path(
+    principal: Principal,
+    neuron_id: u64,
+    amount_e8s: u64,
+    from_subaccount: Option<Vec<u8>>,
+) -> Result<u64> {
+    use super::ledger_ops::{
+        get_icp_ledger_balance, transfer_icp_from_subaccount, wait_for_icp_balance,
+    };
+
+    // Load identity (owner, participant or extra identity)
+    let identity = identity_for_principal(signing_principal(principal))?;
+    let agent = app_context()
+        .agent(identity)
+        .await
+        .context("Failed to create agent")?;
+
+    let ledger_canister = crate::core::utils::network::network_config().ledger_canister;
+    let governance_canister = crate::core::utils::network::network_config().governance_canister;
+
+    // The neuron's account is a subaccount of governance (needs get_full_neuron access)
+    let neuron = get_icp_neuron(&agent, governance_canister, neuron_id)
+        .await
+        .context("Failed to get neuron")?;
+    let subaccount = Some(neuron.account);
+    let balance_before = get_icp_ledger_balance(
+        &agent,
+        ledger_canister,
+        governance_canister,
+        subaccount.clone(),
+    )
+    .await
+    .context("Failed to get neuron account balance")?;
+
+    // Once the ICP is sent, Ctrl+C waits for the refresh (see `interrupt`)
+    let section = CriticalSection::enter();
+    interrupt::check()?;
+    transfer_icp_from_subaccount(
+        &agent,
+        ledger_canister,
+        from_subaccount,
+        governance_canister,
+        amount_e8s,
+        subaccount.clone(),
+    )
+    .await
+    .context("Failed to transfer ICP to the neuron account")?;
+    section.resume_with(format!(
+        "the ICP reached the account of neuron {neuron_id} but its stake is not refreshed: the next refresh-icp-neuron of it counts it"
+    ));
+
+    // Wait for the transfer to land before refreshing
+    wait_for_icp_balance(
+        &agent,
+        ledger_canister,
+        governance_canister,
+        subaccount,
+        balance_before + amount_e8s,
+    )
+    .await
+    .context("ICP transfer to the neuron account did not land")?;
+
+    refresh_neuron(&agent, governance_canister, neuron_id)
+        .await
+        .context("Failed to refresh ICP neuron")?;
+
+    let neuron = get_icp_neuron(&agent, governance_canister, neuron_id)
+        .await
+        .context("Failed to get refreshed neuron")?;
+    Ok(neuron.cached_neuron_stake_e8s)
+}
+
 /// List all ICP neurons for a given principal, sorted by dissolve delay (lowest first) and cached stake (highest first)
 /// Note: ICP neurons are protected and require authentication (the agent must be authenticated as the principal)
 /// The principal parameter is used for documentation - the actual neurons returned are those readable by the authenticated caller
 pub async fn list_icp_neurons_for_principal(
-    agent: &Agent,
+    agent: &dyn CanisterClient,
     governance_canister: Principal,
     _principal: Principal,
 ) -> Result<Vec<super::super::declarations::icp_governance::Neuron>> {
@@ -383,31 +506,40 @@ pub async fn list_icp_neurons_for_principal(
         DissolveState, ListNeurons, ListNeuronsResponse,
     };
 
+    const PAGE_SIZE: u64 = 100;
+
     // Use the new ListNeurons interface - include_neurons_readable_by_caller will return neurons
-    // that the authenticated caller (principal) can read
-    let request = ListNeurons {
-        page_size: Some(100),
-        include_public_neurons_in_full_neurons: Some(false),
-        neuron_ids: Vec::new(),
-        page_number: Some(0),
-        include_empty_neurons_readable_by_caller: Some(false),
-        neuron_subaccounts: None,
-        include_neurons_readable_by_caller: true,
-    };
-    let args = candid::encode_args((request,))?;
+    // that the authenticated caller (principal) can read, a page at a time until
+    // total_pages_available is reached
+    let mut neurons = Vec::new();
+    let mut page_number = 0;
+    loop {
+        let request = ListNeurons {
+            page_size: Some(PAGE_SIZE),
+            include_public_neurons_in_full_neurons: Some(false),
+            neuron_ids: Vec::new(),
+            page_number: Some(page_number),
+            include_empty_neurons_readable_by_caller: Some(false),
+            neuron_subaccounts: None,
+            include_neurons_readable_by_caller: true,
+        };
+        let args = candid::encode_args((request,))?;
 
-    let response = agent
-        .query(&governance_canister, "list_neurons")
-        .with_arg(args)
-        .call()
-        .await
-        .context("Failed to call list_neurons")?;
+        let response = canister_call::query(agent, &governance_canister, "list_neurons", args)
+            .await
+            .context("Failed to call list_neurons")?;
 
-    let result: ListNeuronsResponse = Decode!(&response, ListNeuronsResponse)?;
+        let result: ListNeuronsResponse = Decode!(&response, ListNeuronsResponse)?;
+
+        // Use full_neurons from the response; governance without paging reports no page count
+        neurons.extend(result.full_neurons);
+        page_number += 1;
+        if page_number >= result.total_pages_available.unwrap_or(0) {
+            break;
+        }
+    }
 
-    // Use full_neurons from the response
     // Sort neurons by dissolve delay (lowest first), then by cached stake (highest first)
-    let mut neurons = result.full_neurons;
     neurons.sort_by(|a, b| {
         let a_delay = match &a.dissolve_state {
             Some(DissolveState::DissolveDelaySeconds(seconds)) => *seconds,
@@ -439,69 +571,47 @@ pub async fn list_icp_neurons_for_principal(
 pub async fn list_icp_neurons_for_principal_default_path(
     principal: Principal,
 ) -> Result<Vec<super::super::declarations::icp_governance::Neuron>> {
-    use super::identity::{create_agent, load_dfx_identity, load_identity_from_seed_file};
-    use crate::core::utils::constants::GOVERNANCE_CANISTER;
-    use crate::core::utils::data_output;
-    use std::path::PathBuf;
-
-    // Try to load participant identity from deployment data, fallback to dfx identity
-    let identity = {
-        let deployment_path = data_output::get_output_path();
-        if deployment_path.exists() {
-            if let Ok(data_content) = std::fs::read_to_string(&deployment_path) {
-                if let Ok(deployment_data) =
-                    serde_json::from_str::<data_output::SnsCreationData>(&data_content)
-                {
-                    // Check if principal is the owner
-                    if deployment_data.owner_principal == principal.to_string() {
-                        // Owner uses dfx identity
-                        load_dfx_identity(None).context("Failed to load dfx identity")?
-                    } else if let Some(participant_data) = deployment_data
-                        .participants
-                        .iter()
-                        .find(|p| p.principal == principal.to_string())
-                    {
-                        // Load participant identity from seed file
-                        let seed_path = PathBuf::from(&participant_data.seed_file);
-                        if let Ok(participant_identity) = load_identity_from_seed_file(&seed_path) {
-                            participant_identity
-                        } else {
-                            // Fallback to dfx identity
-                            load_dfx_identity(None).context("Failed to load dfx identity")?
-                        }
-                    } else {
-                        // Principal not found in participants or owner, use dfx identity
-                        load_dfx_identity(None).context("Failed to load dfx identity")?
-                    }
-                } else {
-                    // Failed to parse deployment data, use dfx identity
-                    load_dfx_identity(None).context("Failed to load dfx identity")?
-                }
-            } else {
-                // Failed to read deployment data, use dfx identity
-                load_dfx_identity(None).context("Failed to load dfx identity")?
-            }
-        } else {
-            // No deployment data, use dfx identity
-            load_dfx_identity(None).context("Failed to load dfx identity")?
-        }
-    };
+    // Load identity (owner, participant or extra identity)
+    let identity = identity_for_principal(signing_principal(principal))?;
 
     // Create authenticated agent with the principal's identity
-    let agent = create_agent(identity)
+    let agent = app_context()
+        .agent(identity)
         .await
         .context("Failed to create agent")?;
 
-    let governance_canister = Principal::from_text(GOVERNANCE_CANISTER)
-        .context("Failed to parse ICP Governance canister ID")?;
+    let governance_canister = crate::core::utils::network::network_config().governance_canister;
 
     // List neurons (requires authentication for ICP neurons)
     list_icp_neurons_for_principal(&agent, governance_canister, principal).await
 }
 
+/// ICP neurons of the owner and every participant, listed concurrently
+/// Returns (label, principal, neurons) per account (see `SnsCreationData::labelled_accounts`)
+pub async fn list_all_icp_neurons_default_path() -> Result<
+    Vec<(
+        String,
+        Principal,
+        Vec<super::super::declarations::icp_governance::Neuron>,
+    )>,
+> {
+    let data = app_context().deployment_data()?;
+    futures::future::try_join_all(data.labelled_accounts().into_iter().map(
+        |(label, principal)| async move {
+            let principal = Principal::from_text(&principal)
+                .with_context(|| format!("Failed to parse principal of {}", label))?;
+            let neurons = list_icp_neurons_for_principal_default_path(principal)
+                .await
+                .with_context(|| format!("Failed to list ICP neurons of {}", label))?;
+            Ok((label, principal, neurons))
+        },
+    ))
+    .await
+}
+
 /// Get full neuron information by neuron ID
 pub async fn get_icp_neuron(
-    agent: &Agent,
+    agent: &dyn CanisterClient,
     governance_canister: Principal,
     neuron_id: u64,
 ) -> Result<super::super::declarations::icp_governance::Neuron> {
@@ -509,10 +619,7 @@ pub async fn get_icp_neuron(
 
     let args = candid::encode_args((neuron_id,))?;
 
-    let response = agent
-        .query(&governance_canister, "get_full_neuron")
-        .with_arg(args)
-        .call()
+    let response = canister_call::query(agent, &governance_canister, "get_full_neuron", args)
         .await
         .context("Failed to call get_full_neuron")?;
 
@@ -520,47 +627,59 @@ pub async fn get_icp_neuron(
 
     match result {
         Result2::Ok(neuron) => Ok(neuron),
-        Result2::Err(e) => {
-            anyhow::bail!(
-                "Failed to get neuron: {} (type: {})",
-                e.error_message,
-                e.error_type
-            );
-        }
+        Result2::Err(e) => Err(nns_governance_error("Failed to get neuron", &e)),
     }
 }
 
+/// Get an NNS proposal (None if governance does not know it)
+pub async fn get_nns_proposal_info(
+    agent: &dyn CanisterClient,
+    governance_canister: Principal,
+    proposal_id: u64,
+) -> Result<Option<super::super::declarations::icp_governance::ProposalInfo>> {
+    use super::super::declarations::icp_governance::ProposalInfo;
+
+    let response = canister_call::query(
+        agent,
+        &governance_canister,
+        "get_proposal_info",
+        encode_args((proposal_id,))?,
+    )
+    .await
+    .context("Failed to call get_proposal_info")?;
+
+    Decode!(&response, Option<ProposalInfo>).context("Failed to decode proposal info")
+}
+
 /// High-level function to get ICP neuron information
-/// This reads deployment data and queries the neuron using the owner's identity
+/// get_full_neuron needs the controller (or a hotkey), so the neuron is queried as
+/// `controller` (identity resolved from the deployment data), or as the owner by default
 pub async fn get_icp_neuron_default_path(
     neuron_id: Option<u64>,
+    controller: Option<Principal>,
 ) -> Result<super::super::declarations::icp_governance::Neuron> {
-    use super::identity::{create_agent, load_dfx_identity};
+    use super::identity::load_dfx_identity;
 
     let id = if let Some(id) = neuron_id {
         id
     } else {
-        // Read deployment data
-        let deployment_path = crate::core::utils::data_output::get_output_path();
-        let data_content = std::fs::read_to_string(&deployment_path).with_context(|| {
-            format!("Failed to read deployment data from: {:?}", deployment_path)
-        })?;
-        let deployment_data: crate::core::utils::data_output::SnsCreationData =
-            serde_json::from_str(&data_content).context("Failed to parse deployment data JSON")?;
+        let deployment_data = app_context().deployment_data()?;
         deployment_data.icp_neuron_id
     };
 
-    // Load owner identity (default dfx identity) - get_full_neuron requires authentication
-    let identity = load_dfx_identity(None).context("Failed to load owner dfx identity")?;
+    let identity = match controller {
+        Some(principal) => identity_for_principal(signing_principal(principal))?,
+        None => load_dfx_identity(None).context("Failed to load owner dfx identity")?,
+    };
 
     // Create authenticated agent
-    let agent = create_agent(identity)
+    let agent = app_context()
+        .agent(identity)
         .await
-        .context("Failed to create agent with owner identity")?;
+        .context("Failed to create agent")?;
 
     // ICP Governance canister (standard NNS canister ID for local development)
-    let governance_canister = Principal::from_text("rrkah-fqaaa-aaaaa-aaaaq-cai")
-        .context("Failed to parse ICP Governance canister ID")?;
+    let governance_canister = crate::core::utils::network::network_config().governance_canister;
 
     // Get neuron
     get_icp_neuron(&agent, governance_canister, id).await
@@ -568,7 +687,7 @@ pub async fn get_icp_neuron_default_path(
 
 /// Disburse an ICP neuron to a receiver account
 pub async fn disburse_icp_neuron(
-    agent: &Agent,
+    agent: &dyn CanisterClient,
     governance_canister: Principal,
     neuron_id: u64,
     receiver_principal: Principal,
@@ -596,12 +715,14 @@ pub async fn disburse_icp_neuron(
         neuron_id_or_subaccount: None,
     };
 
-    let result_bytes = agent
-        .update(&governance_canister, "manage_neuron")
-        .with_arg(encode_args((request,))?)
-        .call_and_wait()
-        .await
-        .context("Failed to disburse neuron")?;
+    let result_bytes = canister_call::update(
+        agent,
+        &governance_canister,
+        "manage_neuron",
+        encode_args((request,))?,
+    )
+    .await
+    .context("Failed to disburse neuron")?;
 
     let response: ManageNeuronResponse = Decode!(&result_bytes, ManageNeuronResponse)
         .context("Failed to decode disburse response")?;
@@ -610,16 +731,94 @@ pub async fn disburse_icp_neuron(
         Some(Command1::Disburse(DisburseResponse {
             transfer_block_height,
         })) => Ok(transfer_block_height),
-        Some(Command1::Error(e)) => {
-            anyhow::bail!("Failed to disburse neuron: {}", e.error_message);
-        }
+        Some(Command1::Error(e)) => Err(nns_governance_error("Failed to disburse neuron", &e)),
         _ => anyhow::bail!("Unexpected response from disburse"),
     }
 }
 
+/// Spawn a share of an ICP neuron's maturity into a new neuron, returning its ID
+/// The new neuron is controlled by `new_controller` (the caller if None); NNS governance mints
+/// its stake from the spawned maturity once the neuron has been spawning for a week.
+pub async fn spawn_icp_neuron(
+    agent: &dyn CanisterClient,
+    governance_canister: Principal,
+    neuron_id: u64,
+    percentage_to_spawn: Option<u32>,
+    new_controller: Option<Principal>,
+) -> Result<u64> {
+    let request = ManageNeuronRequest {
+        id: Some(NeuronId { id: neuron_id }),
+        command: Some(ManageNeuronCommandRequest::Spawn(Spawn {
+            percentage_to_spawn,
+            new_controller,
+            nonce: None,
+        })),
+        neuron_id_or_subaccount: None,
+    };
+
+    let result_bytes = canister_call::update(
+        agent,
+        &governance_canister,
+        "manage_neuron",
+        encode_args((request,))?,
+    )
+    .await
+    .context("Failed to spawn neuron")?;
+
+    let response: ManageNeuronResponse =
+        Decode!(&result_bytes, ManageNeuronResponse).context("Failed to decode spawn response")?;
+
+    match response.command {
+        Some(Command1::Spawn(SpawnResponse {
+            created_neuron_id: Some(NeuronId { id }),
+        })) => Ok(id),
+        Some(Command1::Error(e)) => Err(nns_governance_error("Failed to spawn neuron", &e)),
+        _ => anyhow::bail!("Unexpected response from spawn"),
+    }
+}
+
+/// Merge the source neuron into the target neuron (both controlled by the caller)
+/// Moves the source's stake, maturity and age into the target; the source is left empty.
+pub async fn merge_icp_neurons(
+    agent: &dyn CanisterClient,
+    governance_canister: Principal,
+    source_neuron_id: u64,
+    target_neuron_id: u64,
+) -> Result<MergeResponse> {
+    let request = ManageNeuronRequest {
+        id: Some(NeuronId {
+            id: target_neuron_id,
+        }),
+        command: Some(ManageNeuronCommandRequest::Merge(Merge {
+            source_neuron_id: Some(NeuronId {
+                id: source_neuron_id,
+            }),
+        })),
+        neuron_id_or_subaccount: None,
+    };
+
+    let result_bytes = canister_call::update(
+        agent,
+        &governance_canister,
+        "manage_neuron",
+        encode_args((request,))?,
+    )
+    .await
+    .context("Failed to merge neurons")?;
+
+    let response: ManageNeuronResponse =
+        Decode!(&result_bytes, ManageNeuronResponse).context("Failed to decode merge response")?;
+
+    match response.command {
+        Some(Command1::Merge(merge)) => Ok(merge),
+        Some(Command1::Error(e)) => Err(nns_governance_error("Failed to merge neurons", &e)),
+        _ => anyhow::bail!("Unexpected response from merge"),
+    }
+}
+
 /// Start dissolving an ICP neuron
 pub async fn start_dissolving_icp_neuron(
-    agent: &Agent,
+    agent: &dyn CanisterClient,
     governance_canister: Principal,
     neuron_id: u64,
 ) -> Result<()> {
@@ -631,28 +830,28 @@ pub async fn start_dissolving_icp_neuron(
         neuron_id_or_subaccount: None,
     };
 
-    let result_bytes = agent
-        .update(&governance_canister, "manage_neuron")
-        .with_arg(encode_args((request,))?)
-        .call_and_wait()
-        .await
-        .context("Failed to start dissolving")?;
+    let result_bytes = canister_call::update(
+        agent,
+        &governance_canister,
+        "manage_neuron",
+        encode_args((request,))?,
+    )
+    .await
+    .context("Failed to start dissolving")?;
 
     let response: ManageNeuronResponse = Decode!(&result_bytes, ManageNeuronResponse)
         .context("Failed to decode configure response")?;
 
     match response.command {
         Some(Command1::Configure {}) => Ok(()),
-        Some(Command1::Error(e)) => {
-            anyhow::bail!("Failed to start dissolving: {}", e.error_message);
-        }
+        Some(Command1::Error(e)) => Err(nns_governance_error("Failed to start dissolving", &e)),
         _ => anyhow::bail!("Unexpected response from configure"),
     }
 }
 
 /// Stop dissolving an ICP neuron
 pub async fn stop_dissolving_icp_neuron(
-    agent: &Agent,
+    agent: &dyn CanisterClient,
     governance_canister: Principal,
     neuron_id: u64,
 ) -> Result<()> {
@@ -664,28 +863,28 @@ pub async fn stop_dissolving_icp_neuron(
         neuron_id_or_subaccount: None,
     };
 
-    let result_bytes = agent
-        .update(&governance_canister, "manage_neuron")
-        .with_arg(encode_args((request,))?)
-        .call_and_wait()
-        .await
-        .context("Failed to stop dissolving")?;
+    let result_bytes = canister_call::update(
+        agent,
+        &governance_canister,
+        "manage_neuron",
+        encode_args((request,))?,
+    )
+    .await
+    .context("Failed to stop dissolving")?;
 
     let response: ManageNeuronResponse = Decode!(&result_bytes, ManageNeuronResponse)
         .context("Failed to decode configure response")?;
 
     match response.command {
         Some(Command1::Configure {}) => Ok(()),
-        Some(Command1::Error(e)) => {
-            anyhow::bail!("Failed to stop dissolving: {}", e.error_message);
-        }
+        Some(Command1::Error(e)) => Err(nns_governance_error("Failed to stop dissolving", &e)),
         _ => anyhow::bail!("Unexpected response from configure"),
     }
 }
 
 /// Increase dissolve delay for an ICP neuron (wrapper around set_dissolve_delay)
 pub async fn increase_icp_dissolve_delay(
-    agent: &Agent,
+    agent: &dyn CanisterClient,
     governance_canister: Principal,
     neuron_id: u64,
     additional_dissolve_delay_seconds: u64,
@@ -707,46 +906,15 @@ pub async fn disburse_icp_neuron_for_principal_default_path(
     neuron_id: Option<u64>,
     amount_e8s: Option<u64>,
 ) -> Result<u64> {
-    use super::identity::{create_agent, load_dfx_identity, load_identity_from_seed_file};
-    use crate::core::utils::{constants::GOVERNANCE_CANISTER, data_output::get_output_path};
-    use std::fs;
-
-    // Try to load participant identity from deployment data
-    let deployment_path = get_output_path();
-    let identity = if deployment_path.exists() {
-        let data_content =
-            fs::read_to_string(&deployment_path).context("Failed to read deployment data")?;
-        let deployment_data: crate::core::utils::data_output::SnsCreationData =
-            serde_json::from_str(&data_content).context("Failed to parse deployment data JSON")?;
-
-        // Try to find matching participant
-        let mut found_identity = None;
-        for participant in &deployment_data.participants {
-            let participant_principal = Principal::from_text(&participant.principal)
-                .context("Failed to parse participant principal")?;
-            if participant_principal == principal {
-                let seed_path = std::path::PathBuf::from(&participant.seed_file);
-                if let Ok(participant_identity) = load_identity_from_seed_file(&seed_path) {
-                    found_identity = Some(participant_identity);
-                    break;
-                }
-            }
-        }
-        found_identity.unwrap_or_else(|| {
-            load_dfx_identity(None)
-                .context("Failed to load dfx identity")
-                .expect("Failed to load dfx identity as fallback")
-        })
-    } else {
-        load_dfx_identity(None).context("Failed to load dfx identity")?
-    };
+    // Load identity (owner, participant or extra identity)
+    let identity = identity_for_principal(signing_principal(principal))?;
 
-    let agent = create_agent(identity)
+    let agent = app_context()
+        .agent(identity)
         .await
         .context("Failed to create agent")?;
 
-    let governance_canister = Principal::from_text(GOVERNANCE_CANISTER)
-        .context("Failed to parse ICP Governance canister ID")?;
+    let governance_canister = crate::core::utils::network::network_config().governance_canister;
 
     let final_neuron_id = if let Some(id) = neuron_id {
         id
@@ -775,52 +943,74 @@ pub async fn disburse_icp_neuron_for_principal_default_path(
     .await
 }
 
+/// High-level function to spawn the maturity of an ICP neuron of a principal
+/// Returns the ID of the spawned neuron
+pub async fn spawn_icp_neuron_for_principal_default_path(
+    principal: Principal,
+    neuron_id: u64,
+    percentage_to_spawn: Option<u32>,
+    new_controller: Option<Principal>,
+) -> Result<u64> {
+    // Load identity (owner, participant or extra identity)
+    let identity = identity_for_principal(signing_principal(principal))?;
+
+    let agent = app_context()
+        .agent(identity)
+        .await
+        .context("Failed to create agent")?;
+
+    let governance_canister = crate::core::utils::network::network_config().governance_canister;
+
+    spawn_icp_neuron(
+        &agent,
+        governance_canister,
+        neuron_id,
+        percentage_to_spawn,
+        new_controller,
+    )
+    .await
+}
+
+/// High-level function to merge two ICP neurons of a principal
+pub async fn merge_icp_neurons_for_principal_default_path(
+    principal: Principal,
+    source_neuron_id: u64,
+    target_neuron_id: u64,
+) -> Result<MergeResponse> {
+    // Load identity (owner, participant or extra identity)
+    let identity = identity_for_principal(signing_principal(principal))?;
+
+    let agent = app_context()
+        .agent(identity)
+        .await
+        .context("Failed to create agent")?;
+
+    let governance_canister = crate::core::utils::network::network_config().governance_canister;
+
+    merge_icp_neurons(
+        &agent,
+        governance_canister,
+        source_neuron_id,
+        target_neuron_id,
+    )
+    .await
+}
+
 /// High-level function to increase dissolve delay for an ICP neuron
 pub async fn increase_icp_dissolve_delay_for_principal_default_path(
     principal: Principal,
     neuron_id: Option<u64>,
     additional_dissolve_delay_seconds: u64,
 ) -> Result<()> {
-    use super::identity::{create_agent, load_dfx_identity, load_identity_from_seed_file};
-    use crate::core::utils::{constants::GOVERNANCE_CANISTER, data_output::get_output_path};
-    use std::fs;
-
-    // Try to load participant identity from deployment data
-    let deployment_path = get_output_path();
-    let identity = if deployment_path.exists() {
-        let data_content =
-            fs::read_to_string(&deployment_path).context("Failed to read deployment data")?;
-        let deployment_data: crate::core::utils::data_output::SnsCreationData =
-            serde_json::from_str(&data_content).context("Failed to parse deployment data JSON")?;
-
-        // Try to find matching participant
-        let mut found_identity = None;
-        for participant in &deployment_data.participants {
-            let participant_principal = Principal::from_text(&participant.principal)
-                .context("Failed to parse participant principal")?;
-            if participant_principal == principal {
-                let seed_path = std::path::PathBuf::from(&participant.seed_file);
-                if let Ok(participant_identity) = load_identity_from_seed_file(&seed_path) {
-                    found_identity = Some(participant_identity);
-                    break;
-                }
-            }
-        }
-        found_identity.unwrap_or_else(|| {
-            load_dfx_identity(None)
-                .context("Failed to load dfx identity")
-                .expect("Failed to load dfx identity as fallback")
-        })
-    } else {
-        load_dfx_identity(None).context("Failed to load dfx identity")?
-    };
+    // Load identity (owner, participant or extra identity)
+    let identity = identity_for_principal(signing_principal(principal))?;
 
-    let agent = create_agent(identity)
+    let agent = app_context()
+        .agent(identity)
         .await
         .context("Failed to create agent")?;
 
-    let governance_canister = Principal::from_text(GOVERNANCE_CANISTER)
-        .context("Failed to parse ICP Governance canister ID")?;
+    let governance_canister = crate::core::utils::network::network_config().governance_canister;
 
     let final_neuron_id = if let Some(id) = neuron_id {
         id
@@ -854,46 +1044,15 @@ pub async fn manage_icp_dissolving_state_for_principal_default_path(
     neuron_id: Option<u64>,
     start_dissolving: bool,
 ) -> Result<()> {
-    use super::identity::{create_agent, load_dfx_identity, load_identity_from_seed_file};
-    use crate::core::utils::{constants::GOVERNANCE_CANISTER, data_output::get_output_path};
-    use std::fs;
-
-    // Try to load participant identity from deployment data
-    let deployment_path = get_output_path();
-    let identity = if deployment_path.exists() {
-        let data_content =
-            fs::read_to_string(&deployment_path).context("Failed to read deployment data")?;
-        let deployment_data: crate::core::utils::data_output::SnsCreationData =
-            serde_json::from_str(&data_content).context("Failed to parse deployment data JSON")?;
-
-        // Try to find matching participant
-        let mut found_identity = None;
-        for participant in &deployment_data.participants {
-            let participant_principal = Principal::from_text(&participant.principal)
-                .context("Failed to parse participant principal")?;
-            if participant_principal == principal {
-                let seed_path = std::path::PathBuf::from(&participant.seed_file);
-                if let Ok(participant_identity) = load_identity_from_seed_file(&seed_path) {
-                    found_identity = Some(participant_identity);
-                    break;
-                }
-            }
-        }
-        found_identity.unwrap_or_else(|| {
-            load_dfx_identity(None)
-                .context("Failed to load dfx identity")
-                .expect("Failed to load dfx identity as fallback")
-        })
-    } else {
-        load_dfx_identity(None).context("Failed to load dfx identity")?
-    };
+    // Load identity (owner, participant or extra identity)
+    let identity = identity_for_principal(signing_principal(principal))?;
 
-    let agent = create_agent(identity)
+    let agent = app_context()
+        .agent(identity)
         .await
         .context("Failed to create agent")?;
 
-    let governance_canister = Principal::from_text(GOVERNANCE_CANISTER)
-        .context("Failed to parse ICP Governance canister ID")?;
+    let governance_canister = crate::core::utils::network::network_config().governance_canister;
 
     let final_neuron_id = if let Some(id) = neuron_id {
         id
@@ -0,0 +1,188 @@
+// ICP Index canister operations
+
+use super::canister_call;
+use super::canister_client::CanisterClient;
+use super::ledger_ops::{
+    AccountTransactions, IndexSync, LedgerHistoryEntry, generate_subaccount_by_nonce,
+    icp_account_id, query_icp_blocks,
+};
+use super::swap_ops::generate_participant_subaccount;
+use crate::core::context::app_context;
+use crate::core::utils::network::network_config;
+use anyhow::{Context, Result};
+use candid::{Decode, Principal, encode_args};
+use std::collections::HashMap;
+
+use super::super::declarations::icp_index::{
+    GetAccountIdentifierTransactionsArgs, GetAccountIdentifierTransactionsResult, Operation,
+    Status, TransactionWithId,
+};
+
+/// Highest neuron memo checked when naming neuron staking accounts
+/// Auto-assigned memos are the neuron count + 1, so this covers any local setup
+const MAX_NEURON_MEMO: u64 = 100;
+
+/// Get up to `max_results` transactions of a legacy account identifier from the ICP index,
+/// newest first. `start` is the newest transaction id to return (None for the latest)
+pub async fn get_account_identifier_transactions(
+    agent: &dyn CanisterClient,
+    index_canister: Principal,
+    account_identifier: &str,
+    start: Option<u64>,
+    max_results: u64,
+) -> Result<AccountTransactions> {
+    let request = GetAccountIdentifierTransactionsArgs {
+        max_results,
+        start,
+        account_identifier: account_identifier.to_string(),
+    };
+
+    let result_bytes = canister_call::query(
+        agent,
+        &index_canister,
+        "get_account_identifier_transactions",
+        encode_args((request,))?,
+    )
+    .await
+    .context("Failed to call get_account_identifier_transactions")?;
+
+    let result = Decode!(&result_bytes, GetAccountIdentifierTransactionsResult)
+        .context("Failed to decode get_account_identifier_transactions response")?;
+    let response = match result {
+        GetAccountIdentifierTransactionsResult::Ok(response) => response,
+        GetAccountIdentifierTransactionsResult::Err(e) => {
+            anyhow::bail!(
+                "ICP index rejected get_account_identifier_transactions: {}",
+                e.message
+            )
+        }
+    };
+
+    Ok(AccountTransactions {
+        balance_e8s: response.balance,
+        transactions: response
+            .transactions
+            .into_iter()
+            .map(icp_index_transaction_to_entry)
+            .collect(),
+        oldest_tx_id: response.oldest_tx_id,
+    })
+}
+
+fn icp_index_transaction_to_entry(tx: TransactionWithId) -> LedgerHistoryEntry {
+    let transaction = tx.transaction;
+    let (kind, from, to, amount_e8s) = match transaction.operation {
+        Operation::Transfer {
+            from, to, amount, ..
+        } => ("transfer", Some(from), Some(to), amount.e8s),
+        Operation::Mint { to, amount } => ("mint", None, Some(to), amount.e8s),
+        Operation::Burn { from, amount, .. } => ("burn", Some(from), None, amount.e8s),
+        Operation::Approve { from, spender, .. } => ("approve", Some(from), Some(spender), 0),
+    };
+
+    // Prefer the ICRC-1 memo when present, otherwise fall back to the legacy u64 memo
+    let memo = match transaction.icrc1_memo {
+        Some(bytes) => Some(hex::encode(bytes)),
+        None if transaction.memo != 0 => Some(transaction.memo.to_string()),
+        None => None,
+    };
+
+    LedgerHistoryEntry {
+        index: tx.id,
+        kind: kind.to_string(),
+        from,
+        to,
+        amount_e8s,
+        memo,
+        timestamp_nanos: transaction
+            .timestamp
+            .or(transaction.created_at_time)
+            .map_or(0, |t| t.timestamp_nanos),
+    }
+}
+
+/// Compare the blocks the ICP index has processed with the ICP ledger's length
+pub async fn get_icp_index_sync(
+    agent: &dyn CanisterClient,
+    index_canister: Principal,
+    ledger_canister: Principal,
+) -> Result<IndexSync> {
+    let result_bytes = canister_call::query(agent, &index_canister, "status", encode_args(())?)
+        .await
+        .context("Failed to call status")?;
+    let status = Decode!(&result_bytes, Status).context("Failed to decode index status")?;
+    let ledger_blocks = query_icp_blocks(agent, ledger_canister, 0, 0)
+        .await?
+        .chain_length;
+    Ok(IndexSync {
+        blocks_synced: status.num_blocks_synced,
+        ledger_blocks,
+    })
+}
+
+/// Accounts the tool moves a principal's ICP to, by account identifier: the swap escrow of
+/// its participation and the staking accounts of its ICP neurons
+///
+/// The ledger only records account identifiers, so these are derived the same way the
+/// transfers were made to name them in a principal's history.
+#[must_use]
+pub fn known_icp_accounts(
+    principal: Principal,
+    swap_canister: Option<Principal>,
+) -> HashMap<String, String> {
+    let network = network_config();
+    let mut accounts = HashMap::new();
+    accounts.insert(icp_account_id(principal, None), "own account".to_string());
+    if let Some(swap) = swap_canister {
+        accounts.insert(
+            icp_account_id(swap, Some(&generate_participant_subaccount(principal))),
+            "swap escrow".to_string(),
+        );
+    }
+    for memo in 0..=MAX_NEURON_MEMO {
+        accounts.insert(
+            icp_account_id(
+                network.governance_canister,
+                Some(&generate_subaccount_by_nonce(memo, principal)),
+            ),
+            format!("ICP neuron stake (memo {memo})"),
+        );
+    }
+    accounts
+}
+
+/// Name of the other side of a transaction of `own_account`, if it is a known account
+#[must_use]
+pub fn describe_counterparty(
+    entry: &LedgerHistoryEntry,
+    own_account: &str,
+    known: &HashMap<String, String>,
+) -> Option<String> {
+    let counterparty = if entry.from.as_deref() == Some(own_account) {
+        entry.to.as_deref()
+    } else {
+        entry.from.as_deref()
+    };
+    counterparty.and_then(|account| known.get(account).cloned())
+}
+
+/// Get the ICP transactions of a principal's account (or a subaccount) from the ICP index
+pub async fn get_icp_account_transactions_default_path(
+    principal: Principal,
+    subaccount: Option<[u8; 32]>,
+    max_results: u64,
+) -> Result<AccountTransactions> {
+    let agent = app_context().anonymous_agent().await?;
+    let account = icp_account_id(
+        principal,
+        subaccount.map(ic_ledger_types::Subaccount).as_ref(),
+    );
+    get_account_identifier_transactions(
+        &agent,
+        network_config().icp_index_canister,
+        &account,
+        None,
+        max_results,
+    )
+    .await
+}
@@ -1,19 +1,63 @@
 // ICP Ledger operations
 
+use super::canister_call;
+use super::canister_client::CanisterClient;
+use crate::core::utils::exit_code::{ValidationError, invalid};
 use anyhow::{Context, Result};
 use candid::{Decode, Nat, Principal, encode_args};
-use ic_agent::Agent;
 use ic_ledger_types::Subaccount;
 use sha2::{Digest, Sha256};
+use std::time::Duration;
+use tokio::time::Instant;
 
 use super::super::declarations::icp_ledger::{
-    Account as LedgerAccount, TransferArg, TransferResult,
+    Account as LedgerAccount, AccountBalanceArgs, Block as IcpBlock, GetBlocksArgs,
+    Operation as IcpOperation, QueryArchiveResult, QueryBlocksResponse, Tokens, TransferArg,
+    TransferResult,
 };
 use super::super::declarations::sns_ledger::{
-    Account as SnsLedgerAccount, Result2 as SnsTransferResult, TransferArg as SnsTransferArg,
+    Account as SnsLedgerAccount, GetBlocksRequest, GetTransactionsResponse, MetadataValue,
+    Result2 as SnsTransferResult, Transaction as SnsTransaction, TransactionRange,
+    TransferArg as SnsTransferArg,
 };
 
+/// A single ledger transaction, normalized for display across the ICP and SNS ledgers
+pub struct LedgerHistoryEntry {
+    pub index: u64,
+    pub kind: String,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub amount_e8s: u64,
+    pub memo: Option<String>,
+    pub timestamp_nanos: u64,
+}
+
+/// Transactions of one account as an index canister reports them
+pub struct AccountTransactions {
+    /// Balance of the account according to the index
+    pub balance_e8s: u64,
+    /// Newest first
+    pub transactions: Vec<LedgerHistoryEntry>,
+    /// Id of the account's oldest transaction; older pages end there
+    pub oldest_tx_id: Option<u64>,
+}
+
+/// How far an index canister has caught up with its ledger
+pub struct IndexSync {
+    pub blocks_synced: u64,
+    pub ledger_blocks: u64,
+}
+
+impl IndexSync {
+    /// Whether the index has seen every ledger block
+    #[must_use]
+    pub const fn is_synced(&self) -> bool {
+        self.blocks_synced >= self.ledger_blocks
+    }
+}
+
 /// Generate neuron subaccount (matches Rust implementation from test code)
+#[must_use]
 pub fn generate_subaccount_by_nonce(nonce: u64, principal: Principal) -> Subaccount {
     let mut hasher = Sha256::new();
     hasher.update([0x0c]);
@@ -26,13 +70,63 @@ pub fn generate_subaccount_by_nonce(nonce: u64, principal: Principal) -> Subacco
     Subaccount(subaccount)
 }
 
+/// SNS governance subaccount holding the treasury tokens (token distribution nonce 0)
+#[must_use]
+pub fn sns_treasury_subaccount(governance_canister: Principal) -> Subaccount {
+    let mut hasher = Sha256::new();
+    hasher.update([0x12]);
+    hasher.update(b"token-distribution");
+    hasher.update(governance_canister.as_slice());
+    hasher.update(0u64.to_be_bytes());
+    Subaccount(hasher.finalize().into())
+}
+
+/// Memo for a new neuron when none is given: the principal's neuron count + 1, so each new
+/// neuron gets its own staking subaccount
+#[must_use]
+pub const fn auto_neuron_memo(neuron_count: usize) -> u64 {
+    neuron_count as u64 + 1
+}
+
+/// Legacy ICP ledger account identifier (hex) of an owner and subaccount
+#[must_use]
+pub fn icp_account_id(owner: Principal, subaccount: Option<&Subaccount>) -> String {
+    ic_ledger_types::AccountIdentifier::new(
+        &owner,
+        subaccount.unwrap_or(&ic_ledger_types::DEFAULT_SUBACCOUNT),
+    )
+    .to_string()
+}
+
+/// Parse a 32-byte subaccount from hex (an optional `0x` prefix is accepted)
+pub fn parse_subaccount(hex_str: &str) -> Result<Subaccount> {
+    let bytes = hex::decode(hex_str.strip_prefix("0x").unwrap_or(hex_str))
+        .context(ValidationError::new("Failed to decode subaccount from hex"))?;
+    let bytes: [u8; 32] = bytes
+        .try_into()
+        .map_err(|b: Vec<u8>| invalid(format!("Subaccount must be 32 bytes, got {}", b.len())))?;
+    Ok(Subaccount(bytes))
+}
+
 /// Transfer ICP using icrc1_transfer (for general use)
 pub async fn transfer_icp(
-    agent: &Agent,
+    agent: &dyn CanisterClient,
     ledger_canister: Principal,
     to: Principal,
     amount: u64,
     subaccount: Option<Vec<u8>>,
+) -> Result<u64> {
+    transfer_icp_from_subaccount(agent, ledger_canister, None, to, amount, subaccount).await
+}
+
+/// Transfer ICP from a subaccount of the caller (None for the default account)
+pub async fn transfer_icp_from_subaccount(
+    agent: &dyn CanisterClient,
+    ledger_canister: Principal,
+    from_subaccount: Option<Vec<u8>>,
+    to: Principal,
+    amount: u64,
+    subaccount: Option<Vec<u8>>,
 ) -> Result<u64> {
     // Use icrc1_transfer with correct types from ICP ledger
     let args = TransferArg {
@@ -42,17 +136,19 @@ pub async fn transfer_icp(
         },
         fee: None,
         memo: None,
-        from_subaccount: None,
+        from_subaccount,
         created_at_time: None,
         amount: Nat::from(amount),
     };
 
-    let result_bytes = agent
-        .update(&ledger_canister, "icrc1_transfer")
-        .with_arg(encode_args((args,))?)
-        .call_and_wait()
-        .await
-        .context("Failed to call icrc1_transfer")?;
+    let result_bytes = canister_call::update(
+        agent,
+        &ledger_canister,
+        "icrc1_transfer",
+        encode_args((args,))?,
+    )
+    .await
+    .context("Failed to call icrc1_transfer")?;
 
     let result: TransferResult =
         Decode!(&result_bytes, TransferResult).context("Failed to decode transfer result")?;
@@ -71,11 +167,11 @@ pub async fn transfer_icp(
 }
 
 /// Get SNS ledger transfer fee
-pub async fn get_sns_ledger_fee(agent: &Agent, ledger_canister: Principal) -> Result<u64> {
-    let result_bytes = agent
-        .query(&ledger_canister, "icrc1_fee")
-        .with_arg(encode_args(())?)
-        .call()
+pub async fn get_sns_ledger_fee(
+    agent: &dyn CanisterClient,
+    ledger_canister: Principal,
+) -> Result<u64> {
+    let result_bytes = canister_call::query(agent, &ledger_canister, "icrc1_fee", encode_args(())?)
         .await
         .context("Failed to call icrc1_fee")?;
 
@@ -86,9 +182,112 @@ pub async fn get_sns_ledger_fee(agent: &Agent, ledger_canister: Principal) -> Re
     Ok(digits.first().copied().unwrap_or(0))
 }
 
+/// Number of decimals of a ledger (the ICP and SNS ledgers both implement `icrc1_decimals`)
+pub async fn get_ledger_decimals(
+    agent: &dyn CanisterClient,
+    ledger_canister: Principal,
+) -> Result<u8> {
+    let result_bytes =
+        canister_call::query(agent, &ledger_canister, "icrc1_decimals", encode_args(())?)
+            .await
+            .context("Failed to call icrc1_decimals")?;
+
+    Decode!(&result_bytes, u8).context("Failed to decode decimals")
+}
+
+/// What an ICRC-1 ledger says about its token
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct TokenInfo {
+    pub ledger: String,
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub fee_e8s: u64,
+    pub total_supply_e8s: u64,
+    /// `principal` or `principal.subaccount`; None if the ledger cannot mint
+    pub minting_account: Option<String>,
+    /// Every `icrc1_metadata` entry, values rendered as text
+    pub metadata: Vec<(String, String)>,
+}
+
+/// Query a ledger's ICRC-1 name, symbol, decimals, fee, total supply, minting account and
+/// metadata (the ICP and SNS ledgers both implement them)
+pub async fn get_token_info(
+    agent: &dyn CanisterClient,
+    ledger_canister: Principal,
+) -> Result<TokenInfo> {
+    let text_query = |method: &'static str| async move {
+        let bytes = canister_call::query(agent, &ledger_canister, method, encode_args(())?)
+            .await
+            .with_context(|| format!("Failed to call {method}"))?;
+        Decode!(&bytes, String).with_context(|| format!("Failed to decode {method} response"))
+    };
+    let total_supply = async {
+        let bytes = canister_call::query(
+            agent,
+            &ledger_canister,
+            "icrc1_total_supply",
+            encode_args(())?,
+        )
+        .await
+        .context("Failed to call icrc1_total_supply")?;
+        Decode!(&bytes, Nat).context("Failed to decode total supply")
+    };
+    let minting_account = async {
+        let bytes = canister_call::query(
+            agent,
+            &ledger_canister,
+            "icrc1_minting_account",
+            encode_args(())?,
+        )
+        .await
+        .context("Failed to call icrc1_minting_account")?;
+        Decode!(&bytes, Option<SnsLedgerAccount>).context("Failed to decode minting account")
+    };
+    let metadata = async {
+        let bytes =
+            canister_call::query(agent, &ledger_canister, "icrc1_metadata", encode_args(())?)
+                .await
+                .context("Failed to call icrc1_metadata")?;
+        Decode!(&bytes, Vec<(String, MetadataValue)>).context("Failed to decode metadata")
+    };
+
+    let (name, symbol, decimals, fee_e8s, total_supply, minting_account, metadata) = tokio::try_join!(
+        text_query("icrc1_name"),
+        text_query("icrc1_symbol"),
+        get_ledger_decimals(agent, ledger_canister),
+        get_sns_ledger_fee(agent, ledger_canister),
+        total_supply,
+        minting_account,
+        metadata,
+    )?;
+
+    Ok(TokenInfo {
+        ledger: ledger_canister.to_text(),
+        name,
+        symbol,
+        decimals,
+        fee_e8s,
+        total_supply_e8s: nat_to_u64(&total_supply),
+        minting_account: minting_account.as_ref().map(format_sns_account),
+        metadata: metadata
+            .into_iter()
+            .map(|(key, value)| {
+                let value = match value {
+                    MetadataValue::Int(n) => n.to_string(),
+                    MetadataValue::Nat(n) => n.to_string(),
+                    MetadataValue::Blob(bytes) => hex::encode(bytes),
+                    MetadataValue::Text(text) => text,
+                };
+                (key, value)
+            })
+            .collect(),
+    })
+}
+
 /// Get ICP ledger balance for an account
 pub async fn get_icp_ledger_balance(
-    agent: &Agent,
+    agent: &dyn CanisterClient,
     ledger_canister: Principal,
     account: Principal,
     subaccount: Option<Vec<u8>>,
@@ -98,12 +297,14 @@ pub async fn get_icp_ledger_balance(
         subaccount,
     };
 
-    let result_bytes = agent
-        .query(&ledger_canister, "icrc1_balance_of")
-        .with_arg(encode_args((account,))?)
-        .call()
-        .await
-        .context("Failed to call icrc1_balance_of")?;
+    let result_bytes = canister_call::query(
+        agent,
+        &ledger_canister,
+        "icrc1_balance_of",
+        encode_args((account,))?,
+    )
+    .await
+    .context("Failed to call icrc1_balance_of")?;
 
     let balance: Nat = Decode!(&result_bytes, Nat).context("Failed to decode balance")?;
 
@@ -112,9 +313,62 @@ pub async fn get_icp_ledger_balance(
     Ok(digits.first().copied().unwrap_or(0))
 }
 
+/// Minting account of the ICP ledger: transfers from it mint new ICP
+pub async fn get_icp_minting_account(
+    agent: &dyn CanisterClient,
+    ledger_canister: Principal,
+) -> Result<Option<LedgerAccount>> {
+    let result_bytes = canister_call::query(
+        agent,
+        &ledger_canister,
+        "icrc1_minting_account",
+        encode_args(())?,
+    )
+    .await
+    .context("Failed to call icrc1_minting_account")?;
+
+    Decode!(&result_bytes, Option<LedgerAccount>).context("Failed to decode minting account")
+}
+
+/// Whether `value` looks like a legacy account identifier (64 hex chars) rather than a principal
+#[must_use]
+pub fn is_account_id_hex(value: &str) -> bool {
+    value.len() == 64 && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Parse a legacy account identifier from hex, verifying its checksum
+pub fn parse_account_id(hex_str: &str) -> Result<ic_ledger_types::AccountIdentifier> {
+    ic_ledger_types::AccountIdentifier::from_hex(hex_str)
+        .map_err(|e| invalid(format!("Invalid account identifier: {}", e)))
+}
+
+/// Get ICP ledger balance for a legacy account identifier
+/// Uses the ledger's `account_balance` method, for accounts that only surface as account IDs
+pub async fn get_icp_ledger_balance_by_account_id(
+    agent: &dyn CanisterClient,
+    ledger_canister: Principal,
+    account_id: &ic_ledger_types::AccountIdentifier,
+) -> Result<u64> {
+    let args = AccountBalanceArgs {
+        account: account_id.as_bytes().to_vec(),
+    };
+
+    let result_bytes = canister_call::query(
+        agent,
+        &ledger_canister,
+        "account_balance",
+        encode_args((args,))?,
+    )
+    .await
+    .context("Failed to call account_balance")?;
+
+    let tokens = Decode!(&result_bytes, Tokens).context("Failed to decode balance")?;
+    Ok(tokens.e8s)
+}
+
 /// Get SNS ledger balance for an account
 pub async fn get_sns_ledger_balance(
-    agent: &Agent,
+    agent: &dyn CanisterClient,
     ledger_canister: Principal,
     account: Principal,
     subaccount: Option<Vec<u8>>,
@@ -124,12 +378,14 @@ pub async fn get_sns_ledger_balance(
         subaccount,
     };
 
-    let result_bytes = agent
-        .query(&ledger_canister, "icrc1_balance_of")
-        .with_arg(encode_args((account,))?)
-        .call()
-        .await
-        .context("Failed to call icrc1_balance_of")?;
+    let result_bytes = canister_call::query(
+        agent,
+        &ledger_canister,
+        "icrc1_balance_of",
+        encode_args((account,))?,
+    )
+    .await
+    .context("Failed to call icrc1_balance_of")?;
 
     let balance: Nat = Decode!(&result_bytes, Nat).context("Failed to decode balance")?;
 
@@ -138,13 +394,84 @@ pub async fn get_sns_ledger_balance(
     Ok(digits.first().copied().unwrap_or(0))
 }
 
+/// How often to check the balance while waiting for a transfer to land
+const BALANCE_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How long to wait for a transfer to land before giving up
+const BALANCE_POLL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Wait until an ICP ledger account holds at least `minimum_e8s`
+/// Returns as soon as the transfer is visible, so a neuron can be claimed right away
+pub async fn wait_for_icp_balance(
+    agent: &dyn CanisterClient,
+    ledger_canister: Principal,
+    account: Principal,
+    subaccount: Option<Vec<u8>>,
+    minimum_e8s: u64,
+) -> Result<u64> {
+    wait_for_balance(minimum_e8s, || {
+        get_icp_ledger_balance(agent, ledger_canister, account, subaccount.clone())
+    })
+    .await
+}
+
+/// Wait until an SNS ledger account holds at least `minimum_e8s`
+/// Returns as soon as the transfer is visible, so a neuron can be claimed right away
+pub async fn wait_for_sns_balance(
+    agent: &dyn CanisterClient,
+    ledger_canister: Principal,
+    account: Principal,
+    subaccount: Option<Vec<u8>>,
+    minimum_e8s: u64,
+) -> Result<u64> {
+    wait_for_balance(minimum_e8s, || {
+        get_sns_ledger_balance(agent, ledger_canister, account, subaccount.clone())
+    })
+    .await
+}
+
+async fn wait_for_balance<F, Fut>(minimum_e8s: u64, mut fetch_balance: F) -> Result<u64>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<u64>>,
+{
+    let deadline = Instant::now() + BALANCE_POLL_TIMEOUT;
+    loop {
+        let balance = fetch_balance().await?;
+        if balance >= minimum_e8s {
+            return Ok(balance);
+        }
+        if Instant::now() >= deadline {
+            anyhow::bail!(
+                "Timed out after {}s waiting for the transfer to land (balance: {} e8s, expected at least {} e8s)",
+                BALANCE_POLL_TIMEOUT.as_secs(),
+                balance,
+                minimum_e8s
+            );
+        }
+        tokio::time::sleep(BALANCE_POLL_INTERVAL).await;
+    }
+}
+
 /// Transfer SNS tokens using icrc1_transfer
 pub async fn transfer_sns_tokens(
-    agent: &Agent,
+    agent: &dyn CanisterClient,
     ledger_canister: Principal,
     to: Principal,
     amount: u64,
     subaccount: Option<Vec<u8>>,
+) -> Result<u64> {
+    transfer_sns_tokens_from_subaccount(agent, ledger_canister, None, to, amount, subaccount).await
+}
+
+/// Transfer SNS tokens from a subaccount of the caller (None for the default account)
+pub async fn transfer_sns_tokens_from_subaccount(
+    agent: &dyn CanisterClient,
+    ledger_canister: Principal,
+    from_subaccount: Option<Vec<u8>>,
+    to: Principal,
+    amount: u64,
+    subaccount: Option<Vec<u8>>,
 ) -> Result<u64> {
     let args = SnsTransferArg {
         to: SnsLedgerAccount {
@@ -153,17 +480,19 @@ pub async fn transfer_sns_tokens(
         },
         fee: None,
         memo: None,
-        from_subaccount: None,
+        from_subaccount,
         created_at_time: None,
         amount: Nat::from(amount),
     };
 
-    let result_bytes = agent
-        .update(&ledger_canister, "icrc1_transfer")
-        .with_arg(encode_args((args,))?)
-        .call_and_wait()
-        .await
-        .context("Failed to call icrc1_transfer")?;
+    let result_bytes = canister_call::update(
+        agent,
+        &ledger_canister,
+        "icrc1_transfer",
+        encode_args((args,))?,
+    )
+    .await
+    .context("Failed to call icrc1_transfer")?;
 
     let result: SnsTransferResult =
         Decode!(&result_bytes, SnsTransferResult).context("Failed to decode transfer result")?;
@@ -179,3 +508,375 @@ pub async fn transfer_sns_tokens(
         }
     }
 }
+
+/// Query a range of blocks from the ICP ledger
+pub async fn query_icp_blocks(
+    agent: &dyn CanisterClient,
+    ledger_canister: Principal,
+    start: u64,
+    length: u64,
+) -> Result<QueryBlocksResponse> {
+    let args = GetBlocksArgs { start, length };
+
+    let result_bytes = canister_call::query(
+        agent,
+        &ledger_canister,
+        "query_blocks",
+        encode_args((args,))?,
+    )
+    .await
+    .context("Failed to call query_blocks")?;
+
+    Decode!(&result_bytes, QueryBlocksResponse).context("Failed to decode query_blocks response")
+}
+
+/// Get the most recent `limit` transactions from the ICP ledger (newest first)
+/// Blocks that have been moved to archive canisters are fetched from the archives
+pub async fn get_icp_ledger_history(
+    agent: &dyn CanisterClient,
+    ledger_canister: Principal,
+    limit: u64,
+) -> Result<Vec<LedgerHistoryEntry>> {
+    // First call only to learn the chain length
+    let tip = query_icp_blocks(agent, ledger_canister, 0, 0).await?;
+    let start = tip.chain_length.saturating_sub(limit);
+    let response = query_icp_blocks(agent, ledger_canister, start, limit).await?;
+
+    let mut blocks: Vec<(u64, IcpBlock)> = Vec::new();
+
+    for archived in response.archived_blocks {
+        let args = GetBlocksArgs {
+            start: archived.start,
+            length: archived.length,
+        };
+        let result_bytes = canister_call::query(
+            agent,
+            &archived.callback.0.principal,
+            &archived.callback.0.method,
+            encode_args((args,))?,
+        )
+        .await
+        .context("Failed to query archived blocks")?;
+        let result: QueryArchiveResult = Decode!(&result_bytes, QueryArchiveResult)
+            .context("Failed to decode archived blocks")?;
+        match result {
+            QueryArchiveResult::Ok(range) => {
+                blocks.extend((archived.start..).zip(range.blocks));
+            }
+            QueryArchiveResult::Err(e) => {
+                anyhow::bail!("Failed to query archived blocks: {e:?}");
+            }
+        }
+    }
+
+    blocks.extend((response.first_block_index..).zip(response.blocks));
+
+    let mut entries: Vec<LedgerHistoryEntry> = blocks
+        .into_iter()
+        .map(|(index, block)| icp_block_to_entry(index, block))
+        .collect();
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.index));
+    Ok(entries)
+}
+
+fn icp_block_to_entry(index: u64, block: IcpBlock) -> LedgerHistoryEntry {
+    let transaction = block.transaction;
+    let (kind, from, to, amount_e8s) = match transaction.operation {
+        Some(IcpOperation::Transfer {
+            from, to, amount, ..
+        }) => (
+            "transfer",
+            Some(hex::encode(from)),
+            Some(hex::encode(to)),
+            amount.e8s,
+        ),
+        Some(IcpOperation::Mint { to, amount }) => {
+            ("mint", None, Some(hex::encode(to)), amount.e8s)
+        }
+        Some(IcpOperation::Burn { from, amount, .. }) => {
+            ("burn", Some(hex::encode(from)), None, amount.e8s)
+        }
+        Some(IcpOperation::Approve { from, spender, .. }) => (
+            "approve",
+            Some(hex::encode(from)),
+            Some(hex::encode(spender)),
+            0,
+        ),
+        None => ("unknown", None, None, 0),
+    };
+
+    // Prefer the ICRC-1 memo when present, otherwise fall back to the legacy u64 memo
+    let memo = match transaction.icrc1_memo {
+        Some(bytes) => Some(hex::encode(bytes)),
+        None if transaction.memo != 0 => Some(transaction.memo.to_string()),
+        None => None,
+    };
+
+    LedgerHistoryEntry {
+        index,
+        kind: kind.to_string(),
+        from,
+        to,
+        amount_e8s,
+        memo,
+        timestamp_nanos: block.timestamp.timestamp_nanos,
+    }
+}
+
+/// Get a range of transactions from an SNS (ICRC-1) ledger
+pub async fn get_sns_transactions(
+    agent: &dyn CanisterClient,
+    ledger_canister: Principal,
+    start: u64,
+    length: u64,
+) -> Result<GetTransactionsResponse> {
+    let request = GetBlocksRequest {
+        start: Nat::from(start),
+        length: Nat::from(length),
+    };
+
+    let result_bytes = canister_call::query(
+        agent,
+        &ledger_canister,
+        "get_transactions",
+        encode_args((request,))?,
+    )
+    .await
+    .context("Failed to call get_transactions")?;
+
+    Decode!(&result_bytes, GetTransactionsResponse)
+        .context("Failed to decode get_transactions response")
+}
+
+/// Get the most recent `limit` transactions from an SNS ledger (newest first)
+/// Transactions that have been moved to archive canisters are fetched from the archives
+pub async fn get_sns_ledger_history(
+    agent: &dyn CanisterClient,
+    ledger_canister: Principal,
+    limit: u64,
+) -> Result<Vec<LedgerHistoryEntry>> {
+    // First call only to learn the log length
+    let tip = get_sns_transactions(agent, ledger_canister, 0, 0).await?;
+    let log_length = nat_to_u64(&tip.log_length);
+    let start = log_length.saturating_sub(limit);
+    let response = get_sns_transactions(agent, ledger_canister, start, limit).await?;
+
+    let mut transactions: Vec<(u64, SnsTransaction)> = Vec::new();
+
+    for archived in response.archived_transactions {
+        let archived_start = nat_to_u64(&archived.start);
+        let request = GetBlocksRequest {
+            start: archived.start,
+            length: archived.length,
+        };
+        let result_bytes = canister_call::query(
+            agent,
+            &archived.callback.0.principal,
+            &archived.callback.0.method,
+            encode_args((request,))?,
+        )
+        .await
+        .context("Failed to query archived transactions")?;
+        let range: TransactionRange = Decode!(&result_bytes, TransactionRange)
+            .context("Failed to decode archived transactions")?;
+        transactions.extend((archived_start..).zip(range.transactions));
+    }
+
+    transactions.extend((nat_to_u64(&response.first_index)..).zip(response.transactions));
+
+    let mut entries: Vec<LedgerHistoryEntry> = transactions
+        .into_iter()
+        .map(|(index, transaction)| sns_transaction_to_entry(index, transaction))
+        .collect();
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.index));
+    Ok(entries)
+}
+
+pub(crate) fn sns_transaction_to_entry(
+    index: u64,
+    transaction: SnsTransaction,
+) -> LedgerHistoryEntry {
+    let (from, to, amount, memo) = if let Some(transfer) = transaction.transfer {
+        (
+            Some(format_sns_account(&transfer.from)),
+            Some(format_sns_account(&transfer.to)),
+            transfer.amount,
+            transfer.memo,
+        )
+    } else if let Some(mint) = transaction.mint {
+        (
+            None,
+            Some(format_sns_account(&mint.to)),
+            mint.amount,
+            mint.memo,
+        )
+    } else if let Some(burn) = transaction.burn {
+        (
+            Some(format_sns_account(&burn.from)),
+            None,
+            burn.amount,
+            burn.memo,
+        )
+    } else if let Some(approve) = transaction.approve {
+        (
+            Some(format_sns_account(&approve.from)),
+            Some(format_sns_account(&approve.spender)),
+            approve.amount,
+            approve.memo,
+        )
+    } else {
+        (None, None, Nat::from(0u64), None)
+    };
+
+    LedgerHistoryEntry {
+        index,
+        kind: transaction.kind,
+        from,
+        to,
+        amount_e8s: nat_to_u64(&amount),
+        memo: memo.map(hex::encode),
+        timestamp_nanos: transaction.timestamp,
+    }
+}
+
+/// Format an ICRC-1 account as `principal` or `principal.subaccount_hex` for non-default subaccounts
+fn format_sns_account(account: &SnsLedgerAccount) -> String {
+    match &account.subaccount {
+        Some(sub) if sub.iter().any(|b| *b != 0) => {
+            format!("{}.{}", account.owner, hex::encode(sub))
+        }
+        _ => account.owner.to_string(),
+    }
+}
+
+pub(crate) fn nat_to_u64(value: &Nat) -> u64 {
+    // Convert candid::Nat to u64
+    let digits = value.0.to_u64_digits();
+    digits.first().copied().unwrap_or(0)
+}
+
+/// Transfer ICP signed as `from` (owner, participant or extra identity), between any
+/// subaccounts of the two principals. Returns the block height
+pub async fn transfer_icp_default_path(
+    from: Principal,
+    from_subaccount: Option<Vec<u8>>,
+    to: Principal,
+    to_subaccount: Option<Vec<u8>>,
+    amount_e8s: u64,
+) -> Result<u64> {
+    let agent = crate::core::context::app_context()
+        .agent_for_principal(from)
+        .await
+        .with_context(|| format!("Cannot sign as {from}"))?;
+    transfer_icp_from_subaccount(
+        &agent,
+        crate::core::utils::network::network_config().ledger_canister,
+        from_subaccount,
+        to,
+        amount_e8s,
+        to_subaccount,
+    )
+    .await
+}
+
+/// Transfer SNS tokens signed as `from`, between any subaccounts of the two principals
+/// Returns the block height
+pub async fn transfer_sns_tokens_default_path(
+    from: Principal,
+    from_subaccount: Option<Vec<u8>>,
+    to: Principal,
+    to_subaccount: Option<Vec<u8>>,
+    amount_e8s: u64,
+) -> Result<u64> {
+    let context = crate::core::context::app_context();
+    let agent = context
+        .agent_for_principal(from)
+        .await
+        .with_context(|| format!("Cannot sign as {from}"))?;
+    transfer_sns_tokens_from_subaccount(
+        &agent,
+        context.sns_ledger_canister()?,
+        from_subaccount,
+        to,
+        amount_e8s,
+        to_subaccount,
+    )
+    .await
+}
+
+/// ICP and SNS holdings of one principal (see `balances`)
+/// Fields are `None` when the call failed, e.g. a missing seed file for the ICP neurons
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct AccountBalances {
+    /// `owner`, `participant-N` or the name of an extra identity, followed by any aliases
+    pub label: String,
+    pub principal: String,
+    pub icp_e8s: Option<u64>,
+    pub icp_staked_e8s: Option<u64>,
+    pub sns_e8s: Option<u64>,
+    pub sns_staked_e8s: Option<u64>,
+}
+
+/// Balances and staked amounts of the owner and every participant, fetched concurrently
+pub async fn get_balance_overview() -> Result<Vec<AccountBalances>> {
+    let data = crate::core::context::app_context().deployment_data()?;
+    futures::future::try_join_all(
+        data.labelled_accounts()
+            .into_iter()
+            .map(|(label, principal)| account_balances(label, principal)),
+    )
+    .await
+}
+
+async fn account_balances(label: String, principal_text: String) -> Result<AccountBalances> {
+    use super::governance_ops::list_icp_neurons_for_principal_default_path;
+    use super::sns_governance_ops::list_neurons_for_principal;
+    use crate::core::context::app_context;
+    use crate::core::utils::network::network_config;
+
+    let principal = Principal::from_text(&principal_text)
+        .with_context(|| format!("Failed to parse principal of {}", label))?;
+    let agent = app_context().anonymous_agent().await?;
+    let sns_ledger = app_context().sns_ledger_canister()?;
+    let sns_governance = app_context().sns_governance_canister()?;
+
+    let (icp, icp_neurons, sns, sns_neurons) = tokio::join!(
+        get_icp_ledger_balance(&agent, network_config().ledger_canister, principal, None),
+        list_icp_neurons_for_principal_default_path(principal),
+        get_sns_ledger_balance(&agent, sns_ledger, principal, None),
+        list_neurons_for_principal(&agent, sns_governance, principal),
+    );
+
+    // One failed call leaves its column empty instead of failing the whole table
+    let ok = |what: &str, result: Result<u64>| match result {
+        Ok(value) => Some(value),
+        Err(e) => {
+            log::warn!("Failed to get {} of {}: {:#}", what, label, e);
+            None
+        }
+    };
+    Ok(AccountBalances {
+        icp_e8s: ok("ICP balance", icp),
+        icp_staked_e8s: ok(
+            "ICP neurons",
+            icp_neurons.map(|neurons| {
+                neurons
+                    .iter()
+                    .map(|n| n.cached_neuron_stake_e8s)
+                    .fold(0u64, u64::saturating_add)
+            }),
+        ),
+        sns_e8s: ok("SNS balance", sns),
+        sns_staked_e8s: ok(
+            "SNS neurons",
+            sns_neurons.map(|neurons| {
+                neurons
+                    .iter()
+                    .map(|n| n.cached_neuron_stake_e8s)
+                    .fold(0u64, u64::saturating_add)
+            }),
+        ),
+        label,
+        principal: principal_text,
+    })
+}
@@ -0,0 +1,318 @@
+// Candid argument and reply types of the canister methods this tool calls, taken from the
+// declarations, so logged payloads show field names instead of label hashes
+
+use candid::types::internal::{Label, TypeContainer, TypeInner, find_type};
+use candid::types::value::{IDLField, IDLValue, VariantValue};
+use candid::types::{Type, TypeEnv};
+use candid::{CandidType, IDLArgs, Nat, Principal};
+
+use super::super::declarations::{
+    icp_governance, icp_index, icp_ledger, sns_governance, sns_index, sns_ledger, sns_root,
+    sns_swap, sns_wasm,
+};
+use crate::core::utils::constants::CYCLES_LEDGER_CANISTER;
+use crate::core::utils::network::network_config;
+
+/// Which half of a call an encoded argument list belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    Request,
+    Reply,
+}
+
+/// Argument and reply types of one method
+#[derive(Clone, Copy)]
+struct Signature {
+    args: fn(&mut TypeContainer) -> Vec<Type>,
+    reply: fn(&mut TypeContainer) -> Vec<Type>,
+}
+
+const fn sig(
+    args: fn(&mut TypeContainer) -> Vec<Type>,
+    reply: fn(&mut TypeContainer) -> Vec<Type>,
+) -> Signature {
+    Signature { args, reply }
+}
+
+fn one<T: CandidType>(types: &mut TypeContainer) -> Vec<Type> {
+    vec![types.add::<T>()]
+}
+
+const fn none(_: &mut TypeContainer) -> Vec<Type> {
+    Vec::new()
+}
+
+/// Decode a request or reply of `method` on `canister`, naming fields from the method's types
+/// Every value on the wire is kept, including fields the declarations leave out; only the label
+/// hashes the declared types know are replaced by their names. Methods without a declared
+/// signature decode as plain untyped candid.
+pub fn decode(
+    canister: &Principal,
+    method: &str,
+    part: Part,
+    bytes: &[u8],
+) -> candid::Result<IDLArgs> {
+    let mut args = IDLArgs::from_bytes(bytes)?;
+    for signature in signatures(canister, method) {
+        let mut types = TypeContainer::new();
+        let expected = match part {
+            Part::Request => (signature.args)(&mut types),
+            Part::Reply => (signature.reply)(&mut types),
+        };
+        for (value, ty) in args.args.iter_mut().zip(&expected) {
+            name_fields(value, ty, &types.env);
+        }
+    }
+    Ok(args)
+}
+
+/// Replace hashed record and variant labels in `value` with the names `ty` gives them
+/// Parts of the value that do not match the type are left as they are.
+fn name_fields(value: &mut IDLValue, ty: &Type, env: &TypeEnv) {
+    match (value, ty.as_ref()) {
+        (value, TypeInner::Var(name)) => {
+            if let Ok(ty) = env.rec_find_type(name) {
+                name_fields(value, &ty.clone(), env);
+            }
+        }
+        (value, TypeInner::Knot(id)) => {
+            if let Some(ty) = find_type(id) {
+                name_fields(value, &ty, env);
+            }
+        }
+        (IDLValue::Opt(inner), TypeInner::Opt(ty)) => name_fields(inner, ty, env),
+        (IDLValue::Vec(items), TypeInner::Vec(ty)) => {
+            for item in items {
+                name_fields(item, ty, env);
+            }
+        }
+        (IDLValue::Record(fields), TypeInner::Record(declared)) => {
+            for field in fields {
+                name_field(field, declared, env);
+            }
+        }
+        (IDLValue::Variant(VariantValue(field, _)), TypeInner::Variant(declared)) => {
+            name_field(field, declared, env);
+        }
+        _ => {}
+    }
+}
+
+fn name_field(field: &mut IDLField, declared: &[candid::types::Field], env: &TypeEnv) {
+    let Some(declared) = declared.iter().find(|d| d.id.get_id() == field.id.get_id()) else {
+        return;
+    };
+    if let (Label::Id(_), Label::Named(name)) = (&field.id, declared.id.as_ref()) {
+        field.id = Label::Named(name.clone());
+    }
+    name_fields(&mut field.val, &declared.ty, env);
+}
+
+/// Signatures that may apply, all of which are used to name fields
+/// NNS canisters are recognized by principal; any other canister is taken to be part of an SNS
+/// (or one of its ledger archives), where method names do not overlap.
+fn signatures(canister: &Principal, method: &str) -> Vec<Signature> {
+    let network = network_config();
+    if *canister == network.governance_canister {
+        return nns_governance(method).into_iter().collect();
+    }
+    if *canister == network.ledger_canister {
+        return icp_ledger(method).into_iter().collect();
+    }
+    if *canister == network.icp_index_canister {
+        return icp_index(method).into_iter().collect();
+    }
+    if *canister == network.snsw_canister {
+        return sns_wasm(method).into_iter().collect();
+    }
+    if canister.to_text() == CYCLES_LEDGER_CANISTER {
+        return icrc1(method).into_iter().collect();
+    }
+
+    match method {
+        // An SNS ledger, or one of its archives
+        "get_transactions" => vec![
+            sig(
+                one::<sns_ledger::GetBlocksRequest>,
+                one::<sns_ledger::GetTransactionsResponse>,
+            ),
+            sig(
+                one::<sns_ledger::GetBlocksRequest>,
+                one::<sns_ledger::TransactionRange>,
+            ),
+        ],
+        // An ICP ledger archive
+        "get_blocks" => vec![sig(
+            one::<icp_ledger::GetBlocksArgs>,
+            one::<icp_ledger::QueryArchiveResult>,
+        )],
+        _ => sns_governance(method)
+            .or_else(|| sns_swap(method))
+            .or_else(|| sns_root(method))
+            .or_else(|| sns_index(method))
+            .or_else(|| icrc1(method))
+            .into_iter()
+            .collect(),
+    }
+}
+
+fn nns_governance(method: &str) -> Option<Signature> {
+    use icp_governance::{
+        ListNeurons, ListNeuronsResponse, ManageNeuronRequest, ManageNeuronResponse, ProposalInfo,
+        Result2,
+    };
+
+    Some(match method {
+        "manage_neuron" => sig(one::<ManageNeuronRequest>, one::<ManageNeuronResponse>),
+        "list_neurons" => sig(one::<ListNeurons>, one::<ListNeuronsResponse>),
+        "get_full_neuron" => sig(one::<u64>, one::<Result2>),
+        "get_proposal_info" => sig(one::<u64>, one::<Option<ProposalInfo>>),
+        _ => return None,
+    })
+}
+
+fn icp_ledger(method: &str) -> Option<Signature> {
+    use icp_ledger::{
+        Account, AccountBalanceArgs, GetBlocksArgs, QueryBlocksResponse, Tokens, TransferArg,
+        TransferResult,
+    };
+
+    Some(match method {
+        "icrc1_transfer" => sig(one::<TransferArg>, one::<TransferResult>),
+        "icrc1_balance_of" => sig(one::<Account>, one::<Nat>),
+        "icrc1_minting_account" => sig(none, one::<Option<Account>>),
+        "account_balance" => sig(one::<AccountBalanceArgs>, one::<Tokens>),
+        "query_blocks" => sig(one::<GetBlocksArgs>, one::<QueryBlocksResponse>),
+        _ => return icrc1(method),
+    })
+}
+
+/// ICRC-1 methods, typed as the SNS ledger declares them
+fn icrc1(method: &str) -> Option<Signature> {
+    use sns_ledger::{Account, MetadataValue, Result2, TransferArg};
+
+    Some(match method {
+        "icrc1_transfer" => sig(one::<TransferArg>, one::<Result2>),
+        "icrc1_balance_of" => sig(one::<Account>, one::<Nat>),
+        "icrc1_minting_account" => sig(none, one::<Option<Account>>),
+        "icrc1_fee" | "icrc1_total_supply" => sig(none, one::<Nat>),
+        "icrc1_decimals" => sig(none, one::<u8>),
+        "icrc1_name" | "icrc1_symbol" => sig(none, one::<String>),
+        "icrc1_metadata" => sig(none, one::<Vec<(String, MetadataValue)>>),
+        _ => return None,
+    })
+}
+
+fn icp_index(method: &str) -> Option<Signature> {
+    use icp_index::{
+        GetAccountIdentifierTransactionsArgs, GetAccountIdentifierTransactionsResult, Status,
+    };
+
+    Some(match method {
+        "get_account_identifier_transactions" => sig(
+            one::<GetAccountIdentifierTransactionsArgs>,
+            one::<GetAccountIdentifierTransactionsResult>,
+        ),
+        "status" => sig(none, one::<Status>),
+        _ => return None,
+    })
+}
+
+fn sns_wasm(method: &str) -> Option<Signature> {
+    use sns_wasm::{
+        GetDeployedSnsByProposalIdRequest, GetDeployedSnsByProposalIdResponse,
+        ListDeployedSnsesArg, ListDeployedSnsesResponse, ListUpgradeStepsRequest,
+        ListUpgradeStepsResponse,
+    };
+
+    Some(match method {
+        "get_deployed_sns_by_proposal_id" => sig(
+            one::<GetDeployedSnsByProposalIdRequest>,
+            one::<GetDeployedSnsByProposalIdResponse>,
+        ),
+        "list_deployed_snses" => sig(
+            one::<ListDeployedSnsesArg>,
+            one::<ListDeployedSnsesResponse>,
+        ),
+        "list_upgrade_steps" => sig(
+            one::<ListUpgradeStepsRequest>,
+            one::<ListUpgradeStepsResponse>,
+        ),
+        _ => return None,
+    })
+}
+
+fn sns_governance(method: &str) -> Option<Signature> {
+    use sns_governance::{
+        GetMetricsRequest, GetMetricsResponse, GetNeuron, GetNeuronResponse, GetProposal,
+        GetProposalResponse, GetRunningSnsVersionArg, GetRunningSnsVersionResponse,
+        GetUpgradeJournalRequest, GetUpgradeJournalResponse, ListNeurons, ListNeuronsResponse,
+        ListProposals, ListProposalsResponse, ListTopicsRequest, ListTopicsResponse, ManageNeuron,
+        ManageNeuronResponse, NervousSystemParameters,
+    };
+
+    Some(match method {
+        "manage_neuron" => sig(one::<ManageNeuron>, one::<ManageNeuronResponse>),
+        "list_neurons" => sig(one::<ListNeurons>, one::<ListNeuronsResponse>),
+        "get_neuron" => sig(one::<GetNeuron>, one::<GetNeuronResponse>),
+        "get_proposal" => sig(one::<GetProposal>, one::<GetProposalResponse>),
+        "list_proposals" => sig(one::<ListProposals>, one::<ListProposalsResponse>),
+        "get_nervous_system_parameters" => sig(none, one::<NervousSystemParameters>),
+        "get_metrics" => sig(one::<GetMetricsRequest>, one::<GetMetricsResponse>),
+        "get_running_sns_version" => sig(
+            one::<GetRunningSnsVersionArg>,
+            one::<GetRunningSnsVersionResponse>,
+        ),
+        "get_upgrade_journal" => sig(
+            one::<GetUpgradeJournalRequest>,
+            one::<GetUpgradeJournalResponse>,
+        ),
+        "list_topics" => sig(one::<ListTopicsRequest>, one::<ListTopicsResponse>),
+        _ => return None,
+    })
+}
+
+fn sns_swap(method: &str) -> Option<Signature> {
+    use sns_swap::{
+        ErrorRefundIcpRequest, ErrorRefundIcpResponse, FinalizeSwapArg, FinalizeSwapResponse,
+        GetLifecycleArg, GetLifecycleResponse, NewSaleTicketRequest, NewSaleTicketResponse,
+        RefreshBuyerTokensRequest, RefreshBuyerTokensResponse,
+    };
+
+    Some(match method {
+        "get_lifecycle" => sig(one::<GetLifecycleArg>, one::<GetLifecycleResponse>),
+        "new_sale_ticket" => sig(one::<NewSaleTicketRequest>, one::<NewSaleTicketResponse>),
+        "refresh_buyer_tokens" => sig(
+            one::<RefreshBuyerTokensRequest>,
+            one::<RefreshBuyerTokensResponse>,
+        ),
+        "finalize_swap" => sig(one::<FinalizeSwapArg>, one::<FinalizeSwapResponse>),
+        "error_refund_icp" => sig(one::<ErrorRefundIcpRequest>, one::<ErrorRefundIcpResponse>),
+        _ => return None,
+    })
+}
+
+fn sns_root(method: &str) -> Option<Signature> {
+    use sns_root::{GetSnsCanistersSummaryRequest, GetSnsCanistersSummaryResponse};
+
+    Some(match method {
+        "get_sns_canisters_summary" => sig(
+            one::<GetSnsCanistersSummaryRequest>,
+            one::<GetSnsCanistersSummaryResponse>,
+        ),
+        _ => return None,
+    })
+}
+
+fn sns_index(method: &str) -> Option<Signature> {
+    use sns_index::{GetAccountTransactionsArgs, GetTransactionsResult, Status};
+
+    Some(match method {
+        "get_account_transactions" => sig(
+            one::<GetAccountTransactionsArgs>,
+            one::<GetTransactionsResult>,
+        ),
+        "status" => sig(none, one::<Status>),
+        _ => return None,
+    })
+}
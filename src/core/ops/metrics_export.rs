@@ -0,0 +1,265 @@
+// Scrape governance metrics, swap state and balances into a Prometheus metrics page
+
+use anyhow::Result;
+
+use super::ledger_ops::{AccountBalances, get_balance_overview};
+use super::sns_governance_ops::{SnsMetrics, get_sns_metrics_default_path};
+use super::swap_ops::{SwapState, get_swap_state_default_path, lifecycle_name};
+use crate::core::utils::prometheus::Exposition;
+use crate::core::utils::time_format::now_secs;
+
+/// One scrape of the SNS; each source fails on its own so the others are still exported
+pub struct Scrape {
+    pub timestamp_seconds: u64,
+    pub metrics: Result<SnsMetrics>,
+    pub swap: Result<SwapState>,
+    pub balances: Result<Vec<AccountBalances>>,
+}
+
+/// Fetch governance metrics over `time_window_seconds`, the swap state and all balances
+pub async fn scrape_default_path(time_window_seconds: u64) -> Scrape {
+    let (metrics, swap, balances) = tokio::join!(
+        get_sns_metrics_default_path(time_window_seconds),
+        get_swap_state_default_path(),
+        get_balance_overview(),
+    );
+    Scrape {
+        timestamp_seconds: now_secs(),
+        metrics,
+        swap,
+        balances,
+    }
+}
+
+impl Scrape {
+    /// The scrape in Prometheus text format, metric names prefixed with `local_sns_`
+    #[must_use]
+    pub fn to_prometheus(&self) -> String {
+        let mut page = Exposition::new();
+
+        page.gauge(
+            "local_sns_scrape_success",
+            "Whether the last scrape of a source succeeded (1) or failed (0)",
+        );
+        for (source, ok) in [
+            ("governance", self.metrics.is_ok()),
+            ("swap", self.swap.is_ok()),
+            ("balances", self.balances.is_ok()),
+        ] {
+            page.sample(
+                "local_sns_scrape_success",
+                &[("source", source)],
+                f64::from(u8::from(ok)),
+            );
+        }
+        page.single(
+            "local_sns_scrape_timestamp_seconds",
+            "Unix time of the last scrape",
+            self.timestamp_seconds as f64,
+        );
+
+        if let Ok(metrics) = &self.metrics {
+            governance_metrics(&mut page, metrics);
+        }
+        if let Ok(swap) = &self.swap {
+            swap_metrics(&mut page, swap);
+        }
+        if let Ok(balances) = &self.balances {
+            balance_metrics(&mut page, balances);
+        }
+        page.into_text()
+    }
+
+    /// Messages of the sources that failed, for logging
+    #[must_use]
+    pub fn errors(&self) -> Vec<String> {
+        [
+            ("governance", self.metrics.as_ref().err()),
+            ("swap", self.swap.as_ref().err()),
+            ("balances", self.balances.as_ref().err()),
+        ]
+        .into_iter()
+        .filter_map(|(source, err)| err.map(|e| format!("{source}: {e:#}")))
+        .collect()
+    }
+}
+
+fn governance_metrics(page: &mut Exposition, metrics: &SnsMetrics) {
+    page.single(
+        "local_sns_neurons",
+        "Number of SNS neurons",
+        metrics.neuron_count as f64,
+    )
+    .single(
+        "local_sns_staked_e8s",
+        "Total stake of all SNS neurons",
+        metrics.total_staked_e8s as f64,
+    )
+    .single(
+        "local_sns_maturity_e8s",
+        "Total maturity of all SNS neurons",
+        metrics.total_maturity_e8s as f64,
+    );
+
+    let states = [
+        (
+            "not_dissolving",
+            metrics.not_dissolving_count,
+            metrics.not_dissolving_e8s,
+        ),
+        (
+            "dissolving",
+            metrics.dissolving_count,
+            metrics.dissolving_e8s,
+        ),
+        ("dissolved", metrics.dissolved_count, metrics.dissolved_e8s),
+    ];
+    page.gauge(
+        "local_sns_neurons_by_state",
+        "Number of SNS neurons by dissolve state",
+    );
+    for (state, count, _) in states {
+        page.sample(
+            "local_sns_neurons_by_state",
+            &[("state", state)],
+            count as f64,
+        );
+    }
+    page.gauge(
+        "local_sns_staked_by_state_e8s",
+        "Stake of SNS neurons by dissolve state",
+    );
+    for (state, _, e8s) in states {
+        page.sample(
+            "local_sns_staked_by_state_e8s",
+            &[("state", state)],
+            e8s as f64,
+        );
+    }
+
+    page.gauge(
+        "local_sns_dissolve_delay_bucket_e8s",
+        "Stake by dissolve delay in half-year buckets",
+    );
+    for (state, buckets) in [
+        ("not_dissolving", &metrics.not_dissolving_buckets),
+        ("dissolving", &metrics.dissolving_buckets),
+    ] {
+        for bucket in buckets {
+            page.sample(
+                "local_sns_dissolve_delay_bucket_e8s",
+                &[
+                    ("state", state),
+                    ("half_years", &bucket.half_years.to_string()),
+                ],
+                bucket.staked_e8s as f64,
+            );
+        }
+    }
+
+    page.single(
+        "local_sns_eligible_to_vote_neurons",
+        "Neurons with at least the minimum dissolve delay to vote",
+        metrics.eligible_to_vote_count as f64,
+    )
+    .single(
+        "local_sns_eligible_to_vote_e8s",
+        "Stake of the neurons eligible to vote",
+        metrics.eligible_to_vote_e8s as f64,
+    );
+
+    for (name, help, value) in [
+        (
+            "local_sns_potential_voting_power",
+            "Total potential voting power reported by governance",
+            metrics.total_potential_voting_power,
+        ),
+        (
+            "local_sns_recently_submitted_proposals",
+            "Proposals submitted in the metrics time window",
+            metrics.recently_submitted_proposals,
+        ),
+        (
+            "local_sns_recently_executed_proposals",
+            "Proposals executed in the metrics time window",
+            metrics.recently_executed_proposals,
+        ),
+    ] {
+        if let Some(value) = value {
+            page.single(name, help, value as f64);
+        }
+    }
+
+    page.gauge("local_sns_treasury_e8s", "Balance of each treasury account");
+    for treasury in &metrics.treasury {
+        if let Some(amount) = treasury.amount_e8s {
+            page.sample(
+                "local_sns_treasury_e8s",
+                &[("name", &treasury.name)],
+                amount as f64,
+            );
+        }
+    }
+}
+
+fn swap_metrics(page: &mut Exposition, swap: &SwapState) {
+    page.gauge(
+        "local_sns_swap_lifecycle",
+        "Swap lifecycle (1 Pending, 2 Open, 3 Committed, 4 Aborted, 5 Adopted)",
+    )
+    .sample(
+        "local_sns_swap_lifecycle",
+        &[("lifecycle", lifecycle_name(swap.lifecycle))],
+        f64::from(swap.lifecycle),
+    )
+    .single(
+        "local_sns_swap_direct_participants",
+        "Number of direct swap participants",
+        swap.direct_participant_count as f64,
+    )
+    .single(
+        "local_sns_swap_direct_participation_icp_e8s",
+        "ICP committed by direct participants",
+        swap.direct_participation_icp_e8s as f64,
+    )
+    .single(
+        "local_sns_swap_buyer_total_icp_e8s",
+        "ICP committed by all participants",
+        swap.buyer_total_icp_e8s as f64,
+    );
+    if let Some(rate) = swap.sns_tokens_per_icp {
+        page.single(
+            "local_sns_swap_sns_tokens_per_icp",
+            "SNS tokens per ICP at the current participation",
+            rate,
+        );
+    }
+}
+
+fn balance_metrics(page: &mut Exposition, balances: &[AccountBalances]) {
+    page.gauge(
+        "local_sns_account_balance_e8s",
+        "Liquid and staked ICP and SNS tokens of the owner and each participant",
+    );
+    for account in balances {
+        for (token, kind, amount) in [
+            ("icp", "liquid", account.icp_e8s),
+            ("icp", "staked", account.icp_staked_e8s),
+            ("sns", "liquid", account.sns_e8s),
+            ("sns", "staked", account.sns_staked_e8s),
+        ] {
+            if let Some(amount) = amount {
+                page.sample(
+                    "local_sns_account_balance_e8s",
+                    &[
+                        ("account", &account.label),
+                        ("principal", &account.principal),
+                        ("token", token),
+                        ("kind", kind),
+                    ],
+                    amount as f64,
+                );
+            }
+        }
+    }
+}
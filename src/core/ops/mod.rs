@@ -1,10 +1,37 @@
 // Operations modules for interacting with different canisters
 
+pub mod access_ops;
+pub mod audit_log;
+pub mod bench_ops;
+pub mod canister_call;
+pub mod canister_client;
 pub mod commands;
+pub mod cycles_ledger_ops;
 pub mod deployment;
+pub mod extension_ops;
+pub mod generic_function_ops;
+pub mod governance_errors;
 pub mod governance_ops;
+pub mod icp_index_ops;
 pub mod identity;
 pub mod ledger_ops;
+pub mod method_types;
+pub mod metrics_export;
+pub mod neuron_ladder;
+pub mod neuron_view;
+pub mod nns_bootstrap_ops;
+pub mod nns_proposal_ops;
+pub mod preflight_ops;
+pub mod recording;
+pub mod scenario_ops;
+pub mod seed_ops;
 pub mod sns_governance_ops;
+pub mod sns_index_ops;
+pub mod sns_root_ops;
+pub mod sns_upgrade_ops;
 pub mod snsw_ops;
 pub mod swap_ops;
+pub mod time_ops;
+pub mod trace_ops;
+pub mod undo_ops;
+pub mod verify_ops;
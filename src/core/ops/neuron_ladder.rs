@@ -0,0 +1,129 @@
+// Dissolve delay ladder: SNS neurons grouped by the rungs of the swap's neuron baskets
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use super::sns_governance_ops::list_all_neurons;
+use super::swap_ops::get_swap_state;
+use crate::core::context::app_context;
+use crate::core::declarations::sns_governance::{DissolveState, Neuron};
+use crate::core::utils::time_format::now_secs;
+use crate::init::sns_config::SnsConfig;
+
+/// Neurons whose dissolve delay falls in one rung of the ladder
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LadderRung {
+    /// Position in the basket (0 = no dissolve delay); `None` for delays above the ladder
+    pub basket_index: Option<u64>,
+    /// Dissolve delays in `[min_seconds, max_seconds)`, unbounded above the ladder
+    pub min_seconds: u64,
+    pub max_seconds: Option<u64>,
+    pub count: u64,
+    /// How many of `count` are dissolving
+    pub dissolving_count: u64,
+    pub staked_e8s: u64,
+}
+
+/// The ladder of an SNS: one rung per basket neuron, plus the neurons above it
+#[derive(Debug, Clone, Serialize)]
+pub struct NeuronLadder {
+    pub basket_count: u64,
+    pub dissolve_delay_interval_seconds: u64,
+    /// Whether the basket came from the swap canister (otherwise from the default config)
+    pub from_swap: bool,
+    /// Neurons each rung should hold right after the swap: one per direct participant
+    pub direct_participants: u64,
+    pub rungs: Vec<LadderRung>,
+}
+
+/// Group neurons into `basket_count` rungs `dissolve_delay_interval_seconds` wide
+/// Rung `i` starts at the dissolve delay of the basket's `i`th neuron; longer delays (e.g. the
+/// developer neuron) go into a final rung above the ladder, left out when it is empty.
+#[must_use]
+pub fn ladder_rungs(
+    neurons: &[Neuron],
+    basket_count: u64,
+    dissolve_delay_interval_seconds: u64,
+    now: u64,
+) -> Vec<LadderRung> {
+    let interval = dissolve_delay_interval_seconds;
+    let mut rungs: Vec<LadderRung> = (0..basket_count)
+        .map(|i| LadderRung {
+            basket_index: Some(i),
+            min_seconds: i * interval,
+            max_seconds: Some((i + 1) * interval),
+            ..Default::default()
+        })
+        .collect();
+    let mut above = LadderRung {
+        basket_index: None,
+        min_seconds: basket_count * interval,
+        max_seconds: None,
+        ..Default::default()
+    };
+
+    for neuron in neurons {
+        let (delay, dissolving) = match &neuron.dissolve_state {
+            Some(DissolveState::DissolveDelaySeconds(delay)) => (*delay, false),
+            Some(DissolveState::WhenDissolvedTimestampSeconds(when)) => {
+                (when.saturating_sub(now), true)
+            }
+            None => (0, false),
+        };
+        // With no interval every basket neuron has delay 0 and anything longer is above
+        let index = delay
+            .checked_div(interval)
+            .unwrap_or(if delay == 0 { 0 } else { u64::MAX });
+        let rung = usize::try_from(index)
+            .ok()
+            .and_then(|index| rungs.get_mut(index))
+            .unwrap_or(&mut above);
+        rung.count += 1;
+        rung.dissolving_count += u64::from(dissolving);
+        rung.staked_e8s += neuron
+            .cached_neuron_stake_e8s
+            .saturating_sub(neuron.neuron_fees_e8s);
+    }
+
+    if above.count > 0 {
+        rungs.push(above);
+    }
+    rungs
+}
+
+/// Build the ladder of the deployed SNS from all its neurons and the swap's basket parameters
+pub async fn get_neuron_ladder_default_path() -> Result<NeuronLadder> {
+    let agent = app_context().anonymous_agent().await?;
+    let governance_canister = app_context().sns_governance_canister()?;
+    let swap_canister = app_context().sns_swap_canister()?;
+
+    let (neurons, swap) = tokio::join!(
+        list_all_neurons(&agent, governance_canister),
+        get_swap_state(&agent, swap_canister),
+    );
+    let neurons = neurons.context("Failed to list SNS neurons")?;
+    let swap = swap.context("Failed to get the swap state")?;
+
+    let basket = swap
+        .params
+        .as_ref()
+        .and_then(|params| params.neuron_basket_construction_parameters.clone());
+    let (basket_count, interval) = match &basket {
+        Some(basket) => (basket.count, basket.dissolve_delay_interval_seconds),
+        None => {
+            let config = SnsConfig::default();
+            (
+                config.swap.neuron_basket_count,
+                config.swap.neuron_basket_dissolve_delay_interval_seconds,
+            )
+        }
+    };
+
+    Ok(NeuronLadder {
+        basket_count,
+        dissolve_delay_interval_seconds: interval,
+        from_swap: basket.is_some(),
+        direct_participants: swap.direct_participant_count,
+        rungs: ladder_rungs(&neurons, basket_count, interval, now_secs()),
+    })
+}
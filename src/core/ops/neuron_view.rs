@@ -0,0 +1,560 @@
+// Shared rendering of SNS and ICP neurons: tables, details and dissolve states
+
+use crate::core::declarations::{icp_governance, sns_governance};
+use crate::core::ops::sns_governance_ops::{VotingPowerParams, sns_permission_labels};
+use crate::core::utils::exit_code::invalid;
+use crate::core::utils::table::{Table, short_id};
+use crate::core::utils::time_format::{format_span, format_timestamp, now_secs};
+use crate::core::utils::{print_header, print_info};
+
+/// Where a neuron stands in dissolving
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dissolve {
+    NotDissolving { delay_seconds: u64 },
+    DissolvingAt(u64),
+    Unknown,
+}
+
+impl Dissolve {
+    /// Cell of the neuron tables, e.g. "182 days (15778800s)" or "Dissolving (in 3d 4h)"
+    #[must_use]
+    pub fn cell(self) -> String {
+        match self {
+            Self::NotDissolving { delay_seconds } => {
+                format!("{} days ({}s)", delay_seconds / 86400, delay_seconds)
+            }
+            Self::DissolvingAt(timestamp) => Self::countdown(timestamp),
+            Self::Unknown => "No state".to_string(),
+        }
+    }
+
+    /// Short form for the all-neurons table, e.g. "182 days"
+    #[must_use]
+    pub fn short(self) -> String {
+        match self {
+            Self::NotDissolving { delay_seconds } => format!("{} days", delay_seconds / 86400),
+            Self::DissolvingAt(timestamp) => Self::countdown(timestamp),
+            Self::Unknown => "No state".to_string(),
+        }
+    }
+
+    /// "Dissolving (in 3d 4h)", or "Dissolved (2h ago)" once the time has passed
+    fn countdown(timestamp: u64) -> String {
+        let now = now_secs();
+        if timestamp > now {
+            format!("Dissolving (in {})", format_span(timestamp - now))
+        } else {
+            format!("Dissolved ({} ago)", format_span(now - timestamp))
+        }
+    }
+
+    /// Dissolve delay left at `now`: the delay when not dissolving, the time until the
+    /// dissolve timestamp otherwise
+    #[must_use]
+    pub const fn remaining_at(self, now: u64) -> Option<u64> {
+        match self {
+            Self::NotDissolving { delay_seconds } => Some(delay_seconds),
+            Self::DissolvingAt(timestamp) => Some(timestamp.saturating_sub(now)),
+            Self::Unknown => None,
+        }
+    }
+
+    /// Sentence for confirmation summaries, e.g. "not dissolving, dissolve delay 6mo"
+    #[must_use]
+    pub fn summary(self) -> String {
+        use crate::core::utils::cli_args::format_duration_secs;
+        match self {
+            Self::NotDissolving { delay_seconds } => format!(
+                "not dissolving, dissolve delay {}",
+                format_duration_secs(delay_seconds)
+            ),
+            Self::DissolvingAt(_) => "already dissolving".to_string(),
+            Self::Unknown => "unknown".to_string(),
+        }
+    }
+
+    fn print_details(self, now: u64) {
+        match self {
+            Self::NotDissolving { delay_seconds } => {
+                println!("  Type: Dissolve Delay");
+                println!(
+                    "  Delay: {} seconds ({} days, {} hours)",
+                    delay_seconds,
+                    delay_seconds / 86400,
+                    (delay_seconds % 86400) / 3600
+                );
+            }
+            Self::DissolvingAt(timestamp) => {
+                println!("  Type: Dissolving");
+                println!(
+                    "  {}: {}",
+                    if timestamp > now {
+                        "Dissolves at"
+                    } else {
+                        "Dissolved at"
+                    },
+                    format_timestamp(timestamp, now)
+                );
+            }
+            Self::Unknown => println!("  Type: None"),
+        }
+    }
+}
+
+/// Order of the neuron list commands (`--sort`); ties keep the default order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NeuronSort {
+    /// Biggest stake first
+    Stake,
+    /// Least dissolve delay left first, neurons without a dissolve state last
+    Delay,
+    /// Oldest first
+    Created,
+    /// Most maturity first
+    Maturity,
+}
+
+impl std::str::FromStr for NeuronSort {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> anyhow::Result<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "stake" => Ok(Self::Stake),
+            "delay" => Ok(Self::Delay),
+            "created" => Ok(Self::Created),
+            "maturity" => Ok(Self::Maturity),
+            _ => Err(invalid(format!(
+                "Unknown sort '{value}': use stake, delay, created or maturity"
+            ))),
+        }
+    }
+}
+
+impl NeuronSort {
+    pub fn apply<N: NeuronView>(self, neurons: &mut [N], now: u64) {
+        use std::cmp::Reverse;
+
+        match self {
+            Self::Stake => neurons.sort_by_key(|n| Reverse(n.stake_e8s())),
+            Self::Delay => {
+                neurons.sort_by_key(|n| n.dissolve().remaining_at(now).unwrap_or(u64::MAX));
+            }
+            Self::Created => neurons.sort_by_key(NeuronView::created_seconds),
+            Self::Maturity => neurons.sort_by_key(|n| Reverse(n.maturity_e8s())),
+        }
+    }
+}
+
+/// Neurons kept by the neuron list commands (`--filter`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NeuronFilter {
+    /// Dissolving, with time left
+    Dissolving,
+    /// Not dissolving, with a dissolve delay
+    Locked,
+    /// No dissolve delay left: can be disbursed
+    Dissolved,
+    /// Stake of at least this many e8s
+    MinStake(u64),
+}
+
+impl NeuronFilter {
+    #[must_use]
+    pub fn matches(self, neuron: &impl NeuronView, now: u64) -> bool {
+        match (self, neuron.dissolve()) {
+            (Self::Dissolving, Dissolve::DissolvingAt(timestamp)) => timestamp > now,
+            (Self::Locked, Dissolve::NotDissolving { delay_seconds }) => delay_seconds > 0,
+            (Self::Dissolved, dissolve) => dissolve.remaining_at(now) == Some(0),
+            (Self::MinStake(e8s), _) => neuron.stake_e8s() >= e8s,
+            _ => false,
+        }
+    }
+}
+
+/// What the neuron tables and details show of a neuron, for SNS and ICP neurons alike
+pub trait NeuronView {
+    /// "SNS" or "ICP"
+    const KIND: &'static str;
+    /// Header of the last table column
+    const ACCESS_HEADER: &'static str;
+    /// Header of the voting power column
+    const VOTING_POWER_HEADER: &'static str;
+    /// What the voting power column needs besides the neuron
+    type Params;
+
+    /// Full neuron ID (hex for SNS neurons, the number for ICP neurons)
+    fn id_text(&self) -> Option<String>;
+    /// Neuron ID as shown in tables
+    fn id_cell(&self) -> String;
+    fn stake_e8s(&self) -> u64;
+    fn maturity_e8s(&self) -> u64;
+    fn staked_maturity_e8s(&self) -> Option<u64>;
+    fn dissolve(&self) -> Dissolve;
+    fn aging_since_seconds(&self) -> u64;
+    fn created_seconds(&self) -> u64;
+    /// Voting power column: computed for SNS neurons, as reported by governance for ICP neurons
+    fn voting_power_cell(&self, params: &Self::Params, now: u64) -> String;
+    /// Last table column: permissions of SNS neurons, hotkeys of ICP neurons
+    fn access_cell(&self) -> String;
+    /// Details sections only this kind of neuron has, printed after the shared ones
+    fn print_extra_details(&self, now: u64);
+}
+
+/// Table of neurons, numbered for selection (the list commands and the neuron menus)
+pub fn neuron_table<N: NeuronView>(neurons: &[N], params: &N::Params) -> Table {
+    let now = now_secs();
+    let mut table = Table::new(&[
+        "#",
+        "Neuron ID",
+        "Stake (e8s)",
+        "Dissolve Delay",
+        N::VOTING_POWER_HEADER,
+        N::ACCESS_HEADER,
+    ])
+    .right_align(&[0, 2, 4]);
+    for (index, neuron) in neurons.iter().enumerate() {
+        table.add_row(vec![
+            (index + 1).to_string(),
+            neuron.id_cell(),
+            neuron.stake_e8s().to_string(),
+            neuron.dissolve().cell(),
+            neuron.voting_power_cell(params, now),
+            neuron.access_cell(),
+        ]);
+    }
+    table
+}
+
+/// Aging start of a neuron; dissolving neurons do not age, which governance marks with u64::MAX
+fn aging_since_display(timestamp: u64, now: u64) -> String {
+    if timestamp == u64::MAX {
+        "not aging (dissolving)".to_string()
+    } else {
+        format_timestamp(timestamp, now)
+    }
+}
+
+/// Print full details for a single neuron
+/// Timestamps are shown as UTC datetimes with their distance from the local clock
+pub fn print_neuron_details<N: NeuronView>(neuron: &N) {
+    let now = now_secs();
+    print_header(&format!("{} Neuron Details", N::KIND));
+
+    print_info(&format!(
+        "Neuron ID: {}",
+        neuron.id_text().as_deref().unwrap_or("<none>")
+    ));
+
+    println!();
+    print_info("Stake Information:");
+    println!("  Cached Stake: {} e8s", neuron.stake_e8s());
+    if let Some(staked_maturity) = neuron.staked_maturity_e8s() {
+        println!("  Staked Maturity: {} e8s", staked_maturity);
+    }
+    println!("  Maturity: {} e8s", neuron.maturity_e8s());
+
+    println!();
+    print_info("Dissolve State:");
+    neuron.dissolve().print_details(now);
+
+    println!();
+    print_info("Aging:");
+    println!(
+        "  Aging since: {}",
+        aging_since_display(neuron.aging_since_seconds(), now)
+    );
+    println!(
+        "  Created: {}",
+        format_timestamp(neuron.created_seconds(), now)
+    );
+
+    neuron.print_extra_details(now);
+    println!();
+}
+
+impl NeuronView for sns_governance::Neuron {
+    const KIND: &'static str = "SNS";
+    const ACCESS_HEADER: &'static str = "Permissions";
+    const VOTING_POWER_HEADER: &'static str = "Voting Power (e8s)";
+    type Params = VotingPowerParams;
+
+    fn id_text(&self) -> Option<String> {
+        self.id.as_ref().map(|id| hex::encode(&id.id))
+    }
+
+    fn id_cell(&self) -> String {
+        self.id_text()
+            .map_or_else(|| "<none>".to_string(), |id| short_id(&id))
+    }
+
+    fn stake_e8s(&self) -> u64 {
+        self.cached_neuron_stake_e8s
+    }
+
+    fn maturity_e8s(&self) -> u64 {
+        self.maturity_e8s_equivalent
+    }
+
+    fn staked_maturity_e8s(&self) -> Option<u64> {
+        self.staked_maturity_e8s_equivalent
+    }
+
+    fn dissolve(&self) -> Dissolve {
+        use sns_governance::DissolveState;
+        match self.dissolve_state {
+            Some(DissolveState::DissolveDelaySeconds(delay_seconds)) => {
+                Dissolve::NotDissolving { delay_seconds }
+            }
+            Some(DissolveState::WhenDissolvedTimestampSeconds(timestamp)) => {
+                Dissolve::DissolvingAt(timestamp)
+            }
+            None => Dissolve::Unknown,
+        }
+    }
+
+    fn aging_since_seconds(&self) -> u64 {
+        self.aging_since_timestamp_seconds
+    }
+
+    fn created_seconds(&self) -> u64 {
+        self.created_timestamp_seconds
+    }
+
+    fn voting_power_cell(&self, params: &VotingPowerParams, now: u64) -> String {
+        params.voting_power(self, now).to_string()
+    }
+
+    /// Permission types across all principals
+    fn access_cell(&self) -> String {
+        let mut permissions: Vec<i32> = self
+            .permissions
+            .iter()
+            .flat_map(|perm| perm.permission_type.iter().copied())
+            .collect();
+        permissions.sort_unstable();
+        permissions.dedup();
+        sns_permission_labels(&permissions)
+    }
+
+    fn print_extra_details(&self, now: u64) {
+        println!();
+        print_info(&format!(
+            "Voting Power Multiplier: {}%",
+            self.voting_power_percentage_multiplier
+        ));
+
+        println!();
+        print_info("Permissions:");
+        if self.permissions.is_empty() {
+            println!("  None");
+        } else {
+            for perm in &self.permissions {
+                if let Some(principal) = &perm.principal {
+                    println!("  Principal: {}", principal);
+                } else {
+                    println!("  Unknown Principal:");
+                }
+                println!(
+                    "    Permission Types: {}",
+                    sns_permission_labels(&perm.permission_type)
+                );
+            }
+        }
+
+        if let Some(auto_stake) = self.auto_stake_maturity {
+            println!();
+            print_info(&format!("Auto Stake Maturity: {}", auto_stake));
+        }
+
+        if let Some(vesting) = self.vesting_period_seconds {
+            let vested_at = self.created_timestamp_seconds.saturating_add(vesting);
+            println!();
+            print_info(&format!(
+                "Vesting Period: {} seconds ({}), {} {}",
+                vesting,
+                format_span(vesting),
+                if vested_at > now_secs() {
+                    "vests"
+                } else {
+                    "vested"
+                },
+                format_timestamp(vested_at, now_secs())
+            ));
+        }
+
+        if !self.disburse_maturity_in_progress.is_empty() {
+            println!();
+            print_info("Disburse Maturity In Progress:");
+            for disburse in &self.disburse_maturity_in_progress {
+                println!("  Amount: {} e8s", disburse.amount_e8s);
+                println!(
+                    "  Started: {}",
+                    format_timestamp(disburse.timestamp_of_disbursement_seconds, now)
+                );
+                if let Some(account) = &disburse.account_to_disburse_to
+                    && let Some(owner) = &account.owner
+                {
+                    println!("  Account Owner: {}", owner);
+                }
+            }
+        }
+
+        if !self.followees.is_empty() {
+            println!();
+            print_info("Followees:");
+            for (function_id, followees) in &self.followees {
+                println!(
+                    "  Function ID {}: {} followee(s)",
+                    function_id,
+                    followees.followees.len()
+                );
+            }
+        }
+
+        if let Some(topic_followees) = &self.topic_followees
+            && !topic_followees.topic_id_to_followees.is_empty()
+        {
+            use sns_governance::Topic;
+
+            println!();
+            print_info("Topic Followees:");
+            for (topic_id, topic_data) in &topic_followees.topic_id_to_followees {
+                if let Some(topic) = &topic_data.topic {
+                    let topic_str = match topic {
+                        Topic::DappCanisterManagement => "DappCanisterManagement",
+                        Topic::DaoCommunitySettings => "DaoCommunitySettings",
+                        Topic::ApplicationBusinessLogic => "ApplicationBusinessLogic",
+                        Topic::CriticalDappOperations => "CriticalDappOperations",
+                        Topic::TreasuryAssetManagement => "TreasuryAssetManagement",
+                        Topic::Governance => "Governance",
+                        Topic::SnsFrameworkManagement => "SnsFrameworkManagement",
+                    };
+                    println!(
+                        "  Topic {} (ID {}): {} followee(s)",
+                        topic_str,
+                        topic_id,
+                        topic_data.followees.len()
+                    );
+                } else {
+                    println!(
+                        "  Topic ID {}: {} followee(s)",
+                        topic_id,
+                        topic_data.followees.len()
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl NeuronView for icp_governance::Neuron {
+    const KIND: &'static str = "ICP";
+    const ACCESS_HEADER: &'static str = "Hotkeys";
+    const VOTING_POWER_HEADER: &'static str = "Deciding / Potential VP (e8s)";
+    type Params = ();
+
+    fn id_text(&self) -> Option<String> {
+        self.id.as_ref().map(|id| id.id.to_string())
+    }
+
+    fn id_cell(&self) -> String {
+        self.id_text().unwrap_or_else(|| "<none>".to_string())
+    }
+
+    fn stake_e8s(&self) -> u64 {
+        self.cached_neuron_stake_e8s
+    }
+
+    fn maturity_e8s(&self) -> u64 {
+        self.maturity_e8s_equivalent
+    }
+
+    fn staked_maturity_e8s(&self) -> Option<u64> {
+        self.staked_maturity_e8s_equivalent
+    }
+
+    fn dissolve(&self) -> Dissolve {
+        use icp_governance::DissolveState;
+        match self.dissolve_state {
+            Some(DissolveState::DissolveDelaySeconds(delay_seconds)) => {
+                Dissolve::NotDissolving { delay_seconds }
+            }
+            Some(DissolveState::WhenDissolvedTimestampSeconds(timestamp)) => {
+                Dissolve::DissolvingAt(timestamp)
+            }
+            None => Dissolve::Unknown,
+        }
+    }
+
+    fn aging_since_seconds(&self) -> u64 {
+        self.aging_since_timestamp_seconds
+    }
+
+    fn created_seconds(&self) -> u64 {
+        self.created_timestamp_seconds
+    }
+
+    fn voting_power_cell(&self, _params: &(), _now: u64) -> String {
+        let power = |value: Option<u64>| value.map_or_else(|| "-".to_string(), |v| v.to_string());
+        format!(
+            "{} / {}",
+            power(self.deciding_voting_power),
+            power(self.potential_voting_power)
+        )
+    }
+
+    fn access_cell(&self) -> String {
+        if self.hot_keys.is_empty() {
+            "None".to_string()
+        } else {
+            format!("{} hotkey(s)", self.hot_keys.len())
+        }
+    }
+
+    fn print_extra_details(&self, now: u64) {
+        if let Some(controller) = &self.controller {
+            println!();
+            print_info(&format!("Controller: {}", controller));
+        }
+
+        println!();
+        if let Some(voting_power) = self.deciding_voting_power {
+            print_info(&format!("Deciding Voting Power: {} e8s", voting_power));
+        }
+        if let Some(potential_power) = self.potential_voting_power {
+            print_info(&format!("Potential Voting Power: {} e8s", potential_power));
+        }
+        if let Some(refreshed) = self.voting_power_refreshed_timestamp_seconds {
+            print_info(&format!(
+                "Voting Power Refreshed: {}",
+                format_timestamp(refreshed, now)
+            ));
+        }
+
+        println!();
+        print_info("Hotkeys:");
+        if self.hot_keys.is_empty() {
+            println!("  None");
+        } else {
+            for (i, hotkey) in self.hot_keys.iter().enumerate() {
+                println!("  [{}] {}", i + 1, hotkey);
+            }
+        }
+
+        if let Some(visibility) = self.visibility {
+            println!();
+            print_info(&format!(
+                "Visibility: {}",
+                if visibility == 0 { "Public" } else { "Private" }
+            ));
+        }
+
+        println!();
+        print_info(&format!("KYC Verified: {}", self.kyc_verified));
+
+        if let Some(auto_stake) = self.auto_stake_maturity {
+            println!();
+            print_info(&format!("Auto Stake Maturity: {}", auto_stake));
+        }
+    }
+}
@@ -0,0 +1,212 @@
+// NNS bootstrap: start the local replica and install the NNS with dfx, so a new machine can
+// deploy an SNS without any manual setup
+
+use anyhow::{Context, Result};
+use candid::Principal;
+use ic_agent::Agent;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use super::preflight_ops::{check_installed, check_sns_wasms};
+use super::verify_ops::Check;
+use crate::core::context::app_context;
+use crate::core::utils::data_output::{ensure_output_dir, get_nns_canisters_path};
+use crate::core::utils::network::network_config;
+use crate::core::utils::time_format::now_secs;
+use crate::core::utils::{print_header, print_info, print_step, print_success, print_warning};
+
+/// How often to poll the NNS canisters after installing them
+const NNS_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// How long to wait for the NNS canisters to be installed and for SNS-W to hold its wasms
+const NNS_HEALTH_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// How long to wait for a replica started by `dfx start --background` to answer
+const REPLICA_START_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// An NNS canister `init-nns` found installed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NnsCanister {
+    pub name: String,
+    pub canister_id: String,
+    pub module_hash: String,
+}
+
+/// What `init-nns` installed, written next to the deployment data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NnsInstallRecord {
+    pub replica_url: String,
+    /// Unix seconds when the canisters were found healthy
+    pub recorded_at: u64,
+    /// Whether this run installed the NNS (false if it was already there)
+    pub installed: bool,
+    pub canisters: Vec<NnsCanister>,
+}
+
+/// The NNS canisters the tool talks to
+fn nns_canisters() -> [(&'static str, Principal); 3] {
+    let network = network_config();
+    [
+        ("governance", network.governance_canister),
+        ("ledger", network.ledger_canister),
+        ("sns-wasm", network.snsw_canister),
+    ]
+}
+
+/// Start the local replica if it is not running, install the NNS if it is missing, wait until
+/// the NNS canisters and the SNS wasms are in place, and record the canister IDs
+pub async fn init_nns() -> Result<NnsInstallRecord> {
+    let network = network_config();
+    if !network.fetch_root_key {
+        anyhow::bail!(
+            "init-nns only works on a local replica, not {}",
+            network.replica_url
+        );
+    }
+
+    print_header("NNS Bootstrap");
+    let agent = match reachable_agent().await {
+        Some(agent) => {
+            print_success(&format!("Replica at {} is running", network.replica_url));
+            agent
+        }
+        None => {
+            print_step(
+                "Starting the local replica (dfx start --clean --background --system-canisters)...",
+            );
+            run_dfx(&["start", "--clean", "--background", "--system-canisters"]).await?;
+            wait_for_replica().await?
+        }
+    };
+
+    let mut installed = false;
+    if nns_installed(&agent).await {
+        print_success("NNS canisters are already installed");
+    } else {
+        print_step("Installing the NNS (dfx nns install)...");
+        run_dfx(&["nns", "install"]).await.context(
+            "dfx nns install failed. It needs a clean local replica on a system subnet: \
+             run dfx stop, then retry (or start it with dfx start --clean --system-canisters)",
+        )?;
+        installed = true;
+    }
+
+    print_step("Waiting for the NNS canisters and SNS wasms...");
+    wait_for_nns_healthy(&agent).await?;
+    print_success("NNS is ready");
+
+    let mut canisters = Vec::new();
+    for (name, canister_id) in nns_canisters() {
+        let module_hash = agent
+            .read_state_canister_module_hash(canister_id)
+            .await
+            .map(hex::encode)
+            .with_context(|| format!("Failed to read the module hash of {name}"))?;
+        print_info(&format!("{name}: {canister_id}"));
+        canisters.push(NnsCanister {
+            name: name.to_string(),
+            canister_id: canister_id.to_text(),
+            module_hash,
+        });
+    }
+
+    let record = NnsInstallRecord {
+        replica_url: network.replica_url.clone(),
+        recorded_at: now_secs(),
+        installed,
+        canisters,
+    };
+    write_nns_record(&record)?;
+    Ok(record)
+}
+
+/// Agent for the configured replica, or None if it does not answer
+async fn reachable_agent() -> Option<Agent> {
+    let agent = app_context().anonymous_agent().await.ok()?;
+    agent.status().await.ok().map(|_| agent)
+}
+
+async fn wait_for_replica() -> Result<Agent> {
+    let deadline = tokio::time::Instant::now() + REPLICA_START_TIMEOUT;
+    loop {
+        if let Some(agent) = reachable_agent().await {
+            print_success("Replica started");
+            return Ok(agent);
+        }
+        if tokio::time::Instant::now() >= deadline {
+            anyhow::bail!(
+                "Replica at {} did not answer within {}s of dfx start",
+                network_config().replica_url,
+                REPLICA_START_TIMEOUT.as_secs()
+            );
+        }
+        tokio::time::sleep(NNS_POLL_INTERVAL).await;
+    }
+}
+
+/// Whether every NNS canister the tool uses has a module installed
+async fn nns_installed(agent: &Agent) -> bool {
+    for (name, canister_id) in nns_canisters() {
+        if !check_installed(agent, name, canister_id).await.ok {
+            return false;
+        }
+    }
+    true
+}
+
+/// Poll until the NNS canisters are installed and SNS-W offers every SNS wasm
+async fn wait_for_nns_healthy(agent: &Agent) -> Result<()> {
+    let deadline = tokio::time::Instant::now() + NNS_HEALTH_TIMEOUT;
+    loop {
+        let mut failed: Vec<Check> = Vec::new();
+        for (name, canister_id) in nns_canisters() {
+            let check = check_installed(agent, name, canister_id).await;
+            if !check.ok {
+                failed.push(check);
+            }
+        }
+        let wasms = check_sns_wasms(agent, network_config().snsw_canister).await;
+        if !wasms.ok {
+            failed.push(wasms);
+        }
+        if failed.is_empty() {
+            return Ok(());
+        }
+
+        if tokio::time::Instant::now() >= deadline {
+            for check in &failed {
+                print_warning(&format!("{}: {}", check.subject, check.detail));
+            }
+            anyhow::bail!(
+                "NNS not healthy after {}s ({} check(s) failing)",
+                NNS_HEALTH_TIMEOUT.as_secs(),
+                failed.len()
+            );
+        }
+        tokio::time::sleep(NNS_POLL_INTERVAL).await;
+    }
+}
+
+/// Run a dfx command, passing its output through
+async fn run_dfx(args: &[&str]) -> Result<()> {
+    let status = tokio::process::Command::new("dfx")
+        .args(args)
+        .stdin(std::process::Stdio::null())
+        .status()
+        .await
+        .context("Failed to run dfx. Install it from https://internetcomputer.org/docs")?;
+    if !status.success() {
+        anyhow::bail!("dfx {} exited with {}", args.join(" "), status);
+    }
+    Ok(())
+}
+
+fn write_nns_record(record: &NnsInstallRecord) -> Result<()> {
+    ensure_output_dir()?;
+    let path = get_nns_canisters_path();
+    let json = serde_json::to_string_pretty(record)?;
+    std::fs::write(&path, json)
+        .with_context(|| format!("Failed to write NNS canisters: {}", path.display()))?;
+    print_success(&format!("NNS canisters recorded in {}", path.display()));
+    Ok(())
+}
@@ -0,0 +1,164 @@
+// NNS proposals beyond SNS creation: make them and vote on them with the local ICP neuron
+
+use anyhow::{Context, Result};
+use candid::{Decode, Principal, encode_args};
+use ic_agent::Agent;
+
+use super::canister_call;
+use super::canister_client::CanisterClient;
+use super::governance_errors::nns_governance_error;
+use super::governance_ops::get_nns_proposal_info;
+use crate::core::context::app_context;
+use crate::core::declarations::icp_governance::{
+    Command1, MakeProposalRequest, MakeProposalResponse, ManageNeuronCommandRequest,
+    ManageNeuronRequest, ManageNeuronResponse, Motion, NeuronId, ProposalActionRequest, ProposalId,
+    ProposalInfo, RegisterVote,
+};
+
+/// Make an NNS proposal with a neuron the agent controls
+/// Returns the proposal ID
+pub async fn make_nns_proposal(
+    agent: &dyn CanisterClient,
+    governance_canister: Principal,
+    neuron_id: u64,
+    proposal: MakeProposalRequest,
+) -> Result<u64> {
+    let request = ManageNeuronRequest {
+        id: Some(NeuronId { id: neuron_id }),
+        command: Some(ManageNeuronCommandRequest::MakeProposal(proposal)),
+        neuron_id_or_subaccount: None,
+    };
+
+    let result_bytes = canister_call::update(
+        agent,
+        &governance_canister,
+        "manage_neuron",
+        encode_args((request,))?,
+    )
+    .await
+    .context("Failed to call manage_neuron")?;
+
+    let response: ManageNeuronResponse = Decode!(&result_bytes, ManageNeuronResponse)
+        .context("Failed to decode make_proposal response")?;
+
+    match response.command {
+        Some(Command1::MakeProposal(MakeProposalResponse {
+            proposal_id: Some(ProposalId { id }),
+            ..
+        })) => Ok(id),
+        Some(Command1::Error(e)) => Err(nns_governance_error("Failed to create proposal", &e)),
+        _ => anyhow::bail!("Unexpected response from make_proposal"),
+    }
+}
+
+/// Vote on an NNS proposal with a neuron the agent controls
+pub async fn register_nns_vote(
+    agent: &dyn CanisterClient,
+    governance_canister: Principal,
+    neuron_id: u64,
+    proposal_id: u64,
+    yes: bool,
+) -> Result<()> {
+    let request = ManageNeuronRequest {
+        id: Some(NeuronId { id: neuron_id }),
+        command: Some(ManageNeuronCommandRequest::RegisterVote(RegisterVote {
+            vote: if yes { 1 } else { 2 },
+            proposal: Some(ProposalId { id: proposal_id }),
+        })),
+        neuron_id_or_subaccount: None,
+    };
+
+    let result_bytes = canister_call::update(
+        agent,
+        &governance_canister,
+        "manage_neuron",
+        encode_args((request,))?,
+    )
+    .await
+    .context("Failed to call manage_neuron")?;
+
+    let response: ManageNeuronResponse = Decode!(&result_bytes, ManageNeuronResponse)
+        .context("Failed to decode register_vote response")?;
+
+    match response.command {
+        Some(Command1::RegisterVote {}) => Ok(()),
+        Some(Command1::Error(e)) => Err(nns_governance_error("Failed to vote on proposal", &e)),
+        _ => anyhow::bail!("Unexpected response from register_vote"),
+    }
+}
+
+/// A motion proposal: text the NNS votes on without anything being executed
+#[must_use]
+pub fn motion_proposal(title: &str, motion_text: &str, url: &str) -> MakeProposalRequest {
+    MakeProposalRequest {
+        url: url.to_string(),
+        title: Some(title.to_string()),
+        summary: motion_text.to_string(),
+        action: Some(ProposalActionRequest::Motion(Motion {
+            motion_text: motion_text.to_string(),
+        })),
+    }
+}
+
+/// Name of an NNS proposal status
+#[must_use]
+pub const fn nns_proposal_status_name(status: i32) -> &'static str {
+    match status {
+        1 => "Open",
+        2 => "Rejected",
+        3 => "Adopted",
+        4 => "Executed",
+        5 => "Failed",
+        _ => "Unknown",
+    }
+}
+
+/// Agent of the SNS owner and the ICP neuron from the deployment data
+async fn owner_icp_neuron() -> Result<(Agent, u64)> {
+    let deployment_data = app_context().deployment_data()?;
+    let owner = Principal::from_text(&deployment_data.owner_principal)
+        .context("Failed to parse owner principal")?;
+    let agent = app_context().agent_for_principal(owner).await?;
+    Ok((agent, deployment_data.icp_neuron_id))
+}
+
+/// Make a motion proposal with the ICP neuron from the deployment data
+/// Returns the proposal ID
+pub async fn make_motion_proposal_default_path(
+    title: &str,
+    motion_text: &str,
+    url: &str,
+) -> Result<u64> {
+    let (agent, neuron_id) = owner_icp_neuron().await?;
+    make_nns_proposal(
+        &agent,
+        crate::core::utils::network::network_config().governance_canister,
+        neuron_id,
+        motion_proposal(title, motion_text, url),
+    )
+    .await
+}
+
+/// Vote on an NNS proposal with the ICP neuron from the deployment data
+pub async fn vote_on_nns_proposal_default_path(proposal_id: u64, yes: bool) -> Result<()> {
+    let (agent, neuron_id) = owner_icp_neuron().await?;
+    register_nns_vote(
+        &agent,
+        crate::core::utils::network::network_config().governance_canister,
+        neuron_id,
+        proposal_id,
+        yes,
+    )
+    .await
+}
+
+/// Status and tally of an NNS proposal (None if governance does not know it)
+pub async fn get_nns_proposal_default_path(proposal_id: u64) -> Result<Option<ProposalInfo>> {
+    let agent = app_context().anonymous_agent().await?;
+    get_nns_proposal_info(
+        &agent,
+        crate::core::utils::network::network_config().governance_canister,
+        proposal_id,
+    )
+    .await
+}
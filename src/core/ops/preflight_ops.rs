@@ -0,0 +1,231 @@
+// Preflight checks run before deploy-sns, so a broken environment is reported up front
+// instead of as a failure halfway through the deployment
+
+use anyhow::Result;
+use candid::Principal;
+use ic_agent::Agent;
+
+use super::identity::{load_dfx_identity, load_minting_identity};
+use super::ledger_ops::{get_icp_ledger_balance, get_icp_minting_account};
+use super::recording::is_replaying;
+use super::snsw_ops::list_upgrade_steps;
+use super::verify_ops::Check;
+use crate::core::context::app_context;
+use crate::core::utils::constants::{DEVELOPER_ICP, ICP_TRANSFER_FEE};
+use crate::core::utils::network::network_config;
+use crate::core::utils::{print_header, print_info, print_success, print_warning};
+
+const START_HINT: &str = "Start the replica with the NNS: dfx start --clean --system-canisters";
+const NNS_HINT: &str =
+    "Install the NNS: restart with dfx start --clean --system-canisters, or run dfx nns install";
+const FUNDING_HINT: &str =
+    "Use the local NNS ledger (dfx start --clean --system-canisters), or fund the owner with ICP";
+
+/// Check everything deploy-sns needs before it starts
+///
+/// The replica must be reachable, NNS governance, the ICP ledger and SNS-W must be installed,
+/// the dfx identity must load, the owner must be able to get ICP (from the minting account or
+/// an existing balance), and SNS-W must hold a wasm for every SNS canister. Every check runs
+/// so all failures are reported at once; only an unreachable replica stops early.
+pub async fn run_preflight_checks() -> Vec<Check> {
+    let network = network_config();
+    let mut checks = Vec::new();
+
+    let agent = match app_context().anonymous_agent().await {
+        Ok(agent) => agent,
+        Err(e) => {
+            checks.push(
+                Check::failed(
+                    "replica",
+                    format!("cannot reach {}: {e:#}", network.replica_url),
+                )
+                .with_hint(START_HINT),
+            );
+            return checks;
+        }
+    };
+    checks.push(match agent.status().await {
+        Ok(status) => Check::ok(
+            "replica",
+            format!(
+                "{} responds{}",
+                network.replica_url,
+                status
+                    .impl_version
+                    .map(|version| format!(" (version {version})"))
+                    .unwrap_or_default()
+            ),
+        ),
+        Err(e) => Check::failed(
+            "replica",
+            format!("{} does not respond: {e}", network.replica_url),
+        )
+        .with_hint(START_HINT),
+    });
+
+    for (name, canister) in [
+        ("NNS governance", network.governance_canister),
+        ("ICP ledger", network.ledger_canister),
+        ("SNS-W", network.snsw_canister),
+    ] {
+        checks.push(check_installed(&agent, name, canister).await);
+    }
+
+    let owner = match load_dfx_identity(None).and_then(|identity| {
+        identity
+            .sender()
+            .map_err(|e| anyhow::anyhow!("Failed to get identity principal: {e}"))
+    }) {
+        Ok(owner) => {
+            checks.push(Check::ok("dfx identity", format!("owner {owner}")));
+            Some(owner)
+        }
+        Err(e) => {
+            checks.push(Check::failed("dfx identity", format!("{e:#}")).with_hint(
+                "Create one with dfx identity new default, or select one with dfx identity use",
+            ));
+            None
+        }
+    };
+
+    checks.push(check_icp_funding(&agent, network.ledger_canister, owner).await);
+    checks.push(check_sns_wasms(&agent, network.snsw_canister).await);
+    checks
+}
+
+/// Run the preflight checks and print them, failing if any did not pass
+/// Skipped under `--replay`: the replica status and module hash reads are not canister calls,
+/// so a recording cannot answer them, and the replayed calls need no live environment.
+pub async fn ensure_preflight() -> Result<()> {
+    if is_replaying() {
+        print_info("Preflight checks skipped: canister calls are replayed from a recording");
+        return Ok(());
+    }
+    let checks = run_preflight_checks().await;
+    print_header("Preflight Checks");
+    for check in &checks {
+        let line = format!("{}: {}", check.subject, check.detail);
+        if check.ok {
+            print_success(&line);
+        } else {
+            print_warning(&line);
+            if let Some(hint) = &check.hint {
+                print_warning(&format!("  -> {hint}"));
+            }
+        }
+    }
+    println!();
+
+    let failed = checks.iter().filter(|check| !check.ok).count();
+    if failed > 0 {
+        anyhow::bail!(
+            "{} preflight check(s) failed; fix them and run deploy-sns again",
+            failed
+        );
+    }
+    Ok(())
+}
+
+/// Whether a canister has a module installed
+pub(crate) async fn check_installed(agent: &Agent, name: &str, canister: Principal) -> Check {
+    match agent.read_state_canister_module_hash(canister).await {
+        Ok(hash) if !hash.is_empty() => Check::ok(
+            name,
+            format!(
+                "{} installed (module {})",
+                canister,
+                hex::encode(&hash[..hash.len().min(4)])
+            ),
+        ),
+        Ok(_) => {
+            Check::failed(name, format!("{canister} has no module installed")).with_hint(NNS_HINT)
+        }
+        Err(e) => Check::failed(name, format!("{canister} not found: {e}")).with_hint(NNS_HINT),
+    }
+}
+
+/// The owner gets its ICP from the minting account; failing that, it must already hold enough
+async fn check_icp_funding(agent: &Agent, ledger: Principal, owner: Option<Principal>) -> Check {
+    const SUBJECT: &str = "ICP funding";
+    let needed = DEVELOPER_ICP + ICP_TRANSFER_FEE;
+
+    let minting_error = match load_minting_identity().and_then(|identity| {
+        identity
+            .sender()
+            .map_err(|e| anyhow::anyhow!("Failed to get identity principal: {e}"))
+    }) {
+        Ok(minting) => match get_icp_minting_account(agent, ledger).await {
+            Ok(Some(account)) if account.owner == minting && account.subaccount.is_none() => {
+                return Check::ok(SUBJECT, format!("minting identity {minting} loaded"));
+            }
+            Ok(Some(account)) => format!(
+                "the ledger's minting account is {}, not the minting identity {}",
+                account.owner, minting
+            ),
+            Ok(None) => "the ledger has no minting account".to_string(),
+            Err(e) => format!("{e:#}"),
+        },
+        Err(e) => format!("minting identity: {e:#}"),
+    };
+
+    let Some(owner) = owner else {
+        return Check::failed(SUBJECT, minting_error);
+    };
+    match get_icp_ledger_balance(agent, ledger, owner, None).await {
+        Ok(balance) if balance >= needed => Check::ok(
+            SUBJECT,
+            format!(
+                "owner already holds {} e8s, enough without minting ({})",
+                balance, minting_error
+            ),
+        ),
+        Ok(balance) => Check::failed(
+            SUBJECT,
+            format!(
+                "{}; owner holds {} e8s but needs {} e8s",
+                minting_error, balance, needed
+            ),
+        )
+        .with_hint(FUNDING_HINT),
+        Err(e) => Check::failed(SUBJECT, format!("{minting_error}; owner balance: {e:#}"))
+            .with_hint(NNS_HINT),
+    }
+}
+
+/// SNS-W must offer a version with a wasm for every SNS canister
+pub(crate) async fn check_sns_wasms(agent: &Agent, snsw: Principal) -> Check {
+    const SUBJECT: &str = "SNS wasms";
+    let steps = match list_upgrade_steps(agent, snsw).await {
+        Ok(steps) => steps,
+        Err(e) => return Check::failed(SUBJECT, format!("{e:#}")).with_hint(NNS_HINT),
+    };
+    let Some(latest) = steps.into_iter().rev().find_map(|step| step.version) else {
+        return Check::failed(SUBJECT, "SNS-W has no SNS version").with_hint(NNS_HINT);
+    };
+
+    let missing: Vec<&str> = [
+        ("root", &latest.root_wasm_hash),
+        ("governance", &latest.governance_wasm_hash),
+        ("ledger", &latest.ledger_wasm_hash),
+        ("swap", &latest.swap_wasm_hash),
+        ("archive", &latest.archive_wasm_hash),
+        ("index", &latest.index_wasm_hash),
+    ]
+    .into_iter()
+    .filter(|(_, hash)| hash.is_empty())
+    .map(|(role, _)| role)
+    .collect();
+
+    if missing.is_empty() {
+        Check::ok(SUBJECT, "latest SNS-W version has all six canister wasms")
+    } else {
+        Check::failed(
+            SUBJECT,
+            format!(
+                "latest SNS-W version has no wasm for: {}",
+                missing.join(", ")
+            ),
+        )
+        .with_hint(NNS_HINT)
+    }
+}
@@ -0,0 +1,174 @@
+// Record canister calls to a file during a real run, and replay them from it without a replica
+
+use anyhow::{Context, Result};
+use candid::Principal;
+use ic_agent::AgentError;
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, Write};
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+/// One canister call and its outcome, a line of a recording file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedInteraction {
+    /// `query` or `update`
+    pub kind: String,
+    pub canister: String,
+    pub method: String,
+    /// Candid-encoded arguments (hex)
+    pub arg: String,
+    /// Candid-encoded reply (hex), if the call succeeded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reply: Option<String>,
+    /// Error message, if the call failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl RecordedInteraction {
+    fn is_call(&self, kind: &str, canister: &Principal, method: &str) -> bool {
+        self.kind == kind && self.method == method && self.canister == canister.to_text()
+    }
+
+    fn outcome(&self) -> Result<Vec<u8>, AgentError> {
+        match (&self.reply, &self.error) {
+            (Some(reply), _) => hex::decode(reply).map_err(|e| {
+                AgentError::MessageError(format!("Recorded reply is not valid hex: {e}"))
+            }),
+            (None, Some(error)) => Err(AgentError::MessageError(format!("(replayed) {error}"))),
+            (None, None) => Err(AgentError::MessageError(
+                "Recorded call has neither a reply nor an error".to_string(),
+            )),
+        }
+    }
+}
+
+enum Session {
+    Record(Mutex<std::fs::File>),
+    Replay(Mutex<Replay>),
+}
+
+struct Replay {
+    interactions: Vec<RecordedInteraction>,
+    used: Vec<bool>,
+}
+
+static SESSION: OnceLock<Session> = OnceLock::new();
+
+/// Append every canister call and its outcome to `path` (`--record`)
+pub fn start_recording(path: &Path) -> Result<()> {
+    let file = std::fs::File::create(path)
+        .with_context(|| format!("Failed to create recording: {}", path.display()))?;
+    SESSION
+        .set(Session::Record(Mutex::new(file)))
+        .map_err(|_| anyhow::anyhow!("Recording or replay is already set"))
+}
+
+/// Answer canister calls from the recording at `path` instead of the replica (`--replay`)
+pub fn start_replay(path: &Path) -> Result<()> {
+    let interactions = read_recording(path)?;
+    let used = vec![false; interactions.len()];
+    SESSION
+        .set(Session::Replay(Mutex::new(Replay { interactions, used })))
+        .map_err(|_| anyhow::anyhow!("Recording or replay is already set"))
+}
+
+/// Whether canister calls are answered from a recording
+#[must_use]
+pub fn is_replaying() -> bool {
+    matches!(SESSION.get(), Some(Session::Replay(_)))
+}
+
+/// Read the interactions of a recording file
+pub fn read_recording(path: &Path) -> Result<Vec<RecordedInteraction>> {
+    let file = std::fs::File::open(path)
+        .with_context(|| format!("Failed to open recording: {}", path.display()))?;
+    let mut interactions = Vec::new();
+    for (index, line) in std::io::BufReader::new(file).lines().enumerate() {
+        let line = line.with_context(|| format!("Failed to read recording: {}", path.display()))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        interactions.push(serde_json::from_str(&line).with_context(|| {
+            format!("Invalid recording {} at line {}", path.display(), index + 1)
+        })?);
+    }
+    Ok(interactions)
+}
+
+/// The recorded outcome of a call when replaying, None when calls go to the replica
+///
+/// Calls are matched in order: first the next unused call with the same arguments, then the
+/// next unused call of the same method (arguments may hold timestamps), then the last call of
+/// the same method again, so polling loops settle on the final recorded state.
+pub(crate) fn replayed(
+    kind: &str,
+    canister: &Principal,
+    method: &str,
+    arg: &[u8],
+) -> Option<Result<Vec<u8>, AgentError>> {
+    let Some(Session::Replay(replay)) = SESSION.get() else {
+        return None;
+    };
+    let mut replay = replay.lock().expect("replay lock");
+    let arg = hex::encode(arg);
+
+    let unused = |replay: &Replay, same_arg: bool| {
+        replay.interactions.iter().enumerate().position(|(i, x)| {
+            !replay.used[i] && x.is_call(kind, canister, method) && (!same_arg || x.arg == arg)
+        })
+    };
+    if let Some(index) = unused(&replay, true).or_else(|| unused(&replay, false)) {
+        replay.used[index] = true;
+        return Some(replay.interactions[index].outcome());
+    }
+
+    Some(
+        replay
+            .interactions
+            .iter()
+            .rev()
+            .find(|x| x.is_call(kind, canister, method))
+            .map_or_else(
+                || {
+                    Err(AgentError::MessageError(format!(
+                        "No recorded {kind} call of {method} on {canister} to replay"
+                    )))
+                },
+                RecordedInteraction::outcome,
+            ),
+    )
+}
+
+/// Append a call and its outcome to the recording, if one is being made
+pub(crate) fn record(
+    kind: &str,
+    canister: &Principal,
+    method: &str,
+    arg: &[u8],
+    result: &Result<Vec<u8>, AgentError>,
+) {
+    let Some(Session::Record(file)) = SESSION.get() else {
+        return;
+    };
+    let interaction = RecordedInteraction {
+        kind: kind.to_string(),
+        canister: canister.to_text(),
+        method: method.to_string(),
+        arg: hex::encode(arg),
+        reply: result.as_ref().ok().map(hex::encode),
+        error: result.as_ref().err().map(ToString::to_string),
+    };
+    let Ok(mut line) = serde_json::to_string(&interaction) else {
+        return;
+    };
+    line.push('\n');
+    // Written per call, so a run that fails halfway still leaves its calls up to the failure
+    if let Err(e) = file
+        .lock()
+        .expect("recording lock")
+        .write_all(line.as_bytes())
+    {
+        log::warn!("Failed to write recording: {e}");
+    }
+}
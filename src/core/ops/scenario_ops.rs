@@ -0,0 +1,506 @@
+// Scenario playbooks: a declarative sequence of operations with assertions
+
+use crate::core::utils::exit_code::{ValidationError, invalid};
+use anyhow::{Context, Result};
+use candid::Principal;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use super::identity::{resolve_principal, signing_principal};
+use super::ledger_ops::{get_icp_ledger_balance, get_sns_ledger_balance};
+use super::sns_governance_ops::{
+    create_sns_neuron_default_path, list_neurons_for_principal,
+    list_neurons_for_principal_default_path, main_neuron_id, make_proposal,
+    mint_sns_tokens_with_all_votes_default_path, vote_on_proposal,
+};
+use super::{deployment, governance_ops, time_ops};
+use crate::core::context::app_context;
+use crate::core::declarations::sns_governance::{Action, MintSnsTokens, Motion, Proposal};
+use crate::core::utils::cli_args::parse_duration_secs;
+use crate::core::utils::network::network_config;
+use crate::core::utils::{print_header, print_step, print_success};
+use crate::init::sns_config::SnsConfig;
+
+/// A scenario file: a name and the steps to run in order
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub steps: Vec<Step>,
+}
+
+/// One operation or assertion
+///
+/// Principals are written as `owner`, `participant-<n>` (1-based), the name of an extra
+/// identity or a principal ID. Durations are seconds or strings like `30m`, `7d`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "action", rename_all = "kebab-case", deny_unknown_fields)]
+pub enum Step {
+    /// Deploy a new SNS (see `deploy-sns`)
+    DeploySns {
+        #[serde(default)]
+        config: Option<PathBuf>,
+        #[serde(default)]
+        fresh: bool,
+    },
+    /// Mint ICP from the minting account
+    MintIcp { to: String, amount_e8s: u64 },
+    /// Mint SNS tokens via a proposal every participant votes for
+    MintSnsTokens {
+        proposer: String,
+        to: String,
+        amount_e8s: u64,
+    },
+    /// Stake SNS tokens in a new neuron
+    CreateSnsNeuron {
+        principal: String,
+        #[serde(default)]
+        amount_e8s: Option<u64>,
+        #[serde(default)]
+        memo: Option<u64>,
+        #[serde(default)]
+        dissolve_delay: Option<DurationValue>,
+    },
+    /// Stake ICP in a new NNS neuron
+    CreateIcpNeuron {
+        principal: String,
+        amount_e8s: u64,
+        #[serde(default)]
+        memo: Option<u64>,
+        #[serde(default)]
+        dissolve_delay: Option<DurationValue>,
+    },
+    /// Submit an SNS proposal with the proposer's main neuron (a motion or a token mint)
+    SubmitProposal {
+        proposer: String,
+        #[serde(default)]
+        title: Option<String>,
+        #[serde(default)]
+        motion: Option<String>,
+        #[serde(default)]
+        mint_sns_tokens: Option<MintSpec>,
+        /// Label later `vote` steps can refer to the proposal by
+        #[serde(default)]
+        save_as: Option<String>,
+    },
+    /// Vote on an SNS proposal with the voter's main neuron (default: the last proposal submitted)
+    Vote {
+        principal: String,
+        #[serde(default)]
+        proposal: Option<ProposalRef>,
+        #[serde(default = "default_vote")]
+        vote: VoteChoice,
+    },
+    /// Move the replica clock forward (PocketIC only)
+    AdvanceTime { duration: DurationValue },
+    /// Check an ICP ledger balance
+    AssertIcpBalance {
+        principal: String,
+        expect: Expectation,
+    },
+    /// Check an SNS ledger balance
+    AssertSnsBalance {
+        principal: String,
+        expect: Expectation,
+    },
+    /// Check how many SNS neurons a principal has
+    AssertSnsNeuronCount {
+        principal: String,
+        expect: Expectation,
+    },
+}
+
+/// Receiver and amount of a mint proposal
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MintSpec {
+    pub to: String,
+    pub amount_e8s: u64,
+}
+
+/// A proposal ID or the `save_as` label of a proposal submitted earlier in the scenario
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum ProposalRef {
+    Id(u64),
+    Label(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VoteChoice {
+    Yes,
+    No,
+}
+
+const fn default_vote() -> VoteChoice {
+    VoteChoice::Yes
+}
+
+/// Seconds, or a string with a unit suffix (`90s`, `30m`, `2h`, `7d`, `1w`)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum DurationValue {
+    Seconds(u64),
+    Text(String),
+}
+
+impl std::fmt::Display for DurationValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Seconds(seconds) => write!(f, "{}s", seconds),
+            Self::Text(text) => f.write_str(text),
+        }
+    }
+}
+
+impl DurationValue {
+    pub(super) fn seconds(&self) -> Result<u64> {
+        match self {
+            Self::Seconds(seconds) => Ok(*seconds),
+            Self::Text(text) => parse_duration_secs(text),
+        }
+    }
+}
+
+/// Bounds a value must satisfy (any combination)
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Expectation {
+    #[serde(default)]
+    pub equals: Option<u64>,
+    #[serde(default)]
+    pub at_least: Option<u64>,
+    #[serde(default)]
+    pub at_most: Option<u64>,
+}
+
+impl Expectation {
+    fn check(&self, what: &str, actual: u64) -> Result<()> {
+        if self.equals.is_none() && self.at_least.is_none() && self.at_most.is_none() {
+            anyhow::bail!("Assertion on {} needs equals, at_least or at_most", what);
+        }
+        if let Some(expected) = self.equals
+            && actual != expected
+        {
+            anyhow::bail!("{} is {}, expected {}", what, actual, expected);
+        }
+        if let Some(minimum) = self.at_least
+            && actual < minimum
+        {
+            anyhow::bail!("{} is {}, expected at least {}", what, actual, minimum);
+        }
+        if let Some(maximum) = self.at_most
+            && actual > maximum
+        {
+            anyhow::bail!("{} is {}, expected at most {}", what, actual, maximum);
+        }
+        Ok(())
+    }
+}
+
+impl Scenario {
+    /// Load a scenario from a YAML, TOML or JSON file
+    pub fn load(path: &Path) -> Result<Self> {
+        load_spec_file(path, "scenario")
+    }
+}
+
+/// Parse a YAML, TOML or JSON file by its extension; `kind` names the file in errors
+pub(super) fn load_spec_file<T: DeserializeOwned>(path: &Path, kind: &str) -> Result<T> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {} file: {}", kind, path.display()))?;
+
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase)
+        .unwrap_or_default();
+
+    match extension.as_str() {
+        "yaml" | "yml" => serde_yaml::from_str(&content).with_context(|| {
+            ValidationError::new(format!("Invalid YAML in {}: {}", kind, path.display()))
+        }),
+        "toml" => toml::from_str(&content).with_context(|| {
+            ValidationError::new(format!("Invalid TOML in {}: {}", kind, path.display()))
+        }),
+        "json" => serde_json::from_str(&content).with_context(|| {
+            ValidationError::new(format!("Invalid JSON in {}: {}", kind, path.display()))
+        }),
+        _ => Err(invalid(format!(
+            "Unsupported {} format '{}'. Use .yaml, .toml or .json",
+            kind,
+            path.display()
+        ))),
+    }
+}
+
+impl Step {
+    /// Short description for progress output
+    #[must_use]
+    pub fn describe(&self) -> String {
+        match self {
+            Self::DeploySns { .. } => "Deploy SNS".to_string(),
+            Self::MintIcp { to, amount_e8s } => format!("Mint {} e8s ICP to {}", amount_e8s, to),
+            Self::MintSnsTokens { to, amount_e8s, .. } => {
+                format!("Mint {} e8s SNS tokens to {}", amount_e8s, to)
+            }
+            Self::CreateSnsNeuron { principal, .. } => {
+                format!("Create SNS neuron for {}", principal)
+            }
+            Self::CreateIcpNeuron { principal, .. } => {
+                format!("Create ICP neuron for {}", principal)
+            }
+            Self::SubmitProposal { proposer, .. } => format!("Submit proposal as {}", proposer),
+            Self::Vote {
+                principal, vote, ..
+            } => format!("Vote {:?} as {}", vote, principal),
+            Self::AdvanceTime { duration } => format!("Advance time by {}", duration),
+            Self::AssertIcpBalance { principal, .. } => {
+                format!("Check ICP balance of {}", principal)
+            }
+            Self::AssertSnsBalance { principal, .. } => {
+                format!("Check SNS balance of {}", principal)
+            }
+            Self::AssertSnsNeuronCount { principal, .. } => {
+                format!("Check SNS neuron count of {}", principal)
+            }
+        }
+    }
+}
+
+/// State carried between steps
+#[derive(Debug, Default)]
+struct ScenarioState {
+    proposals: HashMap<String, u64>,
+    last_proposal: Option<u64>,
+}
+
+/// Run every step in order, stopping at the first failure
+/// Returns the number of steps run
+pub async fn run_scenario(scenario: &Scenario) -> Result<usize> {
+    print_header(&format!(
+        "Scenario: {}",
+        scenario.name.as_deref().unwrap_or("(unnamed)")
+    ));
+
+    let mut state = ScenarioState::default();
+    let total = scenario.steps.len();
+    for (index, step) in scenario.steps.iter().enumerate() {
+        print_step(&format!("[{}/{}] {}", index + 1, total, step.describe()));
+        let outcome = run_step(step, &mut state)
+            .await
+            .with_context(|| format!("Step {} ({}) failed", index + 1, step.describe()))?;
+        print_success(&outcome);
+    }
+    Ok(total)
+}
+
+async fn run_step(step: &Step, state: &mut ScenarioState) -> Result<String> {
+    match step {
+        Step::DeploySns { config, fresh } => {
+            let config = match config {
+                Some(path) => SnsConfig::load(path)?,
+                None => SnsConfig::default(),
+            };
+            deployment::deploy_sns(config, *fresh, false).await?;
+            Ok("SNS deployed".to_string())
+        }
+        Step::MintIcp { to, amount_e8s } => {
+            let block =
+                governance_ops::mint_icp_default_path(resolve_principal(to)?, *amount_e8s, None)
+                    .await?;
+            Ok(format!("Minted at block {}", block))
+        }
+        Step::MintSnsTokens {
+            proposer,
+            to,
+            amount_e8s,
+        } => {
+            let proposal_id = mint_sns_tokens_with_all_votes_default_path(
+                resolve_principal(proposer)?,
+                resolve_principal(to)?,
+                *amount_e8s,
+            )
+            .await?;
+            state.last_proposal = Some(proposal_id);
+            Ok(format!("Mint proposal {} adopted", proposal_id))
+        }
+        Step::CreateSnsNeuron {
+            principal,
+            amount_e8s,
+            memo,
+            dissolve_delay,
+        } => {
+            let dissolve_delay = dissolve_delay
+                .as_ref()
+                .map(DurationValue::seconds)
+                .transpose()?;
+            let neuron_id = create_sns_neuron_default_path(
+                resolve_principal(principal)?,
+                *amount_e8s,
+                *memo,
+                dissolve_delay,
+                None,
+            )
+            .await?;
+            Ok(format!("Created SNS neuron {}", hex::encode(neuron_id)))
+        }
+        Step::CreateIcpNeuron {
+            principal,
+            amount_e8s,
+            memo,
+            dissolve_delay,
+        } => {
+            let dissolve_delay = dissolve_delay
+                .as_ref()
+                .map(DurationValue::seconds)
+                .transpose()?;
+            let neuron_id = governance_ops::create_icp_neuron_default_path(
+                resolve_principal(principal)?,
+                *amount_e8s,
+                *memo,
+                dissolve_delay,
+                None,
+            )
+            .await?;
+            Ok(format!("Created ICP neuron {}", neuron_id))
+        }
+        Step::SubmitProposal {
+            proposer,
+            title,
+            motion,
+            mint_sns_tokens,
+            save_as,
+        } => {
+            let proposal = build_proposal(
+                title.as_deref(),
+                motion.as_deref(),
+                mint_sns_tokens.as_ref(),
+            )?;
+            let proposer = resolve_principal(proposer)?;
+            let (agent, governance_canister, neuron) = main_neuron_of(proposer).await?;
+            let proposal_id = make_proposal(&agent, governance_canister, neuron, proposal).await?;
+            state.last_proposal = Some(proposal_id);
+            if let Some(label) = save_as {
+                state.proposals.insert(label.clone(), proposal_id);
+            }
+            Ok(format!("Submitted proposal {}", proposal_id))
+        }
+        Step::Vote {
+            principal,
+            proposal,
+            vote,
+        } => {
+            let proposal_id = match proposal {
+                Some(ProposalRef::Id(id)) => *id,
+                Some(ProposalRef::Label(label)) => *state
+                    .proposals
+                    .get(label)
+                    .with_context(|| format!("No proposal saved as '{}'", label))?,
+                None => state
+                    .last_proposal
+                    .context("No proposal submitted yet; set `proposal`")?,
+            };
+            let voter = resolve_principal(principal)?;
+            let (agent, governance_canister, neuron) = main_neuron_of(voter).await?;
+            let vote_code = match vote {
+                VoteChoice::Yes => 1,
+                VoteChoice::No => 2,
+            };
+            vote_on_proposal(&agent, governance_canister, neuron, proposal_id, vote_code).await?;
+            Ok(format!("Voted {:?} on proposal {}", vote, proposal_id))
+        }
+        Step::AdvanceTime { duration } => {
+            let advance = time_ops::advance_time(duration.seconds()?).await?;
+            Ok(format!(
+                "Replica time is now {}",
+                advance.after_nanos / 1_000_000_000
+            ))
+        }
+        Step::AssertIcpBalance { principal, expect } => {
+            let agent = app_context().anonymous_agent().await?;
+            let balance = get_icp_ledger_balance(
+                &agent,
+                network_config().ledger_canister,
+                resolve_principal(principal)?,
+                None,
+            )
+            .await?;
+            expect.check(&format!("ICP balance of {}", principal), balance)?;
+            Ok(format!("ICP balance {} e8s", balance))
+        }
+        Step::AssertSnsBalance { principal, expect } => {
+            let agent = app_context().anonymous_agent().await?;
+            let balance = get_sns_ledger_balance(
+                &agent,
+                app_context().sns_ledger_canister()?,
+                resolve_principal(principal)?,
+                None,
+            )
+            .await?;
+            expect.check(&format!("SNS balance of {}", principal), balance)?;
+            Ok(format!("SNS balance {} e8s", balance))
+        }
+        Step::AssertSnsNeuronCount { principal, expect } => {
+            let neurons =
+                list_neurons_for_principal_default_path(resolve_principal(principal)?).await?;
+            let count = neurons.len() as u64;
+            expect.check(&format!("SNS neuron count of {}", principal), count)?;
+            Ok(format!("{} SNS neurons", count))
+        }
+    }
+}
+
+fn build_proposal(
+    title: Option<&str>,
+    motion: Option<&str>,
+    mint: Option<&MintSpec>,
+) -> Result<Proposal> {
+    let (default_title, action) = match (motion, mint) {
+        (Some(text), None) => (
+            "Motion".to_string(),
+            Action::Motion(Motion {
+                motion_text: text.to_string(),
+            }),
+        ),
+        (None, Some(mint)) => {
+            let receiver = resolve_principal(&mint.to)?;
+            (
+                format!("Mint {} tokens to {}", mint.amount_e8s, receiver),
+                Action::MintSnsTokens(MintSnsTokens {
+                    to_principal: Some(receiver),
+                    to_subaccount: None,
+                    memo: None,
+                    amount_e8s: Some(mint.amount_e8s),
+                }),
+            )
+        }
+        _ => anyhow::bail!("submit-proposal needs exactly one of motion or mint_sns_tokens"),
+    };
+
+    let title = title.map_or(default_title, str::to_string);
+    Ok(Proposal {
+        url: String::new(),
+        summary: title.clone(),
+        title,
+        action: Some(action),
+    })
+}
+
+/// Agent, SNS governance canister and main neuron of a principal
+pub(super) async fn main_neuron_of(
+    principal: Principal,
+) -> Result<(ic_agent::Agent, Principal, Vec<u8>)> {
+    let agent = app_context()
+        .agent_for_principal(signing_principal(principal))
+        .await?;
+    let governance_canister = app_context().sns_governance_canister()?;
+    let neurons = list_neurons_for_principal(&agent, governance_canister, principal).await?;
+    let neuron =
+        main_neuron_id(&neurons).with_context(|| format!("{} has no SNS neurons", principal))?;
+    Ok((agent, governance_canister, neuron.id.clone()))
+}
@@ -0,0 +1,400 @@
+// Seed data: fill the deployed SNS with holders, balances, neurons and following for demos
+
+use crate::core::utils::exit_code::ValidationError;
+use anyhow::{Context, Result};
+use candid::Principal;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use super::governance_ops::mint_icp_default_path;
+use super::identity::resolve_principal;
+use super::identity::{create_named_identity, find_named_identity};
+use super::ledger_ops::{
+    get_sns_ledger_balance, get_sns_ledger_fee, transfer_sns_tokens, wait_for_sns_balance,
+};
+use super::scenario_ops::{DurationValue, load_spec_file, main_neuron_of};
+use super::sns_governance_ops::{
+    create_sns_neuron_default_path, follow_sns_neurons, get_neuron_minimum_stake,
+    list_neurons_for_principal, mint_sns_tokens_with_all_votes_default_path,
+};
+use crate::core::context::app_context;
+use crate::core::utils::{print_info, print_step, print_success};
+
+/// Function ID whose followees apply to every function without followees of its own
+const CATCH_ALL_FUNCTION_ID: u64 = 0;
+
+/// A seed spec: who holds what and how it is staked
+///
+/// Holders are extra identities `<holder_prefix>-1` … `<holder_prefix>-<holders>`, created
+/// on first use. Principals in `minter` and `follow` are written as in scenarios.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SeedSpec {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub holders: usize,
+    #[serde(default = "default_holder_prefix")]
+    pub holder_prefix: String,
+    /// Proposer of the mint proposal, who then pays every holder (needs an SNS neuron)
+    #[serde(default = "default_minter")]
+    pub minter: String,
+    pub sns: SnsAllocation,
+    /// ICP minted to every holder
+    #[serde(default)]
+    pub icp_e8s: Option<u64>,
+    /// Dissolve delay of each neuron a holder stakes; the staked share is split evenly
+    #[serde(default)]
+    pub neuron_ladder: Vec<DurationValue>,
+    /// Principals whose main neurons every seeded neuron follows
+    #[serde(default)]
+    pub follow: Vec<String>,
+}
+
+/// How SNS tokens are spread over the holders
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SnsAllocation {
+    pub total_e8s: u64,
+    #[serde(default)]
+    pub distribution: Distribution,
+    /// Exponent of the `power` distribution (holder n gets a weight of 1/n^exponent)
+    #[serde(default = "default_exponent")]
+    pub exponent: f64,
+    /// Percentage of each holder's balance staked across the neuron ladder
+    #[serde(default = "default_stake_percent")]
+    pub stake_percent: u8,
+}
+
+/// Shape of the balance distribution, largest holder first
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Distribution {
+    #[default]
+    Equal,
+    Linear,
+    Power,
+}
+
+fn default_holder_prefix() -> String {
+    "holder".to_string()
+}
+
+fn default_minter() -> String {
+    "participant-1".to_string()
+}
+
+const fn default_exponent() -> f64 {
+    1.0
+}
+
+const fn default_stake_percent() -> u8 {
+    80
+}
+
+/// Balance and neurons of one holder
+#[derive(Debug, Clone, Serialize)]
+pub struct HolderPlan {
+    pub name: String,
+    pub balance_e8s: u64,
+    pub stakes: Vec<StakePlan>,
+}
+
+/// One rung of a holder's neuron ladder
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct StakePlan {
+    pub amount_e8s: u64,
+    pub dissolve_delay_seconds: u64,
+}
+
+/// What seeding a spec does, computed without touching the network
+#[derive(Debug, Clone, Serialize)]
+pub struct SeedPlan {
+    pub holders: Vec<HolderPlan>,
+    pub icp_e8s: Option<u64>,
+    pub follow: Vec<String>,
+}
+
+impl SeedPlan {
+    /// Number of neurons the plan creates
+    #[must_use]
+    pub fn neuron_count(&self) -> usize {
+        self.holders.iter().map(|h| h.stakes.len()).sum()
+    }
+}
+
+/// Holders and neurons created by a seed run
+#[derive(Debug, Clone)]
+pub struct SeedSummary {
+    pub holders: Vec<(String, Principal)>,
+    pub neurons: usize,
+    pub followed: usize,
+}
+
+impl SeedSpec {
+    /// Load a seed spec from a YAML, TOML or JSON file
+    pub fn load(path: &Path) -> Result<Self> {
+        load_spec_file(path, "seed spec")
+    }
+
+    /// Work out every holder's balance and stakes
+    pub fn plan(&self) -> Result<SeedPlan> {
+        if self.holders == 0 {
+            anyhow::bail!("holders must be at least 1");
+        }
+        if self.sns.stake_percent > 100 {
+            anyhow::bail!(
+                "sns.stake_percent must be at most 100, got {}",
+                self.sns.stake_percent
+            );
+        }
+        if self.sns.distribution == Distribution::Power
+            && !(self.sns.exponent.is_finite() && self.sns.exponent > 0.0)
+        {
+            anyhow::bail!(
+                "sns.exponent must be a positive number, got {}",
+                self.sns.exponent
+            );
+        }
+
+        let delays = self
+            .neuron_ladder
+            .iter()
+            .map(|delay| {
+                delay.seconds().with_context(|| {
+                    ValidationError::new(format!("Invalid neuron_ladder entry '{}'", delay))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let holders = distribute(self.sns.total_e8s, self.holders, &self.sns)
+            .into_iter()
+            .enumerate()
+            .map(|(index, balance_e8s)| {
+                let staked = balance_e8s / 100 * u64::from(self.sns.stake_percent);
+                let per_rung = staked / (delays.len().max(1) as u64);
+                HolderPlan {
+                    name: format!("{}-{}", self.holder_prefix, index + 1),
+                    balance_e8s,
+                    stakes: delays
+                        .iter()
+                        .map(|&dissolve_delay_seconds| StakePlan {
+                            amount_e8s: per_rung,
+                            dissolve_delay_seconds,
+                        })
+                        .collect(),
+                }
+            })
+            .collect();
+
+        Ok(SeedPlan {
+            holders,
+            icp_e8s: self.icp_e8s,
+            follow: self.follow.clone(),
+        })
+    }
+}
+
+/// Split `total` over `count` holders by the distribution's weights; rounding dust goes
+/// to the first (largest) holder
+fn distribute(total: u64, count: usize, allocation: &SnsAllocation) -> Vec<u64> {
+    let weights: Vec<f64> = (1..=count)
+        .map(|n| match allocation.distribution {
+            Distribution::Equal => 1.0,
+            Distribution::Linear => (count + 1 - n) as f64,
+            Distribution::Power => 1.0 / (n as f64).powf(allocation.exponent),
+        })
+        .collect();
+    let weight_sum: f64 = weights.iter().sum();
+
+    let mut shares: Vec<u64> = weights
+        .iter()
+        .map(|w| (total as f64 * w / weight_sum).floor() as u64)
+        .collect();
+    let assigned: u64 = shares.iter().sum();
+    shares[0] += total.saturating_sub(assigned);
+    shares
+}
+
+/// Seed the deployed SNS: create the holders, mint and pay out their tokens, stake the
+/// neuron ladder and set following
+pub async fn run_seed(spec: &SeedSpec) -> Result<SeedSummary> {
+    let plan = spec.plan()?;
+    let deployment_data = app_context().deployment_data()?;
+    let ledger_canister = deployment_data.deployed_sns.ledger_canister()?;
+    let governance_canister = deployment_data.deployed_sns.governance_canister()?;
+    let minter = resolve_principal(&spec.minter)?;
+
+    // Check the plan against the live parameters before anything is minted
+    let anonymous = app_context().anonymous_agent().await?;
+    let fee = get_sns_ledger_fee(&anonymous, ledger_canister).await?;
+    let minimum_stake = get_neuron_minimum_stake(&anonymous, governance_canister).await?;
+    for holder in &plan.holders {
+        for stake in &holder.stakes {
+            if stake.amount_e8s < minimum_stake {
+                anyhow::bail!(
+                    "{} would stake {} e8s per neuron, below the minimum stake of {} e8s. \
+                     Raise sns.total_e8s or sns.stake_percent, or shorten neuron_ladder",
+                    holder.name,
+                    stake.amount_e8s,
+                    minimum_stake
+                );
+            }
+        }
+        let needed: u64 = holder.stakes.iter().map(|s| s.amount_e8s + fee).sum();
+        if needed > holder.balance_e8s {
+            anyhow::bail!(
+                "{} needs {} e8s to stake its neurons (including fees) but holds {} e8s. \
+                 Lower sns.stake_percent",
+                holder.name,
+                needed,
+                holder.balance_e8s
+            );
+        }
+    }
+
+    print_step(&format!(
+        "Preparing {} holder identities",
+        plan.holders.len()
+    ));
+    let mut holders = Vec::with_capacity(plan.holders.len());
+    for holder in &plan.holders {
+        let identity = match find_named_identity(&holder.name) {
+            Ok(identity) => identity,
+            Err(_) => create_named_identity(Some(&holder.name))?,
+        };
+        let principal = Principal::from_text(&identity.principal).with_context(|| {
+            ValidationError::new(format!("Invalid principal for identity '{}'", holder.name))
+        })?;
+        holders.push((holder.name.clone(), principal));
+    }
+
+    // One mint proposal for everything, then the minter pays each holder
+    let payout: u64 = plan.holders.iter().map(|h| h.balance_e8s + fee).sum();
+    print_step(&format!("Minting {} e8s SNS tokens to {}", payout, minter));
+    let minter_agent = app_context().agent_for_principal(minter).await?;
+    let before = get_sns_ledger_balance(&minter_agent, ledger_canister, minter, None).await?;
+    let proposal_id = mint_sns_tokens_with_all_votes_default_path(minter, minter, payout)
+        .await
+        .context("Failed to mint the seed tokens")?;
+    wait_for_sns_balance(
+        &minter_agent,
+        ledger_canister,
+        minter,
+        None,
+        before + payout,
+    )
+    .await
+    .with_context(|| format!("Mint proposal {} did not pay out", proposal_id))?;
+
+    print_step("Paying out holder balances");
+    for (holder, (name, principal)) in plan.holders.iter().zip(&holders) {
+        transfer_sns_tokens(
+            &minter_agent,
+            ledger_canister,
+            *principal,
+            holder.balance_e8s,
+            None,
+        )
+        .await
+        .with_context(|| format!("Failed to pay {}", name))?;
+        print_info(&format!("{}: {} e8s", name, holder.balance_e8s));
+    }
+
+    if let Some(icp_e8s) = plan.icp_e8s {
+        print_step(&format!("Minting {} e8s ICP to every holder", icp_e8s));
+        for (name, principal) in &holders {
+            mint_icp_default_path(*principal, icp_e8s, None)
+                .await
+                .with_context(|| format!("Failed to mint ICP to {}", name))?;
+        }
+    }
+
+    print_step(&format!("Staking {} neurons", plan.neuron_count()));
+    for (holder, (name, principal)) in plan.holders.iter().zip(&holders) {
+        wait_for_sns_balance(
+            &minter_agent,
+            ledger_canister,
+            *principal,
+            None,
+            holder.balance_e8s,
+        )
+        .await
+        .with_context(|| format!("Payout to {} did not land", name))?;
+        for stake in &holder.stakes {
+            create_sns_neuron_default_path(
+                *principal,
+                Some(stake.amount_e8s),
+                None,
+                Some(stake.dissolve_delay_seconds),
+                None,
+            )
+            .await
+            .with_context(|| format!("Failed to stake a neuron for {}", name))?;
+        }
+    }
+
+    let followed = if plan.follow.is_empty() {
+        0
+    } else {
+        set_following(&plan.follow, &holders, governance_canister).await?
+    };
+
+    print_success(&format!(
+        "Seeded {} holders with {} neurons",
+        holders.len(),
+        plan.neuron_count()
+    ));
+    Ok(SeedSummary {
+        holders,
+        neurons: plan.neuron_count(),
+        followed,
+    })
+}
+
+/// Make every neuron of the holders follow the main neurons of `follow`
+/// Returns the number of neurons whose followees were set
+async fn set_following(
+    follow: &[String],
+    holders: &[(String, Principal)],
+    governance_canister: Principal,
+) -> Result<usize> {
+    print_step(&format!(
+        "Following the main neurons of {}",
+        follow.join(", ")
+    ));
+    let mut followees = Vec::with_capacity(follow.len());
+    for reference in follow {
+        let (_, _, neuron_id) = main_neuron_of(resolve_principal(reference)?)
+            .await
+            .with_context(|| format!("Cannot follow {}", reference))?;
+        followees.push(neuron_id);
+    }
+
+    let mut followed = 0;
+    for (name, principal) in holders {
+        let agent = app_context().agent_for_principal(*principal).await?;
+        let neurons = list_neurons_for_principal(&agent, governance_canister, *principal).await?;
+        for neuron_id in neurons.iter().filter_map(|n| n.id.as_ref()) {
+            // A neuron cannot follow itself
+            let others: Vec<Vec<u8>> = followees
+                .iter()
+                .filter(|id| **id != neuron_id.id)
+                .cloned()
+                .collect();
+            if others.is_empty() {
+                continue;
+            }
+            follow_sns_neurons(
+                &agent,
+                governance_canister,
+                neuron_id.id.clone(),
+                CATCH_ALL_FUNCTION_ID,
+                others,
+            )
+            .await
+            .with_context(|| format!("Failed to set followees for a neuron of {}", name))?;
+            followed += 1;
+        }
+    }
+    Ok(followed)
+}
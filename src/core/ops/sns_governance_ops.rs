@@ -1,46 +1,39 @@
 // SNS Governance canister operations
 
+use super::canister_call;
+use super::canister_client::CanisterClient;
+use super::governance_errors::{describe_sns_governance_error, sns_governance_error};
+use super::identity::{resolve_identity_for_principal, signing_principal};
+use crate::core::context::app_context;
+use crate::core::utils::exit_code::invalid;
+use crate::core::utils::interrupt::{self, CriticalSection};
 use anyhow::{Context, Result};
 use candid::{Decode, Principal, encode_args};
-use ic_agent::Agent;
 use std::path::PathBuf;
 
 #[allow(unused_imports)]
 use super::super::declarations::sns_governance::{
     Account, Action, AddNeuronPermissions, By, ClaimOrRefresh, Command, Command1, Configure,
-    Disburse, DissolveState, GetProposal, Governance, IncreaseDissolveDelay, ListNeurons,
-    ListNeuronsResponse, ManageNeuron, ManageNeuronResponse, MemoAndController, MintSnsTokens,
-    NervousSystemParameters, Neuron, NeuronId, NeuronPermissionList, Operation, Proposal,
-    ProposalId, RegisterVote,
+    Disburse, DissolveState, Follow, GetMetricsRequest, GetMetricsResponse, GetMetricsResult,
+    GetNeuron, GetNeuronResponse, GetProposal, GetProposalResponse, Governance,
+    IncreaseDissolveDelay, ListNeurons, ListNeuronsResponse, ListProposals, ListProposalsResponse,
+    ManageNeuron, ManageNeuronResponse, MemoAndController, MintSnsTokens, NervousSystemParameters,
+    Neuron, NeuronId, NeuronPermissionList, Operation, Percentage, Proposal, ProposalData,
+    ProposalId, RegisterVote, RemoveNeuronPermissions, Result_, Result1,
 };
 use super::ledger_ops::{
-    generate_subaccount_by_nonce, get_sns_ledger_balance, get_sns_ledger_fee, transfer_sns_tokens,
+    auto_neuron_memo, generate_subaccount_by_nonce, get_sns_ledger_balance, get_sns_ledger_fee,
+    transfer_sns_tokens_from_subaccount, wait_for_sns_balance,
 };
 
 /// List all neurons for a given principal, sorted by dissolve delay (lowest first) and cached stake (highest first)
 pub async fn list_neurons_for_principal(
-    agent: &Agent,
+    agent: &dyn CanisterClient,
     governance_canister: Principal,
     principal: Principal,
 ) -> Result<Vec<Neuron>> {
-    let request = ListNeurons {
-        of_principal: Some(principal),
-        limit: 100,
-        start_page_at: None,
-    };
-    let args = candid::encode_args((request,))?;
-
-    let response = agent
-        .query(&governance_canister, "list_neurons")
-        .with_arg(args)
-        .call()
-        .await
-        .context("Failed to call list_neurons")?;
-
-    let result: ListNeuronsResponse = Decode!(&response, ListNeuronsResponse)?;
-
     // Sort neurons by dissolve delay (lowest first), then by cached stake (highest first)
-    let mut neurons = result.neurons;
+    let mut neurons = list_neuron_pages(agent, governance_canister, Some(principal)).await?;
     neurons.sort_by(|a, b| {
         let a_delay = match &a.dissolve_state {
             Some(DissolveState::DissolveDelaySeconds(seconds)) => *seconds,
@@ -66,57 +59,220 @@ pub async fn list_neurons_for_principal(
     Ok(neurons)
 }
 
+/// Get the SNS governance parameters
+pub async fn get_nervous_system_parameters(
+    agent: &dyn CanisterClient,
+    governance_canister: Principal,
+) -> Result<NervousSystemParameters> {
+    let result_bytes = canister_call::query(
+        agent,
+        &governance_canister,
+        "get_nervous_system_parameters",
+        encode_args(())?,
+    )
+    .await
+    .context("Failed to call get_nervous_system_parameters")?;
+
+    Decode!(&result_bytes, NervousSystemParameters)
+        .context("Failed to decode nervous system parameters")
+}
+
+/// The nervous system parameters that go into an SNS neuron's voting power
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VotingPowerParams {
+    pub min_dissolve_delay_to_vote_seconds: u64,
+    pub max_dissolve_delay_seconds: u64,
+    pub max_dissolve_delay_bonus_percentage: u64,
+    pub max_neuron_age_for_age_bonus: u64,
+    pub max_age_bonus_percentage: u64,
+}
+
+impl VotingPowerParams {
+    #[must_use]
+    pub fn from_parameters(params: &NervousSystemParameters) -> Self {
+        Self {
+            min_dissolve_delay_to_vote_seconds: params
+                .neuron_minimum_dissolve_delay_to_vote_seconds
+                .unwrap_or(0),
+            max_dissolve_delay_seconds: params.max_dissolve_delay_seconds.unwrap_or(0),
+            max_dissolve_delay_bonus_percentage: params
+                .max_dissolve_delay_bonus_percentage
+                .unwrap_or(0),
+            max_neuron_age_for_age_bonus: params.max_neuron_age_for_age_bonus.unwrap_or(0),
+            max_age_bonus_percentage: params.max_age_bonus_percentage.unwrap_or(0),
+        }
+    }
+
+    /// Voting power of a neuron at `now_seconds`, computed the way SNS governance does
+    /// Stake (minus fees, plus staked maturity) × dissolve delay bonus × age bonus × the
+    /// neuron's voting power multiplier, in integer e8s. Zero below the minimum dissolve delay to vote.
+    #[must_use]
+    pub fn voting_power(&self, neuron: &Neuron, now_seconds: u64) -> u64 {
+        let dissolve_delay = match neuron.dissolve_state {
+            Some(DissolveState::DissolveDelaySeconds(seconds)) => seconds,
+            Some(DissolveState::WhenDissolvedTimestampSeconds(timestamp)) => {
+                timestamp.saturating_sub(now_seconds)
+            }
+            None => 0,
+        };
+        if dissolve_delay < self.min_dissolve_delay_to_vote_seconds {
+            return 0;
+        }
+
+        let stake = u128::from(
+            neuron
+                .cached_neuron_stake_e8s
+                .saturating_sub(neuron.neuron_fees_e8s)
+                .saturating_add(neuron.staked_maturity_e8s_equivalent.unwrap_or(0)),
+        );
+
+        let max_delay = u128::from(self.max_dissolve_delay_seconds);
+        let delay = u128::from(dissolve_delay).min(max_delay);
+        let delay_stake = if max_delay > 0 {
+            stake
+                + stake * delay * u128::from(self.max_dissolve_delay_bonus_percentage)
+                    / (100 * max_delay)
+        } else {
+            stake
+        };
+
+        // Dissolving neurons do not age (aging_since is u64::MAX)
+        let max_age = u128::from(self.max_neuron_age_for_age_bonus);
+        let age = u128::from(now_seconds.saturating_sub(neuron.aging_since_timestamp_seconds))
+            .min(max_age);
+        let aged_stake = if max_age > 0 {
+            delay_stake
+                + delay_stake * age * u128::from(self.max_age_bonus_percentage) / (100 * max_age)
+        } else {
+            delay_stake
+        };
+
+        let power = aged_stake * u128::from(neuron.voting_power_percentage_multiplier) / 100;
+        u64::try_from(power).unwrap_or(u64::MAX)
+    }
+}
+
+/// Voting power parameters of the deployed SNS
+pub async fn get_voting_power_params_default_path() -> Result<VotingPowerParams> {
+    let agent = app_context().anonymous_agent().await?;
+    let params = get_nervous_system_parameters(&agent, app_context().sns_governance_canister()?)
+        .await
+        .context("Failed to get nervous system parameters")?;
+    Ok(VotingPowerParams::from_parameters(&params))
+}
+
 /// Get neuron minimum stake from SNS governance parameters
 pub async fn get_neuron_minimum_stake(
-    agent: &Agent,
+    agent: &dyn CanisterClient,
     governance_canister: Principal,
 ) -> Result<u64> {
-    let result_bytes = agent
-        .query(&governance_canister, "get_nervous_system_parameters")
-        .with_arg(encode_args(())?)
-        .call()
-        .await
-        .context("Failed to call get_nervous_system_parameters")?;
-
-    let params: NervousSystemParameters = Decode!(&result_bytes, NervousSystemParameters)
-        .context("Failed to decode nervous system parameters")?;
+    let params = get_nervous_system_parameters(agent, governance_canister).await?;
 
     params
         .neuron_minimum_stake_e8s
         .ok_or_else(|| anyhow::anyhow!("neuron_minimum_stake_e8s not set in governance parameters"))
 }
 
+/// List every neuron of the SNS, following `list_neurons` pages until the last one
+pub async fn list_all_neurons(
+    agent: &dyn CanisterClient,
+    governance_canister: Principal,
+) -> Result<Vec<Neuron>> {
+    list_neuron_pages(agent, governance_canister, None).await
+}
+
+/// Neurons of `of_principal` (every neuron with `None`), following `list_neurons` pages
+/// until the last one: governance returns at most 100 per call
+async fn list_neuron_pages(
+    agent: &dyn CanisterClient,
+    governance_canister: Principal,
+    of_principal: Option<Principal>,
+) -> Result<Vec<Neuron>> {
+    const PAGE_SIZE: u32 = 100;
+
+    let mut neurons: Vec<Neuron> = Vec::new();
+    loop {
+        let request = ListNeurons {
+            of_principal,
+            limit: PAGE_SIZE,
+            start_page_at: neurons
+                .last()
+                .and_then(|n| n.id.as_ref())
+                .map(|id| NeuronId { id: id.id.clone() }),
+        };
+        let response = canister_call::query(
+            agent,
+            &governance_canister,
+            "list_neurons",
+            encode_args((request,))?,
+        )
+        .await
+        .context("Failed to call list_neurons")?;
+        let page = Decode!(&response, ListNeuronsResponse)
+            .context("Failed to decode list_neurons response")?
+            .neurons;
+
+        let last_page = page.len() < PAGE_SIZE as usize;
+        neurons.extend(page);
+        if last_page {
+            return Ok(neurons);
+        }
+    }
+}
+
 /// High-level function to list neurons for a principal
 /// This reads deployment data and lists neurons using an anonymous agent
 pub async fn list_neurons_for_principal_default_path(principal: Principal) -> Result<Vec<Neuron>> {
-    use super::identity::create_agent;
-
-    // Read deployment data
-    let deployment_path = crate::core::utils::data_output::get_output_path();
-    let data_content = std::fs::read_to_string(&deployment_path)
-        .with_context(|| format!("Failed to read deployment data from: {:?}", deployment_path))?;
-    let deployment_data: crate::core::utils::data_output::SnsCreationData =
-        serde_json::from_str(&data_content).context("Failed to parse deployment data JSON")?;
-
     // Get governance canister ID
-    let governance_canister_id = deployment_data
-        .deployed_sns
-        .governance_canister_id
-        .as_ref()
-        .and_then(|s| Principal::from_text(s).ok())
-        .context("Failed to parse governance canister ID from deployment data")?;
+    let governance_canister_id = app_context().sns_governance_canister()?;
 
     // Create anonymous agent (query doesn't need authentication)
-    let anonymous_identity = ic_agent::identity::AnonymousIdentity;
-    let agent = create_agent(Box::new(anonymous_identity)).await?;
+    let agent = app_context().anonymous_agent().await?;
 
     // List neurons
     list_neurons_for_principal(&agent, governance_canister_id, principal).await
 }
 
+/// High-level function to get an SNS neuron by ID
+/// `neuron_id` is a full hex ID, or a unique prefix (or `prefix...suffix`) of any neuron of the SNS
+pub async fn get_sns_neuron_default_path(neuron_id: &str) -> Result<Neuron> {
+    let governance_canister = app_context().sns_governance_canister()?;
+    let agent = app_context().anonymous_agent().await?;
+
+    let hex_id = neuron_id.trim();
+    let hex_id = hex_id.strip_prefix("0x").unwrap_or(hex_id);
+    // SNS neuron IDs are 32 bytes; a shorter one is matched against every neuron of the SNS
+    let id = if hex_id.len() == 64 {
+        hex::decode(hex_id).context("Failed to decode neuron ID from hex")?
+    } else {
+        let neurons = list_all_neurons(&agent, governance_canister).await?;
+        match_neuron_id(&neurons, hex_id)?
+    };
+    get_sns_neuron(&agent, governance_canister, &id)
+        .await?
+        .with_context(|| format!("No SNS neuron with ID {}", hex::encode(&id)))
+}
+
+/// SNS neurons of the owner and every participant, listed concurrently
+/// Returns (label, principal, neurons) per account (see `SnsCreationData::labelled_accounts`)
+pub async fn list_all_neurons_default_path() -> Result<Vec<(String, Principal, Vec<Neuron>)>> {
+    let data = app_context().deployment_data()?;
+    futures::future::try_join_all(data.labelled_accounts().into_iter().map(
+        |(label, principal)| async move {
+            let principal = Principal::from_text(&principal)
+                .with_context(|| format!("Failed to parse principal of {}", label))?;
+            let neurons = list_neurons_for_principal_default_path(principal)
+                .await
+                .with_context(|| format!("Failed to list SNS neurons of {}", label))?;
+            Ok((label, principal, neurons))
+        },
+    ))
+    .await
+}
+
 /// Add a hotkey to a neuron
 pub async fn add_hotkey_to_neuron(
-    agent: &Agent,
+    agent: &dyn CanisterClient,
     governance_canister: Principal,
     neuron_subaccount: Vec<u8>,
     hotkey_principal: Principal,
@@ -135,10 +291,7 @@ pub async fn add_hotkey_to_neuron(
     };
     let args = candid::encode_args((request,))?;
 
-    let response = agent
-        .update(&governance_canister, "manage_neuron")
-        .with_arg(args)
-        .call_and_wait()
+    let response = canister_call::update(agent, &governance_canister, "manage_neuron", args)
         .await
         .context("Failed to call manage_neuron")?;
 
@@ -148,11 +301,7 @@ pub async fn add_hotkey_to_neuron(
     if let Some(cmd) = result.command {
         match cmd {
             super::super::declarations::sns_governance::Command1::Error(e) => {
-                anyhow::bail!(
-                    "Governance error: {} (type: {})",
-                    e.error_message,
-                    e.error_type
-                );
+                return Err(sns_governance_error("Failed to add hotkey", &e));
             }
             super::super::declarations::sns_governance::Command1::AddNeuronPermission {} => {
                 // Success
@@ -176,50 +325,21 @@ pub async fn add_hotkey_to_participant_neuron(
     permission_types: Option<Vec<i32>>,
     neuron_id: Option<Vec<u8>>,
 ) -> Result<()> {
-    use super::identity::{create_agent, load_dfx_identity, load_identity_from_seed_file};
-
     // Read deployment data
-    let data_content = std::fs::read_to_string(deployment_data_path).with_context(|| {
-        format!(
-            "Failed to read deployment data from: {:?}",
-            deployment_data_path
-        )
-    })?;
-    let deployment_data: crate::core::utils::data_output::SnsCreationData =
-        serde_json::from_str(&data_content).context("Failed to parse deployment data JSON")?;
-
-    // Load identity - check if owner first, then participants, then try dfx for custom principals
-    let identity = if participant_principal.to_text() == deployment_data.owner_principal {
-        // Owner - use dfx identity
-        load_dfx_identity(None)
-            .context("Failed to load owner dfx identity")?
-    } else if let Some(participant_data) = deployment_data
-        .participants
-        .iter()
-        .find(|p| p.principal == participant_principal.to_string())
-    {
-        // Participant - load from seed file
-        let seed_path = PathBuf::from(&participant_data.seed_file);
-        load_identity_from_seed_file(&seed_path)
-            .with_context(|| format!("Failed to load identity from: {}", seed_path.display()))?
-    } else {
-        // Custom principal - try dfx identity as fallback
-        load_dfx_identity(None)
-            .context("Failed to load dfx identity for custom principal")?
-    };
+    let deployment_data = app_context().deployment_data_at(deployment_data_path)?;
+
+    // Load identity (owner, participant or extra identity)
+    let identity =
+        resolve_identity_for_principal(signing_principal(participant_principal), &deployment_data)?;
 
     // Create authenticated agent
-    let agent = create_agent(identity)
+    let agent = app_context()
+        .agent(identity)
         .await
         .context("Failed to create agent")?;
 
     // Get governance canister ID
-    let governance_canister = deployment_data
-        .deployed_sns
-        .governance_canister_id
-        .as_ref()
-        .and_then(|s| Principal::from_text(s).ok())
-        .context("Failed to parse governance canister ID from deployment data")?;
+    let governance_canister = deployment_data.deployed_sns.governance_canister()?;
 
     // Use neuron_id if provided, otherwise find it automatically
     let neuron_subaccount = if let Some(id) = neuron_id {
@@ -294,7 +414,7 @@ pub async fn add_hotkey_to_participant_neuron_default_path(
 /// Disburse a neuron to a specific principal
 /// This disburses the full amount of the neuron
 pub async fn disburse_neuron(
-    agent: &Agent,
+    agent: &dyn CanisterClient,
     governance_canister: Principal,
     neuron_subaccount: Vec<u8>,
     receiver_principal: Principal,
@@ -313,10 +433,7 @@ pub async fn disburse_neuron(
     };
     let args = candid::encode_args((request,))?;
 
-    let response = agent
-        .update(&governance_canister, "manage_neuron")
-        .with_arg(args)
-        .call_and_wait()
+    let response = canister_call::update(agent, &governance_canister, "manage_neuron", args)
         .await
         .context("Failed to call manage_neuron")?;
 
@@ -326,11 +443,7 @@ pub async fn disburse_neuron(
     if let Some(cmd) = result.command {
         match cmd {
             super::super::declarations::sns_governance::Command1::Error(e) => {
-                anyhow::bail!(
-                    "Governance error: {} (type: {})",
-                    e.error_message,
-                    e.error_type
-                );
+                Err(sns_governance_error("Failed to disburse neuron", &e))
             }
             super::super::declarations::sns_governance::Command1::Disburse(response) => {
                 Ok(response.transfer_block_height)
@@ -353,50 +466,21 @@ pub async fn disburse_participant_neuron(
     receiver_principal: Principal,
     neuron_id: Option<Vec<u8>>,
 ) -> Result<u64> {
-    use super::identity::{create_agent, load_dfx_identity, load_identity_from_seed_file};
-
     // Read deployment data
-    let data_content = std::fs::read_to_string(deployment_data_path).with_context(|| {
-        format!(
-            "Failed to read deployment data from: {:?}",
-            deployment_data_path
-        )
-    })?;
-    let deployment_data: crate::core::utils::data_output::SnsCreationData =
-        serde_json::from_str(&data_content).context("Failed to parse deployment data JSON")?;
-
-    // Load identity - check if owner first, then participants, then try dfx for custom principals
-    let identity = if participant_principal.to_text() == deployment_data.owner_principal {
-        // Owner - use dfx identity
-        load_dfx_identity(None)
-            .context("Failed to load owner dfx identity")?
-    } else if let Some(participant_data) = deployment_data
-        .participants
-        .iter()
-        .find(|p| p.principal == participant_principal.to_string())
-    {
-        // Participant - load from seed file
-        let seed_path = PathBuf::from(&participant_data.seed_file);
-        load_identity_from_seed_file(&seed_path)
-            .with_context(|| format!("Failed to load identity from: {}", seed_path.display()))?
-    } else {
-        // Custom principal - try dfx identity as fallback
-        load_dfx_identity(None)
-            .context("Failed to load dfx identity for custom principal")?
-    };
+    let deployment_data = app_context().deployment_data_at(deployment_data_path)?;
+
+    // Load identity (owner, participant or extra identity)
+    let identity =
+        resolve_identity_for_principal(signing_principal(participant_principal), &deployment_data)?;
 
     // Create authenticated agent
-    let agent = create_agent(identity)
+    let agent = app_context()
+        .agent(identity)
         .await
         .context("Failed to create agent")?;
 
     // Get governance canister ID
-    let governance_canister = deployment_data
-        .deployed_sns
-        .governance_canister_id
-        .as_ref()
-        .and_then(|s| Principal::from_text(s).ok())
-        .context("Failed to parse governance canister ID from deployment data")?;
+    let governance_canister = deployment_data.deployed_sns.governance_canister()?;
 
     // Use neuron_id if provided, otherwise find it automatically
     let neuron_subaccount = if let Some(id) = neuron_id {
@@ -452,13 +536,20 @@ pub async fn disburse_participant_neuron_default_path(
 
 /// Create a proposal to mint SNS tokens
 pub async fn make_mint_tokens_proposal(
-    agent: &Agent,
+    agent: &dyn CanisterClient,
     governance_canister: Principal,
     neuron_subaccount: Vec<u8>,
     receiver_principal: Principal,
     amount_e8s: u64,
 ) -> Result<u64> {
-    let proposal = Proposal {
+    let proposal = mint_tokens_proposal(receiver_principal, amount_e8s);
+    make_proposal(agent, governance_canister, neuron_subaccount, proposal).await
+}
+
+/// Proposal to mint `amount_e8s` SNS tokens to a principal
+#[must_use]
+pub fn mint_tokens_proposal(receiver_principal: Principal, amount_e8s: u64) -> Proposal {
+    Proposal {
         url: "".to_string(),
         title: format!("Mint {} tokens to {}", amount_e8s, receiver_principal),
         summary: format!(
@@ -471,8 +562,16 @@ pub async fn make_mint_tokens_proposal(
             memo: None,
             amount_e8s: Some(amount_e8s),
         })),
-    };
+    }
+}
 
+/// Submit a proposal with a neuron, returning the proposal ID
+pub async fn make_proposal(
+    agent: &dyn CanisterClient,
+    governance_canister: Principal,
+    neuron_subaccount: Vec<u8>,
+    proposal: Proposal,
+) -> Result<u64> {
     let command = Command::MakeProposal(proposal);
 
     let request = ManageNeuron {
@@ -481,10 +580,7 @@ pub async fn make_mint_tokens_proposal(
     };
     let args = candid::encode_args((request,))?;
 
-    let response = agent
-        .update(&governance_canister, "manage_neuron")
-        .with_arg(args)
-        .call_and_wait()
+    let response = canister_call::update(agent, &governance_canister, "manage_neuron", args)
         .await
         .context("Failed to call manage_neuron to create proposal")?;
 
@@ -494,11 +590,7 @@ pub async fn make_mint_tokens_proposal(
     if let Some(cmd) = result.command {
         match cmd {
             super::super::declarations::sns_governance::Command1::Error(e) => {
-                anyhow::bail!(
-                    "Governance error: {} (type: {})",
-                    e.error_message,
-                    e.error_type
-                );
+                Err(sns_governance_error("Failed to make proposal", &e))
             }
             super::super::declarations::sns_governance::Command1::MakeProposal(get_proposal) => {
                 // GetProposal contains proposal_id
@@ -517,9 +609,71 @@ pub async fn make_mint_tokens_proposal(
     }
 }
 
+/// The neuron a principal votes and proposes with: the one with the longest dissolve delay
+/// (last in the sorted list, skipping dissolving ones), or the last one if none has a delay
+#[must_use]
+pub fn main_neuron_id(neurons: &[Neuron]) -> Option<&NeuronId> {
+    neurons
+        .iter()
+        .rev()
+        .find(|n| {
+            matches!(
+                n.dissolve_state,
+                Some(DissolveState::DissolveDelaySeconds(_))
+            )
+        })
+        .and_then(|n| n.id.as_ref())
+        .or_else(|| neurons.last().and_then(|n| n.id.as_ref()))
+}
+
+/// Shortest neuron ID prefix accepted in place of a full ID
+pub const MIN_NEURON_ID_PREFIX_LEN: usize = 4;
+
+/// The ID of the one neuron whose hex ID matches `id`, like a git short hash
+///
+/// `id` is a hex prefix of at least [`MIN_NEURON_ID_PREFIX_LEN`] characters, or the shortened
+/// `prefix...suffix` form the tables print. A full ID matches itself.
+pub fn match_neuron_id(neurons: &[Neuron], id: &str) -> Result<Vec<u8>> {
+    let id = id.trim().to_ascii_lowercase();
+    let id = id.strip_prefix("0x").unwrap_or(&id);
+    let (prefix, suffix) = id.split_once("...").unwrap_or((id, ""));
+    if !(prefix.chars().chain(suffix.chars())).all(|c| c.is_ascii_hexdigit()) {
+        return Err(invalid(format!("Invalid neuron ID '{id}': expected hex")));
+    }
+    if prefix.len() < MIN_NEURON_ID_PREFIX_LEN {
+        return Err(invalid(format!(
+            "Invalid neuron ID '{id}': use at least {MIN_NEURON_ID_PREFIX_LEN} hex characters"
+        )));
+    }
+
+    let matches: Vec<Vec<u8>> = neurons
+        .iter()
+        .filter_map(|n| n.id.as_ref())
+        .filter(|n| {
+            let hex_id = hex::encode(&n.id);
+            hex_id.starts_with(prefix) && hex_id.ends_with(suffix)
+        })
+        .map(|n| n.id.clone())
+        .collect();
+    match matches.as_slice() {
+        [only] => Ok(only.clone()),
+        [] => Err(invalid(format!(
+            "Invalid neuron ID '{id}': no neuron matches it"
+        ))),
+        _ => Err(invalid(format!(
+            "Invalid neuron ID '{id}': ambiguous, matches {}",
+            matches
+                .iter()
+                .map(hex::encode)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))),
+    }
+}
+
 /// Vote on a proposal with a neuron
 pub async fn vote_on_proposal(
-    agent: &Agent,
+    agent: &dyn CanisterClient,
     governance_canister: Principal,
     neuron_subaccount: Vec<u8>,
     proposal_id: u64,
@@ -536,10 +690,7 @@ pub async fn vote_on_proposal(
     };
     let args = candid::encode_args((request,))?;
 
-    let response = agent
-        .update(&governance_canister, "manage_neuron")
-        .with_arg(args)
-        .call_and_wait()
+    let response = canister_call::update(agent, &governance_canister, "manage_neuron", args)
         .await
         .context("Failed to call manage_neuron to vote")?;
 
@@ -549,11 +700,7 @@ pub async fn vote_on_proposal(
     if let Some(cmd) = result.command {
         match cmd {
             super::super::declarations::sns_governance::Command1::Error(e) => {
-                anyhow::bail!(
-                    "Governance error: {} (type: {})",
-                    e.error_message,
-                    e.error_type
-                );
+                Err(sns_governance_error("Failed to vote on proposal", &e))
             }
             super::super::declarations::sns_governance::Command1::RegisterVote {} => {
                 // Success
@@ -568,6 +715,310 @@ pub async fn vote_on_proposal(
     }
 }
 
+/// Get a proposal, including its ballots
+pub async fn get_proposal(
+    agent: &dyn CanisterClient,
+    governance_canister: Principal,
+    proposal_id: u64,
+) -> Result<ProposalData> {
+    let request = GetProposal {
+        proposal_id: Some(ProposalId { id: proposal_id }),
+    };
+    let response = canister_call::query(
+        agent,
+        &governance_canister,
+        "get_proposal",
+        encode_args((request,))?,
+    )
+    .await
+    .context("Failed to call get_proposal")?;
+
+    match Decode!(&response, GetProposalResponse)
+        .context("Failed to decode get_proposal response")?
+        .result
+    {
+        Some(Result1::Proposal(proposal)) => Ok(proposal),
+        Some(Result1::Error(e)) => Err(sns_governance_error("Failed to get proposal", &e)),
+        None => anyhow::bail!("Proposal {} not found", proposal_id),
+    }
+}
+
+/// List every proposal of the SNS, newest first, following `list_proposals` pages
+pub async fn list_all_proposals(
+    agent: &dyn CanisterClient,
+    governance_canister: Principal,
+) -> Result<Vec<ProposalData>> {
+    const PAGE_SIZE: u32 = 100;
+
+    let mut proposals: Vec<ProposalData> = Vec::new();
+    loop {
+        let request = ListProposals {
+            include_reward_status: Vec::new(),
+            before_proposal: proposals
+                .last()
+                .and_then(|p| p.id.as_ref())
+                .map(|id| ProposalId { id: id.id }),
+            limit: PAGE_SIZE,
+            exclude_type: Vec::new(),
+            include_topics: None,
+            include_status: Vec::new(),
+        };
+        let response = canister_call::query(
+            agent,
+            &governance_canister,
+            "list_proposals",
+            encode_args((request,))?,
+        )
+        .await
+        .context("Failed to call list_proposals")?;
+        let page = Decode!(&response, ListProposalsResponse)
+            .context("Failed to decode list_proposals response")?
+            .proposals;
+
+        let last_page = page.len() < PAGE_SIZE as usize;
+        proposals.extend(page);
+        if last_page {
+            return Ok(proposals);
+        }
+    }
+}
+
+/// Shares of the total and of the cast voting power that must vote Yes, in basis points
+/// Falls back to SNS governance's defaults for proposals that don't set their own.
+fn yes_thresholds_bp(proposal: &ProposalData) -> (u64, u64) {
+    let basis_points = |p: &Option<Percentage>, default: u64| {
+        p.as_ref().and_then(|p| p.basis_points).unwrap_or(default)
+    };
+    (
+        basis_points(&proposal.minimum_yes_proportion_of_total, 300),
+        basis_points(&proposal.minimum_yes_proportion_of_exercised, 5_000),
+    )
+}
+
+/// Whether a decided proposal was adopted, judged from its final tally like SNS governance
+fn is_adopted(proposal: &ProposalData) -> bool {
+    let Some(tally) = &proposal.latest_tally else {
+        return false;
+    };
+    let (of_total_bp, of_exercised_bp) = yes_thresholds_bp(proposal);
+    let yes = u128::from(tally.yes) * 10_000;
+    yes >= u128::from(tally.total) * u128::from(of_total_bp)
+        && yes > (u128::from(tally.yes) + u128::from(tally.no)) * u128::from(of_exercised_bp)
+}
+
+/// How a proposal ended up
+#[derive(Debug, Clone)]
+pub enum ProposalOutcome {
+    Executed {
+        timestamp_seconds: u64,
+    },
+    /// Adopted but its action failed; `reason` is governance's error message
+    Failed {
+        timestamp_seconds: u64,
+        reason: Option<String>,
+    },
+    Rejected {
+        timestamp_seconds: u64,
+    },
+}
+
+/// Outcome of a proposal, or None while it is open or adopted but not yet executed
+#[must_use]
+pub fn proposal_outcome(proposal: &ProposalData) -> Option<ProposalOutcome> {
+    if proposal.executed_timestamp_seconds > 0 {
+        Some(ProposalOutcome::Executed {
+            timestamp_seconds: proposal.executed_timestamp_seconds,
+        })
+    } else if proposal.failed_timestamp_seconds > 0 {
+        Some(ProposalOutcome::Failed {
+            timestamp_seconds: proposal.failed_timestamp_seconds,
+            reason: proposal
+                .failure_reason
+                .as_ref()
+                .map(describe_sns_governance_error),
+        })
+    } else if proposal.decided_timestamp_seconds > 0 && !is_adopted(proposal) {
+        Some(ProposalOutcome::Rejected {
+            timestamp_seconds: proposal.decided_timestamp_seconds,
+        })
+    } else {
+        None
+    }
+}
+
+/// How often to check a proposal while waiting for it to execute
+const PROPOSAL_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// How long to wait for a proposal to execute before giving up
+const PROPOSAL_POLL_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(300);
+
+/// Poll a proposal until it is executed, fails or is rejected
+/// Gives up after five minutes, e.g. when not enough neurons voted for it to be decided
+/// before its deadline.
+pub async fn wait_for_proposal_outcome(
+    agent: &dyn CanisterClient,
+    governance_canister: Principal,
+    proposal_id: u64,
+) -> Result<ProposalOutcome> {
+    let deadline = std::time::Instant::now() + PROPOSAL_POLL_TIMEOUT;
+    loop {
+        let proposal = get_proposal(agent, governance_canister, proposal_id).await?;
+        if let Some(outcome) = proposal_outcome(&proposal) {
+            return Ok(outcome);
+        }
+        if std::time::Instant::now() >= deadline {
+            let state = if proposal.decided_timestamp_seconds > 0 {
+                "adopted but not executed".to_string()
+            } else {
+                let voting_ends = proposal
+                    .wait_for_quiet_state
+                    .as_ref()
+                    .map(|w| {
+                        format!(
+                            ", voting ends {}",
+                            crate::core::utils::time_format::format_utc(
+                                w.current_deadline_timestamp_seconds
+                            )
+                        )
+                    })
+                    .unwrap_or_default();
+                format!("still open{}", voting_ends)
+            };
+            anyhow::bail!(
+                "Timed out after {}s waiting for proposal {} to execute ({}; see show-ballots {})",
+                PROPOSAL_POLL_TIMEOUT.as_secs(),
+                proposal_id,
+                state,
+                proposal_id
+            );
+        }
+        tokio::time::sleep(PROPOSAL_POLL_INTERVAL).await;
+    }
+}
+
+/// Wait for a proposal of the deployed SNS to execute, fail or be rejected
+pub async fn wait_for_proposal_outcome_default_path(proposal_id: u64) -> Result<ProposalOutcome> {
+    let agent = app_context().anonymous_agent().await?;
+    wait_for_proposal_outcome(
+        &agent,
+        app_context().sns_governance_canister()?,
+        proposal_id,
+    )
+    .await
+}
+
+/// How one neuron voted on a proposal
+#[derive(Debug, Clone)]
+pub struct BallotEntry {
+    pub neuron_id: String,
+    /// Deployment account (`owner`, `participant-N` or identity name) that controls the neuron
+    pub account: Option<String>,
+    /// 0 = not voted yet, 1 = Yes, 2 = No
+    pub vote: i32,
+    pub voting_power: u64,
+    pub cast_timestamp_seconds: u64,
+}
+
+/// Ballots of a proposal with the tally and the thresholds it is decided by
+#[derive(Debug, Clone)]
+pub struct ProposalBallots {
+    pub proposal_id: u64,
+    pub title: Option<String>,
+    pub yes: u64,
+    pub no: u64,
+    pub total: u64,
+    /// Share of the total voting power that must vote Yes, in basis points
+    pub minimum_yes_proportion_of_total_bp: u64,
+    /// Share of the cast voting power that must vote Yes, in basis points
+    pub minimum_yes_proportion_of_exercised_bp: u64,
+    pub decided_timestamp_seconds: u64,
+    pub executed_timestamp_seconds: u64,
+    pub failed_timestamp_seconds: u64,
+    /// Deadline of the voting period, extended by wait-for-quiet
+    pub deadline_timestamp_seconds: Option<u64>,
+    /// Yes first, then No, then not voted; highest voting power first within each
+    pub ballots: Vec<BallotEntry>,
+}
+
+/// Ballots of a proposal, with each neuron mapped to the deployment account that controls it
+/// A neuron belongs to the account holding `ManagePrincipals` on it, so hotkeys don't count.
+pub async fn get_proposal_ballots_default_path(proposal_id: u64) -> Result<ProposalBallots> {
+    let data = app_context().deployment_data()?;
+    let governance_canister = app_context().sns_governance_canister()?;
+    let agent = app_context().anonymous_agent().await?;
+
+    let proposal = get_proposal(&agent, governance_canister, proposal_id).await?;
+    let (minimum_yes_proportion_of_total_bp, minimum_yes_proportion_of_exercised_bp) =
+        yes_thresholds_bp(&proposal);
+    let neurons = list_all_neurons(&agent, governance_canister).await?;
+
+    let accounts: Vec<(String, String)> = data
+        .accounts()
+        .into_iter()
+        .chain(
+            data.identities
+                .iter()
+                .map(|i| (i.name.clone(), i.principal.clone())),
+        )
+        .collect();
+    let account_of = |neuron: &Neuron| {
+        neuron.permissions.iter().find_map(|p| {
+            let principal = p.principal?.to_text();
+            if !p.permission_type.contains(
+                &super::super::declarations::sns_governance::PERMISSION_TYPE_MANAGE_PRINCIPALS,
+            ) {
+                return None;
+            }
+            accounts
+                .iter()
+                .find(|(_, account)| *account == principal)
+                .map(|(label, _)| label.clone())
+        })
+    };
+    let owners: std::collections::HashMap<String, String> = neurons
+        .iter()
+        .filter_map(|n| Some((hex::encode(&n.id.as_ref()?.id), account_of(n)?)))
+        .collect();
+
+    let mut ballots: Vec<BallotEntry> = proposal
+        .ballots
+        .into_iter()
+        .map(|(neuron_id, ballot)| BallotEntry {
+            account: owners.get(&neuron_id).cloned(),
+            neuron_id,
+            vote: ballot.vote,
+            voting_power: ballot.voting_power,
+            cast_timestamp_seconds: ballot.cast_timestamp_seconds,
+        })
+        .collect();
+    // Yes (1), No (2), then not voted (0)
+    ballots.sort_by_key(|b| {
+        (
+            if b.vote == 0 { 3 } else { b.vote },
+            std::cmp::Reverse(b.voting_power),
+        )
+    });
+
+    let tally = proposal.latest_tally.as_ref();
+    Ok(ProposalBallots {
+        proposal_id,
+        title: proposal.proposal.as_ref().map(|p| p.title.clone()),
+        yes: tally.map_or(0, |t| t.yes),
+        no: tally.map_or(0, |t| t.no),
+        total: tally.map_or(0, |t| t.total),
+        minimum_yes_proportion_of_total_bp,
+        minimum_yes_proportion_of_exercised_bp,
+        decided_timestamp_seconds: proposal.decided_timestamp_seconds,
+        executed_timestamp_seconds: proposal.executed_timestamp_seconds,
+        failed_timestamp_seconds: proposal.failed_timestamp_seconds,
+        deadline_timestamp_seconds: proposal
+            .wait_for_quiet_state
+            .as_ref()
+            .map(|w| w.current_deadline_timestamp_seconds),
+        ballots,
+    })
+}
+
 /// High-level function to mint SNS tokens by creating a proposal and getting all neurons to vote
 pub async fn mint_sns_tokens_with_all_votes(
     deployment_data_path: &std::path::Path,
@@ -575,50 +1026,38 @@ pub async fn mint_sns_tokens_with_all_votes(
     receiver_principal: Principal,
     amount_e8s: u64,
 ) -> Result<u64> {
-    use super::identity::{create_agent, load_dfx_identity, load_identity_from_seed_file};
+    submit_proposal_with_all_votes(
+        deployment_data_path,
+        proposer_principal,
+        mint_tokens_proposal(receiver_principal, amount_e8s),
+    )
+    .await
+}
+
+/// Submit a proposal with the proposer's main neuron, then vote Yes with every participant's
+/// main neuron so it is adopted right away. Returns the proposal ID.
+pub async fn submit_proposal_with_all_votes(
+    deployment_data_path: &std::path::Path,
+    proposer_principal: Principal,
+    proposal: Proposal,
+) -> Result<u64> {
+    use super::identity::load_identity_from_seed_file;
 
     // Read deployment data
-    let data_content = std::fs::read_to_string(deployment_data_path).with_context(|| {
-        format!(
-            "Failed to read deployment data from: {:?}",
-            deployment_data_path
-        )
-    })?;
-    let deployment_data: crate::core::utils::data_output::SnsCreationData =
-        serde_json::from_str(&data_content).context("Failed to parse deployment data JSON")?;
-
-    // Load proposer identity - check if owner first, then participants, then try dfx for custom principals
-    let proposer_identity = if proposer_principal.to_text() == deployment_data.owner_principal {
-        // Owner - use dfx identity
-        load_dfx_identity(None)
-            .context("Failed to load owner dfx identity")?
-    } else if let Some(proposer_data) = deployment_data
-        .participants
-        .iter()
-        .find(|p| p.principal == proposer_principal.to_string())
-    {
-        // Participant - load from seed file
-        let seed_path = PathBuf::from(&proposer_data.seed_file);
-        load_identity_from_seed_file(&seed_path)
-            .with_context(|| format!("Failed to load identity from: {}", seed_path.display()))?
-    } else {
-        // Custom principal - try dfx identity as fallback
-        load_dfx_identity(None)
-            .context("Failed to load dfx identity for custom principal")?
-    };
+    let deployment_data = app_context().deployment_data_at(deployment_data_path)?;
+
+    // Load proposer identity (owner, participant or extra identity)
+    let proposer_identity =
+        resolve_identity_for_principal(signing_principal(proposer_principal), &deployment_data)?;
 
     // Create authenticated agent for proposer
-    let proposer_agent = create_agent(proposer_identity)
+    let proposer_agent = app_context()
+        .agent(proposer_identity)
         .await
         .context("Failed to create agent with proposer identity")?;
 
     // Get governance canister ID
-    let governance_canister = deployment_data
-        .deployed_sns
-        .governance_canister_id
-        .as_ref()
-        .and_then(|s| Principal::from_text(s).ok())
-        .context("Failed to parse governance canister ID from deployment data")?;
+    let governance_canister = deployment_data.deployed_sns.governance_canister()?;
 
     // Get proposer neurons (sorted by dissolve delay, then by cached stake)
     let proposer_neurons =
@@ -643,15 +1082,14 @@ pub async fn mint_sns_tokens_with_all_votes(
         })?;
 
     // Create the proposal
-    let proposal_id = make_mint_tokens_proposal(
+    let proposal_id = make_proposal(
         &proposer_agent,
         governance_canister,
         proposer_neuron_id.id.clone(),
-        receiver_principal,
-        amount_e8s,
+        proposal,
     )
     .await
-    .context("Failed to create mint tokens proposal")?;
+    .context("Failed to create proposal")?;
 
     // Now get the main neuron for each participant and have them vote
     // (other neurons follow the main one, so we only need the main one to vote)
@@ -675,7 +1113,8 @@ pub async fn mint_sns_tokens_with_all_votes(
             })?;
 
         // Create authenticated agent for participant
-        let participant_agent = create_agent(participant_identity)
+        let participant_agent = app_context()
+            .agent(participant_identity)
             .await
             .context("Failed to create agent with participant identity")?;
 
@@ -742,9 +1181,18 @@ pub async fn mint_sns_tokens_with_all_votes_default_path(
     .await
 }
 
+/// Submit a proposal that every participant votes for, using the default deployment data
+pub async fn submit_proposal_with_all_votes_default_path(
+    proposer_principal: Principal,
+    proposal: Proposal,
+) -> Result<u64> {
+    let deployment_path = crate::core::utils::data_output::get_output_path();
+    submit_proposal_with_all_votes(&deployment_path, proposer_principal, proposal).await
+}
+
 /// Claim an SNS neuron by memo and controller
 pub async fn claim_sns_neuron(
-    agent: &Agent,
+    agent: &dyn CanisterClient,
     governance_canister: Principal,
     memo: u64,
     controller: Principal,
@@ -762,10 +1210,7 @@ pub async fn claim_sns_neuron(
     };
     let args = encode_args((request,))?;
 
-    let response = agent
-        .update(&governance_canister, "manage_neuron")
-        .with_arg(args)
-        .call_and_wait()
+    let response = canister_call::update(agent, &governance_canister, "manage_neuron", args)
         .await
         .context("Failed to call manage_neuron")?;
 
@@ -780,101 +1225,512 @@ pub async fn claim_sns_neuron(
                 anyhow::bail!("Failed to claim neuron: no neuron ID in response");
             }
         }
-        Some(Command1::Error(e)) => {
-            anyhow::bail!(
-                "Failed to claim neuron: {} (type: {})",
-                e.error_message,
-                e.error_type
-            );
-        }
+        Some(Command1::Error(e)) => Err(sns_governance_error("Failed to claim neuron", &e)),
         _ => anyhow::bail!("Unexpected response from manage_neuron"),
     }
 }
 
-/// Set dissolve delay for an SNS neuron (increases by the specified amount)
-pub async fn set_sns_dissolve_delay(
-    agent: &Agent,
+/// Get an SNS neuron by ID, or None if governance has no such neuron
+pub async fn get_sns_neuron(
+    agent: &dyn CanisterClient,
     governance_canister: Principal,
-    neuron_subaccount: Vec<u8>,
-    dissolve_delay_seconds: u64,
-) -> Result<()> {
-    let command = Command::Configure(Configure {
-        operation: Some(Operation::IncreaseDissolveDelay(IncreaseDissolveDelay {
-            additional_dissolve_delay_seconds: dissolve_delay_seconds as u32,
-        })),
-    });
-
-    let request = ManageNeuron {
-        subaccount: neuron_subaccount,
-        command: Some(command),
+    neuron_id: &[u8],
+) -> Result<Option<Neuron>> {
+    let request = GetNeuron {
+        neuron_id: Some(NeuronId {
+            id: neuron_id.to_vec(),
+        }),
     };
-    let args = encode_args((request,))?;
-
-    let response = agent
-        .update(&governance_canister, "manage_neuron")
-        .with_arg(args)
-        .call_and_wait()
-        .await
-        .context("Failed to call manage_neuron to set dissolve delay")?;
-
-    let result: ManageNeuronResponse = Decode!(&response, ManageNeuronResponse)
-        .context("Failed to decode manage_neuron response")?;
-
-    match result.command {
-        Some(Command1::Configure {}) => Ok(()),
-        Some(Command1::Error(e)) => {
-            anyhow::bail!(
-                "Failed to set dissolve delay: {} (type: {})",
-                e.error_message,
-                e.error_type
-            );
-        }
-        _ => anyhow::bail!("Unexpected response from manage_neuron"),
+    let response = canister_call::query(
+        agent,
+        &governance_canister,
+        "get_neuron",
+        encode_args((request,))?,
+    )
+    .await
+    .context("Failed to call get_neuron")?;
+
+    let response: GetNeuronResponse =
+        Decode!(&response, GetNeuronResponse).context("Failed to decode get_neuron response")?;
+    match response.result {
+        Some(Result_::Neuron(neuron)) => Ok(Some(neuron)),
+        // NotFound
+        Some(Result_::Error(e)) if e.error_type == 3 => Ok(None),
+        Some(Result_::Error(e)) => Err(sns_governance_error("Failed to get neuron", &e)),
+        None => anyhow::bail!("No result from get_neuron"),
     }
 }
 
-/// Start dissolving an SNS neuron
-pub async fn start_dissolving_sns_neuron(
-    agent: &Agent,
-    governance_canister: Principal,
-    neuron_subaccount: Vec<u8>,
-) -> Result<()> {
-    let command = Command::Configure(Configure {
-        operation: Some(Operation::StartDissolving {}),
-    });
-
-    let request = ManageNeuron {
-        subaccount: neuron_subaccount,
-        command: Some(command),
-    };
-    let args = encode_args((request,))?;
-
-    let response = agent
-        .update(&governance_canister, "manage_neuron")
-        .with_arg(args)
-        .call_and_wait()
-        .await
-        .context("Failed to call manage_neuron to start dissolving")?;
+/// The neuron a controller and memo stake, as governance and the ledger see it
+pub struct NeuronResolution {
+    pub controller: Principal,
+    pub memo: u64,
+    /// Neuron ID, which is also its staking subaccount of governance
+    pub neuron_id: Vec<u8>,
+    pub governance_canister: Principal,
+    /// Balance of the staking account on the SNS ledger
+    pub staking_balance_e8s: u64,
+    /// The neuron, if governance has claimed it
+    pub neuron: Option<Neuron>,
+}
 
-    let result: ManageNeuronResponse = Decode!(&response, ManageNeuronResponse)
-        .context("Failed to decode manage_neuron response")?;
+impl NeuronResolution {
+    /// What the state of the neuron and its staking account means, and what to run next
+    #[must_use]
+    pub fn diagnosis(&self) -> String {
+        match &self.neuron {
+            None if self.staking_balance_e8s == 0 => "No neuron and nothing staked: the staking \
+                 transfer has not arrived (check the controller and memo it was sent with)"
+                .to_string(),
+            None => format!(
+                "Tokens are staked but the neuron was never claimed: run claim-sns-neuron \
+                 --controller {} --memo {}",
+                self.controller, self.memo
+            ),
+            Some(neuron) if self.staking_balance_e8s > neuron.cached_neuron_stake_e8s => format!(
+                "The staking account holds more than the neuron's cached stake ({} e8s): run \
+                 refresh-sns-neuron to add the difference",
+                neuron.cached_neuron_stake_e8s
+            ),
+            Some(_) => "The neuron exists and its stake is up to date".to_string(),
+        }
+    }
 
-    match result.command {
-        Some(Command1::Configure {}) => Ok(()),
-        Some(Command1::Error(e)) => {
-            anyhow::bail!(
-                "Failed to start dissolving: {} (type: {})",
-                e.error_message,
-                e.error_type
-            );
+    /// Whether the staking account holds tokens governance has not counted: a stake that was
+    /// never claimed, or a top-up that was never refreshed
+    #[must_use]
+    pub const fn is_orphaned(&self) -> bool {
+        match &self.neuron {
+            None => self.staking_balance_e8s > 0,
+            Some(neuron) => self.staking_balance_e8s > neuron.cached_neuron_stake_e8s,
         }
-        _ => anyhow::bail!("Unexpected response from manage_neuron"),
     }
 }
 
-/// Stop dissolving an SNS neuron
-pub async fn stop_dissolving_sns_neuron(
-    agent: &Agent,
+/// Derive the neuron ID of a controller and memo, and look up the neuron and its staking account
+pub async fn resolve_sns_neuron(
+    agent: &dyn CanisterClient,
+    governance_canister: Principal,
+    ledger_canister: Principal,
+    controller: Principal,
+    memo: u64,
+) -> Result<NeuronResolution> {
+    let subaccount = generate_subaccount_by_nonce(memo, controller);
+    let staking_balance_e8s = get_sns_ledger_balance(
+        agent,
+        ledger_canister,
+        governance_canister,
+        Some(subaccount.0.to_vec()),
+    )
+    .await
+    .context("Failed to get the staking account balance")?;
+    let neuron = get_sns_neuron(agent, governance_canister, &subaccount.0).await?;
+
+    Ok(NeuronResolution {
+        controller,
+        memo,
+        neuron_id: subaccount.0.to_vec(),
+        governance_canister,
+        staking_balance_e8s,
+        neuron,
+    })
+}
+
+/// Resolve a controller and memo against the deployed SNS (see `resolve_sns_neuron`)
+pub async fn resolve_sns_neuron_default_path(
+    controller: Principal,
+    memo: u64,
+) -> Result<NeuronResolution> {
+    let deployed_sns = &app_context().deployment_data()?.deployed_sns;
+    let agent = app_context().anonymous_agent().await?;
+    resolve_sns_neuron(
+        &agent,
+        deployed_sns.governance_canister()?,
+        deployed_sns.ledger_canister()?,
+        controller,
+        memo,
+    )
+    .await
+}
+
+/// Claim an SNS neuron funded by a transfer to the governance subaccount of `memo` and `controller`
+/// Signs as the controller when its identity is known, otherwise anonymously (governance
+/// only checks that the subaccount holds enough tokens). Returns the neuron ID
+pub async fn claim_sns_neuron_default_path(controller: Principal, memo: u64) -> Result<Vec<u8>> {
+    let deployment_data = app_context().deployment_data()?;
+    let governance_canister = deployment_data.deployed_sns.governance_canister()?;
+    let agent = controller_agent(controller, &deployment_data).await?;
+
+    claim_sns_neuron(&agent, governance_canister, memo, controller).await
+}
+
+/// Agent of `controller` when its identity is known, otherwise anonymous
+async fn controller_agent(
+    controller: Principal,
+    deployment_data: &crate::core::utils::data_output::SnsCreationData,
+) -> Result<ic_agent::Agent> {
+    match resolve_identity_for_principal(controller, deployment_data) {
+        Ok(identity) => app_context()
+            .agent(identity)
+            .await
+            .context("Failed to create agent"),
+        Err(e) => {
+            log::info!("Signing anonymously ({e:#})");
+            app_context().anonymous_agent().await
+        }
+    }
+}
+
+/// Attempts at claiming a neuron right after staking it, before leaving it to `recover-stakes`
+const CLAIM_ATTEMPTS: u32 = 3;
+
+/// [`claim_sns_neuron`], retried a few times: once the tokens are staked, a failed claim
+/// would leave them in the staking account
+async fn claim_sns_neuron_with_retry(
+    agent: &dyn CanisterClient,
+    governance_canister: Principal,
+    memo: u64,
+    controller: Principal,
+) -> Result<Vec<u8>> {
+    let mut attempt = 1;
+    loop {
+        match claim_sns_neuron(agent, governance_canister, memo, controller).await {
+            Ok(neuron_id) => return Ok(neuron_id),
+            Err(e) if attempt < CLAIM_ATTEMPTS => {
+                attempt += 1;
+                crate::core::utils::print_warning(&format!(
+                    "Claim failed, retrying ({attempt}/{CLAIM_ATTEMPTS}): {e:#}"
+                ));
+                tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Memos past a controller's neuron count that [`find_orphaned_stakes`] also checks, since
+/// the memo of a neuron whose claim failed is not counted
+pub const ORPHAN_MEMO_WINDOW: u64 = 10;
+
+/// Staking accounts of `controllers` holding tokens governance has not counted (see
+/// [`NeuronResolution::is_orphaned`])
+/// Checks memos 0 to `max_memo` of each controller; None checks up to its neuron count plus
+/// [`ORPHAN_MEMO_WINDOW`], which covers the memos `create-sns-neuron` picks by itself.
+pub async fn find_orphaned_stakes(
+    agent: &dyn CanisterClient,
+    governance_canister: Principal,
+    ledger_canister: Principal,
+    controllers: &[Principal],
+    max_memo: Option<u64>,
+) -> Result<Vec<NeuronResolution>> {
+    let mut orphans = Vec::new();
+    for &controller in controllers {
+        let max_memo = match max_memo {
+            Some(max_memo) => max_memo,
+            None => {
+                let neurons = list_neurons_for_principal(agent, governance_canister, controller)
+                    .await
+                    .context("Failed to list neurons")?;
+                auto_neuron_memo(neurons.len()) + ORPHAN_MEMO_WINDOW
+            }
+        };
+        let resolutions = futures::future::try_join_all((0..=max_memo).map(|memo| {
+            resolve_sns_neuron(
+                agent,
+                governance_canister,
+                ledger_canister,
+                controller,
+                memo,
+            )
+        }))
+        .await
+        .with_context(|| format!("Failed to check the staking accounts of {controller}"))?;
+        orphans.extend(
+            resolutions
+                .into_iter()
+                .filter(NeuronResolution::is_orphaned),
+        );
+    }
+    Ok(orphans)
+}
+
+/// Find orphaned stakes in the deployed SNS (see `find_orphaned_stakes`)
+pub async fn find_orphaned_stakes_default_path(
+    controllers: &[Principal],
+    max_memo: Option<u64>,
+) -> Result<Vec<NeuronResolution>> {
+    let deployed_sns = &app_context().deployment_data()?.deployed_sns;
+    let agent = app_context().anonymous_agent().await?;
+    find_orphaned_stakes(
+        &agent,
+        deployed_sns.governance_canister()?,
+        deployed_sns.ledger_canister()?,
+        controllers,
+        max_memo,
+    )
+    .await
+}
+
+/// What [`recover_stake`] did with an orphaned stake
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StakeRecovery {
+    /// The neuron was claimed (its ID)
+    Claimed(Vec<u8>),
+    /// The neuron's stake was refreshed to count the top-up
+    Refreshed,
+    /// The neuron was claimed and disbursed back to its controller (transfer block)
+    Refunded(u64),
+    /// Below the minimum stake governance can neither claim nor return the tokens
+    BelowMinimum { minimum_stake_e8s: u64 },
+}
+
+/// Claim the neuron of an unclaimed stake, or refresh a neuron for a top-up
+/// With `refund`, a newly claimed neuron (dissolve delay 0, so already dissolved) is then
+/// disbursed to its controller, which `agent` must sign as. Top-ups are only refreshed.
+pub async fn recover_stake(
+    agent: &dyn CanisterClient,
+    orphan: &NeuronResolution,
+    minimum_stake_e8s: u64,
+    refund: bool,
+) -> Result<StakeRecovery> {
+    let governance_canister = orphan.governance_canister;
+    if orphan.neuron.is_some() {
+        refresh_sns_neuron(agent, governance_canister, orphan.neuron_id.clone())
+            .await
+            .context("Failed to refresh the neuron")?;
+        return Ok(StakeRecovery::Refreshed);
+    }
+    if orphan.staking_balance_e8s < minimum_stake_e8s {
+        return Ok(StakeRecovery::BelowMinimum { minimum_stake_e8s });
+    }
+
+    let neuron_id = claim_sns_neuron(agent, governance_canister, orphan.memo, orphan.controller)
+        .await
+        .context("Failed to claim the neuron")?;
+    if !refund {
+        return Ok(StakeRecovery::Claimed(neuron_id));
+    }
+    let block = disburse_neuron(agent, governance_canister, neuron_id, orphan.controller)
+        .await
+        .context("Failed to disburse the claimed neuron")?;
+    Ok(StakeRecovery::Refunded(block))
+}
+
+/// Recover an orphaned stake in the deployed SNS (see `recover_stake`), signing as its
+/// controller when its identity is known
+pub async fn recover_stake_default_path(
+    orphan: &NeuronResolution,
+    refund: bool,
+) -> Result<StakeRecovery> {
+    let deployment_data = app_context().deployment_data()?;
+    let agent = controller_agent(orphan.controller, &deployment_data).await?;
+    let minimum_stake = get_neuron_minimum_stake(&agent, orphan.governance_canister)
+        .await
+        .context("Failed to get neuron minimum stake")?;
+    recover_stake(&agent, orphan, minimum_stake, refund).await
+}
+
+/// Refresh an SNS neuron's stake from the balance of its subaccount (after a top-up transfer)
+pub async fn refresh_sns_neuron(
+    agent: &dyn CanisterClient,
+    governance_canister: Principal,
+    neuron_id: Vec<u8>,
+) -> Result<()> {
+    let command = Command::ClaimOrRefresh(ClaimOrRefresh {
+        by: Some(By::NeuronId {}),
+    });
+    let request = ManageNeuron {
+        subaccount: neuron_id,
+        command: Some(command),
+    };
+    let args = encode_args((request,))?;
+
+    let response = canister_call::update(agent, &governance_canister, "manage_neuron", args)
+        .await
+        .context("Failed to call manage_neuron")?;
+
+    let result: ManageNeuronResponse = Decode!(&response, ManageNeuronResponse)
+        .context("Failed to decode manage_neuron response")?;
+
+    match result.command {
+        Some(Command1::ClaimOrRefresh(_)) => Ok(()),
+        Some(Command1::Error(e)) => Err(sns_governance_error("Failed to refresh neuron", &e)),
+        _ => anyhow::bail!("Unexpected response from manage_neuron"),
+    }
+}
+
+/// Increase the stake of an existing SNS neuron: transfer tokens to its subaccount and refresh it
+/// The neuron ID is the neuron's subaccount of governance. Returns the new cached stake
+pub async fn refresh_sns_neuron_default_path(
+    principal: Principal,
+    neuron_id: Vec<u8>,
+    amount_e8s: u64,
+    from_subaccount: Option<Vec<u8>>,
+) -> Result<u64> {
+    let deployment_data = app_context().deployment_data()?;
+    let ledger_canister = deployment_data.deployed_sns.ledger_canister()?;
+    let governance_canister = deployment_data.deployed_sns.governance_canister()?;
+
+    // Load identity (owner, participant or extra identity)
+    let identity = resolve_identity_for_principal(signing_principal(principal), &deployment_data)?;
+    let agent = app_context()
+        .agent(identity)
+        .await
+        .context("Failed to create agent")?;
+
+    let subaccount = Some(neuron_id.clone());
+    let balance_before = get_sns_ledger_balance(
+        &agent,
+        ledger_canister,
+        governance_canister,
+        subaccount.clone(),
+    )
+    .await
+    .context("Failed to get neuron subaccount balance")?;
+
+    // Once the tokens are sent, Ctrl+C waits for the refresh (see `interrupt`)
+    let section = CriticalSection::enter();
+    interrupt::check()?;
+    transfer_sns_tokens_from_subaccount(
+        &agent,
+        ledger_canister,
+        from_subaccount,
+        governance_canister,
+        amount_e8s,
+        subaccount.clone(),
+    )
+    .await
+    .context("Failed to transfer SNS tokens to the neuron subaccount")?;
+    section.resume_with(format!(
+        "the tokens reached the account of neuron {} but its stake is not refreshed: the next refresh-sns-neuron of it counts them",
+        hex::encode(&neuron_id)
+    ));
+
+    // Wait for the transfer to land before refreshing
+    wait_for_sns_balance(
+        &agent,
+        ledger_canister,
+        governance_canister,
+        subaccount,
+        balance_before + amount_e8s,
+    )
+    .await
+    .context("SNS token transfer to the neuron subaccount did not land")?;
+
+    refresh_sns_neuron(&agent, governance_canister, neuron_id.clone())
+        .await
+        .context("Failed to refresh SNS neuron")?;
+
+    let neurons = list_neurons_for_principal(&agent, governance_canister, principal)
+        .await
+        .context("Failed to list neurons")?;
+    neurons
+        .iter()
+        .find(|n| n.id.as_ref().is_some_and(|id| id.id == neuron_id))
+        .map(|n| n.cached_neuron_stake_e8s)
+        .context("Refreshed neuron not found among the principal's neurons")
+}
+
+/// Set dissolve delay for an SNS neuron (increases by the specified amount)
+pub async fn set_sns_dissolve_delay(
+    agent: &dyn CanisterClient,
+    governance_canister: Principal,
+    neuron_subaccount: Vec<u8>,
+    dissolve_delay_seconds: u64,
+) -> Result<()> {
+    let command = Command::Configure(Configure {
+        operation: Some(Operation::IncreaseDissolveDelay(IncreaseDissolveDelay {
+            additional_dissolve_delay_seconds: dissolve_delay_seconds as u32,
+        })),
+    });
+
+    let request = ManageNeuron {
+        subaccount: neuron_subaccount,
+        command: Some(command),
+    };
+    let args = encode_args((request,))?;
+
+    let response = canister_call::update(agent, &governance_canister, "manage_neuron", args)
+        .await
+        .context("Failed to call manage_neuron to set dissolve delay")?;
+
+    let result: ManageNeuronResponse = Decode!(&response, ManageNeuronResponse)
+        .context("Failed to decode manage_neuron response")?;
+
+    match result.command {
+        Some(Command1::Configure {}) => Ok(()),
+        Some(Command1::Error(e)) => Err(sns_governance_error("Failed to set dissolve delay", &e)),
+        _ => anyhow::bail!("Unexpected response from manage_neuron"),
+    }
+}
+
+/// Make an SNS neuron follow `followees` for a function (0 is the catch-all for every
+/// function without its own followees)
+pub async fn follow_sns_neurons(
+    agent: &dyn CanisterClient,
+    governance_canister: Principal,
+    neuron_subaccount: Vec<u8>,
+    function_id: u64,
+    followees: Vec<Vec<u8>>,
+) -> Result<()> {
+    let command = Command::Follow(Follow {
+        function_id,
+        followees: followees.into_iter().map(|id| NeuronId { id }).collect(),
+    });
+
+    let request = ManageNeuron {
+        subaccount: neuron_subaccount,
+        command: Some(command),
+    };
+    let args = encode_args((request,))?;
+
+    let response = canister_call::update(agent, &governance_canister, "manage_neuron", args)
+        .await
+        .context("Failed to call manage_neuron to set followees")?;
+
+    let result: ManageNeuronResponse = Decode!(&response, ManageNeuronResponse)
+        .context("Failed to decode manage_neuron response")?;
+
+    match result.command {
+        Some(Command1::Follow {}) => Ok(()),
+        Some(Command1::Error(e)) => Err(sns_governance_error("Failed to set followees", &e)),
+        _ => anyhow::bail!("Unexpected response from manage_neuron"),
+    }
+}
+
+/// Start dissolving an SNS neuron
+pub async fn start_dissolving_sns_neuron(
+    agent: &dyn CanisterClient,
+    governance_canister: Principal,
+    neuron_subaccount: Vec<u8>,
+) -> Result<()> {
+    let command = Command::Configure(Configure {
+        operation: Some(Operation::StartDissolving {}),
+    });
+
+    let request = ManageNeuron {
+        subaccount: neuron_subaccount,
+        command: Some(command),
+    };
+    let args = encode_args((request,))?;
+
+    let response = canister_call::update(agent, &governance_canister, "manage_neuron", args)
+        .await
+        .context("Failed to call manage_neuron to start dissolving")?;
+
+    let result: ManageNeuronResponse = Decode!(&response, ManageNeuronResponse)
+        .context("Failed to decode manage_neuron response")?;
+
+    match result.command {
+        Some(Command1::Configure {}) => Ok(()),
+        Some(Command1::Error(e)) => Err(sns_governance_error("Failed to start dissolving", &e)),
+        _ => anyhow::bail!("Unexpected response from manage_neuron"),
+    }
+}
+
+/// Stop dissolving an SNS neuron
+pub async fn stop_dissolving_sns_neuron(
+    agent: &dyn CanisterClient,
     governance_canister: Principal,
     neuron_subaccount: Vec<u8>,
 ) -> Result<()> {
@@ -888,10 +1744,7 @@ pub async fn stop_dissolving_sns_neuron(
     };
     let args = encode_args((request,))?;
 
-    let response = agent
-        .update(&governance_canister, "manage_neuron")
-        .with_arg(args)
-        .call_and_wait()
+    let response = canister_call::update(agent, &governance_canister, "manage_neuron", args)
         .await
         .context("Failed to call manage_neuron to stop dissolving")?;
 
@@ -900,13 +1753,7 @@ pub async fn stop_dissolving_sns_neuron(
 
     match result.command {
         Some(Command1::Configure {}) => Ok(()),
-        Some(Command1::Error(e)) => {
-            anyhow::bail!(
-                "Failed to stop dissolving: {} (type: {})",
-                e.error_message,
-                e.error_type
-            );
-        }
+        Some(Command1::Error(e)) => Err(sns_governance_error("Failed to stop dissolving", &e)),
         _ => anyhow::bail!("Unexpected response from manage_neuron"),
     }
 }
@@ -918,45 +1765,15 @@ pub async fn increase_dissolve_delay_participant_neuron_default_path(
     additional_dissolve_delay_seconds: u64,
     neuron_id: Option<Vec<u8>>,
 ) -> Result<()> {
-    use super::identity::{create_agent, load_identity_from_seed_file};
-    use std::path::PathBuf;
-
-    // Read deployment data
-    let deployment_path = crate::core::utils::data_output::get_output_path();
-    let data_content = std::fs::read_to_string(&deployment_path)
-        .with_context(|| format!("Failed to read deployment data from: {:?}", deployment_path))?;
-    let deployment_data: crate::core::utils::data_output::SnsCreationData =
-        serde_json::from_str(&data_content).context("Failed to parse deployment data JSON")?;
+    let deployment_data = app_context().deployment_data()?;
 
     // Get governance canister ID
-    let governance_canister = deployment_data
-        .deployed_sns
-        .governance_canister_id
-        .as_ref()
-        .and_then(|s| Principal::from_text(s).ok())
-        .context("Failed to parse governance canister ID from deployment data")?;
-
-    // Load identity - check if owner first, then participants, then try dfx for custom principals
-    use super::identity::load_dfx_identity;
-    let identity = if participant_principal.to_text() == deployment_data.owner_principal {
-        // Owner - use dfx identity
-        load_dfx_identity(None)
-            .context("Failed to load owner dfx identity")?
-    } else if let Some(participant_data) = deployment_data
-        .participants
-        .iter()
-        .find(|p| p.principal == participant_principal.to_string())
-    {
-        // Participant - load from seed file
-        let seed_path = PathBuf::from(&participant_data.seed_file);
-        load_identity_from_seed_file(&seed_path)
-            .with_context(|| format!("Failed to load identity from: {}", seed_path.display()))?
-    } else {
-        // Custom principal - try dfx identity as fallback
-        load_dfx_identity(None)
-            .context("Failed to load dfx identity for custom principal")?
-    };
-    let agent = create_agent(identity)
+    let governance_canister = app_context().sns_governance_canister()?;
+
+    let identity =
+        resolve_identity_for_principal(signing_principal(participant_principal), &deployment_data)?;
+    let agent = app_context()
+        .agent(identity)
         .await
         .context("Failed to create agent")?;
 
@@ -1008,45 +1825,15 @@ pub async fn manage_dissolving_state_participant_neuron_default_path(
     start_dissolving: bool,
     neuron_id: Option<Vec<u8>>,
 ) -> Result<()> {
-    use super::identity::{create_agent, load_identity_from_seed_file};
-    use std::path::PathBuf;
-
-    // Read deployment data
-    let deployment_path = crate::core::utils::data_output::get_output_path();
-    let data_content = std::fs::read_to_string(&deployment_path)
-        .with_context(|| format!("Failed to read deployment data from: {:?}", deployment_path))?;
-    let deployment_data: crate::core::utils::data_output::SnsCreationData =
-        serde_json::from_str(&data_content).context("Failed to parse deployment data JSON")?;
+    let deployment_data = app_context().deployment_data()?;
 
     // Get governance canister ID
-    let governance_canister = deployment_data
-        .deployed_sns
-        .governance_canister_id
-        .as_ref()
-        .and_then(|s| Principal::from_text(s).ok())
-        .context("Failed to parse governance canister ID from deployment data")?;
-
-    // Load identity - check if owner first, then participants, then try dfx for custom principals
-    use super::identity::load_dfx_identity;
-    let identity = if participant_principal.to_text() == deployment_data.owner_principal {
-        // Owner - use dfx identity
-        load_dfx_identity(None)
-            .context("Failed to load owner dfx identity")?
-    } else if let Some(participant_data) = deployment_data
-        .participants
-        .iter()
-        .find(|p| p.principal == participant_principal.to_string())
-    {
-        // Participant - load from seed file
-        let seed_path = PathBuf::from(&participant_data.seed_file);
-        load_identity_from_seed_file(&seed_path)
-            .with_context(|| format!("Failed to load identity from: {}", seed_path.display()))?
-    } else {
-        // Custom principal - try dfx identity as fallback
-        load_dfx_identity(None)
-            .context("Failed to load dfx identity for custom principal")?
-    };
-    let agent = create_agent(identity)
+    let governance_canister = app_context().sns_governance_canister()?;
+
+    let identity =
+        resolve_identity_for_principal(signing_principal(participant_principal), &deployment_data)?;
+    let agent = app_context()
+        .agent(identity)
         .await
         .context("Failed to create agent")?;
 
@@ -1099,6 +1886,7 @@ pub async fn create_sns_neuron_default_path(
     amount_e8s: Option<u64>,
     memo: Option<u64>,
     dissolve_delay_seconds: Option<u64>,
+    from_subaccount: Option<Vec<u8>>,
 ) -> Result<Vec<u8>> {
     let deployment_path = crate::core::utils::data_output::get_output_path();
     create_sns_neuron(
@@ -1107,11 +1895,13 @@ pub async fn create_sns_neuron_default_path(
         amount_e8s,
         memo,
         dissolve_delay_seconds,
+        from_subaccount,
     )
     .await
 }
 
 /// Create an SNS neuron by checking balance, transferring tokens, and claiming
+/// The tokens come from `from_subaccount` of the principal (None for its default account).
 /// Returns the neuron subaccount (ID) if successful
 pub async fn create_sns_neuron(
     deployment_data_path: &std::path::Path,
@@ -1119,55 +1909,21 @@ pub async fn create_sns_neuron(
     amount_e8s: Option<u64>,
     memo: Option<u64>,
     dissolve_delay_seconds: Option<u64>,
+    from_subaccount: Option<Vec<u8>>,
 ) -> Result<Vec<u8>> {
-    use super::identity::{create_agent, load_identity_from_seed_file};
-
     // Read deployment data
-    let data_content = std::fs::read_to_string(deployment_data_path).with_context(|| {
-        format!(
-            "Failed to read deployment data from: {:?}",
-            deployment_data_path
-        )
-    })?;
-    let deployment_data: crate::core::utils::data_output::SnsCreationData =
-        serde_json::from_str(&data_content).context("Failed to parse deployment data JSON")?;
+    let deployment_data = app_context().deployment_data_at(deployment_data_path)?;
 
     // Get ledger and governance canister IDs
-    let ledger_canister = deployment_data
-        .deployed_sns
-        .ledger_canister_id
-        .as_ref()
-        .and_then(|s| Principal::from_text(s).ok())
-        .context("Failed to parse ledger canister ID from deployment data")?;
-    let governance_canister = deployment_data
-        .deployed_sns
-        .governance_canister_id
-        .as_ref()
-        .and_then(|s| Principal::from_text(s).ok())
-        .context("Failed to parse governance canister ID from deployment data")?;
-
-    // Try to find principal in deployment data to load identity
-    let agent = if let Some(participant_data) = deployment_data
-        .participants
-        .iter()
-        .find(|p| p.principal == principal.to_string())
-    {
-        // Load participant identity
-        let seed_path = PathBuf::from(&participant_data.seed_file);
-        let identity = load_identity_from_seed_file(&seed_path)
-            .with_context(|| format!("Failed to load identity from: {}", seed_path.display()))?;
-        create_agent(identity)
-            .await
-            .context("Failed to create agent with participant identity")?
-    } else {
-        // Try to load as dfx identity
-        use super::identity::load_dfx_identity;
-        let identity =
-            load_dfx_identity(Some("default")).context("Failed to load dfx identity 'default'")?;
-        create_agent(identity)
-            .await
-            .context("Failed to create agent with dfx identity")?
-    };
+    let ledger_canister = deployment_data.deployed_sns.ledger_canister()?;
+    let governance_canister = deployment_data.deployed_sns.governance_canister()?;
+
+    // Load identity (owner, participant or extra identity)
+    let identity = resolve_identity_for_principal(principal, &deployment_data)?;
+    let agent = app_context()
+        .agent(identity)
+        .await
+        .context("Failed to create agent")?;
 
     // Get minimum stake and transfer fee
     let minimum_stake = get_neuron_minimum_stake(&agent, governance_canister)
@@ -1178,9 +1934,10 @@ pub async fn create_sns_neuron(
         .context("Failed to get SNS ledger transfer fee")?;
 
     // Check balance
-    let balance = get_sns_ledger_balance(&agent, ledger_canister, principal, None)
-        .await
-        .context("Failed to get SNS ledger balance")?;
+    let balance =
+        get_sns_ledger_balance(&agent, ledger_canister, principal, from_subaccount.clone())
+            .await
+            .context("Failed to get SNS ledger balance")?;
 
     // Determine amount to stake (use provided amount or all available minus fee)
     let stake_amount = if let Some(amount) = amount_e8s {
@@ -1225,53 +1982,522 @@ pub async fn create_sns_neuron(
             .await
             .context("Failed to list existing neurons")?;
 
-        // Use neuron count + 1 as the memo (starting from 1)
-        // This ensures each new neuron gets a unique memo
-        let neuron_count = existing_neurons.len() as u64;
-        neuron_count + 1
+        auto_neuron_memo(existing_neurons.len())
     };
 
     // Generate subaccount for neuron
     let subaccount = generate_subaccount_by_nonce(memo_value, principal);
 
+    // Once the tokens are sent, Ctrl+C waits for the claim (see `interrupt`)
+    let section = CriticalSection::enter();
+    interrupt::check()?;
+
     // Transfer SNS tokens to governance canister subaccount
-    transfer_sns_tokens(
+    transfer_sns_tokens_from_subaccount(
         &agent,
         ledger_canister,
+        from_subaccount,
         governance_canister,
         stake_amount,
         Some(subaccount.0.to_vec()),
     )
     .await
     .context("Failed to transfer SNS tokens to governance subaccount")?;
+    section.resume_with(format!(
+        "the tokens are staked but the neuron is not claimed: run claim-sns-neuron --controller {principal} --memo {memo_value}"
+    ));
 
-    // Wait a bit for the transfer to settle
-    tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+    // Wait for the transfer to land before claiming
+    wait_for_sns_balance(
+        &agent,
+        ledger_canister,
+        governance_canister,
+        Some(subaccount.0.to_vec()),
+        stake_amount,
+    )
+    .await
+    .context("SNS token transfer to governance subaccount did not land")?;
 
     // Claim neuron
-    let neuron_id = claim_sns_neuron(&agent, governance_canister, memo_value, principal)
-        .await
-        .context("Failed to claim SNS neuron")?;
+    let neuron_id =
+        claim_sns_neuron_with_retry(&agent, governance_canister, memo_value, principal)
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to claim SNS neuron; the tokens stay staked: run recover-stakes {principal} to retry"
+                )
+            })?;
 
     // Set dissolve delay if specified
-    if let Some(dissolve_delay) = dissolve_delay_seconds {
-        if dissolve_delay > 0 {
-            use crate::core::utils::{print_step, print_success};
-            print_step(&format!(
-                "Setting dissolve delay to {} seconds...",
-                dissolve_delay
-            ));
-            set_sns_dissolve_delay(
-                &agent,
-                governance_canister,
-                neuron_id.clone(),
-                dissolve_delay,
+    if let Some(dissolve_delay) = dissolve_delay_seconds
+        && dissolve_delay > 0
+    {
+        use crate::core::utils::{print_step, print_success};
+        section.resume_with(format!(
+            "neuron {} was created without its dissolve delay: run increase-sns-dissolve-delay",
+            hex::encode(&neuron_id)
+        ));
+        interrupt::check()?;
+        print_step(&format!(
+            "Setting dissolve delay to {} seconds...",
+            dissolve_delay
+        ));
+        set_sns_dissolve_delay(
+            &agent,
+            governance_canister,
+            neuron_id.clone(),
+            dissolve_delay,
+        )
+        .await
+        .context("Failed to set dissolve delay")?;
+        print_success("Dissolve delay set");
+    }
+
+    Ok(neuron_id)
+}
+
+/// Named SNS neuron permission types (value, name)
+pub const SNS_NEURON_PERMISSIONS: &[(i32, &str)] = &[
+    (
+        super::super::declarations::sns_governance::PERMISSION_TYPE_CONFIGURE_DISSOLVE_STATE,
+        "ConfigureDissolveState",
+    ),
+    (
+        super::super::declarations::sns_governance::PERMISSION_TYPE_MANAGE_PRINCIPALS,
+        "ManagePrincipals",
+    ),
+    (
+        super::super::declarations::sns_governance::PERMISSION_TYPE_SUBMIT_PROPOSAL,
+        "SubmitProposal",
+    ),
+    (
+        super::super::declarations::sns_governance::PERMISSION_TYPE_VOTE,
+        "Vote",
+    ),
+    (
+        super::super::declarations::sns_governance::PERMISSION_TYPE_DISBURSE,
+        "Disburse",
+    ),
+    (
+        super::super::declarations::sns_governance::PERMISSION_TYPE_SPLIT,
+        "Split",
+    ),
+    (
+        super::super::declarations::sns_governance::PERMISSION_TYPE_MERGE_MATURITY,
+        "MergeMaturity",
+    ),
+    (
+        super::super::declarations::sns_governance::PERMISSION_TYPE_DISBURSE_MATURITY,
+        "DisburseMaturity",
+    ),
+    (
+        super::super::declarations::sns_governance::PERMISSION_TYPE_STAKE_MATURITY,
+        "StakeMaturity",
+    ),
+    (
+        super::super::declarations::sns_governance::PERMISSION_TYPE_MANAGE_VOTING_PERMISSION,
+        "ManageVotingPermission",
+    ),
+];
+
+/// Parse a permission given by name (case-insensitive, `_`/`-` ignored) or number
+pub fn parse_sns_permission(value: &str) -> Result<i32> {
+    let value = value.trim();
+    if let Ok(number) = value.parse::<i32>() {
+        if SNS_NEURON_PERMISSIONS.iter().any(|(v, _)| *v == number) {
+            return Ok(number);
+        }
+        anyhow::bail!("Unknown permission type {}", number);
+    }
+
+    let normalized: String = value
+        .chars()
+        .filter(|c| *c != '_' && *c != '-')
+        .collect::<String>()
+        .to_lowercase();
+    SNS_NEURON_PERMISSIONS
+        .iter()
+        .find(|(_, name)| name.to_lowercase() == normalized)
+        .map(|(v, _)| *v)
+        .with_context(|| {
+            format!(
+                "Unknown permission '{}'. Valid permissions: {}",
+                value,
+                SNS_NEURON_PERMISSIONS
+                    .iter()
+                    .map(|(_, name)| *name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
             )
-            .await
-            .context("Failed to set dissolve delay")?;
-            print_success("Dissolve delay set");
+        })
+}
+
+/// Parse a comma-separated permission list; `none` or an empty string means no permissions
+pub fn parse_sns_permission_list(value: &str) -> Result<Vec<i32>> {
+    if value.trim().is_empty() || value.trim().eq_ignore_ascii_case("none") {
+        return Ok(Vec::new());
+    }
+    let mut permissions = value
+        .split(',')
+        .map(parse_sns_permission)
+        .collect::<Result<Vec<_>>>()?;
+    permissions.sort_unstable();
+    permissions.dedup();
+    Ok(permissions)
+}
+
+/// Name of a permission type, or its number if unknown
+#[must_use]
+pub fn sns_permission_name(permission: i32) -> String {
+    SNS_NEURON_PERMISSIONS
+        .iter()
+        .find(|(v, _)| *v == permission)
+        .map_or_else(|| permission.to_string(), |(_, name)| (*name).to_string())
+}
+
+/// Name of a permission type followed by its number, e.g. "Vote (4)"
+#[must_use]
+pub fn sns_permission_label(permission: i32) -> String {
+    SNS_NEURON_PERMISSIONS
+        .iter()
+        .find(|(v, _)| *v == permission)
+        .map_or_else(
+            || format!("Unknown ({})", permission),
+            |(_, name)| format!("{} ({})", name, permission),
+        )
+}
+
+/// Comma-separated permission labels, or "None"
+#[must_use]
+pub fn sns_permission_labels(permissions: &[i32]) -> String {
+    if permissions.is_empty() {
+        "None".to_string()
+    } else {
+        permissions
+            .iter()
+            .map(|p| sns_permission_label(*p))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Remove permissions from a principal on a neuron
+pub async fn remove_neuron_permissions(
+    agent: &dyn CanisterClient,
+    governance_canister: Principal,
+    neuron_subaccount: Vec<u8>,
+    principal: Principal,
+    permission_types: Vec<i32>,
+) -> Result<()> {
+    let command = Command::RemoveNeuronPermissions(RemoveNeuronPermissions {
+        permissions_to_remove: Some(NeuronPermissionList {
+            permissions: permission_types,
+        }),
+        principal_id: Some(principal),
+    });
+
+    let request = ManageNeuron {
+        subaccount: neuron_subaccount,
+        command: Some(command),
+    };
+    let args = candid::encode_args((request,))?;
+
+    let response = canister_call::update(agent, &governance_canister, "manage_neuron", args)
+        .await
+        .context("Failed to call manage_neuron")?;
+
+    let result: ManageNeuronResponse = Decode!(&response, ManageNeuronResponse)?;
+
+    if let Some(Command1::Error(e)) = result.command {
+        return Err(sns_governance_error(
+            "Failed to remove neuron permissions",
+            &e,
+        ));
+    }
+
+    Ok(())
+}
+
+/// Permissions a principal currently holds on a neuron (sorted)
+#[must_use]
+pub fn neuron_permissions_of(neuron: &Neuron, principal: Principal) -> Vec<i32> {
+    let mut permissions: Vec<i32> = neuron
+        .permissions
+        .iter()
+        .filter(|p| p.principal == Some(principal))
+        .flat_map(|p| p.permission_type.iter().copied())
+        .collect();
+    permissions.sort_unstable();
+    permissions.dedup();
+    permissions
+}
+
+/// Result of bringing a principal's neuron permissions to a desired set
+#[derive(Debug, Clone, Default)]
+pub struct PermissionChange {
+    pub added: Vec<i32>,
+    pub removed: Vec<i32>,
+}
+
+/// High-level function to set the exact permission set of a principal on a neuron
+/// Adds missing permissions and removes extra ones via AddNeuronPermissions/RemoveNeuronPermissions.
+/// The controller's identity (or the acting hotkey) signs the calls, so it needs ManagePrincipals.
+pub async fn set_neuron_permissions_default_path(
+    controller_principal: Principal,
+    neuron_id: Vec<u8>,
+    target_principal: Principal,
+    desired_permissions: Vec<i32>,
+) -> Result<PermissionChange> {
+    let deployment_data = crate::core::utils::data_output::read_data()?;
+
+    let governance_canister = app_context().sns_governance_canister()?;
+
+    // Load identity (owner, participant or extra identity)
+    let signer = signing_principal(controller_principal);
+    let identity = resolve_identity_for_principal(signer, &deployment_data)?;
+    let agent = app_context()
+        .agent(identity)
+        .await
+        .context("Failed to create agent")?;
+
+    // Current permissions of the target principal on this neuron
+    let neurons = list_neurons_for_principal(&agent, governance_canister, controller_principal)
+        .await
+        .context("Failed to list neurons")?;
+    let neuron = neurons
+        .iter()
+        .find(|n| n.id.as_ref().is_some_and(|id| id.id == neuron_id))
+        .with_context(|| {
+            format!(
+                "Neuron {} not found for principal {}",
+                hex::encode(&neuron_id),
+                controller_principal
+            )
+        })?;
+    let current = neuron_permissions_of(neuron, target_principal);
+
+    let change = PermissionChange {
+        added: desired_permissions
+            .iter()
+            .filter(|p| !current.contains(p))
+            .copied()
+            .collect(),
+        removed: current
+            .iter()
+            .filter(|p| !desired_permissions.contains(p))
+            .copied()
+            .collect(),
+    };
+
+    // Add before removing, so a principal granting itself new permissions keeps ManagePrincipals
+    // until the end
+    if !change.added.is_empty() {
+        add_hotkey_to_neuron(
+            &agent,
+            governance_canister,
+            neuron_id.clone(),
+            target_principal,
+            change.added.clone(),
+        )
+        .await
+        .context("Failed to add neuron permissions")?;
+    }
+    if !change.removed.is_empty() {
+        remove_neuron_permissions(
+            &agent,
+            governance_canister,
+            neuron_id,
+            target_principal,
+            change.removed.clone(),
+        )
+        .await
+        .context("Failed to remove neuron permissions")?;
+    }
+
+    Ok(change)
+}
+
+/// Neurons whose dissolve delay falls in one half-year bucket
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct DissolveDelayBucket {
+    /// Bucket index: the dissolve delay is between `half_years` and `half_years + 1` half-years
+    pub half_years: u64,
+    pub count: u64,
+    pub staked_e8s: u64,
+}
+
+/// One treasury account of the SNS
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct TreasuryBalance {
+    pub name: String,
+    pub ledger_canister_id: Option<Principal>,
+    pub amount_e8s: Option<u64>,
+    pub original_amount_e8s: Option<u64>,
+}
+
+/// Governance metrics of an SNS, computed from its neurons plus `get_metrics`
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct SnsMetrics {
+    pub timestamp_seconds: u64,
+    pub neuron_count: u64,
+    pub total_staked_e8s: u64,
+    pub total_maturity_e8s: u64,
+    pub not_dissolving_count: u64,
+    pub not_dissolving_e8s: u64,
+    pub dissolving_count: u64,
+    pub dissolving_e8s: u64,
+    pub dissolved_count: u64,
+    pub dissolved_e8s: u64,
+    /// Neurons with at least the minimum dissolve delay to vote
+    pub eligible_to_vote_count: u64,
+    pub eligible_to_vote_e8s: u64,
+    pub not_dissolving_buckets: Vec<DissolveDelayBucket>,
+    pub dissolving_buckets: Vec<DissolveDelayBucket>,
+    pub total_potential_voting_power: Option<u64>,
+    pub treasury: Vec<TreasuryBalance>,
+    pub recently_submitted_proposals: Option<u64>,
+    pub recently_executed_proposals: Option<u64>,
+    pub genesis_timestamp_seconds: Option<u64>,
+    pub neuron_minimum_stake_e8s: Option<u64>,
+    pub neuron_minimum_dissolve_delay_to_vote_seconds: Option<u64>,
+}
+
+/// Half a year in seconds, the width of a dissolve delay bucket
+const HALF_YEAR_SECONDS: u64 = 365 * 24 * 60 * 60 / 2;
+
+/// Get governance metrics (treasury, voting power, recent proposals) over a time window
+pub async fn get_governance_metrics(
+    agent: &dyn CanisterClient,
+    governance_canister: Principal,
+    time_window_seconds: u64,
+) -> Result<super::super::declarations::sns_governance::Metrics> {
+    let request = GetMetricsRequest {
+        time_window_seconds: Some(time_window_seconds),
+    };
+
+    let result_bytes = canister_call::query(
+        agent,
+        &governance_canister,
+        "get_metrics",
+        encode_args((request,))?,
+    )
+    .await
+    .context("Failed to call get_metrics")?;
+
+    let response: GetMetricsResponse = Decode!(&result_bytes, GetMetricsResponse)
+        .context("Failed to decode get_metrics response")?;
+
+    match response.get_metrics_result {
+        Some(GetMetricsResult::Ok(metrics)) => Ok(metrics),
+        Some(GetMetricsResult::Err(err)) => Err(sns_governance_error("get_metrics failed", &err)),
+        None => anyhow::bail!("get_metrics returned no result"),
+    }
+}
+
+/// Collect the governance metrics of an SNS
+/// Neuron counts and stakes come from listing every neuron; `time_window_seconds`
+/// bounds the recent proposal counts
+pub async fn get_sns_metrics(
+    agent: &dyn CanisterClient,
+    governance_canister: Principal,
+    time_window_seconds: u64,
+) -> Result<SnsMetrics> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+
+    let params = get_nervous_system_parameters(agent, governance_canister).await?;
+    let neurons = list_all_neurons(agent, governance_canister).await?;
+    let governance_metrics =
+        get_governance_metrics(agent, governance_canister, time_window_seconds).await?;
+
+    let min_delay_to_vote = params.neuron_minimum_dissolve_delay_to_vote_seconds;
+    let mut metrics = SnsMetrics {
+        timestamp_seconds: now,
+        neuron_minimum_stake_e8s: params.neuron_minimum_stake_e8s,
+        neuron_minimum_dissolve_delay_to_vote_seconds: min_delay_to_vote,
+        ..Default::default()
+    };
+
+    for neuron in &neurons {
+        let stake = neuron
+            .cached_neuron_stake_e8s
+            .saturating_sub(neuron.neuron_fees_e8s);
+        metrics.neuron_count += 1;
+        metrics.total_staked_e8s += stake;
+        metrics.total_maturity_e8s += neuron.maturity_e8s_equivalent;
+
+        let (delay, dissolving) = match &neuron.dissolve_state {
+            Some(DissolveState::DissolveDelaySeconds(delay)) => (*delay, false),
+            Some(DissolveState::WhenDissolvedTimestampSeconds(when)) => {
+                (when.saturating_sub(now), true)
+            }
+            None => (0, false),
+        };
+        if delay == 0 {
+            metrics.dissolved_count += 1;
+            metrics.dissolved_e8s += stake;
+            continue;
+        }
+        let buckets = if dissolving {
+            metrics.dissolving_count += 1;
+            metrics.dissolving_e8s += stake;
+            &mut metrics.dissolving_buckets
+        } else {
+            metrics.not_dissolving_count += 1;
+            metrics.not_dissolving_e8s += stake;
+            &mut metrics.not_dissolving_buckets
+        };
+        add_to_bucket(buckets, delay / HALF_YEAR_SECONDS, stake);
+
+        if min_delay_to_vote.is_none_or(|min| delay >= min) {
+            metrics.eligible_to_vote_count += 1;
+            metrics.eligible_to_vote_e8s += stake;
         }
     }
 
-    Ok(neuron_id)
+    metrics.total_potential_voting_power = governance_metrics
+        .voting_power_metrics
+        .and_then(|v| v.governance_total_potential_voting_power);
+    metrics.treasury = governance_metrics
+        .treasury_metrics
+        .unwrap_or_default()
+        .into_iter()
+        .map(|t| TreasuryBalance {
+            name: t.name.unwrap_or_else(|| format!("treasury {}", t.treasury)),
+            ledger_canister_id: t.ledger_canister_id,
+            amount_e8s: t.amount_e8s,
+            original_amount_e8s: t.original_amount_e8s,
+        })
+        .collect();
+    metrics.recently_submitted_proposals = governance_metrics.num_recently_submitted_proposals;
+    metrics.recently_executed_proposals = governance_metrics.num_recently_executed_proposals;
+    metrics.genesis_timestamp_seconds = governance_metrics.genesis_timestamp_seconds;
+
+    Ok(metrics)
+}
+
+/// Collect the governance metrics of the SNS from the deployment data
+pub async fn get_sns_metrics_default_path(time_window_seconds: u64) -> Result<SnsMetrics> {
+    let governance_canister = app_context().sns_governance_canister()?;
+    let agent = app_context().anonymous_agent().await?;
+    get_sns_metrics(&agent, governance_canister, time_window_seconds).await
+}
+
+fn add_to_bucket(buckets: &mut Vec<DissolveDelayBucket>, half_years: u64, stake: u64) {
+    let index = match buckets.binary_search_by_key(&half_years, |b| b.half_years) {
+        Ok(index) => index,
+        Err(index) => {
+            buckets.insert(
+                index,
+                DissolveDelayBucket {
+                    half_years,
+                    ..Default::default()
+                },
+            );
+            index
+        }
+    };
+    buckets[index].count += 1;
+    buckets[index].staked_e8s += stake;
 }
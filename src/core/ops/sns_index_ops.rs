@@ -0,0 +1,109 @@
+// SNS Index canister operations
+
+use super::canister_call;
+use super::canister_client::CanisterClient;
+use super::ledger_ops::{
+    AccountTransactions, IndexSync, get_sns_transactions, nat_to_u64, sns_transaction_to_entry,
+};
+use crate::core::context::app_context;
+use anyhow::{Context, Result};
+use candid::{Decode, Nat, Principal, encode_args};
+
+use super::super::declarations::sns_index::{
+    Account, GetAccountTransactionsArgs, GetTransactionsResult, Status,
+};
+
+/// Get up to `max_results` transactions of an account from the index, newest first
+/// `start` is the newest transaction id to return (None for the latest)
+pub async fn get_account_transactions(
+    agent: &dyn CanisterClient,
+    index_canister: Principal,
+    account: Account,
+    start: Option<u64>,
+    max_results: u64,
+) -> Result<AccountTransactions> {
+    let request = GetAccountTransactionsArgs {
+        account,
+        start: start.map(Nat::from),
+        max_results: Nat::from(max_results),
+    };
+
+    let result_bytes = canister_call::query(
+        agent,
+        &index_canister,
+        "get_account_transactions",
+        encode_args((request,))?,
+    )
+    .await
+    .context("Failed to call get_account_transactions")?;
+
+    let result = Decode!(&result_bytes, GetTransactionsResult)
+        .context("Failed to decode get_account_transactions response")?;
+    let response = match result {
+        GetTransactionsResult::Ok(response) => response,
+        GetTransactionsResult::Err(e) => {
+            anyhow::bail!("Index rejected get_account_transactions: {}", e.message)
+        }
+    };
+
+    Ok(AccountTransactions {
+        balance_e8s: nat_to_u64(&response.balance),
+        transactions: response
+            .transactions
+            .into_iter()
+            .map(|tx| sns_transaction_to_entry(nat_to_u64(&tx.id), tx.transaction))
+            .collect(),
+        oldest_tx_id: response.oldest_tx_id.as_ref().map(nat_to_u64),
+    })
+}
+
+/// Number of ledger blocks the index has processed
+pub async fn get_index_blocks_synced(
+    agent: &dyn CanisterClient,
+    index_canister: Principal,
+) -> Result<u64> {
+    let result_bytes = canister_call::query(agent, &index_canister, "status", encode_args(())?)
+        .await
+        .context("Failed to call status")?;
+    let status = Decode!(&result_bytes, Status).context("Failed to decode index status")?;
+    Ok(nat_to_u64(&status.num_blocks_synced))
+}
+
+/// Compare the blocks the index has processed with the ledger's length
+pub async fn get_index_sync(
+    agent: &dyn CanisterClient,
+    index_canister: Principal,
+    ledger_canister: Principal,
+) -> Result<IndexSync> {
+    let blocks_synced = get_index_blocks_synced(agent, index_canister).await?;
+    let ledger_blocks = nat_to_u64(
+        &get_sns_transactions(agent, ledger_canister, 0, 0)
+            .await?
+            .log_length,
+    );
+    Ok(IndexSync {
+        blocks_synced,
+        ledger_blocks,
+    })
+}
+
+/// Get the transactions of an account from the deployed SNS's index
+pub async fn get_account_transactions_default_path(
+    principal: Principal,
+    subaccount: Option<Vec<u8>>,
+    max_results: u64,
+) -> Result<AccountTransactions> {
+    let index_canister = app_context().sns_index_canister()?;
+    let agent = app_context().anonymous_agent().await?;
+    get_account_transactions(
+        &agent,
+        index_canister,
+        Account {
+            owner: principal,
+            subaccount,
+        },
+        None,
+        max_results,
+    )
+    .await
+}
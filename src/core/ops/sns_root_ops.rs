@@ -0,0 +1,284 @@
+// SNS Root canister operations
+
+use super::canister_call;
+use super::cycles_ledger_ops::{get_cycles_balance, withdraw_cycles};
+use crate::core::context::app_context;
+use anyhow::{Context, Result};
+use candid::{Decode, Nat, Principal, encode_args};
+use ic_agent::Agent;
+use serde::Serialize;
+
+use super::super::declarations::sns_root::{
+    CanisterStatusType, CanisterSummary, GetSnsCanistersSummaryRequest,
+    GetSnsCanistersSummaryResponse,
+};
+use crate::core::utils::constants::CYCLES_LEDGER_FEE;
+use crate::core::utils::data_output::DeployedSnsData;
+
+/// Status of every canister root controls (an update call, but it changes nothing)
+pub async fn get_sns_canisters_summary(
+    agent: &Agent,
+    root_canister: Principal,
+) -> Result<GetSnsCanistersSummaryResponse> {
+    let request = GetSnsCanistersSummaryRequest {
+        update_canister_list: None,
+    };
+
+    let result_bytes = canister_call::read_only_update(
+        agent,
+        &root_canister,
+        "get_sns_canisters_summary",
+        encode_args((request,))?,
+    )
+    .await
+    .context("Failed to get SNS canisters summary")?;
+
+    Decode!(&result_bytes, GetSnsCanistersSummaryResponse)
+        .context("Failed to decode get_sns_canisters_summary response")
+}
+
+/// The canister IDs of the SNS that `root_canister` is the root of, as root reports them
+pub async fn discover_sns_canisters(
+    agent: &Agent,
+    root_canister: Principal,
+) -> Result<DeployedSnsData> {
+    let summary = get_sns_canisters_summary(agent, root_canister)
+        .await
+        .with_context(|| format!("Failed to ask {root_canister} for its SNS canisters"))?;
+    let id = |summary: Option<CanisterSummary>| {
+        summary
+            .and_then(|summary| summary.canister_id)
+            .map(|id| id.to_text())
+    };
+    Ok(DeployedSnsData {
+        root_canister_id: Some(root_canister.to_text()),
+        governance_canister_id: id(summary.governance),
+        index_canister_id: id(summary.index),
+        swap_canister_id: id(summary.swap),
+        ledger_canister_id: id(summary.ledger),
+    })
+}
+
+/// One SNS canister as seen on the replica
+#[derive(Debug, Clone, Serialize)]
+pub struct SnsCanisterInfo {
+    /// root, governance, ledger, swap or index
+    pub role: &'static str,
+    pub canister_id: String,
+    /// Whether the replica reports a module for the canister
+    pub exists: bool,
+    /// Hex-encoded SHA-256 of the installed wasm module
+    pub module_hash: Option<String>,
+    /// Cycle balance reported by root (None if root could not be asked)
+    pub cycles: Option<u128>,
+}
+
+/// Look up the SNS canisters of a deployment: module hashes from the replica's state tree,
+/// cycle balances from root. Fails only if the canister IDs are missing; a failed root
+/// call leaves `cycles` unset and is returned as the second value.
+pub async fn get_sns_canister_info(
+    agent: &Agent,
+    deployed_sns: &DeployedSnsData,
+) -> Result<(Vec<SnsCanisterInfo>, Option<anyhow::Error>)> {
+    let root_canister = deployed_sns.root_canister()?;
+    let roles = [
+        ("root", root_canister),
+        ("governance", deployed_sns.governance_canister()?),
+        ("ledger", deployed_sns.ledger_canister()?),
+        ("swap", deployed_sns.swap_canister()?),
+        ("index", deployed_sns.index_canister()?),
+    ];
+
+    let mut canisters = Vec::with_capacity(roles.len());
+    for (role, canister_id) in roles {
+        let module_hash = agent
+            .read_state_canister_module_hash(canister_id)
+            .await
+            .ok();
+        canisters.push(SnsCanisterInfo {
+            role,
+            canister_id: canister_id.to_text(),
+            exists: module_hash.is_some(),
+            module_hash: module_hash.map(hex::encode),
+            cycles: None,
+        });
+    }
+
+    if !canisters[0].exists {
+        return Ok((canisters, None));
+    }
+    let summary = match get_sns_canisters_summary(agent, root_canister).await {
+        Ok(summary) => summary,
+        Err(e) => return Ok((canisters, Some(e))),
+    };
+    let summaries = [
+        summary.root,
+        summary.governance,
+        summary.ledger,
+        summary.swap,
+        summary.index,
+    ];
+    for (info, summary) in canisters.iter_mut().zip(summaries) {
+        info.cycles = summary.as_ref().and_then(summary_cycles);
+    }
+
+    Ok((canisters, None))
+}
+
+fn summary_cycles(summary: &CanisterSummary) -> Option<u128> {
+    summary
+        .status
+        .as_ref()
+        .and_then(|status| nat_to_u128(&status.cycles))
+}
+
+fn nat_to_u128(value: &Nat) -> Option<u128> {
+    u128::try_from(value.0.clone()).ok()
+}
+
+/// Look up the SNS canisters from the deployment data (see [`get_sns_canister_info`])
+pub async fn get_sns_canister_info_default_path()
+-> Result<(Vec<SnsCanisterInfo>, Option<anyhow::Error>)> {
+    let deployment_data = app_context().deployment_data()?;
+    let agent = app_context().anonymous_agent().await?;
+    get_sns_canister_info(&agent, &deployment_data.deployed_sns).await
+}
+
+/// Status of one canister controlled by SNS root
+#[derive(Debug, Clone, Serialize)]
+pub struct SnsCanisterStatus {
+    /// root, governance, ledger, swap, index, dapp or archive
+    pub role: &'static str,
+    pub canister_id: Principal,
+    /// running, stopping or stopped (None if root could not get the status)
+    pub status: Option<&'static str>,
+    pub cycles: Option<u128>,
+    pub memory_size: Option<u128>,
+    pub idle_cycles_burned_per_day: Option<u128>,
+    /// Hex-encoded SHA-256 of the installed wasm module
+    pub module_hash: Option<String>,
+}
+
+/// Status, cycles, memory and module hash of every canister SNS root controls
+pub async fn get_sns_canister_statuses(
+    agent: &Agent,
+    root_canister: Principal,
+) -> Result<Vec<SnsCanisterStatus>> {
+    let summary = get_sns_canisters_summary(agent, root_canister).await?;
+
+    let named = [
+        ("root", summary.root),
+        ("governance", summary.governance),
+        ("ledger", summary.ledger),
+        ("swap", summary.swap),
+        ("index", summary.index),
+    ]
+    .into_iter()
+    .filter_map(|(role, summary)| summary.map(|summary| (role, summary)));
+    let dapps = summary.dapps.into_iter().map(|summary| ("dapp", summary));
+    let archives = summary
+        .archives
+        .into_iter()
+        .map(|summary| ("archive", summary));
+
+    Ok(named
+        .chain(dapps)
+        .chain(archives)
+        .filter_map(|(role, summary)| {
+            let canister_id = summary.canister_id?;
+            let status = summary.status.as_ref();
+            Some(SnsCanisterStatus {
+                role,
+                canister_id,
+                status: status.map(|s| status_name(s.status)),
+                cycles: status.and_then(|s| nat_to_u128(&s.cycles)),
+                memory_size: status.and_then(|s| nat_to_u128(&s.memory_size)),
+                idle_cycles_burned_per_day: status
+                    .and_then(|s| nat_to_u128(&s.idle_cycles_burned_per_day)),
+                module_hash: status.and_then(|s| s.module_hash.as_ref().map(hex::encode)),
+            })
+        })
+        .collect())
+}
+
+/// Status of every canister of the SNS from the deployment data
+pub async fn get_sns_canister_statuses_default_path() -> Result<Vec<SnsCanisterStatus>> {
+    let root_canister = app_context().sns_root_canister()?;
+    let agent = app_context().anonymous_agent().await?;
+    get_sns_canister_statuses(&agent, root_canister).await
+}
+
+const fn status_name(status: CanisterStatusType) -> &'static str {
+    match status {
+        CanisterStatusType::Running => "running",
+        CanisterStatusType::Stopping => "stopping",
+        CanisterStatusType::Stopped => "stopped",
+    }
+}
+
+#[derive(candid::CandidType, Debug)]
+struct ProvisionalTopUpCanisterArgs {
+    canister_id: Principal,
+    amount: Nat,
+}
+
+/// Add cycles to a canister with the management canister's `provisional_top_up_canister`
+/// Only local replicas (and PocketIC) allow this; it mints the cycles instead of paying for them
+pub async fn top_up_cycles(agent: &Agent, canister: Principal, amount: u128) -> Result<()> {
+    let args = ProvisionalTopUpCanisterArgs {
+        canister_id: canister,
+        amount: Nat::from(amount),
+    };
+
+    canister_call::management_update(
+        agent,
+        &canister,
+        "provisional_top_up_canister",
+        encode_args((args,))?,
+    )
+    .await
+    .with_context(|| format!("Failed to top up {canister} with cycles"))?;
+    Ok(())
+}
+
+/// Where top-up cycles come from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CyclesSource {
+    /// The SNS owner's cycles ledger account, as `dfx canister deposit-cycles` pays
+    CyclesLedger,
+    /// Minted by the replica (`--provisional`), for replicas without a cycles ledger
+    Provisional,
+}
+
+/// Top up canisters with cycles, signed by the SNS owner
+/// From the cycles ledger the owner's balance is checked first, so no canister gets a partial
+/// top-up because the balance ran out halfway.
+pub async fn top_up_cycles_default_path(
+    canisters: &[Principal],
+    amount: u128,
+    source: CyclesSource,
+) -> Result<()> {
+    let owner = Principal::from_text(&app_context().deployment_data()?.owner_principal)
+        .context("Failed to parse owner principal")?;
+    let agent = app_context().agent_for_principal(owner).await?;
+
+    if source == CyclesSource::Provisional {
+        for canister in canisters {
+            top_up_cycles(&agent, *canister, amount).await?;
+        }
+        return Ok(());
+    }
+
+    let needed = (amount.saturating_add(CYCLES_LEDGER_FEE)).saturating_mul(canisters.len() as u128);
+    let balance = get_cycles_balance(&agent, owner).await?;
+    if balance < needed {
+        anyhow::bail!(
+            "The SNS owner {owner} has {balance} cycles on the cycles ledger but the top-up needs {needed} (including fees)\n\
+             Fund it with `dfx cycles convert --amount <icp>` as the owner, or pass --provisional to mint the cycles on a local replica"
+        );
+    }
+    for canister in canisters {
+        withdraw_cycles(&agent, *canister, amount).await?;
+    }
+    Ok(())
+}
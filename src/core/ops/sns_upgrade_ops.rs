@@ -0,0 +1,302 @@
+// SNS framework upgrades: propose them and follow the upgrade journal until they complete
+
+use anyhow::{Context, Result};
+use candid::{Decode, Principal, encode_args};
+use ic_agent::Agent;
+
+use super::canister_call;
+use super::sns_governance_ops::{
+    ProposalOutcome, get_proposal, proposal_outcome, submit_proposal_with_all_votes_default_path,
+};
+use crate::core::context::app_context;
+use crate::core::declarations::sns_governance::{
+    Action, AdvanceSnsTargetVersion, GetRunningSnsVersionArg, GetRunningSnsVersionResponse,
+    GetUpgradeJournalRequest, GetUpgradeJournalResponse, Proposal, UpgradeJournalEntry,
+    UpgradeJournalEntryEventInner, UpgradeOutcomeStatusInner, UpgradeStartedReasonInner, Version,
+};
+
+/// How often to poll governance while an upgrade is in progress
+const UPGRADE_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// How long to wait for an upgrade to complete (each step upgrades one canister)
+const UPGRADE_POLL_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(600);
+
+/// Most journal entries governance returns per call
+const JOURNAL_PAGE_SIZE: u64 = 100;
+
+/// Version governance reports as deployed, and the upgrade in progress if any
+pub async fn get_running_sns_version(
+    agent: &Agent,
+    governance_canister: Principal,
+) -> Result<GetRunningSnsVersionResponse> {
+    let response = canister_call::query(
+        agent,
+        &governance_canister,
+        "get_running_sns_version",
+        encode_args((GetRunningSnsVersionArg {},))?,
+    )
+    .await
+    .context("Failed to call get_running_sns_version")?;
+    Decode!(&response, GetRunningSnsVersionResponse)
+        .context("Failed to decode get_running_sns_version response")
+}
+
+/// Upgrade journal entries from `offset` on, with the deployed and target versions
+pub async fn get_upgrade_journal(
+    agent: &Agent,
+    governance_canister: Principal,
+    offset: Option<u64>,
+) -> Result<GetUpgradeJournalResponse> {
+    let request = GetUpgradeJournalRequest {
+        offset,
+        limit: offset.map(|_| JOURNAL_PAGE_SIZE),
+    };
+    let response = canister_call::query(
+        agent,
+        &governance_canister,
+        "get_upgrade_journal",
+        encode_args((request,))?,
+    )
+    .await
+    .context("Failed to call get_upgrade_journal")?;
+    Decode!(&response, GetUpgradeJournalResponse)
+        .context("Failed to decode get_upgrade_journal response")
+}
+
+/// Upgrade journal of the deployed SNS
+pub async fn get_upgrade_journal_default_path(
+    offset: Option<u64>,
+) -> Result<GetUpgradeJournalResponse> {
+    let agent = app_context().anonymous_agent().await?;
+    get_upgrade_journal(&agent, app_context().sns_governance_canister()?, offset).await
+}
+
+/// Whether two SNS versions have the same wasm for every canister
+#[must_use]
+pub fn same_version(a: &Version, b: &Version) -> bool {
+    a.governance_wasm_hash == b.governance_wasm_hash
+        && a.root_wasm_hash == b.root_wasm_hash
+        && a.swap_wasm_hash == b.swap_wasm_hash
+        && a.ledger_wasm_hash == b.ledger_wasm_hash
+        && a.archive_wasm_hash == b.archive_wasm_hash
+        && a.index_wasm_hash == b.index_wasm_hash
+}
+
+/// One-line summary of an SNS version: the first bytes of each canister's wasm hash
+#[must_use]
+pub fn format_version(version: &Version) -> String {
+    let short = |hash: &[u8]| {
+        if hash.is_empty() {
+            "-".to_string()
+        } else {
+            hex::encode(&hash[..hash.len().min(4)])
+        }
+    };
+    format!(
+        "governance {}, root {}, swap {}, ledger {}, archive {}, index {}",
+        short(&version.governance_wasm_hash),
+        short(&version.root_wasm_hash),
+        short(&version.swap_wasm_hash),
+        short(&version.ledger_wasm_hash),
+        short(&version.archive_wasm_hash),
+        short(&version.index_wasm_hash)
+    )
+}
+
+fn format_optional_version(version: Option<&Version>) -> String {
+    version.map_or_else(|| "none".to_string(), format_version)
+}
+
+/// Human-readable description of an upgrade journal event
+#[must_use]
+pub fn describe_journal_event(event: Option<&UpgradeJournalEntryEventInner>) -> String {
+    match event {
+        Some(UpgradeJournalEntryEventInner::TargetVersionSet(set)) => format!(
+            "Target version set{}: {}",
+            if set.is_advanced_automatically == Some(true) {
+                " automatically"
+            } else {
+                ""
+            },
+            format_optional_version(set.new_target_version.as_ref())
+        ),
+        Some(UpgradeJournalEntryEventInner::TargetVersionReset(reset)) => format!(
+            "Target version reset to {}{}",
+            format_optional_version(reset.new_target_version.as_ref()),
+            reset
+                .human_readable
+                .as_ref()
+                .map(|text| format!(" ({})", text))
+                .unwrap_or_default()
+        ),
+        Some(UpgradeJournalEntryEventInner::UpgradeStepsRefreshed(refreshed)) => format!(
+            "Upgrade steps refreshed: {} step(s) available",
+            refreshed
+                .upgrade_steps
+                .as_ref()
+                .map_or(0, |steps| steps.versions.len().saturating_sub(1))
+        ),
+        Some(UpgradeJournalEntryEventInner::UpgradeStepsReset(reset)) => format!(
+            "Upgrade steps reset: {} step(s) available{}",
+            reset
+                .upgrade_steps
+                .as_ref()
+                .map_or(0, |steps| steps.versions.len().saturating_sub(1)),
+            reset
+                .human_readable
+                .as_ref()
+                .map(|text| format!(" ({})", text))
+                .unwrap_or_default()
+        ),
+        Some(UpgradeJournalEntryEventInner::UpgradeStarted(started)) => format!(
+            "Upgrade started ({}): {}",
+            match &started.reason {
+                Some(UpgradeStartedReasonInner::UpgradeSnsToNextVersionProposal(id)) => {
+                    format!("proposal {}", id.id)
+                }
+                Some(UpgradeStartedReasonInner::BehindTargetVersion {}) => {
+                    "behind target version".to_string()
+                }
+                None => "no reason given".to_string(),
+            },
+            format_optional_version(started.expected_version.as_ref())
+        ),
+        Some(UpgradeJournalEntryEventInner::UpgradeOutcome(outcome)) => format!(
+            "Upgrade {}{}",
+            match &outcome.status {
+                Some(UpgradeOutcomeStatusInner::Success {}) => "succeeded",
+                Some(UpgradeOutcomeStatusInner::Timeout {}) => "timed out",
+                Some(UpgradeOutcomeStatusInner::ExternalFailure {}) => "failed",
+                Some(UpgradeOutcomeStatusInner::InvalidState { .. }) => "hit an invalid state",
+                None => "finished with no status",
+            },
+            outcome
+                .human_readable
+                .as_ref()
+                .map(|text| format!(": {}", text))
+                .unwrap_or_default()
+        ),
+        None => "Unknown event".to_string(),
+    }
+}
+
+/// Propose upgrading one SNS canister to the next version SNS-W offers and have every
+/// participant vote for it
+pub async fn upgrade_sns_to_next_version_default_path(
+    proposer_principal: Principal,
+) -> Result<u64> {
+    let proposal = Proposal {
+        url: String::new(),
+        title: "Upgrade SNS to next version".to_string(),
+        summary: "Upgrade the SNS one step along the SNS-W upgrade path".to_string(),
+        action: Some(Action::UpgradeSnsToNextVersion {}),
+    };
+    submit_proposal_with_all_votes_default_path(proposer_principal, proposal).await
+}
+
+/// Propose advancing the target version to the latest one SNS-W offers and have every
+/// participant vote for it. Governance then upgrades step by step until it gets there.
+pub async fn advance_sns_target_version_default_path(proposer_principal: Principal) -> Result<u64> {
+    let proposal = Proposal {
+        url: String::new(),
+        title: "Advance SNS target version".to_string(),
+        summary: "Advance the SNS target version to the latest version on the upgrade path"
+            .to_string(),
+        action: Some(Action::AdvanceSnsTargetVersion(AdvanceSnsTargetVersion {
+            new_target: None,
+        })),
+    };
+    submit_proposal_with_all_votes_default_path(proposer_principal, proposal).await
+}
+
+/// Follow an upgrade proposal until the SNS runs its target version
+/// Journal entries after `journal_offset` are passed to `on_entry` as they appear. Fails if
+/// the proposal fails or is rejected, or an upgrade step does not succeed. Returns the
+/// deployed version.
+pub async fn wait_for_sns_upgrade(
+    agent: &Agent,
+    governance_canister: Principal,
+    proposal_id: u64,
+    journal_offset: u64,
+    mut on_entry: impl FnMut(&UpgradeJournalEntry),
+) -> Result<Version> {
+    let deadline = std::time::Instant::now() + UPGRADE_POLL_TIMEOUT;
+    let mut next_entry = journal_offset;
+    loop {
+        let journal = get_upgrade_journal(agent, governance_canister, Some(next_entry)).await?;
+        for entry in journal
+            .upgrade_journal
+            .as_ref()
+            .map(|journal| journal.entries.as_slice())
+            .unwrap_or_default()
+        {
+            next_entry += 1;
+            on_entry(entry);
+            if let Some(UpgradeJournalEntryEventInner::UpgradeOutcome(outcome)) = &entry.event
+                && !matches!(outcome.status, Some(UpgradeOutcomeStatusInner::Success {}))
+            {
+                anyhow::bail!(
+                    "SNS upgrade did not succeed: {}",
+                    describe_journal_event(entry.event.as_ref())
+                );
+            }
+        }
+
+        let proposal = get_proposal(agent, governance_canister, proposal_id).await?;
+        match proposal_outcome(&proposal) {
+            Some(ProposalOutcome::Failed { reason, .. }) => anyhow::bail!(
+                "Proposal {} failed to execute: {}",
+                proposal_id,
+                reason.unwrap_or_else(|| "no failure reason given".to_string())
+            ),
+            Some(ProposalOutcome::Rejected { .. }) => anyhow::bail!(
+                "Proposal {} was rejected (see show-ballots {})",
+                proposal_id,
+                proposal_id
+            ),
+            Some(ProposalOutcome::Executed { .. }) => {
+                let running = get_running_sns_version(agent, governance_canister).await?;
+                let at_target = match (&journal.deployed_version, &journal.target_version) {
+                    (Some(deployed), Some(target)) => same_version(deployed, target),
+                    _ => false,
+                };
+                if at_target
+                    && running.pending_version.is_none()
+                    && let Some(deployed) = journal.deployed_version
+                {
+                    return Ok(deployed);
+                }
+            }
+            None => {}
+        }
+
+        if std::time::Instant::now() >= deadline {
+            anyhow::bail!(
+                "Timed out after {}s waiting for the SNS upgrade of proposal {} to complete \
+                 (deployed: {}; target: {})",
+                UPGRADE_POLL_TIMEOUT.as_secs(),
+                proposal_id,
+                format_optional_version(journal.deployed_version.as_ref()),
+                format_optional_version(journal.target_version.as_ref())
+            );
+        }
+        tokio::time::sleep(UPGRADE_POLL_INTERVAL).await;
+    }
+}
+
+/// Follow an upgrade proposal of the deployed SNS until it runs its target version
+pub async fn wait_for_sns_upgrade_default_path(
+    proposal_id: u64,
+    journal_offset: u64,
+    on_entry: impl FnMut(&UpgradeJournalEntry),
+) -> Result<Version> {
+    let agent = app_context().anonymous_agent().await?;
+    wait_for_sns_upgrade(
+        &agent,
+        app_context().sns_governance_canister()?,
+        proposal_id,
+        journal_offset,
+        on_entry,
+    )
+    .await
+}
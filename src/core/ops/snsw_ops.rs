@@ -1,12 +1,17 @@
 // SNS-W (SNS Wrapper) canister operations
 
+use super::canister_call;
+use crate::core::context::app_context;
 use anyhow::{Context, Result};
 use candid::{Decode, Principal, encode_args};
 use ic_agent::Agent;
 
+use crate::core::utils::data_output::DeployedSnsData;
+
 use super::super::declarations::sns_wasm::{
     DeployedSns, GetDeployedSnsByProposalIdRequest, GetDeployedSnsByProposalIdResponse,
     GetDeployedSnsByProposalIdResult, ListDeployedSnsesArg, ListDeployedSnsesResponse,
+    ListUpgradeStep, ListUpgradeStepsRequest, ListUpgradeStepsResponse,
 };
 
 /// Get deployed SNS by proposal ID
@@ -17,12 +22,14 @@ pub async fn get_deployed_sns(
 ) -> Result<DeployedSns> {
     let request = GetDeployedSnsByProposalIdRequest { proposal_id };
 
-    let result_bytes = agent
-        .query(&snsw_canister, "get_deployed_sns_by_proposal_id")
-        .with_arg(encode_args((request,))?)
-        .call()
-        .await
-        .context("Failed to get deployed SNS")?;
+    let result_bytes = canister_call::query(
+        agent,
+        &snsw_canister,
+        "get_deployed_sns_by_proposal_id",
+        encode_args((request,))?,
+    )
+    .await
+    .context("Failed to get deployed SNS")?;
 
     let response: GetDeployedSnsByProposalIdResponse =
         Decode!(&result_bytes, GetDeployedSnsByProposalIdResponse)
@@ -44,12 +51,14 @@ pub async fn list_deployed_snses(
 ) -> Result<Vec<DeployedSns>> {
     let request = ListDeployedSnsesArg {};
 
-    let result_bytes = agent
-        .query(&snsw_canister, "list_deployed_snses")
-        .with_arg(encode_args((request,))?)
-        .call()
-        .await
-        .context("Failed to list deployed SNSes")?;
+    let result_bytes = canister_call::query(
+        agent,
+        &snsw_canister,
+        "list_deployed_snses",
+        encode_args((request,))?,
+    )
+    .await
+    .context("Failed to list deployed SNSes")?;
 
     let response: ListDeployedSnsesResponse = Decode!(&result_bytes, ListDeployedSnsesResponse)
         .context("Failed to decode list_deployed_snses response")?;
@@ -57,6 +66,33 @@ pub async fn list_deployed_snses(
     Ok(response.instances)
 }
 
+/// SNS versions on the upgrade path SNS-W offers, oldest first
+/// The last step is the version new SNSes are deployed with.
+pub async fn list_upgrade_steps(
+    agent: &Agent,
+    snsw_canister: Principal,
+) -> Result<Vec<ListUpgradeStep>> {
+    let request = ListUpgradeStepsRequest {
+        limit: 0,
+        starting_at: None,
+        sns_governance_canister_id: None,
+    };
+
+    let result_bytes = canister_call::query(
+        agent,
+        &snsw_canister,
+        "list_upgrade_steps",
+        encode_args((request,))?,
+    )
+    .await
+    .context("Failed to list upgrade steps")?;
+
+    let response: ListUpgradeStepsResponse = Decode!(&result_bytes, ListUpgradeStepsResponse)
+        .context("Failed to decode list_upgrade_steps response")?;
+
+    Ok(response.steps)
+}
+
 /// Check if any SNS is deployed
 pub async fn check_sns_deployed(agent: &Agent, snsw_canister: Principal) -> Result<bool> {
     let deployed = list_deployed_snses(agent, snsw_canister).await?;
@@ -65,13 +101,104 @@ pub async fn check_sns_deployed(agent: &Agent, snsw_canister: Principal) -> Resu
 
 /// High-level function to check if SNS is deployed using default agent and canister
 pub async fn check_sns_deployed_default_path() -> Result<bool> {
-    use super::identity::create_agent;
-    use crate::core::utils::constants::SNSW_CANISTER;
-
-    let anonymous_identity = ic_agent::identity::AnonymousIdentity;
-    let agent = create_agent(Box::new(anonymous_identity)).await?;
-    let snsw_canister =
-        Principal::from_text(SNSW_CANISTER).context("Failed to parse SNS-W canister ID")?;
+    let agent = app_context().anonymous_agent().await?;
+    let snsw_canister = crate::core::utils::network::network_config().snsw_canister;
 
     check_sns_deployed(&agent, snsw_canister).await
 }
+
+/// List all SNSes deployed on SNS-W using the default agent and canister
+pub async fn list_deployed_snses_default_path() -> Result<Vec<DeployedSns>> {
+    let agent = app_context().anonymous_agent().await?;
+    let snsw_canister = crate::core::utils::network::network_config().snsw_canister;
+
+    list_deployed_snses(&agent, snsw_canister).await
+}
+
+/// Where [`find_sns_by_root`] found the canister IDs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnsSource {
+    SnsW,
+    Root,
+}
+
+/// The canisters of the SNS with root canister `root_canister`: from its SNS-W entry, or
+/// from root itself for an SNS that SNS-W does not list (e.g. installed without SNS-W)
+pub async fn find_sns_by_root(
+    agent: &Agent,
+    snsw_canister: Principal,
+    root_canister: Principal,
+) -> Result<(DeployedSnsData, SnsSource)> {
+    use super::sns_root_ops::discover_sns_canisters;
+
+    match list_deployed_snses(agent, snsw_canister).await {
+        Ok(snses) => {
+            if let Some(entry) = snses
+                .iter()
+                .find(|sns| sns.root_canister_id == Some(root_canister))
+            {
+                return Ok((DeployedSnsData::from(entry), SnsSource::SnsW));
+            }
+        }
+        Err(e) => log::warn!("Failed to list the SNSes on SNS-W, asking root: {e:#}"),
+    }
+    let deployed = discover_sns_canisters(agent, root_canister)
+        .await
+        .with_context(|| {
+            format!("{root_canister} is not the root of an SNS on SNS-W and did not answer as one")
+        })?;
+    Ok((deployed, SnsSource::Root))
+}
+
+/// Look up an SNS by its root canister using the default agent and canister (see [`find_sns_by_root`])
+pub async fn find_sns_by_root_default_path(
+    root_canister: Principal,
+) -> Result<(DeployedSnsData, SnsSource)> {
+    let agent = app_context().anonymous_agent().await?;
+    let snsw_canister = crate::core::utils::network::network_config().snsw_canister;
+
+    find_sns_by_root(&agent, snsw_canister, root_canister).await
+}
+
+/// Canisters whose ID in the deployment data differs from an SNS-W entry
+/// An empty list means the deployment data describes this SNS exactly
+#[must_use]
+pub fn deployment_mismatches(
+    recorded: &DeployedSnsData,
+    deployed: &DeployedSns,
+) -> Vec<&'static str> {
+    let fields = [
+        (
+            "root",
+            &recorded.root_canister_id,
+            deployed.root_canister_id,
+        ),
+        (
+            "governance",
+            &recorded.governance_canister_id,
+            deployed.governance_canister_id,
+        ),
+        (
+            "ledger",
+            &recorded.ledger_canister_id,
+            deployed.ledger_canister_id,
+        ),
+        (
+            "swap",
+            &recorded.swap_canister_id,
+            deployed.swap_canister_id,
+        ),
+        (
+            "index",
+            &recorded.index_canister_id,
+            deployed.index_canister_id,
+        ),
+    ];
+    fields
+        .into_iter()
+        .filter(|(_, recorded, deployed)| {
+            recorded.as_deref() != deployed.map(|p| p.to_text()).as_deref()
+        })
+        .map(|(name, _, _)| name)
+        .collect()
+}
@@ -1,16 +1,21 @@
 // SNS Swap canister operations
 
+use super::canister_call;
+use super::canister_client::CanisterClient;
+use crate::core::context::app_context;
+use crate::core::utils::exit_code::ValidationError;
 use anyhow::{Context, Result};
 use candid::{Decode, Principal, encode_args};
-use ic_agent::Agent;
 use ic_ledger_types::Subaccount;
 
 use super::super::declarations::sns_swap::{
-    FinalizeSwapArg, FinalizeSwapResponse, GetLifecycleArg, GetLifecycleResponse,
-    NewSaleTicketRequest, NewSaleTicketResponse, RefreshBuyerTokensRequest,
-    RefreshBuyerTokensResponse, Result2,
+    ErrorRefundIcpRequest, ErrorRefundIcpResponse, FinalizeSwapArg, FinalizeSwapResponse,
+    GetLifecycleArg, GetLifecycleResponse, NewSaleTicketRequest, NewSaleTicketResponse,
+    RefreshBuyerTokensRequest, RefreshBuyerTokensResponse, Result1, Result2,
 };
 use super::super::utils::{print_info, print_warning};
+use super::ledger_ops::get_icp_ledger_balance;
+use crate::core::utils::constants::ICP_TRANSFER_FEE;
 
 #[derive(candid::CandidType, candid::Deserialize, Debug)]
 struct GetDerivedStateArg {}
@@ -22,13 +27,74 @@ pub struct GetDerivedStateResponse {
     pub cf_participant_count: Option<u64>,
     pub cf_participation_icp_e8s: Option<u64>,
     pub buyer_total_icp_e8s: Option<u64>,
-    pub sns_tokens_per_icp: Option<u64>,
+    pub sns_tokens_per_icp: Option<f64>,
+}
+
+#[derive(candid::CandidType, candid::Deserialize, Debug)]
+struct GetSaleParametersArg {}
+
+#[derive(candid::CandidType, candid::Deserialize, Debug)]
+struct GetSaleParametersResponse {
+    params: Option<SaleParams>,
+}
+
+/// Swap sale parameters (subset of the swap's `Params`)
+#[derive(candid::CandidType, candid::Deserialize, Debug, Clone)]
+pub struct SaleParams {
+    pub min_participants: u32,
+    pub min_icp_e8s: u64,
+    pub max_icp_e8s: u64,
+    pub min_direct_participation_icp_e8s: Option<u64>,
+    pub max_direct_participation_icp_e8s: Option<u64>,
+    pub min_participant_icp_e8s: u64,
+    pub max_participant_icp_e8s: u64,
+    pub sns_token_e8s: u64,
+    pub swap_due_timestamp_seconds: u64,
+    pub neuron_basket_construction_parameters: Option<NeuronBasketParams>,
+}
+
+/// How the swap splits each participant's tokens into neurons
+#[derive(candid::CandidType, candid::Deserialize, Debug, Clone)]
+pub struct NeuronBasketParams {
+    /// Neurons per participant
+    pub count: u64,
+    /// Dissolve delay added for each further neuron of the basket
+    pub dissolve_delay_interval_seconds: u64,
+}
+
+/// Snapshot of a swap: lifecycle, participation and timing
+#[derive(Debug, Clone)]
+pub struct SwapState {
+    pub lifecycle: i32,
+    pub direct_participant_count: u64,
+    pub direct_participation_icp_e8s: u64,
+    pub buyer_total_icp_e8s: u64,
+    /// SNS tokens per ICP at the current participation (None before anyone participates)
+    pub sns_tokens_per_icp: Option<f64>,
+    pub open_timestamp_seconds: Option<u64>,
+    /// When the swap committed or aborted
+    pub termination_timestamp_seconds: Option<u64>,
+    pub params: Option<SaleParams>,
+}
+
+/// Name of a swap lifecycle value
+#[must_use]
+pub const fn lifecycle_name(lifecycle: i32) -> &'static str {
+    match lifecycle {
+        1 => "Pending",
+        2 => "Open",
+        3 => "Committed",
+        4 => "Aborted",
+        5 => "Adopted",
+        _ => "Unspecified",
+    }
 }
 
 /// Generate participant subaccount from principal using ic_ledger_types::Subaccount
 ///
 /// This matches the implementation in ic-ledger-types::Subaccount::from(Principal)
 /// which uses a length prefix: [length_byte, principal_bytes..., 0...]
+#[must_use]
 pub fn generate_participant_subaccount(principal: Principal) -> Subaccount {
     let mut subaccount = [0u8; 32];
     let principal_bytes = principal.as_slice();
@@ -41,7 +107,7 @@ pub fn generate_participant_subaccount(principal: Principal) -> Subaccount {
 /// Returns true if successful, false otherwise
 /// Note: Sale ticket creation is optional - the bash script ignores failures
 pub async fn create_sale_ticket(
-    agent: &Agent,
+    agent: &dyn CanisterClient,
     swap_canister: Principal,
     amount_icp_e8s: u64,
     subaccount: Option<Vec<u8>>,
@@ -51,11 +117,13 @@ pub async fn create_sale_ticket(
         subaccount: subaccount.map(|v| v.to_vec()),
     };
 
-    let result_bytes = match agent
-        .update(&swap_canister, "new_sale_ticket")
-        .with_arg(encode_args((request,))?)
-        .call_and_wait()
-        .await
+    let result_bytes = match canister_call::update(
+        agent,
+        &swap_canister,
+        "new_sale_ticket",
+        encode_args((request,))?,
+    )
+    .await
     {
         Ok(bytes) => bytes,
         Err(_e) => {
@@ -113,7 +181,7 @@ pub async fn create_sale_ticket(
 
 /// Refresh buyer tokens
 pub async fn refresh_buyer_tokens(
-    agent: &Agent,
+    agent: &dyn CanisterClient,
     swap_canister: Principal,
     buyer: Principal,
 ) -> Result<RefreshBuyerTokensResponse> {
@@ -122,12 +190,14 @@ pub async fn refresh_buyer_tokens(
         buyer: buyer.to_string(),
     };
 
-    let result_bytes = agent
-        .update(&swap_canister, "refresh_buyer_tokens")
-        .with_arg(encode_args((request,))?)
-        .call_and_wait()
-        .await
-        .context("Failed to refresh buyer tokens")?;
+    let result_bytes = canister_call::update(
+        agent,
+        &swap_canister,
+        "refresh_buyer_tokens",
+        encode_args((request,))?,
+    )
+    .await
+    .context("Failed to refresh buyer tokens")?;
 
     let response: RefreshBuyerTokensResponse =
         Decode!(&result_bytes, RefreshBuyerTokensResponse)
@@ -143,15 +213,20 @@ pub async fn refresh_buyer_tokens(
 }
 
 /// Get swap lifecycle
-pub async fn get_swap_lifecycle(agent: &Agent, swap_canister: Principal) -> Result<i32> {
+pub async fn get_swap_lifecycle(
+    agent: &dyn CanisterClient,
+    swap_canister: Principal,
+) -> Result<i32> {
     let request = GetLifecycleArg {};
 
-    let result_bytes = agent
-        .query(&swap_canister, "get_lifecycle")
-        .with_arg(encode_args((request,))?)
-        .call()
-        .await
-        .context("Failed to get swap lifecycle")?;
+    let result_bytes = canister_call::query(
+        agent,
+        &swap_canister,
+        "get_lifecycle",
+        encode_args((request,))?,
+    )
+    .await
+    .context("Failed to get swap lifecycle")?;
 
     let response: GetLifecycleResponse = Decode!(&result_bytes, GetLifecycleResponse)
         .context("Failed to decode get_lifecycle response")?;
@@ -161,17 +236,19 @@ pub async fn get_swap_lifecycle(agent: &Agent, swap_canister: Principal) -> Resu
 
 /// Get swap derived state to check participation
 pub async fn get_derived_state(
-    agent: &Agent,
+    agent: &dyn CanisterClient,
     swap_canister: Principal,
 ) -> Result<GetDerivedStateResponse> {
     let request = GetDerivedStateArg {};
 
-    let result_bytes = agent
-        .query(&swap_canister, "get_derived_state")
-        .with_arg(encode_args((request,))?)
-        .call()
-        .await
-        .context("Failed to get derived state")?;
+    let result_bytes = canister_call::query(
+        agent,
+        &swap_canister,
+        "get_derived_state",
+        encode_args((request,))?,
+    )
+    .await
+    .context("Failed to get derived state")?;
 
     let response: GetDerivedStateResponse = Decode!(&result_bytes, GetDerivedStateResponse)
         .context("Failed to decode get_derived_state response")?;
@@ -179,16 +256,138 @@ pub async fn get_derived_state(
     Ok(response)
 }
 
+/// Get swap sale parameters (None if the swap has not been initialized)
+pub async fn get_sale_parameters(
+    agent: &dyn CanisterClient,
+    swap_canister: Principal,
+) -> Result<Option<SaleParams>> {
+    let result_bytes = canister_call::query(
+        agent,
+        &swap_canister,
+        "get_sale_parameters",
+        encode_args((GetSaleParametersArg {},))?,
+    )
+    .await
+    .context("Failed to get sale parameters")?;
+
+    let response: GetSaleParametersResponse = Decode!(&result_bytes, GetSaleParametersResponse)
+        .context("Failed to decode get_sale_parameters response")?;
+
+    Ok(response.params)
+}
+
+/// Get the lifecycle, participation and timing of a swap
+pub async fn get_swap_state(
+    agent: &dyn CanisterClient,
+    swap_canister: Principal,
+) -> Result<SwapState> {
+    let lifecycle_bytes = canister_call::query(
+        agent,
+        &swap_canister,
+        "get_lifecycle",
+        encode_args((GetLifecycleArg {},))?,
+    )
+    .await
+    .context("Failed to get swap lifecycle")?;
+    let lifecycle = Decode!(&lifecycle_bytes, GetLifecycleResponse)
+        .context("Failed to decode get_lifecycle response")?;
+
+    let derived = get_derived_state(agent, swap_canister).await?;
+    let params = get_sale_parameters(agent, swap_canister).await?;
+
+    Ok(SwapState {
+        lifecycle: lifecycle.lifecycle.unwrap_or(0),
+        direct_participant_count: derived.direct_participant_count.unwrap_or(0),
+        direct_participation_icp_e8s: derived.direct_participation_icp_e8s.unwrap_or(0),
+        buyer_total_icp_e8s: derived.buyer_total_icp_e8s.unwrap_or(0),
+        sns_tokens_per_icp: derived.sns_tokens_per_icp.filter(|rate| *rate > 0.0),
+        open_timestamp_seconds: lifecycle.decentralization_sale_open_timestamp_seconds,
+        termination_timestamp_seconds: lifecycle
+            .decentralization_swap_termination_timestamp_seconds,
+        params,
+    })
+}
+
+/// Get the state of the swap from the deployment data
+pub async fn get_swap_state_default_path() -> Result<SwapState> {
+    let swap_canister = app_context().sns_swap_canister()?;
+    let agent = app_context().anonymous_agent().await?;
+    get_swap_state(&agent, swap_canister).await
+}
+
+/// Whether a swap lifecycle is final (Committed or Aborted), so the swap can be finalized
+#[must_use]
+pub const fn is_swap_ended(lifecycle: i32) -> bool {
+    matches!(lifecycle, 3 | 4)
+}
+
+/// Poll the swap lifecycle until it is Committed or Aborted, returning that lifecycle
+/// `on_poll` is called with each lifecycle seen while waiting
+pub async fn wait_for_swap_end(
+    agent: &dyn CanisterClient,
+    swap_canister: Principal,
+    interval: std::time::Duration,
+    mut on_poll: impl FnMut(i32),
+) -> Result<i32> {
+    loop {
+        let lifecycle = get_swap_lifecycle(agent, swap_canister).await?;
+        if is_swap_ended(lifecycle) {
+            return Ok(lifecycle);
+        }
+        on_poll(lifecycle);
+        tokio::time::sleep(interval).await;
+    }
+}
+
+/// Finalize the swap from the deployment data, signed by the SNS owner
+/// With `watch`, waits for the swap to commit or abort first (polling every `interval`);
+/// otherwise fails unless the swap has already ended. Returns the final lifecycle.
+pub async fn finalize_swap_default_path(watch: bool, interval: std::time::Duration) -> Result<i32> {
+    let swap_canister = app_context().sns_swap_canister()?;
+    let owner = Principal::from_text(&app_context().deployment_data()?.owner_principal)
+        .context("Failed to parse owner principal")?;
+    let agent = app_context().agent_for_principal(owner).await?;
+
+    let lifecycle = if watch {
+        print_info(&format!(
+            "Waiting for the swap to commit or abort (checking every {}s)...",
+            interval.as_secs()
+        ));
+        wait_for_swap_end(&agent, swap_canister, interval, |lifecycle| {
+            print_info(&format!("Lifecycle: {}", lifecycle_name(lifecycle)));
+        })
+        .await?
+    } else {
+        let lifecycle = get_swap_lifecycle(&agent, swap_canister).await?;
+        if !is_swap_ended(lifecycle) {
+            anyhow::bail!(
+                "Swap is {}; it can only be finalized once Committed or Aborted (use --watch to wait)",
+                lifecycle_name(lifecycle)
+            );
+        }
+        lifecycle
+    };
+
+    print_info(&format!(
+        "Swap is {}, finalizing...",
+        lifecycle_name(lifecycle)
+    ));
+    finalize_swap(&agent, swap_canister).await?;
+    Ok(lifecycle)
+}
+
 /// Finalize swap
-pub async fn finalize_swap(agent: &Agent, swap_canister: Principal) -> Result<()> {
+pub async fn finalize_swap(agent: &dyn CanisterClient, swap_canister: Principal) -> Result<()> {
     let request = FinalizeSwapArg {};
 
-    let result_bytes = agent
-        .update(&swap_canister, "finalize_swap")
-        .with_arg(encode_args((request,))?)
-        .call_and_wait()
-        .await
-        .context("Failed to finalize swap")?;
+    let result_bytes = canister_call::update(
+        agent,
+        &swap_canister,
+        "finalize_swap",
+        encode_args((request,))?,
+    )
+    .await
+    .context("Failed to finalize swap")?;
 
     let response: FinalizeSwapResponse = Decode!(&result_bytes, FinalizeSwapResponse)
         .context("Failed to decode finalize_swap response")?;
@@ -200,3 +399,144 @@ pub async fn finalize_swap(agent: &Agent, swap_canister: Principal) -> Result<()
 
     Ok(())
 }
+
+/// Refund the ICP left in a participant's swap subaccount (`error_refund_icp`)
+/// Only allowed once the swap is Committed or Aborted. Returns the refund block height.
+pub async fn error_refund_icp(
+    agent: &dyn CanisterClient,
+    swap_canister: Principal,
+    participant: Principal,
+) -> Result<Option<u64>> {
+    let request = ErrorRefundIcpRequest {
+        source_principal_id: Some(participant),
+    };
+
+    let result_bytes = canister_call::update(
+        agent,
+        &swap_canister,
+        "error_refund_icp",
+        encode_args((request,))?,
+    )
+    .await
+    .context("Failed to call error_refund_icp")?;
+
+    let response: ErrorRefundIcpResponse = Decode!(&result_bytes, ErrorRefundIcpResponse)
+        .context("Failed to decode error_refund_icp response")?;
+
+    match response.result {
+        Some(Result1::Ok(ok)) => Ok(ok.block_height),
+        Some(Result1::Err(err)) => anyhow::bail!(
+            "error_refund_icp failed: {}",
+            err.description
+                .unwrap_or_else(|| format!("error type {:?}", err.error_type))
+        ),
+        None => anyhow::bail!("error_refund_icp returned no result"),
+    }
+}
+
+/// Where a swap participant's ICP ended up after an aborted swap
+#[derive(Debug, Clone)]
+pub struct ParticipantRefund {
+    pub principal: Principal,
+    /// Block height of the `error_refund_icp` transfer, if one was needed
+    pub refund_block: Option<u64>,
+    /// ICP still held in the participant's swap subaccount (0 once refunded)
+    pub escrow_e8s: u64,
+    /// ICP in the participant's own account
+    pub balance_e8s: u64,
+}
+
+impl ParticipantRefund {
+    /// Whether the swap still holds enough of the participant's ICP to refund
+    #[must_use]
+    pub const fn is_refundable(&self) -> bool {
+        is_refundable_escrow(self.escrow_e8s)
+    }
+}
+
+/// `error_refund_icp` only moves an escrow that covers the transfer fee
+const fn is_refundable_escrow(escrow_e8s: u64) -> bool {
+    escrow_e8s > ICP_TRANSFER_FEE
+}
+
+/// Drive the swap from the deployment data to Aborted and verify the participants' refunds
+/// The swap must be Aborted, or with `watch` still Pending/Open (it aborts at its due
+/// timestamp when participation is too low). Finalizes the swap, which returns the
+/// escrowed ICP, then calls `error_refund_icp` for any participant whose swap subaccount
+/// still holds ICP.
+pub async fn abort_swap_default_path(
+    watch: bool,
+    interval: std::time::Duration,
+) -> Result<Vec<ParticipantRefund>> {
+    let deployment_data = app_context().deployment_data()?;
+    let swap_canister = deployment_data.deployed_sns.swap_canister()?;
+    let ledger_canister = app_context().network().ledger_canister;
+    let owner = Principal::from_text(&deployment_data.owner_principal)
+        .context("Failed to parse owner principal")?;
+    let agent = app_context().agent_for_principal(owner).await?;
+
+    let mut lifecycle = get_swap_lifecycle(&agent, swap_canister).await?;
+    if !is_swap_ended(lifecycle) {
+        if !watch {
+            let due = get_sale_parameters(&agent, swap_canister)
+                .await?
+                .map(|params| params.swap_due_timestamp_seconds);
+            anyhow::bail!(
+                "Swap is {}; it aborts at its due timestamp ({}) if participation is too low (use --watch to wait, or advance-time to get there)",
+                lifecycle_name(lifecycle),
+                due.map_or_else(|| "unknown".to_string(), |t| t.to_string())
+            );
+        }
+        print_info(&format!(
+            "Waiting for the swap to abort (checking every {}s)...",
+            interval.as_secs()
+        ));
+        lifecycle = wait_for_swap_end(&agent, swap_canister, interval, |lifecycle| {
+            print_info(&format!("Lifecycle: {}", lifecycle_name(lifecycle)));
+        })
+        .await?;
+    }
+    if lifecycle != 4 {
+        anyhow::bail!(
+            "Swap is {}, not Aborted - participation met the swap minimums",
+            lifecycle_name(lifecycle)
+        );
+    }
+
+    print_info("Swap is Aborted, finalizing...");
+    finalize_swap(&agent, swap_canister).await?;
+
+    let mut refunds = Vec::with_capacity(deployment_data.participants.len());
+    for participant in &deployment_data.participants {
+        let principal = Principal::from_text(&participant.principal).with_context(|| {
+            ValidationError::new(format!(
+                "Invalid participant principal: {}",
+                participant.principal
+            ))
+        })?;
+        let subaccount = Some(generate_participant_subaccount(principal).0.to_vec());
+
+        let mut escrow_e8s =
+            get_icp_ledger_balance(&agent, ledger_canister, swap_canister, subaccount.clone())
+                .await?;
+        let mut refund_block = None;
+        if is_refundable_escrow(escrow_e8s) {
+            print_info(&format!(
+                "Refunding {escrow_e8s} e8s left in the swap for {principal}..."
+            ));
+            refund_block = error_refund_icp(&agent, swap_canister, principal).await?;
+            escrow_e8s =
+                get_icp_ledger_balance(&agent, ledger_canister, swap_canister, subaccount).await?;
+        }
+        let balance_e8s = get_icp_ledger_balance(&agent, ledger_canister, principal, None).await?;
+
+        refunds.push(ParticipantRefund {
+            principal,
+            refund_block,
+            escrow_e8s,
+            balance_e8s,
+        });
+    }
+
+    Ok(refunds)
+}
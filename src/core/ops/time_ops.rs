@@ -0,0 +1,49 @@
+// Replica time control (PocketIC), for fast-forwarding dissolve delays, voting periods and swaps
+
+use anyhow::Result;
+
+use crate::core::utils::pocket_ic::PocketIc;
+
+/// Rounds executed after moving the clock, so timers and heartbeats that became due run
+const TICKS_AFTER_ADVANCE: usize = 5;
+
+/// Replica time before and after an advance
+#[derive(Debug, Clone, Copy)]
+pub struct TimeAdvance {
+    pub before_nanos: u64,
+    pub after_nanos: u64,
+}
+
+/// Current replica time in nanoseconds since the epoch
+pub async fn replica_time_nanos() -> Result<u64> {
+    PocketIc::discover().await?.get_time().await
+}
+
+/// Current replica time in seconds, or the local clock when the network does not run on
+/// PocketIC (the two only differ after `advance-time`)
+pub async fn replica_now_secs() -> u64 {
+    match replica_time_nanos().await {
+        Ok(nanos) => nanos / 1_000_000_000,
+        Err(_) => crate::core::utils::time_format::now_secs(),
+    }
+}
+
+/// Move the replica clock forward by `seconds` and let due timers run
+/// Needs the local network to run on PocketIC (see [`PocketIc::discover`])
+pub async fn advance_time(seconds: u64) -> Result<TimeAdvance> {
+    let pocket_ic = PocketIc::discover().await?;
+    log::info!("Advancing time on {}", pocket_ic.endpoint());
+
+    let before_nanos = pocket_ic.get_time().await?;
+    let target = before_nanos.saturating_add(seconds.saturating_mul(1_000_000_000));
+    pocket_ic.set_time(target).await?;
+    for _ in 0..TICKS_AFTER_ADVANCE {
+        pocket_ic.tick().await?;
+    }
+    let after_nanos = pocket_ic.get_time().await?;
+
+    Ok(TimeAdvance {
+        before_nanos,
+        after_nanos,
+    })
+}
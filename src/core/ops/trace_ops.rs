@@ -0,0 +1,417 @@
+// Follow-the-money: a principal's ICP and SNS transactions, neuron stakes and mint proposals
+// stitched into one timeline
+
+use anyhow::Result;
+use candid::Principal;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+use super::icp_index_ops::{get_account_identifier_transactions, known_icp_accounts};
+use super::ledger_ops::{LedgerHistoryEntry, icp_account_id, sns_treasury_subaccount};
+use super::sns_governance_ops::{
+    ProposalOutcome, list_all_proposals, list_neurons_for_principal, proposal_outcome,
+};
+use super::sns_index_ops::get_account_transactions;
+use crate::core::context::app_context;
+use crate::core::declarations::sns_governance::{Action, ProposalData};
+use crate::core::declarations::sns_index::Account;
+use crate::core::utils::data_output::SnsCreationData;
+use crate::core::utils::network::network_config;
+use crate::core::utils::table::short_id;
+
+/// Default number of transactions read per account
+pub const DEFAULT_TRACE_LIMIT: u64 = 100;
+
+/// Where an event of a trace comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TraceSource {
+    /// A block of the ICP ledger
+    Icp,
+    /// A block of the SNS ledger
+    Sns,
+    /// An SNS proposal minting tokens to the principal
+    Proposal,
+}
+
+impl std::fmt::Display for TraceSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Icp => "ICP",
+            Self::Sns => "SNS",
+            Self::Proposal => "proposal",
+        })
+    }
+}
+
+/// One step of a trace
+#[derive(Debug, Clone, Serialize)]
+pub struct TraceEvent {
+    pub timestamp_seconds: u64,
+    pub source: TraceSource,
+    /// Ledger block or proposal id
+    pub id: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount_e8s: Option<u64>,
+    /// What happened, with known accounts named
+    pub description: String,
+}
+
+/// Where a principal's tokens came from and went, oldest first
+#[derive(Debug, Clone, Serialize)]
+pub struct Trace {
+    pub principal: String,
+    /// `owner`, `participant-N` or the name of an extra identity or delegated principal
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Balances according to the indexes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icp_balance_e8s: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sns_balance_e8s: Option<u64>,
+    /// SNS neurons of the principal (hex ids), whose staking accounts were followed too
+    pub sns_neurons: Vec<String>,
+    pub events: Vec<TraceEvent>,
+    /// Sources that could not be read; the trace is incomplete without them
+    pub warnings: Vec<String>,
+}
+
+/// Trace a principal's money: its ICP account (mints, swap escrow, ICP neuron stakes and
+/// disbursements), its SNS account, the staking accounts of its SNS neurons (the swap's
+/// distribution and disbursements) and the proposals minting SNS tokens to it
+///
+/// `limit` caps the transactions read per account, newest first. Sources that fail are
+/// reported as warnings rather than failing the whole trace.
+pub async fn trace_principal(principal: Principal, limit: u64) -> Result<Trace> {
+    let agent = app_context().anonymous_agent().await?;
+    let data = app_context().try_deployment_data();
+    let mut names = AccountNames::new(principal, data.as_deref());
+
+    let mut trace = Trace {
+        principal: principal.to_text(),
+        label: data
+            .as_deref()
+            .and_then(|data| principal_label(data, principal)),
+        icp_balance_e8s: None,
+        sns_balance_e8s: None,
+        sns_neurons: Vec::new(),
+        events: Vec::new(),
+        warnings: Vec::new(),
+    };
+    let mut seen = HashSet::new();
+
+    // ICP: the ledger names accounts by identifier, so only the principal's own is followed
+    let own_icp_account = icp_account_id(principal, None);
+    match get_account_identifier_transactions(
+        &agent,
+        network_config().icp_index_canister,
+        &own_icp_account,
+        None,
+        limit,
+    )
+    .await
+    {
+        Ok(result) => {
+            trace.icp_balance_e8s = Some(result.balance_e8s);
+            for entry in &result.transactions {
+                if seen.insert((TraceSource::Icp, entry.index)) {
+                    trace
+                        .events
+                        .push(ledger_event(TraceSource::Icp, entry, |a| names.icp(a)));
+                }
+            }
+        }
+        Err(e) => trace
+            .warnings
+            .push(format!("ICP history unavailable: {e:#}")),
+    }
+
+    if let (Ok(index), Ok(governance)) = (
+        app_context().sns_index_canister(),
+        app_context().sns_governance_canister(),
+    ) {
+        let mut accounts = vec![(principal, None)];
+        match list_neurons_for_principal(&agent, governance, principal).await {
+            Ok(neurons) => {
+                for id in neurons.iter().filter_map(|n| n.id.as_ref()) {
+                    let hex_id = hex::encode(&id.id);
+                    names.add_own_neuron(governance, &hex_id);
+                    accounts.push((governance, Some(id.id.clone())));
+                    trace.sns_neurons.push(hex_id);
+                }
+            }
+            Err(e) => trace
+                .warnings
+                .push(format!("SNS neurons unavailable: {e:#}")),
+        }
+
+        for (owner, subaccount) in accounts {
+            let is_own = subaccount.is_none();
+            let account = Account { owner, subaccount };
+            match get_account_transactions(&agent, index, account, None, limit).await {
+                Ok(result) => {
+                    if is_own {
+                        trace.sns_balance_e8s = Some(result.balance_e8s);
+                    }
+                    for entry in &result.transactions {
+                        if seen.insert((TraceSource::Sns, entry.index)) {
+                            trace
+                                .events
+                                .push(ledger_event(TraceSource::Sns, entry, |a| names.sns(a)));
+                        }
+                    }
+                }
+                Err(e) => trace
+                    .warnings
+                    .push(format!("SNS history unavailable: {e:#}")),
+            }
+        }
+
+        match list_all_proposals(&agent, governance).await {
+            Ok(proposals) => {
+                for proposal in &proposals {
+                    trace
+                        .events
+                        .extend(mint_proposal_events(proposal, principal, &names));
+                }
+            }
+            Err(e) => trace
+                .warnings
+                .push(format!("SNS proposals unavailable: {e:#}")),
+        }
+    }
+
+    trace
+        .events
+        .sort_by_key(|e| (e.timestamp_seconds, e.source, e.id));
+    Ok(trace)
+}
+
+/// Name the principal goes by in the deployment data, with its aliases
+pub(super) fn principal_label(data: &SnsCreationData, principal: Principal) -> Option<String> {
+    let text = principal.to_text();
+    let label = data
+        .accounts()
+        .into_iter()
+        .chain(
+            data.identities
+                .iter()
+                .map(|i| (i.name.clone(), i.principal.clone())),
+        )
+        .chain(
+            data.delegated
+                .iter()
+                .map(|d| (d.name.clone(), d.principal.clone())),
+        )
+        .find(|(_, p)| *p == text)
+        .map(|(label, _)| label);
+    match label {
+        Some(label) => Some(data.with_aliases(&label, &text)),
+        None => data.aliases_of(&text),
+    }
+}
+
+/// Names of the accounts a trace runs into, keyed as the history entries print them
+struct AccountNames {
+    /// ICP account identifiers
+    icp: HashMap<String, String>,
+    /// SNS accounts, `principal` or `principal.subaccount`
+    sns: HashMap<String, String>,
+    /// Governance canister, to name neuron staking accounts of other principals
+    governance: Option<String>,
+}
+
+impl AccountNames {
+    fn new(principal: Principal, data: Option<&SnsCreationData>) -> Self {
+        let network = network_config();
+        let mut icp = HashMap::new();
+        let mut sns = HashMap::new();
+        let mut governance = None;
+
+        if let Some(data) = data {
+            // Aliased principals first, so a known account's label (with its aliases) replaces them
+            let principals = data
+                .aliases
+                .iter()
+                .filter_map(|a| Some((data.aliases_of(&a.principal)?, a.principal.clone())))
+                .chain(
+                    data.accounts()
+                        .into_iter()
+                        .chain(
+                            data.identities
+                                .iter()
+                                .map(|i| (i.name.clone(), i.principal.clone())),
+                        )
+                        .map(|(label, text)| (data.with_aliases(&label, &text), text)),
+                );
+            for (label, text) in principals {
+                if let Ok(p) = Principal::from_text(&text) {
+                    icp.insert(icp_account_id(p, None), label.clone());
+                }
+                sns.insert(text, label);
+            }
+            for (role, id) in data.deployed_sns.canister_ids() {
+                sns.insert(id.to_string(), format!("SNS {role}"));
+                if let Ok(p) = Principal::from_text(id) {
+                    icp.insert(icp_account_id(p, None), format!("SNS {role}"));
+                }
+                if role == "governance" {
+                    governance = Some(id.to_string());
+                    if let Ok(p) = Principal::from_text(id) {
+                        sns.insert(
+                            format!("{id}.{}", hex::encode(sns_treasury_subaccount(p).0)),
+                            "SNS treasury".to_string(),
+                        );
+                    }
+                }
+            }
+        }
+        icp.insert(
+            icp_account_id(network.governance_canister, None),
+            "NNS governance".to_string(),
+        );
+
+        let swap = data.and_then(|data| data.deployed_sns.swap_canister().ok());
+        icp.extend(known_icp_accounts(principal, swap));
+        sns.insert(principal.to_text(), "own account".to_string());
+
+        Self {
+            icp,
+            sns,
+            governance,
+        }
+    }
+
+    fn add_own_neuron(&mut self, governance: Principal, hex_id: &str) {
+        self.sns.insert(
+            format!("{governance}.{hex_id}"),
+            format!("own SNS neuron {}", short_id(hex_id)),
+        );
+    }
+
+    fn icp(&self, account: &str) -> String {
+        self.icp
+            .get(account)
+            .cloned()
+            .unwrap_or_else(|| short_id(account))
+    }
+
+    fn sns(&self, account: &str) -> String {
+        if let Some(name) = self.sns.get(account) {
+            return name.clone();
+        }
+        match account.split_once('.') {
+            Some((owner, subaccount)) if Some(owner) == self.governance.as_deref() => {
+                format!("SNS neuron {}", short_id(subaccount))
+            }
+            Some((owner, subaccount)) => format!(
+                "{}.{}",
+                self.sns
+                    .get(owner)
+                    .cloned()
+                    .unwrap_or_else(|| short_id(owner)),
+                short_id(subaccount)
+            ),
+            None => short_id(account),
+        }
+    }
+}
+
+fn format_amount(amount_e8s: u64, source: TraceSource) -> String {
+    format!("{:.8} {}", amount_e8s as f64 / 100_000_000.0, source)
+}
+
+fn ledger_event(
+    source: TraceSource,
+    entry: &LedgerHistoryEntry,
+    name: impl Fn(&str) -> String,
+) -> TraceEvent {
+    let account = |a: &Option<String>| a.as_deref().map_or_else(|| "?".to_string(), &name);
+    let amount = format_amount(entry.amount_e8s, source);
+    let description = match entry.kind.as_str() {
+        "mint" => format!("{amount} minted to {}", account(&entry.to)),
+        "burn" => format!("{amount} burned from {}", account(&entry.from)),
+        "approve" => format!(
+            "{} approved {} to spend",
+            account(&entry.from),
+            account(&entry.to)
+        ),
+        _ => format!(
+            "{amount} from {} to {}",
+            account(&entry.from),
+            account(&entry.to)
+        ),
+    };
+    TraceEvent {
+        timestamp_seconds: entry.timestamp_nanos / 1_000_000_000,
+        source,
+        id: entry.index,
+        amount_e8s: (entry.kind != "approve").then_some(entry.amount_e8s),
+        description,
+    }
+}
+
+/// Submission and outcome of a proposal minting SNS tokens to `principal`, if it is one
+fn mint_proposal_events(
+    proposal: &ProposalData,
+    principal: Principal,
+    names: &AccountNames,
+) -> Vec<TraceEvent> {
+    let Some(Action::MintSnsTokens(mint)) =
+        proposal.proposal.as_ref().and_then(|p| p.action.as_ref())
+    else {
+        return Vec::new();
+    };
+    if mint.to_principal != Some(principal) {
+        return Vec::new();
+    }
+    let id = proposal.id.as_ref().map_or(0, |id| id.id);
+    let amount_e8s = mint.amount_e8s.unwrap_or(0);
+    let to = match mint.to_subaccount.as_ref() {
+        Some(sub) if sub.subaccount.iter().any(|b| *b != 0) => {
+            names.sns(&format!("{principal}.{}", hex::encode(&sub.subaccount)))
+        }
+        _ => names.sns(&principal.to_text()),
+    };
+
+    let mut events = vec![TraceEvent {
+        timestamp_seconds: proposal.proposal_creation_timestamp_seconds,
+        source: TraceSource::Proposal,
+        id,
+        amount_e8s: Some(amount_e8s),
+        description: format!(
+            "Proposal {id} submitted to mint {} to {to}",
+            format_amount(amount_e8s, TraceSource::Sns)
+        ),
+    }];
+    let outcome = match proposal_outcome(proposal) {
+        Some(ProposalOutcome::Executed { timestamp_seconds }) => {
+            Some((timestamp_seconds, format!("Proposal {id} executed")))
+        }
+        Some(ProposalOutcome::Failed {
+            timestamp_seconds,
+            reason,
+        }) => Some((
+            timestamp_seconds,
+            format!(
+                "Proposal {id} failed{}",
+                reason.map(|r| format!(": {r}")).unwrap_or_default()
+            ),
+        )),
+        Some(ProposalOutcome::Rejected { timestamp_seconds }) => Some((
+            timestamp_seconds,
+            format!("Proposal {id} rejected; nothing minted"),
+        )),
+        None => None,
+    };
+    if let Some((timestamp_seconds, description)) = outcome {
+        events.push(TraceEvent {
+            timestamp_seconds,
+            source: TraceSource::Proposal,
+            id,
+            amount_e8s: None,
+            description,
+        });
+    }
+    events
+}
@@ -0,0 +1,418 @@
+// Compensating actions for the reversible calls recorded in the audit log
+
+use anyhow::{Context, Result};
+use candid::{Decode, Principal};
+use std::collections::HashSet;
+
+use super::audit_log::{AuditEntry, AuditResult};
+use super::governance_ops::{
+    remove_hotkey_from_icp_neuron, start_dissolving_icp_neuron, stop_dissolving_icp_neuron,
+};
+use super::ledger_ops::{transfer_icp, transfer_sns_tokens};
+use super::sns_governance_ops::{
+    remove_neuron_permissions, sns_permission_labels, start_dissolving_sns_neuron,
+    stop_dissolving_sns_neuron,
+};
+use crate::core::context::app_context;
+use crate::core::declarations::{icp_governance, sns_governance, sns_ledger};
+use crate::core::utils::network::network_config;
+
+/// Ledger of a transfer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Token {
+    Icp,
+    Sns,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Icp => "ICP",
+            Self::Sns => "SNS",
+        })
+    }
+}
+
+/// The call that reverts a logged one, signed by `signer`
+#[derive(Debug, Clone)]
+pub enum UndoAction {
+    /// Revert `AddNeuronPermissions`
+    RemoveSnsPermissions {
+        signer: Principal,
+        neuron: Vec<u8>,
+        principal: Principal,
+        permissions: Vec<i32>,
+    },
+    /// Revert starting or stopping to dissolve an SNS neuron
+    SetSnsDissolving {
+        signer: Principal,
+        neuron: Vec<u8>,
+        start: bool,
+    },
+    /// Revert adding a hotkey to an ICP neuron
+    RemoveIcpHotkey {
+        signer: Principal,
+        neuron_id: u64,
+        hotkey: Principal,
+    },
+    /// Revert starting or stopping to dissolve an ICP neuron
+    SetIcpDissolving {
+        signer: Principal,
+        neuron_id: u64,
+        start: bool,
+    },
+    /// Send tokens back; to the minting account this burns them
+    Transfer {
+        token: Token,
+        from: Principal,
+        to: Principal,
+        amount_e8s: u64,
+    },
+}
+
+/// A suggested undo of an audit log entry
+#[derive(Debug, Clone)]
+pub struct Undo {
+    /// Position of the entry in the audit log, from 1 (as `history` numbers them)
+    pub entry: usize,
+    pub description: String,
+    /// The local_sns command doing the same, where there is one
+    pub command: Option<String>,
+    pub action: UndoAction,
+}
+
+/// The canisters whose calls can be reverted
+struct Canisters {
+    sns_governance: Option<Principal>,
+    sns_ledger: Option<Principal>,
+    nns_governance: Principal,
+    icp_ledger: Principal,
+}
+
+impl Canisters {
+    fn current() -> Self {
+        let network = network_config();
+        Self {
+            sns_governance: app_context().sns_governance_canister().ok(),
+            sns_ledger: app_context().sns_ledger_canister().ok(),
+            nns_governance: network.governance_canister,
+            icp_ledger: network.ledger_canister,
+        }
+    }
+}
+
+/// Undos of every reversible entry that has not been undone yet, most recent first
+///
+/// Entries made by `undo` itself are not offered again.
+#[must_use]
+pub fn undo_suggestions(entries: &[AuditEntry]) -> Vec<Undo> {
+    let canisters = Canisters::current();
+    let undone = undone_entries(entries);
+    entries
+        .iter()
+        .enumerate()
+        .rev()
+        .filter(|(index, _)| !undone.contains(&(index + 1)))
+        .filter_map(|(index, entry)| undo_of(index + 1, entry, &canisters))
+        .collect()
+}
+
+/// The undo of entry `number` (from 1)
+pub fn undo_entry(entries: &[AuditEntry], number: usize) -> Result<Undo> {
+    let entry = number
+        .checked_sub(1)
+        .and_then(|index| entries.get(index))
+        .with_context(|| {
+            format!(
+                "Invalid entry {number}: the audit log has {} entries",
+                entries.len()
+            )
+        })?;
+    undo_of(number, entry, &Canisters::current()).with_context(|| {
+        format!(
+            "Entry {number} ({} on {}) cannot be undone",
+            entry.method, entry.canister
+        )
+    })
+}
+
+/// Entries already reverted by a successful `undo <n> --execute`
+fn undone_entries(entries: &[AuditEntry]) -> HashSet<usize> {
+    entries
+        .iter()
+        .filter(|entry| entry.result == AuditResult::Ok)
+        .filter_map(|entry| {
+            let mut words = entry.command.split_whitespace();
+            (words.next() == Some("undo"))
+                .then(|| words.find_map(|word| word.parse().ok()))
+                .flatten()
+        })
+        .collect()
+}
+
+fn undo_of(number: usize, entry: &AuditEntry, canisters: &Canisters) -> Option<Undo> {
+    if entry.result != AuditResult::Ok || entry.command.starts_with("undo") {
+        return None;
+    }
+    let arg = hex::decode(entry.arg.as_deref()?).ok()?;
+    let signer = Principal::from_text(&entry.identity).ok()?;
+    let canister = Principal::from_text(&entry.canister).ok()?;
+
+    let (description, command, action) = match entry.method.as_str() {
+        "manage_neuron" if Some(canister) == canisters.sns_governance => {
+            sns_neuron_undo(signer, &arg, entry, canister)?
+        }
+        "manage_neuron" if canister == canisters.nns_governance => icp_neuron_undo(signer, &arg)?,
+        "icrc1_transfer" if canister == canisters.icp_ledger => {
+            transfer_undo(Token::Icp, signer, &arg)?
+        }
+        "icrc1_transfer" if Some(canister) == canisters.sns_ledger => {
+            transfer_undo(Token::Sns, signer, &arg)?
+        }
+        _ => return None,
+    };
+    Some(Undo {
+        entry: number,
+        description,
+        command,
+        action,
+    })
+}
+
+type Reversal = (String, Option<String>, UndoAction);
+
+fn sns_neuron_undo(
+    signer: Principal,
+    arg: &[u8],
+    entry: &AuditEntry,
+    governance: Principal,
+) -> Option<Reversal> {
+    use sns_governance::{Action, Command, Operation};
+
+    let request = Decode!(arg, sns_governance::ManageNeuron).ok()?;
+    let neuron = request.subaccount;
+    let neuron_hex = hex::encode(&neuron);
+    match request.command? {
+        Command::AddNeuronPermissions(add) => {
+            let principal = add.principal_id?;
+            let permissions = add.permissions_to_add?.permissions;
+            Some((
+                format!(
+                    "Remove {} from {principal} on SNS neuron {neuron_hex}",
+                    sns_permission_labels(&permissions)
+                ),
+                None,
+                UndoAction::RemoveSnsPermissions {
+                    signer,
+                    neuron,
+                    principal,
+                    permissions,
+                },
+            ))
+        }
+        Command::Configure(configure) => {
+            let start = match configure.operation? {
+                Operation::StartDissolving {} => false,
+                Operation::StopDissolving {} => true,
+                _ => return None,
+            };
+            Some((
+                format!(
+                    "{} dissolving SNS neuron {neuron_hex}",
+                    if start { "Start" } else { "Stop" }
+                ),
+                Some(format!(
+                    "manage-sns-dissolving {signer} {} {neuron_hex}",
+                    if start { "start" } else { "stop" }
+                )),
+                UndoAction::SetSnsDissolving {
+                    signer,
+                    neuron,
+                    start,
+                },
+            ))
+        }
+        Command::MakeProposal(proposal) => {
+            let Action::MintSnsTokens(mint) = proposal.action? else {
+                return None;
+            };
+            if mint.to_subaccount.is_some() {
+                return None;
+            }
+            let from = mint.to_principal?;
+            let amount_e8s = mint.amount_e8s?;
+            let proposal = entry
+                .proposal_id
+                .map_or_else(String::new, |id| format!(" by proposal {id}"));
+            Some((
+                format!(
+                    "Burn the {amount_e8s} e8s minted to {from}{proposal}: send them to SNS governance (the minting account)"
+                ),
+                None,
+                UndoAction::Transfer {
+                    token: Token::Sns,
+                    from,
+                    to: governance,
+                    amount_e8s,
+                },
+            ))
+        }
+        _ => None,
+    }
+}
+
+fn icp_neuron_undo(signer: Principal, arg: &[u8]) -> Option<Reversal> {
+    use icp_governance::{ManageNeuronCommandRequest, NeuronIdOrSubaccount, Operation};
+
+    let request = Decode!(arg, icp_governance::ManageNeuronRequest).ok()?;
+    let neuron_id = match (request.id, request.neuron_id_or_subaccount) {
+        (Some(id), _) | (None, Some(NeuronIdOrSubaccount::NeuronId(id))) => id.id,
+        _ => return None,
+    };
+    let ManageNeuronCommandRequest::Configure(configure) = request.command? else {
+        return None;
+    };
+    match configure.operation? {
+        Operation::AddHotKey(add) => {
+            let hotkey = add.new_hot_key?;
+            Some((
+                format!("Remove hotkey {hotkey} from ICP neuron {neuron_id}"),
+                None,
+                UndoAction::RemoveIcpHotkey {
+                    signer,
+                    neuron_id,
+                    hotkey,
+                },
+            ))
+        }
+        operation @ (Operation::StartDissolving {} | Operation::StopDissolving {}) => {
+            let start = matches!(operation, Operation::StopDissolving {});
+            Some((
+                format!(
+                    "{} dissolving ICP neuron {neuron_id}",
+                    if start { "Start" } else { "Stop" }
+                ),
+                Some(format!(
+                    "manage-icp-dissolving {signer} {} {neuron_id}",
+                    if start { "start" } else { "stop" }
+                )),
+                UndoAction::SetIcpDissolving {
+                    signer,
+                    neuron_id,
+                    start,
+                },
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Transfers between default accounts are sent back by the receiver
+fn transfer_undo(token: Token, signer: Principal, arg: &[u8]) -> Option<Reversal> {
+    let transfer = Decode!(arg, sns_ledger::TransferArg).ok()?;
+    if transfer.from_subaccount.is_some() || transfer.to.subaccount.is_some() {
+        return None;
+    }
+    let amount_e8s = u64::try_from(transfer.amount.0).ok()?;
+    let from = transfer.to.owner;
+    Some((
+        format!("Send {amount_e8s} e8s {token} back from {from} to {signer}"),
+        None,
+        UndoAction::Transfer {
+            token,
+            from,
+            to: signer,
+            amount_e8s,
+        },
+    ))
+}
+
+/// Make the compensating call, signed by the identity the undo needs
+pub async fn execute_undo(undo: &Undo) -> Result<()> {
+    let signer = match &undo.action {
+        UndoAction::RemoveSnsPermissions { signer, .. }
+        | UndoAction::SetSnsDissolving { signer, .. }
+        | UndoAction::RemoveIcpHotkey { signer, .. }
+        | UndoAction::SetIcpDissolving { signer, .. } => *signer,
+        UndoAction::Transfer { from, .. } => *from,
+    };
+    let agent = app_context()
+        .agent_for_principal(signer)
+        .await
+        .with_context(|| format!("Cannot sign as {signer}"))?;
+
+    match &undo.action {
+        UndoAction::RemoveSnsPermissions {
+            neuron,
+            principal,
+            permissions,
+            ..
+        } => {
+            remove_neuron_permissions(
+                &agent,
+                app_context().sns_governance_canister()?,
+                neuron.clone(),
+                *principal,
+                permissions.clone(),
+            )
+            .await
+        }
+        UndoAction::SetSnsDissolving { neuron, start, .. } => {
+            let governance = app_context().sns_governance_canister()?;
+            if *start {
+                start_dissolving_sns_neuron(&agent, governance, neuron.clone()).await
+            } else {
+                stop_dissolving_sns_neuron(&agent, governance, neuron.clone()).await
+            }
+        }
+        UndoAction::RemoveIcpHotkey {
+            neuron_id, hotkey, ..
+        } => {
+            remove_hotkey_from_icp_neuron(
+                &agent,
+                network_config().governance_canister,
+                *neuron_id,
+                *hotkey,
+            )
+            .await
+        }
+        UndoAction::SetIcpDissolving {
+            neuron_id, start, ..
+        } => {
+            let governance = network_config().governance_canister;
+            if *start {
+                start_dissolving_icp_neuron(&agent, governance, *neuron_id).await
+            } else {
+                stop_dissolving_icp_neuron(&agent, governance, *neuron_id).await
+            }
+        }
+        UndoAction::Transfer {
+            token,
+            to,
+            amount_e8s,
+            ..
+        } => match token {
+            Token::Icp => {
+                transfer_icp(
+                    &agent,
+                    network_config().ledger_canister,
+                    *to,
+                    *amount_e8s,
+                    None,
+                )
+                .await
+            }
+            Token::Sns => {
+                transfer_sns_tokens(
+                    &agent,
+                    app_context().sns_ledger_canister()?,
+                    *to,
+                    *amount_e8s,
+                    None,
+                )
+                .await
+            }
+        }
+        .map(|_| ()),
+    }
+}
@@ -0,0 +1,586 @@
+// Cross-check the deployment data file against the live replica
+
+use anyhow::{Context, Result};
+use candid::Principal;
+use serde::Serialize;
+use std::path::PathBuf;
+
+use super::governance_ops::{get_icp_neuron, get_nns_proposal_info};
+use super::identity::{load_dfx_identity, load_identity_from_seed_file};
+use super::ledger_ops::{get_sns_ledger_balance, sns_treasury_subaccount};
+use super::sns_governance_ops::list_neurons_for_principal;
+use super::sns_root_ops::get_sns_canister_info;
+use super::snsw_ops::{deployment_mismatches, list_deployed_snses};
+use crate::core::context::app_context;
+use crate::core::declarations::sns_governance::{DissolveState, Neuron};
+use crate::core::utils::data_output::{self, DeployedSnsData, SnsCreationData};
+use crate::core::utils::network::network_config;
+use crate::core::utils::time_format::now_secs;
+use crate::core::utils::{print_info, print_success, print_warning};
+use crate::init::sns_config::SnsConfig;
+
+/// Outcome of one check
+#[derive(Debug, Clone, Serialize)]
+pub struct Check {
+    /// What was checked, e.g. `participant 2` or `ledger canister`
+    pub subject: String,
+    pub ok: bool,
+    pub detail: String,
+    /// Whether `--repair` fixed the data file for this check
+    pub repaired: bool,
+    /// How to fix a failed check, if there is a known remedy
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+impl Check {
+    pub(crate) fn ok(subject: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            subject: subject.into(),
+            ok: true,
+            detail: detail.into(),
+            repaired: false,
+            hint: None,
+        }
+    }
+
+    pub(crate) fn failed(subject: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            subject: subject.into(),
+            ok: false,
+            detail: detail.into(),
+            repaired: false,
+            hint: None,
+        }
+    }
+
+    /// Attach a remediation hint, shown when the check failed
+    #[must_use]
+    pub(crate) fn with_hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+}
+
+/// Verify every field of the deployment data against the replica
+///
+/// Checks the SNS canister IDs against SNS-W and the replica, the owner against the dfx
+/// identity, the ICP neuron and NNS proposal against NNS governance, and each participant
+/// and extra identity against its seed file and (participants) its SNS neurons. With
+/// `repair`, canister IDs are taken from SNS-W and principals from the seed files, and the
+/// file is rewritten; the other discrepancies cannot be repaired.
+pub async fn verify_deployment(repair: bool) -> Result<Vec<Check>> {
+    let mut data = data_output::read_data()?;
+    let agent = app_context().anonymous_agent().await?;
+    let mut checks = Vec::new();
+    let mut changed = false;
+
+    // Canister IDs against SNS-W
+    let snses = list_deployed_snses(&agent, network_config().snsw_canister).await?;
+    let recorded_root = data.deployed_sns.root_canister().ok();
+    let recorded_governance = data.deployed_sns.governance_canister().ok();
+    let entry = snses.iter().find(|sns| {
+        (recorded_root.is_some() && sns.root_canister_id == recorded_root)
+            || (recorded_governance.is_some() && sns.governance_canister_id == recorded_governance)
+    });
+    match entry {
+        None => checks.push(Check::failed(
+            "SNS-W",
+            "no SNS on SNS-W has the recorded root or governance canister (replica restarted?)",
+        )),
+        Some(entry) => {
+            let mismatches = deployment_mismatches(&data.deployed_sns, entry);
+            if mismatches.is_empty() {
+                checks.push(Check::ok("SNS-W", "canister IDs match"));
+            } else {
+                let mut check = Check::failed(
+                    "SNS-W",
+                    format!("{} canister ID differs from SNS-W", mismatches.join(", ")),
+                );
+                if repair {
+                    data.deployed_sns = DeployedSnsData::from(entry);
+                    check.repaired = true;
+                    changed = true;
+                }
+                checks.push(check);
+            }
+        }
+    }
+
+    // Canisters on the replica
+    match get_sns_canister_info(&agent, &data.deployed_sns).await {
+        Ok((canisters, _root_error)) => {
+            for canister in canisters {
+                let subject = format!("{} canister", canister.role);
+                checks.push(match (canister.exists, canister.module_hash) {
+                    (true, Some(hash)) => Check::ok(
+                        subject,
+                        format!(
+                            "{} installed (module {})",
+                            canister.canister_id,
+                            short(&hash)
+                        ),
+                    ),
+                    _ => Check::failed(
+                        subject,
+                        format!("{} has no module installed", canister.canister_id),
+                    ),
+                });
+            }
+        }
+        Err(e) => checks.push(Check::failed("SNS canisters", format!("{e:#}"))),
+    }
+
+    checks.push(check_owner(&data));
+    checks.push(check_icp_neuron(&data).await);
+    checks.push(check_proposal(&agent, &data).await);
+
+    // Participants: seed file, principal and SNS neurons
+    let governance = data.deployed_sns.governance_canister().ok();
+    for (index, participant) in data.participants.iter_mut().enumerate() {
+        let subject = format!("participant {}", index + 1);
+        let principal = match seed_principal(&participant.seed_file) {
+            Ok(principal) => principal,
+            Err(e) => {
+                checks.push(Check::failed(subject, format!("{e:#}")));
+                continue;
+            }
+        };
+        if principal.to_text() != participant.principal {
+            let mut check = Check::failed(
+                subject.as_str(),
+                format!(
+                    "seed file belongs to {} but {} is recorded",
+                    principal, participant.principal
+                ),
+            );
+            if repair {
+                participant.principal = principal.to_text();
+                check.repaired = true;
+                changed = true;
+            }
+            checks.push(check);
+        }
+
+        let Some(governance) = governance else {
+            continue;
+        };
+        checks.push(
+            match list_neurons_for_principal(&agent, governance, principal).await {
+                Ok(neurons) if neurons.is_empty() => Check::failed(
+                    subject,
+                    format!(
+                        "{} has no SNS neurons (disbursed outside the tool?)",
+                        principal
+                    ),
+                ),
+                Ok(neurons) => Check::ok(
+                    subject,
+                    format!("{} has {} SNS neurons", principal, neurons.len()),
+                ),
+                Err(e) => Check::failed(subject, format!("{e:#}")),
+            },
+        );
+    }
+
+    // Extra identities: seed file and principal
+    for identity in &mut data.identities {
+        let subject = format!("identity {}", identity.name);
+        match seed_principal(&identity.seed_file) {
+            Ok(principal) if principal.to_text() == identity.principal => {
+                checks.push(Check::ok(
+                    subject,
+                    format!("seed file matches {}", principal),
+                ));
+            }
+            Ok(principal) => {
+                let mut check = Check::failed(
+                    subject,
+                    format!(
+                        "seed file belongs to {} but {} is recorded",
+                        principal, identity.principal
+                    ),
+                );
+                if repair {
+                    identity.principal = principal.to_text();
+                    check.repaired = true;
+                    changed = true;
+                }
+                checks.push(check);
+            }
+            Err(e) => checks.push(Check::failed(subject, format!("{e:#}"))),
+        }
+    }
+
+    if changed {
+        data_output::write_data(&data).context("Failed to write repaired deployment data")?;
+    }
+    Ok(checks)
+}
+
+/// Print checks one per line: passed, repaired or failed
+pub fn print_checks(checks: &[Check]) {
+    for check in checks {
+        let line = format!("{}: {}", check.subject, check.detail);
+        if check.ok {
+            print_success(&line);
+        } else if check.repaired {
+            print_info(&format!("{line} (repaired)"));
+        } else {
+            print_warning(&line);
+        }
+    }
+}
+
+/// SNS tokens a swap participant's neuron basket should hold after finalization
+#[derive(Debug, Clone, Serialize)]
+pub struct ExpectedBasket {
+    /// 1-based participant number
+    pub participant: usize,
+    pub contribution_icp_e8s: u64,
+    /// Share of `distribution.swap_e8s`, proportional to the contribution
+    pub sns_e8s: u64,
+    /// Total stake of the basket: the share minus one transfer fee per neuron
+    pub stake_e8s: u64,
+    /// Dissolve delay of each basket neuron, shortest first
+    pub dissolve_delays_seconds: Vec<u64>,
+}
+
+/// Neuron baskets the swap creates for participants contributing `contributions` ICP
+/// Assumes no Neurons' Fund participation, as on a local replica.
+#[must_use]
+pub fn expected_baskets(config: &SnsConfig, contributions: &[u64]) -> Vec<ExpectedBasket> {
+    let total_icp: u128 = contributions.iter().map(|c| u128::from(*c)).sum();
+    let count = config.swap.neuron_basket_count;
+    contributions
+        .iter()
+        .enumerate()
+        .map(|(index, contribution)| {
+            let sns_e8s = (u128::from(config.distribution.swap_e8s) * u128::from(*contribution))
+                .checked_div(total_icp)
+                .map_or(0, |share| u64::try_from(share).unwrap_or(u64::MAX));
+            ExpectedBasket {
+                participant: index + 1,
+                contribution_icp_e8s: *contribution,
+                sns_e8s,
+                stake_e8s: sns_e8s.saturating_sub(count * config.token.transaction_fee_e8s),
+                dissolve_delays_seconds: (0..count)
+                    .map(|i| i * config.swap.neuron_basket_dissolve_delay_interval_seconds)
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+/// Compare the token distribution after swap finalization with what `config` prescribes
+///
+/// Checks the developer neuron's stake and dissolve delay, the treasury balance, the tokens
+/// left in the swap canister and each participant's neuron basket (neuron count, total stake
+/// and dissolve delays). Stakes may differ from the expectation by the ledger fees of the
+/// basket transfers, to allow for rounding.
+pub async fn verify_distribution(config: &SnsConfig) -> Result<Vec<Check>> {
+    let data = data_output::read_data()?;
+    let agent = app_context().anonymous_agent().await?;
+    let governance = data.deployed_sns.governance_canister()?;
+    let ledger = data.deployed_sns.ledger_canister()?;
+    let swap = data.deployed_sns.swap_canister()?;
+    let fee = config.token.transaction_fee_e8s;
+    let mut checks = Vec::new();
+
+    // Developer neuron, minted at genesis with its vesting period
+    let owner = Principal::from_text(&data.owner_principal).context("Invalid owner principal")?;
+    let distribution = &config.distribution;
+    checks.push(
+        match list_neurons_for_principal(&agent, governance, owner).await {
+            Ok(neurons) => {
+                // Extra developer neurons of the owner that vest are listed too
+                let developer: Vec<&Neuron> = neurons
+                    .iter()
+                    .filter(|n| n.vesting_period_seconds.is_some())
+                    .collect();
+                let expected = 1 + distribution
+                    .extra_developer_neurons
+                    .iter()
+                    .filter(|n| n.controller(owner) == owner && n.vesting_period_seconds > 0)
+                    .count();
+                let main = developer.iter().find(|n| {
+                    n.vesting_period_seconds
+                        == Some(distribution.developer_neuron_vesting_period_seconds)
+                        && n.cached_neuron_stake_e8s == distribution.developer_neuron_stake_e8s
+                });
+                match main.or(developer.first()) {
+                    Some(neuron) if developer.len() == expected => {
+                        let delay = dissolve_delay(neuron);
+                        let detail = format!(
+                            "stake {} e8s (expected {}), dissolve delay {}s (expected {}s)",
+                            neuron.cached_neuron_stake_e8s,
+                            distribution.developer_neuron_stake_e8s,
+                            delay,
+                            distribution.developer_neuron_dissolve_delay_seconds
+                        );
+                        if neuron.cached_neuron_stake_e8s == distribution.developer_neuron_stake_e8s
+                            && delay == distribution.developer_neuron_dissolve_delay_seconds
+                        {
+                            Check::ok("developer neuron", detail)
+                        } else {
+                            Check::failed("developer neuron", detail)
+                        }
+                    }
+                    _ => Check::failed(
+                        "developer neuron",
+                        format!(
+                            "owner {} has {} vesting neurons, expected {}",
+                            owner,
+                            developer.len(),
+                            expected
+                        ),
+                    ),
+                }
+            }
+            Err(e) => Check::failed("developer neuron", format!("{e:#}")),
+        },
+    );
+    for (i, extra) in distribution.extra_developer_neurons.iter().enumerate() {
+        let subject = format!("developer neuron {}", i + 2);
+        let controller = extra.controller(owner);
+        let vesting = (extra.vesting_period_seconds > 0).then_some(extra.vesting_period_seconds);
+        let detail = format!(
+            "{} with stake {} e8s, dissolve delay {}s, vesting {}s",
+            controller, extra.stake_e8s, extra.dissolve_delay_seconds, extra.vesting_period_seconds
+        );
+        checks.push(
+            match list_neurons_for_principal(&agent, governance, controller).await {
+                Ok(neurons)
+                    if neurons.iter().any(|n| {
+                        n.cached_neuron_stake_e8s == extra.stake_e8s
+                            && dissolve_delay(n) == extra.dissolve_delay_seconds
+                            && n.vesting_period_seconds == vesting
+                    }) =>
+                {
+                    Check::ok(subject, detail)
+                }
+                Ok(_) => Check::failed(subject, format!("no neuron of {detail}")),
+                Err(e) => Check::failed(subject, format!("{e:#}")),
+            },
+        );
+    }
+
+    // Treasury, held by governance in its token distribution subaccount
+    let treasury_subaccount = sns_treasury_subaccount(governance).0.to_vec();
+    checks.push(
+        match get_sns_ledger_balance(&agent, ledger, governance, Some(treasury_subaccount)).await {
+            Ok(balance) if balance == distribution.treasury_e8s => {
+                Check::ok("treasury", format!("{balance} e8s"))
+            }
+            Ok(balance) => Check::failed(
+                "treasury",
+                format!(
+                    "{balance} e8s, expected {} (spent by a proposal since?)",
+                    distribution.treasury_e8s
+                ),
+            ),
+            Err(e) => Check::failed("treasury", format!("{e:#}")),
+        },
+    );
+
+    // Participant baskets; the swap hands out all of distribution.swap_e8s
+    let contributions: Vec<u64> = data
+        .participants
+        .iter()
+        .enumerate()
+        .map(|(index, p)| {
+            p.contribution_icp_e8s
+                .unwrap_or_else(|| config.participant_contribution_e8s(index + 1))
+        })
+        .collect();
+    let baskets = expected_baskets(config, &contributions);
+    let distributed: u64 = baskets.iter().map(|b| b.sns_e8s).sum();
+    let dust = distribution.swap_e8s.saturating_sub(distributed);
+    checks.push(
+        match get_sns_ledger_balance(&agent, ledger, swap, None).await {
+            Ok(balance) if balance <= dust => Check::ok(
+                "swap canister",
+                format!("{balance} e8s left after distributing {distributed} e8s"),
+            ),
+            Ok(balance) => Check::failed(
+                "swap canister",
+                format!("{balance} e8s left, expected at most {dust} (swap not finalized?)"),
+            )
+            .with_hint("Run finalize-swap, then verify-distribution again"),
+            Err(e) => Check::failed("swap canister", format!("{e:#}")),
+        },
+    );
+
+    for (basket, participant) in baskets.iter().zip(&data.participants) {
+        let subject = format!("participant {}", basket.participant);
+        let principal = match Principal::from_text(&participant.principal) {
+            Ok(principal) => principal,
+            Err(e) => {
+                checks.push(Check::failed(subject, format!("invalid principal: {e}")));
+                continue;
+            }
+        };
+        let neurons = match list_neurons_for_principal(&agent, governance, principal).await {
+            Ok(neurons) => neurons,
+            Err(e) => {
+                checks.push(Check::failed(subject, format!("{e:#}")));
+                continue;
+            }
+        };
+        checks.push(check_basket(subject, basket, &neurons, fee));
+    }
+
+    Ok(checks)
+}
+
+fn check_basket(subject: String, basket: &ExpectedBasket, neurons: &[Neuron], fee: u64) -> Check {
+    let expected_count = basket.dissolve_delays_seconds.len();
+    if neurons.len() != expected_count {
+        return Check::failed(
+            subject,
+            format!(
+                "{} SNS neurons, expected a basket of {}",
+                neurons.len(),
+                expected_count
+            ),
+        );
+    }
+
+    let stake: u64 = neurons.iter().map(|n| n.cached_neuron_stake_e8s).sum();
+    let tolerance = expected_count as u64 * fee;
+    let mut delays: Vec<u64> = neurons.iter().map(dissolve_delay).collect();
+    delays.sort_unstable();
+
+    let mut problems = Vec::new();
+    if stake.abs_diff(basket.stake_e8s) > tolerance {
+        problems.push(format!(
+            "stake {} e8s, expected {} for {} ICP e8s",
+            stake, basket.stake_e8s, basket.contribution_icp_e8s
+        ));
+    }
+    if delays != basket.dissolve_delays_seconds {
+        problems.push(format!(
+            "dissolve delays {:?}s, expected {:?}s",
+            delays, basket.dissolve_delays_seconds
+        ));
+    }
+    if problems.is_empty() {
+        Check::ok(
+            subject,
+            format!(
+                "{} neurons staking {} e8s for {} ICP e8s",
+                neurons.len(),
+                stake,
+                basket.contribution_icp_e8s
+            ),
+        )
+    } else {
+        Check::failed(subject, problems.join("; "))
+    }
+}
+
+/// Current dissolve delay of a neuron, counting down for a dissolving one
+fn dissolve_delay(neuron: &Neuron) -> u64 {
+    match &neuron.dissolve_state {
+        Some(DissolveState::DissolveDelaySeconds(seconds)) => *seconds,
+        Some(DissolveState::WhenDissolvedTimestampSeconds(when)) => when.saturating_sub(now_secs()),
+        None => 0,
+    }
+}
+
+fn check_owner(data: &SnsCreationData) -> Check {
+    let principal = load_dfx_identity(None).and_then(|identity| {
+        identity
+            .sender()
+            .map_err(|e| anyhow::anyhow!("Failed to get dfx identity principal: {e}"))
+    });
+    match principal {
+        Ok(principal) if principal.to_text() == data.owner_principal => Check::ok(
+            "owner",
+            format!("{} is the current dfx identity", principal),
+        ),
+        Ok(principal) => Check::failed(
+            "owner",
+            format!(
+                "{} is recorded but the current dfx identity is {}",
+                data.owner_principal, principal
+            ),
+        ),
+        Err(e) => Check::failed("owner", format!("{e:#}")),
+    }
+}
+
+async fn check_icp_neuron(data: &SnsCreationData) -> Check {
+    if data.icp_neuron_id == 0 {
+        return Check::ok("ICP neuron", "none recorded (SNS imported with import-sns)");
+    }
+    let subject = format!("ICP neuron {}", data.icp_neuron_id);
+    // get_full_neuron needs the controller's identity
+    let neuron = async {
+        let identity = load_dfx_identity(None)?;
+        let agent = app_context().agent(identity).await?;
+        get_icp_neuron(
+            &agent,
+            network_config().governance_canister,
+            data.icp_neuron_id,
+        )
+        .await
+    }
+    .await;
+    match neuron {
+        Ok(neuron)
+            if neuron.controller.map(|c| c.to_text()) == Some(data.owner_principal.clone()) =>
+        {
+            Check::ok(
+                subject,
+                format!(
+                    "stake {} e8s, controlled by the owner",
+                    neuron.cached_neuron_stake_e8s
+                ),
+            )
+        }
+        Ok(neuron) => Check::failed(
+            subject,
+            format!(
+                "controlled by {} instead of the owner",
+                neuron
+                    .controller
+                    .map_or_else(|| "nobody".to_string(), |c| c.to_text())
+            ),
+        ),
+        Err(e) => Check::failed(subject, format!("{e:#} (disbursed or replica restarted?)")),
+    }
+}
+
+async fn check_proposal(agent: &ic_agent::Agent, data: &SnsCreationData) -> Check {
+    if data.proposal_id == 0 {
+        return Check::ok(
+            "NNS proposal",
+            "none recorded (SNS imported with import-sns)",
+        );
+    }
+    let subject = format!("NNS proposal {}", data.proposal_id);
+    match get_nns_proposal_info(
+        agent,
+        network_config().governance_canister,
+        data.proposal_id,
+    )
+    .await
+    {
+        Ok(Some(info)) if info.executed_timestamp_seconds > 0 => Check::ok(subject, "executed"),
+        Ok(Some(info)) => Check::failed(subject, format!("not executed (status {})", info.status)),
+        Ok(None) => Check::failed(subject, "unknown to NNS governance"),
+        Err(e) => Check::failed(subject, format!("{e:#}")),
+    }
+}
+
+fn seed_principal(seed_file: &str) -> Result<Principal> {
+    let identity = load_identity_from_seed_file(&PathBuf::from(seed_file))?;
+    identity
+        .sender()
+        .map_err(|e| anyhow::anyhow!("Failed to get principal of {}: {e}", seed_file))
+}
+
+fn short(hash: &str) -> &str {
+    &hash[..hash.len().min(12)]
+}
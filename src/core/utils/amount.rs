@@ -0,0 +1,85 @@
+// Checking an amount against what an account can actually send, so a transfer or stake
+// the ledger or governance would reject fails before anything is submitted
+
+use anyhow::Result;
+
+/// What one ledger transfer out of an account can move
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Spendable {
+    pub balance_e8s: u64,
+    /// Charged on top of the amount sent
+    pub fee_e8s: u64,
+    /// Smallest stake governance accepts for a new neuron, 0 for plain transfers
+    pub minimum_stake_e8s: u64,
+}
+
+impl Spendable {
+    #[must_use]
+    pub const fn new(balance_e8s: u64, fee_e8s: u64) -> Self {
+        Self {
+            balance_e8s,
+            fee_e8s,
+            minimum_stake_e8s: 0,
+        }
+    }
+
+    /// Also require a new neuron's minimum stake
+    #[must_use]
+    pub const fn minimum_stake(mut self, minimum_stake_e8s: u64) -> Self {
+        self.minimum_stake_e8s = minimum_stake_e8s;
+        self
+    }
+
+    /// Also charge `extra_e8s` on top of the amount, e.g. the fee `create-icp-neuron` adds to
+    /// the stake it sends, which the ledger then charges its own fee for
+    #[must_use]
+    pub const fn extra_fee(mut self, extra_e8s: u64) -> Self {
+        self.fee_e8s = self.fee_e8s.saturating_add(extra_e8s);
+        self
+    }
+
+    /// The largest amount that can be sent: the balance less the fee (what `max` stands for)
+    #[must_use]
+    pub const fn max_e8s(&self) -> u64 {
+        self.balance_e8s.saturating_sub(self.fee_e8s)
+    }
+
+    /// The balance left once `amount_e8s` and the fee are sent
+    #[must_use]
+    pub const fn remaining_e8s(&self, amount_e8s: u64) -> u64 {
+        self.balance_e8s
+            .saturating_sub(amount_e8s)
+            .saturating_sub(self.fee_e8s)
+    }
+
+    /// Fail if sending `amount_e8s` is bound to be rejected: nothing to send, less than the
+    /// minimum stake, or more than the balance once the fee is added
+    pub fn check(&self, amount_e8s: u64) -> Result<()> {
+        let max = self.max_e8s();
+        if max == 0 || max < self.minimum_stake_e8s {
+            anyhow::bail!(
+                "Insufficient balance: {} e8s less the {} e8s fee leaves {max} e8s, below the {} e8s needed",
+                self.balance_e8s,
+                self.fee_e8s,
+                self.minimum_stake_e8s.max(1)
+            );
+        }
+        if amount_e8s == 0 {
+            anyhow::bail!("Amount must be greater than zero");
+        }
+        if amount_e8s < self.minimum_stake_e8s {
+            anyhow::bail!(
+                "{amount_e8s} e8s is below the minimum stake of {} e8s",
+                self.minimum_stake_e8s
+            );
+        }
+        if amount_e8s > max {
+            anyhow::bail!(
+                "Insufficient balance: {amount_e8s} e8s plus the {} e8s fee exceeds the balance of {} e8s (at most {max} e8s can be sent; use `max` for that)",
+                self.fee_e8s,
+                self.balance_e8s
+            );
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,201 @@
+// Execution backend: a dfx replica or a PocketIC instance started by the tool
+
+use crate::core::utils::exit_code::{ValidationError, invalid};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::data_output::{ensure_output_dir, get_output_dir, get_output_path};
+use super::pocket_ic::{PocketIc, set_backend_instance};
+use super::{print_info, print_step, print_success, print_warning};
+
+const STATE_FILE: &str = "pocket_ic_backend.json";
+const PORT_FILE: &str = "pocket_ic_port";
+const LOG_FILE: &str = "pocket_ic.log";
+
+/// Seconds the PocketIC server stays up without requests (it outlives each CLI run)
+const SERVER_TTL_SECS: u64 = 3600;
+
+/// How long to wait for a spawned PocketIC server to report its port
+const SERVER_START_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Where canister calls go
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Backend {
+    /// The replica of a running dfx network (see the network config)
+    #[default]
+    Dfx,
+    /// A PocketIC instance with the NNS and SNS canisters, started by the tool
+    PocketIc,
+}
+
+impl std::str::FromStr for Backend {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.to_lowercase().as_str() {
+            "dfx" => Ok(Self::Dfx),
+            "pocket-ic" | "pocketic" => Ok(Self::PocketIc),
+            _ => Err(invalid(format!(
+                "Unknown backend '{}'. Use dfx or pocket-ic",
+                value
+            ))),
+        }
+    }
+}
+
+impl Backend {
+    /// Backend from `--backend`, falling back to `LOCAL_SNS_BACKEND` (default dfx)
+    pub fn resolve(flag: Option<&str>) -> Result<Self> {
+        match flag {
+            Some(value) => value.parse(),
+            None => match std::env::var("LOCAL_SNS_BACKEND") {
+                Ok(value) => value.parse().with_context(|| {
+                    ValidationError::new(format!("Invalid LOCAL_SNS_BACKEND: {}", value))
+                }),
+                Err(_) => Ok(Self::default()),
+            },
+        }
+    }
+}
+
+/// The PocketIC instance the backend runs on, kept between CLI runs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BackendState {
+    server_url: String,
+    instance: usize,
+    gateway_port: u16,
+}
+
+/// Make sure the PocketIC backend is running and return the replica URL to use
+///
+/// Reuses the instance recorded in `pocket_ic_backend.json` in the data directory while its
+/// server is up. Otherwise creates a new instance with the NNS and SNS canisters on the server
+/// at `LOCAL_SNS_POCKET_IC_URL`, or on a server spawned from `POCKET_IC_BIN` (default
+/// `pocket-ic` on the PATH).
+pub async fn start_pocket_ic_backend() -> Result<String> {
+    if let Some(state) = read_state()? {
+        let instance = PocketIc::new(&state.server_url, state.instance);
+        if instance.is_live().await {
+            log::info!("Reusing PocketIC backend at {}", instance.endpoint());
+            set_backend_instance(instance)?;
+            return Ok(gateway_url(state.gateway_port));
+        }
+        print_warning("The recorded PocketIC backend is gone, starting a new one");
+    }
+
+    let server_url = match std::env::var("LOCAL_SNS_POCKET_IC_URL") {
+        Ok(url) => url,
+        Err(_) => spawn_server().await?,
+    };
+
+    print_step("Creating PocketIC instance with the NNS and SNS canisters...");
+    let instance = PocketIc::create_with_system_canisters(&server_url).await?;
+    let gateway_port = instance.start_http_gateway().await?;
+    print_success(&format!(
+        "PocketIC instance {} ready at {}",
+        instance.endpoint(),
+        gateway_url(gateway_port)
+    ));
+
+    write_state(&BackendState {
+        server_url: instance.url().to_string(),
+        instance: instance.instance(),
+        gateway_port,
+    })?;
+    if get_output_path().exists() {
+        print_warning(
+            "The deployment data belongs to the previous replica. Run deploy-sns --fresh",
+        );
+    }
+
+    set_backend_instance(instance)?;
+    Ok(gateway_url(gateway_port))
+}
+
+fn gateway_url(port: u16) -> String {
+    format!("http://127.0.0.1:{}", port)
+}
+
+/// Spawn a PocketIC server that outlives this process and return its URL
+async fn spawn_server() -> Result<String> {
+    let binary = std::env::var("POCKET_IC_BIN").unwrap_or_else(|_| "pocket-ic".to_string());
+    ensure_output_dir()?;
+    let port_file = get_output_dir().join(PORT_FILE);
+    // The server treats an existing port file as another server's
+    let _ = std::fs::remove_file(&port_file);
+    let log_path = get_output_dir().join(LOG_FILE);
+    let log = std::fs::File::create(&log_path)
+        .with_context(|| format!("Failed to create {}", log_path.display()))?;
+
+    print_step(&format!("Starting PocketIC server ({})...", binary));
+    std::process::Command::new(&binary)
+        .arg("--port-file")
+        .arg(&port_file)
+        .arg("--ttl")
+        .arg(SERVER_TTL_SECS.to_string())
+        .stdin(std::process::Stdio::null())
+        .stdout(log.try_clone()?)
+        .stderr(log)
+        .spawn()
+        .with_context(|| {
+            format!(
+                "Failed to start '{}'. Install the PocketIC server or set POCKET_IC_BIN",
+                binary
+            )
+        })?;
+
+    let port = wait_for_port(&port_file)
+        .await
+        .with_context(|| format!("PocketIC server did not start, see {}", log_path.display()))?;
+    print_info(&format!(
+        "PocketIC server on port {} (stops after {}s without requests)",
+        port, SERVER_TTL_SECS
+    ));
+    Ok(format!("http://127.0.0.1:{}", port))
+}
+
+async fn wait_for_port(port_file: &Path) -> Result<u16> {
+    let deadline = tokio::time::Instant::now() + SERVER_START_TIMEOUT;
+    loop {
+        // The file is created before the port is written, so an unparsable file means "not yet"
+        if let Some(port) = std::fs::read_to_string(port_file)
+            .ok()
+            .and_then(|content| content.trim().parse().ok())
+        {
+            return Ok(port);
+        }
+        if tokio::time::Instant::now() >= deadline {
+            anyhow::bail!(
+                "No port in {} after {:?}",
+                port_file.display(),
+                SERVER_START_TIMEOUT
+            );
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
+
+fn state_path() -> PathBuf {
+    get_output_dir().join(STATE_FILE)
+}
+
+fn read_state() -> Result<Option<BackendState>> {
+    let path = state_path();
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let state = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    Ok(Some(state))
+}
+
+fn write_state(state: &BackendState) -> Result<()> {
+    ensure_output_dir()?;
+    let path = state_path();
+    std::fs::write(&path, serde_json::to_string_pretty(state)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
@@ -0,0 +1,64 @@
+// Candid values as JSON: records become objects, variants single-key objects, options null or
+// their value
+
+use candid::types::Label;
+use candid::types::value::{IDLField, IDLValue, VariantValue};
+use serde_json::{Map, Value, json};
+
+/// Convert a Candid value to JSON
+/// Numbers that don't fit a JSON number (big nat/int) become strings; blobs become hex strings.
+#[must_use]
+pub fn idl_to_json(value: &IDLValue) -> Value {
+    match value {
+        IDLValue::Bool(b) => Value::Bool(*b),
+        IDLValue::Null | IDLValue::None | IDLValue::Reserved => Value::Null,
+        IDLValue::Text(text) => Value::String(text.clone()),
+        IDLValue::Number(n) => big_number(n),
+        IDLValue::Float64(f) => json!(f),
+        IDLValue::Float32(f) => json!(f),
+        IDLValue::Opt(inner) => idl_to_json(inner),
+        IDLValue::Vec(items) => Value::Array(items.iter().map(idl_to_json).collect()),
+        IDLValue::Blob(bytes) => Value::String(hex::encode(bytes)),
+        IDLValue::Record(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|field| (label_name(&field.id), idl_to_json(&field.val)))
+                .collect::<Map<_, _>>(),
+        ),
+        IDLValue::Variant(VariantValue(field, _)) => variant(field),
+        IDLValue::Principal(p) | IDLValue::Service(p) => Value::String(p.to_text()),
+        IDLValue::Func(p, method) => json!({ "principal": p.to_text(), "method": method }),
+        IDLValue::Int(n) => big_number(&n.to_string()),
+        IDLValue::Nat(n) => big_number(&n.to_string().replace('_', "")),
+        IDLValue::Nat8(n) => json!(n),
+        IDLValue::Nat16(n) => json!(n),
+        IDLValue::Nat32(n) => json!(n),
+        IDLValue::Nat64(n) => json!(n),
+        IDLValue::Int8(n) => json!(n),
+        IDLValue::Int16(n) => json!(n),
+        IDLValue::Int32(n) => json!(n),
+        IDLValue::Int64(n) => json!(n),
+    }
+}
+
+fn variant(field: &IDLField) -> Value {
+    let mut object = Map::new();
+    object.insert(label_name(&field.id), idl_to_json(&field.val));
+    Value::Object(object)
+}
+
+fn label_name(label: &Label) -> String {
+    match label {
+        Label::Named(name) => name.clone(),
+        Label::Id(id) | Label::Unnamed(id) => id.to_string(),
+    }
+}
+
+/// A JSON number when it fits in 64 bits, a string otherwise
+fn big_number(digits: &str) -> Value {
+    digits
+        .parse::<u64>()
+        .map(Value::from)
+        .or_else(|_| digits.parse::<i64>().map(Value::from))
+        .unwrap_or_else(|_| Value::String(digits.to_string()))
+}
@@ -0,0 +1,142 @@
+// Helpers for flags that may appear anywhere on the command line
+
+use crate::core::utils::exit_code::invalid;
+use anyhow::Result;
+
+/// Remove a `--name value` / `--name=value` flag from the arguments and return its value
+pub fn take_flag(args: &mut Vec<String>, name: &str) -> Result<Option<String>> {
+    let prefix = format!("{name}=");
+    let Some(pos) = args
+        .iter()
+        .position(|a| a == name || a.starts_with(&prefix))
+    else {
+        return Ok(None);
+    };
+
+    let arg = args.remove(pos);
+    if let Some(value) = arg.strip_prefix(&prefix) {
+        return Ok(Some(value.to_string()));
+    }
+    if pos < args.len() {
+        Ok(Some(args.remove(pos)))
+    } else {
+        Err(invalid(format!("{name} requires a value")))
+    }
+}
+
+/// Remove a boolean `--name` switch from the arguments and return whether it was present
+pub fn take_switch(args: &mut Vec<String>, name: &str) -> bool {
+    let before = args.len();
+    args.retain(|a| a != name);
+    args.len() != before
+}
+
+/// Parse a token amount like `1.5` into base units of a ledger with `decimals` decimals
+pub fn parse_token_amount(value: &str, decimals: u8) -> Result<u64> {
+    let value = value.trim();
+    let bad_amount = || {
+        invalid(format!(
+            "Invalid amount '{value}': expected a number of tokens like 1.5"
+        ))
+    };
+
+    let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+    if whole.is_empty() && fraction.is_empty()
+        || !whole
+            .chars()
+            .chain(fraction.chars())
+            .all(|c| c.is_ascii_digit())
+    {
+        return Err(bad_amount());
+    }
+    if fraction.len() > usize::from(decimals) {
+        return Err(invalid(format!(
+            "Invalid amount '{value}': the ledger has only {decimals} decimals"
+        )));
+    }
+
+    let scale = 10u64
+        .checked_pow(u32::from(decimals))
+        .ok_or_else(bad_amount)?;
+    let whole = if whole.is_empty() {
+        0
+    } else {
+        whole.parse::<u64>().map_err(|_| bad_amount())?
+    };
+    let fraction = if fraction.is_empty() {
+        0
+    } else {
+        format!("{fraction:0<width$}", width = usize::from(decimals))
+            .parse::<u64>()
+            .map_err(|_| bad_amount())?
+    };
+    whole
+        .checked_mul(scale)
+        .and_then(|units| units.checked_add(fraction))
+        .ok_or_else(|| anyhow::anyhow!("Amount '{value}' is too large"))
+}
+
+/// Seconds in a year of 365.25 days, as the NNS and SNS governance canisters count them
+pub const ONE_YEAR_SECS: u64 = 31_557_600;
+/// Seconds in a month (a twelfth of a year)
+pub const ONE_MONTH_SECS: u64 = ONE_YEAR_SECS / 12;
+
+const DURATION_UNITS: &[(&[&str], u64)] = &[
+    (&["s", "sec", "secs", "second", "seconds"], 1),
+    (&["m", "min", "mins", "minute", "minutes"], 60),
+    (&["h", "hr", "hrs", "hour", "hours"], 60 * 60),
+    (&["d", "day", "days"], 24 * 60 * 60),
+    (&["w", "wk", "wks", "week", "weeks"], 7 * 24 * 60 * 60),
+    (&["mo", "month", "months"], ONE_MONTH_SECS),
+    (&["y", "yr", "yrs", "year", "years"], ONE_YEAR_SECS),
+];
+
+/// Parse a duration in seconds, with an optional unit: `90`, `90s`, `30m`, `2h`, `7d`, `1w`,
+/// `6mo`, `1y`, or spelled out like `6 months` and `2 years` (`m` is minutes)
+pub fn parse_duration_secs(value: &str) -> Result<u64> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let unit = unit.trim().to_lowercase();
+
+    let multiplier = if unit.is_empty() {
+        Some(1)
+    } else {
+        DURATION_UNITS
+            .iter()
+            .find(|(names, _)| names.contains(&unit.as_str()))
+            .map(|(_, seconds)| *seconds)
+    };
+    multiplier
+        .zip(number.parse::<u64>().ok())
+        .and_then(|(multiplier, n)| n.checked_mul(multiplier))
+        .ok_or_else(|| {
+            invalid(format!(
+                "Invalid duration '{value}': expected seconds or e.g. 30m, 2h, 7d, 1w, 6 months, 1y"
+            ))
+        })
+}
+
+/// Describe a duration in the largest unit that divides it evenly, e.g. `6 months`, `30 days`
+#[must_use]
+pub fn format_duration_secs(seconds: u64) -> String {
+    let units = [
+        (ONE_YEAR_SECS, "year"),
+        (ONE_MONTH_SECS, "month"),
+        (7 * 24 * 60 * 60, "week"),
+        (24 * 60 * 60, "day"),
+        (60 * 60, "hour"),
+        (60, "minute"),
+    ];
+    let (count, unit) = units
+        .iter()
+        .find(|(size, _)| seconds > 0 && seconds.is_multiple_of(*size))
+        .map_or((seconds, "second"), |(size, unit)| (seconds / size, *unit));
+    if count == 1 {
+        format!("1 {unit}")
+    } else {
+        format!("{count} {unit}s")
+    }
+}
@@ -0,0 +1,65 @@
+// Copy IDs to the system clipboard through the platform's clipboard tool
+
+use anyhow::Result;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static COPY: AtomicBool = AtomicBool::new(false);
+
+/// Clipboard tools tried in order: macOS, Wayland, X11 (two tools), Windows/WSL
+const CLIPBOARD_TOOLS: &[(&str, &[&str])] = &[
+    ("pbcopy", &[]),
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+    ("clip.exe", &[]),
+];
+
+/// Copy the selected neuron ID, principal or canister ID to the clipboard (`--copy`)
+pub fn set_copy(enabled: bool) {
+    COPY.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether `--copy` was passed
+#[must_use]
+pub fn is_copy_enabled() -> bool {
+    COPY.load(Ordering::Relaxed)
+}
+
+/// Put `text` on the system clipboard with the first clipboard tool that is installed
+pub fn copy_to_clipboard(text: &str) -> Result<()> {
+    for (tool, args) in CLIPBOARD_TOOLS {
+        let Ok(mut child) = Command::new(tool)
+            .args(*args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            continue;
+        };
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(text.as_bytes())?;
+        }
+        if child.wait()?.success() {
+            return Ok(());
+        }
+    }
+    anyhow::bail!("No clipboard tool found (install pbcopy, wl-copy, xclip or xsel)")
+}
+
+/// Copy `text` and say so, warning instead of failing when there is no clipboard
+pub fn copy_and_report(what: &str, text: &str) {
+    match copy_to_clipboard(text) {
+        Ok(()) => super::print_success(&format!("Copied {what} to the clipboard")),
+        Err(e) => super::print_warning(&format!("Could not copy {what}: {e}")),
+    }
+}
+
+/// With `--copy`, copy the value the user just selected
+pub fn copy_selected(what: &str, text: &str) {
+    if is_copy_enabled() {
+        copy_and_report(what, text);
+    }
+}
@@ -0,0 +1,66 @@
+// Confirmation prompts before irreversible actions (skipped with --yes)
+
+use anyhow::Result;
+use std::io::{self, IsTerminal};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ASSUME_YES: AtomicBool = AtomicBool::new(false);
+
+/// Error of a command whose action was not confirmed (exit code 5)
+#[derive(Debug)]
+pub struct Cancelled;
+
+impl std::fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Cancelled, nothing was submitted")
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+/// Answer every confirmation prompt with yes (`--yes`)
+pub fn set_assume_yes(enabled: bool) {
+    ASSUME_YES.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether irreversible actions ask before they run
+/// Not with `--yes`, and not in dry-run mode since nothing is submitted there.
+#[must_use]
+pub fn needs_confirmation() -> bool {
+    !ASSUME_YES.load(Ordering::Relaxed) && !crate::core::ops::canister_call::is_dry_run()
+}
+
+/// Print a summary of `action` and ask the user to confirm it
+/// Returns Ok(false), after saying so, if the user declines; callers then fail with
+/// [`Cancelled`]. Without a terminal on stdin there is nobody to ask, so the action is
+/// refused with [`Cancelled`] unless `--yes` was passed.
+pub async fn confirm_action(action: &str, summary: &[(&str, String)]) -> Result<bool> {
+    if !needs_confirmation() {
+        return Ok(true);
+    }
+
+    println!();
+    println!("About to {action}:");
+    let width = summary
+        .iter()
+        .map(|(label, _)| label.len())
+        .max()
+        .unwrap_or(0);
+    for (label, value) in summary {
+        println!("  {label:<width$}  {value}");
+    }
+    println!();
+
+    if !io::stdin().is_terminal() {
+        return Err(anyhow::Error::new(Cancelled).context(format!(
+            "Refusing to {action} without confirmation (pass --yes to skip the prompt)"
+        )));
+    }
+
+    let input = super::input::read_line("Proceed? [y/N]: ").await?;
+    let confirmed = matches!(input.trim().to_lowercase().as_str(), "y" | "yes");
+    if !confirmed {
+        super::print_info("Cancelled, nothing was submitted");
+    }
+    Ok(confirmed)
+}
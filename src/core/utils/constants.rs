@@ -4,12 +4,26 @@
 pub const GOVERNANCE_CANISTER: &str = "rrkah-fqaaa-aaaaa-aaaaq-cai";
 pub const LEDGER_CANISTER: &str = "ryjl3-tyaaa-aaaaa-aaaba-cai";
 pub const SNSW_CANISTER: &str = "qaa6y-5yaaa-aaaaa-aaafa-cai";
+pub const ICP_INDEX_CANISTER: &str = "qhbym-qaaaa-aaaaa-aaafq-cai";
 
 // Amounts in e8s (1 ICP = 100_000_000 e8s)
 pub const DEVELOPER_ICP: u64 = 100_000_000_000_000; // 1M ICP in e8s
 pub const PARTICIPANT_ICP: u64 = 100_000_000_000; // 1000 ICP in e8s
 pub const ICP_TRANSFER_FEE: u64 = 10_000; // ICP transfer fee in e8s (0.0001 ICP)
+pub const ICP_NEURON_MINIMUM_STAKE: u64 = 100_000_000; // NNS governance rejects smaller neurons (1 ICP)
+pub const LARGE_MINT_TOKENS: u64 = 10_000; // Mints from 10,000 whole tokens ask for confirmation
+pub const SNS_LEDGER_DECIMALS: u8 = 8; // SNS-W leaves an SNS ledger's decimals at the ICRC-1 default
+
+// Deployment
+pub const PARTICIPANT_CONCURRENCY: usize = 4; // Participants joining the swap at once
 
 // Neuron configuration
 pub const MEMO: u64 = 1;
 pub const DISSOLVE_DELAY: u64 = 252460800; // 8 years in seconds
+pub const NNS_MIN_DISSOLVE_DELAY_TO_VOTE: u64 = 15_778_800; // 6 months in seconds
+pub const NNS_MAX_DISSOLVE_DELAY: u64 = 252_460_800; // 8 years in seconds
+
+// Cycles
+pub const DEFAULT_TOP_UP_CYCLES: u128 = 10_000_000_000_000; // 10T cycles
+pub const CYCLES_LEDGER_CANISTER: &str = "um5iw-rqaaa-aaaaq-qaaba-cai";
+pub const CYCLES_LEDGER_FEE: u128 = 100_000_000; // Charged per withdraw (0.0001T cycles)
@@ -0,0 +1,69 @@
+// CSV output of listings, for pulling snapshots into spreadsheets
+
+use anyhow::{Context, Result};
+use std::path::Path;
+
+/// A CSV document with a header row
+///
+/// Fields holding commas, quotes or line breaks are quoted as RFC 4180 describes.
+pub struct Csv {
+    lines: Vec<String>,
+}
+
+impl Csv {
+    #[must_use]
+    pub fn new(header: &[&str]) -> Self {
+        Self {
+            lines: vec![Self::line(header.iter().copied())],
+        }
+    }
+
+    pub fn add_row(&mut self, fields: Vec<String>) {
+        self.lines
+            .push(Self::line(fields.iter().map(String::as_str)));
+    }
+
+    /// Number of rows, without the header
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.lines.len() - 1
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Write to `path`, or to stdout without one
+    pub fn write(&self, path: Option<&Path>) -> Result<()> {
+        match path {
+            Some(path) => std::fs::write(path, self.to_string())
+                .with_context(|| format!("Failed to write {}", path.display())),
+            None => {
+                print!("{self}");
+                Ok(())
+            }
+        }
+    }
+
+    fn line<'a>(fields: impl Iterator<Item = &'a str>) -> String {
+        fields.map(Self::field).collect::<Vec<_>>().join(",")
+    }
+
+    fn field(value: &str) -> String {
+        if value.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value.to_string()
+        }
+    }
+}
+
+impl std::fmt::Display for Csv {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for line in &self.lines {
+            writeln!(f, "{line}")?;
+        }
+        Ok(())
+    }
+}
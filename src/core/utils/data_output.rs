@@ -1,25 +1,168 @@
 // Output data structure for SNS creation results
 
 use anyhow::Context;
+use candid::Principal;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParticipantData {
     pub principal: String,
     pub seed_file: String, // Path to the seed file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contribution_icp_e8s: Option<u64>, // ICP contributed to the swap
 }
 
+/// Extra seed-file identity created with `identity new` (not part of the swap)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdentityData {
+    pub name: String,
+    pub principal: String,
+    pub seed_file: String, // Path to the seed file
+}
+
+/// Principal that signs through a delegation, e.g. one a dapp gets from a local Internet
+/// Identity, recorded with `identity add-delegated`. The tool holds no key for it, so it can
+/// receive tokens and act as a hotkey, but everything it signs happens in the browser.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DelegatedPrincipalData {
+    pub name: String,
+    pub principal: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<String>, // Dapp origin the delegation was issued for
+}
+
+/// Human-friendly name for any principal, set with `alias set` and accepted wherever a
+/// principal is expected
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AliasData {
+    pub name: String,
+    pub principal: String,
+}
+
+/// Schema version of [`SnsCreationData`] written by this build
+/// Bump it and add a step to [`migrate_data`] whenever the file format changes.
+pub const DATA_VERSION: u32 = 1;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SnsCreationData {
+    /// Schema version (files without one are version 0)
+    pub version: u32,
     pub icp_neuron_id: u64,
     pub proposal_id: u64,
     pub owner_principal: String,
     pub deployed_sns: DeployedSnsData,
     pub participants: Vec<ParticipantData>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub identities: Vec<IdentityData>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub delegated: Vec<DelegatedPrincipalData>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<AliasData>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl SnsCreationData {
+    /// Stand-in deployment data for an SNS this tool did not deploy, given by its canister
+    /// IDs: no participants or identities, and the dfx identity as owner
+    #[must_use]
+    pub const fn external(deployed_sns: DeployedSnsData, owner_principal: String) -> Self {
+        Self {
+            version: DATA_VERSION,
+            icp_neuron_id: 0,
+            proposal_id: 0,
+            owner_principal,
+            deployed_sns,
+            participants: Vec::new(),
+            identities: Vec::new(),
+            delegated: Vec::new(),
+            aliases: Vec::new(),
+        }
+    }
+
+    /// Seed file of a participant or extra identity with the given principal
+    #[must_use]
+    pub fn seed_file_for(&self, principal: &str) -> Option<&str> {
+        self.participants
+            .iter()
+            .find(|p| p.principal == principal)
+            .map(|p| p.seed_file.as_str())
+            .or_else(|| {
+                self.identities
+                    .iter()
+                    .find(|i| i.principal == principal)
+                    .map(|i| i.seed_file.as_str())
+            })
+    }
+
+    /// Delegated principal with the given name or principal
+    #[must_use]
+    pub fn delegated_principal(&self, query: &str) -> Option<&DelegatedPrincipalData> {
+        self.delegated
+            .iter()
+            .find(|d| d.name == query || d.principal == query)
+    }
+
+    /// Aliases of a principal, comma-separated (None without any)
+    #[must_use]
+    pub fn aliases_of(&self, principal: &str) -> Option<String> {
+        let names: Vec<&str> = self
+            .aliases
+            .iter()
+            .filter(|a| a.principal == principal)
+            .map(|a| a.name.as_str())
+            .collect();
+        (!names.is_empty()).then(|| names.join(", "))
+    }
+
+    /// `label` followed by the principal's aliases, e.g. "participant-1 (alice)"
+    #[must_use]
+    pub fn with_aliases(&self, label: &str, principal: &str) -> String {
+        match self.aliases_of(principal) {
+            Some(aliases) => format!("{label} ({aliases})"),
+            None => label.to_string(),
+        }
+    }
+
+    /// Owner and swap participants as (label, principal), labelled `owner` and `participant-N`
+    #[must_use]
+    pub fn accounts(&self) -> Vec<(String, String)> {
+        std::iter::once(("owner".to_string(), self.owner_principal.clone()))
+            .chain(
+                self.participants
+                    .iter()
+                    .enumerate()
+                    .map(|(i, p)| (format!("participant-{}", i + 1), p.principal.clone())),
+            )
+            .collect()
+    }
+
+    /// [`Self::accounts`] with each label followed by the account's aliases, for output
+    #[must_use]
+    pub fn labelled_accounts(&self) -> Vec<(String, String)> {
+        self.accounts()
+            .into_iter()
+            .map(|(label, principal)| (self.with_aliases(&label, &principal), principal))
+            .collect()
+    }
+
+    /// Principals offered by the principal picker before the owner:
+    /// swap participants, then extra identities (with their name)
+    #[must_use]
+    pub fn selectable_principals(&self) -> Vec<(String, Option<String>)> {
+        self.participants
+            .iter()
+            .map(|p| (p.principal.clone(), None))
+            .chain(
+                self.identities
+                    .iter()
+                    .map(|i| (i.principal.clone(), Some(i.name.clone()))),
+            )
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct DeployedSnsData {
     pub root_canister_id: Option<String>,
     pub governance_canister_id: Option<String>,
@@ -28,9 +171,79 @@ pub struct DeployedSnsData {
     pub ledger_canister_id: Option<String>,
 }
 
+impl DeployedSnsData {
+    /// SNS governance canister
+    pub fn governance_canister(&self) -> anyhow::Result<Principal> {
+        parse_canister(self.governance_canister_id.as_deref(), "governance")
+    }
+
+    /// SNS ledger canister
+    pub fn ledger_canister(&self) -> anyhow::Result<Principal> {
+        parse_canister(self.ledger_canister_id.as_deref(), "ledger")
+    }
+
+    /// SNS swap canister
+    pub fn swap_canister(&self) -> anyhow::Result<Principal> {
+        parse_canister(self.swap_canister_id.as_deref(), "swap")
+    }
+
+    /// SNS root canister
+    pub fn root_canister(&self) -> anyhow::Result<Principal> {
+        parse_canister(self.root_canister_id.as_deref(), "root")
+    }
+
+    /// SNS index canister
+    pub fn index_canister(&self) -> anyhow::Result<Principal> {
+        parse_canister(self.index_canister_id.as_deref(), "index")
+    }
+
+    /// Canister IDs by role (`root`, `governance`, ...), skipping any that are unset
+    #[must_use]
+    pub fn canister_ids(&self) -> Vec<(&'static str, &str)> {
+        [
+            ("root", &self.root_canister_id),
+            ("governance", &self.governance_canister_id),
+            ("index", &self.index_canister_id),
+            ("swap", &self.swap_canister_id),
+            ("ledger", &self.ledger_canister_id),
+        ]
+        .into_iter()
+        .filter_map(|(role, id)| id.as_deref().map(|id| (role, id)))
+        .collect()
+    }
+
+    /// Replace the canister IDs that `overrides` sets, keeping the others
+    pub fn apply_overrides(&mut self, overrides: &Self) {
+        for (target, value) in [
+            (&mut self.root_canister_id, &overrides.root_canister_id),
+            (
+                &mut self.governance_canister_id,
+                &overrides.governance_canister_id,
+            ),
+            (&mut self.index_canister_id, &overrides.index_canister_id),
+            (&mut self.swap_canister_id, &overrides.swap_canister_id),
+            (&mut self.ledger_canister_id, &overrides.ledger_canister_id),
+        ] {
+            if value.is_some() {
+                target.clone_from(value);
+            }
+        }
+    }
+}
+
+fn parse_canister(id: Option<&str>, name: &str) -> anyhow::Result<Principal> {
+    id.and_then(|s| Principal::from_text(s).ok())
+        .with_context(|| {
+            format!(
+                "Failed to parse {name} canister ID from deployment data \
+                 (pass --sns-{name}-canister to use an SNS deployed elsewhere)"
+            )
+        })
+}
+
 impl From<&crate::core::declarations::sns_wasm::DeployedSns> for DeployedSnsData {
     fn from(sns: &crate::core::declarations::sns_wasm::DeployedSns) -> Self {
-        DeployedSnsData {
+        Self {
             root_canister_id: sns.root_canister_id.map(|p| p.to_string()),
             governance_canister_id: sns.governance_canister_id.map(|p| p.to_string()),
             index_canister_id: sns.index_canister_id.map(|p| p.to_string()),
@@ -40,15 +253,102 @@ impl From<&crate::core::declarations::sns_wasm::DeployedSns> for DeployedSnsData
     }
 }
 
-const OUTPUT_DIR: &str = "generated";
+/// Progress of an in-flight deployment, written after each completed stage
+/// so a failed `deploy-sns` can resume instead of starting from scratch
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct DeploymentCheckpoint {
+    pub owner_principal: String,
+    pub minting_done: bool,
+    pub icp_neuron_id: Option<u64>,
+    pub neuron_configured: bool,
+    pub proposal_id: Option<u64>,
+    pub proposal_executed: bool,
+    pub participants: Vec<ParticipantData>, // Participants that completed swap participation
+    pub swap_finalized: bool,
+}
+
+/// Data directory used by earlier versions, relative to the working directory
+const LEGACY_OUTPUT_DIR: &str = "generated";
 const OUTPUT_FILE: &str = "sns_deployment_data.json";
+const CHECKPOINT_FILE: &str = "sns_deployment_checkpoint.json";
+const NNS_CANISTERS_FILE: &str = "nns_canisters.json";
 
+static DATA_FILE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Use `path` as the deployment data file for this process (`--data-file`)
+/// Must be called before the data is first read or written; fails if a path is already in use
+pub fn set_data_file(path: PathBuf) -> anyhow::Result<()> {
+    DATA_FILE
+        .set(path)
+        .map_err(|_| anyhow::anyhow!("Deployment data file is already set"))
+}
+
+/// Directory holding the deployment data, seed files, checkpoint and backend state
+/// (the directory of the deployment data file)
+#[must_use]
 pub fn get_output_dir() -> PathBuf {
-    PathBuf::from(OUTPUT_DIR)
+    get_output_path()
+        .parent()
+        .map_or_else(|| PathBuf::from("."), Path::to_path_buf)
 }
 
+/// The deployment data file
+///
+/// `--data-file`, then `LOCAL_SNS_DATA`, then `generated/sns_deployment_data.json` if an
+/// earlier version left a deployment (or checkpoint) in the working directory, otherwise a per-project file in the
+/// XDG data directory (see [`default_data_dir`]).
+#[must_use]
 pub fn get_output_path() -> PathBuf {
-    get_output_dir().join(OUTPUT_FILE)
+    DATA_FILE
+        .get_or_init(|| {
+            if let Ok(path) = std::env::var("LOCAL_SNS_DATA") {
+                return PathBuf::from(path);
+            }
+            let legacy_dir = PathBuf::from(LEGACY_OUTPUT_DIR);
+            if legacy_dir.join(OUTPUT_FILE).exists() || legacy_dir.join(CHECKPOINT_FILE).exists() {
+                return legacy_dir.join(OUTPUT_FILE);
+            }
+            default_data_dir().join(OUTPUT_FILE)
+        })
+        .clone()
+}
+
+/// Per-project data directory: `$XDG_DATA_HOME/local_sns/<project>` (default
+/// `~/.local/share`), where `<project>` is the working directory's name plus a hash of its
+/// path, so checkouts of different repos keep separate deployments
+#[must_use]
+pub fn default_data_dir() -> PathBuf {
+    let base = std::env::var_os("XDG_DATA_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")));
+    let Some(base) = base else {
+        // No home directory to put data in - keep it next to the project
+        return PathBuf::from(LEGACY_OUTPUT_DIR);
+    };
+    base.join("local_sns").join(project_namespace())
+}
+
+fn project_namespace() -> String {
+    use sha2::{Digest, Sha256};
+
+    let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let cwd = cwd.canonicalize().unwrap_or(cwd);
+    let name: String = cwd
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let hash = Sha256::digest(cwd.to_string_lossy().as_bytes());
+    format!("{}-{}", name, &hex::encode(hash)[..12])
 }
 
 /// Ensure the output directory exists
@@ -64,5 +364,206 @@ pub fn write_data(data: &SnsCreationData) -> anyhow::Result<()> {
     let path = get_output_path();
     let json = serde_json::to_string_pretty(data)?;
     std::fs::write(&path, json)?;
+    crate::core::context::app_context().invalidate_deployment_data();
+    Ok(())
+}
+
+#[must_use]
+pub fn get_checkpoint_path() -> PathBuf {
+    get_output_dir().join(CHECKPOINT_FILE)
+}
+
+/// Where `init-nns` records the NNS canisters it installed
+#[must_use]
+pub fn get_nns_canisters_path() -> PathBuf {
+    get_output_dir().join(NNS_CANISTERS_FILE)
+}
+
+/// Read the deployment checkpoint, if a previous deployment left one behind
+pub fn read_checkpoint() -> anyhow::Result<Option<DeploymentCheckpoint>> {
+    let path = get_checkpoint_path();
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read checkpoint: {}", path.display()))?;
+    let checkpoint = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse checkpoint: {}", path.display()))?;
+    Ok(Some(checkpoint))
+}
+
+pub fn write_checkpoint(checkpoint: &DeploymentCheckpoint) -> anyhow::Result<()> {
+    ensure_output_dir()?;
+    let path = get_checkpoint_path();
+    let json = serde_json::to_string_pretty(checkpoint)?;
+    std::fs::write(&path, json)
+        .with_context(|| format!("Failed to write checkpoint: {}", path.display()))?;
+    Ok(())
+}
+
+/// Remove the checkpoint once a deployment has completed
+pub fn clear_checkpoint() -> anyhow::Result<()> {
+    let path = get_checkpoint_path();
+    if path.exists() {
+        std::fs::remove_file(&path)
+            .with_context(|| format!("Failed to remove checkpoint: {}", path.display()))?;
+    }
     Ok(())
 }
+
+/// Read the deployment data written by the last successful `deploy-sns`
+/// Files from older versions are upgraded and written back
+pub fn read_data() -> anyhow::Result<SnsCreationData> {
+    let path = get_output_path();
+    let (data, migrated) = read_data_at(&path)?;
+    if migrated {
+        let json = serde_json::to_string_pretty(&data)?;
+        std::fs::write(&path, json).with_context(|| {
+            format!(
+                "Failed to write upgraded deployment data: {}",
+                path.display()
+            )
+        })?;
+        log::info!(
+            "Upgraded deployment data {} to version {}",
+            path.display(),
+            DATA_VERSION
+        );
+    }
+    Ok(data)
+}
+
+/// Read deployment data from a file, upgrading older versions in memory
+/// Returns whether the data was migrated
+pub fn read_data_at(path: &Path) -> anyhow::Result<(SnsCreationData, bool)> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read deployment data from: {}", path.display()))?;
+    let value: serde_json::Value =
+        serde_json::from_str(&content).context("Failed to parse deployment data JSON")?;
+    let (value, migrated) = migrate_data(value)
+        .with_context(|| format!("Unsupported deployment data: {}", path.display()))?;
+    let data = serde_json::from_value(value).with_context(|| {
+        format!(
+            "Deployment data {} does not match version {}",
+            path.display(),
+            DATA_VERSION
+        )
+    })?;
+    Ok((data, migrated))
+}
+
+/// Upgrade deployment data JSON to [`DATA_VERSION`], one version at a time
+/// Fails for data written by a newer version of the tool
+fn migrate_data(mut value: serde_json::Value) -> anyhow::Result<(serde_json::Value, bool)> {
+    let object = value
+        .as_object_mut()
+        .context("Deployment data is not a JSON object")?;
+    let mut version = match object.get("version") {
+        None => 0,
+        Some(v) => v
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .with_context(|| format!("Invalid deployment data version: {}", v))?,
+    };
+    if version > DATA_VERSION {
+        anyhow::bail!(
+            "Deployment data version {} was written by a newer local_sns (this one reads up to version {}). Upgrade local_sns or redeploy",
+            version,
+            DATA_VERSION
+        );
+    }
+
+    let migrated = version < DATA_VERSION;
+    while version < DATA_VERSION {
+        match version {
+            // 0 → 1: the version field itself; extra identities became a (possibly empty) list
+            0 => {
+                object
+                    .entry("identities")
+                    .or_insert_with(|| serde_json::Value::Array(Vec::new()));
+            }
+            _ => unreachable!("no migration from deployment data version {version}"),
+        }
+        version += 1;
+        object.insert("version".to_string(), version.into());
+    }
+    Ok((value, migrated))
+}
+
+/// Default `.env` file written by `export`
+pub const DEFAULT_ENV_FILE: &str = ".env";
+
+/// Default dfx canister IDs file written by `export`
+pub const DEFAULT_CANISTER_IDS_FILE: &str = "canister_ids.json";
+
+/// Write the SNS canister IDs into a `.env` file as `SNS_<ROLE>_CANISTER_ID=<id>`
+/// Other lines of an existing file are kept; the SNS variables are replaced.
+/// Returns the variables written.
+pub fn export_env(data: &SnsCreationData, path: &Path) -> anyhow::Result<Vec<String>> {
+    let variables: Vec<(String, &str)> = data
+        .deployed_sns
+        .canister_ids()
+        .into_iter()
+        .map(|(role, id)| (format!("SNS_{}_CANISTER_ID", role.to_uppercase()), id))
+        .collect();
+
+    let existing = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read {}", path.display()));
+        }
+    };
+    let mut lines: Vec<String> = existing
+        .lines()
+        .filter(|line| {
+            let key = line.split('=').next().unwrap_or_default().trim();
+            !variables.iter().any(|(name, _)| name == key)
+        })
+        .map(str::to_string)
+        .collect();
+    lines.extend(variables.iter().map(|(name, id)| format!("{name}={id}")));
+
+    std::fs::write(path, lines.join("\n") + "\n")
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(variables.into_iter().map(|(name, _)| name).collect())
+}
+
+/// Merge the SNS canister IDs into a dfx `canister_ids.json` as `sns_<role>` entries for `network`
+/// Other canisters and networks in an existing file are kept.
+/// Returns the canister names written.
+pub fn export_canister_ids(
+    data: &SnsCreationData,
+    path: &Path,
+    network: &str,
+) -> anyhow::Result<Vec<String>> {
+    let mut ids = match std::fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => serde_json::Map::new(),
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read {}", path.display()));
+        }
+    };
+
+    let mut names = Vec::new();
+    for (role, id) in data.deployed_sns.canister_ids() {
+        let name = format!("sns_{role}");
+        let entry = ids
+            .entry(name.clone())
+            .or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()));
+        let Some(networks) = entry.as_object_mut() else {
+            anyhow::bail!("{} in {} is not an object", name, path.display());
+        };
+        networks.insert(
+            network.to_string(),
+            serde_json::Value::String(id.to_string()),
+        );
+        names.push(name);
+    }
+
+    let json = serde_json::to_string_pretty(&ids)?;
+    std::fs::write(path, json + "\n")
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(names)
+}
@@ -0,0 +1,156 @@
+// Exit codes and structured error output, so wrapper scripts can branch on the kind of failure
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use super::confirm::Cancelled;
+use super::interrupt::Interrupted;
+use crate::core::ops::governance_errors::GovernanceCommandError;
+
+static JSON_ERRORS: AtomicBool = AtomicBool::new(false);
+
+/// Print errors as JSON objects on stderr (`--error-format json`)
+pub fn set_json_errors(enabled: bool) {
+    JSON_ERRORS.store(enabled, Ordering::Relaxed);
+}
+
+/// What kind of failure ended a command, which decides the exit code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// Anything not covered below (exit code 1)
+    Other,
+    /// Bad arguments, flags, config or input files (exit code 2)
+    Validation,
+    /// A canister rejected the call or governance refused the command (exit code 3)
+    Canister,
+    /// The replica could not be reached (exit code 4)
+    Network,
+    /// The user declined a confirmation prompt (exit code 5)
+    Cancelled,
+    /// Ctrl+C stopped the command (exit code 130, as for a shell's SIGINT)
+    Interrupted,
+}
+
+impl ErrorKind {
+    /// Process exit code for this kind of failure
+    #[must_use]
+    pub const fn exit_code(self) -> i32 {
+        match self {
+            Self::Other => 1,
+            Self::Validation => 2,
+            Self::Canister => 3,
+            Self::Network => 4,
+            Self::Cancelled => 5,
+            Self::Interrupted => 130,
+        }
+    }
+}
+
+/// A bad argument, flag, config value or input file, returned by argument parsing and input
+/// checks (exit code 2)
+/// Used as the error itself (see [`invalid`]) or as the context of a parse error.
+#[derive(Debug)]
+pub struct ValidationError {
+    pub message: String,
+}
+
+impl ValidationError {
+    #[must_use]
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Error for a bad argument or input
+#[must_use]
+pub fn invalid(message: impl Into<String>) -> anyhow::Error {
+    ValidationError::new(message).into()
+}
+
+/// Classify an error by the typed errors in its chain
+/// A canister, network or cancellation error wins over a [`ValidationError`] context around it.
+#[must_use]
+pub fn classify(err: &anyhow::Error) -> ErrorKind {
+    for cause in err.chain() {
+        if cause.downcast_ref::<Cancelled>().is_some() {
+            return ErrorKind::Cancelled;
+        }
+        if cause.is::<Interrupted>() {
+            return ErrorKind::Interrupted;
+        }
+        if let Some(agent_error) = cause.downcast_ref::<ic_agent::AgentError>() {
+            return classify_agent_error(agent_error);
+        }
+        if cause.is::<GovernanceCommandError>() || cause.is::<candid::Error>() {
+            return ErrorKind::Canister;
+        }
+    }
+
+    // Finds it as the error and as a context anywhere in the chain
+    if err.downcast_ref::<ValidationError>().is_some() {
+        return ErrorKind::Validation;
+    }
+    ErrorKind::Other
+}
+
+const fn classify_agent_error(err: &ic_agent::AgentError) -> ErrorKind {
+    use ic_agent::AgentError;
+
+    match err {
+        AgentError::TransportError(_)
+        | AgentError::TimeoutWaitingForResponse()
+        | AgentError::InvalidReplicaUrl(_)
+        | AgentError::UrlParseError(_)
+        | AgentError::RouteProviderError(_) => ErrorKind::Network,
+        AgentError::HttpError(payload) if payload.status >= 500 => ErrorKind::Network,
+        AgentError::CertifiedReject { .. }
+        | AgentError::UncertifiedReject { .. }
+        | AgentError::HttpError(_)
+        | AgentError::RequestStatusDoneNoReply(_)
+        | AgentError::CandidError(_) => ErrorKind::Canister,
+        _ => ErrorKind::Other,
+    }
+}
+
+#[derive(Serialize)]
+struct ErrorReport {
+    kind: ErrorKind,
+    exit_code: i32,
+    message: String,
+    /// Underlying errors, outermost first
+    causes: Vec<String>,
+}
+
+/// Print a failed command's error (text, or JSON with `--error-format json`) and return the
+/// exit code for it
+#[must_use]
+pub fn report_error(err: &anyhow::Error) -> i32 {
+    let kind = classify(err);
+    if JSON_ERRORS.load(Ordering::Relaxed) {
+        let report = ErrorReport {
+            kind,
+            exit_code: kind.exit_code(),
+            message: err.to_string(),
+            causes: err.chain().skip(1).map(ToString::to_string).collect(),
+        };
+        match serde_json::to_string(&report) {
+            Ok(json) => eprintln!("{json}"),
+            Err(_) => eprintln!("Error: {err:?}"),
+        }
+    } else if err.chain().count() > 1 || !err.root_cause().is::<Cancelled>() {
+        // A declined prompt has already said so; anything else is printed like anyhow does
+        eprintln!("Error: {err:?}");
+    }
+    kind.exit_code()
+}
@@ -0,0 +1,46 @@
+// Line input for interactive prompts
+// Reads run on the blocking thread pool, so the async runtime keeps going while a prompt
+// waits: spinners, background refreshes and the Ctrl+C handler (see `interrupt`)
+
+use anyhow::{Context, Result};
+use std::io::{self, Write};
+
+/// Print `prompt` and read a line from stdin (with its line ending; empty at end of input)
+pub async fn read_line(prompt: &str) -> Result<String> {
+    print!("{prompt}");
+    io::stdout().flush()?;
+    tokio::task::spawn_blocking(|| {
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).map(|_| input)
+    })
+    .await
+    .context("Input reader stopped")?
+    .context("Failed to read input")
+}
+
+/// [`read_line`] for synchronous code running on the runtime (identity resolution)
+/// The read blocks this worker thread in place, so other tasks move to the remaining workers.
+pub fn read_line_blocking(prompt: &str) -> Result<String> {
+    print!("{prompt}");
+    io::stdout().flush()?;
+    in_place(|| {
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).map(|_| input)
+    })
+    .context("Failed to read input")
+}
+
+/// Read a passphrase without echoing it, like [`read_line_blocking`]
+pub fn read_passphrase_blocking(prompt: &str) -> Result<String> {
+    in_place(|| rpassword::prompt_password(prompt)).context("Failed to read passphrase")
+}
+
+/// Run a blocking read, in place on a multi-thread runtime
+fn in_place<T>(read: impl FnOnce() -> T) -> T {
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == tokio::runtime::RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(read)
+        }
+        _ => read(),
+    }
+}
@@ -0,0 +1,145 @@
+// Ctrl+C handling, so an interrupt cannot leave a multi-step operation half done
+//
+// Outside a critical section Ctrl+C exits at once, as without a handler. Inside one, the
+// in-flight canister call completes and the operation stops at its next step boundary
+// (see `check`); a second Ctrl+C exits immediately, after printing how to resume.
+// Commands that stop on Ctrl+C themselves (watch modes, `metrics serve`) hold a
+// `SelfHandled` guard instead.
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use super::exit_code::ErrorKind;
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+static SELF_HANDLED: AtomicUsize = AtomicUsize::new(0);
+/// Resume hints of the open critical sections, innermost last
+static SECTIONS: Mutex<Vec<Option<String>>> = Mutex::new(Vec::new());
+
+/// Error of an operation stopped at a step boundary by Ctrl+C (exit code 130)
+#[derive(Debug)]
+pub struct Interrupted;
+
+impl std::fmt::Display for Interrupted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Interrupted by Ctrl+C")
+    }
+}
+
+impl std::error::Error for Interrupted {}
+
+/// Install the Ctrl+C handler (once per process, from within the runtime)
+pub fn install() {
+    if INSTALLED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    tokio::spawn(async {
+        while tokio::signal::ctrl_c().await.is_ok() {
+            on_ctrl_c();
+        }
+    });
+}
+
+fn on_ctrl_c() {
+    if SELF_HANDLED.load(Ordering::SeqCst) > 0 {
+        return;
+    }
+    let sections = SECTIONS
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if sections.is_empty() || INTERRUPTED.swap(true, Ordering::SeqCst) {
+        println!();
+        if let Some(hint) = sections.iter().rev().flatten().next() {
+            super::print_warning(&format!("Interrupted: {hint}"));
+        }
+        std::process::exit(ErrorKind::Interrupted.exit_code());
+    }
+    println!();
+    super::print_warning(
+        "Interrupted: stopping after the current step (press Ctrl+C again to quit now)",
+    );
+}
+
+/// Whether Ctrl+C was pressed during a critical section
+#[must_use]
+pub fn is_interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+/// A multi-step operation that Ctrl+C does not cut short mid-call, open until dropped
+#[must_use = "the section ends when the guard is dropped"]
+pub struct CriticalSection {
+    depth: usize,
+}
+
+impl CriticalSection {
+    /// Start a critical section
+    pub fn enter() -> Self {
+        let mut sections = SECTIONS
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        sections.push(None);
+        Self {
+            depth: sections.len(),
+        }
+    }
+
+    /// How to finish the operation by hand if it stops from here on, e.g.
+    /// `run claim-sns-neuron --controller <p> --memo <n> to claim the staked tokens`
+    pub fn resume_with(&self, hint: impl Into<String>) {
+        let mut sections = SECTIONS
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some(slot) = sections.get_mut(self.depth - 1) {
+            *slot = Some(hint.into());
+        }
+    }
+}
+
+impl Drop for CriticalSection {
+    fn drop(&mut self) {
+        let mut sections = SECTIONS
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        sections.truncate(self.depth - 1);
+    }
+}
+
+/// At a step boundary of a critical section: fail with [`Interrupted`] if Ctrl+C was
+/// pressed, saying how to resume if the section knows
+pub fn check() -> anyhow::Result<()> {
+    if !is_interrupted() {
+        return Ok(());
+    }
+    let hint = SECTIONS
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .iter()
+        .rev()
+        .flatten()
+        .next()
+        .cloned();
+    let err = anyhow::Error::new(Interrupted);
+    Err(match hint {
+        Some(hint) => err.context(format!("Stopped before finishing: {hint}")),
+        None => err,
+    })
+}
+
+/// Held by commands that stop on Ctrl+C themselves, so the handler leaves it to them
+#[must_use = "the command handles Ctrl+C only while the guard is held"]
+pub struct SelfHandled(());
+
+impl SelfHandled {
+    pub fn enter() -> Self {
+        SELF_HANDLED.fetch_add(1, Ordering::SeqCst);
+        Self(())
+    }
+}
+
+impl Drop for SelfHandled {
+    fn drop(&mut self) {
+        SELF_HANDLED.fetch_sub(1, Ordering::SeqCst);
+    }
+}
@@ -0,0 +1,92 @@
+// Key files remembered for principals the deployment data does not know, so a custom
+// principal is given its PEM or seed file once and signs by itself from then on
+//
+// Kept next to the user config file rather than in the deployment data, so the keys outlive
+// redeployments and apply to every data file.
+
+use crate::core::utils::exit_code::ValidationError;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use super::user_config::UserConfig;
+
+/// A principal and the key file that signs for it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyEntry {
+    pub principal: String,
+    /// PEM file or hex seed file, as an absolute path
+    pub key_file: String,
+}
+
+/// The key registry file (`keys.toml` next to the user config file)
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct KeyRegistry {
+    #[serde(default, rename = "key")]
+    pub keys: Vec<KeyEntry>,
+}
+
+impl KeyRegistry {
+    /// `LOCAL_SNS_KEYS`, otherwise `keys.toml` in the directory of the user config file
+    #[must_use]
+    pub fn path() -> PathBuf {
+        if let Some(path) = std::env::var_os("LOCAL_SNS_KEYS").filter(|p| !p.is_empty()) {
+            return PathBuf::from(path);
+        }
+        UserConfig::path()
+            .parent()
+            .map_or_else(|| PathBuf::from("."), PathBuf::from)
+            .join("keys.toml")
+    }
+
+    /// Read the registry; a missing file is an empty registry
+    pub fn load() -> Result<Self> {
+        let path = Self::path();
+        match std::fs::read_to_string(&path) {
+            Ok(content) => toml::from_str(&content).with_context(|| {
+                ValidationError::new(format!("Invalid key registry {}", path.display()))
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    /// Write the registry, creating its directory
+    pub fn save(&self) -> Result<PathBuf> {
+        let path = Self::path();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let content = toml::to_string(self).context("Failed to serialize key registry")?;
+        std::fs::write(&path, content)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(path)
+    }
+
+    /// Key file registered for a principal
+    #[must_use]
+    pub fn key_file_for(&self, principal: &str) -> Option<&str> {
+        self.keys
+            .iter()
+            .find(|entry| entry.principal == principal)
+            .map(|entry| entry.key_file.as_str())
+    }
+
+    /// Register the key file of a principal, replacing any earlier one
+    pub fn insert(&mut self, principal: String, key_file: String) {
+        self.keys.retain(|entry| entry.principal != principal);
+        self.keys.push(KeyEntry {
+            principal,
+            key_file,
+        });
+    }
+
+    /// Forget the key file of a principal; returns whether it had one
+    pub fn remove(&mut self, principal: &str) -> bool {
+        let before = self.keys.len();
+        self.keys.retain(|entry| entry.principal != principal);
+        self.keys.len() != before
+    }
+}
@@ -0,0 +1,69 @@
+// Logging setup: verbosity levels on top of the pretty human output
+
+use std::sync::OnceLock;
+
+/// How much the CLI prints
+///
+/// `Normal` is the pretty human output (headers, steps, results). `Verbose` and
+/// `Debug` add log records on stderr; `Debug` includes every canister call with its
+/// candid arguments. `Quiet` drops the progress output and keeps results and warnings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Verbosity {
+    Quiet,
+    #[default]
+    Normal,
+    Verbose,
+    Debug,
+    Trace,
+}
+
+impl Verbosity {
+    /// Verbosity for a count of `-v` flags
+    #[must_use]
+    pub const fn from_count(count: usize) -> Self {
+        match count {
+            0 => Self::Normal,
+            1 => Self::Verbose,
+            2 => Self::Debug,
+            _ => Self::Trace,
+        }
+    }
+
+    /// Log filter used when `RUST_LOG` is not set
+    const fn default_filter(self) -> &'static str {
+        match self {
+            Self::Quiet => "error",
+            Self::Normal => "warn",
+            Self::Verbose => "warn,local_sns=info",
+            Self::Debug => "warn,local_sns=debug",
+            Self::Trace => "info,local_sns=trace",
+        }
+    }
+}
+
+static VERBOSITY: OnceLock<Verbosity> = OnceLock::new();
+
+/// Install the logger for this process (`RUST_LOG` overrides the level filter)
+/// Only the first call has an effect
+pub fn init_logging(verbosity: Verbosity) {
+    if VERBOSITY.set(verbosity).is_err() {
+        return;
+    }
+
+    let env = env_logger::Env::default().default_filter_or(verbosity.default_filter());
+    let _ = env_logger::Builder::from_env(env)
+        .format_timestamp_millis()
+        .try_init();
+}
+
+/// Verbosity in use (`Normal` if logging was never initialized)
+#[must_use]
+pub fn verbosity() -> Verbosity {
+    VERBOSITY.get().copied().unwrap_or_default()
+}
+
+/// Whether progress output should be suppressed (`--quiet`)
+#[must_use]
+pub fn is_quiet() -> bool {
+    verbosity() == Verbosity::Quiet
+}
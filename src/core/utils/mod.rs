@@ -1,26 +1,63 @@
 // Utility functions for printing and formatting
 
+pub mod amount;
+pub mod backend;
+pub mod candid_json;
+pub mod cli_args;
+pub mod clipboard;
+pub mod confirm;
 pub mod constants;
+pub mod csv;
 pub mod data_output;
+pub mod exit_code;
+pub mod input;
+pub mod interrupt;
+pub mod key_registry;
+pub mod logging;
+pub mod network;
+pub mod pem_encryption;
+pub mod pocket_ic;
+pub mod prometheus;
+pub mod qr;
+pub mod selector;
+pub mod spinner;
+pub mod stages;
+pub mod table;
+pub mod time_format;
+pub mod user_config;
 
 pub fn print_header(title: &str) {
-    println!("\n═══════════════════════════════════════");
-    println!("{title}");
-    println!("═══════════════════════════════════════\n");
+    if logging::is_quiet() {
+        return;
+    }
+    stages::suspend_progress(|| {
+        println!("\n═══════════════════════════════════════");
+        println!("{title}");
+        println!("═══════════════════════════════════════\n");
+    });
 }
 
 pub fn print_step(msg: &str) {
-    println!("➜ {msg}");
+    if logging::is_quiet() {
+        return;
+    }
+    stages::suspend_progress(|| println!("➜ {msg}"));
 }
 
 pub fn print_success(msg: &str) {
-    println!("✓ {msg}");
+    if logging::is_quiet() {
+        return;
+    }
+    stages::suspend_progress(|| println!("✓ {msg}"));
 }
 
 pub fn print_info(msg: &str) {
-    println!("ℹ {msg}");
+    if logging::is_quiet() {
+        return;
+    }
+    stages::suspend_progress(|| println!("ℹ {msg}"));
 }
 
 pub fn print_warning(msg: &str) {
-    println!("⚠ {msg}");
+    stages::suspend_progress(|| println!("⚠ {msg}"));
 }
@@ -0,0 +1,298 @@
+// Network configuration: replica endpoint and NNS canister IDs
+
+use crate::core::utils::exit_code::{ValidationError, invalid};
+use anyhow::{Context, Result};
+use candid::Principal;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::OnceLock;
+
+use super::constants::{GOVERNANCE_CANISTER, ICP_INDEX_CANISTER, LEDGER_CANISTER, SNSW_CANISTER};
+
+/// Where to find the replica and the NNS canisters
+///
+/// Defaults to the local dfx network with the standard NNS canister IDs. Every
+/// field can be overridden by a config file, environment variables or CLI flags
+/// (later sources win), so the tool can target a non-default dfx port, a custom
+/// NNS install or a testnet without recompiling.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NetworkConfig {
+    pub replica_url: String,
+    /// Fetch the root key from the replica (required for local replicas, never for mainnet)
+    pub fetch_root_key: bool,
+    pub governance_canister: Principal,
+    pub ledger_canister: Principal,
+    pub snsw_canister: Principal,
+    pub icp_index_canister: Principal,
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        let network = default_dfx_network();
+        Self {
+            replica_url: network.url,
+            fetch_root_key: network.fetch_root_key,
+            governance_canister: Principal::from_text(GOVERNANCE_CANISTER)
+                .expect("GOVERNANCE_CANISTER is a valid principal"),
+            ledger_canister: Principal::from_text(LEDGER_CANISTER)
+                .expect("LEDGER_CANISTER is a valid principal"),
+            snsw_canister: Principal::from_text(SNSW_CANISTER)
+                .expect("SNSW_CANISTER is a valid principal"),
+            icp_index_canister: Principal::from_text(ICP_INDEX_CANISTER)
+                .expect("ICP_INDEX_CANISTER is a valid principal"),
+        }
+    }
+}
+
+impl NetworkConfig {
+    /// Point the config at a dfx network (replica URL and root key fetching)
+    pub fn use_dfx_network(&mut self, name: &str) -> Result<()> {
+        let network = resolve_dfx_network(name)?;
+        self.replica_url = network.url;
+        self.fetch_root_key = network.fetch_root_key;
+        Ok(())
+    }
+
+    /// Build the config from defaults, an optional file and the environment
+    ///
+    /// The file (`path`, or `LOCAL_SNS_NETWORK_CONFIG` if unset) may be JSON or TOML.
+    /// Environment overrides: `LOCAL_SNS_REPLICA_URL`, `LOCAL_SNS_FETCH_ROOT_KEY`,
+    /// `LOCAL_SNS_GOVERNANCE_CANISTER`, `LOCAL_SNS_LEDGER_CANISTER`, `LOCAL_SNS_SNSW_CANISTER`,
+    /// `LOCAL_SNS_ICP_INDEX_CANISTER`.
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let env_path = std::env::var("LOCAL_SNS_NETWORK_CONFIG").ok();
+        let path = path.or_else(|| env_path.as_deref().map(Path::new));
+
+        let mut config = match path {
+            Some(path) => Self::from_file(path)?,
+            None => Self::default(),
+        };
+
+        if let Ok(url) = std::env::var("LOCAL_SNS_REPLICA_URL") {
+            config.replica_url = url;
+        }
+        if let Ok(value) = std::env::var("LOCAL_SNS_FETCH_ROOT_KEY") {
+            config.fetch_root_key = parse_bool(&value).with_context(|| {
+                ValidationError::new(format!("Invalid LOCAL_SNS_FETCH_ROOT_KEY: {}", value))
+            })?;
+        }
+        for (var, target) in [
+            (
+                "LOCAL_SNS_GOVERNANCE_CANISTER",
+                &mut config.governance_canister,
+            ),
+            ("LOCAL_SNS_LEDGER_CANISTER", &mut config.ledger_canister),
+            ("LOCAL_SNS_SNSW_CANISTER", &mut config.snsw_canister),
+            (
+                "LOCAL_SNS_ICP_INDEX_CANISTER",
+                &mut config.icp_index_canister,
+            ),
+        ] {
+            if let Ok(value) = std::env::var(var) {
+                *target = Principal::from_text(&value)
+                    .with_context(|| ValidationError::new(format!("Invalid {}: {}", var, value)))?;
+            }
+        }
+
+        Ok(config)
+    }
+
+    fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read network config: {}", path.display()))?;
+
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => toml::from_str(&content).with_context(|| {
+                ValidationError::new(format!(
+                    "Invalid TOML in network config: {}",
+                    path.display()
+                ))
+            }),
+            Some("json") => serde_json::from_str(&content).with_context(|| {
+                ValidationError::new(format!(
+                    "Invalid JSON in network config: {}",
+                    path.display()
+                ))
+            }),
+            _ => anyhow::bail!(
+                "Unsupported network config format '{}'. Use .json or .toml",
+                path.display()
+            ),
+        }
+    }
+}
+
+static NETWORK_CONFIG: OnceLock<NetworkConfig> = OnceLock::new();
+
+/// Install the network config for this process
+/// Must be called before the first canister call; fails if a config is already in use
+pub fn set_network_config(config: NetworkConfig) -> Result<()> {
+    NETWORK_CONFIG
+        .set(config)
+        .map_err(|_| anyhow::anyhow!("Network config is already set"))
+}
+
+/// The network config in use (defaults plus environment overrides if none was set)
+pub fn network_config() -> &'static NetworkConfig {
+    NETWORK_CONFIG.get_or_init(|| {
+        NetworkConfig::load(None).unwrap_or_else(|e| {
+            eprintln!("Warning: {e:#}. Using default network config.");
+            NetworkConfig::default()
+        })
+    })
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_lowercase().as_str() {
+        "1" | "true" | "yes" => Some(true),
+        "0" | "false" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+/// Replica endpoint of a dfx network
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DfxNetwork {
+    pub url: String,
+    pub fetch_root_key: bool,
+}
+
+/// Default bind address of dfx's local network
+const DFX_DEFAULT_BIND: &str = "127.0.0.1:4943";
+
+/// Mainnet boundary node URL used for the built-in `ic` network
+const IC_MAINNET_URL: &str = "https://icp-api.io";
+
+/// Resolve a dfx network by name, the way `dfx --network <name>` does
+/// Checks in order:
+/// 1. `networks` in the project's `dfx.json` (current directory)
+/// 2. `~/.config/dfx/networks.json`
+/// 3. Built-in networks: `local` (127.0.0.1:4943) and `ic` (mainnet)
+///
+/// Networks with `providers` use the first provider; networks with `bind` use the
+/// port of the running replica (`.dfx/network/<name>/webserver-port`) when available.
+/// The root key is fetched for every network except mainnet.
+pub fn resolve_dfx_network(name: &str) -> Result<DfxNetwork> {
+    let project_networks =
+        read_json(Path::new("dfx.json")).and_then(|json| json.get("networks").cloned());
+    let shared_networks = crate::core::ops::identity::get_dfx_config_dir()
+        .ok()
+        .and_then(|dir| read_json(&dir.join("networks.json")));
+
+    for networks in [project_networks, shared_networks].into_iter().flatten() {
+        if let Some(entry) = networks.get(name) {
+            return network_from_entry(name, entry);
+        }
+    }
+
+    match name {
+        "local" => Ok(DfxNetwork {
+            url: bind_to_url(name, DFX_DEFAULT_BIND),
+            fetch_root_key: true,
+        }),
+        "ic" => Ok(DfxNetwork {
+            url: IC_MAINNET_URL.to_string(),
+            fetch_root_key: false,
+        }),
+        _ => Err(invalid(format!(
+            "Unknown dfx network '{}'. Define it in dfx.json or ~/.config/dfx/networks.json",
+            name
+        ))),
+    }
+}
+
+fn network_from_entry(name: &str, entry: &serde_json::Value) -> Result<DfxNetwork> {
+    let provider = entry
+        .get("providers")
+        .and_then(|p| p.as_array())
+        .and_then(|p| p.first())
+        .and_then(|p| p.as_str());
+
+    let url = if let Some(provider) = provider {
+        provider.trim_end_matches('/').to_string()
+    } else if let Some(bind) = entry.get("bind").and_then(|b| b.as_str()) {
+        bind_to_url(name, bind)
+    } else {
+        anyhow::bail!("dfx network '{}' has neither 'providers' nor 'bind'", name);
+    };
+
+    Ok(DfxNetwork {
+        fetch_root_key: !is_mainnet_url(&url),
+        url,
+    })
+}
+
+/// Turn a bind address into a URL, preferring the port the replica actually listens on
+fn bind_to_url(name: &str, bind: &str) -> String {
+    let host = bind.rsplit_once(':').map_or(bind, |(host, _)| host);
+    match running_webserver_port(name) {
+        Some(port) => format!("http://{}:{}", host, port),
+        None => format!("http://{}", bind),
+    }
+}
+
+/// Port written by a running dfx replica (project-local first, then the shared network)
+fn running_webserver_port(name: &str) -> Option<u16> {
+    running_dfx_port(name, "webserver-port")
+}
+
+/// Port from a file a running dfx network writes, e.g. `webserver-port` or `pocket-ic-port`
+/// Checks the project-local network first, then the shared one
+pub(crate) fn running_dfx_port(name: &str, file: &str) -> Option<u16> {
+    let mut candidates = vec![Path::new(".dfx").join("network").join(name).join(file)];
+    if let Ok(home) = std::env::var("HOME") {
+        candidates.push(
+            Path::new(&home)
+                .join(".local/share/dfx/network")
+                .join(name)
+                .join(file),
+        );
+    }
+
+    candidates
+        .iter()
+        .filter_map(|path| std::fs::read_to_string(path).ok())
+        .find_map(|content| content.trim().parse().ok())
+}
+
+fn is_mainnet_url(url: &str) -> bool {
+    ["icp0.io", "ic0.app", "icp-api.io"]
+        .iter()
+        .any(|host| url.contains(host))
+}
+
+fn read_json(path: &Path) -> Option<serde_json::Value> {
+    let content = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Get the default dfx network from configuration or environment
+/// Checks in order:
+/// 1. DFX_REPLICA_URL environment variable
+/// 2. DFX_REPLICA_PORT environment variable (constructs URL)
+/// 3. The dfx network named by DFX_NETWORK (or "local"), see `resolve_dfx_network`
+fn default_dfx_network() -> DfxNetwork {
+    // Check environment variables first
+    if let Ok(url) = std::env::var("DFX_REPLICA_URL") {
+        return DfxNetwork {
+            fetch_root_key: !is_mainnet_url(&url),
+            url,
+        };
+    }
+
+    if let Ok(port) = std::env::var("DFX_REPLICA_PORT") {
+        return DfxNetwork {
+            url: format!("http://127.0.0.1:{}", port),
+            fetch_root_key: true,
+        };
+    }
+
+    let network_name = std::env::var("DFX_NETWORK").unwrap_or_else(|_| "local".to_string());
+    resolve_dfx_network(&network_name)
+        .or_else(|_| resolve_dfx_network("local"))
+        .unwrap_or_else(|_| DfxNetwork {
+            url: format!("http://{}", DFX_DEFAULT_BIND),
+            fetch_root_key: true,
+        })
+}
@@ -0,0 +1,62 @@
+// Decryption of password-protected dfx identities (`dfx identity new --storage-mode password-protected`)
+//
+// dfx keeps these as `identity.pem.encrypted`, AES-256-GCM encrypted with a key derived from
+// the passphrase by Argon2id; the salt and nonce are in the identity's `identity.json`.
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use anyhow::{Context, Result};
+use argon2::password_hash::{PasswordHasher, Salt};
+use serde::{Deserialize, Serialize};
+
+/// The `encryption` entry of a dfx `identity.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncryptionConfig {
+    /// Argon2 salt, base64 without padding
+    pub pw_salt: String,
+    /// 96-bit AES-GCM nonce
+    pub file_nonce: Vec<u8>,
+}
+
+/// A dfx `identity.json`; only what decides how the key is stored is read
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct DfxIdentityConfig {
+    #[serde(default)]
+    pub encryption: Option<EncryptionConfig>,
+    #[serde(default)]
+    pub keyring_identity_suffix: Option<String>,
+    #[serde(default)]
+    pub hsm: Option<serde_json::Value>,
+}
+
+/// The cipher keyed the way dfx derives it from a passphrase (Argon2id, 64000 KiB, 3 passes,
+/// 1 lane)
+fn cipher(passphrase: &str, pw_salt: &str) -> Result<Aes256Gcm> {
+    let params = argon2::Params::new(64000, 3, 1, Some(32))
+        .map_err(|e| anyhow::anyhow!("Invalid Argon2 parameters: {e}"))?;
+    let argon2 = argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params);
+    let salt = Salt::from_b64(pw_salt).map_err(|e| anyhow::anyhow!("Invalid salt: {e}"))?;
+    let hash = argon2
+        .hash_password(passphrase.as_bytes(), salt)
+        .map_err(|e| anyhow::anyhow!("Failed to derive key from passphrase: {e}"))?;
+    let hash = hash.hash.context("Argon2 produced no hash")?;
+    Aes256Gcm::new_from_slice(hash.as_bytes()).map_err(|e| anyhow::anyhow!("Invalid AES key: {e}"))
+}
+
+/// Decrypt the content of an `identity.pem.encrypted` file to its PEM
+pub fn decrypt_pem(
+    encrypted: &[u8],
+    config: &EncryptionConfig,
+    passphrase: &str,
+) -> Result<String> {
+    let nonce: [u8; 12] = config.file_nonce.as_slice().try_into().map_err(|_| {
+        anyhow::anyhow!(
+            "Invalid nonce: expected 12 bytes, got {}",
+            config.file_nonce.len()
+        )
+    })?;
+    let pem = cipher(passphrase, &config.pw_salt)?
+        .decrypt(&Nonce::from(nonce), encrypted)
+        .map_err(|_| anyhow::anyhow!("Wrong passphrase"))?;
+    String::from_utf8(pem).context("Decrypted identity is not a PEM file")
+}
@@ -0,0 +1,277 @@
+// Client for the PocketIC server's REST API (instances, HTTP gateway, time control)
+
+use crate::core::utils::exit_code::ValidationError;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use super::network::running_dfx_port;
+
+/// How long to wait for an operation the server runs in the background
+const OPERATION_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct RawTime {
+    nanos_since_epoch: u64,
+}
+
+/// A PocketIC instance, e.g. the one behind `dfx start` (dfx 0.24+ runs on PocketIC)
+#[derive(Debug, Clone)]
+pub struct PocketIc {
+    url: String,
+    instance: usize,
+    client: reqwest::Client,
+}
+
+/// Instance of the PocketIC backend (`--backend pocket-ic`), preferred by [`PocketIc::discover`]
+static BACKEND_INSTANCE: OnceLock<PocketIc> = OnceLock::new();
+
+/// Use `instance` for time control in this process instead of the dfx network's server
+pub fn set_backend_instance(instance: PocketIc) -> Result<()> {
+    BACKEND_INSTANCE
+        .set(instance)
+        .map_err(|_| anyhow::anyhow!("PocketIC backend instance is already set"))
+}
+
+impl PocketIc {
+    /// Connect to an instance of a PocketIC server
+    #[must_use]
+    pub fn new(url: &str, instance: usize) -> Self {
+        Self {
+            url: url.trim_end_matches('/').to_string(),
+            instance,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Find the PocketIC server of the local dfx network
+    /// The instance of the PocketIC backend wins if one is in use. `LOCAL_SNS_POCKET_IC_URL` overrides the server URL (otherwise the port dfx writes to
+    /// `.dfx/network/local/pocket-ic-port` is used) and `LOCAL_SNS_POCKET_IC_INSTANCE` the
+    /// instance (otherwise the first live one)
+    pub async fn discover() -> Result<Self> {
+        if let Some(instance) = BACKEND_INSTANCE.get() {
+            return Ok(instance.clone());
+        }
+
+        let url = match std::env::var("LOCAL_SNS_POCKET_IC_URL") {
+            Ok(url) => url,
+            Err(_) => {
+                let port = running_dfx_port("local", "pocket-ic-port").context(
+                    "No PocketIC server found. Start dfx with PocketIC (the default since dfx 0.24) or set LOCAL_SNS_POCKET_IC_URL",
+                )?;
+                format!("http://127.0.0.1:{}", port)
+            }
+        };
+
+        let instance = match std::env::var("LOCAL_SNS_POCKET_IC_INSTANCE") {
+            Ok(value) => value.parse().with_context(|| {
+                ValidationError::new(format!("Invalid LOCAL_SNS_POCKET_IC_INSTANCE: {}", value))
+            })?,
+            Err(_) => Self::first_live_instance(&url).await?,
+        };
+
+        Ok(Self::new(&url, instance))
+    }
+
+    async fn first_live_instance(url: &str) -> Result<usize> {
+        let instances: Vec<serde_json::Value> = reqwest::get(format!("{}/instances", url))
+            .await
+            .with_context(|| format!("Failed to reach the PocketIC server at {}", url))?
+            .error_for_status()?
+            .json()
+            .await
+            .context("Failed to decode the PocketIC instance list")?;
+
+        instances
+            .iter()
+            .position(|status| status.as_str() != Some("Deleted"))
+            .with_context(|| format!("The PocketIC server at {} has no live instance", url))
+    }
+
+    /// Create an instance with the NNS and SNS canisters installed (like `dfx start --system-canisters`)
+    /// Needs a PocketIC server that supports `icp_features` (10.0 or later)
+    pub async fn create_with_system_canisters(url: &str) -> Result<Self> {
+        let subnet =
+            serde_json::json!({ "state_config": "New", "instruction_config": "Production" });
+        let feature = serde_json::json!("DefaultConfig");
+        let config = serde_json::json!({
+            "subnet_config_set": {
+                "nns": subnet,
+                "sns": subnet,
+                "ii": null,
+                "fiduciary": null,
+                "bitcoin": null,
+                "system": [],
+                "application": [subnet],
+                "verified_application": [],
+            },
+            "icp_features": {
+                "registry": feature,
+                "cycles_minting": feature,
+                "icp_token": feature,
+                "cycles_token": feature,
+                "nns_governance": feature,
+                "sns": feature,
+            },
+            "initial_time": { "AutoProgress": { "artificial_delay_ms": null } },
+        });
+
+        let url = url.trim_end_matches('/');
+        let reply: serde_json::Value = reqwest::Client::new()
+            .post(format!("{}/instances", url))
+            .json(&config)
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach the PocketIC server at {}", url))?
+            .json()
+            .await
+            .context("Failed to decode the PocketIC instance creation reply")?;
+        let instance =
+            created_field(&reply, "instance_id").context("Failed to create a PocketIC instance")?;
+
+        Ok(Self::new(url, instance))
+    }
+
+    /// Whether the server is reachable and the instance not deleted
+    pub async fn is_live(&self) -> bool {
+        let Ok(response) = self
+            .client
+            .get(format!("{}/instances", self.url))
+            .send()
+            .await
+        else {
+            return false;
+        };
+        let Ok(instances) = response.json::<Vec<serde_json::Value>>().await else {
+            return false;
+        };
+        instances
+            .get(self.instance)
+            .is_some_and(|status| status.as_str() != Some("Deleted"))
+    }
+
+    /// Start an HTTP gateway for the instance and return its port
+    /// Agents talk to the instance through the gateway like they talk to a dfx replica
+    pub async fn start_http_gateway(&self) -> Result<u16> {
+        let config = serde_json::json!({
+            "ip_addr": null,
+            "port": null,
+            "forward_to": { "PocketIcInstance": self.instance },
+            "domains": null,
+            "https_config": null,
+        });
+        let reply: serde_json::Value = self
+            .client
+            .post(format!("{}/http_gateway", self.url))
+            .json(&config)
+            .send()
+            .await
+            .context("Failed to reach the PocketIC server")?
+            .json()
+            .await
+            .context("Failed to decode the PocketIC HTTP gateway reply")?;
+        let port =
+            created_field(&reply, "port").context("Failed to start a PocketIC HTTP gateway")?;
+        u16::try_from(port).context("PocketIC returned an invalid HTTP gateway port")
+    }
+
+    /// Server URL
+    #[must_use]
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Instance ID on the server
+    #[must_use]
+    pub const fn instance(&self) -> usize {
+        self.instance
+    }
+
+    /// Server URL and instance, for messages
+    #[must_use]
+    pub fn endpoint(&self) -> String {
+        format!("{}/instances/{}", self.url, self.instance)
+    }
+
+    /// Current time of the instance in nanoseconds since the epoch
+    pub async fn get_time(&self) -> Result<u64> {
+        let time: RawTime = self
+            .client
+            .get(format!("{}/read/get_time", self.endpoint()))
+            .send()
+            .await
+            .context("Failed to get the PocketIC time")?
+            .error_for_status()?
+            .json()
+            .await
+            .context("Failed to decode the PocketIC time")?;
+        Ok(time.nanos_since_epoch)
+    }
+
+    /// Set the time of the instance (it can only move forward)
+    pub async fn set_time(&self, nanos_since_epoch: u64) -> Result<()> {
+        self.update("set_time", &RawTime { nanos_since_epoch })
+            .await
+            .context("Failed to set the PocketIC time")
+    }
+
+    /// Execute a round, so timers and heartbeats due at the current time run
+    pub async fn tick(&self) -> Result<()> {
+        self.update("tick", &serde_json::json!({}))
+            .await
+            .context("Failed to tick PocketIC")
+    }
+
+    /// Call an update endpoint, waiting for the operation if the server runs it in the background
+    async fn update(&self, endpoint: &str, body: &impl Serialize) -> Result<()> {
+        let response = self
+            .client
+            .post(format!("{}/update/{}", self.endpoint(), endpoint))
+            .json(body)
+            .send()
+            .await?;
+        if response.status() != reqwest::StatusCode::ACCEPTED {
+            response.error_for_status()?;
+            return Ok(());
+        }
+
+        // 202: the server returns the operation to poll for
+        let started: serde_json::Value = response.json().await?;
+        let (state_label, op_id) = started
+            .get("Started")
+            .and_then(|s| Some((s.get("state_label")?.as_str()?, s.get("op_id")?.as_str()?)))
+            .context("Unexpected response from PocketIC")?;
+        let deadline = tokio::time::Instant::now() + OPERATION_TIMEOUT;
+        loop {
+            let response = self
+                .client
+                .get(format!("{}/read_graph/{}/{}", self.url, state_label, op_id))
+                .send()
+                .await?;
+            if response.status().is_success() {
+                return Ok(());
+            }
+            if tokio::time::Instant::now() >= deadline {
+                anyhow::bail!("PocketIC operation {} timed out", endpoint);
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
+}
+
+/// A numeric field of a `{"Created": {...}}` reply, or the server's `{"Error": {"message"}}`
+fn created_field(reply: &serde_json::Value, field: &str) -> Result<usize> {
+    if let Some(value) = reply.get("Created").and_then(|c| c.get(field)) {
+        return value
+            .as_u64()
+            .and_then(|v| usize::try_from(v).ok())
+            .with_context(|| format!("Unexpected {} in PocketIC reply: {}", field, value));
+    }
+    let message = reply
+        .get("Error")
+        .and_then(|e| e.get("message"))
+        .and_then(serde_json::Value::as_str)
+        .map_or_else(|| reply.to_string(), str::to_string);
+    anyhow::bail!("PocketIC: {}", message)
+}
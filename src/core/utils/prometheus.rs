@@ -0,0 +1,59 @@
+// Prometheus text exposition format (version 0.0.4)
+
+use std::fmt::Write;
+
+/// Content type of the text format, for HTTP responses
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// A metrics page: families of samples, each with a HELP and TYPE line
+#[derive(Default)]
+pub struct Exposition {
+    text: String,
+}
+
+impl Exposition {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start a gauge family; its samples follow with [`Self::sample`]
+    pub fn gauge(&mut self, name: &str, help: &str) -> &mut Self {
+        let _ = writeln!(self.text, "# HELP {name} {help}");
+        let _ = writeln!(self.text, "# TYPE {name} gauge");
+        self
+    }
+
+    /// One sample of the current family
+    pub fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: f64) -> &mut Self {
+        self.text.push_str(name);
+        if !labels.is_empty() {
+            let labels = labels
+                .iter()
+                .map(|(key, value)| format!("{key}=\"{}\"", escape_label(value)))
+                .collect::<Vec<_>>()
+                .join(",");
+            let _ = write!(self.text, "{{{labels}}}");
+        }
+        let _ = writeln!(self.text, " {value}");
+        self
+    }
+
+    /// A gauge family with a single unlabelled sample
+    pub fn single(&mut self, name: &str, help: &str, value: f64) -> &mut Self {
+        self.gauge(name, help).sample(name, &[], value)
+    }
+
+    #[must_use]
+    pub fn into_text(self) -> String {
+        self.text
+    }
+}
+
+/// Escape a label value: backslash, double quote and line feed
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
@@ -0,0 +1,111 @@
+// QR codes and payment deep links for receiving accounts, to test mobile wallet flows
+
+use anyhow::{Context, Result};
+use candid::Principal;
+use qrcode::QrCode;
+use qrcode::render::unicode::Dense1x2;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static QR: AtomicBool = AtomicBool::new(false);
+
+/// Show a QR code and deep link wherever a receiving account is printed (`--qr`)
+pub fn set_qr(enabled: bool) {
+    QR.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether `--qr` was passed
+#[must_use]
+pub fn is_qr_enabled() -> bool {
+    QR.load(Ordering::Relaxed)
+}
+
+/// ICRC-1 textual encoding of an account: the principal for the default account, otherwise
+/// `<principal>-<checksum>.<subaccount hex without leading zeros>`
+#[must_use]
+pub fn icrc1_account_text(owner: Principal, subaccount: Option<&[u8; 32]>) -> String {
+    let Some(subaccount) = subaccount.filter(|s| s.iter().any(|b| *b != 0)) else {
+        return owner.to_text();
+    };
+    let mut bytes = owner.as_slice().to_vec();
+    bytes.extend_from_slice(subaccount);
+    let checksum = base32(&crc32(&bytes).to_be_bytes());
+    let hex = hex::encode(subaccount);
+    format!("{owner}-{checksum}.{}", hex.trim_start_matches('0'))
+}
+
+/// Payment request for a ledger's `transfer`, in the ICRC-22 style wallets scan:
+/// `icp:<ledger>/transfer?to=<account>[&amount=<e8s>]`
+#[must_use]
+pub fn payment_link(ledger_canister: Principal, account: &str, amount_e8s: Option<u64>) -> String {
+    let mut link = format!("icp:{ledger_canister}/transfer?to={account}");
+    if let Some(amount) = amount_e8s {
+        link.push_str(&format!("&amount={amount}"));
+    }
+    link
+}
+
+/// `text` as a QR code of unicode half blocks, light on dark so it scans from a dark terminal
+pub fn render_qr(text: &str) -> Result<String> {
+    let code = QrCode::new(text.as_bytes()).context("Failed to encode QR code")?;
+    Ok(code
+        .render::<Dense1x2>()
+        .dark_color(Dense1x2::Light)
+        .light_color(Dense1x2::Dark)
+        .build())
+}
+
+/// With `--qr`, print the payment link for an account on a ledger and its QR code
+pub fn show_receiving_account(
+    ledger_canister: Principal,
+    owner: Principal,
+    subaccount: Option<&[u8; 32]>,
+    amount_e8s: Option<u64>,
+) {
+    if !is_qr_enabled() {
+        return;
+    }
+    let link = payment_link(
+        ledger_canister,
+        &icrc1_account_text(owner, subaccount),
+        amount_e8s,
+    );
+    println!();
+    super::print_info(&format!("Payment link: {link}"));
+    match render_qr(&link) {
+        Ok(qr) => println!("{qr}"),
+        Err(e) => super::print_warning(&format!("Could not render the QR code: {e:#}")),
+    }
+}
+
+/// CRC-32 (IEEE), the checksum of ICRC-1 textual accounts
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for byte in bytes {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+/// Lowercase RFC 4648 base32 without padding, as principals are written
+fn base32(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+    let mut out = String::new();
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for byte in bytes {
+        buffer = (buffer << 8) | u32::from(*byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(ALPHABET[((buffer >> bits) & 31) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(ALPHABET[((buffer << (5 - bits)) & 31) as usize] as char);
+    }
+    out
+}
@@ -0,0 +1,249 @@
+// Interactive principal picker: numbered choices, fuzzy filtering on part of a principal,
+// name or alias, and the same back/cancel inputs as the other prompts
+
+use crate::core::utils::exit_code::invalid;
+use anyhow::Result;
+use candid::Principal;
+
+use super::input::read_line;
+
+/// Unified error type for user navigation
+#[derive(Debug)]
+pub enum UserNavigation {
+    GoBack,
+    GoToMainMenu,
+}
+
+impl std::fmt::Display for UserNavigation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::GoBack => write!(f, "User went back"),
+            Self::GoToMainMenu => write!(f, "User went to main menu"),
+        }
+    }
+}
+
+impl std::error::Error for UserNavigation {}
+
+/// A principal offered by the picker
+#[derive(Debug, Clone)]
+pub struct Choice {
+    pub principal: Principal,
+    /// Names the choice is found by when filtering, besides its principal
+    /// (`owner`, `participant-N`, identity names, aliases)
+    pub keys: Vec<String>,
+    /// Shown in parentheses after the principal
+    pub notes: Vec<String>,
+}
+
+impl Choice {
+    #[must_use]
+    pub const fn new(principal: Principal) -> Self {
+        Self {
+            principal,
+            keys: Vec::new(),
+            notes: Vec::new(),
+        }
+    }
+
+    /// The choice as listed, e.g. `aaaaa-aa (identity: alice, alias: bob)`
+    #[must_use]
+    pub fn display(&self) -> String {
+        if self.notes.is_empty() {
+            self.principal.to_text()
+        } else {
+            format!("{} ({})", self.principal, self.notes.join(", "))
+        }
+    }
+
+    /// Best [`fuzzy_score`] of `query` against the keys and the principal
+    /// Principals are long enough to contain most short queries as a subsequence, so they
+    /// only match on a prefix or substring.
+    #[must_use]
+    pub fn score(&self, query: &str) -> Option<u8> {
+        let principal = fuzzy_score(query, &self.principal.to_text()).filter(|score| *score < 2);
+        self.keys
+            .iter()
+            .filter_map(|key| fuzzy_score(query, key))
+            .chain(principal)
+            .min()
+    }
+}
+
+/// What was entered at the picker prompt
+#[derive(Debug, PartialEq, Eq)]
+pub enum SelectorInput {
+    /// A listed choice, as an index into the choices shown
+    Pick(usize),
+    /// The "Enter custom principal" entry
+    Custom,
+    /// `b` or `back`
+    Back,
+    /// Enter, or the "Go back to main menu" entry
+    MainMenu,
+    /// Anything else: a principal, a name, or text to filter the choices by
+    Text(String),
+}
+
+impl SelectorInput {
+    /// Parse a line entered while `shown` choices are listed; numbers past the two
+    /// entries that follow the choices are rejected
+    pub fn parse(input: &str, shown: usize) -> Result<Self> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Ok(Self::MainMenu);
+        }
+        if matches!(input.to_lowercase().as_str(), "b" | "back") {
+            return Ok(Self::Back);
+        }
+        let Ok(number) = input.parse::<usize>() else {
+            return Ok(Self::Text(input.to_string()));
+        };
+        match number {
+            n if (1..=shown).contains(&n) => Ok(Self::Pick(n - 1)),
+            n if n == shown + 1 => Ok(Self::Custom),
+            n if n == shown + 2 => Ok(Self::MainMenu),
+            _ => Err(invalid(format!(
+                "Invalid selection. Please choose a number between 1 and {}",
+                shown + 2
+            ))),
+        }
+    }
+}
+
+/// How well `query` matches `text`, ignoring case: 0 for a prefix, 1 for a substring,
+/// 2 when its characters appear in order (`p3` in `participant-3`), None otherwise
+#[must_use]
+pub fn fuzzy_score(query: &str, text: &str) -> Option<u8> {
+    let query = query.to_lowercase();
+    let text = text.to_lowercase();
+    if query.is_empty() {
+        return None;
+    }
+    if text.starts_with(&query) {
+        return Some(0);
+    }
+    if text.contains(&query) {
+        return Some(1);
+    }
+    let mut rest = text.chars();
+    query
+        .chars()
+        .all(|q| rest.by_ref().any(|c| c == q))
+        .then_some(2)
+}
+
+/// Numbered list of principals to pick from, followed by entries to enter a custom
+/// principal and to go back to the main menu
+pub struct Selector {
+    title: Option<String>,
+    choices: Vec<Choice>,
+}
+
+impl Selector {
+    #[must_use]
+    pub const fn new(choices: Vec<Choice>) -> Self {
+        Self {
+            title: None,
+            choices,
+        }
+    }
+
+    /// Line printed above the choices
+    #[must_use]
+    pub fn title(mut self, title: Option<&str>) -> Self {
+        self.title = title.map(str::to_string);
+        self
+    }
+
+    #[must_use]
+    pub fn choices(&self) -> &[Choice] {
+        &self.choices
+    }
+
+    /// Indices of the choices `query` matches, best matches first
+    #[must_use]
+    pub fn filter(&self, query: &str) -> Vec<usize> {
+        let mut matches: Vec<(u8, usize)> = self
+            .choices
+            .iter()
+            .enumerate()
+            .filter_map(|(i, choice)| Some((choice.score(query)?, i)))
+            .collect();
+        matches.sort();
+        matches.into_iter().map(|(_, i)| i).collect()
+    }
+
+    /// Let the user pick a principal
+    ///
+    /// Text that is not a number goes to `resolve` first, so a full principal or an exact
+    /// name always wins. Otherwise it filters the choices: a single match is picked, several
+    /// are listed again on their own (`b` goes back to the full list). `b` on the full
+    /// list fails with [`UserNavigation::GoBack`], Enter with
+    /// [`UserNavigation::GoToMainMenu`].
+    pub async fn run(&self, resolve: impl Fn(&str) -> Result<Principal>) -> Result<Principal> {
+        let all: Vec<usize> = (0..self.choices.len()).collect();
+        let mut shown = all.clone();
+        if let Some(title) = &self.title {
+            println!("{title}");
+            println!();
+        }
+        loop {
+            self.print(&shown);
+            let input = read_line(&format!(
+                "Select option number (1-{}), press Enter/[b]ack to go back, or type part of a principal, name or alias: ",
+                shown.len() + 2
+            ))
+            .await?;
+            match SelectorInput::parse(&input, shown.len())? {
+                SelectorInput::Pick(i) => return Ok(self.choices[shown[i]].principal),
+                SelectorInput::Custom => {
+                    let input =
+                        read_line("Enter principal or name (or press Enter/[b]ack to go back): ")
+                            .await?;
+                    match SelectorInput::parse(&input, 0) {
+                        Ok(SelectorInput::Back) => continue,
+                        Ok(SelectorInput::MainMenu) => {
+                            return Err(UserNavigation::GoToMainMenu.into());
+                        }
+                        _ => return resolve(input.trim()),
+                    }
+                }
+                SelectorInput::Back if shown.len() < all.len() => shown = all.clone(),
+                SelectorInput::Back => return Err(UserNavigation::GoBack.into()),
+                SelectorInput::MainMenu => return Err(UserNavigation::GoToMainMenu.into()),
+                SelectorInput::Text(text) => {
+                    let err = match resolve(&text) {
+                        Ok(principal) => return Ok(principal),
+                        Err(e) => e,
+                    };
+                    match self.filter(&text).as_slice() {
+                        [] => return Err(err),
+                        [only] => {
+                            let choice = &self.choices[*only];
+                            super::print_info(&format!("Matched {}", choice.display()));
+                            return Ok(choice.principal);
+                        }
+                        matches => {
+                            println!();
+                            println!("{} options match '{text}':", matches.len());
+                            shown = matches.to_vec();
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Print the choices at `shown`, numbered from 1, and the two trailing entries
+    fn print(&self, shown: &[usize]) {
+        println!("Available options:");
+        println!();
+        for (n, i) in shown.iter().enumerate() {
+            println!("  [{}] {}", n + 1, self.choices[*i].display());
+        }
+        println!("  [{}] Enter custom principal", shown.len() + 1);
+        println!("  [{}] Go back to main menu", shown.len() + 2);
+        println!();
+    }
+}
@@ -0,0 +1,55 @@
+// Terminal spinner for slow network calls
+
+use std::io::{IsTerminal, Write};
+use std::time::Duration;
+
+const FRAMES: &[char] = &['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+/// Spinner drawn on stdout while an operation runs
+/// Does nothing when stdout is not a terminal, with `--quiet` or while a stage progress bar is
+/// drawn (see `stages`), so piped output stays clean
+pub struct Spinner {
+    task: Option<tokio::task::JoinHandle<()>>,
+}
+
+impl Spinner {
+    /// Start spinning with a message (requires a tokio runtime)
+    #[must_use]
+    pub fn start(message: &str) -> Self {
+        if !std::io::stdout().is_terminal()
+            || super::logging::is_quiet()
+            || super::stages::progress_active()
+        {
+            return Self { task: None };
+        }
+
+        let message = message.to_string();
+        let task = tokio::spawn(async move {
+            for frame in FRAMES.iter().cycle() {
+                print!("\r{frame} {message}");
+                let _ = std::io::stdout().flush();
+                tokio::time::sleep(Duration::from_millis(80)).await;
+            }
+        });
+        Self { task: Some(task) }
+    }
+
+    /// Stop spinning and clear the spinner line
+    pub fn finish(mut self) {
+        self.stop();
+    }
+
+    fn stop(&mut self) {
+        if let Some(task) = self.task.take() {
+            task.abort();
+            print!("\r\x1b[2K");
+            let _ = std::io::stdout().flush();
+        }
+    }
+}
+
+impl Drop for Spinner {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
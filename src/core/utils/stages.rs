@@ -0,0 +1,213 @@
+// Stage progress and timing for long multi-step operations (deploy-sns)
+//
+// While a stage runs, a progress bar (stage position and elapsed time) is drawn below its
+// output. The print helpers in `utils` print above the bar through `suspend_progress`, so the
+// step lines of a stage don't tear it.
+
+use anyhow::Result;
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use std::future::Future;
+use std::io::IsTerminal;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use super::table::Table;
+use super::{print_header, print_info, print_success, print_warning};
+
+/// Progress bar of the running stage, if one is drawn
+static PROGRESS: Mutex<Option<ProgressBar>> = Mutex::new(None);
+
+/// Run `print` with the stage progress bar (if any) hidden, so the output lands above it
+pub fn suspend_progress<R>(print: impl FnOnce() -> R) -> R {
+    let bar = PROGRESS.lock().ok().and_then(|progress| progress.clone());
+    match bar {
+        Some(bar) => bar.suspend(print),
+        None => print(),
+    }
+}
+
+/// Whether a stage progress bar is drawn (the spinner stays off meanwhile)
+#[must_use]
+pub fn progress_active() -> bool {
+    PROGRESS.lock().is_ok_and(|progress| progress.is_some())
+}
+
+/// Draw a bar for a stage on a terminal; nothing with `--quiet` or piped output
+fn start_progress(position: usize, total: usize, name: &str) {
+    if !std::io::stdout().is_terminal() || super::logging::is_quiet() {
+        return;
+    }
+    let bar = ProgressBar::with_draw_target(Some(total as u64), ProgressDrawTarget::stdout());
+    if let Ok(style) =
+        ProgressStyle::with_template("{spinner} [{bar:20}] {prefix} {msg} ({elapsed})")
+    {
+        bar.set_style(style.progress_chars("=> "));
+    }
+    bar.set_position(position.saturating_sub(1) as u64);
+    bar.set_prefix(format_position(position, total));
+    bar.set_message(name.to_string());
+    bar.enable_steady_tick(Duration::from_millis(100));
+    if let Ok(mut progress) = PROGRESS.lock() {
+        *progress = Some(bar);
+    }
+}
+
+/// Remove the stage's bar before its result line is printed
+fn finish_progress() {
+    let bar = PROGRESS
+        .lock()
+        .ok()
+        .and_then(|mut progress| progress.take());
+    if let Some(bar) = bar {
+        bar.finish_and_clear();
+    }
+}
+
+/// How a stage ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StageResult {
+    Done,
+    /// Already done by an earlier, resumed run (or not needed)
+    Skipped,
+    Failed,
+}
+
+impl StageResult {
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Done => "done",
+            Self::Skipped => "skipped",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+struct Stage {
+    name: String,
+    duration: Duration,
+    result: StageResult,
+}
+
+/// Numbers the stages of an operation as they run (`[3/9]`), shows a live progress bar while
+/// each one runs, times them and prints a summary table at the end, or as far as it got when
+/// a stage fails
+pub struct StageTracker {
+    total: usize,
+    started: Instant,
+    stages: Vec<Stage>,
+}
+
+impl StageTracker {
+    /// Track an operation of `total` stages
+    #[must_use]
+    pub fn new(total: usize) -> Self {
+        Self {
+            total,
+            started: Instant::now(),
+            stages: Vec::new(),
+        }
+    }
+
+    /// Run a stage and record how long it took
+    /// If it fails, the summary so far is printed before the error is returned. After
+    /// Ctrl+C in a critical section (see `interrupt`), no further stage starts.
+    pub async fn run<T>(
+        &mut self,
+        name: &str,
+        stage: impl Future<Output = Result<T>>,
+    ) -> Result<T> {
+        if let Err(e) = super::interrupt::check() {
+            self.print_summary();
+            return Err(e);
+        }
+        print_info(&format!(
+            "{} {}",
+            format_position(self.stages.len() + 1, self.total),
+            name
+        ));
+        start_progress(self.stages.len() + 1, self.total, name);
+        let started = Instant::now();
+        let result = stage.await;
+        let duration = started.elapsed();
+        finish_progress();
+        let outcome = if result.is_ok() {
+            StageResult::Done
+        } else {
+            StageResult::Failed
+        };
+        self.stages.push(Stage {
+            name: name.to_string(),
+            duration,
+            result: outcome,
+        });
+
+        match result {
+            Ok(value) => {
+                print_success(&format!(
+                    "{} {} ({})",
+                    format_position(self.stages.len(), self.total),
+                    name,
+                    format_duration(duration)
+                ));
+                Ok(value)
+            }
+            Err(e) => {
+                print_warning(&format!(
+                    "{} {} failed after {}",
+                    format_position(self.stages.len(), self.total),
+                    name,
+                    format_duration(duration)
+                ));
+                self.print_summary();
+                Err(e)
+            }
+        }
+    }
+
+    /// Record a stage that does not need to run
+    pub fn skip(&mut self, name: &str, reason: &str) {
+        print_info(&format!(
+            "{} Skipping {} ({})",
+            format_position(self.stages.len() + 1, self.total),
+            name,
+            reason
+        ));
+        self.stages.push(Stage {
+            name: name.to_string(),
+            duration: Duration::ZERO,
+            result: StageResult::Skipped,
+        });
+    }
+
+    /// Print every recorded stage with its duration and result, and the total time
+    pub fn print_summary(&self) {
+        print_header("Stage Summary");
+        let mut table = Table::new(&["Stage", "Duration", "Result"]).right_align(&[1]);
+        for stage in &self.stages {
+            table.add_row(vec![
+                stage.name.clone(),
+                format_duration(stage.duration),
+                stage.result.label().to_string(),
+            ]);
+        }
+        table.add_row(vec![
+            "Total".to_string(),
+            format_duration(self.started.elapsed()),
+            String::new(),
+        ]);
+        table.print();
+    }
+}
+
+fn format_position(index: usize, total: usize) -> String {
+    format!("[{}/{}]", index, total)
+}
+
+/// Seconds with a decimal under a minute (`4.2s`), minutes and seconds above (`2m 5s`)
+fn format_duration(duration: Duration) -> String {
+    if duration.as_secs() < 60 {
+        format!("{:.1}s", duration.as_secs_f64())
+    } else {
+        super::time_format::format_span(duration.as_secs())
+    }
+}
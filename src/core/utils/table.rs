@@ -0,0 +1,71 @@
+// Tables for the listing commands: columns sized to their content and the terminal
+
+use comfy_table::{CellAlignment, ContentArrangement, presets};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static WIDE: AtomicBool = AtomicBool::new(false);
+static NO_TRUNCATE: AtomicBool = AtomicBool::new(false);
+
+/// Let tables grow past the terminal width instead of wrapping cells (`--wide`)
+pub fn set_wide(enabled: bool) {
+    WIDE.store(enabled, Ordering::Relaxed);
+}
+
+/// Show long IDs in full instead of shortening them (`--no-truncate`)
+pub fn set_no_truncate(enabled: bool) {
+    NO_TRUNCATE.store(enabled, Ordering::Relaxed);
+}
+
+/// A table with a header row, printed to stdout
+///
+/// Columns are as wide as their widest cell. On a terminal the table is fitted to its
+/// width by wrapping cells, unless `--wide` was passed.
+pub struct Table {
+    inner: comfy_table::Table,
+}
+
+impl Table {
+    #[must_use]
+    pub fn new(header: &[&str]) -> Self {
+        let mut inner = comfy_table::Table::new();
+        inner
+            .load_preset(presets::UTF8_FULL_CONDENSED)
+            .set_content_arrangement(if WIDE.load(Ordering::Relaxed) {
+                ContentArrangement::Disabled
+            } else {
+                ContentArrangement::Dynamic
+            })
+            .set_header(header.to_vec());
+        Self { inner }
+    }
+
+    /// Right-align the given columns (amounts and counts)
+    #[must_use]
+    pub fn right_align(mut self, columns: &[usize]) -> Self {
+        for &index in columns {
+            if let Some(column) = self.inner.column_mut(index) {
+                column.set_cell_alignment(CellAlignment::Right);
+            }
+        }
+        self
+    }
+
+    pub fn add_row(&mut self, cells: Vec<String>) {
+        self.inner.add_row(cells);
+    }
+
+    pub fn print(&self) {
+        println!("{}", self.inner);
+    }
+}
+
+/// First 7 and last 8 characters of a long ID (hex neuron IDs, account identifiers),
+/// or the whole ID with `--no-truncate`
+#[must_use]
+pub fn short_id(id: &str) -> String {
+    if id.len() >= 15 && !NO_TRUNCATE.load(Ordering::Relaxed) {
+        format!("{}...{}", &id[..7], &id[id.len() - 8..])
+    } else {
+        id.to_string()
+    }
+}
@@ -0,0 +1,76 @@
+// Human-readable timestamps: UTC datetimes and how far they are from now
+
+/// Seconds since the epoch on the local clock
+#[must_use]
+pub fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// UTC datetime of a Unix timestamp, e.g. `2026-10-18 14:03:27 UTC`
+#[must_use]
+pub fn format_utc(timestamp_seconds: u64) -> String {
+    let days = timestamp_seconds / 86_400;
+    let secs_of_day = timestamp_seconds % 86_400;
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        secs_of_day / 3_600,
+        (secs_of_day % 3_600) / 60,
+        secs_of_day % 60
+    )
+}
+
+/// A span of time in its two largest units, e.g. `3d 4h`, `12m 5s`, `2y 41d`
+#[must_use]
+pub fn format_span(seconds: u64) -> String {
+    use super::cli_args::ONE_YEAR_SECS;
+
+    let units = [
+        (ONE_YEAR_SECS, "y"),
+        (86_400, "d"),
+        (3_600, "h"),
+        (60, "m"),
+        (1, "s"),
+    ];
+    let Some(first) = units.iter().position(|(size, _)| seconds >= *size) else {
+        return "0s".to_string();
+    };
+    let (size, unit) = units[first];
+    let mut span = format!("{}{}", seconds / size, unit);
+    if let Some((next_size, next_unit)) = units.get(first + 1) {
+        let rest = (seconds % size) / next_size;
+        if rest > 0 {
+            span.push_str(&format!(" {}{}", rest, next_unit));
+        }
+    }
+    span
+}
+
+/// UTC datetime with the distance from now, e.g. `2026-10-21 18:00:00 UTC (in 3d 4h)`
+#[must_use]
+pub fn format_timestamp(timestamp_seconds: u64, now_seconds: u64) -> String {
+    let relative = if timestamp_seconds > now_seconds {
+        format!("in {}", format_span(timestamp_seconds - now_seconds))
+    } else if timestamp_seconds == now_seconds {
+        "now".to_string()
+    } else {
+        format!("{} ago", format_span(now_seconds - timestamp_seconds))
+    };
+    format!("{} ({})", format_utc(timestamp_seconds), relative)
+}
@@ -0,0 +1,238 @@
+// Defaults for the global flags from environment variables and a user config file
+//
+// Precedence: command-line flag, then environment variable, then config file
+
+use crate::core::utils::exit_code::{ValidationError, invalid};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// A setting of the config file
+pub struct Setting {
+    /// Key in the config file and in `config get/set`
+    pub key: &'static str,
+    /// The command-line flag it is the default for
+    pub flag: &'static str,
+    /// Environment variables that override the file, first set one wins
+    pub env: &'static [&'static str],
+    pub description: &'static str,
+}
+
+pub const SETTINGS: &[Setting] = &[
+    Setting {
+        key: "network",
+        flag: "--network",
+        env: &["LOCAL_SNS_NETWORK", "DFX_NETWORK"],
+        description: "dfx network to use (e.g. local)",
+    },
+    Setting {
+        key: "backend",
+        flag: "--backend",
+        env: &["LOCAL_SNS_BACKEND"],
+        description: "Replica backend: dfx or pocket-ic",
+    },
+    Setting {
+        key: "data_file",
+        flag: "--data-file",
+        env: &["LOCAL_SNS_DATA"],
+        description: "Deployment data file",
+    },
+    Setting {
+        key: "identity",
+        flag: "--identity",
+        env: &["LOCAL_SNS_IDENTITY"],
+        description: "dfx identity of the SNS owner (default: default)",
+    },
+    Setting {
+        key: "error_format",
+        flag: "--error-format",
+        env: &["LOCAL_SNS_ERROR_FORMAT"],
+        description: "How errors are printed: text or json",
+    },
+    Setting {
+        key: "yes",
+        flag: "--yes",
+        env: &["LOCAL_SNS_YES"],
+        description: "Skip confirmation prompts: true or false",
+    },
+];
+
+/// The user config file (`~/.config/local-sns/config.toml`)
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UserConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backend: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_file: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identity: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_format: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub yes: Option<bool>,
+}
+
+/// Where a default came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Env(&'static str),
+    File,
+}
+
+impl std::fmt::Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Env(var) => write!(f, "env {var}"),
+            Self::File => write!(f, "config file"),
+        }
+    }
+}
+
+impl UserConfig {
+    /// `LOCAL_SNS_CONFIG`, otherwise `config.toml` in `$XDG_CONFIG_HOME/local-sns` (default `~/.config`)
+    #[must_use]
+    pub fn path() -> PathBuf {
+        if let Some(path) = std::env::var_os("LOCAL_SNS_CONFIG").filter(|p| !p.is_empty()) {
+            return PathBuf::from(path);
+        }
+        std::env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("local-sns")
+            .join("config.toml")
+    }
+
+    /// Read the config file; a missing file is an empty config
+    pub fn load() -> Result<Self> {
+        let path = Self::path();
+        match std::fs::read_to_string(&path) {
+            Ok(content) => toml::from_str(&content).with_context(|| {
+                ValidationError::new(format!("Invalid config file {}", path.display()))
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    /// Write the config file, creating its directory
+    pub fn save(&self) -> Result<PathBuf> {
+        let path = Self::path();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let content = toml::to_string(self).context("Failed to serialize config")?;
+        std::fs::write(&path, content)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(path)
+    }
+
+    /// Value of a setting in the file
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<String> {
+        match key {
+            "network" => self.network.clone(),
+            "backend" => self.backend.clone(),
+            "data_file" => self.data_file.clone(),
+            "identity" => self.identity.clone(),
+            "error_format" => self.error_format.clone(),
+            "yes" => self.yes.map(|yes| yes.to_string()),
+            _ => None,
+        }
+    }
+
+    /// Set (or with None, remove) a setting, checking the value
+    pub fn set(&mut self, key: &str, value: Option<&str>) -> Result<()> {
+        let text = value.map(str::to_string);
+        match key {
+            "network" => self.network = text,
+            "backend" => {
+                if let Some(value) = value {
+                    value.parse::<super::backend::Backend>()?;
+                }
+                self.backend = text;
+            }
+            "data_file" => self.data_file = text,
+            "identity" => self.identity = text,
+            "error_format" => {
+                if let Some(value) = value
+                    && !matches!(value, "text" | "json")
+                {
+                    return Err(invalid(format!(
+                        "Invalid error_format '{value}': expected text or json"
+                    )));
+                }
+                self.error_format = text;
+            }
+            "yes" => self.yes = value.map(parse_bool).transpose()?,
+            _ => {
+                return Err(invalid(format!(
+                    "Unknown config key '{key}'. Keys: {}",
+                    keys()
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The config keys, comma separated
+#[must_use]
+pub fn keys() -> String {
+    SETTINGS
+        .iter()
+        .map(|s| s.key)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Parse a boolean setting (`true`/`false`, `1`/`0`, `yes`/`no`)
+pub fn parse_bool(value: &str) -> Result<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "true" | "1" | "yes" => Ok(true),
+        "false" | "0" | "no" => Ok(false),
+        _ => Err(invalid(format!(
+            "Invalid boolean '{value}': expected true or false"
+        ))),
+    }
+}
+
+static USER_CONFIG: OnceLock<UserConfig> = OnceLock::new();
+
+/// The user config file, read on first use (an unreadable file is reported once and ignored)
+fn user_config() -> &'static UserConfig {
+    USER_CONFIG.get_or_init(|| {
+        UserConfig::load().unwrap_or_else(|e| {
+            super::print_warning(&format!("Ignoring config file: {e:#}"));
+            UserConfig::default()
+        })
+    })
+}
+
+/// Default of a setting when its flag is not given: environment, then config file
+#[must_use]
+pub fn config_default(key: &str) -> Option<(String, Source)> {
+    let setting = SETTINGS.iter().find(|s| s.key == key)?;
+    setting
+        .env
+        .iter()
+        .find_map(|var| {
+            std::env::var(var)
+                .ok()
+                .filter(|value| !value.is_empty())
+                .map(|value| (value, Source::Env(var)))
+        })
+        .or_else(|| user_config().get(key).map(|value| (value, Source::File)))
+}
+
+/// Default value of a setting, without its source
+#[must_use]
+pub fn config_value(key: &str) -> Option<String> {
+    config_default(key).map(|(value, _)| value)
+}
@@ -1,8 +1,13 @@
 // SNS configuration for deployment
-// Modify this file to customize SNS parameters
+// Modify this file to customize the default SNS parameters, or pass
+// `deploy-sns --config <file>` to override them without editing Rust source
 
+use crate::core::utils::exit_code::{ValidationError, invalid};
+use anyhow::{Context, Result};
 use base64::{Engine as _, engine::general_purpose};
 use candid::Principal;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
 
 use crate::core::declarations::icp_governance::{
     Countries, CreateServiceNervousSystem, DeveloperDistribution, Duration, GovernanceParameters,
@@ -10,6 +15,7 @@ use crate::core::declarations::icp_governance::{
     NeuronDistribution, Percentage, SwapDistribution, SwapParameters, Tokens,
     VotingRewardParameters,
 };
+use crate::core::utils::cli_args::format_duration_secs;
 
 /// Name of the PNG logo file in the src directory
 /// Set this to the filename of your logo (e.g., "logo.png")
@@ -19,9 +25,51 @@ pub const LOGO_FILENAME: &str = "logo.png";
 #[allow(dead_code)]
 pub const DEFAULT_LOGO_BASE64: &str = "data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAACAAAAAgCAIAAAD8GO2jAAAFJElEQVR4nG2WT4slZxXGf8+puvd298z0ZDLp9BCTjAlGBnElCAoDLty6cCTgwm8gfoCAe79CNu7cKAiShW6yUBAkKEgEEYOQsZ1BkkxPt9N/771VdR4Xb1Xd23On4HKr3fe9589zznnOq//+8K4YnuDxw+bkuJMAbHZvVW/cnRjAlXmiyQ9uv31YRQBm6Xj/zqMHN48XDkA1zz7xkz9mVL2+hFoYKDaEimqBKT+QleXVoyfjq2RpJSohW4AE";
 
-/// Load PNG image from init directory and convert to base64 data URI
+// ============================================================================
+// NNS LIMITS
+// ============================================================================
+// Bounds the NNS enforces when it validates a CreateServiceNervousSystem proposal
+
+const DAY_SECONDS: u64 = 24 * 60 * 60;
+
+/// Length of the SNS name and token name
+pub const NAME_LENGTH: std::ops::RangeInclusive<usize> = 4..=255;
+pub const DESCRIPTION_LENGTH: std::ops::RangeInclusive<usize> = 10..=2_000;
+pub const URL_LENGTH: std::ops::RangeInclusive<usize> = 10..=512;
+pub const TOKEN_SYMBOL_LENGTH: std::ops::RangeInclusive<usize> = 3..=10;
+pub const INITIAL_VOTING_PERIOD_SECONDS: std::ops::RangeInclusive<u64> =
+    DAY_SECONDS..=30 * DAY_SECONDS;
+pub const WAIT_FOR_QUIET_DEADLINE_INCREASE_SECONDS: std::ops::RangeInclusive<u64> =
+    1..=30 * DAY_SECONDS;
+pub const SWAP_DURATION_SECONDS: std::ops::RangeInclusive<u64> = DAY_SECONDS..=90 * DAY_SECONDS;
+/// Highest voting reward rate, in basis points
+pub const MAX_REWARD_RATE_BP: u64 = 10_000;
+/// Highest dissolve delay bonus (900%), in basis points
+pub const MAX_DISSOLVE_DELAY_BONUS_BP: u64 = 90_000;
+pub const MIN_NEURON_BASKET_COUNT: u64 = 2;
+/// Most neurons the swap may create for direct participants
+pub const MAX_DIRECT_PARTICIPANT_NEURONS: u64 = 100_000;
+
+/// Load PNG image and convert to base64 data URI
+/// Uses `logo_path` from the config file if set, otherwise src/init/logo.png
 /// Returns the base64-encoded image with data URI prefix, or falls back to default if file not found
-fn load_logo_base64() -> String {
+fn load_logo_base64(logo_path: Option<&str>) -> String {
+    if let Some(path) = logo_path {
+        match std::fs::read(path) {
+            Ok(image_bytes) => {
+                let base64_encoded = general_purpose::STANDARD.encode(&image_bytes);
+                return format!("data:image/png;base64,{}", base64_encoded);
+            }
+            Err(e) => {
+                eprintln!(
+                    "Warning: Failed to read logo {}: {}. Using default logo.",
+                    path, e
+                );
+                return DEFAULT_LOGO_BASE64.to_string();
+            }
+        }
+    }
+
     // Get the project root directory (where Cargo.toml is)
     if let Ok(current_dir) = std::env::current_dir() {
         // Look for logo.png in the src/init/ directory
@@ -55,210 +103,760 @@ fn load_logo_base64() -> String {
     DEFAULT_LOGO_BASE64.to_string()
 }
 
-/// Build SNS configuration
+/// SNS deployment configuration
 ///
-/// This function constructs the `CreateServiceNervousSystem` struct with all
-/// the initial parameters for the SNS deployment. Modify the values below to
-/// customize your SNS configuration.
-pub fn build_sns_config(owner_principal: Principal) -> CreateServiceNervousSystem {
+/// Every field has a default (the values below), so a config file only needs
+/// to contain the values it wants to change. Loaded with `SnsConfig::load`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SnsConfig {
     // ============================================================================
     // BASIC SNS INFORMATION
     // ============================================================================
-    let sns_name = "AcmeDAO";
-    let sns_description = "AcmeDAO is a decentralized autonomous organization built on the Internet Computer Protocol. It enables community governance, token distribution, and collaborative decision-making through transparent voting mechanisms and smart contract automation.";
-    let sns_url = "https://acmedao.io";
-
-    // Load logo from PNG file in project root, or use default if not found
-    let logo_base64 = load_logo_base64();
+    pub name: String,
+    pub description: String,
+    pub url: String,
+    /// Path to a PNG logo (defaults to src/init/logo.png)
+    pub logo_path: Option<String>,
+    /// Fallback controllers (defaults to the deploying dfx identity)
+    pub fallback_controller_principal_ids: Vec<String>,
+    pub proposal_title: Option<String>,
+    pub proposal_summary: Option<String>,
 
-    // ============================================================================
-    // LEDGER PARAMETERS
-    // ============================================================================
-    let transaction_fee_e8s = 10_000; // 0.0001 tokens
-    let token_symbol = "ACME";
-    let token_name = "Acme Token";
+    pub token: TokenConfig,
+    pub governance: GovernanceConfig,
+    pub swap: SwapConfig,
+    pub distribution: DistributionConfig,
+    pub participants: ParticipantsConfig,
+}
 
-    // ============================================================================
-    // GOVERNANCE PARAMETERS
-    // ============================================================================
-    let neuron_maximum_dissolve_delay_bonus_bp = 10_000; // 100% (basis points)
-    let neuron_maximum_age_bonus_bp = 0; // 0% (basis points)
-    let neuron_minimum_stake_e8s = 10_000_000; // 0.1 tokens
-    let neuron_maximum_age_for_age_bonus_seconds = 4 * 365 * 24 * 60 * 60; // 4 years
-    let neuron_maximum_dissolve_delay_seconds = 8 * 365 * 24 * 60 * 60; // 8 years
-    let neuron_minimum_dissolve_delay_to_vote_seconds = 30 * 24 * 60 * 60; // 30 days
-    let proposal_initial_voting_period_seconds = 4 * 24 * 60 * 60; // 4 days
-    let proposal_wait_for_quiet_deadline_increase_seconds = 24 * 60 * 60; // 1 day
-    let proposal_rejection_fee_e8s = 11_000_000; // 0.11 tokens
+// ============================================================================
+// LEDGER PARAMETERS
+// ============================================================================
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TokenConfig {
+    pub name: String,
+    pub symbol: String,
+    pub transaction_fee_e8s: u64,
+}
 
+// ============================================================================
+// GOVERNANCE PARAMETERS
+// ============================================================================
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GovernanceConfig {
+    pub neuron_maximum_dissolve_delay_bonus_bp: u64,
+    pub neuron_maximum_age_bonus_bp: u64,
+    pub neuron_minimum_stake_e8s: u64,
+    pub neuron_maximum_age_for_age_bonus_seconds: u64,
+    pub neuron_maximum_dissolve_delay_seconds: u64,
+    pub neuron_minimum_dissolve_delay_to_vote_seconds: u64,
+    pub proposal_initial_voting_period_seconds: u64,
+    pub proposal_wait_for_quiet_deadline_increase_seconds: u64,
+    pub proposal_rejection_fee_e8s: u64,
     // Voting reward parameters
-    let initial_reward_rate_bp = 0; // 0% (basis points)
-    let final_reward_rate_bp = 0; // 0% (basis points)
-    let reward_rate_transition_duration_seconds = 0; // 0 seconds
-
-    // ============================================================================
-    // SWAP PARAMETERS
-    // ============================================================================
-    let minimum_participants = 5;
-    let neurons_fund_participation = false;
-    let minimum_direct_participation_icp_e8s = 100_000_000 * 5; // 5 ICP
-    let maximum_direct_participation_icp_e8s = 1_000_000_000 * 5; // 50 ICP
-    let minimum_participant_icp_e8s = 100_000_000; // 1 ICP
-    let maximum_participant_icp_e8s = 1_000_000_000; // 10 ICP
-    let swap_duration_seconds = 7 * 24 * 60 * 60; // 7 days
+    pub initial_reward_rate_bp: u64,
+    pub final_reward_rate_bp: u64,
+    pub reward_rate_transition_duration_seconds: u64,
+}
 
+// ============================================================================
+// SWAP PARAMETERS
+// ============================================================================
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SwapConfig {
+    pub minimum_participants: u64,
+    pub neurons_fund_participation: bool,
+    pub minimum_direct_participation_icp_e8s: u64,
+    pub maximum_direct_participation_icp_e8s: u64,
+    pub minimum_participant_icp_e8s: u64,
+    pub maximum_participant_icp_e8s: u64,
+    pub duration_seconds: u64,
     // Neuron basket construction parameters
-    let neuron_basket_count = 3;
-    let neuron_basket_dissolve_delay_interval_seconds = 30 * 24 * 60 * 60; // 30 days
+    pub neuron_basket_count: u64,
+    pub neuron_basket_dissolve_delay_interval_seconds: u64,
+    /// Restricted countries (ISO codes)
+    pub restricted_countries: Vec<String>,
+}
 
-    // Restricted countries (ISO codes)
-    let restricted_countries = vec!["AQ".to_string()]; // Antarctica (placeholder)
+// ============================================================================
+// INITIAL TOKEN DISTRIBUTION
+// ============================================================================
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DistributionConfig {
+    /// Treasury distribution (tokens held by the treasury)
+    pub treasury_e8s: u64,
+    /// Developer distribution (tokens allocated to developers)
+    pub developer_neuron_stake_e8s: u64,
+    pub developer_neuron_dissolve_delay_seconds: u64,
+    pub developer_neuron_vesting_period_seconds: u64,
+    /// More developer neurons minted at genesis, e.g. vesting neurons for other principals
+    /// to test how UIs handle them (vesting can only be set at genesis)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub extra_developer_neurons: Vec<DeveloperNeuronConfig>,
+    /// Swap distribution (tokens available in the swap)
+    pub swap_e8s: u64,
+}
 
-    // ============================================================================
-    // INITIAL TOKEN DISTRIBUTION
-    // ============================================================================
-    // Treasury distribution (tokens held by the treasury)
-    let treasury_distribution_e8s = 1_000_000_000; // 10 tokens
+/// A developer neuron besides the owner's
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DeveloperNeuronConfig {
+    /// Controller principal; defaults to the deploying identity
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub controller: Option<String>,
+    pub stake_e8s: u64,
+    #[serde(default)]
+    pub dissolve_delay_seconds: u64,
+    /// 0 for a neuron that does not vest
+    #[serde(default)]
+    pub vesting_period_seconds: u64,
+}
 
-    // Developer distribution (tokens allocated to developers)
-    let developer_neuron_stake_e8s = 100_000_000; // 1 token
-    let developer_neuron_dissolve_delay_seconds = 2 * 365 * 24 * 60 * 60; // 2 years
-    let developer_neuron_vesting_period_seconds = 4 * 365 * 24 * 60 * 60; // 4 years
+impl DeveloperNeuronConfig {
+    /// Controller of the neuron, `owner_principal` unless one is configured
+    /// An invalid controller is reported by [`SnsConfig::validate`]
+    #[must_use]
+    pub fn controller(&self, owner_principal: Principal) -> Principal {
+        self.controller
+            .as_deref()
+            .and_then(|p| Principal::from_text(p).ok())
+            .unwrap_or(owner_principal)
+    }
+}
 
-    // Swap distribution (tokens available in the swap)
-    let swap_distribution_e8s = 2_000_000_000; // 20 tokens
+// ============================================================================
+// SIMULATED SWAP PARTICIPANTS
+// ============================================================================
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ParticipantsConfig {
+    /// Number of participants created to join the swap
+    pub count: usize,
+    /// ICP each participant contributes to the swap, in order (participant 1 first)
+    /// Participants without an entry contribute `swap.maximum_participant_icp_e8s`
+    pub contributions_icp_e8s: Vec<u64>,
+}
+
+impl Default for SnsConfig {
+    fn default() -> Self {
+        Self {
+            name: "AcmeDAO".to_string(),
+            description: "AcmeDAO is a decentralized autonomous organization built on the Internet Computer Protocol. It enables community governance, token distribution, and collaborative decision-making through transparent voting mechanisms and smart contract automation.".to_string(),
+            url: "https://acmedao.io".to_string(),
+            logo_path: None,
+            fallback_controller_principal_ids: vec![],
+            proposal_title: None,
+            proposal_summary: None,
+            token: TokenConfig::default(),
+            governance: GovernanceConfig::default(),
+            swap: SwapConfig::default(),
+            distribution: DistributionConfig::default(),
+            participants: ParticipantsConfig::default(),
+        }
+    }
+}
+
+impl Default for TokenConfig {
+    fn default() -> Self {
+        Self {
+            name: "Acme Token".to_string(),
+            symbol: "ACME".to_string(),
+            transaction_fee_e8s: 10_000, // 0.0001 tokens
+        }
+    }
+}
+
+impl Default for GovernanceConfig {
+    fn default() -> Self {
+        Self {
+            neuron_maximum_dissolve_delay_bonus_bp: 10_000, // 100% (basis points)
+            neuron_maximum_age_bonus_bp: 0,                 // 0% (basis points)
+            neuron_minimum_stake_e8s: 10_000_000,           // 0.1 tokens
+            neuron_maximum_age_for_age_bonus_seconds: 4 * 365 * 24 * 60 * 60, // 4 years
+            neuron_maximum_dissolve_delay_seconds: 8 * 365 * 24 * 60 * 60, // 8 years
+            neuron_minimum_dissolve_delay_to_vote_seconds: 30 * 24 * 60 * 60, // 30 days
+            proposal_initial_voting_period_seconds: 4 * 24 * 60 * 60, // 4 days
+            proposal_wait_for_quiet_deadline_increase_seconds: 24 * 60 * 60, // 1 day
+            proposal_rejection_fee_e8s: 11_000_000,         // 0.11 tokens
+            initial_reward_rate_bp: 0,                      // 0% (basis points)
+            final_reward_rate_bp: 0,                        // 0% (basis points)
+            reward_rate_transition_duration_seconds: 0,     // 0 seconds
+        }
+    }
+}
+
+impl Default for SwapConfig {
+    fn default() -> Self {
+        Self {
+            minimum_participants: 5,
+            neurons_fund_participation: false,
+            minimum_direct_participation_icp_e8s: 100_000_000 * 5, // 5 ICP
+            maximum_direct_participation_icp_e8s: 1_000_000_000 * 5, // 50 ICP
+            minimum_participant_icp_e8s: 100_000_000,              // 1 ICP
+            maximum_participant_icp_e8s: 1_000_000_000,            // 10 ICP
+            duration_seconds: 7 * 24 * 60 * 60,                    // 7 days
+            neuron_basket_count: 3,
+            neuron_basket_dissolve_delay_interval_seconds: 30 * 24 * 60 * 60, // 30 days
+            restricted_countries: vec!["AQ".to_string()], // Antarctica (placeholder)
+        }
+    }
+}
+
+impl Default for DistributionConfig {
+    fn default() -> Self {
+        Self {
+            treasury_e8s: 1_000_000_000,             // 10 tokens
+            developer_neuron_stake_e8s: 100_000_000, // 1 token
+            developer_neuron_dissolve_delay_seconds: 2 * 365 * 24 * 60 * 60, // 2 years
+            developer_neuron_vesting_period_seconds: 4 * 365 * 24 * 60 * 60, // 4 years
+            extra_developer_neurons: vec![],
+            swap_e8s: 2_000_000_000, // 20 tokens
+        }
+    }
+}
+
+impl Default for ParticipantsConfig {
+    fn default() -> Self {
+        Self {
+            count: 5,
+            contributions_icp_e8s: vec![],
+        }
+    }
+}
+
+impl SnsConfig {
+    /// Load a config file, choosing the format from its extension (.yaml/.yml, .toml or .json)
+    /// Missing fields keep their defaults. The result is validated before it is returned.
+    pub fn load(path: &Path) -> Result<Self> {
+        let config = Self::parse(path)?;
+        config.validate().with_context(|| {
+            ValidationError::new(format!("Invalid SNS config: {}", path.display()))
+        })?;
+        Ok(config)
+    }
+
+    /// Read a config file like [`Self::load`], without validating it
+    pub fn parse(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read SNS config file: {}", path.display()))?;
+
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_lowercase)
+            .unwrap_or_default();
+
+        let config: Self = match extension.as_str() {
+            "yaml" | "yml" => serde_yaml::from_str(&content).with_context(|| {
+                ValidationError::new(format!("Invalid YAML in SNS config: {}", path.display()))
+            })?,
+            "toml" => toml::from_str(&content).with_context(|| {
+                ValidationError::new(format!("Invalid TOML in SNS config: {}", path.display()))
+            })?,
+            "json" => serde_json::from_str(&content).with_context(|| {
+                ValidationError::new(format!("Invalid JSON in SNS config: {}", path.display()))
+            })?,
+            _ => {
+                return Err(invalid(format!(
+                    "Unsupported SNS config format '{}'. Use .yaml, .yml, .toml or .json",
+                    path.display()
+                )));
+            }
+        };
+
+        Ok(config)
+    }
+
+    /// Check the configuration for values the NNS would reject or that would stall the deployment
+    /// All problems are reported together so they can be fixed in one pass
+    pub fn validate(&self) -> Result<()> {
+        let errors = self.problems();
+        if !errors.is_empty() {
+            anyhow::bail!(
+                "{} problem(s) found:\n  - {}",
+                errors.len(),
+                errors.join("\n  - ")
+            );
+        }
+        Ok(())
+    }
+
+    /// Every problem [`Self::validate`] reports, empty for a valid config
+    #[must_use]
+    pub fn problems(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        let governance = &self.governance;
+        let swap = &self.swap;
+        let distribution = &self.distribution;
+
+        // Lengths the NNS checks on the proposal
+        for (field, value, range) in [
+            ("name", &self.name, NAME_LENGTH),
+            ("description", &self.description, DESCRIPTION_LENGTH),
+            ("url", &self.url, URL_LENGTH),
+            ("token.name", &self.token.name, NAME_LENGTH),
+            ("token.symbol", &self.token.symbol, TOKEN_SYMBOL_LENGTH),
+        ] {
+            let length = value.trim().chars().count();
+            if !range.contains(&length) {
+                errors.push(format!(
+                    "{field} must be {}-{} characters (got {length})",
+                    range.start(),
+                    range.end()
+                ));
+            }
+        }
+        for principal in &self.fallback_controller_principal_ids {
+            if Principal::from_text(principal).is_err() {
+                errors.push(format!(
+                    "fallback_controller_principal_ids contains an invalid principal: '{}'",
+                    principal
+                ));
+            }
+        }
+
+        // Governance
+        if governance.neuron_minimum_stake_e8s <= self.token.transaction_fee_e8s {
+            errors.push(format!(
+                "governance.neuron_minimum_stake_e8s ({}) must be greater than token.transaction_fee_e8s ({})",
+                governance.neuron_minimum_stake_e8s, self.token.transaction_fee_e8s
+            ));
+        }
+        if governance.neuron_minimum_dissolve_delay_to_vote_seconds
+            > governance.neuron_maximum_dissolve_delay_seconds
+        {
+            errors.push(
+                "governance.neuron_minimum_dissolve_delay_to_vote_seconds must not exceed governance.neuron_maximum_dissolve_delay_seconds".to_string(),
+            );
+        }
+        if !INITIAL_VOTING_PERIOD_SECONDS
+            .contains(&governance.proposal_initial_voting_period_seconds)
+        {
+            errors.push(format!(
+                "governance.proposal_initial_voting_period_seconds must be between 1 day and 30 days (got {})",
+                format_duration_secs(governance.proposal_initial_voting_period_seconds)
+            ));
+        }
+        if !WAIT_FOR_QUIET_DEADLINE_INCREASE_SECONDS
+            .contains(&governance.proposal_wait_for_quiet_deadline_increase_seconds)
+            || governance.proposal_wait_for_quiet_deadline_increase_seconds
+                > governance.proposal_initial_voting_period_seconds / 2
+        {
+            errors.push(format!(
+                "governance.proposal_wait_for_quiet_deadline_increase_seconds must be at least 1 second and at most half of the initial voting period (got {})",
+                format_duration_secs(governance.proposal_wait_for_quiet_deadline_increase_seconds)
+            ));
+        }
+        if governance.neuron_maximum_dissolve_delay_bonus_bp > MAX_DISSOLVE_DELAY_BONUS_BP {
+            errors.push(format!(
+                "governance.neuron_maximum_dissolve_delay_bonus_bp must be at most {MAX_DISSOLVE_DELAY_BONUS_BP} (900%), got {}",
+                governance.neuron_maximum_dissolve_delay_bonus_bp
+            ));
+        }
+        if governance.initial_reward_rate_bp > MAX_REWARD_RATE_BP {
+            errors.push(format!(
+                "governance.initial_reward_rate_bp must be at most {MAX_REWARD_RATE_BP} (100%), got {}",
+                governance.initial_reward_rate_bp
+            ));
+        }
+        if governance.final_reward_rate_bp > governance.initial_reward_rate_bp {
+            errors.push(format!(
+                "governance.final_reward_rate_bp ({}) must not exceed governance.initial_reward_rate_bp ({})",
+                governance.final_reward_rate_bp, governance.initial_reward_rate_bp
+            ));
+        }
+
+        // Swap
+        if swap.minimum_participants == 0 {
+            errors.push("swap.minimum_participants must be at least 1".to_string());
+        }
+        if swap.maximum_participant_icp_e8s > swap.maximum_direct_participation_icp_e8s {
+            errors.push(
+                "swap.maximum_participant_icp_e8s must not exceed swap.maximum_direct_participation_icp_e8s"
+                    .to_string(),
+            );
+        }
+        if swap
+            .minimum_participants
+            .saturating_mul(swap.minimum_participant_icp_e8s)
+            > swap.maximum_direct_participation_icp_e8s
+        {
+            errors.push(format!(
+                "swap.maximum_direct_participation_icp_e8s ({}) must be at least swap.minimum_participants x swap.minimum_participant_icp_e8s ({}) or the swap can never reach its minimum participants",
+                swap.maximum_direct_participation_icp_e8s,
+                swap.minimum_participants
+                    .saturating_mul(swap.minimum_participant_icp_e8s)
+            ));
+        }
+        if self.swap.minimum_participant_icp_e8s > self.swap.maximum_participant_icp_e8s {
+            errors.push(
+                "swap.minimum_participant_icp_e8s must not exceed swap.maximum_participant_icp_e8s"
+                    .to_string(),
+            );
+        }
+        if self.swap.minimum_direct_participation_icp_e8s
+            > self.swap.maximum_direct_participation_icp_e8s
+        {
+            errors.push(
+                "swap.minimum_direct_participation_icp_e8s must not exceed swap.maximum_direct_participation_icp_e8s".to_string(),
+            );
+        }
+        if swap.neuron_basket_count < MIN_NEURON_BASKET_COUNT {
+            errors.push(format!(
+                "swap.neuron_basket_count must be at least {MIN_NEURON_BASKET_COUNT} (got {})",
+                swap.neuron_basket_count
+            ));
+        }
+        if swap
+            .minimum_participants
+            .saturating_mul(swap.neuron_basket_count)
+            > MAX_DIRECT_PARTICIPANT_NEURONS
+        {
+            errors.push(format!(
+                "swap.minimum_participants x swap.neuron_basket_count must not exceed {MAX_DIRECT_PARTICIPANT_NEURONS} neurons"
+            ));
+        }
+        if swap
+            .neuron_basket_dissolve_delay_interval_seconds
+            .saturating_mul(swap.neuron_basket_count.saturating_sub(1))
+            > governance.neuron_maximum_dissolve_delay_seconds
+        {
+            errors.push(
+                "the last basket neuron's dissolve delay (swap.neuron_basket_dissolve_delay_interval_seconds x (swap.neuron_basket_count - 1)) must not exceed governance.neuron_maximum_dissolve_delay_seconds".to_string(),
+            );
+        }
+        // The smallest participation must buy every basket neuron its minimum stake plus a fee
+        if swap.maximum_direct_participation_icp_e8s > 0 {
+            let minimum_participant_sns_e8s = u128::from(swap.minimum_participant_icp_e8s)
+                * u128::from(distribution.swap_e8s)
+                / u128::from(swap.maximum_direct_participation_icp_e8s);
+            let minimum_basket_e8s = u128::from(swap.neuron_basket_count)
+                * (u128::from(governance.neuron_minimum_stake_e8s)
+                    + u128::from(self.token.transaction_fee_e8s));
+            if minimum_participant_sns_e8s < minimum_basket_e8s {
+                errors.push(format!(
+                    "swap.minimum_participant_icp_e8s buys only {minimum_participant_sns_e8s} e8s of SNS tokens at the maximum participation, but a basket of {} neurons needs {minimum_basket_e8s} (neuron_minimum_stake_e8s + transaction_fee_e8s each); raise it or distribution.swap_e8s",
+                    swap.neuron_basket_count
+                ));
+            }
+        }
+        if !SWAP_DURATION_SECONDS.contains(&swap.duration_seconds) {
+            errors.push(format!(
+                "swap.duration_seconds must be between 1 day and 90 days (got {})",
+                format_duration_secs(swap.duration_seconds)
+            ));
+        }
+        let mut countries = std::collections::BTreeSet::new();
+        for code in &swap.restricted_countries {
+            if code.len() != 2 || !code.chars().all(|c| c.is_ascii_uppercase()) {
+                errors.push(format!(
+                    "swap.restricted_countries must hold two-letter uppercase ISO codes (got '{code}')"
+                ));
+            } else if !countries.insert(code) {
+                errors.push(format!("swap.restricted_countries lists '{code}' twice"));
+            }
+        }
+
+        // Token distribution
+        if distribution.swap_e8s == 0 {
+            errors.push("distribution.swap_e8s must be greater than 0".to_string());
+        }
+        if self.total_supply_e8s().is_none() {
+            errors.push(
+                "distribution.treasury_e8s + developer neuron stakes + swap_e8s overflows the token supply".to_string(),
+            );
+        }
+        if distribution.developer_neuron_stake_e8s < governance.neuron_minimum_stake_e8s {
+            errors.push(format!(
+                "distribution.developer_neuron_stake_e8s ({}) must be at least governance.neuron_minimum_stake_e8s ({})",
+                distribution.developer_neuron_stake_e8s, governance.neuron_minimum_stake_e8s
+            ));
+        }
+        if distribution.developer_neuron_dissolve_delay_seconds
+            > governance.neuron_maximum_dissolve_delay_seconds
+        {
+            errors.push(
+                "distribution.developer_neuron_dissolve_delay_seconds must not exceed governance.neuron_maximum_dissolve_delay_seconds".to_string(),
+            );
+        }
+        for (i, neuron) in distribution.extra_developer_neurons.iter().enumerate() {
+            let field = format!("distribution.extra_developer_neurons[{i}]");
+            if let Some(controller) = &neuron.controller
+                && Principal::from_text(controller).is_err()
+            {
+                errors.push(format!(
+                    "{field}.controller is not a valid principal: '{controller}'"
+                ));
+            }
+            if neuron.stake_e8s < governance.neuron_minimum_stake_e8s {
+                errors.push(format!(
+                    "{field}.stake_e8s ({}) must be at least governance.neuron_minimum_stake_e8s ({})",
+                    neuron.stake_e8s, governance.neuron_minimum_stake_e8s
+                ));
+            }
+            if neuron.dissolve_delay_seconds > governance.neuron_maximum_dissolve_delay_seconds {
+                errors.push(format!(
+                    "{field}.dissolve_delay_seconds must not exceed governance.neuron_maximum_dissolve_delay_seconds"
+                ));
+            }
+        }
+
+        // Simulated participants
+        if (self.participants.count as u64) < self.swap.minimum_participants {
+            errors.push(format!(
+                "participants.count ({}) must be at least swap.minimum_participants ({}) or the swap can never commit",
+                self.participants.count, self.swap.minimum_participants
+            ));
+        }
+        if self.participants.contributions_icp_e8s.len() > self.participants.count {
+            errors.push(format!(
+                "participants.contributions_icp_e8s has {} entries but participants.count is {}",
+                self.participants.contributions_icp_e8s.len(),
+                self.participants.count
+            ));
+        }
+        for (i, amount) in self.participants.contributions_icp_e8s.iter().enumerate() {
+            if *amount < self.swap.minimum_participant_icp_e8s
+                || *amount > self.swap.maximum_participant_icp_e8s
+            {
+                errors.push(format!(
+                    "participants.contributions_icp_e8s[{}] ({}) must be between swap.minimum_participant_icp_e8s ({}) and swap.maximum_participant_icp_e8s ({})",
+                    i,
+                    amount,
+                    self.swap.minimum_participant_icp_e8s,
+                    self.swap.maximum_participant_icp_e8s
+                ));
+            }
+        }
+        // Saturating: a sum past u64::MAX is above any minimum anyway
+        let total_contribution = (1..=self.participants.count)
+            .map(|n| self.participant_contribution_e8s(n))
+            .fold(0u64, u64::saturating_add);
+        if total_contribution < self.swap.minimum_direct_participation_icp_e8s {
+            errors.push(format!(
+                "total participant contribution ({}) cannot reach swap.minimum_direct_participation_icp_e8s ({})",
+                total_contribution, self.swap.minimum_direct_participation_icp_e8s
+            ));
+        }
+
+        errors
+    }
+
+    /// Tokens minted at genesis: treasury, developer neurons and swap; `None` on overflow
+    #[must_use]
+    pub fn total_supply_e8s(&self) -> Option<u64> {
+        self.distribution
+            .extra_developer_neurons
+            .iter()
+            .try_fold(self.distribution.treasury_e8s, |total, neuron| {
+                total.checked_add(neuron.stake_e8s)
+            })?
+            .checked_add(self.distribution.developer_neuron_stake_e8s)?
+            .checked_add(self.distribution.swap_e8s)
+    }
+
+    /// ICP contribution for a participant (1-based), defaulting to the per-participant maximum
+    #[must_use]
+    pub fn participant_contribution_e8s(&self, participant_num: usize) -> u64 {
+        participant_num
+            .checked_sub(1)
+            .and_then(|i| self.participants.contributions_icp_e8s.get(i))
+            .copied()
+            .unwrap_or(self.swap.maximum_participant_icp_e8s)
+    }
+
+    /// Resolve fallback controllers, defaulting to the deploying identity
+    fn fallback_controllers(&self, owner_principal: Principal) -> Vec<Principal> {
+        let controllers: Vec<Principal> = self
+            .fallback_controller_principal_ids
+            .iter()
+            .filter_map(|p| Principal::from_text(p).ok())
+            .collect();
+        if controllers.is_empty() {
+            vec![owner_principal]
+        } else {
+            controllers
+        }
+    }
+}
+
+/// Build SNS configuration
+///
+/// This function constructs the `CreateServiceNervousSystem` struct with all
+/// the initial parameters for the SNS deployment from an `SnsConfig`.
+#[must_use]
+pub fn build_sns_config(
+    owner_principal: Principal,
+    config: &SnsConfig,
+) -> CreateServiceNervousSystem {
+    let token = &config.token;
+    let governance = &config.governance;
+    let swap = &config.swap;
+    let distribution = &config.distribution;
+
+    // Load logo from PNG file, or use default if not found
+    let logo_base64 = load_logo_base64(config.logo_path.as_deref());
 
-    // ============================================================================
-    // BUILD CONFIGURATION
-    // ============================================================================
     CreateServiceNervousSystem {
-        name: Some(sns_name.to_string()),
-        description: Some(sns_description.to_string()),
-        url: Some(sns_url.to_string()),
+        name: Some(config.name.clone()),
+        description: Some(config.description.clone()),
+        url: Some(config.url.clone()),
         logo: Some(Image {
             base64_encoding: Some(logo_base64.to_string()),
         }),
-        fallback_controller_principal_ids: vec![owner_principal],
+        fallback_controller_principal_ids: config.fallback_controllers(owner_principal),
         dapp_canisters: vec![],
         ledger_parameters: Some(LedgerParameters {
             transaction_fee: Some(Tokens {
-                e8s: Some(transaction_fee_e8s),
+                e8s: Some(token.transaction_fee_e8s),
             }),
-            token_symbol: Some(token_symbol.to_string()),
+            token_symbol: Some(token.symbol.clone()),
             token_logo: Some(Image {
-                base64_encoding: Some(logo_base64.to_string()),
+                base64_encoding: Some(logo_base64),
             }),
-            token_name: Some(token_name.to_string()),
+            token_name: Some(token.name.clone()),
         }),
         governance_parameters: Some(GovernanceParameters {
             neuron_maximum_dissolve_delay_bonus: Some(Percentage {
-                basis_points: Some(neuron_maximum_dissolve_delay_bonus_bp),
+                basis_points: Some(governance.neuron_maximum_dissolve_delay_bonus_bp),
             }),
             neuron_maximum_age_bonus: Some(Percentage {
-                basis_points: Some(neuron_maximum_age_bonus_bp),
+                basis_points: Some(governance.neuron_maximum_age_bonus_bp),
             }),
             neuron_minimum_stake: Some(Tokens {
-                e8s: Some(neuron_minimum_stake_e8s),
+                e8s: Some(governance.neuron_minimum_stake_e8s),
             }),
             neuron_maximum_age_for_age_bonus: Some(Duration {
-                seconds: Some(neuron_maximum_age_for_age_bonus_seconds),
+                seconds: Some(governance.neuron_maximum_age_for_age_bonus_seconds),
             }),
             neuron_maximum_dissolve_delay: Some(Duration {
-                seconds: Some(neuron_maximum_dissolve_delay_seconds),
+                seconds: Some(governance.neuron_maximum_dissolve_delay_seconds),
             }),
             neuron_minimum_dissolve_delay_to_vote: Some(Duration {
-                seconds: Some(neuron_minimum_dissolve_delay_to_vote_seconds),
+                seconds: Some(governance.neuron_minimum_dissolve_delay_to_vote_seconds),
             }),
             proposal_initial_voting_period: Some(Duration {
-                seconds: Some(proposal_initial_voting_period_seconds),
+                seconds: Some(governance.proposal_initial_voting_period_seconds),
             }),
             proposal_wait_for_quiet_deadline_increase: Some(Duration {
-                seconds: Some(proposal_wait_for_quiet_deadline_increase_seconds),
+                seconds: Some(governance.proposal_wait_for_quiet_deadline_increase_seconds),
             }),
             proposal_rejection_fee: Some(Tokens {
-                e8s: Some(proposal_rejection_fee_e8s),
+                e8s: Some(governance.proposal_rejection_fee_e8s),
             }),
             voting_reward_parameters: Some(VotingRewardParameters {
                 initial_reward_rate: Some(Percentage {
-                    basis_points: Some(initial_reward_rate_bp),
+                    basis_points: Some(governance.initial_reward_rate_bp),
                 }),
                 final_reward_rate: Some(Percentage {
-                    basis_points: Some(final_reward_rate_bp),
+                    basis_points: Some(governance.final_reward_rate_bp),
                 }),
                 reward_rate_transition_duration: Some(Duration {
-                    seconds: Some(reward_rate_transition_duration_seconds),
+                    seconds: Some(governance.reward_rate_transition_duration_seconds),
                 }),
             }),
         }),
         swap_parameters: Some(SwapParameters {
-            minimum_participants: Some(minimum_participants),
-            neurons_fund_participation: Some(neurons_fund_participation),
+            minimum_participants: Some(swap.minimum_participants),
+            neurons_fund_participation: Some(swap.neurons_fund_participation),
             minimum_direct_participation_icp: Some(Tokens {
-                e8s: Some(minimum_direct_participation_icp_e8s),
+                e8s: Some(swap.minimum_direct_participation_icp_e8s),
             }),
             maximum_direct_participation_icp: Some(Tokens {
-                e8s: Some(maximum_direct_participation_icp_e8s),
+                e8s: Some(swap.maximum_direct_participation_icp_e8s),
             }),
             minimum_participant_icp: Some(Tokens {
-                e8s: Some(minimum_participant_icp_e8s),
+                e8s: Some(swap.minimum_participant_icp_e8s),
             }),
             maximum_participant_icp: Some(Tokens {
-                e8s: Some(maximum_participant_icp_e8s),
+                e8s: Some(swap.maximum_participant_icp_e8s),
             }),
             confirmation_text: None,
             minimum_icp: None,
             maximum_icp: None,
             neurons_fund_investment_icp: None,
             restricted_countries: Some(Countries {
-                iso_codes: restricted_countries,
+                iso_codes: swap.restricted_countries.clone(),
             }),
             start_time: None,
             duration: Some(Duration {
-                seconds: Some(swap_duration_seconds),
+                seconds: Some(swap.duration_seconds),
             }),
             neuron_basket_construction_parameters: Some(NeuronBasketConstructionParameters {
-                count: Some(neuron_basket_count),
+                count: Some(swap.neuron_basket_count),
                 dissolve_delay_interval: Some(Duration {
-                    seconds: Some(neuron_basket_dissolve_delay_interval_seconds),
+                    seconds: Some(swap.neuron_basket_dissolve_delay_interval_seconds),
                 }),
             }),
         }),
         initial_token_distribution: Some(InitialTokenDistribution {
             treasury_distribution: Some(SwapDistribution {
                 total: Some(Tokens {
-                    e8s: Some(treasury_distribution_e8s),
+                    e8s: Some(distribution.treasury_e8s),
                 }),
             }),
             developer_distribution: Some(DeveloperDistribution {
-                developer_neurons: vec![NeuronDistribution {
+                developer_neurons: std::iter::once(NeuronDistribution {
                     controller: Some(owner_principal),
                     dissolve_delay: Some(Duration {
-                        seconds: Some(developer_neuron_dissolve_delay_seconds),
+                        seconds: Some(distribution.developer_neuron_dissolve_delay_seconds),
                     }),
                     memo: Some(0),
                     vesting_period: Some(Duration {
-                        seconds: Some(developer_neuron_vesting_period_seconds),
+                        seconds: Some(distribution.developer_neuron_vesting_period_seconds),
                     }),
                     stake: Some(Tokens {
-                        e8s: Some(developer_neuron_stake_e8s),
+                        e8s: Some(distribution.developer_neuron_stake_e8s),
                     }),
-                }],
+                })
+                // Memos only need to be unique per controller; numbering them keeps them so
+                .chain(distribution.extra_developer_neurons.iter().zip(1..).map(
+                    |(neuron, memo)| NeuronDistribution {
+                        controller: Some(neuron.controller(owner_principal)),
+                        dissolve_delay: Some(Duration {
+                            seconds: Some(neuron.dissolve_delay_seconds),
+                        }),
+                        memo: Some(memo),
+                        vesting_period: (neuron.vesting_period_seconds > 0).then_some(Duration {
+                            seconds: Some(neuron.vesting_period_seconds),
+                        }),
+                        stake: Some(Tokens {
+                            e8s: Some(neuron.stake_e8s),
+                        }),
+                    },
+                ))
+                .collect(),
             }),
             swap_distribution: Some(SwapDistribution {
                 total: Some(Tokens {
-                    e8s: Some(swap_distribution_e8s),
+                    e8s: Some(distribution.swap_e8s),
                 }),
             }),
         }),
     }
 }
 
-/// Get default proposal title
-pub fn default_proposal_title() -> String {
-    "Deploy AcmeDAO SNS".to_string()
+/// Get proposal title (config override or default)
+#[must_use]
+pub fn proposal_title(config: &SnsConfig) -> String {
+    config
+        .proposal_title
+        .clone()
+        .unwrap_or_else(|| format!("Deploy {} SNS", config.name))
 }
 
-/// Get default proposal summary
-pub fn default_proposal_summary() -> String {
-    "This proposal creates a new Service Nervous System (SNS) for AcmeDAO with configured governance parameters, token distribution, and swap mechanics.".to_string()
+/// Get proposal summary (config override or default)
+#[must_use]
+pub fn proposal_summary(config: &SnsConfig) -> String {
+    config.proposal_summary.clone().unwrap_or_else(|| {
+        format!(
+            "This proposal creates a new Service Nervous System (SNS) for {} with configured governance parameters, token distribution, and swap mechanics.",
+            config.name
+        )
+    })
 }
@@ -0,0 +1,35 @@
+//! Deploy and interact with a Service Nervous System (SNS) on a local dfx network.
+//!
+//! The `local_sns` binary is a thin wrapper around this library, so everything the
+//! CLI does can also be driven from Rust, for example from an integration test
+//! harness. [`LocalSns`] is the high-level entry point; the lower-level canister
+//! operations live in [`core::ops`]:
+//!
+//! - [`core::ops::deployment`]: the full SNS deployment flow
+//! - [`core::ops::governance_ops`]: NNS governance (ICP neurons, proposals)
+//! - [`core::ops::sns_governance_ops`]: SNS governance (SNS neurons, proposals, votes)
+//! - [`core::ops::ledger_ops`]: ICP and SNS ledgers
+//! - [`core::ops::swap_ops`]: SNS swap
+//! - [`core::ops::canister_client`]: the agent layer these call through, with a mock for tests
+//!
+//! ```no_run
+//! use local_sns::LocalSns;
+//! use local_sns::init::sns_config::SnsConfig;
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let sns = LocalSns::new();
+//! let deployment = sns.deploy_sns(SnsConfig::default()).await?;
+//!
+//! let owner = deployment.owner_principal.parse()?;
+//! sns.mint_sns_tokens(owner, owner, 1_000_000_000).await?;
+//! let neuron_id = sns.create_sns_neuron(owner, 100_000_000, None, None).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod cli;
+mod client;
+pub mod core;
+pub mod init;
+
+pub use client::LocalSns;
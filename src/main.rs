@@ -1,94 +1,11 @@
-mod core;
-mod init;
-
-use anyhow::Result;
-
-use core::ops::commands::{
-    handle_add_hotkey, handle_check_sns_deployed, handle_create_icp_neuron,
-    handle_create_sns_neuron, handle_disburse_icp_neuron, handle_disburse_sns_neuron,
-    handle_get_icp_balance, handle_get_icp_neuron, handle_get_sns_balance,
-    handle_increase_icp_dissolve_delay, handle_increase_sns_dissolve_delay,
-    handle_list_icp_neurons, handle_list_neurons, handle_manage_icp_dissolving,
-    handle_manage_sns_dissolving, handle_mint_icp, handle_mint_sns_tokens,
-    handle_set_icp_visibility,
-};
-use core::ops::deployment::deploy_sns;
-
-// Helper to check if error is a navigation error (user went back or to main menu)
-fn is_navigation_error(err: &anyhow::Error) -> bool {
-    err.to_string().contains("User went back")
-        || err.to_string().contains("User went to main menu")
-        || err.to_string().contains("User cancelled")
-}
+use local_sns::core::utils::exit_code::report_error;
 
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() {
     // Parse command line arguments
     let args: Vec<String> = std::env::args().collect();
 
-    // Handle CLI commands
-    if args.len() > 1 {
-        let result = match args[1].as_str() {
-            "deploy-sns" => deploy_sns().await,
-            "add-hotkey" => handle_add_hotkey(&args).await,
-            "list-sns-neurons" => handle_list_neurons(&args).await,
-            "list-icp-neurons" => handle_list_icp_neurons(&args).await,
-            "mint-sns-tokens" => handle_mint_sns_tokens(&args).await,
-            "create-sns-neuron" => handle_create_sns_neuron(&args).await,
-            "disburse-sns-neuron" => handle_disburse_sns_neuron(&args).await,
-            "disburse-icp-neuron" => handle_disburse_icp_neuron(&args).await,
-            "increase-sns-dissolve-delay" => handle_increase_sns_dissolve_delay(&args).await,
-            "increase-icp-dissolve-delay" => handle_increase_icp_dissolve_delay(&args).await,
-            "manage-sns-dissolving" => handle_manage_sns_dissolving(&args).await,
-            "manage-icp-dissolving" => handle_manage_icp_dissolving(&args).await,
-            "set-icp-visibility" => handle_set_icp_visibility(&args).await,
-            "get-icp-neuron" => handle_get_icp_neuron(&args).await,
-            "get-icp-balance" => handle_get_icp_balance(&args).await,
-            "get-sns-balance" => handle_get_sns_balance(&args).await,
-            "mint-icp" => handle_mint_icp(&args).await,
-            "create-icp-neuron" => handle_create_icp_neuron(&args).await,
-            "check-sns-deployed" => handle_check_sns_deployed(&args).await,
-            _ => {
-                eprintln!("Unknown command: {}", args[1]);
-                eprintln!("\nAvailable commands:");
-                eprintln!("  deploy-sns          - Deploy a new SNS on local dfx network");
-                eprintln!("  add-hotkey          - Add a hotkey to an SNS or ICP neuron");
-                eprintln!("  list-sns-neurons    - List SNS neurons for a principal");
-                eprintln!("  list-icp-neurons    - List ICP neurons for a principal");
-                eprintln!("  mint-sns-tokens     - Create proposal to mint SNS tokens and vote");
-                eprintln!("  create-sns-neuron        - Create an SNS neuron by staking tokens");
-                eprintln!(
-                    "  disburse-sns-neuron      - Disburse an SNS neuron to a receiver principal"
-                );
-                eprintln!(
-                    "  disburse-icp-neuron      - Disburse an ICP neuron to a receiver principal"
-                );
-                eprintln!(
-                    "  increase-sns-dissolve-delay - Increase dissolve delay for an SNS neuron"
-                );
-                eprintln!(
-                    "  increase-icp-dissolve-delay - Increase dissolve delay for an ICP neuron"
-                );
-                eprintln!("  manage-sns-dissolving    - Start or stop dissolving an SNS neuron");
-                eprintln!("  manage-icp-dissolving    - Start or stop dissolving an ICP neuron");
-                eprintln!("  set-icp-visibility       - Set ICP neuron visibility");
-                eprintln!("  get-icp-neuron           - Get ICP neuron information");
-                eprintln!("  get-icp-balance          - Get ICP ledger balance for an account");
-                eprintln!("  get-sns-balance          - Get SNS ledger balance for an account");
-                eprintln!("  mint-icp                 - Mint ICP tokens from minting account");
-                eprintln!("  create-icp-neuron        - Create an ICP neuron by staking ICP");
-                return Err(anyhow::anyhow!("Unknown command"));
-            }
-        };
-
-        // If result is a navigation error, return Ok(()) to gracefully exit
-        match result {
-            Ok(()) => Ok(()),
-            Err(e) if is_navigation_error(&e) => Ok(()),
-            Err(e) => Err(e),
-        }
-    } else {
-        // Default behavior: deploy SNS if no arguments
-        deploy_sns().await
+    if let Err(e) = local_sns::cli::run(&args).await {
+        std::process::exit(report_error(&e));
     }
 }
@@ -0,0 +1,42 @@
+// Amount checks made before a transfer or stake is submitted
+
+use local_sns::core::utils::amount::Spendable;
+
+#[test]
+fn max_is_the_balance_less_the_fee() {
+    let spendable = Spendable::new(1_000_000, 10_000);
+    assert_eq!(spendable.max_e8s(), 990_000);
+    assert!(spendable.check(990_000).is_ok());
+    assert_eq!(spendable.remaining_e8s(990_000), 0);
+    assert_eq!(spendable.remaining_e8s(500_000), 490_000);
+
+    let err = spendable.check(990_001).unwrap_err().to_string();
+    assert!(err.contains("at most 990000 e8s"), "{err}");
+    assert!(spendable.check(0).is_err());
+}
+
+#[test]
+fn stakes_below_the_minimum_are_refused() {
+    let spendable = Spendable::new(1_000_000, 10_000).minimum_stake(400_000);
+    assert!(spendable.check(400_000).is_ok());
+    let err = spendable.check(399_999).unwrap_err().to_string();
+    assert!(err.contains("minimum stake of 400000 e8s"), "{err}");
+
+    let short = Spendable::new(400_000, 10_000).minimum_stake(400_000);
+    let err = short.check(390_000).unwrap_err().to_string();
+    assert!(err.starts_with("Insufficient balance"), "{err}");
+}
+
+#[test]
+fn icp_stake_max_leaves_room_for_both_fees() {
+    // create-icp-neuron sends the stake plus a fee, and the ledger charges another
+    let fee = 10_000;
+    let spendable = Spendable::new(300_000_000, fee)
+        .extra_fee(fee)
+        .minimum_stake(100_000_000);
+    let max = spendable.max_e8s();
+    assert_eq!(max + fee + fee, 300_000_000);
+    assert!(spendable.check(max).is_ok());
+    assert_eq!(spendable.remaining_e8s(max), 0);
+    assert!(spendable.check(max + 1).is_err());
+}
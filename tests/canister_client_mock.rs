@@ -0,0 +1,328 @@
+// Ops functions against the mock canister client: argument encoding, reply decoding and the
+// decisions made on the replies, without a replica
+
+use candid::{Nat, Principal};
+use local_sns::core::declarations::icp_governance::{
+    self, By, ClaimOrRefreshResponse, ManageNeuronCommandRequest, ManageNeuronRequest,
+    ManageNeuronResponse,
+};
+use local_sns::core::declarations::icp_ledger::Account;
+use local_sns::core::declarations::sns_governance::{
+    self, DissolveState, ListNeurons, ListNeuronsResponse, Neuron, NeuronId,
+};
+use local_sns::core::ops::canister_client::{CallKind, MockCanisterClient};
+use local_sns::core::ops::governance_errors::{Governance, GovernanceCommandError};
+use local_sns::core::ops::governance_ops::claim_neuron;
+use local_sns::core::ops::identity::resolve_identity_for_principal;
+use local_sns::core::ops::ledger_ops::{
+    auto_neuron_memo, generate_subaccount_by_nonce, get_icp_ledger_balance,
+};
+use local_sns::core::ops::sns_governance_ops::{
+    StakeRecovery, find_orphaned_stakes, list_neurons_for_principal, main_neuron_id,
+    match_neuron_id, recover_stake,
+};
+use local_sns::core::utils::data_output::SnsCreationData;
+use local_sns::core::utils::exit_code::{ErrorKind, classify};
+
+const LEDGER: Principal = Principal::from_slice(&[1]);
+const GOVERNANCE: Principal = Principal::from_slice(&[2]);
+const OWNER: Principal = Principal::from_slice(&[3]);
+
+fn neuron(id: u8, dissolve_state: Option<DissolveState>, stake_e8s: u64) -> Neuron {
+    Neuron {
+        id: Some(NeuronId { id: vec![id] }),
+        staked_maturity_e8s_equivalent: None,
+        permissions: Vec::new(),
+        maturity_e8s_equivalent: 0,
+        cached_neuron_stake_e8s: stake_e8s,
+        created_timestamp_seconds: 0,
+        topic_followees: None,
+        source_nns_neuron_id: None,
+        auto_stake_maturity: None,
+        aging_since_timestamp_seconds: 0,
+        dissolve_state,
+        voting_power_percentage_multiplier: 100,
+        vesting_period_seconds: None,
+        disburse_maturity_in_progress: Vec::new(),
+        followees: Vec::new(),
+        neuron_fees_e8s: 0,
+    }
+}
+
+#[tokio::test]
+async fn icp_balance_queries_the_owner_account() {
+    let mock = MockCanisterClient::new(OWNER);
+    mock.reply(LEDGER, "icrc1_balance_of", &Nat::from(250_000_000u64));
+
+    let balance = get_icp_ledger_balance(&mock, LEDGER, OWNER, None)
+        .await
+        .unwrap();
+    assert_eq!(balance, 250_000_000);
+
+    let calls = mock.calls_to("icrc1_balance_of");
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].kind, CallKind::Query);
+    let account: Account = calls[0].decode_arg().unwrap();
+    assert_eq!(account.owner, OWNER);
+    assert_eq!(account.subaccount, None);
+}
+
+#[tokio::test]
+async fn claim_neuron_sends_the_memo_and_returns_the_neuron_id() {
+    let mock = MockCanisterClient::new(OWNER);
+    mock.reply(
+        GOVERNANCE,
+        "manage_neuron",
+        &ManageNeuronResponse {
+            command: Some(icp_governance::Command1::ClaimOrRefresh(
+                ClaimOrRefreshResponse {
+                    refreshed_neuron_id: Some(icp_governance::NeuronId { id: 42 }),
+                },
+            )),
+        },
+    );
+
+    assert_eq!(claim_neuron(&mock, GOVERNANCE, 7).await.unwrap(), 42);
+
+    let calls = mock.calls_to("manage_neuron");
+    assert_eq!(calls[0].kind, CallKind::Update);
+    let request: ManageNeuronRequest = calls[0].decode_arg().unwrap();
+    let Some(ManageNeuronCommandRequest::ClaimOrRefresh(claim)) = request.command else {
+        panic!("expected a ClaimOrRefresh command");
+    };
+    assert!(matches!(claim.by, Some(By::Memo(7))));
+}
+
+#[tokio::test]
+async fn governance_errors_are_typed_and_classified() {
+    let mock = MockCanisterClient::new(OWNER);
+    mock.reply(
+        GOVERNANCE,
+        "manage_neuron",
+        &ManageNeuronResponse {
+            command: Some(icp_governance::Command1::Error(
+                icp_governance::GovernanceError {
+                    error_message: "Caller is not authorized".to_string(),
+                    error_type: 3,
+                },
+            )),
+        },
+    );
+
+    let err = claim_neuron(&mock, GOVERNANCE, 1).await.unwrap_err();
+    let governance_error = err.downcast_ref::<GovernanceCommandError>().unwrap();
+    assert_eq!(governance_error.governance, Governance::Nns);
+    assert_eq!(governance_error.error_type_name(), "NotAuthorized");
+    assert!(governance_error.hint().is_some());
+    assert_eq!(classify(&err), ErrorKind::Canister);
+}
+
+#[tokio::test]
+async fn call_without_a_reply_fails() {
+    let mock = MockCanisterClient::new(OWNER);
+
+    let err = get_icp_ledger_balance(&mock, LEDGER, OWNER, None)
+        .await
+        .unwrap_err();
+    assert!(format!("{err:#}").contains("icrc1_balance_of"));
+}
+
+#[tokio::test]
+async fn neurons_sort_by_dissolve_delay_then_stake() {
+    let mock = MockCanisterClient::new(OWNER);
+    mock.reply(
+        GOVERNANCE,
+        "list_neurons",
+        &ListNeuronsResponse {
+            neurons: vec![
+                neuron(1, Some(DissolveState::DissolveDelaySeconds(2_000)), 10),
+                neuron(2, None, 10),
+                neuron(3, Some(DissolveState::WhenDissolvedTimestampSeconds(5)), 10),
+                neuron(4, Some(DissolveState::DissolveDelaySeconds(1_000)), 10),
+                neuron(5, Some(DissolveState::DissolveDelaySeconds(1_000)), 99),
+            ],
+        },
+    );
+
+    let neurons = list_neurons_for_principal(&mock, GOVERNANCE, OWNER)
+        .await
+        .unwrap();
+    let order: Vec<u8> = neurons
+        .iter()
+        .map(|n| n.id.as_ref().unwrap().id[0])
+        .collect();
+    // Dissolving first, then by delay with the bigger stake first, no dissolve state last
+    assert_eq!(order, [3, 5, 4, 1, 2]);
+
+    // The main neuron has the longest delay, skipping ones without a dissolve state
+    assert_eq!(main_neuron_id(&neurons).unwrap().id, [1]);
+
+    let request: ListNeurons = mock.calls_to("list_neurons")[0].decode_arg().unwrap();
+    assert_eq!(request.of_principal, Some(OWNER));
+}
+
+#[tokio::test]
+async fn neurons_are_listed_across_pages() {
+    let mock = MockCanisterClient::new(OWNER);
+    let delay = || Some(DissolveState::DissolveDelaySeconds(1_000));
+    mock.reply(
+        GOVERNANCE,
+        "list_neurons",
+        &ListNeuronsResponse {
+            neurons: (1..=100).map(|id| neuron(id, delay(), 10)).collect(),
+        },
+    );
+    mock.reply(
+        GOVERNANCE,
+        "list_neurons",
+        &ListNeuronsResponse {
+            neurons: (101..=103).map(|id| neuron(id, delay(), 10)).collect(),
+        },
+    );
+
+    let neurons = list_neurons_for_principal(&mock, GOVERNANCE, OWNER)
+        .await
+        .unwrap();
+    assert_eq!(neurons.len(), 103);
+
+    // The second page starts after the last neuron of the first, and a short page ends it
+    let calls = mock.calls_to("list_neurons");
+    assert_eq!(calls.len(), 2);
+    let request: ListNeurons = calls[1].decode_arg().unwrap();
+    assert_eq!(request.start_page_at.map(|id| id.id), Some(vec![100]));
+    assert_eq!(request.of_principal, Some(OWNER));
+}
+
+#[tokio::test]
+async fn unclaimed_stakes_are_found_and_claimed() {
+    let mock = MockCanisterClient::new(OWNER);
+    let not_found = || sns_governance::GetNeuronResponse {
+        result: Some(sns_governance::Result_::Error(
+            sns_governance::GovernanceError {
+                error_message: "No neuron for given NeuronId".to_string(),
+                error_type: 3,
+            },
+        )),
+    };
+    // Memo 0 was never staked, memo 1 holds a stake whose claim failed
+    mock.reply(LEDGER, "icrc1_balance_of", &Nat::from(0u64));
+    mock.reply(LEDGER, "icrc1_balance_of", &Nat::from(500_000_000u64));
+    mock.reply(GOVERNANCE, "get_neuron", &not_found());
+    mock.reply(GOVERNANCE, "get_neuron", &not_found());
+
+    let orphans = find_orphaned_stakes(&mock, GOVERNANCE, LEDGER, &[OWNER], Some(1))
+        .await
+        .unwrap();
+    assert_eq!(orphans.len(), 1);
+    assert_eq!(orphans[0].memo, 1);
+    assert_eq!(
+        orphans[0].neuron_id,
+        generate_subaccount_by_nonce(1, OWNER).0.to_vec()
+    );
+
+    // Below the minimum stake governance would reject the claim, so none is sent
+    assert_eq!(
+        recover_stake(&mock, &orphans[0], 1_000_000_000, false)
+            .await
+            .unwrap(),
+        StakeRecovery::BelowMinimum {
+            minimum_stake_e8s: 1_000_000_000
+        }
+    );
+    assert!(mock.calls_to("manage_neuron").is_empty());
+
+    mock.reply(
+        GOVERNANCE,
+        "manage_neuron",
+        &sns_governance::ManageNeuronResponse {
+            command: Some(sns_governance::Command1::ClaimOrRefresh(
+                sns_governance::ClaimOrRefreshResponse {
+                    refreshed_neuron_id: Some(NeuronId { id: vec![9] }),
+                },
+            )),
+        },
+    );
+    assert_eq!(
+        recover_stake(&mock, &orphans[0], 100_000_000, false)
+            .await
+            .unwrap(),
+        StakeRecovery::Claimed(vec![9])
+    );
+    let request: sns_governance::ManageNeuron =
+        mock.calls_to("manage_neuron")[0].decode_arg().unwrap();
+    assert!(matches!(
+        request.command,
+        Some(sns_governance::Command::ClaimOrRefresh(_))
+    ));
+}
+
+#[test]
+fn main_neuron_falls_back_to_the_last_neuron() {
+    let neurons = [
+        neuron(1, Some(DissolveState::WhenDissolvedTimestampSeconds(5)), 10),
+        neuron(2, None, 10),
+    ];
+    assert_eq!(main_neuron_id(&neurons).unwrap().id, [2]);
+    assert!(main_neuron_id(&[]).is_none());
+}
+
+#[test]
+fn auto_memo_is_neuron_count_plus_one() {
+    assert_eq!(auto_neuron_memo(0), 1);
+    assert_eq!(auto_neuron_memo(2), 3);
+}
+
+#[test]
+fn participants_sign_with_their_seed_file() {
+    let seed = [7u8; 32];
+    let identity = ic_agent::identity::BasicIdentity::from_raw_key(&seed);
+    let principal = ic_agent::Identity::sender(&identity).unwrap();
+
+    let seed_file =
+        std::env::temp_dir().join(format!("local_sns_mock_{}.seed", std::process::id()));
+    std::fs::write(&seed_file, hex::encode(seed)).unwrap();
+
+    let data: SnsCreationData = serde_json::from_value(serde_json::json!({
+        "version": 1,
+        "icp_neuron_id": 1,
+        "proposal_id": 1,
+        "owner_principal": OWNER.to_text(),
+        "deployed_sns": {},
+        "participants": [{
+            "principal": principal.to_text(),
+            "seed_file": seed_file.to_string_lossy(),
+        }],
+    }))
+    .unwrap();
+
+    let resolved = resolve_identity_for_principal(principal, &data).unwrap();
+    std::fs::remove_file(&seed_file).unwrap();
+    assert_eq!(resolved.sender().unwrap(), principal);
+}
+
+#[test]
+fn neuron_ids_match_by_unique_prefix() {
+    let a_id =
+        hex::decode("e35f1b8a00000000000000000000000000000000000000000000000012345678").unwrap();
+    let b_id =
+        hex::decode("e35f2c0b00000000000000000000000000000000000000000000000087654321").unwrap();
+    let mut a = neuron(0, None, 10);
+    a.id = Some(NeuronId { id: a_id.clone() });
+    let mut b = neuron(0, None, 10);
+    b.id = Some(NeuronId { id: b_id });
+    let neurons = [a, b];
+
+    assert_eq!(match_neuron_id(&neurons, "e35f1b8").unwrap(), a_id);
+    assert_eq!(match_neuron_id(&neurons, "0xE35F1").unwrap(), a_id);
+    // The shortened form the tables print
+    assert_eq!(
+        match_neuron_id(&neurons, "e35f1b8...12345678").unwrap(),
+        a_id
+    );
+
+    let ambiguous = match_neuron_id(&neurons, "e35f").unwrap_err();
+    assert!(ambiguous.to_string().contains("ambiguous"));
+    assert_eq!(classify(&ambiguous), ErrorKind::Validation);
+    assert!(match_neuron_id(&neurons, "ffff").is_err());
+    assert!(match_neuron_id(&neurons, "e35").is_err());
+}
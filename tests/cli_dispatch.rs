@@ -0,0 +1,80 @@
+// Every command handler must be reachable from the CLI dispatcher and listed in its help
+
+const COMMANDS_RS: &str = include_str!("../src/core/ops/commands.rs");
+const CLI_RS: &str = include_str!("../src/cli.rs");
+
+/// Names of the public `handle_*` functions in commands.rs
+fn handlers() -> Vec<&'static str> {
+    COMMANDS_RS
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim_start();
+            let rest = line
+                .strip_prefix("pub async fn ")
+                .or_else(|| line.strip_prefix("pub fn "))?;
+            let name = &rest[..rest.find('(')?];
+            name.starts_with("handle_").then_some(name)
+        })
+        .collect()
+}
+
+/// (command, handler) pairs of the `"command" => handle_x(args)` dispatch arms in cli.rs
+fn dispatch_arms() -> Vec<(&'static str, &'static str)> {
+    CLI_RS
+        .lines()
+        .filter_map(|line| {
+            let (command, handler) = line.trim().split_once(" => ")?;
+            let command = command.strip_prefix('"')?.strip_suffix('"')?;
+            let handler = &handler[..handler.find('(')?];
+            handler.starts_with("handle_").then_some((command, handler))
+        })
+        .collect()
+}
+
+#[test]
+fn every_handler_has_a_dispatch_arm() {
+    let arms = dispatch_arms();
+    let handlers = handlers();
+    assert!(!handlers.is_empty(), "no handlers found in commands.rs");
+
+    let missing: Vec<_> = handlers
+        .iter()
+        .filter(|handler| !arms.iter().any(|(_, h)| h == *handler))
+        .collect();
+    assert!(
+        missing.is_empty(),
+        "handlers without a command in cli.rs: {missing:?}"
+    );
+}
+
+#[test]
+fn every_dispatched_command_is_in_the_help() {
+    let missing: Vec<_> = dispatch_arms()
+        .into_iter()
+        .map(|(command, _)| command)
+        .filter(|command| !CLI_RS.contains(&format!("\"  {command} ")))
+        .collect();
+    assert!(
+        missing.is_empty(),
+        "commands missing from the help output: {missing:?}"
+    );
+}
+
+#[test]
+fn icp_neuron_commands_are_dispatched() {
+    let arms = dispatch_arms();
+    for (command, handler) in [
+        ("disburse-icp-neuron", "handle_disburse_icp_neuron"),
+        ("list-icp-neurons", "handle_list_icp_neurons"),
+        (
+            "increase-icp-dissolve-delay",
+            "handle_increase_icp_dissolve_delay",
+        ),
+        ("manage-icp-dissolving", "handle_manage_icp_dissolving"),
+    ] {
+        assert!(
+            arms.contains(&(command, handler)),
+            "{command} is not dispatched to {handler}"
+        );
+    }
+}
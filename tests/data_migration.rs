@@ -0,0 +1,70 @@
+// Deployment data files written by older versions of the tool are upgraded on read
+
+use local_sns::core::utils::data_output::{DATA_VERSION, read_data_at};
+
+/// A deployment data file as written before the file carried a version
+const VERSION_0: &str = r#"{
+  "icp_neuron_id": 4242,
+  "proposal_id": 7,
+  "owner_principal": "2vxsx-fae",
+  "deployed_sns": {
+    "root_canister_id": "zxeu2-7aaaa-aaaaq-aaafa-cai",
+    "governance_canister_id": "zqfso-syaaa-aaaaq-aaafq-cai",
+    "index_canister_id": "zlaol-iaaaa-aaaaq-aaaha-cai",
+    "swap_canister_id": "zcdfx-6iaaa-aaaaq-aaagq-cai",
+    "ledger_canister_id": "zfcdd-tqaaa-aaaaq-aaaga-cai"
+  },
+  "participants": [
+    {
+      "principal": "2vxsx-fae",
+      "seed_file": "participants/participant_1.seed"
+    }
+  ]
+}"#;
+
+fn write_temp(name: &str, content: &str) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!("local_sns_{name}_{}.json", std::process::id()));
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+#[test]
+fn reads_and_upgrades_unversioned_data() {
+    let path = write_temp("data_v0", VERSION_0);
+    let result = read_data_at(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    let (data, migrated) = result.unwrap();
+    assert!(migrated);
+    assert_eq!(data.version, DATA_VERSION);
+    assert_eq!(data.icp_neuron_id, 4242);
+    assert_eq!(data.participants.len(), 1);
+    assert!(data.identities.is_empty());
+    assert_eq!(
+        data.deployed_sns.governance_canister().unwrap().to_text(),
+        "zqfso-syaaa-aaaaq-aaafq-cai"
+    );
+}
+
+#[test]
+fn current_data_is_not_migrated() {
+    let current = VERSION_0.replacen('{', &format!("{{\n  \"version\": {DATA_VERSION},"), 1);
+    let path = write_temp("data_current", &current);
+    let result = read_data_at(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    let (data, migrated) = result.unwrap();
+    assert!(!migrated);
+    assert_eq!(data.version, DATA_VERSION);
+}
+
+#[test]
+fn data_from_a_newer_version_is_rejected() {
+    let newer = VERSION_0.replacen('{', &format!("{{\n  \"version\": {},", DATA_VERSION + 1), 1);
+    let path = write_temp("data_newer", &newer);
+    let result = read_data_at(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    let err = result.unwrap_err();
+    assert!(format!("{err:#}").contains("newer local_sns"), "{err:#}");
+}
@@ -0,0 +1,67 @@
+// Exit codes of failures, decided by the typed errors in their chain
+
+use anyhow::Context;
+use local_sns::core::ops::governance_errors::{Governance, GovernanceCommandError};
+use local_sns::core::utils::cli_args::parse_token_amount;
+use local_sns::core::utils::confirm::Cancelled;
+use local_sns::core::utils::exit_code::{ErrorKind, ValidationError, classify, invalid};
+use local_sns::core::utils::interrupt::Interrupted;
+
+#[test]
+fn validation_errors_exit_with_2() {
+    let err = invalid("Unknown argument for transfer: --bogus");
+    assert_eq!(classify(&err), ErrorKind::Validation);
+    assert_eq!(classify(&err).exit_code(), 2);
+
+    let wrapped = Err::<(), _>(err)
+        .context("Failed to run transfer")
+        .unwrap_err();
+    assert_eq!(classify(&wrapped), ErrorKind::Validation);
+}
+
+#[test]
+fn validation_context_around_a_parse_error_exits_with_2() {
+    let err = "abc"
+        .parse::<u64>()
+        .context(ValidationError::new("Invalid --memo (expected a u64)"))
+        .unwrap_err();
+    assert_eq!(classify(&err), ErrorKind::Validation);
+    assert_eq!(err.to_string(), "Invalid --memo (expected a u64)");
+}
+
+#[test]
+fn bad_amounts_exit_with_2() {
+    for value in ["abc", "1.2.3", "0.000000001"] {
+        let err = parse_token_amount(value, 8).unwrap_err();
+        assert_eq!(classify(&err), ErrorKind::Validation, "{value}: {err:#}");
+    }
+}
+
+#[test]
+fn wording_alone_does_not_make_a_validation_error() {
+    let err = anyhow::anyhow!("Invalid response from the ledger. Usage: unknown argument");
+    assert_eq!(classify(&err), ErrorKind::Other);
+    assert_eq!(classify(&err).exit_code(), 1);
+}
+
+#[test]
+fn cancellation_and_interruption_have_their_own_codes() {
+    let cancelled = anyhow::Error::new(Cancelled).context("Failed to transfer");
+    assert_eq!(classify(&cancelled).exit_code(), 5);
+
+    let interrupted = anyhow::Error::new(Interrupted);
+    assert_eq!(classify(&interrupted).exit_code(), 130);
+}
+
+#[test]
+fn governance_errors_win_over_a_validation_context() {
+    let governance = GovernanceCommandError {
+        governance: Governance::Sns,
+        action: "Failed to disburse neuron".to_string(),
+        error_type: 3,
+        message: "Caller is not authorized".to_string(),
+    };
+    let err = anyhow::Error::new(governance).context(ValidationError::new("Invalid neuron"));
+    assert_eq!(classify(&err), ErrorKind::Canister);
+    assert_eq!(classify(&err).exit_code(), 3);
+}
@@ -0,0 +1,22 @@
+// The key registry's file format and per-principal bookkeeping, without touching the real file
+
+use local_sns::core::utils::key_registry::KeyRegistry;
+
+#[test]
+fn one_key_file_per_principal_and_round_trips_through_toml() {
+    let mut registry = KeyRegistry::default();
+    registry.insert("aaaaa-aa".to_string(), "/keys/old.pem".to_string());
+    registry.insert("2vxsx-fae".to_string(), "/keys/anon.seed".to_string());
+    registry.insert("aaaaa-aa".to_string(), "/keys/new.pem".to_string());
+    assert_eq!(registry.keys.len(), 2);
+    assert_eq!(registry.key_file_for("aaaaa-aa"), Some("/keys/new.pem"));
+
+    let text = toml::to_string(&registry).unwrap();
+    assert!(text.contains("[[key]]"));
+    let mut parsed: KeyRegistry = toml::from_str(&text).unwrap();
+    assert_eq!(parsed.keys, registry.keys);
+
+    assert!(parsed.remove("2vxsx-fae"));
+    assert!(!parsed.remove("2vxsx-fae"));
+    assert_eq!(parsed.key_file_for("2vxsx-fae"), None);
+}
@@ -0,0 +1,94 @@
+// Decoding logged canister payloads with the called method's declared types
+
+use candid::{Encode, IDLArgs, Principal};
+use local_sns::core::declarations::{icp_governance, sns_governance};
+use local_sns::core::ops::method_types::{Part, decode};
+use local_sns::core::utils::network::network_config;
+
+fn sns_canister() -> Principal {
+    Principal::from_text("zxeu2-7aaaa-aaaaq-aaafa-cai").unwrap()
+}
+
+#[test]
+fn sns_requests_decode_with_field_names() {
+    let request = sns_governance::ListNeurons {
+        of_principal: Some(Principal::anonymous()),
+        limit: 25,
+        start_page_at: None,
+    };
+    let bytes = Encode!(&request).unwrap();
+
+    let text = decode(&sns_canister(), "list_neurons", Part::Request, &bytes)
+        .unwrap()
+        .to_string();
+    assert!(text.contains("of_principal"), "{text}");
+    assert!(text.contains("limit = 25"), "{text}");
+
+    // Untyped, the same bytes only carry label hashes
+    let untyped = IDLArgs::from_bytes(&bytes).unwrap().to_string();
+    assert!(!untyped.contains("of_principal"), "{untyped}");
+}
+
+#[test]
+fn nns_governance_uses_its_own_declarations() {
+    let reply = icp_governance::ListNeuronsResponse {
+        neuron_infos: vec![],
+        full_neurons: vec![],
+        total_pages_available: Some(1),
+    };
+    let bytes = Encode!(&reply).unwrap();
+
+    let text = decode(
+        &network_config().governance_canister,
+        "list_neurons",
+        Part::Reply,
+        &bytes,
+    )
+    .unwrap()
+    .to_string();
+    assert!(text.contains("full_neurons"), "{text}");
+}
+
+#[test]
+fn fields_missing_from_the_declarations_are_still_shown() {
+    #[derive(candid::CandidType)]
+    struct NewerListNeurons {
+        of_principal: Option<Principal>,
+        limit: u32,
+        include_empty_neurons: Option<bool>,
+    }
+    let bytes = Encode!(&NewerListNeurons {
+        of_principal: None,
+        limit: 10,
+        include_empty_neurons: Some(true),
+    })
+    .unwrap();
+
+    let args = decode(&sns_canister(), "list_neurons", Part::Request, &bytes).unwrap();
+    let text = args.to_string();
+    assert!(text.contains("limit = 10"), "{text}");
+    // Undeclared, so shown by its hash, but not dropped
+    assert!(text.contains("opt true"), "{text}");
+}
+
+#[test]
+fn unknown_methods_and_mismatched_bytes_decode_untyped() {
+    let request = sns_governance::ListNeurons {
+        of_principal: None,
+        limit: 5,
+        start_page_at: None,
+    };
+    let bytes = Encode!(&request).unwrap();
+    let text = decode(&sns_canister(), "no_such_method", Part::Request, &bytes)
+        .unwrap()
+        .to_string();
+    assert!(!text.contains("limit"), "{text}");
+
+    let number = Encode!(&42u64).unwrap();
+    let text = decode(&sns_canister(), "list_neurons", Part::Request, &number)
+        .unwrap()
+        .to_string();
+    assert!(text.contains("42"), "{text}");
+
+    assert!(decode(&sns_canister(), "list_neurons", Part::Request, b"garbage").is_err());
+}
@@ -0,0 +1,46 @@
+// Seed phrase import: BIP-39 validation and the key derived along the ICP path
+
+use ic_agent::Identity;
+use ic_agent::identity::Secp256k1Identity;
+use local_sns::core::ops::identity::secret_key_from_mnemonic;
+
+/// The all-zero-entropy phrase of the BIP-39 test vectors
+const ABANDON_ABOUT: &str =
+    "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+fn principal_of(phrase: &str) -> String {
+    let key = secret_key_from_mnemonic(phrase).expect("valid phrase");
+    Secp256k1Identity::from_private_key(key)
+        .sender()
+        .expect("principal")
+        .to_text()
+}
+
+#[test]
+fn the_test_vector_phrase_derives_the_first_icp_account() {
+    // m/44'/223'/0'/0/0 of the standard phrase, whose BIP-39 seed is 5eb00bbd...e38e4
+    assert_eq!(
+        principal_of(ABANDON_ABOUT),
+        "tgzar-4lpln-fq34h-6hxo4-wlm3x-6g3or-6hxvr-d6jbw-ooh2b-lzsw4-aqe"
+    );
+}
+
+#[test]
+fn case_and_spacing_do_not_change_the_key() {
+    let messy = format!("  {}  ", ABANDON_ABOUT.to_uppercase().replace(' ', "   "));
+    assert_eq!(principal_of(&messy), principal_of(ABANDON_ABOUT));
+}
+
+#[test]
+fn a_bad_checksum_is_refused() {
+    let phrase = ["abandon"; 12].join(" ");
+    let err = secret_key_from_mnemonic(&phrase).unwrap_err().to_string();
+    assert!(err.contains("Invalid seed phrase"), "{err}");
+}
+
+#[test]
+fn words_outside_the_wordlist_are_refused() {
+    let phrase = ABANDON_ABOUT.replace("about", "aboutt");
+    assert!(secret_key_from_mnemonic(&phrase).is_err());
+    assert!(secret_key_from_mnemonic("abandon about").is_err());
+}
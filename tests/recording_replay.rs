@@ -0,0 +1,49 @@
+// Replaying a recording answers canister calls without a replica. The replay is process-wide,
+// so this file holds a single test.
+
+use candid::{Nat, Principal};
+use local_sns::core::ops::canister_client::MockCanisterClient;
+use local_sns::core::ops::ledger_ops::get_icp_ledger_balance;
+use local_sns::core::ops::recording::{RecordedInteraction, is_replaying, start_replay};
+
+const LEDGER: Principal = Principal::from_slice(&[1]);
+const OWNER: Principal = Principal::from_slice(&[3]);
+
+fn balance_reply(e8s: u64) -> RecordedInteraction {
+    RecordedInteraction {
+        kind: "query".to_string(),
+        canister: LEDGER.to_text(),
+        method: "icrc1_balance_of".to_string(),
+        arg: String::new(),
+        reply: Some(hex::encode(candid::encode_one(Nat::from(e8s)).unwrap())),
+        error: None,
+    }
+}
+
+#[tokio::test]
+async fn replay_serves_recorded_replies_in_order() {
+    let path = std::env::temp_dir().join(format!("local_sns_replay_{}.jsonl", std::process::id()));
+    let lines: Vec<String> = [balance_reply(100), balance_reply(200)]
+        .iter()
+        .map(|interaction| serde_json::to_string(interaction).unwrap())
+        .collect();
+    std::fs::write(&path, lines.join("\n")).unwrap();
+
+    start_replay(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(is_replaying());
+
+    // The mock has no replies, so every answer comes from the recording
+    let mock = MockCanisterClient::new(OWNER);
+    let mut balances = Vec::new();
+    for _ in 0..3 {
+        balances.push(
+            get_icp_ledger_balance(&mock, LEDGER, OWNER, None)
+                .await
+                .unwrap(),
+        );
+    }
+    // The last reply repeats once the recording runs out, as for a polling loop
+    assert_eq!(balances, [100, 200, 200]);
+    assert!(mock.calls().is_empty());
+}
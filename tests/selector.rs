@@ -0,0 +1,77 @@
+// The principal picker's input parsing and fuzzy filtering, without a terminal
+
+use candid::Principal;
+use local_sns::core::utils::selector::{Choice, Selector, SelectorInput, fuzzy_score};
+
+fn choice(id: u8, keys: &[&str]) -> Choice {
+    let mut choice = Choice::new(Principal::from_slice(&[id; 29]));
+    choice.keys = keys.iter().map(ToString::to_string).collect();
+    choice
+}
+
+fn selector() -> Selector {
+    Selector::new(vec![
+        choice(1, &["participant-1", "alice"]),
+        choice(2, &["participant-2"]),
+        choice(3, &["bob"]),
+        choice(4, &["owner", "treasury"]),
+    ])
+}
+
+#[test]
+fn numbers_pick_choices_then_custom_then_main_menu() {
+    assert_eq!(
+        SelectorInput::parse("1", 4).unwrap(),
+        SelectorInput::Pick(0)
+    );
+    assert_eq!(
+        SelectorInput::parse(" 4 ", 4).unwrap(),
+        SelectorInput::Pick(3)
+    );
+    assert_eq!(SelectorInput::parse("5", 4).unwrap(), SelectorInput::Custom);
+    assert_eq!(
+        SelectorInput::parse("6", 4).unwrap(),
+        SelectorInput::MainMenu
+    );
+    assert!(SelectorInput::parse("0", 4).is_err());
+    assert!(SelectorInput::parse("7", 4).is_err());
+}
+
+#[test]
+fn back_and_cancel_inputs_are_recognised() {
+    assert_eq!(
+        SelectorInput::parse("", 4).unwrap(),
+        SelectorInput::MainMenu
+    );
+    assert_eq!(SelectorInput::parse("b", 4).unwrap(), SelectorInput::Back);
+    assert_eq!(
+        SelectorInput::parse("BACK", 4).unwrap(),
+        SelectorInput::Back
+    );
+    assert_eq!(
+        SelectorInput::parse("ali", 4).unwrap(),
+        SelectorInput::Text("ali".to_string())
+    );
+}
+
+#[test]
+fn fuzzy_score_ranks_prefix_then_substring_then_subsequence() {
+    assert_eq!(fuzzy_score("Ali", "alice"), Some(0));
+    assert_eq!(fuzzy_score("lic", "alice"), Some(1));
+    assert_eq!(fuzzy_score("p3", "participant-3"), Some(2));
+    assert_eq!(fuzzy_score("3p", "participant-3"), None);
+    assert_eq!(fuzzy_score("", "alice"), None);
+}
+
+#[test]
+fn filter_matches_keys_and_principals_best_first() {
+    let selector = selector();
+    assert_eq!(selector.filter("bob"), vec![2]);
+    assert_eq!(selector.filter("participant"), vec![0, 1]);
+    assert_eq!(selector.filter("tre"), vec![3]);
+    assert_eq!(selector.filter("pt2"), vec![1]);
+    assert!(selector.filter("zzz").is_empty());
+
+    let principal = selector.choices()[2].principal.to_text();
+    assert_eq!(selector.filter(&principal[..5]), vec![2]);
+}